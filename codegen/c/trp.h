@@ -0,0 +1,290 @@
+/* Generated by cargo xtask gen --lang c */
+#ifndef TRP_H
+#define TRP_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Strings are NUL-terminated UTF-8 owned by the struct. Optional values are
+ * NULL pointers, or come with a `has_` flag when stored inline. Lists are a
+ * pointer and a `_len` count, maps parallel `_keys` and `_values` arrays.
+ * Fields without a fixed layout hold raw JSON text in a `_json` member.
+ */
+
+/* Returned by the `_from_json` functions. */
+#define TRP_OK 0
+#define TRP_ERR_PARSE -1
+#define TRP_ERR_INVALID -2
+
+typedef struct trp_bytes_envelope trp_bytes_envelope;
+typedef struct trp_check_status_params trp_check_status_params;
+typedef struct trp_check_status_response trp_check_status_response;
+typedef struct trp_input_query_diagnostic trp_input_query_diagnostic;
+typedef struct trp_search_space_diagnostic trp_search_space_diagnostic;
+typedef struct trp_input_not_resolved_diagnostic trp_input_not_resolved_diagnostic;
+typedef struct trp_missing_tx_arg_diagnostic trp_missing_tx_arg_diagnostic;
+typedef struct trp_tir_info trp_tir_info;
+typedef struct trp_resolve_params trp_resolve_params;
+typedef struct trp_submit_params trp_submit_params;
+typedef struct trp_submit_response trp_submit_response;
+typedef struct trp_submit_witness trp_submit_witness;
+typedef struct trp_tx_envelope trp_tx_envelope;
+typedef struct trp_tx_script_failure_diagnostic trp_tx_script_failure_diagnostic;
+typedef struct trp_tx_status trp_tx_status;
+typedef struct trp_unsupported_tir_diagnostic trp_unsupported_tir_diagnostic;
+
+/* BytesEnvelope */
+struct trp_bytes_envelope {
+    char *encoding;
+    char *payload;
+};
+
+/* CheckStatusParams */
+struct trp_check_status_params {
+    char **hashes;
+    size_t hashes_len;
+};
+
+/* CheckStatusResponse */
+struct trp_check_status_response {
+    char **statuses_keys;
+    trp_tx_status *statuses_values;
+    size_t statuses_len;
+};
+
+/* InputQueryDiagnostic */
+struct trp_input_query_diagnostic {
+    char *address;
+    bool collateral;
+    char **min_amount_keys;
+    char **min_amount_values;
+    size_t min_amount_len;
+    char **refs;
+    size_t refs_len;
+    bool support_many;
+};
+
+/* SearchSpaceDiagnostic */
+struct trp_search_space_diagnostic {
+    bool has_by_address_count;
+    int64_t by_address_count;
+    bool has_by_asset_class_count;
+    int64_t by_asset_class_count;
+    bool has_by_ref_count;
+    int64_t by_ref_count;
+    char **matched;
+    size_t matched_len;
+};
+
+/* InputNotResolvedDiagnostic */
+struct trp_input_not_resolved_diagnostic {
+    char *name;
+    trp_input_query_diagnostic query;
+    trp_search_space_diagnostic search_space;
+};
+
+/* MissingTxArgDiagnostic */
+struct trp_missing_tx_arg_diagnostic {
+    char *key;
+    char *type;
+};
+
+/* TirInfo */
+struct trp_tir_info {
+    char *bytecode;
+    char *encoding;
+    char *version;
+};
+
+/* ResolveParams */
+struct trp_resolve_params {
+    char *args_json; /* raw JSON */
+    char *env_json; /* raw JSON */
+    trp_tir_info tir;
+};
+
+/* SubmitParams */
+struct trp_submit_params {
+    trp_bytes_envelope tx;
+    trp_submit_witness *witnesses;
+    size_t witnesses_len;
+};
+
+/* SubmitResponse */
+struct trp_submit_response {
+    char *hash;
+};
+
+/* SubmitWitness */
+struct trp_submit_witness {
+    trp_bytes_envelope key;
+    trp_bytes_envelope signature;
+    char *type;
+};
+
+/* TxEnvelope */
+struct trp_tx_envelope {
+    char *hash;
+    char *tx;
+};
+
+/* TxScriptFailureDiagnostic */
+struct trp_tx_script_failure_diagnostic {
+    char **logs;
+    size_t logs_len;
+};
+
+/* TxStatus */
+struct trp_tx_status {
+    int64_t confirmations;
+    char *stage;
+};
+
+/* UnsupportedTirDiagnostic */
+struct trp_unsupported_tir_diagnostic {
+    char *expected;
+    char *provided;
+};
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_bytes_envelope_free. */
+int trp_bytes_envelope_from_json(const char *json, size_t len, trp_bytes_envelope *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_bytes_envelope_to_json(const trp_bytes_envelope *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_bytes_envelope_free(trp_bytes_envelope *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_check_status_params_free. */
+int trp_check_status_params_from_json(const char *json, size_t len, trp_check_status_params *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_check_status_params_to_json(const trp_check_status_params *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_check_status_params_free(trp_check_status_params *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_check_status_response_free. */
+int trp_check_status_response_from_json(const char *json, size_t len, trp_check_status_response *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_check_status_response_to_json(const trp_check_status_response *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_check_status_response_free(trp_check_status_response *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_input_query_diagnostic_free. */
+int trp_input_query_diagnostic_from_json(const char *json, size_t len, trp_input_query_diagnostic *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_input_query_diagnostic_to_json(const trp_input_query_diagnostic *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_input_query_diagnostic_free(trp_input_query_diagnostic *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_search_space_diagnostic_free. */
+int trp_search_space_diagnostic_from_json(const char *json, size_t len, trp_search_space_diagnostic *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_search_space_diagnostic_to_json(const trp_search_space_diagnostic *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_search_space_diagnostic_free(trp_search_space_diagnostic *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_input_not_resolved_diagnostic_free. */
+int trp_input_not_resolved_diagnostic_from_json(const char *json, size_t len, trp_input_not_resolved_diagnostic *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_input_not_resolved_diagnostic_to_json(const trp_input_not_resolved_diagnostic *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_input_not_resolved_diagnostic_free(trp_input_not_resolved_diagnostic *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_missing_tx_arg_diagnostic_free. */
+int trp_missing_tx_arg_diagnostic_from_json(const char *json, size_t len, trp_missing_tx_arg_diagnostic *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_missing_tx_arg_diagnostic_to_json(const trp_missing_tx_arg_diagnostic *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_missing_tx_arg_diagnostic_free(trp_missing_tx_arg_diagnostic *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_tir_info_free. */
+int trp_tir_info_from_json(const char *json, size_t len, trp_tir_info *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_tir_info_to_json(const trp_tir_info *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_tir_info_free(trp_tir_info *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_resolve_params_free. */
+int trp_resolve_params_from_json(const char *json, size_t len, trp_resolve_params *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_resolve_params_to_json(const trp_resolve_params *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_resolve_params_free(trp_resolve_params *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_submit_params_free. */
+int trp_submit_params_from_json(const char *json, size_t len, trp_submit_params *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_submit_params_to_json(const trp_submit_params *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_submit_params_free(trp_submit_params *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_submit_response_free. */
+int trp_submit_response_from_json(const char *json, size_t len, trp_submit_response *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_submit_response_to_json(const trp_submit_response *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_submit_response_free(trp_submit_response *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_submit_witness_free. */
+int trp_submit_witness_from_json(const char *json, size_t len, trp_submit_witness *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_submit_witness_to_json(const trp_submit_witness *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_submit_witness_free(trp_submit_witness *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_tx_envelope_free. */
+int trp_tx_envelope_from_json(const char *json, size_t len, trp_tx_envelope *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_tx_envelope_to_json(const trp_tx_envelope *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_tx_envelope_free(trp_tx_envelope *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_tx_script_failure_diagnostic_free. */
+int trp_tx_script_failure_diagnostic_from_json(const char *json, size_t len, trp_tx_script_failure_diagnostic *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_tx_script_failure_diagnostic_to_json(const trp_tx_script_failure_diagnostic *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_tx_script_failure_diagnostic_free(trp_tx_script_failure_diagnostic *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_tx_status_free. */
+int trp_tx_status_from_json(const char *json, size_t len, trp_tx_status *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_tx_status_to_json(const trp_tx_status *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_tx_status_free(trp_tx_status *value);
+
+/* Parses `len` bytes of JSON into `out`, which is owned by the caller and
+ * released with trp_unsupported_tir_diagnostic_free. */
+int trp_unsupported_tir_diagnostic_from_json(const char *json, size_t len, trp_unsupported_tir_diagnostic *out);
+/* Encodes `value` as JSON, released with trp_string_free. NULL on failure. */
+char *trp_unsupported_tir_diagnostic_to_json(const trp_unsupported_tir_diagnostic *value);
+/* Frees the memory owned by `value`, but not `value` itself. */
+void trp_unsupported_tir_diagnostic_free(trp_unsupported_tir_diagnostic *value);
+
+/* Frees a string returned by a `_to_json` function. */
+void trp_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* TRP_H */
@@ -0,0 +1,292 @@
+// Generated by cargo xtask gen --lang deno
+import * as constants from "./constants.ts";
+import type * as types from "./types.ts";
+
+/** Options of a single call. */
+export interface CallOptions {
+    /** Aborts the call when signalled. */
+    signal?: AbortSignal;
+    /** Milliseconds after which the call is aborted. */
+    timeout?: number;
+    /**
+     * Idempotency key of the call, sent in the `idempotency-key` header. Calls
+     * of the methods the spec marks with `x-idempotency-key` get a fresh one
+     * unless given: retrying a call with the key it was first made with lets
+     * the server answer its first result rather than run it twice.
+     */
+    idempotencyKey?: string;
+}
+
+/** A fresh idempotency key. */
+export function idempotencyKey(): string {
+    return globalThis.crypto.randomUUID();
+}
+
+/**
+ * Carries JSON-RPC calls to a TRP server and resolves with their result.
+ * Implement it to run the client over something else than `fetch`.
+ */
+export interface Transport {
+    request(method: string, params: unknown, options?: CallOptions): Promise<unknown>;
+}
+
+/** Error object returned by the server instead of a result. */
+export class JsonRpcError extends Error {
+    readonly code: number;
+    readonly data?: unknown;
+
+    constructor(code: number, message: string, data?: unknown) {
+        super(message);
+        this.name = "JsonRpcError";
+        this.code = code;
+        this.data = data;
+    }
+}
+
+/** The server implements a spec version incompatible with the client's. */
+export class VersionMismatchError extends Error {
+    readonly clientVersion: string;
+    readonly serverVersion: string;
+
+    constructor(clientVersion: string, serverVersion: string) {
+        super(`server implements spec version ${serverVersion}, incompatible with the client's ${clientVersion}`);
+        this.name = "VersionMismatchError";
+        this.clientVersion = clientVersion;
+        this.serverVersion = serverVersion;
+    }
+}
+
+/** Spec version of the client, checked against the server's. */
+const CLIENT_VERSION: string | undefined = constants.SPEC_VERSION;
+
+/** Code of the error answering methods a server doesn't implement. */
+const METHOD_NOT_FOUND = -32601;
+
+/**
+ * The components of `version` up to its first non-zero one. Versions
+ * agreeing on them are compatible, as with semver caret ranges: `1.2.0` and
+ * `1.5.1` are, `0.1.0` and `0.2.0` aren't.
+ */
+function significant(version: string): string {
+    const components: string[] = [];
+    for (const component of version.split(/[.+-]/).slice(0, 3)) {
+        components.push(component);
+        if (component !== "0") {
+            break;
+        }
+    }
+    return components.join(".");
+}
+
+export interface ClientOptions {
+    /**
+     * Calls the server whatever spec version it implements. Otherwise the
+     * first call checks it, as told by `rpc.discover`.
+     */
+    skipVersionCheck?: boolean;
+}
+
+export interface HttpTransportOptions {
+    /** `fetch` implementation, for runtimes without a global one or for tests. */
+    fetch?: typeof fetch;
+    /** Headers sent with every call, e.g. for authentication. */
+    headers?: Record<string, string>;
+    /** Timeout of every call in milliseconds, unless the call sets its own. */
+    timeout?: number;
+}
+
+/** What a call ended with, as seen by middleware. */
+export type CallOutcome = { ok: true; result: unknown } | { ok: false; error: unknown };
+
+/**
+ * Hooks run around every call of a client, for logging, auth refresh or
+ * metrics. `onRequest` may return replacement params.
+ */
+export interface Middleware {
+    onRequest?(method: string, params: unknown): unknown | Promise<unknown>;
+    /** `latency` is in milliseconds. */
+    onResponse?(method: string, outcome: CallOutcome, latency: number): void | Promise<void>;
+}
+
+/** Posts JSON-RPC 2.0 requests to an HTTP endpoint. */
+export class HttpTransport implements Transport {
+    private readonly endpoint: string;
+    private readonly options: HttpTransportOptions;
+    private nextId = 0;
+
+    constructor(endpoint: string, options: HttpTransportOptions = {}) {
+        this.endpoint = endpoint;
+        this.options = options;
+    }
+
+    async request(method: string, params: unknown, options: CallOptions = {}): Promise<unknown> {
+        const fetchImpl = this.options.fetch ?? globalThis.fetch;
+        if (!fetchImpl) {
+            throw new Error("no global fetch, pass one in HttpTransportOptions");
+        }
+
+        const controller = new AbortController();
+        const { signal } = options;
+        const abort = () => controller.abort(signal?.reason);
+        if (signal?.aborted) {
+            abort();
+        } else {
+            signal?.addEventListener("abort", abort, { once: true });
+        }
+        const timeout = options.timeout ?? this.options.timeout;
+        const timer = timeout === undefined
+            ? undefined
+            : setTimeout(() => controller.abort(new Error(`${method} timed out after ${timeout}ms`)), timeout);
+
+        try {
+            const response = await fetchImpl(this.endpoint, {
+                method: "POST",
+                headers: {
+                    "content-type": "application/json",
+                    ...this.options.headers,
+                    ...(options.idempotencyKey === undefined
+                        ? {}
+                        : { [constants.IDEMPOTENCY_HEADER]: options.idempotencyKey }),
+                },
+                body: JSON.stringify({ jsonrpc: "2.0", id: ++this.nextId, method, params }),
+                signal: controller.signal,
+            });
+            const body = await response.json().catch(() => undefined);
+            if (body?.error) {
+                throw new JsonRpcError(body.error.code, body.error.message, body.error.data);
+            }
+            if (!response.ok || body === undefined) {
+                throw new Error(`${method} failed with HTTP status ${response.status}`);
+            }
+            return body.result;
+        } finally {
+            clearTimeout(timer);
+            signal?.removeEventListener("abort", abort);
+        }
+    }
+}
+
+/**
+ * Typed calls to the methods of the spec, which `TrpClient` makes over a
+ * transport. Code depending on it rather than on `TrpClient` can be handed a
+ * `MockTrpClient` in tests.
+ */
+export interface TrpApi {
+    trpResolve(params: types.ResolveParams, options?: CallOptions): Promise<types.TxEnvelope>;
+    trpSubmit(params: types.SubmitParams, options?: CallOptions): Promise<types.SubmitResponse>;
+    trpCheckStatus(params: types.CheckStatusParams, options?: CallOptions): Promise<types.CheckStatusResponse>;
+}
+
+/** Typed calls to the methods of the spec. */
+export class TrpClient implements TrpApi {
+    readonly transport: Transport;
+    private readonly middleware: Middleware[];
+    private readonly options: ClientOptions;
+    private serverVersion?: Promise<string | undefined>;
+
+    constructor(transport: Transport, middleware: Middleware[] = [], options: ClientOptions = {}) {
+        this.transport = transport;
+        this.middleware = [...middleware];
+        this.options = options;
+    }
+
+    /** A client posting to `endpoint` over `fetch`. */
+    static http(endpoint: string, options?: HttpTransportOptions & ClientOptions): TrpClient {
+        return new TrpClient(new HttpTransport(endpoint, options), [], options);
+    }
+
+    /** Runs `middleware` around every call, after the ones added before it. */
+    use(middleware: Middleware): this {
+        this.middleware.push(middleware);
+        return this;
+    }
+
+    /**
+     * Checks that the server implements a spec version compatible with the
+     * client's, as the first call does, throwing a `VersionMismatchError`
+     * otherwise. Servers not telling their version are assumed compatible.
+     */
+    async checkVersion(): Promise<void> {
+        this.serverVersion ??= this.discoverVersion().catch((error) => {
+            this.serverVersion = undefined;
+            throw error;
+        });
+        const serverVersion = await this.serverVersion;
+        if (
+            CLIENT_VERSION !== undefined &&
+            serverVersion !== undefined &&
+            significant(CLIENT_VERSION) !== significant(serverVersion)
+        ) {
+            throw new VersionMismatchError(CLIENT_VERSION, serverVersion);
+        }
+    }
+
+    private async discoverVersion(): Promise<string | undefined> {
+        try {
+            const spec = (await this.transport.request(constants.DISCOVER_METHOD, [])) as
+                | { info?: { version?: unknown } }
+                | undefined;
+            const version = spec?.info?.version;
+            return typeof version === "string" ? version : undefined;
+        } catch (error) {
+            if (error instanceof JsonRpcError && error.code === METHOD_NOT_FOUND) {
+                return undefined;
+            }
+            throw error;
+        }
+    }
+
+    private async call(method: string, params: unknown, options?: CallOptions): Promise<unknown> {
+        if (!this.options.skipVersionCheck) {
+            await this.checkVersion();
+        }
+        for (const middleware of this.middleware) {
+            params = (await middleware.onRequest?.(method, params)) ?? params;
+        }
+        const start = Date.now();
+        let outcome: CallOutcome;
+        try {
+            outcome = { ok: true, result: await this.transport.request(method, params, options) };
+        } catch (error) {
+            outcome = { ok: false, error };
+        }
+        const latency = Date.now() - start;
+        for (const middleware of this.middleware) {
+            await middleware.onResponse?.(method, outcome, latency);
+        }
+        if (!outcome.ok) {
+            throw outcome.error;
+        }
+        return outcome.result;
+    }
+
+    /** Resolve a proto transaction into a signed transaction envelope. */
+    async trpResolve(
+        params: types.ResolveParams,
+        options?: CallOptions,
+    ): Promise<types.TxEnvelope> {
+        const result = await this.call(constants.TRP_RESOLVE, params, options);
+        return result as types.TxEnvelope;
+    }
+
+    /** Submit a resolved transaction with witnesses. */
+    async trpSubmit(
+        params: types.SubmitParams,
+        options?: CallOptions,
+    ): Promise<types.SubmitResponse> {
+        const result = await this.call(constants.TRP_SUBMIT, params, {
+            ...options,
+            idempotencyKey: options?.idempotencyKey ?? idempotencyKey(),
+        });
+        return result as types.SubmitResponse;
+    }
+
+    /** Check how far submitted transactions have made it on chain. */
+    async trpCheckStatus(
+        params: types.CheckStatusParams,
+        options?: CallOptions,
+    ): Promise<types.CheckStatusResponse> {
+        const result = await this.call(constants.TRP_CHECK_STATUS, params, options);
+        return result as types.CheckStatusResponse;
+    }
+}
@@ -0,0 +1,117 @@
+// Generated by cargo xtask gen --lang deno
+import type { CallOptions, TrpApi } from "./client.ts";
+import * as constants from "./constants.ts";
+import type * as types from "./types.ts";
+
+/** A call made to a `MockTrpClient`. */
+export interface MockCall {
+    /** Wire name of the method, e.g. `constants.TRP_RESOLVE`. */
+    method: string;
+    params: unknown;
+    options?: CallOptions;
+}
+
+/** Result of the first example of each method of the spec, by wire name. */
+export const EXAMPLE_RESULTS: Readonly<Record<string, unknown>> = {
+    [constants.TRP_RESOLVE]: {
+        "tx": "84a300d90102818258200000000000000000000000000000000000000000000000000000000000000000000181a200581d60000000000000000000000000000000000000000000000000000000001a000f4240021a0002a8b1a0f5f6",
+        "hash": "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+    },
+    [constants.TRP_SUBMIT]: {
+        "hash": "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+    },
+    [constants.TRP_CHECK_STATUS]: {
+        "statuses": {
+            "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001": {
+                "stage": "confirmed",
+                "confirmations": 3
+            }
+        }
+    },
+};
+
+/**
+ * Stand-in for `TrpClient` in unit tests, implementing the same `TrpApi`.
+ * Calls resolve with a copy of the result of their method's first spec
+ * example, unless stubbed, and are recorded in `calls`:
+ *
+ * ```ts
+ * const client = new MockTrpClient().rejects("trpResolve", new JsonRpcError(-32000, "down"));
+ * ```
+ */
+export class MockTrpClient implements TrpApi {
+    readonly calls: MockCall[] = [];
+    private stubs: Partial<TrpApi>;
+
+    constructor(stubs: Partial<TrpApi> = {}) {
+        this.stubs = { ...stubs };
+    }
+
+    /** Answers the calls of `method` with `handler`. */
+    stub<K extends keyof TrpApi>(method: K, handler: TrpApi[K]): this {
+        this.stubs[method] = handler;
+        return this;
+    }
+
+    /** Resolves the calls of `method` with `result`. */
+    resolves<K extends keyof TrpApi>(method: K, result: Awaited<ReturnType<TrpApi[K]>>): this {
+        return this.stub(method, (async () => result) as unknown as TrpApi[K]);
+    }
+
+    /** Rejects the calls of `method` with `error`, e.g. a `JsonRpcError`. */
+    rejects<K extends keyof TrpApi>(method: K, error: unknown): this {
+        return this.stub(method, (async () => {
+            throw error;
+        }) as unknown as TrpApi[K]);
+    }
+
+    /** Drops the stubs and the recorded calls. */
+    reset(): this {
+        this.stubs = {};
+        this.calls.length = 0;
+        return this;
+    }
+
+    async trpResolve(
+        params: types.ResolveParams,
+        options?: CallOptions,
+    ): Promise<types.TxEnvelope> {
+        this.calls.push({ method: constants.TRP_RESOLVE, params, options });
+        const stub = this.stubs.trpResolve;
+        if (stub) {
+            return stub(params, options);
+        }
+        return example(constants.TRP_RESOLVE) as types.TxEnvelope;
+    }
+
+    async trpSubmit(
+        params: types.SubmitParams,
+        options?: CallOptions,
+    ): Promise<types.SubmitResponse> {
+        this.calls.push({ method: constants.TRP_SUBMIT, params, options });
+        const stub = this.stubs.trpSubmit;
+        if (stub) {
+            return stub(params, options);
+        }
+        return example(constants.TRP_SUBMIT) as types.SubmitResponse;
+    }
+
+    async trpCheckStatus(
+        params: types.CheckStatusParams,
+        options?: CallOptions,
+    ): Promise<types.CheckStatusResponse> {
+        this.calls.push({ method: constants.TRP_CHECK_STATUS, params, options });
+        const stub = this.stubs.trpCheckStatus;
+        if (stub) {
+            return stub(params, options);
+        }
+        return example(constants.TRP_CHECK_STATUS) as types.CheckStatusResponse;
+    }
+}
+
+function example(method: string): unknown {
+    if (!(method in EXAMPLE_RESULTS)) {
+        throw new Error(`${method} has no example result in the spec, stub it`);
+    }
+    return structuredClone(EXAMPLE_RESULTS[method]);
+}
@@ -0,0 +1,102 @@
+// Generated by cargo xtask gen --lang deno
+
+export interface BytesEnvelope {
+    encoding: string;
+    payload: string;
+}
+
+
+export interface CheckStatusParams {
+    hashes: string[];
+}
+
+
+export interface CheckStatusResponse {
+    statuses: Record<string, TxStatus>;
+}
+
+
+export interface InputNotResolvedDiagnostic {
+    name: string;
+    query: InputQueryDiagnostic;
+    search_space: SearchSpaceDiagnostic;
+}
+
+
+export interface InputQueryDiagnostic {
+    address?: string | null;
+    collateral: boolean;
+    min_amount: Record<string, string>;
+    refs: string[];
+    support_many: boolean;
+}
+
+
+export interface MissingTxArgDiagnostic {
+    key: string;
+    type: string;
+}
+
+
+export interface ResolveParams {
+    args: Record<string, any>;
+    env: Record<string, any>;
+    tir: TirInfo;
+}
+
+
+export interface SearchSpaceDiagnostic {
+    by_address_count?: number | null;
+    by_asset_class_count?: number | null;
+    by_ref_count?: number | null;
+    matched: string[];
+}
+
+
+export interface SubmitParams {
+    tx: BytesEnvelope;
+    witnesses: SubmitWitness[];
+}
+
+
+export interface SubmitResponse {
+    hash: string;
+}
+
+
+export interface SubmitWitness {
+    key: BytesEnvelope;
+    signature: BytesEnvelope;
+    type: "vkey";
+}
+
+
+export interface TirInfo {
+    bytecode: string;
+    encoding: string;
+    version: string;
+}
+
+
+export interface TxEnvelope {
+    hash: string;
+    tx: string;
+}
+
+
+export interface TxScriptFailureDiagnostic {
+    logs: string[];
+}
+
+
+export interface TxStatus {
+    confirmations: number;
+    stage: "pending" | "propagated" | "acknowledged" | "confirmed" | "finalized" | "dropped" | "rolledBack" | "unknown";
+}
+
+
+export interface UnsupportedTirDiagnostic {
+    expected: string;
+    provided: string;
+}
+
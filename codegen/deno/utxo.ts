@@ -0,0 +1,60 @@
+// Generated by cargo xtask gen --lang deno
+
+/** Length of a transaction hash in bytes. */
+export const TXID_LENGTH = 32;
+
+/**
+ * A reference to the output `index` of the transaction hashed `txid`, as
+ * lowercase hex. Build one with {@link utxoRef} or {@link parseUtxoRef}
+ * rather than by hand, so that it is valid and canonical.
+ */
+export interface UtxoRef {
+    readonly txid: string;
+    readonly index: number;
+}
+
+/** Thrown for text or values that aren't a valid UTxO reference. */
+export class InvalidUtxoRefError extends Error {
+    constructor(message: string) {
+        super(message);
+        this.name = "InvalidUtxoRefError";
+    }
+}
+
+const TXID = new RegExp(`^[0-9a-fA-F]{${TXID_LENGTH * 2}}$`);
+const INDEX = /^[0-9]+$/;
+
+/** The reference to output `index` of the transaction hashed `txid`, as hex. */
+export function utxoRef(txid: string, index: number): UtxoRef {
+    if (!TXID.test(txid)) {
+        throw new InvalidUtxoRefError(`expected a transaction hash of ${TXID_LENGTH * 2} hex digits, got "${txid}"`);
+    }
+    if (!Number.isInteger(index) || index < 0 || index > 0xffffffff) {
+        throw new InvalidUtxoRefError(`the output index ${index} isn't a 32-bit unsigned integer`);
+    }
+    return { txid: txid.toLowerCase(), index };
+}
+
+/**
+ * Parses the `txid#index` text form of a UTxO reference, or its JSON object
+ * form `{ txid, index }`.
+ */
+export function parseUtxoRef(value: string | { txid: string; index: number }): UtxoRef {
+    if (typeof value !== "string") {
+        return utxoRef(value.txid, value.index);
+    }
+    const separator = value.indexOf("#");
+    if (separator < 0) {
+        throw new InvalidUtxoRefError(`expected a \`txid#index\` UTxO reference, got "${value}"`);
+    }
+    const index = value.slice(separator + 1);
+    if (!INDEX.test(index)) {
+        throw new InvalidUtxoRefError(`the output index "${index}" isn't a 32-bit unsigned integer`);
+    }
+    return utxoRef(value.slice(0, separator), Number(index));
+}
+
+/** The canonical `txid#index` text form of `ref`, as sent on the wire. */
+export function formatUtxoRef(ref: UtxoRef): string {
+    return `${ref.txid.toLowerCase()}#${ref.index}`;
+}
\ No newline at end of file
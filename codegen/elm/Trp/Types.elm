@@ -0,0 +1,400 @@
+module Trp.Types exposing (..)
+
+-- Generated by cargo xtask gen --lang elm
+
+import Dict
+import Json.Decode as Decode
+import Json.Encode as Encode
+
+
+type alias BytesEnvelope =
+    { encoding : String
+    , payload : String
+    }
+
+
+bytesEnvelopeDecoder : Decode.Decoder BytesEnvelope
+bytesEnvelopeDecoder =
+    Decode.succeed BytesEnvelope
+        |> andMap (Decode.field "encoding" Decode.string)
+        |> andMap (Decode.field "payload" Decode.string)
+
+
+encodeBytesEnvelope : BytesEnvelope -> Encode.Value
+encodeBytesEnvelope value =
+    Encode.object
+        [ ( "encoding", Encode.string value.encoding )
+        , ( "payload", Encode.string value.payload )
+        ]
+
+
+type alias CheckStatusParams =
+    { hashes : List String
+    }
+
+
+checkStatusParamsDecoder : Decode.Decoder CheckStatusParams
+checkStatusParamsDecoder =
+    Decode.succeed CheckStatusParams
+        |> andMap (Decode.field "hashes" (Decode.list Decode.string))
+
+
+encodeCheckStatusParams : CheckStatusParams -> Encode.Value
+encodeCheckStatusParams value =
+    Encode.object
+        [ ( "hashes", Encode.list Encode.string value.hashes )
+        ]
+
+
+type alias CheckStatusResponse =
+    { statuses : Dict.Dict String TxStatus
+    }
+
+
+checkStatusResponseDecoder : Decode.Decoder CheckStatusResponse
+checkStatusResponseDecoder =
+    Decode.succeed CheckStatusResponse
+        |> andMap (Decode.field "statuses" (Decode.dict (Decode.lazy (\_ -> txStatusDecoder))))
+
+
+encodeCheckStatusResponse : CheckStatusResponse -> Encode.Value
+encodeCheckStatusResponse value =
+    Encode.object
+        [ ( "statuses", Encode.dict identity encodeTxStatus value.statuses )
+        ]
+
+
+type alias InputNotResolvedDiagnostic =
+    { name : String
+    , query : InputQueryDiagnostic
+    , searchSpace : SearchSpaceDiagnostic
+    }
+
+
+inputNotResolvedDiagnosticDecoder : Decode.Decoder InputNotResolvedDiagnostic
+inputNotResolvedDiagnosticDecoder =
+    Decode.succeed InputNotResolvedDiagnostic
+        |> andMap (Decode.field "name" Decode.string)
+        |> andMap (Decode.field "query" (Decode.lazy (\_ -> inputQueryDiagnosticDecoder)))
+        |> andMap (Decode.field "search_space" (Decode.lazy (\_ -> searchSpaceDiagnosticDecoder)))
+
+
+encodeInputNotResolvedDiagnostic : InputNotResolvedDiagnostic -> Encode.Value
+encodeInputNotResolvedDiagnostic value =
+    Encode.object
+        [ ( "name", Encode.string value.name )
+        , ( "query", encodeInputQueryDiagnostic value.query )
+        , ( "search_space", encodeSearchSpaceDiagnostic value.searchSpace )
+        ]
+
+
+type alias InputQueryDiagnostic =
+    { address : Maybe String
+    , collateral : Bool
+    , minAmount : Dict.Dict String String
+    , refs : List String
+    , supportMany : Bool
+    }
+
+
+inputQueryDiagnosticDecoder : Decode.Decoder InputQueryDiagnostic
+inputQueryDiagnosticDecoder =
+    Decode.succeed InputQueryDiagnostic
+        |> andMap (optionalField "address" Decode.string)
+        |> andMap (Decode.field "collateral" Decode.bool)
+        |> andMap (Decode.field "min_amount" (Decode.dict Decode.string))
+        |> andMap (Decode.field "refs" (Decode.list Decode.string))
+        |> andMap (Decode.field "support_many" Decode.bool)
+
+
+encodeInputQueryDiagnostic : InputQueryDiagnostic -> Encode.Value
+encodeInputQueryDiagnostic value =
+    Encode.object
+        (List.filterMap identity
+            [ Maybe.map (\inner -> ( "address", Encode.string inner )) value.address
+            , Just ( "collateral", Encode.bool value.collateral )
+            , Just ( "min_amount", Encode.dict identity Encode.string value.minAmount )
+            , Just ( "refs", Encode.list Encode.string value.refs )
+            , Just ( "support_many", Encode.bool value.supportMany )
+            ]
+        )
+
+
+type alias MissingTxArgDiagnostic =
+    { key : String
+    , type_ : String
+    }
+
+
+missingTxArgDiagnosticDecoder : Decode.Decoder MissingTxArgDiagnostic
+missingTxArgDiagnosticDecoder =
+    Decode.succeed MissingTxArgDiagnostic
+        |> andMap (Decode.field "key" Decode.string)
+        |> andMap (Decode.field "type" Decode.string)
+
+
+encodeMissingTxArgDiagnostic : MissingTxArgDiagnostic -> Encode.Value
+encodeMissingTxArgDiagnostic value =
+    Encode.object
+        [ ( "key", Encode.string value.key )
+        , ( "type", Encode.string value.type_ )
+        ]
+
+
+type alias ResolveParams =
+    { args : Dict.Dict String Decode.Value
+    , env : Dict.Dict String Decode.Value
+    , tir : TirInfo
+    }
+
+
+resolveParamsDecoder : Decode.Decoder ResolveParams
+resolveParamsDecoder =
+    Decode.succeed ResolveParams
+        |> andMap (Decode.field "args" (Decode.dict Decode.value))
+        |> andMap (Decode.field "env" (Decode.dict Decode.value))
+        |> andMap (Decode.field "tir" (Decode.lazy (\_ -> tirInfoDecoder)))
+
+
+encodeResolveParams : ResolveParams -> Encode.Value
+encodeResolveParams value =
+    Encode.object
+        [ ( "args", Encode.dict identity identity value.args )
+        , ( "env", Encode.dict identity identity value.env )
+        , ( "tir", encodeTirInfo value.tir )
+        ]
+
+
+type alias SearchSpaceDiagnostic =
+    { byAddressCount : Maybe Int
+    , byAssetClassCount : Maybe Int
+    , byRefCount : Maybe Int
+    , matched : List String
+    }
+
+
+searchSpaceDiagnosticDecoder : Decode.Decoder SearchSpaceDiagnostic
+searchSpaceDiagnosticDecoder =
+    Decode.succeed SearchSpaceDiagnostic
+        |> andMap (optionalField "by_address_count" Decode.int)
+        |> andMap (optionalField "by_asset_class_count" Decode.int)
+        |> andMap (optionalField "by_ref_count" Decode.int)
+        |> andMap (Decode.field "matched" (Decode.list Decode.string))
+
+
+encodeSearchSpaceDiagnostic : SearchSpaceDiagnostic -> Encode.Value
+encodeSearchSpaceDiagnostic value =
+    Encode.object
+        (List.filterMap identity
+            [ Maybe.map (\inner -> ( "by_address_count", Encode.int inner )) value.byAddressCount
+            , Maybe.map (\inner -> ( "by_asset_class_count", Encode.int inner )) value.byAssetClassCount
+            , Maybe.map (\inner -> ( "by_ref_count", Encode.int inner )) value.byRefCount
+            , Just ( "matched", Encode.list Encode.string value.matched )
+            ]
+        )
+
+
+type alias SubmitParams =
+    { tx : BytesEnvelope
+    , witnesses : List SubmitWitness
+    }
+
+
+submitParamsDecoder : Decode.Decoder SubmitParams
+submitParamsDecoder =
+    Decode.succeed SubmitParams
+        |> andMap (Decode.field "tx" (Decode.lazy (\_ -> bytesEnvelopeDecoder)))
+        |> andMap (Decode.field "witnesses" (Decode.list (Decode.lazy (\_ -> submitWitnessDecoder))))
+
+
+encodeSubmitParams : SubmitParams -> Encode.Value
+encodeSubmitParams value =
+    Encode.object
+        [ ( "tx", encodeBytesEnvelope value.tx )
+        , ( "witnesses", Encode.list encodeSubmitWitness value.witnesses )
+        ]
+
+
+type alias SubmitResponse =
+    { hash : String
+    }
+
+
+submitResponseDecoder : Decode.Decoder SubmitResponse
+submitResponseDecoder =
+    Decode.succeed SubmitResponse
+        |> andMap (Decode.field "hash" Decode.string)
+
+
+encodeSubmitResponse : SubmitResponse -> Encode.Value
+encodeSubmitResponse value =
+    Encode.object
+        [ ( "hash", Encode.string value.hash )
+        ]
+
+
+type alias SubmitWitness =
+    { key : BytesEnvelope
+    , signature : BytesEnvelope
+    , type_ : String
+    }
+
+
+submitWitnessDecoder : Decode.Decoder SubmitWitness
+submitWitnessDecoder =
+    Decode.succeed SubmitWitness
+        |> andMap (Decode.field "key" (Decode.lazy (\_ -> bytesEnvelopeDecoder)))
+        |> andMap (Decode.field "signature" (Decode.lazy (\_ -> bytesEnvelopeDecoder)))
+        |> andMap (Decode.field "type" Decode.string)
+
+
+encodeSubmitWitness : SubmitWitness -> Encode.Value
+encodeSubmitWitness value =
+    Encode.object
+        [ ( "key", encodeBytesEnvelope value.key )
+        , ( "signature", encodeBytesEnvelope value.signature )
+        , ( "type", Encode.string value.type_ )
+        ]
+
+
+type alias TirInfo =
+    { bytecode : String
+    , encoding : String
+    , version : String
+    }
+
+
+tirInfoDecoder : Decode.Decoder TirInfo
+tirInfoDecoder =
+    Decode.succeed TirInfo
+        |> andMap (Decode.field "bytecode" Decode.string)
+        |> andMap (Decode.field "encoding" Decode.string)
+        |> andMap (Decode.field "version" Decode.string)
+
+
+encodeTirInfo : TirInfo -> Encode.Value
+encodeTirInfo value =
+    Encode.object
+        [ ( "bytecode", Encode.string value.bytecode )
+        , ( "encoding", Encode.string value.encoding )
+        , ( "version", Encode.string value.version )
+        ]
+
+
+type alias TxEnvelope =
+    { hash : String
+    , tx : String
+    }
+
+
+txEnvelopeDecoder : Decode.Decoder TxEnvelope
+txEnvelopeDecoder =
+    Decode.succeed TxEnvelope
+        |> andMap (Decode.field "hash" Decode.string)
+        |> andMap (Decode.field "tx" Decode.string)
+
+
+encodeTxEnvelope : TxEnvelope -> Encode.Value
+encodeTxEnvelope value =
+    Encode.object
+        [ ( "hash", Encode.string value.hash )
+        , ( "tx", Encode.string value.tx )
+        ]
+
+
+type alias TxScriptFailureDiagnostic =
+    { logs : List String
+    }
+
+
+txScriptFailureDiagnosticDecoder : Decode.Decoder TxScriptFailureDiagnostic
+txScriptFailureDiagnosticDecoder =
+    Decode.succeed TxScriptFailureDiagnostic
+        |> andMap (Decode.field "logs" (Decode.list Decode.string))
+
+
+encodeTxScriptFailureDiagnostic : TxScriptFailureDiagnostic -> Encode.Value
+encodeTxScriptFailureDiagnostic value =
+    Encode.object
+        [ ( "logs", Encode.list Encode.string value.logs )
+        ]
+
+
+type alias TxStatus =
+    { confirmations : Int
+    , stage : String
+    }
+
+
+txStatusDecoder : Decode.Decoder TxStatus
+txStatusDecoder =
+    Decode.succeed TxStatus
+        |> andMap (Decode.field "confirmations" Decode.int)
+        |> andMap (Decode.field "stage" Decode.string)
+
+
+encodeTxStatus : TxStatus -> Encode.Value
+encodeTxStatus value =
+    Encode.object
+        [ ( "confirmations", Encode.int value.confirmations )
+        , ( "stage", Encode.string value.stage )
+        ]
+
+
+type alias UnsupportedTirDiagnostic =
+    { expected : String
+    , provided : String
+    }
+
+
+unsupportedTirDiagnosticDecoder : Decode.Decoder UnsupportedTirDiagnostic
+unsupportedTirDiagnosticDecoder =
+    Decode.succeed UnsupportedTirDiagnostic
+        |> andMap (Decode.field "expected" Decode.string)
+        |> andMap (Decode.field "provided" Decode.string)
+
+
+encodeUnsupportedTirDiagnostic : UnsupportedTirDiagnostic -> Encode.Value
+encodeUnsupportedTirDiagnostic value =
+    Encode.object
+        [ ( "expected", Encode.string value.expected )
+        , ( "provided", Encode.string value.provided )
+        ]
+
+
+
+-- HELPERS
+
+
+{-| Applies a decoded value to a decoded function, to decode records one
+field at a time.
+-}
+andMap : Decode.Decoder a -> Decode.Decoder (a -> b) -> Decode.Decoder b
+andMap =
+    Decode.map2 (|>)
+
+
+{-| Decodes a field that may be absent or `null` as `Nothing`.
+-}
+optionalField : String -> Decode.Decoder a -> Decode.Decoder (Maybe a)
+optionalField name decoder =
+    Decode.maybe (Decode.field name Decode.value)
+        |> Decode.andThen
+            (\raw ->
+                case raw of
+                    Just _ ->
+                        Decode.field name (Decode.nullable decoder)
+
+                    Nothing ->
+                        Decode.succeed Nothing
+            )
+
+
+encodeNullable : (a -> Encode.Value) -> Maybe a -> Encode.Value
+encodeNullable encode value =
+    case value of
+        Just inner ->
+            encode inner
+
+        Nothing ->
+            Encode.null
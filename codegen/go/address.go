@@ -0,0 +1,301 @@
+// Generated by cargo xtask gen --lang go
+package types
+
+import (
+    "bytes"
+    "encoding/hex"
+    "errors"
+    "fmt"
+    "math/big"
+    "strings"
+)
+
+const (
+    bech32Charset  = "qpzry9x8gf2tvdw0s3jn54khce6mua7l"
+    base58Alphabet = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+)
+
+// Mainnet is the network id of mainnet; every other id is a test network.
+const Mainnet = 1
+
+// ErrInvalidAddress is wrapped by the errors of text or bytes that aren't a
+// valid address.
+var ErrInvalidAddress = errors.New("invalid address")
+
+// Address is a Cardano address: its header byte, telling its kind and
+// network, then its payload. Fields marked with format: address hold its
+// text form, bech32 for Shelley addresses (addr1…, stake_test1…) and base58
+// for Byron ones, which String gives back.
+type Address struct {
+    bytes []byte
+}
+
+func invalidAddress(format string, args ...any) error {
+    return fmt.Errorf("%w: %s", ErrInvalidAddress, fmt.Sprintf(format, args...))
+}
+
+// AddressFromBytes returns the address made of b, checked to be of a known
+// kind.
+func AddressFromBytes(b []byte) (Address, error) {
+    if len(b) == 0 {
+        return Address{}, invalidAddress("empty")
+    }
+    var valid bool
+    switch kind := b[0] >> 4; {
+    case kind <= 3:
+        valid = len(b) == 57
+    case kind <= 5:
+        valid = len(b) >= 32
+    case kind <= 7 || kind >= 14:
+        valid = len(b) == 29
+    case kind == 8:
+        valid = bytes.HasPrefix(b, []byte{0x82, 0xd8, 0x18})
+    default:
+        return Address{}, invalidAddress("unknown header %#02x", b[0])
+    }
+    if !valid {
+        return Address{}, invalidAddress("an address of this kind can't be %d bytes long", len(b))
+    }
+    return Address{bytes: bytes.Clone(b)}, nil
+}
+
+// AddressFromHex returns the address whose bytes text encodes.
+func AddressFromHex(text string) (Address, error) {
+    b, err := hex.DecodeString(text)
+    if err != nil {
+        return Address{}, invalidAddress("not hex")
+    }
+    return AddressFromBytes(b)
+}
+
+// ParseAddress parses the bech32 or base58 text form of an address.
+func ParseAddress(text string) (Address, error) {
+    separator := strings.LastIndexByte(text, '1')
+    // Byron addresses are mixed-case, unlike bech32 prefixes.
+    if separator > 0 && isBech32Prefix(text[:separator]) {
+        prefix := strings.ToLower(text[:separator])
+        b, err := bech32Decode(prefix, text[separator+1:])
+        if err != nil {
+            return Address{}, err
+        }
+        address, err := AddressFromBytes(b)
+        if err != nil {
+            return Address{}, err
+        }
+        if expected, _ := address.Prefix(); expected != prefix {
+            return Address{}, invalidAddress("expected the prefix %q, got %q", expected, prefix)
+        }
+        return address, nil
+    }
+    b, err := base58Decode(text)
+    if err != nil {
+        return Address{}, err
+    }
+    address, err := AddressFromBytes(b)
+    if err != nil {
+        return Address{}, err
+    }
+    if !address.IsByron() {
+        return Address{}, invalidAddress("neither bech32 nor base58")
+    }
+    return address, nil
+}
+
+// IsAddress reports whether text is a valid address, as Validate checks the
+// fields marked with format: address.
+func IsAddress(text string) bool {
+    _, err := ParseAddress(text)
+    return err == nil
+}
+
+// Bytes returns the bytes of the address.
+func (a Address) Bytes() []byte {
+    return bytes.Clone(a.bytes)
+}
+
+// Hex returns the bytes of the address as lowercase hex.
+func (a Address) Hex() string {
+    return hex.EncodeToString(a.bytes)
+}
+
+func (a Address) IsByron() bool {
+    return a.bytes[0]>>4 == 8
+}
+
+// IsReward reports whether the address names a stake credential rather
+// than a payment one.
+func (a Address) IsReward() bool {
+    return a.bytes[0]>>4 >= 14
+}
+
+// NetworkID returns the network id of a Shelley address. Byron addresses
+// don't tell it in their header.
+func (a Address) NetworkID() (byte, bool) {
+    if a.IsByron() {
+        return 0, false
+    }
+    return a.bytes[0] & 0x0f, true
+}
+
+// Prefix returns the bech32 prefix of a Shelley address.
+func (a Address) Prefix() (string, bool) {
+    network, ok := a.NetworkID()
+    if !ok {
+        return "", false
+    }
+    prefix := "addr"
+    if a.IsReward() {
+        prefix = "stake"
+    }
+    if network != Mainnet {
+        prefix += "_test"
+    }
+    return prefix, true
+}
+
+// Bech32 returns the bech32 form of a Shelley address.
+func (a Address) Bech32() (string, bool) {
+    prefix, ok := a.Prefix()
+    if !ok {
+        return "", false
+    }
+    data, _ := regroup(a.bytes, 8, 5, true)
+    var text strings.Builder
+    text.WriteString(prefix)
+    text.WriteByte('1')
+    for _, value := range append(data, bech32Checksum(prefix, data)...) {
+        text.WriteByte(bech32Charset[value])
+    }
+    return text.String(), true
+}
+
+// String returns the text form of the address, as fields hold it.
+func (a Address) String() string {
+    if text, ok := a.Bech32(); ok {
+        return text
+    }
+    return base58Encode(a.bytes)
+}
+
+func isBech32Prefix(prefix string) bool {
+    lower, upper := true, true
+    for _, c := range prefix {
+        digit := c >= '0' && c <= '9' || c == '_'
+        lower = lower && (digit || c >= 'a' && c <= 'z')
+        upper = upper && (digit || c >= 'A' && c <= 'Z')
+    }
+    return lower || upper
+}
+
+func bech32Polymod(values []byte) uint32 {
+    generators := [5]uint32{0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3}
+    checksum := uint32(1)
+    for _, value := range values {
+        top := checksum >> 25
+        checksum = (checksum&0x1ffffff)<<5 ^ uint32(value)
+        for i, generator := range generators {
+            if (top>>i)&1 == 1 {
+                checksum ^= generator
+            }
+        }
+    }
+    return checksum
+}
+
+func bech32PrefixValues(prefix string) []byte {
+    values := make([]byte, 0, len(prefix)*2+1)
+    for i := 0; i < len(prefix); i++ {
+        values = append(values, prefix[i]>>5)
+    }
+    values = append(values, 0)
+    for i := 0; i < len(prefix); i++ {
+        values = append(values, prefix[i]&31)
+    }
+    return values
+}
+
+func bech32Checksum(prefix string, data []byte) []byte {
+    values := append(append(bech32PrefixValues(prefix), data...), 0, 0, 0, 0, 0, 0)
+    polymod := bech32Polymod(values) ^ 1
+    checksum := make([]byte, 6)
+    for i := range checksum {
+        checksum[i] = byte(polymod>>(5*(5-i))) & 31
+    }
+    return checksum
+}
+
+func bech32Decode(prefix, data string) ([]byte, error) {
+    if len(data) < 6 || (data != strings.ToLower(data) && data != strings.ToUpper(data)) {
+        return nil, invalidAddress("not bech32")
+    }
+    values := make([]byte, len(data))
+    for i, c := range strings.ToLower(data) {
+        position := strings.IndexRune(bech32Charset, c)
+        if position < 0 {
+            return nil, invalidAddress("not bech32")
+        }
+        values[i] = byte(position)
+    }
+    if bech32Polymod(append(bech32PrefixValues(prefix), values...)) != 1 {
+        return nil, invalidAddress("the checksum doesn't match")
+    }
+    return regroup(values[:len(values)-6], 5, 8, false)
+}
+
+// regroup regroups values of from bits into values of to bits, padding the
+// last one with zeros if pad, and else requiring the leftover bits to be.
+func regroup(values []byte, from, to uint, pad bool) ([]byte, error) {
+    var acc uint32
+    var bits uint
+    var out []byte
+    mask := uint32(1)<<to - 1
+    for _, value := range values {
+        acc = acc<<from | uint32(value)
+        bits += from
+        for bits >= to {
+            bits -= to
+            out = append(out, byte(acc>>bits&mask))
+        }
+    }
+    if pad && bits > 0 {
+        out = append(out, byte(acc<<(to-bits)&mask))
+    } else if !pad && (bits >= from || acc&(uint32(1)<<bits-1) != 0) {
+        return nil, invalidAddress("not bech32")
+    }
+    return out, nil
+}
+
+func base58Decode(text string) ([]byte, error) {
+    number := new(big.Int)
+    radix := big.NewInt(58)
+    for _, c := range text {
+        digit := strings.IndexRune(base58Alphabet, c)
+        if digit < 0 {
+            return nil, invalidAddress("neither bech32 nor base58")
+        }
+        number.Mul(number, radix).Add(number, big.NewInt(int64(digit)))
+    }
+    zeros := len(text) - len(strings.TrimLeft(text, "1"))
+    return append(make([]byte, zeros), number.Bytes()...), nil
+}
+
+func base58Encode(b []byte) string {
+    number := new(big.Int).SetBytes(b)
+    radix := big.NewInt(58)
+    digit := new(big.Int)
+    var digits []byte
+    for number.Sign() > 0 {
+        number.DivMod(number, radix, digit)
+        digits = append(digits, base58Alphabet[digit.Int64()])
+    }
+    for _, value := range b {
+        if value != 0 {
+            break
+        }
+        digits = append(digits, '1')
+    }
+    for i, j := 0, len(digits)-1; i < j; i, j = i+1, j-1 {
+        digits[i], digits[j] = digits[j], digits[i]
+    }
+    return string(digits)
+}
\ No newline at end of file
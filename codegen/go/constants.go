@@ -0,0 +1,20 @@
+// Generated by cargo xtask gen --lang go
+package types
+
+// SpecVersion is the version of the spec these bindings were generated from.
+const SpecVersion = "0.1.1"
+
+const TrpResolve = "trp.resolve"
+
+// TrpResolveParams lists the params of trp.resolve in declared order.
+var TrpResolveParams = []string{"tir", "args", "env"}
+
+const TrpSubmit = "trp.submit"
+
+// TrpSubmitParams lists the params of trp.submit in declared order.
+var TrpSubmitParams = []string{"tx", "witnesses"}
+
+const TrpCheckStatus = "trp.checkStatus"
+
+// TrpCheckStatusParams lists the params of trp.checkStatus in declared order.
+var TrpCheckStatusParams = []string{"hashes"}
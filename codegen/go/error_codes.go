@@ -0,0 +1,127 @@
+// Generated by cargo xtask gen --lang go
+package types
+
+import (
+    "encoding/json"
+    "fmt"
+)
+
+// ErrorClass tells who answers errors of a given code, the codes being split
+// into ranges so that the errors of JSON-RPC itself, of transports and of the
+// spec's methods never share a code.
+type ErrorClass string
+
+const (
+    // Errors of JSON-RPC itself, reserved by it.
+    ErrorClassProtocol ErrorClass = "protocol"
+    // Errors answered by servers, proxies and their JSON-RPC library, whatever the method.
+    ErrorClassTransport ErrorClass = "transport"
+    // Errors the spec's methods declare.
+    ErrorClassApplication ErrorClass = "application"
+)
+
+// ErrorCodeRange holds the first and last code of a class.
+type ErrorCodeRange struct {
+    Start int
+    End   int
+}
+
+// Contains reports whether code lies in the range.
+func (r ErrorCodeRange) Contains(code int) bool {
+    return code >= r.Start && code <= r.End
+}
+
+// Ranges of the codes of each class.
+var (
+    ProtocolRange = ErrorCodeRange{Start: -32768, End: -32100}
+    TransportRange = ErrorCodeRange{Start: -32099, End: -32004}
+    ApplicationRange = ErrorCodeRange{Start: -32003, End: -32000}
+)
+
+// Codes the protocol and the servers answer, which the spec's methods don't
+// declare.
+const (
+    CodeParseError = -32700 // Parse error, a protocol error.
+    CodeInvalidRequest = -32600 // Invalid request, a protocol error.
+    CodeMethodNotFound = -32601 // Method not found, a protocol error.
+    CodeInvalidParams = -32602 // Invalid params, a protocol error.
+    CodeInternalError = -32603 // Internal error, a protocol error.
+    CodeUpstreamUnavailable = -32004 // No upstream available, a transport error.
+    CodeBatchesNotSupported = -32005 // Batched requests are not supported, a transport error.
+    CodeTooManySubscriptions = -32006 // Too many subscriptions, a transport error.
+    CodeOversizedRequest = -32007 // Request is too big, a transport error.
+    CodeOversizedResponse = -32008 // Response is too big, a transport error.
+    CodeServerIsBusy = -32009 // Server is busy, a transport error.
+    CodeTooBigBatchRequest = -32010 // Batch request is too big, a transport error.
+    CodeTooBigBatchResponse = -32011 // Batch response is too big, a transport error.
+    CodeRateLimited = -32012 // Rate limit exceeded, a transport error.
+    CodeIdempotencyConflict = -32013 // Idempotency key conflict, a transport error.
+    CodeRequestCancelled = -32800 // Request cancelled, a transport error.
+)
+
+// ApplicationCodes lists the codes of the errors the spec declares.
+var ApplicationCodes = []int{ErrCodeUnsupportedTir, ErrCodeMissingTransactionArgument, ErrCodeInputNotResolved, ErrCodeTxScriptFailure}
+
+var declaredMessages = map[int]string{
+    ErrCodeUnsupportedTir: "Unsupported TIR",
+    ErrCodeMissingTransactionArgument: "Missing transaction argument",
+    ErrCodeInputNotResolved: "Input not resolved",
+    ErrCodeTxScriptFailure: "Tx script failure",
+}
+
+// ErrorClassOf returns the class of code, and false for codes outside of
+// every range.
+func ErrorClassOf(code int) (ErrorClass, bool) {
+    switch {
+    case code == CodeRequestCancelled:
+        return ErrorClassTransport, true
+    case ProtocolRange.Contains(code):
+        return ErrorClassProtocol, true
+    case TransportRange.Contains(code):
+        return ErrorClassTransport, true
+    case ApplicationRange.Contains(code):
+        return ErrorClassApplication, true
+    }
+    return "", false
+}
+
+// Class returns the class of the error's code, and false for codes outside
+// of every range.
+func (e *TrpError) Class() (ErrorClass, bool) {
+    return ErrorClassOf(e.Code)
+}
+
+// DeclaredError returns the error the spec declares with code, with its
+// declared message and carrying data unless it's nil. It fails for codes the
+// spec doesn't declare.
+func DeclaredError(code int, data interface{}) (*TrpError, error) {
+    message, ok := declaredMessages[code]
+    if !ok {
+        return nil, fmt.Errorf("%d is not an error code the spec declares", code)
+    }
+    return newError(code, message, data)
+}
+
+// ReservedError returns a protocol or transport error, as answered whatever
+// the method, carrying data unless it's nil. It fails for other codes:
+// errors of the methods must be declared by the spec, and built with
+// DeclaredError.
+func ReservedError(code int, message string, data interface{}) (*TrpError, error) {
+    class, ok := ErrorClassOf(code)
+    if !ok || (class != ErrorClassProtocol && class != ErrorClassTransport) {
+        return nil, fmt.Errorf("%d is not a protocol or transport error code", code)
+    }
+    return newError(code, message, data)
+}
+
+func newError(code int, message string, data interface{}) (*TrpError, error) {
+    err := &TrpError{Code: code, Message: message}
+    if data != nil {
+        raw, jsonErr := json.Marshal(data)
+        if jsonErr != nil {
+            return nil, jsonErr
+        }
+        err.Data = raw
+    }
+    return err, nil
+}
\ No newline at end of file
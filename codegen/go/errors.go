@@ -0,0 +1,106 @@
+// Generated by cargo xtask gen --lang go
+package types
+
+import (
+    "encoding/json"
+    "fmt"
+)
+
+// Codes of the errors declared by the spec.
+const (
+    ErrCodeUnsupportedTir = -32000
+    ErrCodeMissingTransactionArgument = -32001
+    ErrCodeInputNotResolved = -32002
+    ErrCodeTxScriptFailure = -32003
+)
+
+// Sentinels of the declared errors, matched by code with errors.Is.
+var (
+    ErrUnsupportedTir = &TrpError{Code: ErrCodeUnsupportedTir, Message: "Unsupported TIR"}
+    ErrMissingTransactionArgument = &TrpError{Code: ErrCodeMissingTransactionArgument, Message: "Missing transaction argument"}
+    ErrInputNotResolved = &TrpError{Code: ErrCodeInputNotResolved, Message: "Input not resolved"}
+    ErrTxScriptFailure = &TrpError{Code: ErrCodeTxScriptFailure, Message: "Tx script failure"}
+)
+
+// TrpError is the error object of a failed call. Extract it with errors.As.
+type TrpError struct {
+    Code    int             `json:"code"`
+    Message string          `json:"message"`
+    Data    json.RawMessage `json:"data,omitempty"`
+}
+
+func (e *TrpError) Error() string {
+    return fmt.Sprintf("%s (%d)", e.Message, e.Code)
+}
+
+// Is reports whether target is a TrpError with the same code.
+func (e *TrpError) Is(target error) bool {
+    t, ok := target.(*TrpError)
+    return ok && t.Code == e.Code
+}
+
+// Retryable reports whether a call failing with this error may succeed when
+// retried.
+func (e *TrpError) Retryable() bool {
+    switch e.Code {
+    case ErrCodeInputNotResolved:
+        return true
+    }
+    return false
+}
+
+// UnsupportedTirData decodes the data of an error with code
+// ErrCodeUnsupportedTir. It returns nil when the error has another code or
+// carries no data.
+func (e *TrpError) UnsupportedTirData() (*UnsupportedTirDiagnostic, error) {
+    if e.Code != ErrCodeUnsupportedTir || len(e.Data) == 0 {
+        return nil, nil
+    }
+    var data UnsupportedTirDiagnostic
+    if err := json.Unmarshal(e.Data, &data); err != nil {
+        return nil, err
+    }
+    return &data, nil
+}
+
+// MissingTransactionArgumentData decodes the data of an error with code
+// ErrCodeMissingTransactionArgument. It returns nil when the error has another code or
+// carries no data.
+func (e *TrpError) MissingTransactionArgumentData() (*MissingTxArgDiagnostic, error) {
+    if e.Code != ErrCodeMissingTransactionArgument || len(e.Data) == 0 {
+        return nil, nil
+    }
+    var data MissingTxArgDiagnostic
+    if err := json.Unmarshal(e.Data, &data); err != nil {
+        return nil, err
+    }
+    return &data, nil
+}
+
+// InputNotResolvedData decodes the data of an error with code
+// ErrCodeInputNotResolved. It returns nil when the error has another code or
+// carries no data.
+func (e *TrpError) InputNotResolvedData() (*InputNotResolvedDiagnostic, error) {
+    if e.Code != ErrCodeInputNotResolved || len(e.Data) == 0 {
+        return nil, nil
+    }
+    var data InputNotResolvedDiagnostic
+    if err := json.Unmarshal(e.Data, &data); err != nil {
+        return nil, err
+    }
+    return &data, nil
+}
+
+// TxScriptFailureData decodes the data of an error with code
+// ErrCodeTxScriptFailure. It returns nil when the error has another code or
+// carries no data.
+func (e *TrpError) TxScriptFailureData() (*TxScriptFailureDiagnostic, error) {
+    if e.Code != ErrCodeTxScriptFailure || len(e.Data) == 0 {
+        return nil, nil
+    }
+    var data TxScriptFailureDiagnostic
+    if err := json.Unmarshal(e.Data, &data); err != nil {
+        return nil, err
+    }
+    return &data, nil
+}
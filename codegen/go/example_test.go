@@ -0,0 +1,123 @@
+// Generated by cargo xtask gen --lang go
+package types
+
+import (
+    "encoding/json"
+    "fmt"
+)
+
+// ExampleResolveParams_resolveTransfer decodes the resolve-transfer example of trp.resolve.
+func ExampleResolveParams_resolveTransfer() {
+    var params ResolveParams
+    if err := json.Unmarshal([]byte(`{
+  "tir": {
+    "version": "v1alpha8",
+    "bytecode": "a1646e616d65687472616e73666572",
+    "encoding": "hex"
+  },
+  "args": {
+    "quantity": 1000000
+  },
+  "env": {}
+}`), &params); err != nil {
+        panic(err)
+    }
+    if _, err := json.Marshal(params); err != nil {
+        panic(err)
+    }
+    fmt.Println(params.Validate())
+
+    var result TxEnvelope
+    if err := json.Unmarshal([]byte(`{
+  "tx": "84a300d90102818258200000000000000000000000000000000000000000000000000000000000000000000181a200581d60000000000000000000000000000000000000000000000000000000001a000f4240021a0002a8b1a0f5f6",
+  "hash": "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+}`), &result); err != nil {
+        panic(err)
+    }
+    if _, err := json.Marshal(result); err != nil {
+        panic(err)
+    }
+    fmt.Println(result.Validate())
+    // Output:
+    // <nil>
+    // <nil>
+}
+
+// ExampleSubmitParams_submitVkeyWitness decodes the submit-vkey-witness example of trp.submit.
+func ExampleSubmitParams_submitVkeyWitness() {
+    var params SubmitParams
+    if err := json.Unmarshal([]byte(`{
+  "tx": {
+    "encoding": "hex",
+    "payload": "84a300d90102818258200000000000000000000000000000000000000000000000000000000000000000000181a200581d60000000000000000000000000000000000000000000000000000000001a000f4240021a0002a8b1a0f5f6"
+  },
+  "witnesses": [
+    {
+      "type": "vkey",
+      "key": {
+        "encoding": "hex",
+        "payload": "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+      },
+      "signature": {
+        "encoding": "hex",
+        "payload": "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+      }
+    }
+  ]
+}`), &params); err != nil {
+        panic(err)
+    }
+    if _, err := json.Marshal(params); err != nil {
+        panic(err)
+    }
+    fmt.Println(params.Validate())
+
+    var result SubmitResponse
+    if err := json.Unmarshal([]byte(`{
+  "hash": "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+}`), &result); err != nil {
+        panic(err)
+    }
+    if _, err := json.Marshal(result); err != nil {
+        panic(err)
+    }
+    fmt.Println(result.Validate())
+    // Output:
+    // <nil>
+    // <nil>
+}
+
+// ExampleCheckStatusParams_checkStatusConfirmed decodes the check-status-confirmed example of trp.checkStatus.
+func ExampleCheckStatusParams_checkStatusConfirmed() {
+    var params CheckStatusParams
+    if err := json.Unmarshal([]byte(`{
+  "hashes": [
+    "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+  ]
+}`), &params); err != nil {
+        panic(err)
+    }
+    if _, err := json.Marshal(params); err != nil {
+        panic(err)
+    }
+    fmt.Println(params.Validate())
+
+    var result CheckStatusResponse
+    if err := json.Unmarshal([]byte(`{
+  "statuses": {
+    "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001": {
+      "stage": "confirmed",
+      "confirmations": 3
+    }
+  }
+}`), &result); err != nil {
+        panic(err)
+    }
+    if _, err := json.Marshal(result); err != nil {
+        panic(err)
+    }
+    fmt.Println(result.Validate())
+    // Output:
+    // <nil>
+    // <nil>
+}
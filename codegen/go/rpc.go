@@ -0,0 +1,222 @@
+// Generated by cargo xtask gen --lang go
+package types
+
+import (
+    "bytes"
+    "context"
+    "encoding/json"
+    "fmt"
+    "io"
+    "net/http"
+    "time"
+)
+
+// Request is a JSON-RPC 2.0 request calling Method with Params.
+type Request[P any] struct {
+    JSONRPC string `json:"jsonrpc"`
+    ID      int64  `json:"id"`
+    Method  string `json:"method"`
+    Params  P      `json:"params"`
+}
+
+// Response is a JSON-RPC 2.0 response, holding either a Result or an Error.
+// ID is nil when the server couldn't read the id of the request.
+type Response[R any] struct {
+    JSONRPC string    `json:"jsonrpc"`
+    ID      *int64    `json:"id"`
+    Result  *R        `json:"result,omitempty"`
+    Error   *TrpError `json:"error,omitempty"`
+}
+
+// Call is a method of the spec, typed with its params P and result R. Methods
+// taking their params by position are typed with an array of them.
+type Call[P, R any] struct {
+    Method string
+}
+
+// TrpResolveCall calls trp.resolve.
+var TrpResolveCall = Call[ResolveParams, TxEnvelope]{Method: TrpResolve}
+
+// TrpSubmitCall calls trp.submit.
+var TrpSubmitCall = Call[SubmitParams, SubmitResponse]{Method: TrpSubmit}
+
+// TrpCheckStatusCall calls trp.checkStatus.
+var TrpCheckStatusCall = Call[CheckStatusParams, CheckStatusResponse]{Method: TrpCheckStatus}
+
+// Request is the request calling the method with params.
+func (c Call[P, R]) Request(id int64, params P) Request[P] {
+    return Request[P]{JSONRPC: "2.0", ID: id, Method: c.Method, Params: params}
+}
+
+// Decode decodes a response of the method. Error objects are returned as a
+// *TrpError, matched against the sentinels with errors.Is.
+func (c Call[P, R]) Decode(body []byte) (R, error) {
+    var result R
+    var response Response[R]
+    if err := json.Unmarshal(body, &response); err != nil {
+        return result, fmt.Errorf("%s: decoding the response: %w", c.Method, err)
+    }
+    if response.Error != nil {
+        return result, response.Error
+    }
+    if response.Result != nil {
+        result = *response.Result
+    }
+    return result, nil
+}
+
+// Do posts the request calling the method with params to url and decodes
+// the response. A nil client stands for http.DefaultClient.
+func (c Call[P, R]) Do(ctx context.Context, client *http.Client, url string, params P) (R, error) {
+    var result R
+    body, err := post(ctx, client, url, c.Request(1, params))
+    if err != nil {
+        return result, fmt.Errorf("%s: %w", c.Method, err)
+    }
+    return c.Decode(body)
+}
+
+// DoTrpResolve calls trp.resolve on the server at url.
+func DoTrpResolve(ctx context.Context, client *http.Client, url string, params ResolveParams) (TxEnvelope, error) {
+    return TrpResolveCall.Do(ctx, client, url, params)
+}
+
+// DoTrpSubmit calls trp.submit on the server at url.
+func DoTrpSubmit(ctx context.Context, client *http.Client, url string, params SubmitParams) (SubmitResponse, error) {
+    return TrpSubmitCall.Do(ctx, client, url, params)
+}
+
+// DoTrpCheckStatus calls trp.checkStatus on the server at url.
+func DoTrpCheckStatus(ctx context.Context, client *http.Client, url string, params CheckStatusParams) (CheckStatusResponse, error) {
+    return TrpCheckStatusCall.Do(ctx, client, url, params)
+}
+
+// Middleware is a pair of hooks run around every call of a Client, for
+// logging, auth refresh or metrics. Either may be nil.
+type Middleware struct {
+    // OnRequest runs before a call of method is sent, and returns the params
+    // to send in place of params.
+    OnRequest func(ctx context.Context, method string, params interface{}) interface{}
+    // OnResponse runs once a call of method ended, with its undecoded result
+    // or its error, and how long it took.
+    OnResponse func(ctx context.Context, method string, result json.RawMessage, err error, latency time.Duration)
+}
+
+// Client calls the spec's methods on the server at URL, running its
+// Middleware around each call in the order they were added. A nil HTTP
+// stands for http.DefaultClient.
+type Client struct {
+    URL        string
+    HTTP       *http.Client
+    Middleware []Middleware
+}
+
+// NewClient is a client of the server at url.
+func NewClient(url string) *Client {
+    return &Client{URL: url}
+}
+
+// Use adds m after the client's middleware, and returns the client.
+func (c *Client) Use(m Middleware) *Client {
+    c.Middleware = append(c.Middleware, m)
+    return c
+}
+
+// rawResponse is a JSON-RPC 2.0 response whose result is left undecoded.
+type rawResponse struct {
+    Result json.RawMessage `json:"result,omitempty"`
+    Error  *TrpError       `json:"error,omitempty"`
+}
+
+// call sends the call of method with params through the middleware and
+// decodes its result into result.
+func (c *Client) call(ctx context.Context, method string, params interface{}, result interface{}) error {
+    for _, m := range c.Middleware {
+        if m.OnRequest != nil {
+            params = m.OnRequest(ctx, method, params)
+        }
+    }
+    start := time.Now()
+    raw, err := c.send(ctx, method, params)
+    latency := time.Since(start)
+    for _, m := range c.Middleware {
+        if m.OnResponse != nil {
+            m.OnResponse(ctx, method, raw, err, latency)
+        }
+    }
+    if err != nil || len(raw) == 0 {
+        return err
+    }
+    if err := json.Unmarshal(raw, result); err != nil {
+        return fmt.Errorf("%s: decoding the result: %w", method, err)
+    }
+    return nil
+}
+
+// send posts the call of method with params and returns its undecoded
+// result. Error objects are returned as a *TrpError.
+func (c *Client) send(ctx context.Context, method string, params interface{}) (json.RawMessage, error) {
+    body, err := post(ctx, c.HTTP, c.URL, Request[interface{}]{JSONRPC: "2.0", ID: 1, Method: method, Params: params})
+    if err != nil {
+        return nil, fmt.Errorf("%s: %w", method, err)
+    }
+    var response rawResponse
+    if err := json.Unmarshal(body, &response); err != nil {
+        return nil, fmt.Errorf("%s: decoding the response: %w", method, err)
+    }
+    if response.Error != nil {
+        return nil, response.Error
+    }
+    return response.Result, nil
+}
+
+// TrpResolve calls trp.resolve.
+func (c *Client) TrpResolve(ctx context.Context, params ResolveParams) (TxEnvelope, error) {
+    var result TxEnvelope
+    err := c.call(ctx, TrpResolve, params, &result)
+    return result, err
+}
+
+// TrpSubmit calls trp.submit.
+func (c *Client) TrpSubmit(ctx context.Context, params SubmitParams) (SubmitResponse, error) {
+    var result SubmitResponse
+    err := c.call(ctx, TrpSubmit, params, &result)
+    return result, err
+}
+
+// TrpCheckStatus calls trp.checkStatus.
+func (c *Client) TrpCheckStatus(ctx context.Context, params CheckStatusParams) (CheckStatusResponse, error) {
+    var result CheckStatusResponse
+    err := c.call(ctx, TrpCheckStatus, params, &result)
+    return result, err
+}
+
+// post sends request to url as JSON and reads the body of the response.
+// Error statuses whose body isn't JSON fail with the status.
+func post(ctx context.Context, client *http.Client, url string, request interface{}) ([]byte, error) {
+    payload, err := json.Marshal(request)
+    if err != nil {
+        return nil, err
+    }
+    req, err := http.NewRequestWithContext(ctx, http.MethodPost, url, bytes.NewReader(payload))
+    if err != nil {
+        return nil, err
+    }
+    req.Header.Set("Content-Type", "application/json")
+    if client == nil {
+        client = http.DefaultClient
+    }
+    resp, err := client.Do(req)
+    if err != nil {
+        return nil, err
+    }
+    defer resp.Body.Close()
+    body, err := io.ReadAll(resp.Body)
+    if err != nil {
+        return nil, err
+    }
+    if resp.StatusCode >= 300 && !json.Valid(body) {
+        return nil, fmt.Errorf("HTTP %s", resp.Status)
+    }
+    return body, nil
+}
@@ -0,0 +1,122 @@
+// Generated by cargo xtask gen --lang go
+package types
+
+import (
+    "encoding/base64"
+    "encoding/hex"
+    "errors"
+    "fmt"
+    "strings"
+)
+
+// TirVersions are the versions of the TIR the spec supports.
+var TirVersions = []string{"v1alpha8", "v1beta0"}
+
+// ErrInvalidTir is wrapped by the errors of TIR programs that servers
+// wouldn't resolve.
+var ErrInvalidTir = errors.New("invalid TIR program")
+
+func invalidTir(format string, args ...any) error {
+    return fmt.Errorf("%w: %s", ErrInvalidTir, fmt.Sprintf(format, args...))
+}
+
+func checkTirEncoding(encoding string) error {
+    if encoding != "hex" && encoding != "base64" {
+        return invalidTir("unsupported encoding %q, expected hex or base64", encoding)
+    }
+    return nil
+}
+
+// DecodeBytecode returns the bytes text encodes as encoding, "hex" (either
+// case) or "base64" (the standard or URL-safe alphabet, padded or not).
+func DecodeBytecode(encoding, text string) ([]byte, error) {
+    if err := checkTirEncoding(encoding); err != nil {
+        return nil, err
+    }
+    if encoding == "hex" {
+        b, err := hex.DecodeString(text)
+        if err != nil {
+            return nil, invalidTir("the bytecode isn't valid hex: %v", err)
+        }
+        return b, nil
+    }
+    data := strings.TrimRight(text, "=")
+    if padding := len(text) - len(data); padding > 2 || (padding > 0 && len(text)%4 != 0) {
+        return nil, invalidTir("the bytecode isn't valid base64: misplaced padding at offset %d", len(data))
+    }
+    if strings.ContainsAny(data, "-_") {
+        data = strings.NewReplacer("-", "+", "_", "/").Replace(data)
+    }
+    b, err := base64.RawStdEncoding.DecodeString(data)
+    if err != nil {
+        return nil, invalidTir("the bytecode isn't valid base64: %v", err)
+    }
+    return b, nil
+}
+
+// EncodeBytecode returns b as text of encoding, hex being lowercase and
+// base64 standard and padded.
+func EncodeBytecode(encoding string, b []byte) (string, error) {
+    if err := checkTirEncoding(encoding); err != nil {
+        return "", err
+    }
+    if encoding == "hex" {
+        return hex.EncodeToString(b), nil
+    }
+    return base64.StdEncoding.EncodeToString(b), nil
+}
+
+// TirEnvelope is a TIR program of a supported version, with its bytecode
+// decoded. Build one with NewTirEnvelope or TirEnvelopeFromInfo, which fail
+// with ErrInvalidTir, and send it with Info.
+type TirEnvelope struct {
+    version  string
+    encoding string
+    bytecode []byte
+}
+
+// NewTirEnvelope returns the program of bytecode compiled to version, to be
+// sent as encoding.
+func NewTirEnvelope(version, encoding string, bytecode []byte) (TirEnvelope, error) {
+    supported := false
+    for _, v := range TirVersions {
+        supported = supported || v == version
+    }
+    if !supported {
+        return TirEnvelope{}, invalidTir("unsupported version %q, expected one of %s", version, strings.Join(TirVersions, ", "))
+    }
+    if err := checkTirEncoding(encoding); err != nil {
+        return TirEnvelope{}, err
+    }
+    if len(bytecode) == 0 {
+        return TirEnvelope{}, invalidTir("the bytecode is empty")
+    }
+    return TirEnvelope{version: version, encoding: encoding, bytecode: bytecode}, nil
+}
+
+// TirEnvelopeFromInfo checks and decodes the program of info.
+func TirEnvelopeFromInfo(info TirInfo) (TirEnvelope, error) {
+    b, err := DecodeBytecode(info.Encoding, info.Bytecode)
+    if err != nil {
+        return TirEnvelope{}, err
+    }
+    return NewTirEnvelope(info.Version, info.Encoding, b)
+}
+
+func (e TirEnvelope) Version() string { return e.version }
+
+func (e TirEnvelope) Encoding() string { return e.encoding }
+
+func (e TirEnvelope) Bytecode() []byte { return e.bytecode }
+
+// WithEncoding returns the same program, to be sent as encoding.
+func (e TirEnvelope) WithEncoding(encoding string) (TirEnvelope, error) {
+    return NewTirEnvelope(e.version, encoding, e.bytecode)
+}
+
+// Info returns the program as trp.resolve takes it.
+func (e TirEnvelope) Info() TirInfo {
+    // The encoding was checked when building the envelope.
+    bytecode, _ := EncodeBytecode(e.encoding, e.bytecode)
+    return TirInfo{Bytecode: bytecode, Encoding: e.encoding, Version: e.version}
+}
\ No newline at end of file
@@ -1,53 +1,127 @@
 // Generated by cargo xtask gen --lang go
 package types
 
+import (
+    "fmt"
+)
 
-type SubmitParams struct {
-    Tx BytesEnvelope `json:"tx"`
-    Witnesses []SubmitWitness `json:"witnesses"`
+// ValidationError reports a schema constraint violated by a value, as
+// returned by the Validate methods.
+type ValidationError struct {
+    // Path locates the offending value, e.g. "tx.payload" or "witnesses[0]".
+    Path    string
+    Message string
 }
 
+func (e *ValidationError) Error() string {
+    return e.Path + ": " + e.Message
+}
 
-type TxEnvelope struct {
-    Hash string `json:"hash"`
-    Tx string `json:"tx"`
+func nestValidationError(parent string, err error) error {
+    if verr, ok := err.(*ValidationError); ok {
+        return &ValidationError{Path: parent + "." + verr.Path, Message: verr.Message}
+    }
+    return err
 }
 
 
-type TirInfo struct {
-    Bytecode string `json:"bytecode"`
+type BytesEnvelope struct {
     Encoding string `json:"encoding"`
-    Version string `json:"version"`
+    Payload string `json:"payload"`
+}
+
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v BytesEnvelope) Validate() error {
+    return nil
 }
 
 
-type SubmitResponse struct {
-    Hash string `json:"hash"`
+type CheckStatusParams struct {
+    Hashes []string `json:"hashes"`
 }
 
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v CheckStatusParams) Validate() error {
+    {
+        value := v.Hashes
+        if len(value) < 1 {
+            return &ValidationError{Path: "hashes", Message: "must contain at least 1 items"}
+        }
+    }
+    return nil
+}
 
-type SearchSpaceDiagnostic struct {
-    ByAddressCount int64 `json:"by_address_count"`
-    ByAssetClassCount int64 `json:"by_asset_class_count"`
-    ByRefCount int64 `json:"by_ref_count"`
-    Matched []string `json:"matched"`
+
+type CheckStatusResponse struct {
+    Statuses map[string]TxStatus `json:"statuses"`
 }
 
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v CheckStatusResponse) Validate() error {
+    return nil
+}
 
-type BytesEnvelope struct {
-    Encoding string `json:"encoding"`
-    Payload string `json:"payload"`
+
+type InputNotResolvedDiagnostic struct {
+    Name string `json:"name"`
+    Query InputQueryDiagnostic `json:"query"`
+    SearchSpace SearchSpaceDiagnostic `json:"search_space"`
+}
+
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v InputNotResolvedDiagnostic) Validate() error {
+    {
+        value := v.Query
+        if err := value.Validate(); err != nil {
+            return nestValidationError("query", err)
+        }
+    }
+    {
+        value := v.SearchSpace
+        if err := value.Validate(); err != nil {
+            return nestValidationError("search_space", err)
+        }
+    }
+    return nil
 }
 
 
 type InputQueryDiagnostic struct {
-    Address string `json:"address"`
+    Address *string `json:"address,omitempty"`
     Collateral bool `json:"collateral"`
     MinAmount map[string]string `json:"min_amount"`
     Refs []string `json:"refs"`
     SupportMany bool `json:"support_many"`
 }
 
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v InputQueryDiagnostic) Validate() error {
+    if v.Address != nil {
+        value := *v.Address
+        if !IsAddress(value) {
+            return &ValidationError{Path: "address", Message: "must be a valid address"}
+        }
+    }
+    return nil
+}
+
+
+type MissingTxArgDiagnostic struct {
+    Key string `json:"key"`
+    Type string `json:"type"`
+}
+
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v MissingTxArgDiagnostic) Validate() error {
+    return nil
+}
+
 
 type ResolveParams struct {
     Args map[string]interface{} `json:"args"`
@@ -55,6 +129,69 @@ type ResolveParams struct {
     Tir TirInfo `json:"tir"`
 }
 
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v ResolveParams) Validate() error {
+    {
+        value := v.Tir
+        if err := value.Validate(); err != nil {
+            return nestValidationError("tir", err)
+        }
+    }
+    return nil
+}
+
+
+type SearchSpaceDiagnostic struct {
+    ByAddressCount *int64 `json:"by_address_count,omitempty"`
+    ByAssetClassCount *int64 `json:"by_asset_class_count,omitempty"`
+    ByRefCount *int64 `json:"by_ref_count,omitempty"`
+    Matched []string `json:"matched"`
+}
+
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v SearchSpaceDiagnostic) Validate() error {
+    return nil
+}
+
+
+type SubmitParams struct {
+    Tx BytesEnvelope `json:"tx"`
+    Witnesses []SubmitWitness `json:"witnesses"`
+}
+
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v SubmitParams) Validate() error {
+    {
+        value := v.Tx
+        if err := value.Validate(); err != nil {
+            return nestValidationError("tx", err)
+        }
+    }
+    {
+        value := v.Witnesses
+        for i, item := range value {
+            if err := item.Validate(); err != nil {
+                return nestValidationError(fmt.Sprintf("witnesses[%d]", i), err)
+            }
+        }
+    }
+    return nil
+}
+
+
+type SubmitResponse struct {
+    Hash string `json:"hash"`
+}
+
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v SubmitResponse) Validate() error {
+    return nil
+}
+
 
 type SubmitWitness struct {
     Key BytesEnvelope `json:"key"`
@@ -62,23 +199,52 @@ type SubmitWitness struct {
     Type string `json:"type"`
 }
 
+// Values of SubmitWitness.Type.
+const (
+    SubmitWitnessTypeVkey = "vkey"
+)
 
-type UnsupportedTirDiagnostic struct {
-    Expected string `json:"expected"`
-    Provided string `json:"provided"`
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v SubmitWitness) Validate() error {
+    {
+        value := v.Key
+        if err := value.Validate(); err != nil {
+            return nestValidationError("key", err)
+        }
+    }
+    {
+        value := v.Signature
+        if err := value.Validate(); err != nil {
+            return nestValidationError("signature", err)
+        }
+    }
+    return nil
 }
 
 
-type InputNotResolvedDiagnostic struct {
-    Name string `json:"name"`
-    Query InputQueryDiagnostic `json:"query"`
-    SearchSpace SearchSpaceDiagnostic `json:"search_space"`
+type TirInfo struct {
+    Bytecode string `json:"bytecode"`
+    Encoding string `json:"encoding"`
+    Version string `json:"version"`
 }
 
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v TirInfo) Validate() error {
+    return nil
+}
 
-type MissingTxArgDiagnostic struct {
-    Key string `json:"key"`
-    Type string `json:"type"`
+
+type TxEnvelope struct {
+    Hash string `json:"hash"`
+    Tx string `json:"tx"`
+}
+
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v TxEnvelope) Validate() error {
+    return nil
 }
 
 
@@ -86,3 +252,51 @@ type TxScriptFailureDiagnostic struct {
     Logs []string `json:"logs"`
 }
 
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v TxScriptFailureDiagnostic) Validate() error {
+    return nil
+}
+
+
+type TxStatus struct {
+    Confirmations int64 `json:"confirmations"`
+    Stage string `json:"stage"`
+}
+
+// Values of TxStatus.Stage.
+const (
+    TxStatusStagePending = "pending"
+    TxStatusStagePropagated = "propagated"
+    TxStatusStageAcknowledged = "acknowledged"
+    TxStatusStageConfirmed = "confirmed"
+    TxStatusStageFinalized = "finalized"
+    TxStatusStageDropped = "dropped"
+    TxStatusStageRolledBack = "rolledBack"
+    TxStatusStageUnknown = "unknown"
+)
+
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v TxStatus) Validate() error {
+    {
+        value := v.Confirmations
+        if value < 0 {
+            return &ValidationError{Path: "confirmations", Message: "must be at least 0"}
+        }
+    }
+    return nil
+}
+
+
+type UnsupportedTirDiagnostic struct {
+    Expected string `json:"expected"`
+    Provided string `json:"provided"`
+}
+
+// Validate checks the constraints of the spec that decoding alone does not
+// enforce.
+func (v UnsupportedTirDiagnostic) Validate() error {
+    return nil
+}
+
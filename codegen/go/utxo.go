@@ -0,0 +1,88 @@
+// Generated by cargo xtask gen --lang go
+package types
+
+import (
+    "bytes"
+    "encoding/hex"
+    "encoding/json"
+    "fmt"
+    "strconv"
+    "strings"
+)
+
+// TxIDLength is the length of a transaction hash in bytes.
+const TxIDLength = 32
+
+// UtxoRef references the output Index of the transaction hashed TxID. It
+// parses from the txid#index text form with ParseUtxoRef, and from JSON as
+// that string or as an object {"txid": ..., "index": ...}. It marshals
+// canonically, as the string with the hash in lowercase hex.
+type UtxoRef struct {
+    TxID  [TxIDLength]byte
+    Index uint32
+}
+
+// NewUtxoRef returns the reference to output index of the transaction
+// hashed txid, as hex.
+func NewUtxoRef(txid string, index uint32) (UtxoRef, error) {
+    if len(txid) != TxIDLength*2 {
+        return UtxoRef{}, fmt.Errorf("expected a transaction hash of %d hex digits, got %d", TxIDLength*2, len(txid))
+    }
+    ref := UtxoRef{Index: index}
+    if _, err := hex.Decode(ref.TxID[:], []byte(txid)); err != nil {
+        return UtxoRef{}, fmt.Errorf("the transaction hash isn't hex: %w", err)
+    }
+    return ref, nil
+}
+
+// ParseUtxoRef parses the txid#index text form of a UTxO reference.
+func ParseUtxoRef(text string) (UtxoRef, error) {
+    txid, index, ok := strings.Cut(text, "#")
+    if !ok {
+        return UtxoRef{}, fmt.Errorf("expected a txid#index UTxO reference, got %q", text)
+    }
+    // strconv.ParseUint takes no sign, unlike Atoi.
+    n, err := strconv.ParseUint(index, 10, 32)
+    if err != nil {
+        return UtxoRef{}, fmt.Errorf("the output index %q isn't a 32-bit unsigned integer", index)
+    }
+    return NewUtxoRef(txid, uint32(n))
+}
+
+// String returns the canonical txid#index text form of the reference.
+func (r UtxoRef) String() string {
+    return fmt.Sprintf("%s#%d", hex.EncodeToString(r.TxID[:]), r.Index)
+}
+
+func (r UtxoRef) MarshalJSON() ([]byte, error) {
+    return json.Marshal(r.String())
+}
+
+func (r *UtxoRef) UnmarshalJSON(data []byte) error {
+    var parsed UtxoRef
+    var err error
+    if bytes.HasPrefix(bytes.TrimSpace(data), []byte("{")) {
+        var object struct {
+            TxID  *string `json:"txid"`
+            Index *uint32 `json:"index"`
+        }
+        if err := json.Unmarshal(data, &object); err != nil {
+            return err
+        }
+        if object.TxID == nil || object.Index == nil {
+            return fmt.Errorf("a UTxO reference object needs txid and index")
+        }
+        parsed, err = NewUtxoRef(*object.TxID, *object.Index)
+    } else {
+        var text string
+        if err := json.Unmarshal(data, &text); err != nil {
+            return err
+        }
+        parsed, err = ParseUtxoRef(text)
+    }
+    if err != nil {
+        return err
+    }
+    *r = parsed
+    return nil
+}
\ No newline at end of file
@@ -0,0 +1,131 @@
+(* Generated by cargo xtask gen --lang ocaml *)
+
+(* Components often share field names. *)
+[@@@warning "-30"]
+
+(* Objects whose values share one type, as key/value pairs. *)
+type 'a string_map = (string * 'a) list
+
+let string_map_to_yojson value_to_yojson map =
+  `Assoc (List.map (fun (key, value) -> (key, value_to_yojson value)) map)
+
+let string_map_of_yojson value_of_yojson = function
+  | `Assoc pairs ->
+      List.fold_right
+        (fun (key, json) acc ->
+          match (acc, value_of_yojson json) with
+          | Ok rest, Ok value -> Ok ((key, value) :: rest)
+          | (Error _ as error), _ -> error
+          | _, Error error -> Error error)
+        pairs (Ok [])
+  | _ -> Error "expected an object"
+
+(* Decodes [json] with the first of [decoders] accepting it. *)
+let first_of name decoders json =
+  let rec go = function
+    | [] -> Error (name ^ ": no option matched")
+    | decode :: rest -> (
+        match decode json with Ok _ as ok -> ok | Error _ -> go rest)
+  in
+  go decoders
+
+type bytes_envelope = {
+  encoding : string;
+  payload : string;
+}
+[@@deriving yojson { strict = false }]
+
+type check_status_params = {
+  hashes : string list;
+}
+[@@deriving yojson { strict = false }]
+
+type tx_status = {
+  confirmations : int;
+  stage : string;
+}
+[@@deriving yojson { strict = false }]
+
+type check_status_response = {
+  statuses : tx_status string_map;
+}
+[@@deriving yojson { strict = false }]
+
+type input_query_diagnostic = {
+  address : string option [@default None];
+  collateral : bool;
+  min_amount : string string_map;
+  refs : string list;
+  support_many : bool;
+}
+[@@deriving yojson { strict = false }]
+
+type search_space_diagnostic = {
+  by_address_count : int option [@default None];
+  by_asset_class_count : int option [@default None];
+  by_ref_count : int option [@default None];
+  matched : string list;
+}
+[@@deriving yojson { strict = false }]
+
+type input_not_resolved_diagnostic = {
+  name : string;
+  query : input_query_diagnostic;
+  search_space : search_space_diagnostic;
+}
+[@@deriving yojson { strict = false }]
+
+type missing_tx_arg_diagnostic = {
+  key : string;
+  type_ : string [@key "type"];
+}
+[@@deriving yojson { strict = false }]
+
+type tir_info = {
+  bytecode : string;
+  encoding : string;
+  version : string;
+}
+[@@deriving yojson { strict = false }]
+
+type resolve_params = {
+  args : Yojson.Safe.t;
+  env : Yojson.Safe.t;
+  tir : tir_info;
+}
+[@@deriving yojson { strict = false }]
+
+type submit_witness = {
+  key : bytes_envelope;
+  signature : bytes_envelope;
+  type_ : string [@key "type"];
+}
+[@@deriving yojson { strict = false }]
+
+type submit_params = {
+  tx : bytes_envelope;
+  witnesses : submit_witness list;
+}
+[@@deriving yojson { strict = false }]
+
+type submit_response = {
+  hash : string;
+}
+[@@deriving yojson { strict = false }]
+
+type tx_envelope = {
+  hash : string;
+  tx : string;
+}
+[@@deriving yojson { strict = false }]
+
+type tx_script_failure_diagnostic = {
+  logs : string list;
+}
+[@@deriving yojson { strict = false }]
+
+type unsupported_tir_diagnostic = {
+  expected : string;
+  provided : string;
+}
+[@@deriving yojson { strict = false }]
@@ -0,0 +1,38 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class BytesEnvelope implements \JsonSerializable
+{
+    public function __construct(
+        public string $encoding,
+        public string $payload,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            encoding: $data['encoding'] ?? throw new \InvalidArgumentException('missing field encoding'),
+            payload: $data['payload'] ?? throw new \InvalidArgumentException('missing field payload'),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'encoding' => $this->encoding,
+            'payload' => $this->payload,
+        ];
+        return (object) $data;
+    }
+}
@@ -0,0 +1,38 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class CheckStatusParams implements \JsonSerializable
+{
+    /**
+     * @param list<string> $hashes
+     */
+    public function __construct(
+        public array $hashes,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            hashes: $data['hashes'] ?? throw new \InvalidArgumentException('missing field hashes'),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'hashes' => $this->hashes,
+        ];
+        return (object) $data;
+    }
+}
@@ -0,0 +1,38 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class CheckStatusResponse implements \JsonSerializable
+{
+    /**
+     * @param array<string, TxStatus> $statuses
+     */
+    public function __construct(
+        public array $statuses,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            statuses: array_map(static fn ($item) => TxStatus::fromArray($item), $data['statuses'] ?? throw new \InvalidArgumentException('missing field statuses')),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'statuses' => (object) $this->statuses,
+        ];
+        return (object) $data;
+    }
+}
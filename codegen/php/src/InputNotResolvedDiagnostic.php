@@ -0,0 +1,41 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class InputNotResolvedDiagnostic implements \JsonSerializable
+{
+    public function __construct(
+        public string $name,
+        public InputQueryDiagnostic $query,
+        public SearchSpaceDiagnostic $searchSpace,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            name: $data['name'] ?? throw new \InvalidArgumentException('missing field name'),
+            query: InputQueryDiagnostic::fromArray($data['query'] ?? throw new \InvalidArgumentException('missing field query')),
+            searchSpace: SearchSpaceDiagnostic::fromArray($data['search_space'] ?? throw new \InvalidArgumentException('missing field search_space')),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'name' => $this->name,
+            'query' => $this->query,
+            'search_space' => $this->searchSpace,
+        ];
+        return (object) $data;
+    }
+}
@@ -0,0 +1,53 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class InputQueryDiagnostic implements \JsonSerializable
+{
+    /**
+     * @param array<string, string> $minAmount
+     * @param list<string> $refs
+     */
+    public function __construct(
+        public bool $collateral,
+        public array $minAmount,
+        public array $refs,
+        public bool $supportMany,
+        public ?string $address = null,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            collateral: $data['collateral'] ?? throw new \InvalidArgumentException('missing field collateral'),
+            minAmount: $data['min_amount'] ?? throw new \InvalidArgumentException('missing field min_amount'),
+            refs: $data['refs'] ?? throw new \InvalidArgumentException('missing field refs'),
+            supportMany: $data['support_many'] ?? throw new \InvalidArgumentException('missing field support_many'),
+            address: $data['address'] ?? null,
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'collateral' => $this->collateral,
+            'min_amount' => (object) $this->minAmount,
+            'refs' => $this->refs,
+            'support_many' => $this->supportMany,
+        ];
+        if ($this->address !== null) {
+            $data['address'] = $this->address;
+        }
+        return (object) $data;
+    }
+}
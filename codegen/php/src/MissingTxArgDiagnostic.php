@@ -0,0 +1,38 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class MissingTxArgDiagnostic implements \JsonSerializable
+{
+    public function __construct(
+        public string $key,
+        public string $type,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            key: $data['key'] ?? throw new \InvalidArgumentException('missing field key'),
+            type: $data['type'] ?? throw new \InvalidArgumentException('missing field type'),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'key' => $this->key,
+            'type' => $this->type,
+        ];
+        return (object) $data;
+    }
+}
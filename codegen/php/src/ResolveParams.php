@@ -0,0 +1,45 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class ResolveParams implements \JsonSerializable
+{
+    /**
+     * @param array<string, mixed> $args
+     * @param array<string, mixed> $env
+     */
+    public function __construct(
+        public array $args,
+        public array $env,
+        public TirInfo $tir,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            args: $data['args'] ?? throw new \InvalidArgumentException('missing field args'),
+            env: $data['env'] ?? throw new \InvalidArgumentException('missing field env'),
+            tir: TirInfo::fromArray($data['tir'] ?? throw new \InvalidArgumentException('missing field tir')),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'args' => (object) $this->args,
+            'env' => (object) $this->env,
+            'tir' => $this->tir,
+        ];
+        return (object) $data;
+    }
+}
@@ -0,0 +1,53 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class SearchSpaceDiagnostic implements \JsonSerializable
+{
+    /**
+     * @param list<string> $matched
+     */
+    public function __construct(
+        public array $matched,
+        public ?int $byAddressCount = null,
+        public ?int $byAssetClassCount = null,
+        public ?int $byRefCount = null,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            matched: $data['matched'] ?? throw new \InvalidArgumentException('missing field matched'),
+            byAddressCount: $data['by_address_count'] ?? null,
+            byAssetClassCount: $data['by_asset_class_count'] ?? null,
+            byRefCount: $data['by_ref_count'] ?? null,
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'matched' => $this->matched,
+        ];
+        if ($this->byAddressCount !== null) {
+            $data['by_address_count'] = $this->byAddressCount;
+        }
+        if ($this->byAssetClassCount !== null) {
+            $data['by_asset_class_count'] = $this->byAssetClassCount;
+        }
+        if ($this->byRefCount !== null) {
+            $data['by_ref_count'] = $this->byRefCount;
+        }
+        return (object) $data;
+    }
+}
@@ -0,0 +1,41 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class SubmitParams implements \JsonSerializable
+{
+    /**
+     * @param list<SubmitWitness> $witnesses
+     */
+    public function __construct(
+        public BytesEnvelope $tx,
+        public array $witnesses,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            tx: BytesEnvelope::fromArray($data['tx'] ?? throw new \InvalidArgumentException('missing field tx')),
+            witnesses: array_map(static fn ($item) => SubmitWitness::fromArray($item), $data['witnesses'] ?? throw new \InvalidArgumentException('missing field witnesses')),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'tx' => $this->tx,
+            'witnesses' => $this->witnesses,
+        ];
+        return (object) $data;
+    }
+}
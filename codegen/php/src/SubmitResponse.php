@@ -0,0 +1,35 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class SubmitResponse implements \JsonSerializable
+{
+    public function __construct(
+        public string $hash,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            hash: $data['hash'] ?? throw new \InvalidArgumentException('missing field hash'),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'hash' => $this->hash,
+        ];
+        return (object) $data;
+    }
+}
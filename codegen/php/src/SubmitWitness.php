@@ -0,0 +1,44 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class SubmitWitness implements \JsonSerializable
+{
+    /**
+     * @param 'vkey' $type
+     */
+    public function __construct(
+        public BytesEnvelope $key,
+        public BytesEnvelope $signature,
+        public string $type,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            key: BytesEnvelope::fromArray($data['key'] ?? throw new \InvalidArgumentException('missing field key')),
+            signature: BytesEnvelope::fromArray($data['signature'] ?? throw new \InvalidArgumentException('missing field signature')),
+            type: $data['type'] ?? throw new \InvalidArgumentException('missing field type'),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'key' => $this->key,
+            'signature' => $this->signature,
+            'type' => $this->type,
+        ];
+        return (object) $data;
+    }
+}
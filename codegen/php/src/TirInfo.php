@@ -0,0 +1,41 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class TirInfo implements \JsonSerializable
+{
+    public function __construct(
+        public string $bytecode,
+        public string $encoding,
+        public string $version,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            bytecode: $data['bytecode'] ?? throw new \InvalidArgumentException('missing field bytecode'),
+            encoding: $data['encoding'] ?? throw new \InvalidArgumentException('missing field encoding'),
+            version: $data['version'] ?? throw new \InvalidArgumentException('missing field version'),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'bytecode' => $this->bytecode,
+            'encoding' => $this->encoding,
+            'version' => $this->version,
+        ];
+        return (object) $data;
+    }
+}
@@ -0,0 +1,38 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class TxScriptFailureDiagnostic implements \JsonSerializable
+{
+    /**
+     * @param list<string> $logs
+     */
+    public function __construct(
+        public array $logs,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            logs: $data['logs'] ?? throw new \InvalidArgumentException('missing field logs'),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'logs' => $this->logs,
+        ];
+        return (object) $data;
+    }
+}
@@ -0,0 +1,41 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class TxStatus implements \JsonSerializable
+{
+    /**
+     * @param 'pending'|'propagated'|'acknowledged'|'confirmed'|'finalized'|'dropped'|'rolledBack'|'unknown' $stage
+     */
+    public function __construct(
+        public int $confirmations,
+        public string $stage,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            confirmations: $data['confirmations'] ?? throw new \InvalidArgumentException('missing field confirmations'),
+            stage: $data['stage'] ?? throw new \InvalidArgumentException('missing field stage'),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'confirmations' => $this->confirmations,
+            'stage' => $this->stage,
+        ];
+        return (object) $data;
+    }
+}
@@ -0,0 +1,38 @@
+<?php
+// Generated by cargo xtask gen --lang php
+
+declare(strict_types=1);
+
+namespace Tx3\Trp;
+
+final class UnsupportedTirDiagnostic implements \JsonSerializable
+{
+    public function __construct(
+        public string $expected,
+        public string $provided,
+    ) {
+    }
+
+    /**
+     * Builds an instance from a decoded JSON object, as returned by
+     * `json_decode($json, true)`.
+     *
+     * @param array<string, mixed> $data
+     */
+    public static function fromArray(array $data): self
+    {
+        return new self(
+            expected: $data['expected'] ?? throw new \InvalidArgumentException('missing field expected'),
+            provided: $data['provided'] ?? throw new \InvalidArgumentException('missing field provided'),
+        );
+    }
+
+    public function jsonSerialize(): mixed
+    {
+        $data = [
+            'expected' => $this->expected,
+            'provided' => $this->provided,
+        ];
+        return (object) $data;
+    }
+}
@@ -0,0 +1,12 @@
+# Generated by cargo xtask gen --lang python
+from . import address, constants, error_codes, errors, tir, types, utxo
+
+__all__ = [
+    "address",
+    "constants",
+    "error_codes",
+    "errors",
+    "tir",
+    "types",
+    "utxo",
+]
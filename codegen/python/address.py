@@ -0,0 +1,204 @@
+# Generated by cargo xtask gen --lang python
+from __future__ import annotations
+
+import re
+from typing import List, Optional, Union
+
+__all__ = [
+    "MAINNET",
+    "InvalidAddressError",
+    "Address",
+    "is_address",
+]
+
+_BECH32_CHARSET = "qpzry9x8gf2tvdw0s3jn54khce6mua7l"
+_BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+_GENERATORS = (0x3B6A57B2, 0x26508E6D, 0x1EA119FA, 0x3D4233DD, 0x2A1462B3)
+
+# Network id of mainnet; every other id is a test network.
+MAINNET = 1
+
+
+class InvalidAddressError(ValueError):
+    """Text or bytes that aren't a valid address."""
+
+
+class Address:
+    """A Cardano address: its header byte, telling its kind and network,
+    then its payload.
+
+    Fields marked with ``format: address`` hold its text form, bech32 for
+    Shelley addresses (``addr1…``, ``stake_test1…``) and base58 for Byron
+    ones, which ``str(address)`` gives back.
+    """
+
+    def __init__(self, data: bytes) -> None:
+        """The address made of ``data``, checked to be of a known kind."""
+        if not data:
+            raise InvalidAddressError("the address is empty")
+        kind = data[0] >> 4
+        if kind <= 3:
+            valid = len(data) == 57
+        elif kind <= 5:
+            valid = len(data) >= 32
+        elif kind <= 7 or kind >= 14:
+            valid = len(data) == 29
+        elif kind == 8:
+            valid = data.startswith(b"\x82\xd8\x18")
+        else:
+            raise InvalidAddressError(f"unknown address header {data[0]:#04x}")
+        if not valid:
+            raise InvalidAddressError(f"an address of this kind can't be {len(data)} bytes long")
+        self.data = bytes(data)
+
+    @classmethod
+    def parse(cls, text: str) -> Address:
+        """Parses the bech32 or base58 text form of an address."""
+        prefix, separator, data = text.rpartition("1")
+        # Byron addresses are mixed-case, unlike bech32 prefixes.
+        if separator and re.fullmatch(r"[a-z0-9_]+|[A-Z0-9_]+", prefix):
+            address = cls(_bech32_decode(prefix.lower(), data))
+            if address.prefix != prefix.lower():
+                raise InvalidAddressError(f"expected the address prefix {address.prefix}, got {prefix}")
+            return address
+        address = cls(_base58_decode(text))
+        if not address.is_byron:
+            raise InvalidAddressError("the address is neither bech32 nor base58")
+        return address
+
+    @classmethod
+    def from_hex(cls, text: str) -> Address:
+        """The address whose bytes ``text`` encodes."""
+        try:
+            data = bytes.fromhex(text)
+        except ValueError as error:
+            raise InvalidAddressError("the address isn't hex") from error
+        return cls(data)
+
+    @property
+    def is_byron(self) -> bool:
+        return self.data[0] >> 4 == 8
+
+    @property
+    def is_reward(self) -> bool:
+        """Whether the address names a stake credential rather than a payment one."""
+        return self.data[0] >> 4 >= 14
+
+    @property
+    def network_id(self) -> Optional[int]:
+        """The network id of a Shelley address; Byron addresses don't tell it in their header."""
+        return None if self.is_byron else self.data[0] & 0x0F
+
+    @property
+    def prefix(self) -> Optional[str]:
+        """The bech32 prefix of a Shelley address."""
+        network = self.network_id
+        if network is None:
+            return None
+        return ("stake" if self.is_reward else "addr") + ("" if network == MAINNET else "_test")
+
+    def to_hex(self) -> str:
+        return self.data.hex()
+
+    def to_bech32(self) -> Optional[str]:
+        """The bech32 form of a Shelley address."""
+        prefix = self.prefix
+        if prefix is None:
+            return None
+        values = _regroup(self.data, 8, 5, True)
+        values += _bech32_checksum(prefix, values)
+        return prefix + "1" + "".join(_BECH32_CHARSET[value] for value in values)
+
+    def __str__(self) -> str:
+        return self.to_bech32() or _base58_encode(self.data)
+
+    def __repr__(self) -> str:
+        return f"Address({str(self)!r})"
+
+    def __eq__(self, other: object) -> bool:
+        return isinstance(other, Address) and other.data == self.data
+
+    def __hash__(self) -> int:
+        return hash(self.data)
+
+
+def is_address(text: str) -> bool:
+    """Whether ``text`` is a valid address, as the models check the fields
+    marked with ``format: address``."""
+    try:
+        Address.parse(text)
+    except InvalidAddressError:
+        return False
+    return True
+
+
+def _bech32_polymod(values: List[int]) -> int:
+    checksum = 1
+    for value in values:
+        top = checksum >> 25
+        checksum = ((checksum & 0x1FFFFFF) << 5) ^ value
+        for i, generator in enumerate(_GENERATORS):
+            if (top >> i) & 1:
+                checksum ^= generator
+    return checksum
+
+
+def _bech32_prefix_values(prefix: str) -> List[int]:
+    return [ord(c) >> 5 for c in prefix] + [0] + [ord(c) & 31 for c in prefix]
+
+
+def _bech32_checksum(prefix: str, data: List[int]) -> List[int]:
+    polymod = _bech32_polymod(_bech32_prefix_values(prefix) + data + [0] * 6) ^ 1
+    return [(polymod >> (5 * (5 - i))) & 31 for i in range(6)]
+
+
+def _bech32_decode(prefix: str, data: str) -> bytes:
+    if len(data) < 6 or (data != data.lower() and data != data.upper()):
+        raise InvalidAddressError("the address isn't bech32")
+    values = [_BECH32_CHARSET.find(c) for c in data.lower()]
+    if -1 in values:
+        raise InvalidAddressError("the address isn't bech32")
+    if _bech32_polymod(_bech32_prefix_values(prefix) + values) != 1:
+        raise InvalidAddressError("the address checksum doesn't match")
+    return bytes(_regroup(values[:-6], 5, 8, False))
+
+
+def _regroup(values: Union[bytes, List[int]], source: int, target: int, pad: bool) -> List[int]:
+    """Regroups ``values`` of ``source`` bits into values of ``target``
+    bits, padding the last one with zeros if ``pad``, and else requiring
+    the leftover bits to be."""
+    acc = bits = 0
+    out: List[int] = []
+    mask = (1 << target) - 1
+    for value in values:
+        acc = (acc << source) | value
+        bits += source
+        while bits >= target:
+            bits -= target
+            out.append((acc >> bits) & mask)
+    if pad and bits:
+        out.append((acc << (target - bits)) & mask)
+    elif not pad and (bits >= source or acc & ((1 << bits) - 1)):
+        raise InvalidAddressError("the address isn't bech32")
+    return out
+
+
+def _base58_decode(text: str) -> bytes:
+    number = 0
+    for c in text:
+        digit = _BASE58_ALPHABET.find(c)
+        if digit < 0:
+            raise InvalidAddressError("the address is neither bech32 nor base58")
+        number = number * 58 + digit
+    zeros = len(text) - len(text.lstrip("1"))
+    return b"\x00" * zeros + number.to_bytes((number.bit_length() + 7) // 8, "big")
+
+
+def _base58_encode(data: bytes) -> str:
+    number = int.from_bytes(data, "big")
+    digits = ""
+    while number:
+        number, digit = divmod(number, 58)
+        digits = _BASE58_ALPHABET[digit] + digits
+    zeros = len(data) - len(data.lstrip(b"\x00"))
+    return "1" * zeros + digits
\ No newline at end of file
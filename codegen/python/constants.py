@@ -0,0 +1,26 @@
+# Generated by cargo xtask gen --lang python
+
+__all__ = [
+    "SPEC_VERSION",
+    "TRP_RESOLVE",
+    "TRP_RESOLVE_PARAMS",
+    "TRP_SUBMIT",
+    "TRP_SUBMIT_PARAMS",
+    "TRP_CHECK_STATUS",
+    "TRP_CHECK_STATUS_PARAMS",
+]
+
+# Version of the spec these bindings were generated from.
+SPEC_VERSION = "0.1.1"
+
+TRP_RESOLVE = "trp.resolve"
+# Params of trp.resolve in declared order.
+TRP_RESOLVE_PARAMS = ("tir", "args", "env")
+
+TRP_SUBMIT = "trp.submit"
+# Params of trp.submit in declared order.
+TRP_SUBMIT_PARAMS = ("tx", "witnesses")
+
+TRP_CHECK_STATUS = "trp.checkStatus"
+# Params of trp.checkStatus in declared order.
+TRP_CHECK_STATUS_PARAMS = ("hashes",)
@@ -0,0 +1,23 @@
+# Generated by cargo xtask gen --lang python
+from typing import Final, Tuple
+
+__all__ = [
+    "SPEC_VERSION",
+    "TRP_RESOLVE",
+    "TRP_RESOLVE_PARAMS",
+    "TRP_SUBMIT",
+    "TRP_SUBMIT_PARAMS",
+    "TRP_CHECK_STATUS",
+    "TRP_CHECK_STATUS_PARAMS",
+]
+
+SPEC_VERSION: Final = "0.1.1"
+
+TRP_RESOLVE: Final = "trp.resolve"
+TRP_RESOLVE_PARAMS: Final[Tuple[str, ...]]
+
+TRP_SUBMIT: Final = "trp.submit"
+TRP_SUBMIT_PARAMS: Final[Tuple[str, ...]]
+
+TRP_CHECK_STATUS: Final = "trp.checkStatus"
+TRP_CHECK_STATUS_PARAMS: Final[Tuple[str, ...]]
@@ -0,0 +1,141 @@
+# Generated by cargo xtask gen --lang python
+"""The JSON-RPC error codes of the protocol, split into ranges so that the
+errors of JSON-RPC itself, of transports and of the spec's methods never
+share a code."""
+
+from __future__ import annotations
+
+from typing import Any, Dict, Optional, Tuple
+
+from . import errors
+
+__all__ = [
+    "ERROR_CODE_RANGES",
+    "PARSE_ERROR",
+    "INVALID_REQUEST",
+    "METHOD_NOT_FOUND",
+    "INVALID_PARAMS",
+    "INTERNAL_ERROR",
+    "UPSTREAM_UNAVAILABLE",
+    "BATCHES_NOT_SUPPORTED",
+    "TOO_MANY_SUBSCRIPTIONS",
+    "OVERSIZED_REQUEST",
+    "OVERSIZED_RESPONSE",
+    "SERVER_IS_BUSY",
+    "TOO_BIG_BATCH_REQUEST",
+    "TOO_BIG_BATCH_RESPONSE",
+    "RATE_LIMITED",
+    "IDEMPOTENCY_CONFLICT",
+    "REQUEST_CANCELLED",
+    "APPLICATION_CODES",
+    "error_class",
+    "declared_error",
+    "reserved_error",
+]
+
+# First and last code of each class: protocol, transport, application.
+ERROR_CODE_RANGES: Dict[str, Tuple[int, int]] = {
+    # Errors of JSON-RPC itself, reserved by it.
+    "protocol": (-32768, -32100),
+    # Errors answered by servers, proxies and their JSON-RPC library, whatever the method.
+    "transport": (-32099, -32004),
+    # Errors the spec's methods declare.
+    "application": (-32003, -32000),
+}
+
+# "Parse error", a protocol error.
+PARSE_ERROR = -32700
+
+# "Invalid request", a protocol error.
+INVALID_REQUEST = -32600
+
+# "Method not found", a protocol error.
+METHOD_NOT_FOUND = -32601
+
+# "Invalid params", a protocol error.
+INVALID_PARAMS = -32602
+
+# "Internal error", a protocol error.
+INTERNAL_ERROR = -32603
+
+# "No upstream available", a transport error.
+UPSTREAM_UNAVAILABLE = -32004
+
+# "Batched requests are not supported", a transport error.
+BATCHES_NOT_SUPPORTED = -32005
+
+# "Too many subscriptions", a transport error.
+TOO_MANY_SUBSCRIPTIONS = -32006
+
+# "Request is too big", a transport error.
+OVERSIZED_REQUEST = -32007
+
+# "Response is too big", a transport error.
+OVERSIZED_RESPONSE = -32008
+
+# "Server is busy", a transport error.
+SERVER_IS_BUSY = -32009
+
+# "Batch request is too big", a transport error.
+TOO_BIG_BATCH_REQUEST = -32010
+
+# "Batch response is too big", a transport error.
+TOO_BIG_BATCH_RESPONSE = -32011
+
+# "Rate limit exceeded", a transport error.
+RATE_LIMITED = -32012
+
+# "Idempotency key conflict", a transport error.
+IDEMPOTENCY_CONFLICT = -32013
+
+# "Request cancelled", a transport error.
+REQUEST_CANCELLED = -32800
+
+# Codes of the errors the spec declares.
+APPLICATION_CODES: Tuple[int, ...] = (errors.UNSUPPORTED_TIR, errors.MISSING_TRANSACTION_ARGUMENT, errors.INPUT_NOT_RESOLVED, errors.TX_SCRIPT_FAILURE,)
+
+_MESSAGES: Dict[int, str] = {
+    errors.UNSUPPORTED_TIR: "Unsupported TIR",
+    errors.MISSING_TRANSACTION_ARGUMENT: "Missing transaction argument",
+    errors.INPUT_NOT_RESOLVED: "Input not resolved",
+    errors.TX_SCRIPT_FAILURE: "Tx script failure",
+}
+
+_OUTSIDE_RANGES: Dict[int, str] = {
+    REQUEST_CANCELLED: "transport",
+}
+
+
+def error_class(code: int) -> Optional[str]:
+    """The class of ``code``, ``None`` for codes outside of every range."""
+    if code in _OUTSIDE_RANGES:
+        return _OUTSIDE_RANGES[code]
+    for name, (start, end) in ERROR_CODE_RANGES.items():
+        if start <= code <= end:
+            return name
+    return None
+
+
+def _error(code: int, message: str, data: Any) -> Dict[str, Any]:
+    error: Dict[str, Any] = {"code": code, "message": message}
+    if data is not None:
+        error["data"] = data
+    return error
+
+
+def declared_error(code: int, data: Any = None) -> Dict[str, Any]:
+    """The error object of the error the spec declares for ``code``, with its
+    declared message. Raises ``ValueError`` for codes the spec doesn't
+    declare."""
+    if code not in _MESSAGES:
+        raise ValueError(f"{code} is not an error code the spec declares")
+    return _error(code, _MESSAGES[code], data)
+
+
+def reserved_error(code: int, message: str, data: Any = None) -> Dict[str, Any]:
+    """The error object of a protocol or transport error, as answered whatever
+    the method. Raises ``ValueError`` for other codes: errors of the methods
+    must be declared by the spec, and built with ``declared_error``."""
+    if error_class(code) not in ("protocol", "transport"):
+        raise ValueError(f"{code} is not a protocol or transport error code")
+    return _error(code, message, data)
\ No newline at end of file
@@ -0,0 +1,51 @@
+# Generated by cargo xtask gen --lang python
+from typing import Any, Dict, Final, Optional, Tuple
+
+__all__ = [
+    "ERROR_CODE_RANGES",
+    "PARSE_ERROR",
+    "INVALID_REQUEST",
+    "METHOD_NOT_FOUND",
+    "INVALID_PARAMS",
+    "INTERNAL_ERROR",
+    "UPSTREAM_UNAVAILABLE",
+    "BATCHES_NOT_SUPPORTED",
+    "TOO_MANY_SUBSCRIPTIONS",
+    "OVERSIZED_REQUEST",
+    "OVERSIZED_RESPONSE",
+    "SERVER_IS_BUSY",
+    "TOO_BIG_BATCH_REQUEST",
+    "TOO_BIG_BATCH_RESPONSE",
+    "RATE_LIMITED",
+    "IDEMPOTENCY_CONFLICT",
+    "REQUEST_CANCELLED",
+    "APPLICATION_CODES",
+    "error_class",
+    "declared_error",
+    "reserved_error",
+]
+
+ERROR_CODE_RANGES: Dict[str, Tuple[int, int]]
+
+PARSE_ERROR: Final = -32700
+INVALID_REQUEST: Final = -32600
+METHOD_NOT_FOUND: Final = -32601
+INVALID_PARAMS: Final = -32602
+INTERNAL_ERROR: Final = -32603
+UPSTREAM_UNAVAILABLE: Final = -32004
+BATCHES_NOT_SUPPORTED: Final = -32005
+TOO_MANY_SUBSCRIPTIONS: Final = -32006
+OVERSIZED_REQUEST: Final = -32007
+OVERSIZED_RESPONSE: Final = -32008
+SERVER_IS_BUSY: Final = -32009
+TOO_BIG_BATCH_REQUEST: Final = -32010
+TOO_BIG_BATCH_RESPONSE: Final = -32011
+RATE_LIMITED: Final = -32012
+IDEMPOTENCY_CONFLICT: Final = -32013
+REQUEST_CANCELLED: Final = -32800
+
+APPLICATION_CODES: Tuple[int, ...]
+
+def error_class(code: int) -> Optional[str]: ...
+def declared_error(code: int, data: Any = None) -> Dict[str, Any]: ...
+def reserved_error(code: int, message: str, data: Any = None) -> Dict[str, Any]: ...
\ No newline at end of file
@@ -0,0 +1,98 @@
+# Generated by cargo xtask gen --lang python
+from __future__ import annotations
+
+from typing import Any, ClassVar, Dict, Mapping, Optional, Type
+
+from pydantic import BaseModel, ValidationError
+
+from .types import UnsupportedTirDiagnostic, MissingTxArgDiagnostic, InputNotResolvedDiagnostic, TxScriptFailureDiagnostic
+
+
+__all__ = [
+    "UNSUPPORTED_TIR",
+    "MISSING_TRANSACTION_ARGUMENT",
+    "INPUT_NOT_RESOLVED",
+    "TX_SCRIPT_FAILURE",
+    "TrpError",
+    "UnsupportedTirError",
+    "MissingTransactionArgumentError",
+    "InputNotResolvedError",
+    "TxScriptFailureError",
+    "ERRORS_BY_CODE",
+]
+
+# Code of the "Unsupported TIR" error.
+UNSUPPORTED_TIR = -32000
+
+# Code of the "Missing transaction argument" error.
+MISSING_TRANSACTION_ARGUMENT = -32001
+
+# Code of the "Input not resolved" error.
+INPUT_NOT_RESOLVED = -32002
+
+# Code of the "Tx script failure" error.
+TX_SCRIPT_FAILURE = -32003
+
+
+class TrpError(Exception):
+    """Error object of a failed call.
+
+    Declared errors are raised as the subclass registered for their code in
+    ``ERRORS_BY_CODE``. Subclasses declaring a ``data_model`` parse ``data``
+    into it, and keep it as is when it doesn't match.
+    """
+
+    retryable: ClassVar[bool] = False
+    data_model: ClassVar[Optional[Type[BaseModel]]] = None
+
+    def __init__(self, code: int, message: str, data: Any = None) -> None:
+        super().__init__(f"{message} ({code})")
+        self.code = code
+        self.message = message
+        self.data = data
+        if self.data_model is not None and data is not None:
+            try:
+                self.data = self.data_model.model_validate(data)
+            except ValidationError:
+                pass
+
+    @classmethod
+    def from_error(cls, error: Mapping[str, Any]) -> "TrpError":
+        """Builds the exception matching a JSON-RPC error object."""
+        code = error["code"]
+        exception = ERRORS_BY_CODE.get(code, TrpError)
+        return exception(code, error.get("message", ""), error.get("data"))
+
+
+class UnsupportedTirError(TrpError):
+    """Unsupported TIR (-32000)"""
+
+    data_model = UnsupportedTirDiagnostic
+
+
+class MissingTransactionArgumentError(TrpError):
+    """Missing transaction argument (-32001)"""
+
+    data_model = MissingTxArgDiagnostic
+
+
+class InputNotResolvedError(TrpError):
+    """Input not resolved (-32002)"""
+
+    retryable = True
+
+    data_model = InputNotResolvedDiagnostic
+
+
+class TxScriptFailureError(TrpError):
+    """Tx script failure (-32003)"""
+
+    data_model = TxScriptFailureDiagnostic
+
+
+ERRORS_BY_CODE: Dict[int, Type[TrpError]] = {
+    UNSUPPORTED_TIR: UnsupportedTirError,
+    MISSING_TRANSACTION_ARGUMENT: MissingTransactionArgumentError,
+    INPUT_NOT_RESOLVED: InputNotResolvedError,
+    TX_SCRIPT_FAILURE: TxScriptFailureError,
+}
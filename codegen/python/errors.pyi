@@ -0,0 +1,48 @@
+# Generated by cargo xtask gen --lang python
+from typing import Any, ClassVar, Dict, Final, Mapping, Optional, Type
+
+from pydantic import BaseModel
+
+from .types import UnsupportedTirDiagnostic, MissingTxArgDiagnostic, InputNotResolvedDiagnostic, TxScriptFailureDiagnostic
+
+__all__ = [
+    "UNSUPPORTED_TIR",
+    "MISSING_TRANSACTION_ARGUMENT",
+    "INPUT_NOT_RESOLVED",
+    "TX_SCRIPT_FAILURE",
+    "TrpError",
+    "UnsupportedTirError",
+    "MissingTransactionArgumentError",
+    "InputNotResolvedError",
+    "TxScriptFailureError",
+    "ERRORS_BY_CODE",
+]
+
+UNSUPPORTED_TIR: Final = -32000
+MISSING_TRANSACTION_ARGUMENT: Final = -32001
+INPUT_NOT_RESOLVED: Final = -32002
+TX_SCRIPT_FAILURE: Final = -32003
+
+class TrpError(Exception):
+    retryable: ClassVar[bool]
+    data_model: ClassVar[Optional[Type[BaseModel]]]
+    code: int
+    message: str
+    data: Any
+    def __init__(self, code: int, message: str, data: Any = None) -> None: ...
+    @classmethod
+    def from_error(cls, error: Mapping[str, Any]) -> TrpError: ...
+
+class UnsupportedTirError(TrpError):
+    data_model: ClassVar[Type[UnsupportedTirDiagnostic]]
+
+class MissingTransactionArgumentError(TrpError):
+    data_model: ClassVar[Type[MissingTxArgDiagnostic]]
+
+class InputNotResolvedError(TrpError):
+    data_model: ClassVar[Type[InputNotResolvedDiagnostic]]
+
+class TxScriptFailureError(TrpError):
+    data_model: ClassVar[Type[TxScriptFailureDiagnostic]]
+
+ERRORS_BY_CODE: Dict[int, Type[TrpError]]
@@ -0,0 +1,125 @@
+# Generated by cargo xtask gen --lang python
+from __future__ import annotations
+
+import base64
+import binascii
+import re
+from dataclasses import dataclass
+from typing import Any, Mapping, Tuple, Union
+
+from .types import TirInfo
+
+__all__ = [
+    "TIR_VERSIONS",
+    "TIR_ENCODINGS",
+    "InvalidTirError",
+    "decode_bytecode",
+    "encode_bytecode",
+    "TirEnvelope",
+]
+
+# Versions of the TIR the spec supports.
+TIR_VERSIONS: Tuple[str, ...] = ("v1alpha8", "v1beta0",)
+
+# Encodings of TIR bytecode. Base64 decodes from the standard or URL-safe
+# alphabet, padded or not, and encodes as standard padded base64.
+TIR_ENCODINGS: Tuple[str, ...] = ("hex", "base64")
+
+_HEX = re.compile(r"[0-9a-fA-F]")
+_BASE64 = re.compile(r"[A-Za-z0-9+/_-]")
+
+
+class InvalidTirError(ValueError):
+    """A TIR program that servers wouldn't resolve."""
+
+
+def _invalid(encoding: str, offset: int) -> InvalidTirError:
+    return InvalidTirError(f"the TIR bytecode isn't valid {encoding} at offset {offset}")
+
+
+def _check_encoding(encoding: str) -> None:
+    if encoding not in TIR_ENCODINGS:
+        raise InvalidTirError(f"unsupported TIR encoding {encoding!r}, expected hex or base64")
+
+
+def decode_bytecode(encoding: str, text: str) -> bytes:
+    """The bytes ``text`` encodes, lowercase or uppercase hex alike."""
+    _check_encoding(encoding)
+    if encoding == "hex":
+        for offset in range(len(text) + len(text) % 2):
+            if not _HEX.fullmatch(text[offset : offset + 1]):
+                raise _invalid(encoding, offset)
+        return bytes.fromhex(text)
+    data = text.rstrip("=")
+    for offset, char in enumerate(data):
+        if not _BASE64.fullmatch(char):
+            raise _invalid(encoding, offset)
+    if len(data) % 4 == 1:
+        raise _invalid(encoding, len(data) - 1)
+    padding = len(text) - len(data)
+    if padding and (padding > 2 or len(text) % 4):
+        raise _invalid(encoding, len(data))
+    data = data.replace("-", "+").replace("_", "/")
+    try:
+        return base64.b64decode(data + "=" * (-len(data) % 4), validate=True)
+    except binascii.Error as error:
+        raise _invalid(encoding, 0) from error
+
+
+def encode_bytecode(encoding: str, data: bytes) -> str:
+    """``data`` as text of ``encoding``, hex being lowercase."""
+    _check_encoding(encoding)
+    if encoding == "hex":
+        return data.hex()
+    return base64.b64encode(data).decode("ascii")
+
+
+@dataclass(frozen=True)
+class TirEnvelope:
+    """A TIR program of a supported version, with its bytecode decoded.
+
+    It validates itself, raising ``InvalidTirError`` for unsupported
+    versions and encodings and for empty bytecode. ``from_info`` checks and
+    decodes the ``TirInfo`` of ``trp.resolve`` params, and ``to_info``
+    encodes it back as ``encoding`` tells.
+    """
+
+    version: str
+    bytecode: bytes
+    encoding: str = "hex"
+
+    def __post_init__(self) -> None:
+        if self.version not in TIR_VERSIONS:
+            raise InvalidTirError(
+                f"unsupported TIR version {self.version!r}, expected one of {', '.join(TIR_VERSIONS)}"
+            )
+        _check_encoding(self.encoding)
+        if not isinstance(self.bytecode, (bytes, bytearray)):
+            raise InvalidTirError(f"expected the TIR bytecode as bytes, got {type(self.bytecode).__name__}")
+        if not self.bytecode:
+            raise InvalidTirError("the TIR bytecode is empty")
+        object.__setattr__(self, "bytecode", bytes(self.bytecode))
+
+    @classmethod
+    def from_info(cls, info: Union[TirInfo, Mapping[str, Any]]) -> TirEnvelope:
+        """Checks and decodes the program of ``info``, a model or its JSON
+        object form."""
+        if isinstance(info, TirInfo):
+            info = info.model_dump(mode="json")
+        encoding = info["encoding"]
+        _check_encoding(encoding)
+        return cls(info["version"], decode_bytecode(encoding, info["bytecode"]), encoding)
+
+    def with_encoding(self, encoding: str) -> TirEnvelope:
+        """The same program, to be sent as ``encoding``."""
+        return TirEnvelope(self.version, self.bytecode, encoding)
+
+    def to_info(self) -> TirInfo:
+        """The program as ``trp.resolve`` takes it."""
+        return TirInfo.model_validate(
+            {
+                "bytecode": encode_bytecode(self.encoding, self.bytecode),
+                "encoding": self.encoding,
+                "version": self.version,
+            }
+        )
\ No newline at end of file
@@ -1,89 +1,181 @@
 # Generated by cargo xtask gen --lang python
-from dataclasses import dataclass
-from typing import Any, Dict, List, Optional, Union, Literal
-
-
-@dataclass
-class SubmitParams:
-    tx: BytesEnvelope
-    witnesses: List[SubmitWitness]
-
-
-@dataclass
-class TxEnvelope:
-    hash: str
-    tx: str
-
-
-@dataclass
-class TirInfo:
-    bytecode: str
+from __future__ import annotations
+
+from enum import Enum
+from typing import Annotated, Any, Dict, List, Optional, Tuple, Union, Literal
+
+from annotated_types import Ge, Le, MaxLen, MinLen
+from pydantic import BaseModel, ConfigDict, Field, StringConstraints, field_validator
+
+from .address import is_address
+
+__all__ = [
+    "BytesEnvelope",
+    "CheckStatusParams",
+    "CheckStatusResponse",
+    "InputNotResolvedDiagnostic",
+    "InputQueryDiagnostic",
+    "MissingTxArgDiagnostic",
+    "ResolveParams",
+    "SearchSpaceDiagnostic",
+    "SubmitParams",
+    "SubmitResponse",
+    "SubmitWitnessType",
+    "SubmitWitness",
+    "TirInfo",
+    "TxEnvelope",
+    "TxScriptFailureDiagnostic",
+    "TxStatusStage",
+    "TxStatus",
+    "UnsupportedTirDiagnostic",
+]
+
+
+class BytesEnvelope(BaseModel):
     encoding: str
-    version: str
+    payload: str
 
 
-@dataclass
-class SubmitResponse:
-    hash: str
+class CheckStatusParams(BaseModel):
+    hashes: Annotated[List[str], MinLen(1)]
 
 
-@dataclass
-class SearchSpaceDiagnostic:
-    by_address_count: Optional[int]
-    by_asset_class_count: Optional[int]
-    by_ref_count: Optional[int]
-    matched: List[str]
+class CheckStatusResponse(BaseModel):
+    statuses: Dict[str, TxStatus]
 
 
-@dataclass
-class BytesEnvelope:
-    encoding: str
-    payload: str
+class InputNotResolvedDiagnostic(BaseModel):
+    name: str
+    query: InputQueryDiagnostic
+    search_space: SearchSpaceDiagnostic
 
 
-@dataclass
-class InputQueryDiagnostic:
-    address: Optional[str]
+class InputQueryDiagnostic(BaseModel):
+    address: Optional[str] = None
     collateral: bool
     min_amount: Dict[str, str]
     refs: List[str]
     support_many: bool
 
+    @field_validator("address")
+    @classmethod
+    def _address_address(cls, value: Any) -> Any:
+        if value is not None and not is_address(value):
+            raise ValueError("must be a valid address")
+        return value
+
 
-@dataclass
-class ResolveParams:
+class MissingTxArgDiagnostic(BaseModel):
+    key: str
+    type: str
+
+
+class ResolveParams(BaseModel):
     args: Dict[str, Any]
     env: Dict[str, Any]
     tir: TirInfo
 
 
-@dataclass
-class SubmitWitness:
+class SearchSpaceDiagnostic(BaseModel):
+    by_address_count: Optional[int] = None
+    by_asset_class_count: Optional[int] = None
+    by_ref_count: Optional[int] = None
+    matched: List[str]
+
+
+class SubmitParams(BaseModel):
+    tx: BytesEnvelope
+    witnesses: List[SubmitWitness]
+
+
+class SubmitResponse(BaseModel):
+    hash: str
+
+
+class SubmitWitnessType(str, Enum):
+    """Values of `SubmitWitness.type`."""
+    VKEY = "vkey"
+
+    def __str__(self) -> str:
+        return self.value
+
+    @classmethod
+    def parse(cls, value: str) -> Optional[SubmitWitnessType]:
+        """The member with wire value `value`, if there is one."""
+        for member in cls:
+            if member.value == value:
+                return member
+        return None
+
+    @classmethod
+    def coerce(cls, value: str) -> SubmitWitnessType:
+        """The member with wire value `value`, raising `ValueError` otherwise."""
+        member = cls.parse(value)
+        if member is None:
+            valid = ", ".join(repr(m.value) for m in cls)
+            raise ValueError(f"{value!r} is not one of {valid}")
+        return member
+
+
+class SubmitWitness(BaseModel):
     key: BytesEnvelope
     signature: BytesEnvelope
-    type: Literal["vkey"]
+    type: SubmitWitnessType
 
 
-@dataclass
-class UnsupportedTirDiagnostic:
-    expected: str
-    provided: str
+class TirInfo(BaseModel):
+    bytecode: str
+    encoding: str
+    version: str
 
 
-@dataclass
-class InputNotResolvedDiagnostic:
-    name: str
-    query: InputQueryDiagnostic
-    search_space: SearchSpaceDiagnostic
+class TxEnvelope(BaseModel):
+    hash: str
+    tx: str
 
 
-@dataclass
-class MissingTxArgDiagnostic:
-    key: str
-    type: str
+class TxScriptFailureDiagnostic(BaseModel):
+    logs: List[str]
 
 
-@dataclass
-class TxScriptFailureDiagnostic:
-    logs: List[str]
+class TxStatusStage(str, Enum):
+    """Values of `TxStatus.stage`."""
+    PENDING = "pending"
+    PROPAGATED = "propagated"
+    ACKNOWLEDGED = "acknowledged"
+    CONFIRMED = "confirmed"
+    FINALIZED = "finalized"
+    DROPPED = "dropped"
+    ROLLED_BACK = "rolledBack"
+    UNKNOWN = "unknown"
+
+    def __str__(self) -> str:
+        return self.value
+
+    @classmethod
+    def parse(cls, value: str) -> Optional[TxStatusStage]:
+        """The member with wire value `value`, if there is one."""
+        for member in cls:
+            if member.value == value:
+                return member
+        return None
+
+    @classmethod
+    def coerce(cls, value: str) -> TxStatusStage:
+        """The member with wire value `value`, raising `ValueError` otherwise."""
+        member = cls.parse(value)
+        if member is None:
+            valid = ", ".join(repr(m.value) for m in cls)
+            raise ValueError(f"{value!r} is not one of {valid}")
+        return member
+
+
+class TxStatus(BaseModel):
+    confirmations: Annotated[int, Ge(0)]
+    stage: TxStatusStage
+
+
+class UnsupportedTirDiagnostic(BaseModel):
+    expected: str
+    provided: str
 
@@ -0,0 +1,218 @@
+# Generated by cargo xtask gen --lang python
+from enum import Enum
+from typing import Annotated, Any, Dict, List, Optional, Tuple, Union, Literal
+
+from annotated_types import Ge, Le, MaxLen, MinLen
+from pydantic import BaseModel, Field, StringConstraints
+
+__all__ = [
+    "BytesEnvelope",
+    "CheckStatusParams",
+    "CheckStatusResponse",
+    "InputNotResolvedDiagnostic",
+    "InputQueryDiagnostic",
+    "MissingTxArgDiagnostic",
+    "ResolveParams",
+    "SearchSpaceDiagnostic",
+    "SubmitParams",
+    "SubmitResponse",
+    "SubmitWitnessType",
+    "SubmitWitness",
+    "TirInfo",
+    "TxEnvelope",
+    "TxScriptFailureDiagnostic",
+    "TxStatusStage",
+    "TxStatus",
+    "UnsupportedTirDiagnostic",
+]
+
+class BytesEnvelope(BaseModel):
+    encoding: str
+    payload: str
+    def __init__(
+        self,
+        *,
+        encoding: str,
+        payload: str,
+    ) -> None: ...
+
+class CheckStatusParams(BaseModel):
+    hashes: Annotated[List[str], MinLen(1)]
+    def __init__(
+        self,
+        *,
+        hashes: Annotated[List[str], MinLen(1)],
+    ) -> None: ...
+
+class CheckStatusResponse(BaseModel):
+    statuses: Dict[str, TxStatus]
+    def __init__(
+        self,
+        *,
+        statuses: Dict[str, TxStatus],
+    ) -> None: ...
+
+class InputNotResolvedDiagnostic(BaseModel):
+    name: str
+    query: InputQueryDiagnostic
+    search_space: SearchSpaceDiagnostic
+    def __init__(
+        self,
+        *,
+        name: str,
+        query: InputQueryDiagnostic,
+        search_space: SearchSpaceDiagnostic,
+    ) -> None: ...
+
+class InputQueryDiagnostic(BaseModel):
+    address: Optional[str]
+    collateral: bool
+    min_amount: Dict[str, str]
+    refs: List[str]
+    support_many: bool
+    def __init__(
+        self,
+        *,
+        address: Optional[str] = ...,
+        collateral: bool,
+        min_amount: Dict[str, str],
+        refs: List[str],
+        support_many: bool,
+    ) -> None: ...
+
+class MissingTxArgDiagnostic(BaseModel):
+    key: str
+    type: str
+    def __init__(
+        self,
+        *,
+        key: str,
+        type: str,
+    ) -> None: ...
+
+class ResolveParams(BaseModel):
+    args: Dict[str, Any]
+    env: Dict[str, Any]
+    tir: TirInfo
+    def __init__(
+        self,
+        *,
+        args: Dict[str, Any],
+        env: Dict[str, Any],
+        tir: TirInfo,
+    ) -> None: ...
+
+class SearchSpaceDiagnostic(BaseModel):
+    by_address_count: Optional[int]
+    by_asset_class_count: Optional[int]
+    by_ref_count: Optional[int]
+    matched: List[str]
+    def __init__(
+        self,
+        *,
+        by_address_count: Optional[int] = ...,
+        by_asset_class_count: Optional[int] = ...,
+        by_ref_count: Optional[int] = ...,
+        matched: List[str],
+    ) -> None: ...
+
+class SubmitParams(BaseModel):
+    tx: BytesEnvelope
+    witnesses: List[SubmitWitness]
+    def __init__(
+        self,
+        *,
+        tx: BytesEnvelope,
+        witnesses: List[SubmitWitness],
+    ) -> None: ...
+
+class SubmitResponse(BaseModel):
+    hash: str
+    def __init__(
+        self,
+        *,
+        hash: str,
+    ) -> None: ...
+
+class SubmitWitnessType(str, Enum):
+    VKEY = "vkey"
+    @classmethod
+    def parse(cls, value: str) -> Optional[SubmitWitnessType]: ...
+    @classmethod
+    def coerce(cls, value: str) -> SubmitWitnessType: ...
+
+class SubmitWitness(BaseModel):
+    key: BytesEnvelope
+    signature: BytesEnvelope
+    type: SubmitWitnessType
+    def __init__(
+        self,
+        *,
+        key: BytesEnvelope,
+        signature: BytesEnvelope,
+        type: Union[SubmitWitnessType, Literal["vkey"]],
+    ) -> None: ...
+
+class TirInfo(BaseModel):
+    bytecode: str
+    encoding: str
+    version: str
+    def __init__(
+        self,
+        *,
+        bytecode: str,
+        encoding: str,
+        version: str,
+    ) -> None: ...
+
+class TxEnvelope(BaseModel):
+    hash: str
+    tx: str
+    def __init__(
+        self,
+        *,
+        hash: str,
+        tx: str,
+    ) -> None: ...
+
+class TxScriptFailureDiagnostic(BaseModel):
+    logs: List[str]
+    def __init__(
+        self,
+        *,
+        logs: List[str],
+    ) -> None: ...
+
+class TxStatusStage(str, Enum):
+    PENDING = "pending"
+    PROPAGATED = "propagated"
+    ACKNOWLEDGED = "acknowledged"
+    CONFIRMED = "confirmed"
+    FINALIZED = "finalized"
+    DROPPED = "dropped"
+    ROLLED_BACK = "rolledBack"
+    UNKNOWN = "unknown"
+    @classmethod
+    def parse(cls, value: str) -> Optional[TxStatusStage]: ...
+    @classmethod
+    def coerce(cls, value: str) -> TxStatusStage: ...
+
+class TxStatus(BaseModel):
+    confirmations: Annotated[int, Ge(0)]
+    stage: TxStatusStage
+    def __init__(
+        self,
+        *,
+        confirmations: Annotated[int, Ge(0)],
+        stage: Union[TxStatusStage, Literal["pending", "propagated", "acknowledged", "confirmed", "finalized", "dropped", "rolledBack", "unknown"]],
+    ) -> None: ...
+
+class UnsupportedTirDiagnostic(BaseModel):
+    expected: str
+    provided: str
+    def __init__(
+        self,
+        *,
+        expected: str,
+        provided: str,
+    ) -> None: ...
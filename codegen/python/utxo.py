@@ -0,0 +1,59 @@
+# Generated by cargo xtask gen --lang python
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass
+from typing import Any, Mapping, Union
+
+__all__ = [
+    "TXID_LENGTH",
+    "InvalidUtxoRefError",
+    "UtxoRef",
+]
+
+# Length of a transaction hash in bytes.
+TXID_LENGTH = 32
+
+_TXID = re.compile("[0-9a-fA-F]{" + str(TXID_LENGTH * 2) + "}")
+_INDEX = re.compile(r"[0-9]+")
+
+
+class InvalidUtxoRefError(ValueError):
+    """Text or values that aren't a valid UTxO reference."""
+
+
+@dataclass(frozen=True)
+class UtxoRef:
+    """A reference to the output ``index`` of the transaction hashed ``txid``.
+
+    ``txid`` is kept as lowercase hex. ``str(ref)`` is the canonical
+    ``txid#index`` text form sent on the wire.
+    """
+
+    txid: str
+    index: int
+
+    def __post_init__(self) -> None:
+        if not isinstance(self.txid, str) or not _TXID.fullmatch(self.txid):
+            raise InvalidUtxoRefError(
+                f"expected a transaction hash of {TXID_LENGTH * 2} hex digits, got {self.txid!r}"
+            )
+        if isinstance(self.index, bool) or not isinstance(self.index, int) or not 0 <= self.index <= 0xFFFFFFFF:
+            raise InvalidUtxoRefError(f"the output index {self.index!r} isn't a 32-bit unsigned integer")
+        object.__setattr__(self, "txid", self.txid.lower())
+
+    @classmethod
+    def parse(cls, value: Union[str, Mapping[str, Any]]) -> UtxoRef:
+        """Parses the ``txid#index`` text form, or the JSON object form
+        ``{"txid": ..., "index": ...}``."""
+        if not isinstance(value, str):
+            return cls(value["txid"], value["index"])
+        txid, separator, index = value.partition("#")
+        if not separator:
+            raise InvalidUtxoRefError(f"expected a `txid#index` UTxO reference, got {value!r}")
+        if not _INDEX.fullmatch(index):
+            raise InvalidUtxoRefError(f"the output index {index!r} isn't a 32-bit unsigned integer")
+        return cls(txid, int(index))
+
+    def __str__(self) -> str:
+        return f"{self.txid}#{self.index}"
\ No newline at end of file
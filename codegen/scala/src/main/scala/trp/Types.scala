@@ -0,0 +1,360 @@
+// Generated by cargo xtask gen --lang scala
+package trp
+
+import io.circe.{Decoder, Encoder, Json, JsonObject}
+import io.circe.syntax._
+
+final case class BytesEnvelope(
+    encoding: String,
+    payload: String,
+)
+
+object BytesEnvelope {
+  implicit lazy val decoder: Decoder[BytesEnvelope] = Decoder.instance { cursor =>
+    for {
+      encoding <- cursor.downField("encoding").as[String]
+      payload <- cursor.downField("payload").as[String]
+    } yield BytesEnvelope(encoding, payload)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[BytesEnvelope] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "encoding" -> value.encoding.asJson,
+      "payload" -> value.payload.asJson,
+    )
+  }
+}
+
+final case class CheckStatusParams(
+    hashes: List[String],
+)
+
+object CheckStatusParams {
+  implicit lazy val decoder: Decoder[CheckStatusParams] = Decoder.instance { cursor =>
+    for {
+      hashes <- cursor.downField("hashes").as[List[String]]
+    } yield CheckStatusParams(hashes)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[CheckStatusParams] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "hashes" -> value.hashes.asJson,
+    )
+  }
+}
+
+final case class CheckStatusResponse(
+    statuses: Map[String, TxStatus],
+)
+
+object CheckStatusResponse {
+  implicit lazy val decoder: Decoder[CheckStatusResponse] = Decoder.instance { cursor =>
+    for {
+      statuses <- cursor.downField("statuses").as[Map[String, TxStatus]]
+    } yield CheckStatusResponse(statuses)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[CheckStatusResponse] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "statuses" -> value.statuses.asJson,
+    )
+  }
+}
+
+final case class InputNotResolvedDiagnostic(
+    name: String,
+    query: InputQueryDiagnostic,
+    searchSpace: SearchSpaceDiagnostic,
+)
+
+object InputNotResolvedDiagnostic {
+  implicit lazy val decoder: Decoder[InputNotResolvedDiagnostic] = Decoder.instance { cursor =>
+    for {
+      name <- cursor.downField("name").as[String]
+      query <- cursor.downField("query").as[InputQueryDiagnostic]
+      searchSpace <- cursor.downField("search_space").as[SearchSpaceDiagnostic]
+    } yield InputNotResolvedDiagnostic(name, query, searchSpace)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[InputNotResolvedDiagnostic] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "name" -> value.name.asJson,
+      "query" -> value.query.asJson,
+      "search_space" -> value.searchSpace.asJson,
+    )
+  }
+}
+
+final case class InputQueryDiagnostic(
+    address: Option[String] = None,
+    collateral: Boolean,
+    minAmount: Map[String, String],
+    refs: List[String],
+    supportMany: Boolean,
+)
+
+object InputQueryDiagnostic {
+  implicit lazy val decoder: Decoder[InputQueryDiagnostic] = Decoder.instance { cursor =>
+    for {
+      address <- cursor.downField("address").as[Option[String]]
+      collateral <- cursor.downField("collateral").as[Boolean]
+      minAmount <- cursor.downField("min_amount").as[Map[String, String]]
+      refs <- cursor.downField("refs").as[List[String]]
+      supportMany <- cursor.downField("support_many").as[Boolean]
+    } yield InputQueryDiagnostic(address, collateral, minAmount, refs, supportMany)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[InputQueryDiagnostic] = Encoder.AsObject.instance { value =>
+    JsonObject.fromIterable(
+      List(
+        value.address.map(v => "address" -> v.asJson),
+        Some("collateral" -> value.collateral.asJson),
+        Some("min_amount" -> value.minAmount.asJson),
+        Some("refs" -> value.refs.asJson),
+        Some("support_many" -> value.supportMany.asJson),
+      ).flatten
+    )
+  }
+}
+
+final case class MissingTxArgDiagnostic(
+    key: String,
+    `type`: String,
+)
+
+object MissingTxArgDiagnostic {
+  implicit lazy val decoder: Decoder[MissingTxArgDiagnostic] = Decoder.instance { cursor =>
+    for {
+      key <- cursor.downField("key").as[String]
+      type_ <- cursor.downField("type").as[String]
+    } yield MissingTxArgDiagnostic(key, type_)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[MissingTxArgDiagnostic] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "key" -> value.key.asJson,
+      "type" -> value.`type`.asJson,
+    )
+  }
+}
+
+final case class ResolveParams(
+    args: JsonObject,
+    env: JsonObject,
+    tir: TirInfo,
+)
+
+object ResolveParams {
+  implicit lazy val decoder: Decoder[ResolveParams] = Decoder.instance { cursor =>
+    for {
+      args <- cursor.downField("args").as[JsonObject]
+      env <- cursor.downField("env").as[JsonObject]
+      tir <- cursor.downField("tir").as[TirInfo]
+    } yield ResolveParams(args, env, tir)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[ResolveParams] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "args" -> value.args.asJson,
+      "env" -> value.env.asJson,
+      "tir" -> value.tir.asJson,
+    )
+  }
+}
+
+final case class SearchSpaceDiagnostic(
+    byAddressCount: Option[Long] = None,
+    byAssetClassCount: Option[Long] = None,
+    byRefCount: Option[Long] = None,
+    matched: List[String],
+)
+
+object SearchSpaceDiagnostic {
+  implicit lazy val decoder: Decoder[SearchSpaceDiagnostic] = Decoder.instance { cursor =>
+    for {
+      byAddressCount <- cursor.downField("by_address_count").as[Option[Long]]
+      byAssetClassCount <- cursor.downField("by_asset_class_count").as[Option[Long]]
+      byRefCount <- cursor.downField("by_ref_count").as[Option[Long]]
+      matched <- cursor.downField("matched").as[List[String]]
+    } yield SearchSpaceDiagnostic(byAddressCount, byAssetClassCount, byRefCount, matched)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[SearchSpaceDiagnostic] = Encoder.AsObject.instance { value =>
+    JsonObject.fromIterable(
+      List(
+        value.byAddressCount.map(v => "by_address_count" -> v.asJson),
+        value.byAssetClassCount.map(v => "by_asset_class_count" -> v.asJson),
+        value.byRefCount.map(v => "by_ref_count" -> v.asJson),
+        Some("matched" -> value.matched.asJson),
+      ).flatten
+    )
+  }
+}
+
+final case class SubmitParams(
+    tx: BytesEnvelope,
+    witnesses: List[SubmitWitness],
+)
+
+object SubmitParams {
+  implicit lazy val decoder: Decoder[SubmitParams] = Decoder.instance { cursor =>
+    for {
+      tx <- cursor.downField("tx").as[BytesEnvelope]
+      witnesses <- cursor.downField("witnesses").as[List[SubmitWitness]]
+    } yield SubmitParams(tx, witnesses)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[SubmitParams] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "tx" -> value.tx.asJson,
+      "witnesses" -> value.witnesses.asJson,
+    )
+  }
+}
+
+final case class SubmitResponse(
+    hash: String,
+)
+
+object SubmitResponse {
+  implicit lazy val decoder: Decoder[SubmitResponse] = Decoder.instance { cursor =>
+    for {
+      hash <- cursor.downField("hash").as[String]
+    } yield SubmitResponse(hash)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[SubmitResponse] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "hash" -> value.hash.asJson,
+    )
+  }
+}
+
+final case class SubmitWitness(
+    key: BytesEnvelope,
+    signature: BytesEnvelope,
+    `type`: String,
+)
+
+object SubmitWitness {
+  implicit lazy val decoder: Decoder[SubmitWitness] = Decoder.instance { cursor =>
+    for {
+      key <- cursor.downField("key").as[BytesEnvelope]
+      signature <- cursor.downField("signature").as[BytesEnvelope]
+      type_ <- cursor.downField("type").as[String]
+    } yield SubmitWitness(key, signature, type_)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[SubmitWitness] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "key" -> value.key.asJson,
+      "signature" -> value.signature.asJson,
+      "type" -> value.`type`.asJson,
+    )
+  }
+}
+
+final case class TirInfo(
+    bytecode: String,
+    encoding: String,
+    version: String,
+)
+
+object TirInfo {
+  implicit lazy val decoder: Decoder[TirInfo] = Decoder.instance { cursor =>
+    for {
+      bytecode <- cursor.downField("bytecode").as[String]
+      encoding <- cursor.downField("encoding").as[String]
+      version <- cursor.downField("version").as[String]
+    } yield TirInfo(bytecode, encoding, version)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[TirInfo] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "bytecode" -> value.bytecode.asJson,
+      "encoding" -> value.encoding.asJson,
+      "version" -> value.version.asJson,
+    )
+  }
+}
+
+final case class TxEnvelope(
+    hash: String,
+    tx: String,
+)
+
+object TxEnvelope {
+  implicit lazy val decoder: Decoder[TxEnvelope] = Decoder.instance { cursor =>
+    for {
+      hash <- cursor.downField("hash").as[String]
+      tx <- cursor.downField("tx").as[String]
+    } yield TxEnvelope(hash, tx)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[TxEnvelope] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "hash" -> value.hash.asJson,
+      "tx" -> value.tx.asJson,
+    )
+  }
+}
+
+final case class TxScriptFailureDiagnostic(
+    logs: List[String],
+)
+
+object TxScriptFailureDiagnostic {
+  implicit lazy val decoder: Decoder[TxScriptFailureDiagnostic] = Decoder.instance { cursor =>
+    for {
+      logs <- cursor.downField("logs").as[List[String]]
+    } yield TxScriptFailureDiagnostic(logs)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[TxScriptFailureDiagnostic] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "logs" -> value.logs.asJson,
+    )
+  }
+}
+
+final case class TxStatus(
+    confirmations: Long,
+    stage: String,
+)
+
+object TxStatus {
+  implicit lazy val decoder: Decoder[TxStatus] = Decoder.instance { cursor =>
+    for {
+      confirmations <- cursor.downField("confirmations").as[Long]
+      stage <- cursor.downField("stage").as[String]
+    } yield TxStatus(confirmations, stage)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[TxStatus] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "confirmations" -> value.confirmations.asJson,
+      "stage" -> value.stage.asJson,
+    )
+  }
+}
+
+final case class UnsupportedTirDiagnostic(
+    expected: String,
+    provided: String,
+)
+
+object UnsupportedTirDiagnostic {
+  implicit lazy val decoder: Decoder[UnsupportedTirDiagnostic] = Decoder.instance { cursor =>
+    for {
+      expected <- cursor.downField("expected").as[String]
+      provided <- cursor.downField("provided").as[String]
+    } yield UnsupportedTirDiagnostic(expected, provided)
+  }
+
+  implicit lazy val encoder: Encoder.AsObject[UnsupportedTirDiagnostic] = Encoder.AsObject.instance { value =>
+    JsonObject(
+      "expected" -> value.expected.asJson,
+      "provided" -> value.provided.asJson,
+    )
+  }
+}
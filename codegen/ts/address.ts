@@ -0,0 +1,234 @@
+// Generated by cargo xtask gen --lang ts
+
+const BECH32_CHARSET = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/** Network id of mainnet; every other id is a test network. */
+export const MAINNET = 1;
+
+/** Thrown for text or bytes that aren't a valid address. */
+export class InvalidAddressError extends Error {
+    constructor(message: string) {
+        super(message);
+        this.name = "InvalidAddressError";
+    }
+}
+
+/**
+ * A Cardano address: its header byte, telling its kind and network, then
+ * its payload. Fields marked with `format: address` hold its text form,
+ * bech32 for Shelley addresses (`addr1…`, `stake_test1…`) and base58 for
+ * Byron ones.
+ */
+export class Address {
+    private constructor(readonly bytes: Uint8Array) {}
+
+    /** Parses the bech32 or base58 text form of an address. */
+    static parse(text: string): Address {
+        const separator = text.lastIndexOf("1");
+        const prefix = text.slice(0, separator);
+        // Byron addresses are mixed-case, unlike bech32 prefixes.
+        if (separator > 0 && (/^[a-z0-9_]+$/.test(prefix) || /^[A-Z0-9_]+$/.test(prefix))) {
+            const address = Address.fromBytes(bech32Decode(prefix.toLowerCase(), text.slice(separator + 1)));
+            if (address.prefix !== prefix.toLowerCase()) {
+                throw new InvalidAddressError(`expected the address prefix ${address.prefix ?? "of none"}, got ${prefix}`);
+            }
+            return address;
+        }
+        const address = Address.fromBytes(base58Decode(text));
+        if (!address.isByron) {
+            throw new InvalidAddressError("the address is neither bech32 nor base58");
+        }
+        return address;
+    }
+
+    /** The address whose bytes `hex` encodes. */
+    static fromHex(hex: string): Address {
+        if (!/^([0-9a-fA-F]{2})*$/.test(hex)) {
+            throw new InvalidAddressError("the address isn't hex");
+        }
+        return Address.fromBytes(Uint8Array.from(hex.match(/../g) ?? [], (pair) => parseInt(pair, 16)));
+    }
+
+    /** The address made of `bytes`, checked to be of a known kind. */
+    static fromBytes(bytes: Uint8Array): Address {
+        const header = bytes[0];
+        if (header === undefined) {
+            throw new InvalidAddressError("the address is empty");
+        }
+        const kind = header >> 4;
+        let valid: boolean;
+        if (kind <= 3) {
+            valid = bytes.length === 57;
+        } else if (kind <= 5) {
+            valid = bytes.length >= 32;
+        } else if (kind <= 7 || kind >= 14) {
+            valid = bytes.length === 29;
+        } else if (kind === 8) {
+            valid = bytes[1] === 0xd8 && bytes[2] === 0x18 && header === 0x82;
+        } else {
+            throw new InvalidAddressError(`unknown address header 0x${header.toString(16)}`);
+        }
+        if (!valid) {
+            throw new InvalidAddressError(`an address of this kind can't be ${bytes.length} bytes long`);
+        }
+        return new Address(bytes);
+    }
+
+    get isByron(): boolean {
+        return this.bytes[0] >> 4 === 8;
+    }
+
+    /** Whether the address names a stake credential rather than a payment one. */
+    get isReward(): boolean {
+        return this.bytes[0] >> 4 >= 14;
+    }
+
+    /** The network id of a Shelley address; Byron addresses don't tell it in their header. */
+    get networkId(): number | undefined {
+        return this.isByron ? undefined : this.bytes[0] & 0x0f;
+    }
+
+    /** The bech32 prefix of a Shelley address. */
+    get prefix(): string | undefined {
+        const network = this.networkId;
+        if (network === undefined) {
+            return undefined;
+        }
+        return (this.isReward ? "stake" : "addr") + (network === MAINNET ? "" : "_test");
+    }
+
+    toHex(): string {
+        return Array.from(this.bytes, (byte) => byte.toString(16).padStart(2, "0")).join("");
+    }
+
+    /** The bech32 form of a Shelley address. */
+    toBech32(): string | undefined {
+        const prefix = this.prefix;
+        if (prefix === undefined) {
+            return undefined;
+        }
+        const data = regroup(this.bytes, 8, 5, true);
+        const values = [...data, ...bech32Checksum(prefix, data)];
+        return `${prefix}1${values.map((value) => BECH32_CHARSET[value]).join("")}`;
+    }
+
+    /** The text form of the address, as fields hold it. */
+    toString(): string {
+        return this.toBech32() ?? base58Encode(this.bytes);
+    }
+}
+
+/** Whether `text` is a valid address, as the schemas check the fields marked with `format: address`. */
+export function isAddress(text: string): boolean {
+    try {
+        Address.parse(text);
+        return true;
+    } catch {
+        return false;
+    }
+}
+
+function bech32Polymod(values: number[]): number {
+    const generators = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let checksum = 1;
+    for (const value of values) {
+        const top = checksum >>> 25;
+        checksum = (((checksum & 0x1ffffff) << 5) ^ value) >>> 0;
+        for (let i = 0; i < 5; i++) {
+            if ((top >>> i) & 1) {
+                checksum = (checksum ^ generators[i]) >>> 0;
+            }
+        }
+    }
+    return checksum;
+}
+
+function bech32PrefixValues(prefix: string): number[] {
+    const codes = Array.from(prefix, (c) => c.charCodeAt(0));
+    return [...codes.map((c) => c >> 5), 0, ...codes.map((c) => c & 31)];
+}
+
+function bech32Checksum(prefix: string, data: number[]): number[] {
+    const polymod = bech32Polymod([...bech32PrefixValues(prefix), ...data, 0, 0, 0, 0, 0, 0]) ^ 1;
+    return [0, 1, 2, 3, 4, 5].map((i) => (polymod >>> (5 * (5 - i))) & 31);
+}
+
+function bech32Decode(prefix: string, data: string): Uint8Array {
+    if (data.length < 6 || (data !== data.toLowerCase() && data !== data.toUpperCase())) {
+        throw new InvalidAddressError("the address isn't bech32");
+    }
+    const values = Array.from(data.toLowerCase(), (c) => BECH32_CHARSET.indexOf(c));
+    if (values.includes(-1)) {
+        throw new InvalidAddressError("the address isn't bech32");
+    }
+    if (bech32Polymod([...bech32PrefixValues(prefix), ...values]) !== 1) {
+        throw new InvalidAddressError("the address checksum doesn't match");
+    }
+    return Uint8Array.from(regroup(values.slice(0, -6), 5, 8, false));
+}
+
+/**
+ * Regroups `values` of `from` bits into values of `to` bits, padding the
+ * last one with zeros if `pad`, and else requiring the leftover bits to be.
+ */
+function regroup(values: Iterable<number>, from: number, to: number, pad: boolean): number[] {
+    let acc = 0;
+    let bits = 0;
+    const out: number[] = [];
+    const mask = (1 << to) - 1;
+    for (const value of values) {
+        acc = ((acc << from) | value) & 0xffffff;
+        bits += from;
+        while (bits >= to) {
+            bits -= to;
+            out.push((acc >> bits) & mask);
+        }
+    }
+    if (pad && bits > 0) {
+        out.push((acc << (to - bits)) & mask);
+    } else if (!pad && (bits >= from || (acc & ((1 << bits) - 1)) !== 0)) {
+        throw new InvalidAddressError("the address isn't bech32");
+    }
+    return out;
+}
+
+function base58Decode(text: string): Uint8Array {
+    const bytes: number[] = [];
+    for (const c of text) {
+        let carry = BASE58_ALPHABET.indexOf(c);
+        if (carry < 0) {
+            throw new InvalidAddressError("the address is neither bech32 nor base58");
+        }
+        for (let i = bytes.length - 1; i >= 0; i--) {
+            carry += bytes[i] * 58;
+            bytes[i] = carry & 0xff;
+            carry >>= 8;
+        }
+        while (carry > 0) {
+            bytes.unshift(carry & 0xff);
+            carry >>= 8;
+        }
+    }
+    const zeros = text.length - text.replace(/^1+/, "").length;
+    return Uint8Array.from([...new Array<number>(zeros).fill(0), ...bytes]);
+}
+
+function base58Encode(bytes: Uint8Array): string {
+    const digits: number[] = [];
+    for (const byte of bytes) {
+        let carry = byte;
+        for (let i = digits.length - 1; i >= 0; i--) {
+            carry += digits[i] << 8;
+            digits[i] = carry % 58;
+            carry = Math.floor(carry / 58);
+        }
+        while (carry > 0) {
+            digits.unshift(carry % 58);
+            carry = Math.floor(carry / 58);
+        }
+    }
+    const zeros = bytes.findIndex((byte) => byte !== 0);
+    const ones = "1".repeat(zeros < 0 ? bytes.length : zeros);
+    return ones + digits.map((digit) => BASE58_ALPHABET[digit]).join("");
+}
\ No newline at end of file
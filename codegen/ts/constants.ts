@@ -0,0 +1,26 @@
+// Generated by cargo xtask gen --lang ts
+
+/** Version of the spec these bindings were generated from. */
+export const SPEC_VERSION = "0.1.1";
+
+export const TRP_RESOLVE = "trp.resolve";
+/** Params of `trp.resolve` in declared order. */
+export const TRP_RESOLVE_PARAMS = ["tir", "args", "env"] as const;
+
+export const TRP_SUBMIT = "trp.submit";
+/** Params of `trp.submit` in declared order. */
+export const TRP_SUBMIT_PARAMS = ["tx", "witnesses"] as const;
+
+export const TRP_CHECK_STATUS = "trp.checkStatus";
+/** Params of `trp.checkStatus` in declared order. */
+export const TRP_CHECK_STATUS_PARAMS = ["hashes"] as const;
+
+
+/** OpenRPC method answering the spec document of a server, whose version clients check. */
+export const DISCOVER_METHOD = "rpc.discover";
+
+/**
+ * HTTP header carrying the idempotency key of a call: servers answer the calls
+ * repeating the key of an earlier one with its result rather than running them again.
+ */
+export const IDEMPOTENCY_HEADER = "idempotency-key";
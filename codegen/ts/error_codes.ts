@@ -0,0 +1,112 @@
+// Generated by cargo xtask gen --lang ts
+/**
+ * The JSON-RPC error codes of the protocol, split into ranges so that the
+ * errors of JSON-RPC itself, of transports and of the spec's methods never
+ * share a code.
+ */
+import { JsonRpcError } from "./client";
+import { UNSUPPORTED_TIR, MISSING_TRANSACTION_ARGUMENT, INPUT_NOT_RESOLVED, TX_SCRIPT_FAILURE, type TrpError, type TrpErrorCode, type TrpErrorData } from "./errors";
+
+/** Who answers errors of a given code. */
+export type ErrorClass = "protocol" | "transport" | "application";
+
+/** First and last code of each class. */
+export const ERROR_CODE_RANGES: Readonly<Record<ErrorClass, readonly [number, number]>> = {
+    /** Errors of JSON-RPC itself, reserved by it. */
+    protocol: [-32768, -32100],
+    /** Errors answered by servers, proxies and their JSON-RPC library, whatever the method. */
+    transport: [-32099, -32004],
+    /** Errors the spec's methods declare. */
+    application: [-32003, -32000],
+};
+
+/** `Parse error`, a protocol error. */
+export const PARSE_ERROR = -32700;
+
+/** `Invalid request`, a protocol error. */
+export const INVALID_REQUEST = -32600;
+
+/** `Method not found`, a protocol error. */
+export const METHOD_NOT_FOUND = -32601;
+
+/** `Invalid params`, a protocol error. */
+export const INVALID_PARAMS = -32602;
+
+/** `Internal error`, a protocol error. */
+export const INTERNAL_ERROR = -32603;
+
+/** `No upstream available`, a transport error. */
+export const UPSTREAM_UNAVAILABLE = -32004;
+
+/** `Batched requests are not supported`, a transport error. */
+export const BATCHES_NOT_SUPPORTED = -32005;
+
+/** `Too many subscriptions`, a transport error. */
+export const TOO_MANY_SUBSCRIPTIONS = -32006;
+
+/** `Request is too big`, a transport error. */
+export const OVERSIZED_REQUEST = -32007;
+
+/** `Response is too big`, a transport error. */
+export const OVERSIZED_RESPONSE = -32008;
+
+/** `Server is busy`, a transport error. */
+export const SERVER_IS_BUSY = -32009;
+
+/** `Batch request is too big`, a transport error. */
+export const TOO_BIG_BATCH_REQUEST = -32010;
+
+/** `Batch response is too big`, a transport error. */
+export const TOO_BIG_BATCH_RESPONSE = -32011;
+
+/** `Rate limit exceeded`, a transport error. */
+export const RATE_LIMITED = -32012;
+
+/** `Idempotency key conflict`, a transport error. */
+export const IDEMPOTENCY_CONFLICT = -32013;
+
+/** `Request cancelled`, a transport error. */
+export const REQUEST_CANCELLED = -32800;
+
+/** Messages the spec declares for its errors. */
+const MESSAGES: Record<TrpErrorCode, string> = {
+    [UNSUPPORTED_TIR]: "Unsupported TIR",
+    [MISSING_TRANSACTION_ARGUMENT]: "Missing transaction argument",
+    [INPUT_NOT_RESOLVED]: "Input not resolved",
+    [TX_SCRIPT_FAILURE]: "Tx script failure",
+};
+
+/** The class of `code`, `undefined` for codes outside of every range. */
+export function errorClass(code: number): ErrorClass | undefined {
+    switch (code) {
+        case REQUEST_CANCELLED:
+            return "transport";
+    }
+    for (const [name, [start, end]] of Object.entries(ERROR_CODE_RANGES)) {
+        if (code >= start && code <= end) {
+            return name as ErrorClass;
+        }
+    }
+    return undefined;
+}
+
+/** The error the spec declares for `code`, with its declared message. */
+export function declaredError<C extends TrpErrorCode>(
+    code: C,
+    data?: C extends keyof TrpErrorData ? TrpErrorData[C] : unknown,
+): TrpError<C> {
+    return new JsonRpcError(code, MESSAGES[code], data) as TrpError<C>;
+}
+
+/**
+ * A protocol or transport error, as answered whatever the method. Throws a
+ * `RangeError` for other codes: errors of the methods must be declared by
+ * the spec, and built with {@link declaredError}.
+ */
+export function reservedError(code: number, message: string, data?: unknown): JsonRpcError {
+    const cls = errorClass(code);
+    if (cls !== "protocol" && cls !== "transport") {
+        throw new RangeError(`${code} is not a protocol or transport error code`);
+    }
+    return new JsonRpcError(code, message, data);
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+// Generated by cargo xtask gen --lang ts
+import { JsonRpcError } from "./client";
+import type * as types from "./types";
+
+/** Code of the `Unsupported TIR` error. */
+export const UNSUPPORTED_TIR = -32000;
+
+/** Code of the `Missing transaction argument` error. */
+export const MISSING_TRANSACTION_ARGUMENT = -32001;
+
+/** Code of the `Input not resolved` error. */
+export const INPUT_NOT_RESOLVED = -32002;
+
+/** Code of the `Tx script failure` error. */
+export const TX_SCRIPT_FAILURE = -32003;
+
+/** Codes of the errors declared by the spec. */
+export const TRP_ERROR_CODES = [UNSUPPORTED_TIR, MISSING_TRANSACTION_ARGUMENT, INPUT_NOT_RESOLVED, TX_SCRIPT_FAILURE] as const;
+
+export type TrpErrorCode = (typeof TRP_ERROR_CODES)[number];
+
+/** Codes of the errors a call may recover from when retried. */
+export const RETRYABLE_ERROR_CODES: readonly number[] = [INPUT_NOT_RESOLVED];
+
+/** Types of the `data` of the declared errors, by code. */
+export interface TrpErrorData {
+    [UNSUPPORTED_TIR]: types.UnsupportedTirDiagnostic;
+    [MISSING_TRANSACTION_ARGUMENT]: types.MissingTxArgDiagnostic;
+    [INPUT_NOT_RESOLVED]: types.InputNotResolvedDiagnostic;
+    [TX_SCRIPT_FAILURE]: types.TxScriptFailureDiagnostic;
+}
+
+/**
+ * A {@link JsonRpcError} with one of the codes declared by the spec, whose
+ * `data` has the type declared for that code.
+ */
+export type TrpError<C extends TrpErrorCode = TrpErrorCode> = JsonRpcError & {
+    readonly code: C;
+    readonly data?: C extends keyof TrpErrorData ? TrpErrorData[C] : unknown;
+};
+
+export function isTrpError(error: unknown): error is TrpError {
+    return error instanceof JsonRpcError && (TRP_ERROR_CODES as readonly number[]).includes(error.code);
+}
+
+export function isUnsupportedTir(error: unknown): error is TrpError<typeof UNSUPPORTED_TIR> {
+    return error instanceof JsonRpcError && error.code === UNSUPPORTED_TIR;
+}
+
+export function isMissingTransactionArgument(error: unknown): error is TrpError<typeof MISSING_TRANSACTION_ARGUMENT> {
+    return error instanceof JsonRpcError && error.code === MISSING_TRANSACTION_ARGUMENT;
+}
+
+export function isInputNotResolved(error: unknown): error is TrpError<typeof INPUT_NOT_RESOLVED> {
+    return error instanceof JsonRpcError && error.code === INPUT_NOT_RESOLVED;
+}
+
+export function isTxScriptFailure(error: unknown): error is TrpError<typeof TX_SCRIPT_FAILURE> {
+    return error instanceof JsonRpcError && error.code === TX_SCRIPT_FAILURE;
+}
+
+/** Whether a call failing with `error` may succeed when retried. */
+export function isRetryable(error: unknown): boolean {
+    return error instanceof JsonRpcError && RETRYABLE_ERROR_CODES.includes(error.code);
+}
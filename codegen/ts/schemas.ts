@@ -0,0 +1,90 @@
+// Generated by cargo xtask gen --lang ts
+import { z } from "zod";
+
+import type * as types from "./types";
+import { isAddress } from "./address";
+
+export const BytesEnvelopeSchema: z.ZodType<types.BytesEnvelope> = z.object({
+    encoding: z.string(),
+    payload: z.string(),
+});
+
+export const CheckStatusParamsSchema: z.ZodType<types.CheckStatusParams> = z.object({
+    hashes: z.array(z.string()).min(1, { message: "must contain at least 1 items" }),
+});
+
+export const CheckStatusResponseSchema: z.ZodType<types.CheckStatusResponse> = z.object({
+    statuses: z.record(z.string(), z.lazy(() => TxStatusSchema)),
+});
+
+export const InputNotResolvedDiagnosticSchema: z.ZodType<types.InputNotResolvedDiagnostic> = z.object({
+    name: z.string(),
+    query: z.lazy(() => InputQueryDiagnosticSchema),
+    search_space: z.lazy(() => SearchSpaceDiagnosticSchema),
+});
+
+export const InputQueryDiagnosticSchema: z.ZodType<types.InputQueryDiagnostic> = z.object({
+    address: z.string().refine(isAddress, { message: "must be a valid address" }).nullable().optional(),
+    collateral: z.boolean(),
+    min_amount: z.record(z.string(), z.string()),
+    refs: z.array(z.string()),
+    support_many: z.boolean(),
+});
+
+export const MissingTxArgDiagnosticSchema: z.ZodType<types.MissingTxArgDiagnostic> = z.object({
+    key: z.string(),
+    type: z.string(),
+});
+
+export const ResolveParamsSchema: z.ZodType<types.ResolveParams> = z.object({
+    args: z.record(z.string(), z.any()),
+    env: z.record(z.string(), z.any()),
+    tir: z.lazy(() => TirInfoSchema),
+});
+
+export const SearchSpaceDiagnosticSchema: z.ZodType<types.SearchSpaceDiagnostic> = z.object({
+    by_address_count: z.number().int().nullable().optional(),
+    by_asset_class_count: z.number().int().nullable().optional(),
+    by_ref_count: z.number().int().nullable().optional(),
+    matched: z.array(z.string()),
+});
+
+export const SubmitParamsSchema: z.ZodType<types.SubmitParams> = z.object({
+    tx: z.lazy(() => BytesEnvelopeSchema),
+    witnesses: z.array(z.lazy(() => SubmitWitnessSchema)),
+});
+
+export const SubmitResponseSchema: z.ZodType<types.SubmitResponse> = z.object({
+    hash: z.string(),
+});
+
+export const SubmitWitnessSchema: z.ZodType<types.SubmitWitness> = z.object({
+    key: z.lazy(() => BytesEnvelopeSchema),
+    signature: z.lazy(() => BytesEnvelopeSchema),
+    type: z.enum(["vkey"]),
+});
+
+export const TirInfoSchema: z.ZodType<types.TirInfo> = z.object({
+    bytecode: z.string(),
+    encoding: z.string(),
+    version: z.string(),
+});
+
+export const TxEnvelopeSchema: z.ZodType<types.TxEnvelope> = z.object({
+    hash: z.string(),
+    tx: z.string(),
+});
+
+export const TxScriptFailureDiagnosticSchema: z.ZodType<types.TxScriptFailureDiagnostic> = z.object({
+    logs: z.array(z.string()),
+});
+
+export const TxStatusSchema: z.ZodType<types.TxStatus> = z.object({
+    confirmations: z.number().int().gte(0, { message: "must be at least 0" }),
+    stage: z.enum(["pending", "propagated", "acknowledged", "confirmed", "finalized", "dropped", "rolledBack", "unknown"]),
+});
+
+export const UnsupportedTirDiagnosticSchema: z.ZodType<types.UnsupportedTirDiagnostic> = z.object({
+    expected: z.string(),
+    provided: z.string(),
+});
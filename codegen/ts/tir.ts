@@ -0,0 +1,153 @@
+// Generated by cargo xtask gen --lang ts
+import type { TirInfo } from "./types";
+
+/** Versions of the TIR the spec supports. */
+export const TIR_VERSIONS = ["v1alpha8", "v1beta0"] as const;
+
+export type TirVersion = (typeof TIR_VERSIONS)[number];
+
+/**
+ * Encoding of TIR bytecode. Base64 decodes from the standard or URL-safe
+ * alphabet, padded or not, and encodes as standard padded base64.
+ */
+export type TirEncoding = "hex" | "base64";
+
+/** Thrown for TIR programs that servers wouldn't resolve. */
+export class InvalidTirError extends Error {
+    constructor(message: string) {
+        super(message);
+        this.name = "InvalidTirError";
+    }
+}
+
+const BASE64 = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+function sextet(c: string): number {
+    switch (c) {
+        case "-":
+            return 62;
+        case "_":
+            return 63;
+        default:
+            return BASE64.indexOf(c);
+    }
+}
+
+function invalid(encoding: TirEncoding, offset: number): InvalidTirError {
+    return new InvalidTirError(`the TIR bytecode isn't valid ${encoding} at offset ${offset}`);
+}
+
+/** The bytes `text` encodes, lowercase or uppercase hex alike. */
+export function decodeBytecode(encoding: TirEncoding, text: string): Uint8Array {
+    if (encoding === "hex") {
+        const bytes = new Uint8Array(Math.floor(text.length / 2));
+        for (let i = 0; i < text.length; i += 2) {
+            for (const offset of [i, i + 1]) {
+                if (!/[0-9a-fA-F]/.test(text.charAt(offset))) {
+                    throw invalid(encoding, offset);
+                }
+            }
+            bytes[i / 2] = parseInt(text.slice(i, i + 2), 16);
+        }
+        return bytes;
+    }
+    const data = text.replace(/=+$/, "");
+    const bytes: number[] = [];
+    let bits = 0;
+    let length = 0;
+    for (let offset = 0; offset < data.length; offset++) {
+        const value = sextet(data.charAt(offset));
+        if (value < 0) {
+            throw invalid(encoding, offset);
+        }
+        bits = (bits << 6) | value;
+        length += 6;
+        if (length >= 8) {
+            length -= 8;
+            bytes.push((bits >> length) & 0xff);
+            bits &= (1 << length) - 1;
+        }
+    }
+    if (data.length % 4 === 1) {
+        throw invalid(encoding, data.length - 1);
+    }
+    const padding = text.length - data.length;
+    if (padding > 0 && (padding > 2 || text.length % 4 !== 0)) {
+        throw invalid(encoding, data.length);
+    }
+    return Uint8Array.from(bytes);
+}
+
+/** `bytes` as text of `encoding`, hex being lowercase. */
+export function encodeBytecode(encoding: TirEncoding, bytes: Uint8Array): string {
+    if (encoding === "hex") {
+        return Array.from(bytes, (byte) => byte.toString(16).padStart(2, "0")).join("");
+    }
+    let text = "";
+    for (let i = 0; i < bytes.length; i += 3) {
+        const chunk = bytes.subarray(i, i + 3);
+        const bits = (chunk[0] << 16) | ((chunk[1] ?? 0) << 8) | (chunk[2] ?? 0);
+        for (let j = 0; j < 4; j++) {
+            text += j <= chunk.length ? BASE64.charAt((bits >> (18 - 6 * j)) & 0x3f) : "=";
+        }
+    }
+    return text;
+}
+
+function isVersion(version: string): version is TirVersion {
+    return (TIR_VERSIONS as readonly string[]).includes(version);
+}
+
+/**
+ * A TIR program of a supported version, with its bytecode decoded. Build
+ * one with {@link TirEnvelope.create} or {@link TirEnvelope.fromInfo}, which
+ * throw an {@link InvalidTirError} for unsupported versions and encodings
+ * and for bytecode that doesn't decode, and send it with
+ * {@link TirEnvelope.toInfo}.
+ */
+export class TirEnvelope {
+    private constructor(
+        readonly version: TirVersion,
+        readonly encoding: TirEncoding,
+        readonly bytecode: Uint8Array,
+    ) {}
+
+    /** The program of `bytecode` compiled to `version`, to be sent as `encoding`. */
+    static create(version: string, bytecode: Uint8Array, encoding: TirEncoding = "hex"): TirEnvelope {
+        if (!isVersion(version)) {
+            throw new InvalidTirError(
+                `unsupported TIR version "${version}", expected one of ${TIR_VERSIONS.join(", ")}`,
+            );
+        }
+        if (encoding !== "hex" && encoding !== "base64") {
+            throw new InvalidTirError(`unsupported TIR encoding "${encoding}", expected hex or base64`);
+        }
+        if (bytecode.length === 0) {
+            throw new InvalidTirError("the TIR bytecode is empty");
+        }
+        return new TirEnvelope(version, encoding, bytecode);
+    }
+
+    /** Checks and decodes the program of `info`, as received untyped or typed. */
+    static fromInfo(info: { version: string; encoding: string; bytecode: string }): TirEnvelope {
+        const encoding = info.encoding as TirEncoding;
+        if (encoding !== "hex" && encoding !== "base64") {
+            throw new InvalidTirError(`unsupported TIR encoding "${info.encoding}", expected hex or base64`);
+        }
+        return TirEnvelope.create(info.version, decodeBytecode(encoding, info.bytecode), encoding);
+    }
+
+    /** The same program, to be sent as `encoding`. */
+    withEncoding(encoding: TirEncoding): TirEnvelope {
+        return TirEnvelope.create(this.version, this.bytecode, encoding);
+    }
+
+    /** The program as `trp.resolve` takes it. */
+    toInfo(): TirInfo {
+        return {
+            bytecode: encodeBytecode(this.encoding, this.bytecode),
+            encoding: this.encoding,
+            version: this.version,
+        };
+    }
+}
\ No newline at end of file
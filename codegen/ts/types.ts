@@ -1,45 +1,30 @@
 // Generated by cargo xtask gen --lang ts
 
-export interface SubmitParams {
-    tx: BytesEnvelope;
-    witnesses: SubmitWitness[];
-}
-
-
-export interface TxEnvelope {
-    hash: string;
-    tx: string;
-}
-
-
-export interface TirInfo {
-    bytecode: string;
+export interface BytesEnvelope {
     encoding: string;
-    version: string;
+    payload: string;
 }
 
 
-export interface SubmitResponse {
-    hash: string;
+export interface CheckStatusParams {
+    hashes: string[];
 }
 
 
-export interface SearchSpaceDiagnostic {
-    by_address_count: number | null;
-    by_asset_class_count: number | null;
-    by_ref_count: number | null;
-    matched: string[];
+export interface CheckStatusResponse {
+    statuses: Record<string, TxStatus>;
 }
 
 
-export interface BytesEnvelope {
-    encoding: string;
-    payload: string;
+export interface InputNotResolvedDiagnostic {
+    name: string;
+    query: InputQueryDiagnostic;
+    search_space: SearchSpaceDiagnostic;
 }
 
 
 export interface InputQueryDiagnostic {
-    address: string | null;
+    address?: string | null;
     collateral: boolean;
     min_amount: Record<string, string>;
     refs: string[];
@@ -47,6 +32,12 @@ export interface InputQueryDiagnostic {
 }
 
 
+export interface MissingTxArgDiagnostic {
+    key: string;
+    type: string;
+}
+
+
 export interface ResolveParams {
     args: Record<string, any>;
     env: Record<string, any>;
@@ -54,6 +45,25 @@ export interface ResolveParams {
 }
 
 
+export interface SearchSpaceDiagnostic {
+    by_address_count?: number | null;
+    by_asset_class_count?: number | null;
+    by_ref_count?: number | null;
+    matched: string[];
+}
+
+
+export interface SubmitParams {
+    tx: BytesEnvelope;
+    witnesses: SubmitWitness[];
+}
+
+
+export interface SubmitResponse {
+    hash: string;
+}
+
+
 export interface SubmitWitness {
     key: BytesEnvelope;
     signature: BytesEnvelope;
@@ -61,26 +71,32 @@ export interface SubmitWitness {
 }
 
 
-export interface UnsupportedTirDiagnostic {
-    expected: string;
-    provided: string;
+export interface TirInfo {
+    bytecode: string;
+    encoding: string;
+    version: string;
 }
 
 
-export interface InputNotResolvedDiagnostic {
-    name: string;
-    query: InputQueryDiagnostic;
-    search_space: SearchSpaceDiagnostic;
+export interface TxEnvelope {
+    hash: string;
+    tx: string;
 }
 
 
-export interface MissingTxArgDiagnostic {
-    key: string;
-    type: string;
+export interface TxScriptFailureDiagnostic {
+    logs: string[];
 }
 
 
-export interface TxScriptFailureDiagnostic {
-    logs: string[];
+export interface TxStatus {
+    confirmations: number;
+    stage: "pending" | "propagated" | "acknowledged" | "confirmed" | "finalized" | "dropped" | "rolledBack" | "unknown";
+}
+
+
+export interface UnsupportedTirDiagnostic {
+    expected: string;
+    provided: string;
 }
 
@@ -0,0 +1,144 @@
+//! Benchmarks of the generator on a synthetic spec, far larger than the TRP
+//! one, so that regressions in the resolver and mappers stand out.
+//!
+//! Run with `cargo bench -p trp-codegen`; `--bench generate -- render/rust`
+//! narrows them down to one language.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use serde_json::{json, Map, Value};
+
+use trp_codegen::resolver::resolve_components;
+use trp_codegen::{generate, LanguageConfig, Spec};
+
+/// Components of the synthetic spec.
+const TYPES: usize = 400;
+/// Methods of the synthetic spec, each taking and returning a component.
+const METHODS: usize = 100;
+
+const LANGUAGES: &[&str] = &[
+    "ts",
+    "deno",
+    "python",
+    "go",
+    "rust",
+    "elm",
+    "php",
+    "scala",
+    "c",
+    "ocaml",
+    "proto",
+    "cddl",
+    "jsonschema",
+    "openapi",
+];
+
+/// A spec of [`TYPES`] objects referring to the ones declared before them,
+/// with the usual mix of scalars, constraints, enums, arrays, maps,
+/// nullable references and unions.
+fn synthetic_spec() -> String {
+    let reference = |i: usize| json!({ "$ref": format!("#/components/schemas/Type{}", i) });
+    let mut schemas = Map::new();
+    for i in 0..TYPES {
+        let mut properties = Map::new();
+        properties.insert("id".into(), json!({ "type": "string", "minLength": 1 }));
+        properties.insert(
+            "count".into(),
+            json!({ "type": "integer", "minimum": 0, "maximum": 1000 }),
+        );
+        properties.insert("ratio".into(), json!({ "type": "number" }));
+        properties.insert("enabled".into(), json!({ "type": "boolean" }));
+        properties.insert(
+            "mode".into(),
+            json!({ "type": "string", "enum": ["fast", "safe", "balanced"] }),
+        );
+        properties.insert(
+            "tags".into(),
+            json!({ "type": "array", "items": { "type": "string" } }),
+        );
+        properties.insert(
+            "labels".into(),
+            json!({ "type": "object", "additionalProperties": { "type": "string" } }),
+        );
+        if i > 0 {
+            properties.insert("parent".into(), reference(i - 1));
+            properties.insert(
+                "previous".into(),
+                json!({ "anyOf": [reference(i - 1), { "type": "null" }] }),
+            );
+            properties.insert(
+                "children".into(),
+                json!({ "type": "array", "items": reference(i / 2) }),
+            );
+        }
+        if i > 1 {
+            properties.insert(
+                "either".into(),
+                json!({ "oneOf": [reference(i - 2), { "type": "string" }] }),
+            );
+        }
+        schemas.insert(
+            format!("Type{}", i),
+            json!({
+                "type": "object",
+                "description": format!("Synthetic type number {}.", i),
+                "properties": properties,
+                "required": ["id", "count"],
+            }),
+        );
+    }
+
+    let methods: Vec<Value> = (0..METHODS)
+        .map(|i| {
+            json!({
+                "name": format!("bench.method{}", i),
+                "summary": format!("Synthetic method number {}.", i),
+                "tags": [{ "name": format!("group{}", i % 10) }],
+                "params": { "name": "params", "schema": reference(i * TYPES / METHODS) },
+                "result": { "name": "result", "schema": reference((i * 7) % TYPES) },
+            })
+        })
+        .collect();
+
+    json!({
+        "openrpc": "1.3.2",
+        "info": { "title": "Synthetic", "version": "1.0.0" },
+        "methods": methods,
+        "components": { "schemas": schemas },
+    })
+    .to_string()
+}
+
+fn parse(c: &mut Criterion) {
+    let source = synthetic_spec();
+    c.bench_function("parse", |b| {
+        b.iter(|| Spec::parse(black_box(source.clone()), "synthetic").unwrap())
+    });
+}
+
+fn resolve(c: &mut Criterion) {
+    let spec = Spec::parse(synthetic_spec(), "synthetic").unwrap();
+    c.bench_function("resolve", |b| {
+        b.iter(|| resolve_components(black_box(&spec.document)).unwrap())
+    });
+}
+
+/// Resolution, mapping and rendering of each language, the whole of
+/// `generate`.
+fn render(c: &mut Criterion) {
+    let spec = Spec::parse(synthetic_spec(), "synthetic").unwrap();
+    let mut group = c.benchmark_group("render");
+    for lang in LANGUAGES {
+        let config = LanguageConfig::new(lang);
+        group.bench_with_input(BenchmarkId::from_parameter(lang), &config, |b, config| {
+            b.iter(|| generate(black_box(&spec), config).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20);
+    targets = parse, resolve, render
+}
+criterion_main!(benches);
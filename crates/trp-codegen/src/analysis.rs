@@ -0,0 +1,177 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+
+use crate::openrpc::{Method, OpenRpc};
+use crate::resolver::ref_to_name;
+
+/// Component names referenced directly by each component schema.
+pub type RefGraph = BTreeMap<String, BTreeSet<String>>;
+
+pub fn component_graph(spec: &OpenRpc) -> RefGraph {
+    let mut graph = RefGraph::new();
+    if let Some(schemas) = spec.schemas() {
+        for (name, schema) in schemas {
+            let value = serde_json::to_value(schema).unwrap_or(Value::Null);
+            graph.insert(name.clone(), collect_refs(&value));
+        }
+    }
+    graph
+}
+
+/// Component names referenced directly by a method's params, result and
+/// error data.
+pub fn method_refs(method: &Method) -> BTreeSet<String> {
+    let mut refs = BTreeSet::new();
+    let schemas = method
+        .params
+        .iter()
+        .chain(method.result.iter())
+        .map(|d| serde_json::to_value(&d.schema).unwrap_or(Value::Null));
+    for value in schemas {
+        refs.extend(collect_refs(&value));
+    }
+    for error in &method.errors {
+        if let Some(data) = &error.data {
+            refs.extend(collect_refs(data));
+        }
+    }
+    refs
+}
+
+/// Every component reachable from at least one method.
+pub fn reachable_components(spec: &OpenRpc, graph: &RefGraph) -> BTreeSet<String> {
+    let mut seen = BTreeSet::new();
+    let mut queue: VecDeque<String> = spec.methods.iter().flat_map(method_refs).collect();
+    while let Some(name) = queue.pop_front() {
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        if let Some(next) = graph.get(&name) {
+            queue.extend(next.iter().cloned());
+        }
+    }
+    seen
+}
+
+pub fn collect_refs(value: &Value) -> BTreeSet<String> {
+    let mut refs = BTreeSet::new();
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    if let Ok(name) = ref_to_name(reference) {
+                        refs.insert(name);
+                    }
+                }
+                stack.extend(map.values());
+            }
+            Value::Array(items) => stack.extend(items),
+            _ => {}
+        }
+    }
+    refs
+}
+
+/// Reports schemas (components and their properties) that no value can ever
+/// satisfy, e.g. `allOf` branches demanding disjoint types.
+pub fn unsatisfiable_schemas(components: &HashMap<String, Schema>) -> Vec<(String, String)> {
+    let mut findings = Vec::new();
+    let mut names: Vec<&String> = components.keys().collect();
+    names.sort();
+    for name in names {
+        if let Schema::Object(obj) = &components[name] {
+            check_satisfiable(name, obj, components, &mut findings);
+        }
+    }
+    findings
+}
+
+fn check_satisfiable(
+    path: &str,
+    schema: &SchemaObject,
+    components: &HashMap<String, Schema>,
+    out: &mut Vec<(String, String)>,
+) {
+    if let Some(all_of) = schema.subschemas.as_ref().and_then(|s| s.all_of.as_ref()) {
+        let mut branches = vec![schema];
+        branches.extend(all_of.iter().filter_map(|s| resolve(s, components)));
+
+        let mut allowed: Option<BTreeSet<InstanceType>> = None;
+        for branch in &branches {
+            if let Some(types) = expanded_types(branch) {
+                allowed = Some(match allowed {
+                    Some(current) => current.intersection(&types).copied().collect(),
+                    None => types,
+                });
+            }
+        }
+        if allowed.is_some_and(|types| types.is_empty()) {
+            out.push((
+                path.to_string(),
+                "allOf branches require disjoint types".to_string(),
+            ));
+        }
+
+        let consts: BTreeSet<String> = branches
+            .iter()
+            .filter_map(|b| b.const_value.as_ref().map(Value::to_string))
+            .collect();
+        if consts.len() > 1 {
+            out.push((
+                path.to_string(),
+                "allOf branches declare conflicting const values".to_string(),
+            ));
+        }
+    }
+
+    if let Some(object) = &schema.object {
+        for name in &object.required {
+            if let Some(Schema::Bool(false)) = object.properties.get(name) {
+                out.push((
+                    format!("{}.{}", path, name),
+                    "required property can never be present".to_string(),
+                ));
+            }
+        }
+        for (name, prop) in &object.properties {
+            if let Schema::Object(prop) = prop {
+                check_satisfiable(&format!("{}.{}", path, name), prop, components, out);
+            }
+        }
+    }
+}
+
+fn resolve<'a>(
+    schema: &'a Schema,
+    components: &'a HashMap<String, Schema>,
+) -> Option<&'a SchemaObject> {
+    let Schema::Object(obj) = schema else {
+        return None;
+    };
+    match &obj.reference {
+        Some(reference) => match components.get(&ref_to_name(reference).ok()?)? {
+            Schema::Object(target) => Some(target),
+            Schema::Bool(_) => None,
+        },
+        None => Some(obj),
+    }
+}
+
+// `number` admits integers, so it expands to both for intersection purposes.
+fn expanded_types(schema: &SchemaObject) -> Option<BTreeSet<InstanceType>> {
+    let types = match schema.instance_type.as_ref()? {
+        SingleOrVec::Single(t) => vec![**t],
+        SingleOrVec::Vec(list) => list.clone(),
+    };
+    let mut set = BTreeSet::new();
+    for t in types {
+        set.insert(t);
+        if t == InstanceType::Number {
+            set.insert(InstanceType::Integer);
+        }
+    }
+    Some(set)
+}
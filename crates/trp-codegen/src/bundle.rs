@@ -0,0 +1,184 @@
+//! Specs split over a directory, for reviewable diffs: a root `openrpc.json`
+//! whose methods and `components.schemas` entries are `$ref`s to files of
+//! their own under `methods/` and `components/`.
+//!
+//! ```text
+//! specs/trp/
+//!   openrpc.json      {"methods": [{"$ref": "methods/trp.resolve.json"}, ...],
+//!                      "components": {"schemas": {"TxEnvelope": {"$ref": "components/TxEnvelope.json"}, ...}}}
+//!   methods/trp.resolve.json
+//!   components/TxEnvelope.json
+//! ```
+//!
+//! Merging replaces each file reference with the file, in place, so the
+//! bundled document keeps the root's order and splitting it again gives
+//! back the same files. References starting with `#` are left alone.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+/// The file of a split spec holding everything but its methods and schemas.
+pub const ROOT: &str = "openrpc.json";
+pub const METHODS: &str = "methods";
+pub const COMPONENTS: &str = "components";
+
+/// Merges the split spec in `dir` into a single document.
+pub fn merge(dir: &Path) -> Result<Value> {
+    merge_with(|file| {
+        let path = dir.join(file);
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))
+    })
+}
+
+/// Merges a split spec whose files, relative to its directory, are read by
+/// `read`, for layouts that don't live on disk (e.g. at a git revision).
+pub fn merge_with(read: impl Fn(&Path) -> Result<String>) -> Result<Value> {
+    let parse = |file: &Path| -> Result<Value> {
+        serde_json::from_str(&read(file)?)
+            .with_context(|| format!("failed to parse {}", file.display()))
+    };
+    let mut spec = parse(Path::new(ROOT))?;
+    if let Some(methods) = spec.get_mut("methods").and_then(Value::as_array_mut) {
+        for method in methods {
+            if let Some(file) = file_ref(method)? {
+                *method = parse(&file)?;
+            }
+        }
+    }
+    if let Some(schemas) = spec
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+    {
+        for schema in schemas.values_mut() {
+            if let Some(file) = file_ref(schema)? {
+                *schema = parse(&file)?;
+            }
+        }
+    }
+    Ok(spec)
+}
+
+/// The files `spec` splits into, relative to the spec's directory: the
+/// root, then a file per method and per schema.
+pub fn split(spec: &Value) -> Result<Vec<(PathBuf, Value)>> {
+    let mut root = spec.clone();
+    let mut files = Vec::new();
+    if let Some(methods) = root.get_mut("methods").and_then(Value::as_array_mut) {
+        for method in methods {
+            let name = method
+                .get("name")
+                .and_then(Value::as_str)
+                .context("method without a name")?;
+            let file = Path::new(METHODS).join(file_name(name)?);
+            let method = std::mem::replace(method, reference(&file));
+            files.push((file, method));
+        }
+    }
+    if let Some(schemas) = root
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+    {
+        for (name, schema) in schemas.iter_mut() {
+            let file = Path::new(COMPONENTS).join(file_name(name)?);
+            let schema = std::mem::replace(schema, reference(&file));
+            files.push((file, schema));
+        }
+    }
+    files.insert(0, (PathBuf::from(ROOT), root));
+    Ok(files)
+}
+
+/// Writes `spec` split into `dir`, removing the method and schema files of
+/// an earlier split that are no longer part of it.
+pub fn write(dir: &Path, spec: &Value) -> Result<()> {
+    let files = split(spec)?;
+    for sub in [METHODS, COMPONENTS] {
+        let sub = dir.join(sub);
+        fs::create_dir_all(&sub)
+            .with_context(|| format!("failed to create directory {}", sub.display()))?;
+        for stale in json_files(&sub)? {
+            let relative = stale.strip_prefix(dir).unwrap_or(&stale);
+            if !files.iter().any(|(file, _)| file == relative) {
+                fs::remove_file(&stale)
+                    .with_context(|| format!("failed to remove {}", stale.display()))?;
+            }
+        }
+    }
+    for (file, value) in files {
+        let path = dir.join(file);
+        let data = serde_json::to_string_pretty(&value).context("failed to serialize spec")?;
+        fs::write(&path, data).with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// The files of the split spec in `dir` that merging it reads.
+pub fn files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![dir.join(ROOT)];
+    for sub in [METHODS, COMPONENTS] {
+        files.extend(json_files(&dir.join(sub))?);
+    }
+    Ok(files)
+}
+
+/// The file `value` stands for, when it is nothing but a `$ref` to one.
+fn file_ref(value: &Value) -> Result<Option<PathBuf>> {
+    let Some(object) = value.as_object().filter(|o| o.len() == 1) else {
+        return Ok(None);
+    };
+    let Some(target) = object.get("$ref").and_then(Value::as_str) else {
+        return Ok(None);
+    };
+    if target.starts_with('#') {
+        return Ok(None);
+    }
+    let file = PathBuf::from(target);
+    if !file
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        anyhow::bail!("{} is outside of the spec's directory", target);
+    }
+    Ok(Some(file))
+}
+
+fn reference(file: &Path) -> Value {
+    let mut object = Map::new();
+    // References are URIs, separated by slashes on every platform.
+    let target = file
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    object.insert("$ref".to_string(), Value::String(target));
+    Value::Object(object)
+}
+
+/// `{name}.json`, for names that can serve as file names.
+fn file_name(name: &str) -> Result<String> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        anyhow::bail!("{} can't be the name of a file of a split spec", name);
+    }
+    Ok(format!("{}.json", name))
+}
+
+/// The `.json` files directly under `dir`, sorted; none if it's missing.
+fn json_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("failed to list {}", dir.display()))?
+            .path();
+        if path.extension().is_some_and(|e| e == "json") && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
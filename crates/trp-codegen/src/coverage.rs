@@ -0,0 +1,202 @@
+//! How faithfully each language's bindings represent the schema constructs
+//! of a spec. Every property of every component is mapped on its own, and
+//! the constructs its mapper reports falling back to the dynamic JSON type
+//! for (see [`LanguageContext::degrade`]) are partially supported there.
+//! Constructs that no mapper reads are unsupported everywhere. JSON Schema
+//! and OpenAPI documents copy the schemas and aren't covered.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use schemars::schema::SchemaObject;
+use serde::Serialize;
+
+use crate::mapper::naming::Naming;
+use crate::mapper::{
+    self, c, cddl, elm, field_refs, go, nullable_inner, ocaml, php, proto, python, rust, scala, ts,
+    union_options, Construct, LanguageContext,
+};
+use crate::openrpc::OpenRpc;
+use crate::resolver::{
+    array_item_schema, array_tuple_items, object_additional_properties, object_pattern_properties,
+    resolve_components, visit_schemas, ResolvedField, ResolvedType,
+};
+
+/// Languages the report covers, in the order of its columns.
+pub const LANGUAGES: &[&str] = &[
+    "ts", "python", "go", "rust", "elm", "php", "scala", "ocaml", "c", "proto", "cddl",
+];
+
+/// How a language's bindings represent a construct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Support {
+    /// Mapped to a type of its own.
+    Full,
+    /// Mapped to the dynamic JSON type, losing what the schema says.
+    Partial,
+    /// Ignored: values the schema rejects pass unnoticed.
+    Unsupported,
+}
+
+/// A construct used by a property of a component, and its support in each
+/// of the [`LANGUAGES`].
+#[derive(Debug, Serialize)]
+pub struct Entry {
+    /// `Component.property`.
+    pub location: String,
+    pub construct: Construct,
+    pub support: BTreeMap<String, Support>,
+}
+
+impl Entry {
+    /// Whether every language supports the construct fully.
+    pub fn is_full(&self) -> bool {
+        self.support.values().all(|s| *s == Support::Full)
+    }
+}
+
+/// The constructs used by the properties of the components of `spec`, in
+/// component and property order.
+pub fn report(spec: &OpenRpc, languages: &[&str]) -> Result<Vec<Entry>> {
+    let types = resolve_components(spec)?;
+    let naming = Naming::default();
+    let contexts: Vec<(&str, LanguageContext)> = languages
+        .iter()
+        .map(|lang| (*lang, mapper::build_context(&types, lang, &naming)))
+        .collect();
+
+    let mut entries = Vec::new();
+    for ty in &types {
+        for field in &ty.fields {
+            let mut degraded: BTreeMap<&str, BTreeSet<Construct>> = BTreeMap::new();
+            for (lang, ctx) in &contexts {
+                map_field(lang, ty, field, &types, ctx);
+                degraded.insert(lang, ctx.take_degraded().into_iter().collect());
+            }
+            let mut found = constructs(&field.schema);
+            found.extend(degraded.values().flatten());
+            for construct in found {
+                let support = contexts
+                    .iter()
+                    .map(|(lang, _)| {
+                        let support = if construct.is_ignored() {
+                            Support::Unsupported
+                        } else if degraded[lang].contains(&construct) {
+                            Support::Partial
+                        } else {
+                            Support::Full
+                        };
+                        (lang.to_string(), support)
+                    })
+                    .collect();
+                entries.push(Entry {
+                    location: format!("{}.{}", ty.name, field.name),
+                    construct,
+                    support,
+                });
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Maps `field` the way the bindings of `lang` do, for its mapper to report
+/// what it degrades.
+fn map_field(
+    lang: &str,
+    ty: &ResolvedType,
+    field: &ResolvedField,
+    types: &[ResolvedType],
+    ctx: &LanguageContext,
+) {
+    let schema = &field.schema;
+    match lang {
+        "ts" => {
+            ts::map_type(schema, ctx);
+        }
+        "python" => {
+            python::map_type(schema, ctx);
+        }
+        "go" => {
+            go::map_type(schema, ctx);
+        }
+        "rust" => {
+            rust::map_type(schema, ctx);
+        }
+        "elm" => {
+            elm::map_type(schema, ctx);
+        }
+        "php" => {
+            php::map_type(schema, ctx);
+        }
+        "scala" => {
+            scala::map_type(schema, ctx, &ty.name, &mut Vec::new());
+        }
+        "ocaml" => {
+            ocaml::map_field(field, &ty.name, types, ctx);
+        }
+        "c" => {
+            let graph = field_refs(types);
+            c::members(field, &ty.name, &graph, ctx, &mut BTreeSet::new());
+        }
+        "proto" => {
+            proto::shape(field, ctx);
+        }
+        "cddl" => {
+            cddl::map_type(schema, ctx);
+        }
+        _ => {}
+    }
+}
+
+/// The constructs `schema` and the schemas nested in it use.
+fn constructs(schema: &SchemaObject) -> BTreeSet<Construct> {
+    let mut found = BTreeSet::new();
+    visit_schemas(schema, &mut |schema| {
+        if nullable_inner(schema).is_some() {
+            found.insert(Construct::Nullable);
+        }
+        if let Some(subschemas) = &schema.subschemas {
+            if union_options(schema).is_some() {
+                found.insert(Construct::Union);
+            }
+            if subschemas.all_of.is_some() {
+                found.insert(Construct::Composition);
+            }
+            if subschemas.not.is_some() {
+                found.insert(Construct::Not);
+            }
+            if subschemas.if_schema.is_some() {
+                found.insert(Construct::Conditional);
+            }
+        }
+        if let Some(array) = schema.array.as_deref() {
+            if array_tuple_items(array).is_some() {
+                found.insert(Construct::Tuple);
+            } else if array_item_schema(array).is_some() {
+                found.insert(Construct::Array);
+            }
+            if array.contains.is_some() {
+                found.insert(Construct::Contains);
+            }
+        }
+        if let Some(object) = schema.object.as_deref() {
+            if object_additional_properties(object).is_some()
+                || object_pattern_properties(object).is_some()
+            {
+                found.insert(Construct::Map);
+            }
+            if object.property_names.is_some() {
+                found.insert(Construct::PropertyNames);
+            }
+        }
+        if schema.enum_values.is_some() {
+            found.insert(Construct::Enum);
+        }
+        if schema.const_value.is_some() {
+            found.insert(Construct::Const);
+        }
+    });
+    found
+}
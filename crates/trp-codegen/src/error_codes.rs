@@ -0,0 +1,330 @@
+//! TRP's registry of JSON-RPC error codes, split into ranges so that the
+//! errors of the protocol, of transports and of the spec's methods never
+//! share a code:
+//!
+//! - protocol, -32768 to -32100: JSON-RPC's pre-defined errors (parse
+//!   error, invalid request, method not found, invalid params, internal
+//!   error), the rest being reserved for future ones;
+//! - transport, -32099 to -32004: JSON-RPC's implementation-defined server
+//!   errors, answered by servers, proxies and their JSON-RPC library
+//!   whatever the method, such as rate limiting or oversized requests;
+//! - application, -32003 to -32000: the errors the spec's methods declare,
+//!   the first block of the implementation-defined server errors.
+//!
+//! The codes taken by the protocol and by the generated servers are listed
+//! in [`RESERVED`], along with request cancellation's -32800, which follows
+//! LSP outside of JSON-RPC's reserved range. Every binding gets the registry
+//! as an `error_codes` module, completed with the spec's declared errors.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::mapper;
+use crate::openrpc::OpenRpc;
+
+/// Who answers errors of a given code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorClass {
+    Protocol,
+    Transport,
+    Application,
+}
+
+impl ErrorClass {
+    pub const ALL: [ErrorClass; 3] = [
+        ErrorClass::Protocol,
+        ErrorClass::Transport,
+        ErrorClass::Application,
+    ];
+
+    /// The first and last code of the class's range.
+    pub fn range(self) -> (i64, i64) {
+        match self {
+            ErrorClass::Protocol => (-32768, -32100),
+            ErrorClass::Transport => (-32099, -32004),
+            ErrorClass::Application => (-32003, -32000),
+        }
+    }
+
+    pub fn start(self) -> i64 {
+        self.range().0
+    }
+
+    pub fn end(self) -> i64 {
+        self.range().1
+    }
+
+    /// The class of `code`: that of its entry in [`RESERVED`], or else of
+    /// the range holding it.
+    pub fn of(code: i64) -> Option<Self> {
+        if let Some(reserved) = RESERVED.iter().find(|r| r.code == code) {
+            return Some(reserved.class);
+        }
+        Self::ALL.into_iter().find(|class| {
+            let (start, end) = class.range();
+            (start..=end).contains(&code)
+        })
+    }
+
+    /// `protocol`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorClass::Protocol => "protocol",
+            ErrorClass::Transport => "transport",
+            ErrorClass::Application => "application",
+        }
+    }
+
+    /// `Protocol`.
+    pub fn pascal_name(self) -> &'static str {
+        match self {
+            ErrorClass::Protocol => "Protocol",
+            ErrorClass::Transport => "Transport",
+            ErrorClass::Application => "Application",
+        }
+    }
+
+    /// `PROTOCOL`.
+    pub fn screaming_name(self) -> String {
+        self.name().to_uppercase()
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ErrorClass::Protocol => "Errors of JSON-RPC itself, reserved by it.",
+            ErrorClass::Transport => {
+                "Errors answered by servers, proxies and their JSON-RPC library, whatever the method."
+            }
+            ErrorClass::Application => "Errors the spec's methods declare.",
+        }
+    }
+}
+
+/// A code the protocol or the generated servers answer, which the spec's
+/// methods must not declare.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct ReservedCode {
+    pub code: i64,
+    /// `METHOD_NOT_FOUND`.
+    pub name: &'static str,
+    pub message: &'static str,
+    pub class: ErrorClass,
+}
+
+impl ReservedCode {
+    /// Whether the code lies in the range of its class, which all but
+    /// request cancellation's do.
+    pub fn in_range(&self) -> bool {
+        let (start, end) = self.class.range();
+        (start..=end).contains(&self.code)
+    }
+
+    /// The message as a double quoted string literal.
+    pub fn quoted_message(&self) -> String {
+        serde_json::to_string(self.message).unwrap_or_default()
+    }
+
+    /// `MethodNotFound`.
+    pub fn pascal_name(&self) -> String {
+        mapper::sanitize_identifier(&self.name.to_lowercase())
+    }
+}
+
+const fn reserved(
+    code: i64,
+    name: &'static str,
+    message: &'static str,
+    class: ErrorClass,
+) -> ReservedCode {
+    ReservedCode {
+        code,
+        name,
+        message,
+        class,
+    }
+}
+
+/// The codes taken outside of the application range, in code order within
+/// each class. The transport ones are those of jsonrpsee, which the Rust
+/// server is built on, and of the generated server and proxy. jsonrpsee's
+/// -32000 and -32001 aren't among them: it only answers them for errors
+/// the handlers build, which the generated server's never do.
+pub const RESERVED: &[ReservedCode] = &[
+    reserved(-32700, "PARSE_ERROR", "Parse error", ErrorClass::Protocol),
+    reserved(
+        -32600,
+        "INVALID_REQUEST",
+        "Invalid request",
+        ErrorClass::Protocol,
+    ),
+    reserved(
+        -32601,
+        "METHOD_NOT_FOUND",
+        "Method not found",
+        ErrorClass::Protocol,
+    ),
+    reserved(
+        -32602,
+        "INVALID_PARAMS",
+        "Invalid params",
+        ErrorClass::Protocol,
+    ),
+    reserved(
+        -32603,
+        "INTERNAL_ERROR",
+        "Internal error",
+        ErrorClass::Protocol,
+    ),
+    reserved(
+        -32004,
+        "UPSTREAM_UNAVAILABLE",
+        "No upstream available",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32005,
+        "BATCHES_NOT_SUPPORTED",
+        "Batched requests are not supported",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32006,
+        "TOO_MANY_SUBSCRIPTIONS",
+        "Too many subscriptions",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32007,
+        "OVERSIZED_REQUEST",
+        "Request is too big",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32008,
+        "OVERSIZED_RESPONSE",
+        "Response is too big",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32009,
+        "SERVER_IS_BUSY",
+        "Server is busy",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32010,
+        "TOO_BIG_BATCH_REQUEST",
+        "Batch request is too big",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32011,
+        "TOO_BIG_BATCH_RESPONSE",
+        "Batch response is too big",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32012,
+        "RATE_LIMITED",
+        "Rate limit exceeded",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32013,
+        "IDEMPOTENCY_CONFLICT",
+        "Idempotency key conflict",
+        ErrorClass::Transport,
+    ),
+    reserved(
+        -32800,
+        "REQUEST_CANCELLED",
+        "Request cancelled",
+        ErrorClass::Transport,
+    ),
+];
+
+/// How much a problem found by [`check`] matters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// A code outside of the application range, which is only reported:
+    /// renumbering a declared error breaks the servers and clients already
+    /// answering and matching it.
+    Warning,
+    /// A code declared with different messages, which clients can't tell
+    /// apart.
+    Error,
+}
+
+/// A problem with an error declared by a method of the spec.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// Where the error is declared, as `methods.{method}.errors.{code}`.
+    pub location: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Problems with the errors the methods of `spec` declare: codes outside of
+/// the application range, reserved ones included, as warnings, and codes
+/// declared with different messages by different methods, as errors.
+pub fn check(spec: &OpenRpc) -> Vec<Problem> {
+    let (start, end) = ErrorClass::Application.range();
+    let mut problems = Vec::new();
+    let mut messages: BTreeMap<i64, (&str, &str)> = BTreeMap::new();
+    for method in &spec.methods {
+        for error in &method.errors {
+            let location = format!("methods.{}.errors.{}", method.name, error.code);
+            let range = if let Some(reserved) = RESERVED.iter().find(|r| r.code == error.code) {
+                Some(format!(
+                    "{} is reserved for the {} error {}; declare method errors from {} to {}",
+                    error.code,
+                    reserved.class.name(),
+                    reserved.name,
+                    start,
+                    end
+                ))
+            } else {
+                match ErrorClass::of(error.code) {
+                    Some(ErrorClass::Application) => None,
+                    Some(class) => Some(format!(
+                        "{} is in the range of {} errors ({} to {}); declare method errors from {} to {}",
+                        error.code,
+                        class.name(),
+                        class.start(),
+                        class.end(),
+                        start,
+                        end
+                    )),
+                    None => Some(format!(
+                        "{} is outside of the range of application errors ({} to {})",
+                        error.code, start, end
+                    )),
+                }
+            };
+            if let Some(message) = range {
+                problems.push(Problem {
+                    location: location.clone(),
+                    severity: Severity::Warning,
+                    message,
+                });
+            }
+            match messages.get(&error.code) {
+                Some((other, message)) if *message != error.message => problems.push(Problem {
+                    location,
+                    severity: Severity::Error,
+                    message: format!(
+                        "{} is declared as \"{}\" by {}, but as \"{}\" here",
+                        error.code, message, other, error.message
+                    ),
+                }),
+                Some(_) => {}
+                None => {
+                    messages.insert(error.code, (&method.name, &error.message));
+                }
+            }
+        }
+    }
+    problems
+}
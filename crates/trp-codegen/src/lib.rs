@@ -0,0 +1,1494 @@
+//! Generates TypeScript (for Node or Deno), Python, Go, Rust, Elm, PHP, Scala
+//! and OCaml bindings, a C header, protobuf messages, a CDDL description, plain JSON Schema
+//! documents and an OpenAPI document from the TRP OpenRPC spec. The `xtask gen` command is a thin CLI over [`generate`];
+//! tools that want to embed binding generation can call it directly.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+pub mod analysis;
+pub mod bundle;
+pub mod coverage;
+pub mod error_codes;
+pub mod mapper;
+pub mod metadata;
+pub mod openrpc;
+pub mod resolver;
+pub mod split;
+
+mod render;
+
+use mapper::naming::Naming;
+use openrpc::OpenRpc;
+use split::Split;
+
+/// A parsed spec together with the document it was parsed from, which keeps
+/// the declared key order and can be embedded in the bindings.
+#[derive(Debug)]
+pub struct Spec {
+    pub document: OpenRpc,
+    pub raw: Value,
+    pub source: String,
+}
+
+impl Spec {
+    /// Loads a spec from a file, or from a directory holding a split spec
+    /// (see [`bundle`]).
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(openrpc::read(path)?, &path.display().to_string())
+    }
+
+    /// Parses a spec from memory; `origin` only serves error reporting.
+    pub fn parse(source: String, origin: &str) -> Result<Self> {
+        let document = openrpc::parse(&source, origin)?;
+        let raw =
+            serde_json::from_str(&source).with_context(|| format!("failed to parse {}", origin))?;
+        Ok(Self {
+            document,
+            raw,
+            source,
+        })
+    }
+}
+
+/// What to generate for one language.
+#[derive(Clone, Debug)]
+pub struct LanguageConfig {
+    /// One of `ts` (or `typescript`), `deno`, `python`, `go`, `rust`, `elm`, `php`,
+    /// `scala`, `ocaml`, `c`, `proto`, `cddl`, `jsonschema`, `openapi`,
+    /// `asyncapi` or `wasm`.
+    pub language: String,
+    /// Reject unknown fields when decoding types declared with
+    /// `additionalProperties: false`.
+    pub strict: bool,
+    pub split: Split,
+    /// Embed the spec document as an `OPENRPC_SPEC` constant.
+    pub embed_spec: bool,
+    /// For Rust, lay the files out as a crate with this name: a
+    /// `Cargo.toml` next to a `src` directory holding the modules.
+    pub rust_crate: Option<String>,
+    /// For Rust, also declare a `borrowed` module with counterparts of the
+    /// types holding `Cow<'a, str>` strings, for zero-copy deserialization.
+    pub rust_borrowed: bool,
+    /// For Rust, the spec of the previous major version, whose types are
+    /// declared in a module named after it (`v1`), along with conversions
+    /// to and from the current types where their fields are compatible.
+    pub rust_previous: Option<Arc<Spec>>,
+    /// For TypeScript, also generate `react.ts`: a React hook per method
+    /// (`useTrpResolve`) calling it with a client, tracking the loading and
+    /// error state of the last call and aborting it on unmount.
+    pub ts_react: bool,
+    /// For wasm, the crate wrapping the Rust client with wasm-bindgen and
+    /// the crate of the Rust types it wraps.
+    pub wasm: WasmCrate,
+    /// For Go, declare the JSON-RPC envelopes as generic types
+    /// (`Request[P]`, `Response[R]`, `Call[P, R]`), which need Go 1.18.
+    /// Without them, `rpc.go` falls back to envelopes holding raw JSON.
+    pub go_generics: bool,
+    /// For Go, the module path of a `go.mod` to write next to the files,
+    /// suffixed with the major version of the spec from 2 on.
+    pub go_module: Option<String>,
+    /// For Go, the version of the `go` directive of `go.mod`, by default
+    /// the oldest one the files build with.
+    pub go_version: Option<String>,
+    /// Naming conventions overriding the ones derived for the language,
+    /// and those the spec declares in an `x-{lang}-naming` extension.
+    pub naming: Naming,
+    /// Import lines emitted as is at the top of the types files, for the
+    /// external types fields are mapped to with `x-{lang}-type`. They come
+    /// before those the spec lists in an `x-{lang}-imports` extension.
+    pub imports: Vec<String>,
+    /// For protobuf, the field numbers of an earlier run, which new numbers
+    /// are assigned around so that existing messages stay compatible.
+    pub proto_numbers: mapper::proto::FieldNumbers,
+}
+
+impl LanguageConfig {
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.to_lowercase(),
+            strict: false,
+            split: Split::None,
+            embed_spec: false,
+            rust_crate: None,
+            rust_borrowed: false,
+            rust_previous: None,
+            ts_react: false,
+            wasm: WasmCrate::default(),
+            go_generics: true,
+            go_module: None,
+            go_version: None,
+            naming: Naming::default(),
+            imports: Vec::new(),
+            proto_numbers: Default::default(),
+        }
+    }
+}
+
+/// Names of the wasm-bindgen crate and of the Rust types crate it depends
+/// on, along with the path of the latter relative to the former when they
+/// live in the same workspace. Without a path, the wasm crate depends on
+/// the types crate published for the spec's version.
+#[derive(Clone, Debug)]
+pub struct WasmCrate {
+    pub name: String,
+    pub types: String,
+    pub types_path: Option<String>,
+}
+
+impl Default for WasmCrate {
+    fn default() -> Self {
+        Self {
+            name: "trp-wasm".to_string(),
+            types: "trp-types".to_string(),
+            types_path: None,
+        }
+    }
+}
+
+/// Languages whose naming conventions and imports can be configured.
+const CONFIGURABLE_LANGUAGES: &[&str] = &["ts", "typescript", "deno", "python", "go", "rust"];
+
+/// A rendered file, with its path relative to the language's output
+/// directory.
+#[derive(Clone, Debug)]
+pub struct GeneratedFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// Renders the bindings of one language.
+///
+/// Resolution and rendering are traced as the `resolve` and `render` spans,
+/// for callers with a `tracing` subscriber to time them.
+pub fn generate(spec: &Spec, config: &LanguageConfig) -> Result<Vec<GeneratedFile>> {
+    let types = tracing::debug_span!("resolve")
+        .in_scope(|| resolver::resolve_components(&spec.document))?;
+    let modules = match config.split {
+        Split::None => None,
+        Split::PerTag => Some(split::by_tag(&spec.document, &types)),
+        Split::PerType => Some(split::by_type(&types)),
+    };
+    let mut meta = metadata::collect(&spec.raw);
+    // Crate servers answer `rpc.discover` with the spec.
+    if config.embed_spec || (config.rust_crate.is_some() && config.language == "rust") {
+        meta.spec = Some(spec.source.clone());
+    }
+    // The wasm crate refers to the types as the Rust crate it wraps names
+    // them.
+    let language = match config.language.as_str() {
+        "wasm" => "rust",
+        language => language,
+    };
+    let naming = config.naming.or(Naming::from_spec(&spec.raw, language)?);
+    if naming.is_set() && !CONFIGURABLE_LANGUAGES.contains(&language) {
+        anyhow::bail!("naming conventions can't be configured for {}", language);
+    }
+    if naming.fields.is_some() && matches!(language, "ts" | "typescript" | "deno") {
+        // Properties of TypeScript interfaces are the JSON keys themselves.
+        anyhow::bail!("TypeScript field names can't be configured");
+    }
+    let mut imports = config.imports.clone();
+    for line in spec_imports(&spec.raw, language)? {
+        if !imports.contains(&line) {
+            imports.push(line);
+        }
+    }
+    if !imports.is_empty() && !CONFIGURABLE_LANGUAGES.contains(&language) {
+        anyhow::bail!("imports can't be configured for {}", language);
+    }
+    if config.go_version.is_some() && config.go_module.is_none() {
+        anyhow::bail!("a Go version needs a Go module to write go.mod for");
+    }
+    let ctx = mapper::build_context(&types, language, &naming).with_imports(imports.clone());
+    let previous = match (&config.rust_previous, config.language.as_str()) {
+        (Some(previous), "rust") => Some(previous_version(
+            previous,
+            meta.version.as_deref(),
+            &naming,
+            imports,
+        )?),
+        (Some(_), language) => {
+            anyhow::bail!("previous spec versions are Rust only, not {}", language)
+        }
+        (None, _) => None,
+    };
+
+    let _render = tracing::debug_span!("render").entered();
+    let mut files = render::render_language(
+        &config.language,
+        &types,
+        modules.as_deref(),
+        &ctx,
+        &meta,
+        config,
+        previous.as_ref(),
+    )?;
+    if let (Some(name), "rust") = (&config.rust_crate, config.language.as_str()) {
+        // The crate gets a README of its own, at its root.
+        files.retain(|(path, _)| path != "README.md");
+        for (path, _) in &mut files {
+            *path = format!("src/{}", path);
+        }
+        files.extend(render::render_rust_crate(name, &types, &meta, &ctx)?);
+    }
+    Ok(files
+        .into_iter()
+        .map(|(path, contents)| GeneratedFile { path, contents })
+        .collect())
+}
+
+/// Resolves the types of the spec of an earlier major version than
+/// `current`, to be rendered next to the current ones.
+fn previous_version(
+    spec: &Spec,
+    current: Option<&str>,
+    naming: &Naming,
+    imports: Vec<String>,
+) -> Result<render::Previous> {
+    let module_of = |version: Option<&str>, which: &str| {
+        version
+            .and_then(mapper::rust::version_module)
+            .with_context(|| format!("the {} spec has no semantic version", which))
+    };
+    let module = module_of(spec.document.version(), "previous")?;
+    let current = module_of(current, "current")?;
+    if module == current {
+        anyhow::bail!("the previous spec has the same major version as the current one");
+    }
+    let types = resolver::resolve_components(&spec.document)?;
+    let ctx = mapper::build_context(&types, "rust", naming).with_imports(imports);
+    Ok(render::Previous {
+        module,
+        current,
+        types,
+        ctx,
+    })
+}
+
+/// Import lines the spec lists for `lang` in an `x-{lang}-imports`
+/// extension at its root.
+fn spec_imports(raw: &Value, lang: &str) -> Result<Vec<String>> {
+    let lang = match lang {
+        "typescript" | "deno" => "ts",
+        other => other,
+    };
+    let key = format!("x-{}-imports", lang);
+    match raw.get(&key) {
+        Some(value) => serde_json::from_value(value.clone())
+            .with_context(|| format!("invalid {} in spec, expected a list of lines", key)),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::naming;
+    use serde_json::json;
+
+    /// Covers enums, unions, arrays, maps, optional fields and recursion.
+    const FIXTURE: &str = include_str!("../tests/fixtures/spec.json");
+
+    fn assert_snapshots(lang: &str) {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        for file in generate(&spec, &LanguageConfig::new(lang)).unwrap() {
+            insta::assert_snapshot!(format!("{}_{}", lang, file.path), file.contents);
+        }
+    }
+
+    #[test]
+    fn ts() {
+        assert_snapshots("ts");
+    }
+
+    /// The Deno flavor renders the `ts` templates; only its imports and
+    /// package manifests differ.
+    #[test]
+    fn deno() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        for file in generate(&spec, &LanguageConfig::new("deno")).unwrap() {
+            if file.path.ends_with(".json") {
+                insta::assert_snapshot!(format!("deno_{}", file.path), file.contents);
+            }
+            for line in file.contents.lines().filter(|l| l.contains(" from \"./")) {
+                assert!(line.ends_with(".ts\";"), "{}: {}", file.path, line);
+            }
+        }
+    }
+
+    #[test]
+    fn python() {
+        assert_snapshots("python");
+    }
+
+    /// Split Python packages get a stub per module, the package stub
+    /// re-exporting their types as the package does.
+    #[test]
+    fn python_stubs() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let config = LanguageConfig {
+            split: Split::PerType,
+            ..LanguageConfig::new("python")
+        };
+        let files = generate(&spec, &config).unwrap();
+        let file = |path: &str| &files.iter().find(|f| f.path == path).unwrap().contents;
+        let package = file("types/__init__.pyi");
+        assert!(package.contains("from .walk_params import WalkParamsOrder, WalkParams\n"));
+        assert!(package.contains("    \"WalkParams\",\n"));
+        let walk = file("types/walk_params.pyi");
+        assert!(walk.contains("from .node import Node\n"));
+        assert!(walk.contains("        root: Node,\n    ) -> None: ..."));
+        assert_eq!(file("py.typed"), "");
+        assert!(file("__init__.py")
+            .contains("from . import address, constants, error_codes, errors, types, utxo"));
+    }
+
+    /// `pyproject.toml` spells the spec's version the PEP 440 way.
+    #[test]
+    fn python_versions() {
+        use mapper::python::pep440;
+
+        assert_eq!(pep440("1.2.0"), "1.2.0");
+        assert_eq!(pep440("1.2.0-rc.1"), "1.2.0rc1");
+        assert_eq!(pep440("1.2.0-alpha.3"), "1.2.0a3");
+        assert_eq!(pep440("1.2.0-beta"), "1.2.0b0");
+        assert_eq!(pep440("1.2.0-nightly.7+abc"), "1.2.0+nightly.7.abc");
+    }
+
+    /// String enum fields get an enum class declared next to their model,
+    /// which their default names a member of and the split package exports.
+    #[test]
+    fn python_enums() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let config = LanguageConfig {
+            split: Split::PerType,
+            ..LanguageConfig::new("python")
+        };
+        let files = generate(&spec, &config).unwrap();
+        let file = |path: &str| &files.iter().find(|f| f.path == path).unwrap().contents;
+        let walk = file("types/walk_params.py");
+        assert!(walk.contains("class WalkParamsOrder(str, Enum):\n"));
+        assert!(walk.contains("    PRE = \"pre\"\n    POST = \"post\"\n"));
+        assert!(walk.contains("    order: Optional[WalkParamsOrder] = WalkParamsOrder.PRE\n"));
+        let stub = file("types/walk_params.pyi");
+        assert!(stub
+            .contains("order: Optional[Union[WalkParamsOrder, Literal[\"pre\", \"post\"]]] = ..."));
+        assert!(file("types/__init__.py")
+            .contains("from .walk_params import WalkParamsOrder, WalkParams\n"));
+    }
+
+    /// Constraints are `annotated_types` metadata of the annotation, inside
+    /// the `Optional` of nullable fields, in the models and their stubs.
+    #[test]
+    fn python_constraints() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let files = generate(&spec, &LanguageConfig::new("python")).unwrap();
+        let file = |path: &str| &files.iter().find(|f| f.path == path).unwrap().contents;
+        for path in ["types.py", "types.pyi"] {
+            let types = file(path);
+            assert!(types.contains("from annotated_types import Ge, Le, MaxLen, MinLen\n"));
+            assert!(types.contains(
+                "    label: Annotated[str, MinLen(1), StringConstraints(pattern=\"^[a-z]+$\")]\n"
+            ));
+            assert!(types.contains("    depth: Optional[Annotated[int, Ge(1), Le(64)]]"));
+            assert!(types.contains("    tags: Annotated[List[str], MinLen(1)]\n"));
+        }
+        assert!(!file("types.py").contains("Field(min_length"));
+    }
+
+    #[test]
+    fn go() {
+        assert_snapshots("go");
+    }
+
+    /// Go envelopes are generic unless disabled, the `Do*` functions keeping
+    /// their signature either way.
+    #[test]
+    fn go_rpc() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let rpc = |go_generics| {
+            let config = LanguageConfig {
+                go_generics,
+                ..LanguageConfig::new("go")
+            };
+            let files = generate(&spec, &config).unwrap();
+            files
+                .into_iter()
+                .find(|f| f.path == "rpc.go")
+                .unwrap()
+                .contents
+        };
+        let do_walk = "func DoTreeWalk(ctx context.Context, client *http.Client, url string, params WalkParams) (Node, error) {";
+        let generic = rpc(true);
+        assert!(generic.contains("type Response[R any] struct {"));
+        assert!(generic.contains("var TreeWalkCall = Call[WalkParams, Node]{Method: TreeWalk}\n"));
+        assert!(generic.contains(do_walk));
+        let fallback = rpc(false);
+        assert!(!fallback.contains("[P any]"));
+        assert!(fallback.contains("    Result  json.RawMessage `json:\"result,omitempty\"`\n"));
+        assert!(fallback.contains(do_walk));
+    }
+
+    /// `go.mod` is written for a configured module, whose path takes the
+    /// major version of the spec from 2 on.
+    #[test]
+    fn go_module() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let files = generate(&spec, &LanguageConfig::new("go")).unwrap();
+        assert!(files.iter().all(|f| f.path != "go.mod"));
+
+        let config = LanguageConfig {
+            go_module: Some("example.com/trp".to_string()),
+            ..LanguageConfig::new("go")
+        };
+        let files = generate(&spec, &config).unwrap();
+        let file = |path: &str| {
+            files
+                .iter()
+                .find(|f| f.path == path)
+                .unwrap()
+                .contents
+                .clone()
+        };
+        let module = file("go.mod");
+        assert!(module.contains("tagged v1.2.3"));
+        assert!(module.ends_with("module example.com/trp\n\ngo 1.18\n"));
+        assert!(file("README.md").contains("go get example.com/trp@v1.2.3"));
+
+        let v2 = Spec::parse(
+            FIXTURE.replace("\"version\": \"1.2.3\"", "\"version\": \"2.0.0\""),
+            "fixture",
+        )
+        .unwrap();
+        let config = LanguageConfig {
+            go_module: Some("example.com/trp".to_string()),
+            go_version: Some("1.21".to_string()),
+            ..LanguageConfig::new("go")
+        };
+        let files = generate(&v2, &config).unwrap();
+        let module = &files.iter().find(|f| f.path == "go.mod").unwrap().contents;
+        assert!(module.ends_with("module example.com/trp/v2\n\ngo 1.21\n"));
+
+        let config = LanguageConfig {
+            go_module: Some("example.com/trp".to_string()),
+            go_version: Some("1.17".to_string()),
+            ..LanguageConfig::new("go")
+        };
+        let err = generate(&spec, &config).unwrap_err().to_string();
+        assert!(
+            err.starts_with("the Go files need Go 1.18, not 1.17"),
+            "{}",
+            err
+        );
+        let config = LanguageConfig {
+            go_generics: false,
+            ..config
+        };
+        assert!(generate(&spec, &config).is_ok());
+    }
+
+    #[test]
+    fn rust() {
+        assert_snapshots("rust");
+    }
+
+    #[test]
+    fn elm() {
+        assert_snapshots("elm");
+    }
+
+    #[test]
+    fn php() {
+        assert_snapshots("php");
+    }
+
+    #[test]
+    fn scala() {
+        assert_snapshots("scala");
+    }
+
+    #[test]
+    fn c() {
+        assert_snapshots("c");
+    }
+
+    #[test]
+    fn ocaml() {
+        assert_snapshots("ocaml");
+    }
+
+    #[test]
+    fn proto() {
+        assert_snapshots("proto");
+    }
+
+    /// Fields keep their numbers across runs, new ones get fresh numbers and
+    /// dropped ones stay reserved.
+    #[test]
+    fn proto_numbers_are_stable() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let mut config = LanguageConfig::new("proto");
+        config
+            .proto_numbers
+            .entry("Predicate".to_string())
+            .or_default()
+            .extend([("tags".to_string(), 7), ("legacy".to_string(), 2)]);
+        let files = generate(&spec, &config).unwrap();
+        let proto = &files
+            .iter()
+            .find(|f| f.path == "trp.proto")
+            .unwrap()
+            .contents;
+        assert!(proto.contains("repeated string tags = 7;"));
+        assert!(proto.contains("string kind = 8;"));
+        assert!(proto.contains("reserved 2;\n  reserved \"legacy\";"));
+    }
+
+    #[test]
+    fn cddl() {
+        assert_snapshots("cddl");
+    }
+
+    #[test]
+    fn jsonschema() {
+        assert_snapshots("jsonschema");
+    }
+
+    #[test]
+    fn openapi() {
+        assert_snapshots("openapi");
+    }
+
+    /// Only methods marked with `x-subscription` make it into the AsyncAPI
+    /// document, which specs without any can't have.
+    #[test]
+    fn asyncapi() {
+        let fixture = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        assert!(generate(&fixture, &LanguageConfig::new("asyncapi")).is_err());
+
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        raw["servers"] = json!([{ "url": "https://example.com/rpc" }]);
+        raw["methods"][0]["x-subscription"] = json!({
+            "notification": "tree.visited",
+            "schema": { "$ref": "#/components/schemas/Node" },
+        });
+        let spec = Spec::parse(raw.to_string(), "subscription").unwrap();
+        let files = generate(&spec, &LanguageConfig::new("asyncapi")).unwrap();
+        let [file] = files.as_slice() else {
+            panic!("{} files", files.len())
+        };
+        assert_eq!(file.path, "asyncapi.json");
+        insta::assert_snapshot!("asyncapi", file.contents);
+    }
+
+    #[test]
+    fn wasm() {
+        assert_snapshots("wasm");
+
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let config = LanguageConfig {
+            wasm: WasmCrate {
+                name: "fixture-wasm".to_string(),
+                types: "fixture-types".to_string(),
+                types_path: Some("../fixture-types".to_string()),
+            },
+            ..LanguageConfig::new("wasm")
+        };
+        let files = generate(&spec, &config).unwrap();
+        let file = |path: &str| {
+            files
+                .iter()
+                .find(|file| file.path == path)
+                .map(|file| file.contents.as_str())
+                .unwrap()
+        };
+        assert!(file("Cargo.toml")
+            .contains("fixture-types = { path = \"../fixture-types\", features = [\"client\"] }"));
+        let lib = file("src/lib.rs");
+        assert!(lib.contains("use fixture_types::client::{Client, ClientError};"));
+        assert!(lib.contains("js_name = treeWalk"));
+        assert!(lib.contains("unchecked_param_type = \"WalkParams\""));
+        assert!(lib.contains("export interface WalkParams"));
+    }
+
+    /// Contexts carry the schema metadata of every field next to the
+    /// generated type names.
+    #[test]
+    fn context_metadata() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let types = resolver::resolve_components(&spec.document).unwrap();
+        let ctx = mapper::build_context(&types, "go", &Naming::default());
+        let walk = ctx.type_info("WalkParams").unwrap();
+        assert_eq!(walk.name, ctx.type_name("WalkParams"));
+        let order = &walk.fields["order"];
+        assert_eq!(order.enum_values, vec!["pre", "post"]);
+        assert!(!order.nullable);
+        assert!(ctx.field_info("Node", "parent").unwrap().nullable);
+        assert!(ctx.field_info("Node", "weight").unwrap().deprecated);
+    }
+
+    /// Components share the fields they pull in through `$ref` and `allOf`,
+    /// and cycles among them are reported rather than followed forever.
+    #[test]
+    fn all_of_resolution() {
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        let schemas = &mut raw["components"]["schemas"];
+        schemas["Base"] = serde_json::json!({
+            "type": "object",
+            "properties": { "id": { "type": "string" } },
+        });
+        schemas["Derived"] = serde_json::json!({
+            "allOf": [{ "$ref": "#/components/schemas/Base" }],
+            "properties": { "extra": { "type": "integer" } },
+            "required": ["id"],
+        });
+        schemas["Alias"] = serde_json::json!({ "$ref": "#/components/schemas/Derived" });
+        let spec = Spec::parse(raw.to_string(), "fixture").unwrap();
+        let types = resolver::resolve_components(&spec.document).unwrap();
+        let fields = |name: &str| {
+            let ty = types.iter().find(|t| t.name == name).unwrap();
+            ty.fields
+                .iter()
+                .map(|f| (f.name.clone(), f.required))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(fields("Base"), [("id".to_string(), false)]);
+        let derived = [("id".to_string(), true), ("extra".to_string(), false)];
+        assert_eq!(fields("Derived"), derived);
+        assert_eq!(fields("Alias"), derived);
+
+        raw["components"]["schemas"]["Base"]["allOf"] =
+            serde_json::json!([{ "$ref": "#/components/schemas/Alias" }]);
+        let spec = Spec::parse(raw.to_string(), "fixture").unwrap();
+        let err = resolver::resolve_components(&spec.document).unwrap_err();
+        assert!(format!("{:#}", err).contains("through $ref or allOf"));
+    }
+
+    /// A split spec merges back into the document it was split from, with
+    /// a file per method and schema.
+    #[test]
+    fn bundle_round_trip() {
+        let raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        let files = bundle::split(&raw).unwrap();
+        assert_eq!(files[0].0, Path::new(bundle::ROOT));
+        assert!(files
+            .iter()
+            .any(|(path, _)| path == Path::new("methods/tree.walk.json")));
+        assert!(files
+            .iter()
+            .any(|(path, _)| path == Path::new("components/WalkParams.json")));
+        assert_eq!(
+            files[0].1["methods"][0],
+            serde_json::json!({ "$ref": "methods/tree.walk.json" })
+        );
+
+        let merged = bundle::merge_with(|file| {
+            let (_, value) = files.iter().find(|(path, _)| path == file).unwrap();
+            Ok(value.to_string())
+        })
+        .unwrap();
+        assert_eq!(
+            serde_json::to_string(&merged).unwrap(),
+            serde_json::to_string(&raw).unwrap()
+        );
+
+        let escape = serde_json::json!({
+            "openrpc": "1.3.2",
+            "methods": [{ "$ref": "../elsewhere.json" }],
+        });
+        let err = bundle::merge_with(|_| Ok(escape.to_string())).unwrap_err();
+        assert!(err.to_string().contains("outside of the spec's directory"));
+    }
+
+    /// Constructs a mapper falls back to dynamic JSON for are partially
+    /// supported in its language, and those no mapper reads nowhere.
+    #[test]
+    fn coverage() {
+        use coverage::Support;
+        use mapper::Construct;
+
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        raw["components"]["schemas"]["Span"] = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "bounds": {
+                    "type": "array",
+                    "items": [
+                        { "type": "integer" },
+                        { "type": "integer" },
+                        { "type": "string" },
+                        { "type": "boolean" },
+                    ],
+                },
+                "name": { "type": "string", "not": { "const": "" } },
+            },
+        });
+        let spec = Spec::parse(raw.to_string(), "fixture").unwrap();
+        let entries = coverage::report(&spec.document, coverage::LANGUAGES).unwrap();
+        let support = |location: &str, construct: Construct| {
+            let entry = entries
+                .iter()
+                .find(|e| e.location == location && e.construct == construct)
+                .unwrap();
+            &entry.support
+        };
+
+        let filter = support("WalkParams.filter", Construct::Union);
+        for lang in ["elm", "php", "c"] {
+            assert_eq!(filter[lang], Support::Partial, "{}", lang);
+        }
+        for lang in [
+            "ts", "python", "go", "rust", "scala", "ocaml", "proto", "cddl",
+        ] {
+            assert_eq!(filter[lang], Support::Full, "{}", lang);
+        }
+        let bounds = support("Span.bounds", Construct::Tuple);
+        assert_eq!(bounds["elm"], Support::Partial);
+        assert_eq!(bounds["c"], Support::Partial);
+        assert_eq!(bounds["proto"], Support::Partial);
+        assert_eq!(bounds["ts"], Support::Full);
+        let name = support("Span.name", Construct::Not);
+        assert!(name.values().all(|s| *s == Support::Unsupported));
+        assert!(support("WalkParams.order", Construct::Enum)
+            .values()
+            .all(|s| *s == Support::Full));
+    }
+
+    /// Configured conventions replace the derived names, and the spec's
+    /// `x-{lang}-naming` fills in what the configuration leaves unset.
+    #[test]
+    fn naming() {
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        raw["x-rust-naming"] = serde_json::json!({ "fields": "screaming-snake" });
+        let spec = Spec::parse(raw.to_string(), "fixture").unwrap();
+        let mut config = LanguageConfig::new("rust");
+        config.naming.set("types", "snake").unwrap();
+        let files = generate(&spec, &config).unwrap();
+        let types = &files
+            .iter()
+            .find(|f| f.path == "types.rs")
+            .unwrap()
+            .contents;
+        assert!(types.contains("pub struct walk_params {"));
+        assert!(types.contains("pub ROOT: node,"));
+
+        assert_eq!(
+            naming::convert(
+                "HTTPServer_id",
+                naming::Case::Pascal,
+                Some(naming::Acronyms::Upper)
+            ),
+            "HTTPServerID"
+        );
+        assert_eq!(
+            naming::convert("txURL", naming::Case::Camel, Some(naming::Acronyms::Title)),
+            "txUrl"
+        );
+        assert!(generate(
+            &spec,
+            &LanguageConfig {
+                naming: config.naming,
+                ..LanguageConfig::new("elm")
+            }
+        )
+        .is_err());
+    }
+
+    /// With `rust_borrowed`, components holding strings get a counterpart
+    /// borrowing them, converting to and from the owned type.
+    #[test]
+    fn rust_borrowed() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let config = LanguageConfig {
+            rust_borrowed: true,
+            ..LanguageConfig::new("rust")
+        };
+        let files = generate(&spec, &config).unwrap();
+        let types = &files
+            .iter()
+            .find(|f| f.path == "types.rs")
+            .unwrap()
+            .contents;
+        insta::assert_snapshot!(
+            "rust_borrowed",
+            &types[types.find("pub mod borrowed").unwrap()..]
+        );
+    }
+
+    /// With `rust_previous`, the types of the previous major version get a
+    /// module, converting to and from the current ones as far as their
+    /// fields allow.
+    #[test]
+    fn rust_previous() {
+        use serde_json::json;
+
+        let spec = |version: &str, schemas: Value| {
+            let raw = json!({
+                "openrpc": "1.2.6",
+                "info": { "title": "Versions", "version": version },
+                "methods": [],
+                "components": { "schemas": schemas },
+            });
+            Spec::parse(raw.to_string(), version).unwrap()
+        };
+        let previous = spec(
+            "1.4.0",
+            json!({
+                "Params": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "limit": { "type": "integer" },
+                        "tags": { "type": "array", "items": { "type": "string" } },
+                    },
+                    "required": ["name", "limit"],
+                },
+                "Query": {
+                    "type": "object",
+                    "properties": { "params": { "$ref": "#/components/schemas/Params" } },
+                    "required": ["params"],
+                },
+                "Item": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string" },
+                        "amount": { "type": "integer" },
+                    },
+                    "required": ["id", "amount"],
+                },
+                "Page": {
+                    "type": "object",
+                    "properties": {
+                        "items": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } },
+                    },
+                    "required": ["items"],
+                },
+            }),
+        );
+        // `limit` turns optional, `amount` fractional, and `cursor`, `note`
+        // and `next` are new, the latter required.
+        let current = spec(
+            "2.0.0",
+            json!({
+                "Params": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "limit": { "type": "integer" },
+                        "tags": { "type": "array", "items": { "type": "string" } },
+                        "cursor": { "type": "string" },
+                    },
+                    "required": ["name"],
+                },
+                "Query": {
+                    "type": "object",
+                    "properties": { "params": { "$ref": "#/components/schemas/Params" } },
+                    "required": ["params"],
+                },
+                "Item": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string" },
+                        "amount": { "type": "number" },
+                        "note": { "type": "string" },
+                    },
+                    "required": ["id", "amount"],
+                },
+                "Page": {
+                    "type": "object",
+                    "properties": {
+                        "items": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } },
+                        "next": { "type": "string" },
+                    },
+                    "required": ["items", "next"],
+                },
+            }),
+        );
+        let config = LanguageConfig {
+            rust_previous: Some(Arc::new(previous)),
+            ..LanguageConfig::new("rust")
+        };
+        let files = generate(&current, &config).unwrap();
+        let file = |path: &str| &files.iter().find(|f| f.path == path).unwrap().contents;
+        assert!(file("types.rs").contains("pub mod v1;"));
+        assert!(file("types.rs").contains("pub mod v2 {"));
+        let v1 = file("types/v1.rs");
+        insta::assert_snapshot!(
+            "rust_previous",
+            &v1[v1.find("#[allow(deprecated)]\nimpl").unwrap()..]
+        );
+        assert!(!v1.contains("for super::Page"));
+        assert!(!v1.contains("for Page"));
+
+        let config = LanguageConfig {
+            rust_previous: Some(Arc::new(spec("2.1.0", json!({})))),
+            ..LanguageConfig::new("rust")
+        };
+        assert!(generate(&current, &config).is_err());
+    }
+
+    /// Fields can map to hand-written types, imported as configured and as
+    /// the spec's `x-{lang}-imports` lists.
+    #[test]
+    fn external_types() {
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        let label = &mut raw["components"]["schemas"]["Node"]["properties"]["label"];
+        label["x-rust-type"] = serde_json::json!("domain::Label");
+        label["x-go-type"] = serde_json::json!("domain.Label");
+        raw["x-rust-imports"] = serde_json::json!(["use crate::domain;"]);
+        let spec = Spec::parse(raw.to_string(), "fixture").unwrap();
+
+        let config = LanguageConfig {
+            imports: vec!["use crate::domain;".to_string()],
+            ..LanguageConfig::new("rust")
+        };
+        let files = generate(&spec, &config).unwrap();
+        let types = &files
+            .iter()
+            .find(|f| f.path == "types.rs")
+            .unwrap()
+            .contents;
+        assert_eq!(types.matches("use crate::domain;").count(), 1);
+        assert!(types.contains("pub label: domain::Label,"));
+
+        // Go only imports where an overridden type is used.
+        let config = LanguageConfig {
+            imports: vec!["\"example.com/domain\"".to_string()],
+            split: Split::PerType,
+            ..LanguageConfig::new("go")
+        };
+        for file in generate(&spec, &config).unwrap() {
+            let imported = file.contents.contains("\"example.com/domain\"");
+            assert_eq!(imported, file.path == "node.go", "{}", file.path);
+        }
+
+        assert!(generate(
+            &spec,
+            &LanguageConfig {
+                imports: vec!["import Domain".to_string()],
+                ..LanguageConfig::new("elm")
+            }
+        )
+        .is_err());
+    }
+
+    /// Names that aren't identifiers in a target language are folded to
+    /// ASCII and kept clear of leading digits and keywords.
+    #[test]
+    fn identifiers() {
+        use crate::mapper::{go, python, rust, sanitize_identifier, snake_case, ts};
+
+        assert_eq!(sanitize_identifier("3rdParty"), "Type3rdParty");
+        assert_eq!(sanitize_identifier("ÉtatCivil"), "EtatCivil");
+        assert_eq!(sanitize_identifier("名前"), "U540dU524d");
+        assert_eq!(snake_case("straße"), "strasse");
+        assert_eq!(go::field_name("3d"), "Field3d");
+        assert_eq!(python::field_name("class"), "class_");
+        assert_eq!(python::field_name("search-space"), "search_space");
+        assert_eq!(rust::field_name("naïve"), "naive");
+        assert_eq!(ts::field_name("3rd-party"), "\"3rd-party\"");
+
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        raw["components"]["schemas"]["3rdParty"] = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "名前": { "type": "string" },
+                "2fa": { "type": "boolean" },
+                "": { "type": "integer" },
+            },
+        });
+        let spec = Spec::parse(raw.to_string(), "fixture").unwrap();
+        for lang in [
+            "ts", "python", "go", "rust", "elm", "php", "scala", "c", "ocaml", "proto", "cddl",
+        ] {
+            generate(&spec, &LanguageConfig::new(lang)).unwrap();
+        }
+    }
+
+    /// String fields with `format: address` are checked by each binding's
+    /// validation, and Rust types get a method parsing them.
+    #[test]
+    fn address_fields() {
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        raw["components"]["schemas"]["Payment"] = serde_json::json!({
+            "type": "object",
+            "required": ["to"],
+            "properties": {
+                "to": { "type": "string", "format": "address" },
+                "change": { "type": "string", "format": "address" },
+            },
+        });
+        let spec = Spec::parse(raw.to_string(), "fixture").unwrap();
+        let file = |lang: &str, path: &str| {
+            generate(&spec, &LanguageConfig::new(lang))
+                .unwrap()
+                .into_iter()
+                .find(|file| file.path == path)
+                .unwrap()
+                .contents
+        };
+
+        let rust = file("rust", "types.rs");
+        assert!(rust.contains("!address::is_address(value)"));
+        assert!(rust.contains("pub fn parse_to(&self)"));
+        assert!(rust.contains("pub fn parse_change(&self)"));
+        assert!(file("ts", "schemas.ts").contains(".refine(isAddress"));
+        assert!(file("python", "types.py").contains("def _to_address("));
+        assert!(file("go", "types.go").contains("!IsAddress(value)"));
+        insta::assert_snapshot!("address_fields", rust);
+    }
+
+    /// TRP specs get a `backend` module, other specs don't.
+    #[test]
+    fn rust_backend() {
+        let config = LanguageConfig {
+            rust_crate: Some("trp-types".to_string()),
+            ..LanguageConfig::new("rust")
+        };
+        let backend = |spec: &Spec| {
+            generate(spec, &config)
+                .unwrap()
+                .into_iter()
+                .find(|file| file.path == "src/backend.rs")
+        };
+        let fixture = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        assert!(backend(&fixture).is_none());
+
+        let trp = include_str!("../../../specs/trp.json");
+        let trp = Spec::parse(trp.to_string(), "trp").unwrap();
+        let file = backend(&trp).unwrap();
+        assert!(file
+            .contents
+            .contains("impl<B: ResolverBackend> Handler for Backend<B>"));
+        insta::assert_snapshot!("rust_backend", file.contents);
+    }
+
+    /// TRP specs get the `trp` program behind a `cli` feature, and a `watch`
+    /// module polling `trp.checkStatus`.
+    #[test]
+    fn rust_cli() {
+        let config = LanguageConfig {
+            rust_crate: Some("trp-types".to_string()),
+            ..LanguageConfig::new("rust")
+        };
+        let file = |spec: &Spec, path: &str| {
+            generate(spec, &config)
+                .unwrap()
+                .into_iter()
+                .find(|file| file.path == path)
+        };
+        let fixture = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        assert!(file(&fixture, "src/bin/trp.rs").is_none());
+        assert!(file(&fixture, "src/watch.rs").is_none());
+        let manifest = file(&fixture, "Cargo.toml").unwrap().contents;
+        assert!(!manifest.contains("cli = "));
+
+        let trp = include_str!("../../../specs/trp.json");
+        let trp = Spec::parse(trp.to_string(), "trp").unwrap();
+        let manifest = file(&trp, "Cargo.toml").unwrap().contents;
+        assert!(manifest.contains("cli = [\"blocking\", \"dep:blake2\", \"dep:ed25519-dalek\"]"));
+        assert!(manifest.contains("name = \"trp\""));
+        let bin = file(&trp, "src/bin/trp.rs").unwrap();
+        insta::assert_snapshot!("rust_cli", bin.contents);
+        let watch = file(&trp, "src/watch.rs").unwrap();
+        insta::assert_snapshot!("rust_watch", watch.contents);
+    }
+
+    /// Every Rust output fingerprints requests, and crate servers key
+    /// idempotent calls and log records by those fingerprints.
+    #[test]
+    fn rust_fingerprint() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let paths: Vec<String> = generate(&spec, &LanguageConfig::new("rust"))
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        assert!(paths.contains(&"fingerprint.rs".to_string()));
+        assert!(paths.contains(&"hex.rs".to_string()));
+
+        let config = LanguageConfig {
+            rust_crate: Some("fixture-types".to_string()),
+            ..LanguageConfig::new("rust")
+        };
+        let files = generate(&spec, &config).unwrap();
+        let file = |path: &str| {
+            &files
+                .iter()
+                .find(|file| file.path == path)
+                .unwrap()
+                .contents
+        };
+        assert!(file("src/fingerprint.rs").contains("pub fn request_fingerprint("));
+        assert!(file("src/lib.rs").contains("pub mod fingerprint;"));
+        assert!(file("src/lib.rs").contains("pub mod hex;"));
+        assert!(!file("src/fingerprint.rs").contains("fn sha256"));
+        let server = file("src/server.rs");
+        assert!(server.contains("store.claim(&key, &fingerprint)"));
+        assert!(server.contains("params_hash: to_hex(&request_fingerprint("));
+    }
+
+    /// Crates embed the JSON Schemas of the components, which clients check
+    /// results against when asked to.
+    #[test]
+    fn rust_response_validation() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let config = LanguageConfig {
+            rust_crate: Some("fixture-types".to_string()),
+            ..LanguageConfig::new("rust")
+        };
+        let files = generate(&spec, &config).unwrap();
+        let file = |path: &str| {
+            &files
+                .iter()
+                .find(|file| file.path == path)
+                .unwrap()
+                .contents
+        };
+        let schema = file("src/schema.rs");
+        assert!(schema.contains(r##""$ref": "#/$defs/Node""##));
+        assert!(schema.contains(r#"constants::TREE_WALK => Some("Node"),"#));
+        assert!(file("src/lib.rs").contains("pub mod schema;"));
+        let client = file("src/client.rs");
+        assert!(client.contains("pub fn validate_responses(mut self) -> Self"));
+        assert!(client.contains("schema::validate_result(method, &result)?;"));
+    }
+
+    /// Crates generate requests of every method for servers to test
+    /// themselves against, behind the `testing` feature.
+    #[test]
+    fn rust_testing() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let config = LanguageConfig {
+            rust_crate: Some("fixture-types".to_string()),
+            ..LanguageConfig::new("rust")
+        };
+        let files = generate(&spec, &config).unwrap();
+        let file = |path: &str| {
+            &files
+                .iter()
+                .find(|file| file.path == path)
+                .unwrap()
+                .contents
+        };
+        let testing = file("src/testing.rs");
+        assert!(testing.contains("constants::TREE_WALK => any::<WalkParams>()"));
+        assert!(file("src/schema.rs").contains(r#"constants::TREE_WALK => Some("WalkParams"),"#));
+        assert!(file("src/lib.rs").contains("#[cfg(feature = \"testing\")]\npub mod testing;"));
+        assert!(file("Cargo.toml").contains("testing = [\"arbitrary\"]"));
+    }
+
+    /// The TypeScript mock implements the client's interface, answering
+    /// with the results of the spec's examples.
+    #[test]
+    fn ts_mock() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let files = generate(&spec, &LanguageConfig::new("ts")).unwrap();
+        let file = |path: &str| &files.iter().find(|f| f.path == path).unwrap().contents;
+        assert!(file("client.ts").contains("export class TrpClient implements TrpApi {"));
+        assert!(file("client.ts").contains(
+            "    treeWalk(params: types.WalkParams, options?: CallOptions): Promise<types.Node>;"
+        ));
+        let mock = file("mock.ts");
+        assert!(mock.contains("export class MockTrpClient implements TrpApi {"));
+        assert!(mock.contains("    [constants.TREE_WALK]: {\n        \"label\": \"a\"\n    },"));
+        assert!(mock.contains("return example(constants.TREE_WALK) as types.Node;"));
+    }
+
+    /// `ts_react` adds a hook per method, exported by the npm package.
+    #[test]
+    fn ts_react() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let plain = generate(&spec, &LanguageConfig::new("ts")).unwrap();
+        assert!(!plain.iter().any(|f| f.path == "react.ts"));
+        for lang in ["ts", "deno"] {
+            let config = LanguageConfig {
+                ts_react: true,
+                ..LanguageConfig::new(lang)
+            };
+            let files = generate(&spec, &config).unwrap();
+            let react = files.iter().find(|f| f.path == "react.ts");
+            assert_eq!(react.is_some(), lang == "ts");
+            let Some(react) = react else { continue };
+            assert!(react.contents.contains(
+                "export function useTreeWalk(client: TrpApi): TrpCall<types.WalkParams, types.Node> {"
+            ));
+            let file = |path: &str| &files.iter().find(|f| f.path == path).unwrap().contents;
+            assert!(file("package.json").contains("\"./react\": \"./react.ts\""));
+            assert!(file("package.json").contains("\"react\": \">=16.8\""));
+            assert!(file("README.md").contains("`useTreeWalk(client)`"));
+            insta::assert_snapshot!("ts_react", react.contents);
+        }
+    }
+
+    /// Unions get an enum listing their variants in Rust and a matcher in
+    /// TypeScript, which tell variants apart by their discriminator when
+    /// the spec declares one.
+    #[test]
+    fn union_exhaustiveness() {
+        let file = |spec: &Spec, lang: &str, path: &str| {
+            generate(spec, &LanguageConfig::new(lang))
+                .unwrap()
+                .into_iter()
+                .find(|file| file.path == path)
+                .map(|file| file.contents)
+        };
+        let fixture = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let rust = file(&fixture, "rust", "types.rs").unwrap();
+        assert!(rust.contains("pub filter: Option<StringOrPredicate>,"));
+        assert!(rust.contains("#[serde(untagged)]\npub enum StringOrPredicate {"));
+        assert!(rust.contains("            \"String\",\n            \"Predicate\",\n"));
+        assert!(rust.contains("macro_rules! match_all {"));
+        let config = LanguageConfig {
+            rust_crate: Some("fixture-types".to_string()),
+            ..LanguageConfig::new("rust")
+        };
+        let files = generate(&fixture, &config).unwrap();
+        let arbitrary = files.iter().find(|f| f.path == "src/arbitrary.rs").unwrap();
+        assert!(arbitrary
+            .contents
+            .contains("any::<Predicate>().prop_map(Self::Predicate),"));
+        let ts = file(&fixture, "ts", "unions.ts").unwrap();
+        assert!(ts.contains("if (typeof value === \"string\") {"));
+        assert!(file(&fixture, "deno", "jsr.json")
+            .unwrap()
+            .contains("\"./unions\": \"./unions.ts\""));
+
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        let schemas = &mut raw["components"]["schemas"];
+        let mut glob = schemas["Predicate"].clone();
+        glob["properties"]["kind"]["const"] = "glob".into();
+        schemas["Glob"] = glob;
+        schemas["WalkParams"]["properties"]["filter"] = serde_json::json!({
+            "oneOf": [
+                { "$ref": "#/components/schemas/Predicate" },
+                { "$ref": "#/components/schemas/Glob" }
+            ],
+            "discriminator": { "propertyName": "kind" }
+        });
+        let tagged = Spec::parse(raw.to_string(), "tagged").unwrap();
+        let rust = file(&tagged, "rust", "types.rs").unwrap();
+        assert!(rust.contains("Self::Glob(_) => \"glob\","));
+        assert!(rust.contains("Self::Glob(value) => value.validate(),\n        }"));
+        let ts = file(&tagged, "ts", "unions.ts").unwrap();
+        assert!(ts.contains(
+            "export const PredicateOrGlobVariants = [\"predicate\", \"glob\"] as const;"
+        ));
+        assert!(ts.contains("if (value[\"kind\"] === \"glob\") {"));
+
+        // Specs without unions have nothing to declare.
+        let trp = Spec::parse(include_str!("../../../specs/trp.json").to_string(), "trp").unwrap();
+        assert!(!file(&trp, "rust", "types.rs")
+            .unwrap()
+            .contains("match_all"));
+        assert!(file(&trp, "ts", "unions.ts").is_none());
+    }
+
+    /// Specs with a `TirInfo` listing its supported versions in
+    /// `x-supported` get `tir` helpers supporting those versions, while the
+    /// schema of the version stays a plain string.
+    #[test]
+    fn tir_helpers() {
+        let file = |spec: &Spec, lang: &str, path: &str| {
+            generate(spec, &LanguageConfig::new(lang))
+                .unwrap()
+                .into_iter()
+                .find(|file| file.path == path)
+                .map(|file| file.contents)
+        };
+        let fixture = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        assert!(file(&fixture, "rust", "tir.rs").is_none());
+        assert!(file(&fixture, "ts", "tir.ts").is_none());
+
+        let mut raw: Value = serde_json::from_str(include_str!("../../../specs/trp.json")).unwrap();
+        raw["components"]["schemas"]["TirInfo"]["properties"]["version"]["x-supported"] =
+            serde_json::json!(["v1beta0", "v1beta1"]);
+        let trp = Spec::parse(raw.to_string(), "trp").unwrap();
+        let rust = file(&trp, "rust", "tir.rs").unwrap();
+        assert!(rust.contains("pub const TIR_VERSIONS: &[&str] = &[\"v1beta0\", \"v1beta1\"];"));
+        assert!(rust.contains("impl TryFrom<&TirInfo> for TirEnvelope"));
+        let ts = file(&trp, "ts", "tir.ts").unwrap();
+        assert!(ts.contains("export const TIR_VERSIONS = [\"v1beta0\", \"v1beta1\"] as const;"));
+        assert!(file(&trp, "deno", "tir.ts")
+            .unwrap()
+            .contains("from \"./types.ts\""));
+        let python = file(&trp, "python", "tir.py").unwrap();
+        assert!(python.contains("TIR_VERSIONS: Tuple[str, ...] = (\"v1beta0\", \"v1beta1\",)"));
+        let go = file(&trp, "go", "tir.go").unwrap();
+        assert!(go.contains("var TirVersions = []string{\"v1beta0\", \"v1beta1\"}"));
+        insta::assert_snapshot!("tir_helpers", rust);
+
+        let schema = file(&trp, "jsonschema", "TirInfo.schema.json").unwrap();
+        assert!(!schema.contains("\"enum\""));
+
+        // Without a list of versions there is nothing to check against.
+        raw["components"]["schemas"]["TirInfo"]["properties"]["version"] =
+            serde_json::json!({ "type": "string" });
+        let trp = Spec::parse(raw.to_string(), "trp").unwrap();
+        assert!(file(&trp, "rust", "tir.rs").is_none());
+        assert!(file(&trp, "go", "tir.go").is_none());
+    }
+
+    /// Clients send the params of methods taking them by position as an
+    /// array, and servers accept what `paramStructure` allows.
+    #[test]
+    fn param_structure() {
+        let file = |spec: &Spec, config: &LanguageConfig, path: &str| {
+            generate(spec, config)
+                .unwrap()
+                .into_iter()
+                .find(|file| file.path == path)
+                .map(|file| file.contents)
+                .unwrap()
+        };
+        let crate_config = LanguageConfig {
+            rust_crate: Some("fixture-types".to_string()),
+            ..LanguageConfig::new("rust")
+        };
+        let fixture = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let server = file(&fixture, &crate_config, "src/server.rs");
+        assert!(server.contains("parse_params(params, ParamStructure::Either)?;"));
+
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        raw["methods"][0]["paramStructure"] = "by-position".into();
+        let spec = Spec::parse(raw.to_string(), "by-position").unwrap();
+        let ts = file(&spec, &LanguageConfig::new("ts"), "client.ts");
+        assert!(ts.contains("this.call(constants.TREE_WALK, [params], {"));
+        let go = file(&spec, &LanguageConfig::new("go"), "rpc.go");
+        assert!(go.contains("var TreeWalkCall = Call[[1]WalkParams, Node]{Method: TreeWalk}"));
+        assert!(go.contains("TreeWalkCall.Do(ctx, client, url, [1]WalkParams{params})"));
+        assert!(go.contains("c.call(ctx, TreeWalk, [1]WalkParams{params}, &result)"));
+        let client = file(&spec, &crate_config, "src/client.rs");
+        assert!(client.contains("self.call_with_key(constants::TREE_WALK, &[params], key).await"));
+        let server = file(&spec, &crate_config, "src/server.rs");
+        assert!(server.contains("parse_params(params, ParamStructure::ByPosition)?;"));
+        let testing = file(&spec, &crate_config, "src/testing.rs");
+        assert!(testing.contains("const BY_POSITION: &[&str] = &[\n    constants::TREE_WALK,\n];"));
+    }
+
+    /// Errors must be declared in the application range, the other codes
+    /// being taken by the protocol and by servers, and the registry of
+    /// codes goes to every binding with errors helpers.
+    #[test]
+    fn error_codes() {
+        use crate::error_codes::{check, ErrorClass, Severity, RESERVED};
+
+        for reserved in RESERVED {
+            assert_eq!(ErrorClass::of(reserved.code), Some(reserved.class));
+            assert!(reserved.in_range() || reserved.name == "REQUEST_CANCELLED");
+        }
+        let codes: std::collections::BTreeSet<i64> = RESERVED.iter().map(|r| r.code).collect();
+        assert_eq!(codes.len(), RESERVED.len(), "reserved codes collide");
+        assert_eq!(ErrorClass::of(-32050), Some(ErrorClass::Transport));
+        assert_eq!(ErrorClass::of(-32004), Some(ErrorClass::Transport));
+        assert_eq!(ErrorClass::of(-32003), Some(ErrorClass::Application));
+        assert_eq!(ErrorClass::of(-32000), Some(ErrorClass::Application));
+        assert_eq!(ErrorClass::of(1000), None);
+
+        let fixture = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        assert!(check(&fixture.document).is_empty());
+        let trp = Spec::parse(include_str!("../../../specs/trp.json").to_string(), "trp").unwrap();
+        assert!(check(&trp.document).is_empty());
+
+        let mut raw: Value = serde_json::from_str(FIXTURE).unwrap();
+        let errors = &mut raw["methods"][0]["errors"];
+        errors[0]["code"] = (-32005).into();
+        errors[1]["code"] = 2000.into();
+        errors
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({ "code": -32050, "message": "Busy" }));
+        let mut other = raw["methods"][0].clone();
+        other["name"] = "tree.prune".into();
+        other["errors"] = serde_json::json!([
+            { "code": -32002, "message": "Node not found" },
+            { "code": -32002, "message": "Node is locked" }
+        ]);
+        raw["methods"].as_array_mut().unwrap().push(other);
+        let spec = Spec::parse(raw.to_string(), "reserved").unwrap();
+        let problems = check(&spec.document);
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (problem.location.as_str(), problem.severity))
+                .collect::<Vec<_>>(),
+            [
+                ("methods.tree.walk.errors.-32005", Severity::Warning),
+                ("methods.tree.walk.errors.2000", Severity::Warning),
+                ("methods.tree.walk.errors.-32050", Severity::Warning),
+                ("methods.tree.prune.errors.-32002", Severity::Error),
+            ]
+        );
+        assert!(problems[0]
+            .message
+            .contains("reserved for the transport error BATCHES_NOT_SUPPORTED"));
+        assert!(problems[1]
+            .message
+            .contains("outside of the range of application errors"));
+        assert!(problems[2]
+            .message
+            .contains("in the range of transport errors"));
+        assert!(problems[3]
+            .message
+            .contains("declared as \"Node not found\" by tree.prune"));
+
+        let file = |lang: &str, path: &str| {
+            generate(&fixture, &LanguageConfig::new(lang))
+                .unwrap()
+                .into_iter()
+                .find(|file| file.path == path)
+                .map(|file| file.contents)
+        };
+        let rust = file("rust", "error_codes.rs").unwrap();
+        assert!(rust.contains("pub const RATE_LIMITED: i64 = -32012;"));
+        assert!(rust.contains(
+            "pub const APPLICATION_CODES: &[i64] = &[super::errors::NODE_NOT_FOUND, super::errors::WALK_INTERRUPTED];"
+        ));
+        assert!(file("ts", "error_codes.ts")
+            .unwrap()
+            .contains("export function declaredError<C extends TrpErrorCode>("));
+        assert!(file("deno", "error_codes.ts").is_some());
+        assert!(file("python", "error_codes.py")
+            .unwrap()
+            .contains("def declared_error(code: int, data: Any = None) -> Dict[str, Any]:"));
+        let go = file("go", "error_codes.go").unwrap();
+        assert!(go.contains("CodeRateLimited = -32012 // Rate limit exceeded, a transport error."));
+        assert!(go
+            .contains("var ApplicationCodes = []int{ErrCodeNodeNotFound, ErrCodeWalkInterrupted}"));
+        assert!(go.contains("case ApplicationRange.Contains(code):"));
+        assert!(go.contains(
+            "func ReservedError(code int, message string, data interface{}) (*TrpError, error) {"
+        ));
+    }
+
+    /// The files a crate layout adds to the modules covered by `rust`.
+    #[test]
+    fn rust_crate() {
+        let spec = Spec::parse(FIXTURE.to_string(), "fixture").unwrap();
+        let config = LanguageConfig {
+            rust_crate: Some("fixture-types".to_string()),
+            ..LanguageConfig::new("rust")
+        };
+        let modules = [
+            "src/lib.rs",
+            "src/client.rs",
+            "src/health.rs",
+            "src/server.rs",
+            "src/proxy.rs",
+            "src/bin/proxy.rs",
+            "src/cbor.rs",
+            "src/arbitrary.rs",
+        ];
+        for file in generate(&spec, &config).unwrap() {
+            if file.path.starts_with("src/") && !modules.contains(&file.path.as_str()) {
+                continue;
+            }
+            let name = format!("rust_crate_{}", file.path.replace('/', "_"));
+            insta::assert_snapshot!(name, file.contents);
+        }
+    }
+}
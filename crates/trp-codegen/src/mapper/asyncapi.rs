@@ -0,0 +1,182 @@
+use anyhow::{bail, Result};
+use serde_json::{json, Map, Value};
+
+use super::openapi::{self, schema_ref};
+use crate::metadata::{Metadata, MethodMetadata, Subscription};
+use crate::resolver::ResolvedType;
+
+/// Version of the AsyncAPI specification the document follows.
+pub const VERSION: &str = "3.0.0";
+
+/// Server of a declared URL, reached over WebSocket: `https` URLs become
+/// `wss` servers and `http` ones `ws` servers.
+fn server(url: &str) -> Value {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("https", url));
+    let protocol = match scheme {
+        "http" | "ws" => "ws",
+        _ => "wss",
+    };
+    let mut server = Map::new();
+    match rest.split_once('/') {
+        Some((host, path)) if !path.is_empty() => {
+            server.insert("host".to_string(), Value::from(host));
+            server.insert("pathname".to_string(), Value::from(format!("/{}", path)));
+        }
+        _ => {
+            server.insert("host".to_string(), Value::from(rest.trim_end_matches('/')));
+        }
+    }
+    server.insert("protocol".to_string(), Value::from(protocol));
+    Value::Object(server)
+}
+
+/// Messages given as examples, out of OpenAPI examples keyed by name.
+fn message_examples(examples: Map<String, Value>) -> Value {
+    examples
+        .into_iter()
+        .map(|(name, example)| json!({ "name": name, "payload": example["value"] }))
+        .collect()
+}
+
+/// Schema of the notifications of a subscription, carrying the id the
+/// method returned and an event.
+fn notification_schema(method: &MethodMetadata, subscription: &Subscription) -> Value {
+    let id = method
+        .result_type
+        .as_deref()
+        .map(schema_ref)
+        .unwrap_or_else(|| json!({}));
+    let event = subscription
+        .event_type
+        .as_deref()
+        .map(schema_ref)
+        .unwrap_or_else(|| json!({}));
+    json!({
+        "type": "object",
+        "required": ["jsonrpc", "method", "params"],
+        "properties": {
+            "jsonrpc": { "const": "2.0" },
+            "method": { "const": subscription.notification },
+            "params": {
+                "type": "object",
+                "required": ["subscription", "result"],
+                "properties": { "subscription": id, "result": event },
+            },
+        },
+    })
+}
+
+/// Channel of a subscription: the call opening it, its response and the
+/// notifications that follow.
+fn channel(method: &MethodMetadata, subscription: &Subscription, meta: &Metadata) -> Value {
+    let (request_examples, response_examples) = openapi::examples(method);
+    let mut request = Map::new();
+    request.insert("name".to_string(), Value::from(method.name.as_str()));
+    if let Some(summary) = &method.summary {
+        request.insert("summary".to_string(), Value::from(summary.as_str()));
+    }
+    request.insert("payload".to_string(), openapi::request_schema(method));
+    if !request_examples.is_empty() {
+        request.insert("examples".to_string(), message_examples(request_examples));
+    }
+    let mut response = Map::new();
+    response.insert(
+        "payload".to_string(),
+        openapi::response_schema(method, meta),
+    );
+    if !response_examples.is_empty() {
+        response.insert("examples".to_string(), message_examples(response_examples));
+    }
+    json!({
+        "address": "/",
+        "messages": {
+            "request": request,
+            "response": response,
+            "notification": {
+                "name": subscription.notification,
+                "payload": notification_schema(method, subscription),
+            },
+        },
+    })
+}
+
+/// AsyncAPI document describing the subscriptions of the methods marked
+/// with `x-subscription` as WebSocket channels: callers send the call
+/// opening a subscription, get the response back, then receive its
+/// notifications. Fails for specs without subscriptions, which have
+/// nothing to describe.
+pub fn document(types: &[ResolvedType], meta: &Metadata) -> Result<Value> {
+    let subscriptions: Vec<(&MethodMetadata, &Subscription)> = meta
+        .methods
+        .iter()
+        .filter_map(|method| Some((method, method.subscription.as_ref()?)))
+        .collect();
+    if subscriptions.is_empty() {
+        bail!("no method is marked as a subscription with x-subscription");
+    }
+
+    let mut info = Map::new();
+    info.insert("title".to_string(), Value::from(meta.title_or_default()));
+    info.insert(
+        "version".to_string(),
+        Value::from(meta.version.as_deref().unwrap_or("0.0.0")),
+    );
+    if let Some(license) = &meta.license {
+        info.insert("license".to_string(), json!({ "name": license }));
+    }
+
+    let mut document = Map::new();
+    document.insert("asyncapi".to_string(), Value::from(VERSION));
+    document.insert("info".to_string(), Value::Object(info));
+    document.insert(
+        "defaultContentType".to_string(),
+        Value::from("application/json"),
+    );
+    if !meta.servers.is_empty() {
+        let servers: Map<String, Value> = meta
+            .servers
+            .iter()
+            .enumerate()
+            .map(|(index, url)| (format!("server-{}", index + 1), server(url)))
+            .collect();
+        document.insert("servers".to_string(), Value::Object(servers));
+    }
+
+    let mut channels = Map::new();
+    let mut operations = Map::new();
+    for (method, subscription) in subscriptions {
+        let name = method.camel_name();
+        let messages = |names: &[&str]| -> Value {
+            names
+                .iter()
+                .map(|message| json!({ "$ref": format!("#/channels/{}/messages/{}", name, message) }))
+                .collect()
+        };
+        let channel_ref = json!({ "$ref": format!("#/channels/{}", name) });
+        operations.insert(
+            name.clone(),
+            json!({
+                "action": "send",
+                "channel": channel_ref,
+                "messages": messages(&["request"]),
+                "reply": { "channel": channel_ref, "messages": messages(&["response"]) },
+            }),
+        );
+        operations.insert(
+            format!("{}Notification", name),
+            json!({
+                "action": "receive",
+                "channel": channel_ref,
+                "messages": messages(&["notification"]),
+            }),
+        );
+        channels.insert(name.clone(), channel(method, subscription, meta));
+    }
+    document.insert("channels".to_string(), Value::Object(channels));
+    document.insert("operations".to_string(), Value::Object(operations));
+    document.insert(
+        "components".to_string(),
+        json!({ "schemas": openapi::schemas(types)? }),
+    );
+    Ok(Value::Object(document))
+}
@@ -0,0 +1,310 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::Value;
+
+use super::{
+    extension_override, field_refs, is_scalar, map_primitive, nullable_inner, reaches, snake_case,
+    Construct, LanguageContext,
+};
+use crate::resolver::{
+    array_item_schema, array_tuple_items, object_additional_properties, object_pattern_properties,
+    ref_to_name, ResolvedField, ResolvedType,
+};
+
+const KEYWORDS: &[&str] = &[
+    "auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+    "else", "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while",
+];
+
+/// A struct declared for a component, with its members in field order.
+pub struct CStruct {
+    pub name: String,
+    /// Name of the component in the spec.
+    pub component: String,
+    pub deprecated: bool,
+    pub members: Vec<CMember>,
+}
+
+/// A struct member, e.g. `int64_t depth`, with an optional trailing comment.
+pub struct CMember {
+    pub declaration: String,
+    pub comment: Option<String>,
+}
+
+impl CMember {
+    fn new(declaration: String) -> Self {
+        Self {
+            declaration,
+            comment: None,
+        }
+    }
+}
+
+/// `trp_` prefixed snake case name of a component, e.g. `trp_tir_info`.
+pub fn type_name(raw: &str) -> String {
+    let name = snake_case(raw);
+    if name.is_empty() {
+        "trp_type".to_string()
+    } else {
+        format!("trp_{}", name)
+    }
+}
+
+pub fn field_name(raw: &str) -> String {
+    let mut out = snake_case(raw);
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert_str(0, "field_");
+    }
+    if KEYWORDS.contains(&out.as_str()) {
+        out.push('_');
+    }
+    out
+}
+
+/// Identifier of a member, honouring an `x-c-name` override.
+pub fn field_ident(field: &ResolvedField) -> String {
+    extension_override(&field.schema, "c", "name").unwrap_or_else(|| field_name(&field.name))
+}
+
+/// C type of a scalar schema, if it is one. Strings are owned `char *`.
+fn scalar(schema: &SchemaObject) -> Option<&'static str> {
+    if let Some(literal) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+        return Some(match literal {
+            Value::String(_) => "char *",
+            Value::Bool(_) => "bool",
+            Value::Number(n) if n.is_f64() => "double",
+            _ => "int64_t",
+        });
+    }
+    if schema.subschemas.is_some() {
+        return None;
+    }
+    if let Some(values) = &schema.enum_values {
+        return values.iter().all(|v| v.is_string()).then_some("char *");
+    }
+    match map_primitive(schema)? {
+        InstanceType::String => Some("char *"),
+        InstanceType::Integer => Some("int64_t"),
+        InstanceType::Number => Some("double"),
+        InstanceType::Boolean => Some("bool"),
+        _ => None,
+    }
+}
+
+fn reference(schema: &SchemaObject) -> Option<String> {
+    schema
+        .reference
+        .as_deref()
+        .and_then(|r| ref_to_name(r).ok())
+}
+
+/// Element type of an array of `schema`, for scalars and components.
+fn element(schema: &SchemaObject, ctx: &LanguageContext) -> Option<String> {
+    match reference(schema) {
+        Some(name) => Some(ctx.type_name(&name)),
+        None => scalar(schema).map(str::to_string),
+    }
+}
+
+/// Pointer to `ty`, e.g. `char **` for `char *`.
+fn pointer(ty: &str) -> String {
+    if ty.ends_with('*') {
+        format!("{}*", ty)
+    } else {
+        format!("{} *", ty)
+    }
+}
+
+/// Joins a C type with a declarator, e.g. `char *` and `name`.
+fn declare(ty: &str, declarator: &str) -> String {
+    if ty.ends_with('*') {
+        format!("{}{}", ty, declarator)
+    } else {
+        format!("{} {}", ty, declarator)
+    }
+}
+
+/// Members storing one field. Optional values are NULL pointers, or get a
+/// `has_` flag when stored inline; schemas without a fixed layout are kept
+/// as raw JSON text. `by_value` collects the components embedded by value,
+/// which must be declared first.
+pub(crate) fn members(
+    field: &ResolvedField,
+    owner: &str,
+    graph: &BTreeMap<String, BTreeSet<String>>,
+    ctx: &LanguageContext,
+    by_value: &mut BTreeSet<String>,
+) -> Vec<CMember> {
+    let ident = field_ident(field);
+    if let Some(ty) = extension_override(&field.schema, "c", "type") {
+        return vec![CMember::new(declare(&ty, &ident))];
+    }
+    let inner = nullable_inner(&field.schema);
+    let optional = !field.required || inner.is_some();
+    let schema = inner.unwrap_or_else(|| field.schema.clone());
+    let has = CMember::new(format!("bool has_{}", ident));
+
+    if let Some(name) = reference(&schema) {
+        let ty = ctx.type_name(&name);
+        // Embedding a component that leads back here would make the struct
+        // infinitely large.
+        if optional || reaches(graph, &name, owner) {
+            return vec![CMember::new(declare(&pointer(&ty), &ident))];
+        }
+        by_value.insert(name);
+        return vec![CMember::new(format!("{} {}", ty, ident))];
+    }
+
+    if let Some(ty) = scalar(&schema) {
+        let member = CMember::new(declare(ty, &ident));
+        return match optional && !ty.ends_with('*') {
+            true => vec![has, member],
+            false => vec![member],
+        };
+    }
+
+    let inline = match map_primitive(&schema) {
+        Some(InstanceType::Array) => {
+            let array = schema.array.as_deref();
+            if let Some(items) = array.and_then(array_tuple_items) {
+                // Only tuples of one scalar type fit a C array.
+                let types: Option<Vec<&str>> = items.iter().map(scalar).collect();
+                match types.as_deref() {
+                    Some([first, rest @ ..]) if rest.iter().all(|t| t == first) => {
+                        Some(vec![CMember::new(declare(
+                            first,
+                            &format!("{}[{}]", ident, items.len()),
+                        ))])
+                    }
+                    _ => None,
+                }
+            } else {
+                array
+                    .and_then(array_item_schema)
+                    .and_then(|item| element(&item, ctx))
+                    .map(|ty| {
+                        vec![
+                            CMember::new(declare(&pointer(&ty), &ident)),
+                            CMember::new(format!("size_t {}_len", ident)),
+                        ]
+                    })
+            }
+        }
+        Some(InstanceType::Object) => schema
+            .object
+            .as_deref()
+            .and_then(|object| {
+                object_additional_properties(object)
+                    .or_else(|| object_pattern_properties(object).map(|(_, value)| value))
+            })
+            .and_then(|value| element(&value, ctx))
+            .map(|ty| {
+                vec![
+                    CMember::new(format!("char **{}_keys", ident)),
+                    CMember::new(declare(&pointer(&ty), &format!("{}_values", ident))),
+                    CMember::new(format!("size_t {}_len", ident)),
+                ]
+            }),
+        _ => None,
+    };
+
+    match inline {
+        Some(mut members) => {
+            if optional {
+                members.insert(0, has);
+            }
+            members
+        }
+        None => {
+            degrade(&schema, ctx);
+            vec![CMember {
+                declaration: format!("char *{}_json", ident),
+                comment: Some("raw JSON".to_string()),
+            }]
+        }
+    }
+}
+
+/// Records the construct of `schema` that [`members`] keeps as raw JSON.
+/// Schemas without a type are JSON of any shape to begin with.
+fn degrade(schema: &SchemaObject, ctx: &LanguageContext) {
+    let array = schema.array.as_deref();
+    let object = schema.object.as_deref();
+    if schema.subschemas.is_some() {
+        ctx.degrade_subschemas(schema);
+    } else if schema.enum_values.is_some() {
+        ctx.degrade(Construct::Enum);
+    } else if array.and_then(array_tuple_items).is_some() {
+        ctx.degrade(Construct::Tuple);
+    } else if array.and_then(array_item_schema).is_some() {
+        ctx.degrade(Construct::Array);
+    } else if object
+        .and_then(|o| {
+            object_additional_properties(o).or_else(|| object_pattern_properties(o).map(|(_, v)| v))
+        })
+        .is_some()
+    {
+        ctx.degrade(Construct::Map);
+    }
+}
+
+/// Structs of the given components, ordered so that components embedded by
+/// value come before the structs embedding them.
+pub fn structs(types: &[ResolvedType], ctx: &LanguageContext) -> Vec<CStruct> {
+    let graph = field_refs(types);
+    let mut deps: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    let mut structs: Vec<CStruct> = Vec::new();
+    for ty in types {
+        let mut by_value = BTreeSet::new();
+        let members = ty
+            .fields
+            .iter()
+            .flat_map(|field| {
+                let mut members = members(field, &ty.name, &graph, ctx, &mut by_value);
+                if field.schema.metadata.as_ref().is_some_and(|m| m.deprecated) {
+                    for member in &mut members {
+                        member.comment = Some(match member.comment.take() {
+                            Some(comment) => format!("{}, deprecated", comment),
+                            None => "deprecated".to_string(),
+                        });
+                    }
+                }
+                members
+            })
+            .collect();
+        deps.insert(&ty.name, by_value);
+        structs.push(CStruct {
+            name: ctx.type_name(&ty.name),
+            component: ty.name.clone(),
+            deprecated: ty.schema.metadata.as_ref().is_some_and(|m| m.deprecated),
+            members,
+        });
+    }
+
+    let mut ordered = Vec::new();
+    let mut placed = BTreeSet::new();
+    fn place<'a>(
+        name: &'a str,
+        deps: &'a BTreeMap<&str, BTreeSet<String>>,
+        placed: &mut BTreeSet<&'a str>,
+        ordered: &mut Vec<&'a str>,
+    ) {
+        if !placed.insert(name) {
+            return;
+        }
+        for dep in deps.get(name).into_iter().flatten() {
+            place(dep, deps, placed, ordered);
+        }
+        ordered.push(name);
+    }
+    for ty in types {
+        place(&ty.name, &deps, &mut placed, &mut ordered);
+    }
+    let position: BTreeMap<&str, usize> =
+        ordered.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    structs.sort_by_key(|s| position.get(s.component.as_str()).copied());
+    structs
+}
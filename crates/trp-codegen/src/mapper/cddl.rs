@@ -0,0 +1,340 @@
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::Value;
+
+use super::{
+    extension_override, is_scalar, map_primitive, map_reference, nullable_inner, snake_case,
+    Construct, LanguageContext,
+};
+use crate::metadata::Metadata;
+use crate::resolver::{
+    any_of, array_item_schema, array_tuple_items, constraints, object_additional_properties,
+    object_pattern_properties, one_of, Constraint, ResolvedField, ResolvedType,
+};
+
+/// Types of the standard prelude (RFC 8610, appendix D), which a rule must
+/// not redefine.
+const PRELUDE: &[&str] = &[
+    "any",
+    "uint",
+    "nint",
+    "int",
+    "bstr",
+    "bytes",
+    "tstr",
+    "text",
+    "tdate",
+    "time",
+    "number",
+    "biguint",
+    "bignint",
+    "bigint",
+    "integer",
+    "unsigned",
+    "decfrac",
+    "bigfloat",
+    "eb64url",
+    "eb64legacy",
+    "eb16",
+    "encoded-cbor",
+    "uri",
+    "b64url",
+    "b64legacy",
+    "regexp",
+    "mime-message",
+    "cbor-any",
+    "float16",
+    "float32",
+    "float64",
+    "float16-32",
+    "float32-64",
+    "float",
+    "false",
+    "true",
+    "bool",
+    "nil",
+    "null",
+    "undefined",
+];
+
+/// A rule defining a component.
+pub struct CddlRule {
+    pub name: String,
+    /// Comment lines above the rule, from the description.
+    pub comments: Vec<String>,
+    /// Entries of the map the rule defines, or `None` for a rule naming
+    /// another type.
+    pub entries: Option<Vec<CddlEntry>>,
+    /// The type of a rule that isn't a map.
+    pub ty: String,
+}
+
+/// A member of a map, e.g. `? depth: 1..64`.
+pub struct CddlEntry {
+    pub comments: Vec<String>,
+    pub entry: String,
+}
+
+pub fn type_name(raw: &str) -> String {
+    let mut name = snake_case(raw);
+    if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert_str(0, "t_");
+    }
+    if PRELUDE.contains(&name.as_str()) {
+        name.push_str("_type");
+    }
+    name
+}
+
+/// A map key: a bare word when CDDL reads it as the same text, otherwise a
+/// text literal.
+fn key(name: &str) -> String {
+    let bare = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    match bare {
+        true => name.to_string(),
+        false => text(name),
+    }
+}
+
+/// CDDL text string literal.
+fn text(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A `.regexp` control. CDDL takes XSD regular expressions, which match
+/// the whole text, so the anchors of a JSON Schema pattern are dropped and
+/// an unanchored end may match anything.
+fn regexp(pattern: &str) -> String {
+    let mut xsd = String::new();
+    match pattern.strip_prefix('^') {
+        Some(rest) => xsd.push_str(rest),
+        None => {
+            xsd.push_str(".*");
+            xsd.push_str(pattern);
+        }
+    }
+    let escaped = xsd.ends_with("\\$");
+    match xsd.strip_suffix('$').filter(|_| !escaped) {
+        Some(rest) => xsd = rest.to_string(),
+        None => xsd.push_str(".*"),
+    }
+    format!(".regexp {}", text(&xsd))
+}
+
+/// Literal of a scalar JSON value.
+fn literal(value: &Value) -> String {
+    match value {
+        Value::String(s) => text(s),
+        other => other.to_string(),
+    }
+}
+
+/// Integer bound as written in CDDL, e.g. `64` for `64.0`.
+fn bound(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        value.to_string()
+    }
+}
+
+fn choice(options: Vec<String>) -> String {
+    match options.len() {
+        1 => options.into_iter().next().unwrap_or_default(),
+        _ => format!("({})", options.join(" / ")),
+    }
+}
+
+/// Integers as a range or a bounded `int`, from `minimum`/`maximum`.
+fn integer(schema: &SchemaObject) -> String {
+    let mut minimum = None;
+    let mut maximum = None;
+    for constraint in constraints(schema) {
+        match constraint {
+            Constraint::Minimum(n) => minimum = Some(n),
+            Constraint::Maximum(n) => maximum = Some(n),
+            _ => {}
+        }
+    }
+    match (minimum, maximum) {
+        (Some(min), Some(max)) => format!("{}..{}", bound(min), bound(max)),
+        (Some(0.0), None) => "uint".to_string(),
+        (Some(min), None) => format!("int .ge {}", bound(min)),
+        (None, Some(max)) => format!("int .le {}", bound(max)),
+        (None, None) => "int".to_string(),
+    }
+}
+
+/// Text strings, with a `pattern` as `.regexp` or lengths as `.size`. CDDL
+/// applies one control per type, so a pattern wins over lengths.
+fn string(schema: &SchemaObject) -> String {
+    let mut min_length = 0;
+    let mut max_length = None;
+    for constraint in constraints(schema) {
+        match constraint {
+            Constraint::Pattern(pattern) => return format!("tstr {}", regexp(&pattern)),
+            Constraint::MinLength(n) => min_length = n,
+            Constraint::MaxLength(n) => max_length = Some(n),
+            _ => {}
+        }
+    }
+    match max_length {
+        Some(max) => format!("tstr .size ({}..{})", min_length, max),
+        None => "tstr".to_string(),
+    }
+}
+
+pub fn map_type(schema: &SchemaObject, ctx: &LanguageContext) -> String {
+    if let Some(ty) = ctx.type_override(schema) {
+        return ty;
+    }
+
+    if let Some(reference) = map_reference(schema, ctx) {
+        return reference;
+    }
+
+    if let Some(inner) = nullable_inner(schema) {
+        return choice(vec![map_type(&inner, ctx), "null".to_string()]);
+    }
+
+    if let Some(value) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+        return literal(value);
+    }
+
+    if let Some(subschemas) = &schema.subschemas {
+        return match one_of(subschemas).or_else(|| any_of(subschemas)) {
+            Some(options) => choice(options.iter().map(|o| map_type(o, ctx)).collect()),
+            None => {
+                ctx.degrade(Construct::Composition);
+                "any".to_string()
+            }
+        };
+    }
+
+    if let Some(values) = &schema.enum_values {
+        if !values.is_empty() && values.iter().all(is_scalar) {
+            return choice(values.iter().map(literal).collect());
+        }
+    }
+
+    match map_primitive(schema) {
+        Some(InstanceType::Array) => {
+            let array = schema.array.as_deref();
+            if let Some(items) = array.and_then(array_tuple_items) {
+                let items: Vec<String> = items.iter().map(|s| map_type(s, ctx)).collect();
+                return format!("[{}]", items.join(", "));
+            }
+            let occurrence = match array.and_then(|a| a.min_items).unwrap_or(0) {
+                0 => "*",
+                _ => "+",
+            };
+            match array.and_then(array_item_schema) {
+                Some(item) => format!("[{} {}]", occurrence, map_type(&item, ctx)),
+                None => format!("[{} any]", occurrence),
+            }
+        }
+        Some(InstanceType::Object) => {
+            let object = schema.object.as_deref();
+            if let Some(value) = object.and_then(object_additional_properties) {
+                return format!("{{ * tstr => {} }}", map_type(&value, ctx));
+            }
+            match object.and_then(object_pattern_properties) {
+                Some((pattern, value)) => format!(
+                    "{{ * (tstr {}) => {} }}",
+                    regexp(&pattern),
+                    map_type(&value, ctx)
+                ),
+                None => "{ * tstr => any }".to_string(),
+            }
+        }
+        Some(InstanceType::String) => string(schema),
+        Some(InstanceType::Integer) => integer(schema),
+        Some(InstanceType::Number) => "number".to_string(),
+        Some(InstanceType::Boolean) => "bool".to_string(),
+        Some(InstanceType::Null) => "null".to_string(),
+        _ => "any".to_string(),
+    }
+}
+
+/// Comment lines carrying a description, followed by a deprecation note.
+fn comment_lines(description: Option<&str>, deprecated: bool) -> Vec<String> {
+    let mut comments: Vec<String> = description
+        .map(|d| d.lines().map(str::to_string).collect())
+        .unwrap_or_default();
+    if deprecated {
+        comments.push("Deprecated.".to_string());
+    }
+    comments
+}
+
+fn entry(ty: &ResolvedType, field: &ResolvedField, ctx: &LanguageContext) -> CddlEntry {
+    let comments = ctx
+        .field_info(&ty.name, &field.name)
+        .map(|info| comment_lines(info.description.as_deref(), info.deprecated))
+        .unwrap_or_default();
+    let ty = extension_override(&field.schema, "cddl", "type")
+        .unwrap_or_else(|| map_type(&field.schema, ctx));
+    let occurrence = if field.required { "" } else { "? " };
+    CddlEntry {
+        comments,
+        entry: format!("{}{}: {}", occurrence, key(&field.name), ty),
+    }
+}
+
+pub fn rules(types: &[ResolvedType], ctx: &LanguageContext) -> Vec<CddlRule> {
+    types
+        .iter()
+        .map(|ty| {
+            let comments = ctx
+                .type_info(&ty.name)
+                .map(|info| comment_lines(info.description.as_deref(), info.deprecated))
+                .unwrap_or_default();
+            let is_map = !ty.fields.is_empty()
+                || (map_primitive(&ty.schema) == Some(InstanceType::Object)
+                    && ty.schema.object.as_deref().is_some_and(|o| {
+                        object_additional_properties(o).is_none()
+                            && object_pattern_properties(o).is_none()
+                    }));
+            let entries = is_map.then(|| {
+                let mut entries: Vec<CddlEntry> = ty
+                    .fields
+                    .iter()
+                    .map(|field| entry(ty, field, ctx))
+                    .collect();
+                // JSON objects take unknown members unless the schema
+                // closes them.
+                if !ty.closed() {
+                    entries.push(CddlEntry {
+                        comments: Vec::new(),
+                        entry: "* tstr => any".to_string(),
+                    });
+                }
+                entries
+            });
+            CddlRule {
+                name: ctx.type_name(&ty.name),
+                comments,
+                ty: match entries {
+                    Some(_) => String::new(),
+                    None => map_type(&ty.schema, ctx),
+                },
+                entries,
+            }
+        })
+        .collect()
+}
+
+/// Choice of the components methods take and return, which leads the
+/// document since CDDL reads its first rule as the root.
+pub fn root(meta: &Metadata, ctx: &LanguageContext) -> Option<String> {
+    let mut names: Vec<String> = Vec::new();
+    for method in &meta.methods {
+        for name in method.params_type.iter().chain(&method.result_type) {
+            let name = ctx.type_name(name);
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    (!names.is_empty()).then(|| names.join(" / "))
+}
@@ -0,0 +1,327 @@
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::Value;
+
+use super::{
+    camel_case, extension_override, field_refs, is_scalar, map_primitive, map_reference,
+    nullable_inner, reaches, sanitize_identifier, Construct, LanguageContext,
+};
+use crate::resolver::{
+    array_item_schema, array_tuple_items, object_additional_properties, object_pattern_properties,
+    ResolvedField, ResolvedType,
+};
+
+/// Words that can't name a record field or a variable in Elm.
+const KEYWORDS: &[&str] = &[
+    "alias", "as", "case", "effect", "else", "exposing", "if", "import", "in", "infix", "let",
+    "module", "of", "port", "then", "type", "where",
+];
+
+/// How a schema is written in Elm: its type, the decoder reading it and the
+/// encoder function writing it back. Decoders and encoders are parenthesized
+/// whenever they are applications, so they can be passed on as arguments.
+pub struct ElmCodec {
+    pub ty: String,
+    pub decoder: String,
+    pub encoder: String,
+}
+
+/// A component rendered as a record, with its decoder and encoder.
+/// Recursive components can't be type aliases and are wrapped in a custom
+/// type with a single constructor of the same name instead.
+pub struct ElmType {
+    pub name: String,
+    pub decoder: String,
+    pub encoder: String,
+    pub recursive: bool,
+    pub deprecated: bool,
+    pub fields: Vec<ElmField>,
+}
+
+pub struct ElmField {
+    pub ident: String,
+    pub ty: String,
+    /// Decoder of the field within the record, e.g. `Decode.field "a" Decode.int`.
+    pub decoder: String,
+    /// Item of the `Encode.object` list; a `Maybe` of the key/value pair for
+    /// optional fields, which are left out when `Nothing`.
+    pub encoder: String,
+    pub required: bool,
+}
+
+impl ElmType {
+    pub fn has_optional_fields(&self) -> bool {
+        self.fields.iter().any(|f| !f.required)
+    }
+}
+
+pub fn type_name(raw: &str) -> String {
+    sanitize_identifier(raw)
+}
+
+/// lowerCamelCase identifier of a field, e.g. `searchSpace` for
+/// `search_space`, with a trailing `_` on Elm keywords.
+pub fn field_name(raw: &str) -> String {
+    let mut out = camel_case(raw);
+    if out.is_empty() {
+        return "field".to_string();
+    }
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert_str(0, "field");
+    }
+    if KEYWORDS.contains(&out.as_str()) {
+        out.push('_');
+    }
+    out
+}
+
+/// Identifier of a field, honouring an `x-elm-name` override.
+pub fn field_ident(field: &ResolvedField) -> String {
+    extension_override(&field.schema, "elm", "name").unwrap_or_else(|| field_name(&field.name))
+}
+
+/// Name of the decoder of a component, e.g. `tirInfoDecoder`.
+pub fn decoder_name(type_name: &str) -> String {
+    let mut chars = type_name.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}Decoder", first.to_ascii_lowercase(), chars.as_str()),
+        None => "decoder".to_string(),
+    }
+}
+
+/// Name of the encoder of a component, e.g. `encodeTirInfo`.
+pub fn encoder_name(type_name: &str) -> String {
+    format!("encode{}", type_name)
+}
+
+/// Wraps `expr` in parentheses unless it is a single word or already
+/// enclosed, so it can be used as an argument.
+pub fn parens(expr: &str) -> String {
+    let enclosed = (expr.starts_with('(') && expr.ends_with(')'))
+        || (expr.starts_with('{') && expr.ends_with('}'));
+    if enclosed || !expr.contains(' ') {
+        expr.to_string()
+    } else {
+        format!("({})", expr)
+    }
+}
+
+/// Applies an encoder to `arg`, dropping the parentheses around encoders
+/// that are themselves applications.
+fn apply(encoder: &str, arg: &str) -> String {
+    match encoder.strip_prefix('(').and_then(|e| e.strip_suffix(')')) {
+        Some(inner) if !inner.starts_with('\\') => format!("{} {}", inner, arg),
+        _ => format!("{} {}", encoder, arg),
+    }
+}
+
+fn primitive(ty: &str, codec: &str) -> ElmCodec {
+    ElmCodec {
+        ty: ty.to_string(),
+        decoder: format!("Decode.{}", codec),
+        encoder: format!("Encode.{}", codec),
+    }
+}
+
+/// Any JSON value, for unions and other schemas without an Elm equivalent.
+fn json_value() -> ElmCodec {
+    ElmCodec {
+        ty: "Decode.Value".to_string(),
+        decoder: "Decode.value".to_string(),
+        encoder: "identity".to_string(),
+    }
+}
+
+pub fn map_type(schema: &SchemaObject, ctx: &LanguageContext) -> ElmCodec {
+    map_codec(schema, ctx, 0)
+}
+
+/// `depth` keeps the variables bound by nested tuple encoders distinct, as
+/// Elm rejects shadowing.
+fn map_codec(schema: &SchemaObject, ctx: &LanguageContext, depth: usize) -> ElmCodec {
+    if let Some(name) = map_reference(schema, ctx) {
+        return ElmCodec {
+            decoder: format!("(Decode.lazy (\\_ -> {}))", decoder_name(&name)),
+            encoder: encoder_name(&name),
+            ty: name,
+        };
+    }
+
+    if let Some(inner) = nullable_inner(schema) {
+        let inner = map_codec(&inner, ctx, depth);
+        return ElmCodec {
+            ty: ctx.wrap_nullable(&inner.ty),
+            decoder: format!("(Decode.nullable {})", inner.decoder),
+            encoder: format!("(encodeNullable {})", inner.encoder),
+        };
+    }
+
+    if let Some(literal) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+        return match literal {
+            Value::String(_) => primitive("String", "string"),
+            Value::Bool(_) => primitive("Bool", "bool"),
+            Value::Number(n) if n.is_f64() => primitive("Float", "float"),
+            _ => primitive("Int", "int"),
+        };
+    }
+
+    if schema.subschemas.is_some() {
+        ctx.degrade_subschemas(schema);
+        return json_value();
+    }
+
+    if let Some(enum_values) = &schema.enum_values {
+        if enum_values.iter().all(|v| v.is_string()) {
+            return primitive("String", "string");
+        }
+    }
+
+    match map_primitive(schema) {
+        Some(InstanceType::Array) => {
+            let Some(array) = &schema.array else {
+                return list(json_value());
+            };
+            if let Some(items) = array_tuple_items(array) {
+                let items: Vec<ElmCodec> =
+                    items.iter().map(|s| map_codec(s, ctx, depth + 1)).collect();
+                return tuple(&items, depth).unwrap_or_else(|| {
+                    ctx.degrade(Construct::Tuple);
+                    json_value()
+                });
+            }
+            match array_item_schema(array) {
+                Some(item) => list(map_codec(&item, ctx, depth)),
+                None => list(json_value()),
+            }
+        }
+        Some(InstanceType::Object) => {
+            let value = schema.object.as_deref().and_then(|object| {
+                object_additional_properties(object)
+                    .or_else(|| object_pattern_properties(object).map(|(_, value)| value))
+            });
+            match value {
+                Some(value) => {
+                    let value = map_codec(&value, ctx, depth);
+                    ElmCodec {
+                        ty: format!("Dict.Dict String {}", parens(&value.ty)),
+                        decoder: format!("(Decode.dict {})", value.decoder),
+                        encoder: format!("(Encode.dict identity {})", value.encoder),
+                    }
+                }
+                None => ElmCodec {
+                    ty: "Dict.Dict String Decode.Value".to_string(),
+                    decoder: "(Decode.dict Decode.value)".to_string(),
+                    encoder: "(Encode.dict identity identity)".to_string(),
+                },
+            }
+        }
+        Some(InstanceType::String) => primitive("String", "string"),
+        Some(InstanceType::Integer) => primitive("Int", "int"),
+        Some(InstanceType::Number) => primitive("Float", "float"),
+        Some(InstanceType::Boolean) => primitive("Bool", "bool"),
+        Some(InstanceType::Null) => ElmCodec {
+            ty: "()".to_string(),
+            decoder: "(Decode.null ())".to_string(),
+            encoder: "(\\_ -> Encode.null)".to_string(),
+        },
+        _ => json_value(),
+    }
+}
+
+fn list(item: ElmCodec) -> ElmCodec {
+    ElmCodec {
+        ty: format!("List {}", parens(&item.ty)),
+        decoder: format!("(Decode.list {})", item.decoder),
+        encoder: format!("(Encode.list {})", item.encoder),
+    }
+}
+
+/// Elm only has tuples of two and three items; longer ones stay JSON values.
+fn tuple(items: &[ElmCodec], depth: usize) -> Option<ElmCodec> {
+    let vars: Vec<String> = ["a", "b", "c"][..items.len().min(3)]
+        .iter()
+        .map(|v| format!("{}{}", v, depth))
+        .collect();
+    let map = match items.len() {
+        2 => "Decode.map2 Tuple.pair".to_string(),
+        3 => format!(
+            "Decode.map3 (\\{} -> ( {} ))",
+            vars.join(" "),
+            vars.join(", ")
+        ),
+        _ => return None,
+    };
+    let types: Vec<&str> = items.iter().map(|i| i.ty.as_str()).collect();
+    let decoders: Vec<String> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| format!("(Decode.index {} {})", index, item.decoder))
+        .collect();
+    let encoded: Vec<String> = items
+        .iter()
+        .zip(&vars)
+        .map(|(item, var)| format!("{} {}", item.encoder, var))
+        .collect();
+    Some(ElmCodec {
+        ty: format!("( {} )", types.join(", ")),
+        decoder: format!("({} {})", map, decoders.join(" ")),
+        encoder: format!(
+            "(\\( {} ) -> Encode.list identity [ {} ])",
+            vars.join(", "),
+            encoded.join(", ")
+        ),
+    })
+}
+
+/// Records, decoders and encoders of the given components.
+pub fn types(types: &[ResolvedType], ctx: &LanguageContext) -> Vec<ElmType> {
+    let graph = field_refs(types);
+    types
+        .iter()
+        .map(|ty| {
+            let name = ctx.type_name(&ty.name);
+            ElmType {
+                decoder: decoder_name(&name),
+                encoder: encoder_name(&name),
+                recursive: reaches(&graph, &ty.name, &ty.name),
+                deprecated: ty.schema.metadata.as_ref().is_some_and(|m| m.deprecated),
+                fields: ty.fields.iter().map(|f| field(f, ctx)).collect(),
+                name,
+            }
+        })
+        .collect()
+}
+
+/// Optional fields decode to `Nothing` when absent or `null`, so a nullable
+/// optional field is a single `Maybe`.
+fn field(field: &ResolvedField, ctx: &LanguageContext) -> ElmField {
+    let ident = field_ident(field);
+    let key = Value::String(field.name.clone()).to_string();
+    if field.required {
+        let codec = map_type(&field.schema, ctx);
+        return ElmField {
+            decoder: format!("Decode.field {} {}", key, codec.decoder),
+            encoder: format!(
+                "( {}, {} )",
+                key,
+                apply(&codec.encoder, &format!("value.{}", ident))
+            ),
+            ty: codec.ty,
+            ident,
+            required: true,
+        };
+    }
+    let inner = nullable_inner(&field.schema).unwrap_or_else(|| field.schema.clone());
+    let codec = map_type(&inner, ctx);
+    ElmField {
+        ty: ctx.wrap_optional(&codec.ty),
+        decoder: format!("optionalField {} {}", key, codec.decoder),
+        encoder: format!(
+            "Maybe.map (\\inner -> ( {}, {} )) value.{}",
+            key,
+            apply(&codec.encoder, "inner"),
+            ident
+        ),
+        ident,
+        required: false,
+    }
+}
@@ -0,0 +1,693 @@
+use anyhow::{anyhow, bail, Result};
+use schemars::schema::{InstanceType, SchemaObject};
+
+use std::collections::BTreeSet;
+
+use super::{
+    extension_override, is_empty_object, map_primitive, map_reference, nullable_inner, pascal_case,
+    sanitize_identifier, strip_null, LanguageContext,
+};
+use crate::metadata::Metadata;
+use crate::resolver::{
+    any_of, array_item_schema, array_tuple_items, extra_properties, object_additional_properties,
+    object_pattern_properties, one_of, ref_to_name, visit_schemas, Constraint, ResolvedField,
+    ResolvedType,
+};
+
+/// Wrapper struct generated for a positional tuple array, since Go has no
+/// tuple types. Items are exposed as `V0`, `V1`, ...
+pub struct GoTuple {
+    pub name: String,
+    pub items: Vec<String>,
+}
+
+/// Wrapper struct generated for a `oneOf`/`anyOf` union, holding one pointer
+/// per variant of which decoding sets exactly one.
+pub struct GoUnion {
+    pub name: String,
+    pub variants: Vec<GoVariant>,
+    /// Property whose value tells the variants apart, from an OpenAPI
+    /// `discriminator`. Without one, variants are tried in order.
+    pub discriminator: Option<String>,
+}
+
+impl GoUnion {
+    /// Variant types for the decoding error, e.g. `string, Predicate`.
+    pub fn variant_list(&self) -> String {
+        let types: Vec<&str> = self.variants.iter().map(|v| v.ty.as_str()).collect();
+        types.join(", ")
+    }
+}
+
+pub struct GoVariant {
+    /// Name of the field and, prefixed with `As`, of the accessor.
+    pub name: String,
+    pub ty: String,
+    /// Discriminator value selecting this variant, as a Go literal.
+    pub tag: Option<String>,
+    /// Whether the variant is a generated struct with a `Validate()` method.
+    pub validates: bool,
+}
+
+/// The generated `Validate()` method of a type: its body, one statement per
+/// line, the package-level regexps it relies on and the imports it needs.
+#[derive(Default)]
+pub struct GoValidation {
+    pub lines: Vec<String>,
+    pub patterns: Vec<(String, String)>,
+    pub imports: BTreeSet<&'static str>,
+}
+
+impl GoValidation {
+    /// Declares the package-level regexp for a field's pattern.
+    fn pattern_var(&mut self, type_name: String, field: &str, pattern: &str) -> String {
+        self.imports.insert("regexp");
+        let mut var = format!("{}{}Pattern", type_name, field);
+        if let Some(first) = var.get_mut(0..1) {
+            first.make_ascii_lowercase();
+        }
+        self.patterns.push((var.clone(), format!("{:?}", pattern)));
+        var
+    }
+}
+
+/// An `Example*` function decoding and validating the params, and the
+/// result when it is a component too, of a method example.
+pub struct GoExample {
+    pub function: String,
+    pub method: String,
+    pub example: String,
+    /// Decoded values as `(variable, type, JSON string literal)`.
+    pub values: Vec<(&'static str, String, String)>,
+}
+
+/// A method as `rpc.go` calls it, e.g. `DoTrpResolve`.
+pub struct GoCall {
+    /// Pascal form of the wire name, naming the method's constant.
+    pub name: String,
+    pub method: String,
+    /// Types of the params and result, raw JSON when they aren't components.
+    pub params: String,
+    pub result: String,
+    /// Whether the params are sent as an array, the call then being typed
+    /// with `[1]` of the params.
+    pub by_position: bool,
+}
+
+/// Calls of the spec's methods, in declared order.
+pub fn calls(meta: &Metadata, ctx: &LanguageContext) -> Vec<GoCall> {
+    let raw = || "json.RawMessage".to_string();
+    meta.methods
+        .iter()
+        .map(|method| GoCall {
+            name: method.pascal_name(),
+            method: method.name.clone(),
+            params: method
+                .params_type
+                .as_deref()
+                .map_or_else(raw, |t| ctx.type_name(t)),
+            result: method
+                .result_type
+                .as_deref()
+                .map_or_else(raw, |t| ctx.type_name(t)),
+            by_position: method.by_position(),
+        })
+        .collect()
+}
+
+/// Path of the module of version `version`: `path` itself up to major
+/// version 1, and from 2 on suffixed with the major version as Go requires,
+/// e.g. `example.com/trp/v2`.
+pub fn module_path(path: &str, version: Option<&str>) -> String {
+    let path = path.trim_end_matches('/');
+    let major = version
+        .and_then(|v| v.trim_start_matches('v').split('.').next())
+        .and_then(|major| major.parse::<u64>().ok())
+        .filter(|major| *major >= 2);
+    match major {
+        Some(major) if !path.ends_with(&format!("/v{}", major)) => {
+            format!("{}/v{}", path, major)
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// Version of the `go` directive: `configured`, checked to be a Go release
+/// that can build the files, or else the oldest one that can. Generic
+/// envelopes need Go 1.18, the others Go 1.16 for `io.ReadAll`.
+pub fn go_directive(configured: Option<&str>, generics: bool) -> Result<String> {
+    let oldest = if generics { (1, 18) } else { (1, 16) };
+    let Some(version) = configured else {
+        return Ok(format!("{}.{}", oldest.0, oldest.1));
+    };
+    let parts: Vec<u64> = version
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| anyhow!("invalid Go version `{}`", version))?;
+    let release = match parts.as_slice() {
+        [major, minor] | [major, minor, _] => (*major, *minor),
+        _ => bail!("invalid Go version `{}`", version),
+    };
+    if release < oldest {
+        bail!(
+            "the Go files need Go {}.{}, not {}{}",
+            oldest.0,
+            oldest.1,
+            version,
+            if generics {
+                "; generate them with --go-no-generics for older toolchains"
+            } else {
+                ""
+            }
+        );
+    }
+    Ok(version.to_string())
+}
+
+/// Example functions for the method examples whose params are a component.
+/// They are named after the params type, suffixed with the example name.
+pub fn examples(meta: &Metadata, ctx: &LanguageContext) -> Vec<GoExample> {
+    let mut functions = BTreeSet::new();
+    let mut out = Vec::new();
+    for method in &meta.methods {
+        let Some(params_type) = &method.params_type else {
+            continue;
+        };
+        for example in &method.examples {
+            let mut suffix = sanitize_identifier(&example.name);
+            if let Some(first) = suffix.get_mut(0..1) {
+                first.make_ascii_lowercase();
+            }
+            if !suffix.starts_with(|c: char| c.is_ascii_lowercase()) {
+                suffix = format!("example{}", suffix);
+            }
+            let base = format!("Example{}_{}", ctx.type_name(params_type), suffix);
+            let mut function = base.clone();
+            let mut n = 1;
+            while !functions.insert(function.clone()) {
+                n += 1;
+                function = format!("{}{}", base, n);
+            }
+
+            let mut values = vec![(
+                "params",
+                ctx.type_name(params_type),
+                string_literal(&example.params),
+            )];
+            if let (Some(result_type), Some(result)) = (&method.result_type, &example.result) {
+                values.push(("result", ctx.type_name(result_type), string_literal(result)));
+            }
+            out.push(GoExample {
+                function,
+                method: method.name.clone(),
+                example: example.name.clone(),
+                values,
+            });
+        }
+    }
+    out
+}
+
+/// Indented JSON of `value` as a raw string literal, or as an interpreted one
+/// when the JSON holds a backquote.
+fn string_literal(value: &serde_json::Value) -> String {
+    let json = serde_json::to_string_pretty(value).unwrap_or_default();
+    if json.contains('`') {
+        serde_json::to_string(&json).unwrap_or_default()
+    } else {
+        format!("`{}`", json)
+    }
+}
+
+pub fn type_name(raw: &str) -> String {
+    sanitize_identifier(raw)
+}
+
+pub fn field_name(raw: &str) -> String {
+    let ident = pascal_case(raw);
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("Field{}", ident)
+    } else {
+        ident
+    }
+}
+
+/// Suffix of an enum constant, which follows the type and field names and
+/// so may start with a digit.
+fn variant_suffix(raw: &str) -> String {
+    let suffix = pascal_case(raw);
+    if suffix.is_empty() {
+        "Empty".to_string()
+    } else {
+        suffix
+    }
+}
+
+/// Identifier of a field, honouring an `x-go-name` override. Fields stay
+/// exported whatever the configured case, as `encoding/json` skips the
+/// others.
+pub fn field_ident(field: &ResolvedField, ctx: &LanguageContext) -> String {
+    extension_override(&field.schema, "go", "name").unwrap_or_else(|| {
+        let mut ident = ctx.field_name(&field.name, field_name);
+        if let Some(first) = ident.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        ident
+    })
+}
+
+pub fn map_type(schema: &SchemaObject, ctx: &LanguageContext) -> String {
+    if let Some(ty) = ctx.type_override(schema) {
+        return ty;
+    }
+
+    if let Some(reference) = map_reference(schema, ctx) {
+        return reference;
+    }
+
+    if let Some(inner) = nullable_inner(schema) {
+        return ctx.wrap_nullable(&map_type(&inner, ctx));
+    }
+
+    if let Some(options) = union_options(schema) {
+        let variants: Vec<String> = options.iter().map(|s| map_type(s, ctx)).collect();
+        return union_name(&variants);
+    }
+
+    if let Some(InstanceType::Array) = map_primitive(schema) {
+        if let Some(array) = &schema.array {
+            if let Some(items) = array_tuple_items(array) {
+                let items: Vec<String> = items.iter().map(|s| map_type(s, ctx)).collect();
+                return tuple_name(&items);
+            }
+            if let Some(item) = array_item_schema(array) {
+                return format!("[]{}", map_type(&item, ctx));
+            }
+        }
+        return "[]interface{}".to_string();
+    }
+
+    if is_empty_object(schema) {
+        return "struct{}".to_string();
+    }
+
+    if let Some(InstanceType::Object) = map_primitive(schema) {
+        if let Some(object) = &schema.object {
+            if let Some(additional) = object_additional_properties(object) {
+                return format!("map[string]{}", map_type(&additional, ctx));
+            }
+            if let Some((_, value)) = object_pattern_properties(object) {
+                return format!("map[string]{}", map_type(&value, ctx));
+            }
+        }
+        return "map[string]interface{}".to_string();
+    }
+
+    match map_primitive(schema) {
+        Some(InstanceType::String) => "string".to_string(),
+        Some(InstanceType::Integer) => "int64".to_string(),
+        Some(InstanceType::Number) => "float64".to_string(),
+        Some(InstanceType::Boolean) => "bool".to_string(),
+        Some(InstanceType::Null) => "interface{}".to_string(),
+        _ => {
+            ctx.degrade_subschemas(schema);
+            "interface{}".to_string()
+        }
+    }
+}
+
+/// Collects the distinct tuple wrappers referenced anywhere in `types`.
+pub fn collect_tuples(types: &[ResolvedType], ctx: &LanguageContext) -> Vec<GoTuple> {
+    let mut tuples: Vec<GoTuple> = Vec::new();
+    let mut visit = |schema: &SchemaObject| {
+        let Some(items) = schema.array.as_deref().and_then(array_tuple_items) else {
+            return;
+        };
+        let items: Vec<String> = items.iter().map(|s| map_type(s, ctx)).collect();
+        let name = tuple_name(&items);
+        if !tuples.iter().any(|t| t.name == name) {
+            tuples.push(GoTuple { name, items });
+        }
+    };
+    for ty in types {
+        visit_schemas(&ty.schema, &mut visit);
+        for field in &ty.fields {
+            visit_schemas(&field.schema, &mut visit);
+        }
+    }
+    tuples.sort_by(|a, b| a.name.cmp(&b.name));
+    tuples
+}
+
+// Tuple wrappers are named after their item types, e.g. `TupleStringInt64`.
+fn tuple_name(items: &[String]) -> String {
+    let parts: Vec<String> = items.iter().map(|item| type_part(item)).collect();
+    format!("Tuple{}", parts.concat())
+}
+
+/// Identifier part standing for a Go type in the name of a wrapper.
+fn type_part(ty: &str) -> String {
+    sanitize_identifier(&ty.replace("[]", "List ").replace("map[string]", "Map "))
+}
+
+/// Options of a union that isn't merely a nullable type, less the `null`
+/// option, which decodes to a wrapper holding no variant.
+fn union_options(schema: &SchemaObject) -> Option<Vec<SchemaObject>> {
+    let subschemas = schema.subschemas.as_ref()?;
+    let options: Vec<SchemaObject> = one_of(subschemas)
+        .or_else(|| any_of(subschemas))?
+        .into_iter()
+        .filter(|option| map_primitive(option) != Some(InstanceType::Null))
+        .collect();
+    (!options.is_empty()).then_some(options)
+}
+
+// Union wrappers are named after their variant types, e.g.
+// `StringOrPredicate`.
+fn union_name(variants: &[String]) -> String {
+    let parts: Vec<String> = variants.iter().map(|v| type_part(v)).collect();
+    parts.join("Or")
+}
+
+/// Collects the distinct union wrappers referenced anywhere in `types`.
+pub fn collect_unions(types: &[ResolvedType], ctx: &LanguageContext) -> Vec<GoUnion> {
+    let mut unions: Vec<GoUnion> = Vec::new();
+    let mut visit = |schema: &SchemaObject| {
+        if ctx.type_override(schema).is_some() || nullable_inner(schema).is_some() {
+            return;
+        }
+        let Some(options) = union_options(schema) else {
+            return;
+        };
+        let name = map_type(schema, ctx);
+        if unions.iter().any(|u| u.name == name) {
+            return;
+        }
+        let discriminator = schema
+            .extensions
+            .get("discriminator")
+            .and_then(|d| d.get("propertyName"))
+            .and_then(|p| p.as_str());
+        let mut variants: Vec<GoVariant> = Vec::new();
+        for (position, option) in options.iter().enumerate() {
+            let ty = map_type(option, ctx);
+            let mut variant = type_part(&ty);
+            if variants.iter().any(|v| v.name == variant) {
+                variant = format!("{}{}", variant, position + 1);
+            }
+            let component = option
+                .reference
+                .as_deref()
+                .and_then(|r| ref_to_name(r).ok())
+                .and_then(|name| types.iter().find(|t| t.name == name));
+            variants.push(GoVariant {
+                name: variant,
+                ty,
+                tag: discriminator
+                    .zip(component)
+                    .and_then(|(property, component)| tag(component, property)),
+                validates: component.is_some(),
+            });
+        }
+        // Dispatching on the discriminator takes a distinct value per variant.
+        let tags: BTreeSet<&String> = variants.iter().filter_map(|v| v.tag.as_ref()).collect();
+        let discriminator = discriminator
+            .filter(|_| tags.len() == variants.len())
+            .map(str::to_string);
+        unions.push(GoUnion {
+            name,
+            variants,
+            discriminator,
+        });
+    };
+    for ty in types {
+        visit_schemas(&ty.schema, &mut visit);
+        for field in &ty.fields {
+            visit_schemas(&field.schema, &mut visit);
+        }
+    }
+    unions.sort_by(|a, b| a.name.cmp(&b.name));
+    unions
+}
+
+/// Go literal of the string `const` a component sets `property` to.
+fn tag(component: &ResolvedType, property: &str) -> Option<String> {
+    let field = component.fields.iter().find(|f| f.name == property)?;
+    let value = field.schema.const_value.as_ref()?.as_str()?;
+    Some(format!("{:?}", value))
+}
+
+/// Map of `ty` holding the members beyond its declared fields, if it types
+/// them. `encoding/json` has no catch-all, so the map is skipped by the
+/// struct tags and filled by custom `MarshalJSON`/`UnmarshalJSON` methods.
+pub struct GoExtra {
+    pub name: String,
+    /// Value type of the map.
+    pub ty: String,
+    /// Declared JSON keys, as Go string literals.
+    pub keys: Vec<String>,
+}
+
+impl GoExtra {
+    pub fn key_list(&self) -> String {
+        self.keys.join(", ")
+    }
+}
+
+pub fn extra(ty: &ResolvedType, ctx: &LanguageContext) -> Option<GoExtra> {
+    let extra = extra_properties(&ty.schema)?;
+    let mut name = "Extra".to_string();
+    while ty.fields.iter().any(|f| field_ident(f, ctx) == name) {
+        name.push('_');
+    }
+    Some(GoExtra {
+        name,
+        ty: map_type(&extra, ctx),
+        keys: ty.fields.iter().map(|f| format!("{:?}", f.name)).collect(),
+    })
+}
+
+/// Builds the `Validate()` body of `ty`. Fields are checked against their
+/// constraints and fields holding other generated structs are validated
+/// recursively. Optional fields are only checked when they hold a non-zero
+/// value, since that is how Go represents their absence.
+pub fn validation(ty: &ResolvedType, ctx: &LanguageContext) -> GoValidation {
+    let mut out = GoValidation::default();
+    for field in &ty.fields {
+        if ctx.type_override(&field.schema).is_some() {
+            continue;
+        }
+        let name = field_ident(field, ctx);
+        let inner = nullable_inner(&field.schema).unwrap_or_else(|| field.schema.clone());
+        let integer = map_primitive(&inner) == Some(InstanceType::Integer);
+        let fail = |constraint: &Constraint| {
+            format!(
+                "return &ValidationError{{Path: {:?}, Message: {:?}}}",
+                field.name,
+                constraint.message()
+            )
+        };
+
+        let mut checks = Vec::new();
+        for constraint in &field.constraints {
+            let condition = match constraint {
+                Constraint::MinLength(n) => {
+                    out.imports.insert("unicode/utf8");
+                    format!("utf8.RuneCountInString(value) < {}", n)
+                }
+                Constraint::MaxLength(n) => {
+                    out.imports.insert("unicode/utf8");
+                    format!("utf8.RuneCountInString(value) > {}", n)
+                }
+                Constraint::Pattern(pattern) => {
+                    let var = out.pattern_var(ctx.type_name(&ty.name), &name, pattern);
+                    format!("!{}.MatchString(value)", var)
+                }
+                Constraint::KeyPattern(pattern) => {
+                    let var = out.pattern_var(ctx.type_name(&ty.name), &name, pattern);
+                    checks.push("for key := range value {".to_string());
+                    checks.push(format!("    if !{}.MatchString(key) {{", var));
+                    checks.push(format!("        {}", fail(constraint)));
+                    checks.push("    }".to_string());
+                    checks.push("}".to_string());
+                    continue;
+                }
+                Constraint::Minimum(n) => {
+                    format!("{} < {}", operand(integer, *n), bound(integer, *n))
+                }
+                Constraint::Maximum(n) => {
+                    format!("{} > {}", operand(integer, *n), bound(integer, *n))
+                }
+                Constraint::MinItems(n) => format!("len(value) < {}", n),
+                Constraint::Address => "!IsAddress(value)".to_string(),
+                Constraint::UniqueItems => {
+                    out.imports.insert("encoding/json");
+                    checks.push("seen := make(map[string]bool, len(value))".to_string());
+                    checks.push("for _, item := range value {".to_string());
+                    checks.push("    key, err := json.Marshal(item)".to_string());
+                    checks.push("    if err != nil {".to_string());
+                    checks.push("        return err".to_string());
+                    checks.push("    }".to_string());
+                    checks.push("    if seen[string(key)] {".to_string());
+                    checks.push(format!("        {}", fail(constraint)));
+                    checks.push("    }".to_string());
+                    checks.push("    seen[string(key)] = true".to_string());
+                    checks.push("}".to_string());
+                    continue;
+                }
+            };
+            checks.push(format!("if {} {{", condition));
+            checks.push(format!("    {}", fail(constraint)));
+            checks.push("}".to_string());
+        }
+
+        // Union wrappers validate the variant they hold.
+        if inner.reference.is_some() || union_options(&inner).is_some() {
+            checks.push("if err := value.Validate(); err != nil {".to_string());
+            checks.push(format!(
+                "    return nestValidationError({:?}, err)",
+                field.name
+            ));
+            checks.push("}".to_string());
+        } else if let Some(item) = inner.array.as_deref().and_then(array_item_schema) {
+            if item.reference.is_some() {
+                out.imports.insert("fmt");
+                checks.push("for i, item := range value {".to_string());
+                checks.push("    if err := item.Validate(); err != nil {".to_string());
+                checks.push(format!(
+                    "        return nestValidationError(fmt.Sprintf(\"{}[%d]\", i), err)",
+                    field.name
+                ));
+                checks.push("    }".to_string());
+                checks.push("}".to_string());
+            }
+        }
+
+        if checks.is_empty() {
+            continue;
+        }
+        if nullable_inner(&field.schema).is_some() {
+            out.lines.push(format!("if v.{} != nil {{", name));
+            out.lines.push(format!("    value := *v.{}", name));
+        } else if !field.required {
+            let ty = map_type(&field.schema, ctx);
+            let present = match ty.as_str() {
+                "string" => format!("v.{} != \"\"", name),
+                "int64" | "float64" => format!("v.{} != 0", name),
+                "interface{}" => format!("v.{} != nil", name),
+                _ if ty.starts_with("[]") || ty.starts_with("map[") => {
+                    format!("len(v.{}) > 0", name)
+                }
+                _ => {
+                    out.imports.insert("reflect");
+                    format!("!reflect.ValueOf(v.{}).IsZero()", name)
+                }
+            };
+            out.lines.push(format!("if {} {{", present));
+            out.lines.push(format!("    value := v.{}", name));
+        } else {
+            out.lines.push("{".to_string());
+            out.lines.push(format!("    value := v.{}", name));
+        }
+        out.lines
+            .extend(checks.into_iter().map(|line| format!("    {}", line)));
+        out.lines.push("}".to_string());
+    }
+    out
+}
+
+/// Whether a field of `types` has its type overridden by `x-go-type`, in
+/// which case the file declaring them takes the configured imports. Go
+/// rejects unused imports, so the others don't.
+pub fn uses_overrides(types: &[ResolvedType], ctx: &LanguageContext) -> bool {
+    let mut used = false;
+    for ty in types {
+        for field in &ty.fields {
+            visit_schemas(&field.schema, &mut |schema| {
+                used |= ctx.type_override(schema).is_some();
+            });
+        }
+    }
+    used
+}
+
+/// Standard library packages imported by the generated file.
+pub fn imports(
+    types: &[ResolvedType],
+    tuples: &[GoTuple],
+    unions: &[GoUnion],
+    ctx: &LanguageContext,
+) -> Vec<&'static str> {
+    let mut imports = BTreeSet::new();
+    if !tuples.is_empty() || !unions.is_empty() {
+        imports.extend(["encoding/json", "fmt"]);
+    }
+    if !unions.is_empty() {
+        imports.insert("bytes");
+    }
+    for ty in types {
+        if extra_properties(&ty.schema).is_some() {
+            imports.extend(["encoding/json", "fmt"]);
+        }
+        imports.extend(validation(ty, ctx).imports);
+    }
+    imports.into_iter().collect()
+}
+
+fn operand(integer: bool, bound: f64) -> &'static str {
+    if integer && bound.fract() != 0.0 {
+        "float64(value)"
+    } else {
+        "value"
+    }
+}
+
+fn bound(integer: bool, bound: f64) -> String {
+    if integer && bound.fract() == 0.0 {
+        format!("{}", bound as i64)
+    } else {
+        format!("{:?}", bound)
+    }
+}
+
+/// Named constants for the values of a string enum field, e.g.
+/// `SubmitWitnessTypeVkey`. The suffixes derive from the values unless the
+/// schema lists them in `x-enum-varnames`.
+pub fn enum_constants(
+    ty: &ResolvedType,
+    field: &ResolvedField,
+    ctx: &LanguageContext,
+) -> Vec<(String, String)> {
+    let schema = strip_null(&field.schema).unwrap_or_else(|| field.schema.clone());
+    let Some(values) = &schema.enum_values else {
+        return Vec::new();
+    };
+    let varnames: Vec<&str> = schema
+        .extensions
+        .get("x-enum-varnames")
+        .and_then(|v| v.as_array())
+        .map(|names| names.iter().filter_map(|n| n.as_str()).collect())
+        .unwrap_or_default();
+
+    values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, value)| {
+            let text = value.as_str()?;
+            let suffix = match varnames.get(i) {
+                Some(name) => variant_suffix(name),
+                None => ctx.variant_name(text, variant_suffix),
+            };
+            Some((
+                format!(
+                    "{}{}{}",
+                    ctx.type_name(&ty.name),
+                    field_ident(field, ctx),
+                    suffix
+                ),
+                value.to_string(),
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+use crate::metadata::Metadata;
+use crate::resolver::{ref_to_name, ResolvedType};
+
+/// Dialect the exported schemas declare.
+pub const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Name of the file holding every component under `$defs`.
+pub const BUNDLE: &str = "bundle.schema.json";
+
+/// Where references point within an exported document.
+const DEFS: &str = "#/$defs/";
+
+/// Keywords whose values are instances rather than schemas, left untouched.
+const INSTANCE_KEYWORDS: &[&str] = &["const", "default", "enum", "example", "examples"];
+
+pub fn file_name(raw: &str) -> String {
+    format!("{}.schema.json", raw)
+}
+
+/// Schema of a component as JSON, with references pointing under `defs`,
+/// e.g. `#/$defs/`, and draft-07 tuple keywords rewritten for 2020-12.
+/// Bounds schemars keeps as floats are written back as integers where they
+/// are whole.
+pub fn convert(ty: &ResolvedType, defs: &str) -> Result<Value> {
+    let mut value = serde_json::to_value(&ty.schema)
+        .with_context(|| format!("failed to serialize schema of {}", ty.name))?;
+    rewrite(&mut value, defs);
+    Ok(value)
+}
+
+fn rewrite(value: &mut Value, defs: &str) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get_mut("$ref") {
+                if let Ok(name) = ref_to_name(reference) {
+                    *reference = format!("{}{}", defs, name);
+                }
+            }
+            // Tuples moved from `items` to `prefixItems`, and what follows
+            // them from `additionalItems` to `items`.
+            if matches!(object.get("items"), Some(Value::Array(_))) {
+                let items = object.remove("items").unwrap_or_default();
+                let rest = object.remove("additionalItems");
+                object.insert("prefixItems".to_string(), items);
+                if let Some(rest) = rest {
+                    object.insert("items".to_string(), rest);
+                }
+            }
+            if let Some(definitions) = object.remove("definitions") {
+                object.insert("$defs".to_string(), definitions);
+            }
+            for (key, nested) in object.iter_mut() {
+                if !INSTANCE_KEYWORDS.contains(&key.as_str()) {
+                    rewrite(nested, defs);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| rewrite(item, defs)),
+        Value::Number(number) => {
+            if let Some(whole) = number
+                .as_f64()
+                .filter(|n| number.is_f64() && n.fract() == 0.0 && n.abs() < 1e15)
+            {
+                *value = Value::from(whole as i64);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Components referenced anywhere in `value`.
+fn references(value: &Value, found: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(name) = object
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix(DEFS))
+            {
+                found.insert(name.to_string());
+            }
+            for (key, nested) in object {
+                if !INSTANCE_KEYWORDS.contains(&key.as_str()) {
+                    references(nested, found);
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| references(item, found)),
+        _ => {}
+    }
+}
+
+/// Prepends the dialect, `$id` and a title to a converted schema.
+fn document(id: &str, title: &str, schema: Value) -> Map<String, Value> {
+    let mut document = Map::new();
+    document.insert("$schema".to_string(), Value::from(DIALECT));
+    document.insert("$id".to_string(), Value::from(id));
+    let Value::Object(schema) = schema else {
+        return document;
+    };
+    if !schema.contains_key("title") {
+        document.insert("title".to_string(), Value::from(title));
+    }
+    document.extend(schema);
+    document
+}
+
+/// Standalone schema of every component, keyed by file name. Each one
+/// carries the components it reaches under `$defs`, so that it validates
+/// without the others.
+pub fn standalone(types: &[ResolvedType]) -> Result<Vec<(String, Value)>> {
+    let converted = types
+        .iter()
+        .map(|ty| Ok((ty.name.as_str(), convert(ty, DEFS)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let lookup = |name: &str| converted.iter().find(|(n, _)| *n == name).map(|(_, v)| v);
+
+    let mut files = Vec::new();
+    for (name, schema) in &converted {
+        let mut reached = BTreeSet::new();
+        let mut pending = vec![schema];
+        while let Some(next) = pending.pop() {
+            let mut found = BTreeSet::new();
+            references(next, &mut found);
+            for dep in found {
+                if dep != *name && reached.insert(dep.clone()) {
+                    pending.extend(lookup(&dep));
+                }
+            }
+        }
+
+        // The component itself is the root of its document rather than
+        // one of its `$defs`.
+        let own = format!("{}{}", DEFS, name);
+        let mut root = schema.clone();
+        retarget(&mut root, &own);
+        let mut document = document(&file_name(name), name, root);
+        let defs: Map<String, Value> = reached
+            .iter()
+            .filter_map(|dep| {
+                let mut schema = lookup(dep)?.clone();
+                retarget(&mut schema, &own);
+                Some((dep.clone(), schema))
+            })
+            .collect();
+        if !defs.is_empty() {
+            document.insert("$defs".to_string(), Value::Object(defs));
+        }
+        files.push((file_name(name), Value::Object(document)));
+    }
+    Ok(files)
+}
+
+/// Points references to `own` at the document root.
+fn retarget(value: &mut Value, own: &str) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get_mut("$ref") {
+                if reference == own {
+                    *reference = "#".to_string();
+                }
+            }
+            for (key, nested) in object.iter_mut() {
+                if !INSTANCE_KEYWORDS.contains(&key.as_str()) {
+                    retarget(nested, own);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| retarget(item, own)),
+        _ => {}
+    }
+}
+
+/// Every component under the `$defs` of a single document.
+pub fn bundle(types: &[ResolvedType], meta: &Metadata) -> Result<Value> {
+    let defs = types
+        .iter()
+        .map(|ty| Ok((ty.name.clone(), convert(ty, DEFS)?)))
+        .collect::<Result<Map<_, _>>>()?;
+    let mut document = Map::new();
+    document.insert("$schema".to_string(), Value::from(DIALECT));
+    document.insert("$id".to_string(), Value::from(BUNDLE));
+    document.insert("title".to_string(), Value::from(meta.title_or_default()));
+    document.insert("$defs".to_string(), Value::Object(defs));
+    Ok(Value::Object(document))
+}
@@ -0,0 +1,672 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Deref as _;
+
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde::Serialize;
+use serde_json::Value;
+
+use self::naming::Naming;
+use crate::resolver::{any_of, one_of, ref_to_name, visit_schemas, ResolvedField, ResolvedType};
+
+pub mod asyncapi;
+pub mod c;
+pub mod cddl;
+pub mod elm;
+pub mod go;
+pub mod jsonschema;
+pub mod naming;
+pub mod ocaml;
+pub mod openapi;
+pub mod php;
+pub mod proto;
+pub mod python;
+pub mod rust;
+pub mod scala;
+pub mod ts;
+
+/// What a language's templates know about the components: the name each
+/// one is generated under along with the schema metadata its declarations
+/// are documented and mapped from, so that mappers don't have to dig it out
+/// of the raw schemas again.
+#[derive(Debug, Serialize)]
+pub struct LanguageContext {
+    types: BTreeMap<String, TypeInfo>,
+    language: String,
+    naming: Naming,
+    /// Import lines for the external types fields are mapped to.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    imports: Vec<String>,
+    /// Constructs mapped to the language's dynamic JSON type so far.
+    #[serde(skip)]
+    degraded: RefCell<Vec<Construct>>,
+}
+
+/// A kind of schema, as the coverage report lists them. Mappers report the
+/// ones they can't give a faithful type and map to the language's dynamic
+/// JSON type instead (`any`, `serde_json::Value`, `interface{}`...).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Construct {
+    /// A `oneOf` or `anyOf` of several schemas.
+    Union,
+    /// An `allOf` on a property; those of components are merged into their
+    /// fields.
+    Composition,
+    /// An array of items with positional schemas.
+    Tuple,
+    /// An array whose items have a schema.
+    Array,
+    /// An object of `additionalProperties` or `patternProperties` values.
+    Map,
+    Enum,
+    Const,
+    /// A schema admitting `null` besides its type.
+    Nullable,
+    /// `not`, which no mapper reads.
+    Not,
+    /// `if`, `then` and `else`, which no mapper reads.
+    Conditional,
+    /// `contains`, which no mapper reads.
+    Contains,
+    /// `propertyNames`, which no mapper reads.
+    PropertyNames,
+}
+
+impl Construct {
+    /// Whether no mapper reads the construct, leaving it unchecked in every
+    /// binding.
+    pub fn is_ignored(self) -> bool {
+        matches!(
+            self,
+            Self::Not | Self::Conditional | Self::Contains | Self::PropertyNames
+        )
+    }
+
+    /// The construct as the report names it.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Union => "union",
+            Self::Composition => "allOf",
+            Self::Tuple => "tuple",
+            Self::Array => "array",
+            Self::Map => "map",
+            Self::Enum => "enum",
+            Self::Const => "const",
+            Self::Nullable => "nullable",
+            Self::Not => "not",
+            Self::Conditional => "if/then/else",
+            Self::Contains => "contains",
+            Self::PropertyNames => "propertyNames",
+        }
+    }
+}
+
+/// A component as seen by one language, keyed by raw name in its context.
+#[derive(Debug, Serialize)]
+pub struct TypeInfo {
+    /// Name of the generated type.
+    pub name: String,
+    pub description: Option<String>,
+    pub deprecated: bool,
+    /// Declared properties, keyed by wire name.
+    pub fields: BTreeMap<String, FieldInfo>,
+}
+
+/// A property of a component.
+#[derive(Debug, Serialize)]
+pub struct FieldInfo {
+    pub description: Option<String>,
+    /// The `format` of the property's schema, e.g. `uri` or `int64`.
+    pub format: Option<String>,
+    /// Values of an `enum`, in declared order.
+    pub enum_values: Vec<Value>,
+    pub required: bool,
+    /// Whether the value may be `null`, through `type: [T, "null"]` or a
+    /// union of a single schema and `null`.
+    pub nullable: bool,
+    pub deprecated: bool,
+}
+
+impl TypeInfo {
+    fn new(ty: &ResolvedType, name: String) -> Self {
+        Self {
+            name,
+            description: description(&ty.schema),
+            deprecated: deprecated(&ty.schema),
+            fields: ty
+                .fields
+                .iter()
+                .map(|f| (f.name.clone(), FieldInfo::new(f)))
+                .collect(),
+        }
+    }
+}
+
+impl FieldInfo {
+    fn new(field: &ResolvedField) -> Self {
+        let schema = &field.schema;
+        Self {
+            description: description(schema),
+            format: schema.format.clone(),
+            enum_values: schema.enum_values.clone().unwrap_or_default(),
+            required: field.required,
+            nullable: nullable_inner(schema).is_some(),
+            deprecated: deprecated(schema),
+        }
+    }
+}
+
+fn description(schema: &SchemaObject) -> Option<String> {
+    schema.metadata.as_ref().and_then(|m| m.description.clone())
+}
+
+fn deprecated(schema: &SchemaObject) -> bool {
+    schema.metadata.as_ref().is_some_and(|m| m.deprecated)
+}
+
+impl LanguageContext {
+    pub fn type_name(&self, raw: &str) -> String {
+        self.types
+            .get(raw)
+            .map(|t| t.name.clone())
+            .unwrap_or_else(|| sanitize_identifier(raw))
+    }
+
+    /// Whether a component is generated under `name`.
+    pub fn has_type_name(&self, name: &str) -> bool {
+        self.types.values().any(|t| t.name == name)
+    }
+
+    /// Identifier of a field named `raw` on the wire, in the configured case
+    /// or as `derive` names it.
+    pub fn field_name(&self, raw: &str, derive: fn(&str) -> String) -> String {
+        self.naming.field_name(raw, derive)
+    }
+
+    /// Identifier of an enum variant, in the configured case or as `derive`
+    /// names it.
+    pub fn variant_name(&self, raw: &str, derive: fn(&str) -> String) -> String {
+        self.naming.variant_name(raw, derive)
+    }
+
+    /// The component named `raw` in the spec, if there is one.
+    pub fn type_info(&self, raw: &str) -> Option<&TypeInfo> {
+        self.types.get(raw)
+    }
+
+    /// The property `field` of the component named `ty`.
+    pub fn field_info(&self, ty: &str, field: &str) -> Option<&FieldInfo> {
+        self.types.get(ty)?.fields.get(field)
+    }
+
+    /// Wraps the type of a field that may be absent. TypeScript marks such
+    /// fields with `?` on the property name instead.
+    pub fn wrap_optional(&self, ty: &str) -> String {
+        match self.language.as_str() {
+            "python" => format!("Optional[{}]", ty),
+            "go" => format!("*{}", ty),
+            "rust" => format!("Option<{}>", ty),
+            "elm" => format!("Maybe {}", elm::parens(ty)),
+            _ => ty.to_string(),
+        }
+    }
+
+    /// Wraps the type of a value that may be present but `null`.
+    pub fn wrap_nullable(&self, ty: &str) -> String {
+        match self.language.as_str() {
+            "ts" | "typescript" | "deno" => format!("{} | null", ty),
+            "python" => format!("Optional[{}]", ty),
+            "go" => format!("*{}", ty),
+            "rust" => format!("Option<{}>", ty),
+            "elm" => format!("Maybe {}", elm::parens(ty)),
+            _ => ty.to_string(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// `self`, with import lines to emit at the top of the types files.
+    pub fn with_imports(self, imports: Vec<String>) -> Self {
+        Self { imports, ..self }
+    }
+
+    /// Import lines bringing in the external types of `x-{lang}-type`
+    /// overrides, as configured.
+    pub fn external_imports(&self) -> &[String] {
+        &self.imports
+    }
+
+    /// Type forced by an `x-{lang}-type` extension on `schema`, if any.
+    pub fn type_override(&self, schema: &SchemaObject) -> Option<String> {
+        extension_override(schema, &self.language, "type")
+    }
+
+    /// Records that `construct` was mapped to the dynamic JSON type.
+    pub fn degrade(&self, construct: Construct) {
+        self.degraded.borrow_mut().push(construct);
+    }
+
+    /// Records the subschemas of `schema`, if it has any, as mapped to the
+    /// dynamic JSON type: a union when it has options, a composition
+    /// otherwise.
+    pub fn degrade_subschemas(&self, schema: &SchemaObject) {
+        if let Some(subschemas) = &schema.subschemas {
+            self.degrade(match one_of(subschemas).or_else(|| any_of(subschemas)) {
+                Some(_) => Construct::Union,
+                None => Construct::Composition,
+            });
+        }
+    }
+
+    /// The constructs recorded since the last call.
+    pub fn take_degraded(&self) -> Vec<Construct> {
+        self.degraded.take()
+    }
+}
+
+pub fn build_context(types: &[ResolvedType], lang: &str, naming: &Naming) -> LanguageContext {
+    let derive: fn(&str) -> String = match lang {
+        "ts" | "typescript" | "deno" => ts::type_name,
+        "python" => python::type_name,
+        "go" => go::type_name,
+        "rust" => rust::type_name,
+        "elm" => elm::type_name,
+        "c" => c::type_name,
+        "php" => php::type_name,
+        "scala" => scala::type_name,
+        "ocaml" => ocaml::type_name,
+        "proto" => proto::type_name,
+        "cddl" => cddl::type_name,
+        _ => sanitize_identifier,
+    };
+    let mut infos = BTreeMap::new();
+    for ty in types {
+        let name = extension_override(&ty.schema, lang, "name")
+            .unwrap_or_else(|| naming.type_name(&ty.name, derive));
+        infos.insert(ty.name.clone(), TypeInfo::new(ty, name));
+    }
+
+    LanguageContext {
+        types: infos,
+        language: lang.to_string(),
+        naming: *naming,
+        imports: Vec::new(),
+        degraded: RefCell::default(),
+    }
+}
+
+/// Value of the `x-{lang}-{kind}` vendor extension of `schema`, such as
+/// `x-go-name` or `x-rust-type`. These let the spec fix an awkward derived
+/// identifier or type for one language without touching the templates.
+pub fn extension_override(schema: &SchemaObject, lang: &str, kind: &str) -> Option<String> {
+    let lang = match lang {
+        "typescript" | "deno" => "ts",
+        other => other,
+    };
+    schema
+        .extensions
+        .get(&format!("x-{}-{}", lang, kind))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+/// Components referenced by the fields of each component, keyed by raw
+/// component name.
+pub fn field_refs(types: &[ResolvedType]) -> BTreeMap<String, BTreeSet<String>> {
+    types
+        .iter()
+        .map(|ty| {
+            let mut refs = BTreeSet::new();
+            for field in &ty.fields {
+                visit_schemas(&field.schema, &mut |schema| {
+                    if let Some(name) = schema
+                        .reference
+                        .as_deref()
+                        .and_then(|r| ref_to_name(r).ok())
+                    {
+                        refs.insert(name);
+                    }
+                });
+            }
+            (ty.name.clone(), refs)
+        })
+        .collect()
+}
+
+/// Whether `to` is reachable from the fields of `from`. A component reaching
+/// itself is recursive.
+pub fn reaches(graph: &BTreeMap<String, BTreeSet<String>>, from: &str, to: &str) -> bool {
+    let mut seen = BTreeSet::new();
+    let mut stack: Vec<&str> = graph
+        .get(from)
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    while let Some(next) = stack.pop() {
+        if next == to {
+            return true;
+        }
+        if seen.insert(next) {
+            stack.extend(graph.get(next).into_iter().flatten().map(String::as_str));
+        }
+    }
+    false
+}
+
+pub fn map_reference(schema: &SchemaObject, ctx: &LanguageContext) -> Option<String> {
+    schema
+        .reference
+        .as_deref()
+        .and_then(|r| ref_to_name(r).ok())
+        .map(|n| ctx.type_name(&n))
+}
+
+pub fn map_primitive(schema: &SchemaObject) -> Option<InstanceType> {
+    match &schema.instance_type {
+        Some(types) => match types {
+            SingleOrVec::Single(t) => Some(*t.deref()),
+            SingleOrVec::Vec(list) => list.first().copied(),
+        },
+        None => None,
+    }
+}
+
+/// For `type: [T, "null"]` returns the schema with the `null` member removed,
+/// so mappers can render `T` and wrap it as nullable.
+pub fn strip_null(schema: &SchemaObject) -> Option<SchemaObject> {
+    let Some(SingleOrVec::Vec(types)) = &schema.instance_type else {
+        return None;
+    };
+    if !types.contains(&InstanceType::Null) {
+        return None;
+    }
+    let rest: Vec<InstanceType> = types
+        .iter()
+        .copied()
+        .filter(|t| *t != InstanceType::Null)
+        .collect();
+    let instance_type = match rest.as_slice() {
+        [] => return None,
+        [single] => SingleOrVec::Single(Box::new(*single)),
+        _ => SingleOrVec::Vec(rest),
+    };
+    Some(SchemaObject {
+        instance_type: Some(instance_type),
+        ..schema.clone()
+    })
+}
+
+/// Like [`strip_null`], but also unwraps a `oneOf`/`anyOf` of a single
+/// schema and `null`, for mappers that render both as the same optional type.
+pub fn nullable_inner(schema: &SchemaObject) -> Option<SchemaObject> {
+    if let Some(inner) = strip_null(schema) {
+        return Some(inner);
+    }
+    let subschemas = schema.subschemas.as_ref()?;
+    let options = one_of(subschemas).or_else(|| any_of(subschemas))?;
+    let is_null = |s: &SchemaObject| map_primitive(s) == Some(InstanceType::Null);
+    match options.as_slice() {
+        [a, b] if is_null(b) && !is_null(a) => Some(a.clone()),
+        [a, b] if is_null(a) && !is_null(b) => Some(b.clone()),
+        _ => None,
+    }
+}
+
+/// Whether `schema` is an object that can hold no member at all, such as the
+/// result of a method returning no payload: it declares no properties and
+/// allows no others.
+pub fn is_empty_object(schema: &SchemaObject) -> bool {
+    if map_primitive(schema) != Some(InstanceType::Object) || schema.subschemas.is_some() {
+        return false;
+    }
+    let Some(object) = schema.object.as_deref() else {
+        return false;
+    };
+    object.properties.is_empty()
+        && object.pattern_properties.is_empty()
+        && (object.max_properties == Some(0)
+            || matches!(
+                object.additional_properties.as_deref(),
+                Some(Schema::Bool(false))
+            ))
+}
+
+/// Whether a `const`/`default` value can be expressed as a literal in every
+/// target language.
+pub fn is_scalar(value: &serde_json::Value) -> bool {
+    value.is_string() || value.is_number() || value.is_boolean()
+}
+
+/// Whether any field of `types` is marked with `format: address`, so that
+/// validating it needs the `address` helpers of the binding.
+pub fn uses_address(types: &[ResolvedType], ctx: &LanguageContext) -> bool {
+    types.iter().flat_map(|ty| &ty.fields).any(|field| {
+        ctx.type_override(&field.schema).is_none()
+            && field
+                .constraints
+                .contains(&crate::resolver::Constraint::Address)
+    })
+}
+
+/// The `TirInfo` component of a TRP spec, which the `tir` helpers of the
+/// bindings check and decode, under its generated name and with the TIR
+/// versions its `version` lists in `x-supported`.
+pub struct Tir {
+    pub info: String,
+    pub versions: Vec<String>,
+}
+
+impl Tir {
+    /// The versions as a comma separated list of string literals, which
+    /// every binding reads alike.
+    pub fn version_literals(&self) -> String {
+        self.versions
+            .iter()
+            .map(|version| Value::String(version.clone()).to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// `None` for specs without a `TirInfo` of `bytecode`, `encoding` and a
+/// `version` listing the supported ones in `x-supported`. The list is an
+/// extension rather than an `enum` so that the schema itself stays open:
+/// clients built against it keep accepting the versions servers add later.
+pub fn tir(types: &[ResolvedType], ctx: &LanguageContext) -> Option<Tir> {
+    let info = types.iter().find(|ty| ty.name == "TirInfo")?;
+    let field = |name: &str| info.fields.iter().find(|f| f.name == name && f.required);
+    field("bytecode")?;
+    field("encoding")?;
+    let versions: Vec<String> = field("version")?
+        .schema
+        .extensions
+        .get("x-supported")?
+        .as_array()?
+        .iter()
+        .filter_map(|value| value.as_str().map(str::to_string))
+        .collect();
+    if versions.is_empty() {
+        return None;
+    }
+    Some(Tir {
+        info: ctx.type_name(&info.name),
+        versions,
+    })
+}
+
+/// Options of a `oneOf`/`anyOf` union of at least two types, less the
+/// `null` option, which optional fields take care of.
+pub fn union_options(schema: &SchemaObject) -> Option<Vec<SchemaObject>> {
+    let subschemas = schema.subschemas.as_ref()?;
+    let options: Vec<SchemaObject> = one_of(subschemas)
+        .or_else(|| any_of(subschemas))?
+        .into_iter()
+        .filter(|option| map_primitive(option) != Some(InstanceType::Null))
+        .collect();
+    (options.len() > 1).then_some(options)
+}
+
+/// The property named by the OpenAPI `discriminator` of a union and the
+/// values its variants give it, in the order of `options`, when each is a
+/// component setting it to a distinct string `const`.
+pub fn union_tags(
+    schema: &SchemaObject,
+    options: &[SchemaObject],
+    types: &[ResolvedType],
+) -> Option<(String, Vec<String>)> {
+    let property = schema
+        .extensions
+        .get("discriminator")
+        .and_then(|d| d.get("propertyName"))
+        .and_then(Value::as_str)?;
+    let tag = |option: &SchemaObject| {
+        let name = ref_to_name(option.reference.as_deref()?).ok()?;
+        let component = types.iter().find(|t| t.name == name)?;
+        let field = component.fields.iter().find(|f| f.name == property)?;
+        field
+            .schema
+            .const_value
+            .as_ref()?
+            .as_str()
+            .map(str::to_string)
+    };
+    let tags: Vec<String> = options.iter().map(tag).collect::<Option<_>>()?;
+    let distinct: BTreeSet<&String> = tags.iter().collect();
+    (distinct.len() == tags.len()).then(|| (property.to_string(), tags))
+}
+
+pub fn is_nullable(schema: &SchemaObject) -> bool {
+    strip_null(schema).is_some()
+}
+
+/// ASCII form of `raw` that the case conversions below work on. Accented
+/// Latin letters are transliterated (`é` to `e`, `ß` to `ss`); other
+/// letters and digits are escaped by code point as a word of their own
+/// (`名` to `u540d`), so that distinct names stay distinct.
+pub fn ascii_fold(raw: &str) -> String {
+    let mut out = String::new();
+    for ch in raw.chars() {
+        if ch.is_ascii() {
+            out.push(ch);
+        } else if let Some(latin) = transliterate(ch) {
+            // Capitals keep marking word boundaries: `ÉtatCivil` folds to
+            // `EtatCivil`.
+            let mut chars = latin.chars();
+            if let (true, Some(first)) = (ch.is_uppercase(), chars.next()) {
+                out.push(first.to_ascii_uppercase());
+                out.push_str(chars.as_str());
+            } else {
+                out.push_str(latin);
+            }
+        } else if ch.is_alphanumeric() {
+            out.push_str(&format!("_u{:04x}_", ch as u32));
+        } else {
+            out.push('_');
+        }
+    }
+    out
+}
+
+/// Lower case ASCII spelling of an accented Latin letter.
+fn transliterate(ch: char) -> Option<&'static str> {
+    Some(match ch.to_lowercase().next()? {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => "i",
+        'ł' | 'ľ' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ß' => "ss",
+        'ś' | 'š' | 'ş' => "s",
+        'ť' | 'ţ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
+
+/// PascalCase form of `raw`, e.g. `SearchSpace` for `search_space`. Empty
+/// when `raw` has no letters or digits, and may start with a digit.
+pub fn pascal_case(raw: &str) -> String {
+    let mut out = String::new();
+    let mut capitalize = true;
+    for ch in ascii_fold(raw).chars() {
+        if ch.is_ascii_alphanumeric() {
+            if capitalize {
+                out.push(ch.to_ascii_uppercase());
+                capitalize = false;
+            } else {
+                out.push(ch);
+            }
+        } else {
+            capitalize = true;
+        }
+    }
+    out
+}
+
+/// A PascalCase identifier for `name`, never empty nor starting with a digit
+/// (`Type3rdParty` for `3rd-party`).
+pub fn sanitize_identifier(name: &str) -> String {
+    let out = pascal_case(name);
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("Type{}", out)
+    } else {
+        out
+    }
+}
+
+/// lowerCamelCase form of a wire name, e.g. `searchSpace` for
+/// `search_space`. Empty when `raw` has no letters or digits.
+pub fn camel_case(raw: &str) -> String {
+    let mut out = String::new();
+    let mut capitalize = false;
+    for ch in ascii_fold(raw).chars() {
+        if !ch.is_ascii_alphanumeric() {
+            capitalize = !out.is_empty();
+            continue;
+        }
+        if out.is_empty() {
+            out.push(ch.to_ascii_lowercase());
+        } else if capitalize {
+            out.push(ch.to_ascii_uppercase());
+        } else {
+            out.push(ch);
+        }
+        capitalize = false;
+    }
+    out
+}
+
+pub fn snake_case(raw: &str) -> String {
+    let mut out = String::new();
+    let mut prev_lower = false;
+    for ch in ascii_fold(raw).chars() {
+        if ch.is_ascii_alphanumeric() {
+            if ch.is_ascii_uppercase() && prev_lower {
+                out.push('_');
+            }
+            out.push(ch.to_ascii_lowercase());
+            prev_lower = ch.is_ascii_lowercase() || ch.is_ascii_digit();
+        } else {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    out.trim_end_matches('_').to_string()
+}
@@ -0,0 +1,199 @@
+//! Naming conventions configurable per language. Each mapper derives
+//! identifiers with rules of its own; a [`Naming`] replaces those rules for
+//! type, field or enum variant names, so that a binding can follow the
+//! conventions of the codebase consuming it.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Case of a generated identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Case {
+    /// `SearchSpace`
+    Pascal,
+    /// `searchSpace`
+    Camel,
+    /// `search_space`
+    Snake,
+    /// `SEARCH_SPACE`
+    ScreamingSnake,
+}
+
+/// How initialisms such as `id` or `url` are cased within PascalCase and
+/// camelCase identifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Acronyms {
+    /// Like any other word: `TxId`, `ServerUrl`.
+    Title,
+    /// Upper case as a whole: `TxID`, `ServerURL`.
+    Upper,
+}
+
+/// Words treated as initialisms by [`Acronyms`], after Go's lint list.
+const ACRONYMS: &[&str] = &[
+    "acl", "api", "ascii", "cbor", "cpu", "css", "dns", "eof", "guid", "html", "http", "https",
+    "id", "ip", "json", "rpc", "sql", "ssh", "tcp", "tls", "ttl", "udp", "ui", "uid", "uri", "url",
+    "utf8", "utxo", "uuid", "xml",
+];
+
+/// Naming conventions of one language. Unset entries keep the rules of the
+/// language's mapper.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Naming {
+    pub types: Option<Case>,
+    pub fields: Option<Case>,
+    pub variants: Option<Case>,
+    /// Applies to the identifiers cased by this configuration; derived ones
+    /// keep the initialisms as spelled in the spec.
+    pub acronyms: Option<Acronyms>,
+}
+
+impl Naming {
+    /// Sets one entry from its name (`types`, `fields`, `variants` or
+    /// `acronyms`) and value, as given on the command line.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "types" => self.types = Some(parse(key, value)?),
+            "fields" => self.fields = Some(parse(key, value)?),
+            "variants" => self.variants = Some(parse(key, value)?),
+            "acronyms" => self.acronyms = Some(parse(key, value)?),
+            _ => anyhow::bail!(
+                "unknown naming entry {}, expected types, fields, variants or acronyms",
+                key
+            ),
+        }
+        Ok(())
+    }
+
+    /// `self`, with its unset entries taken from `fallback`.
+    pub fn or(self, fallback: Naming) -> Naming {
+        Naming {
+            types: self.types.or(fallback.types),
+            fields: self.fields.or(fallback.fields),
+            variants: self.variants.or(fallback.variants),
+            acronyms: self.acronyms.or(fallback.acronyms),
+        }
+    }
+
+    /// Whether any entry is set.
+    pub fn is_set(&self) -> bool {
+        *self != Naming::default()
+    }
+
+    pub fn type_name(&self, raw: &str, derive: fn(&str) -> String) -> String {
+        self.apply(self.types, raw, derive)
+    }
+
+    pub fn field_name(&self, raw: &str, derive: fn(&str) -> String) -> String {
+        self.apply(self.fields, raw, derive)
+    }
+
+    pub fn variant_name(&self, raw: &str, derive: fn(&str) -> String) -> String {
+        self.apply(self.variants, raw, derive)
+    }
+
+    fn apply(&self, case: Option<Case>, raw: &str, derive: fn(&str) -> String) -> String {
+        // The language's own rules know how to make an identifier of a name
+        // no case can start with.
+        match case.map(|case| convert(raw, case, self.acronyms)) {
+            Some(name) if name.starts_with(|c: char| c.is_ascii_alphabetic()) => name,
+            _ => derive(raw),
+        }
+    }
+
+    /// Conventions the spec declares for `lang` in an `x-{lang}-naming`
+    /// extension at its root, e.g. `"x-go-naming": {"acronyms": "upper"}`.
+    pub fn from_spec(raw: &Value, lang: &str) -> Result<Naming> {
+        let lang = match lang {
+            "typescript" | "deno" => "ts",
+            other => other,
+        };
+        let key = format!("x-{}-naming", lang);
+        match raw.get(&key) {
+            Some(value) => serde_json::from_value(value.clone())
+                .with_context(|| format!("invalid {} in spec", key)),
+            None => Ok(Naming::default()),
+        }
+    }
+}
+
+fn parse<T: DeserializeOwned>(key: &str, value: &str) -> Result<T> {
+    serde_json::from_value(Value::String(value.to_string()))
+        .with_context(|| format!("invalid {} naming: {}", key, value))
+}
+
+/// Splits an identifier into words at separators and case changes, keeping
+/// runs of capitals together: `HTTPServer_id` is `HTTP`, `Server`, `id`.
+pub fn words(raw: &str) -> Vec<String> {
+    let chars: Vec<char> = super::ascii_fold(raw).chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (i, &ch) in chars.iter().enumerate() {
+        if !ch.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if ch.is_ascii_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// `raw` in `case`, with initialisms cased after `acronyms` or, when unset,
+/// as spelled in `raw`.
+pub fn convert(raw: &str, case: Case, acronyms: Option<Acronyms>) -> String {
+    let words = words(raw);
+    match case {
+        Case::Snake => join_lower(&words, "_"),
+        Case::ScreamingSnake => join_lower(&words, "_").to_ascii_uppercase(),
+        Case::Pascal | Case::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                let lower = word.to_ascii_lowercase();
+                if i == 0 && case == Case::Camel {
+                    return lower;
+                }
+                match acronyms {
+                    Some(Acronyms::Upper) if ACRONYMS.contains(&lower.as_str()) => {
+                        word.to_ascii_uppercase()
+                    }
+                    Some(Acronyms::Title) => capitalize(&lower),
+                    _ => capitalize(word),
+                }
+            })
+            .collect(),
+    }
+}
+
+fn join_lower(words: &[String], separator: &str) -> String {
+    let words: Vec<String> = words.iter().map(|w| w.to_ascii_lowercase()).collect();
+    words.join(separator)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
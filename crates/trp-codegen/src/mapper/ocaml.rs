@@ -0,0 +1,427 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::Value;
+
+use super::{
+    extension_override, field_refs, is_scalar, map_primitive, map_reference, nullable_inner,
+    reaches, sanitize_identifier, snake_case, LanguageContext,
+};
+use crate::resolver::{
+    any_of, array_item_schema, array_tuple_items, object_additional_properties,
+    object_pattern_properties, one_of, ref_to_name, visit_schemas, ResolvedField, ResolvedType,
+};
+
+/// Keywords, and the predefined types a generated type would shadow.
+const RESERVED: &[&str] = &[
+    "and",
+    "as",
+    "assert",
+    "begin",
+    "bool",
+    "class",
+    "constraint",
+    "do",
+    "done",
+    "downto",
+    "else",
+    "end",
+    "exception",
+    "external",
+    "false",
+    "float",
+    "for",
+    "fun",
+    "function",
+    "functor",
+    "if",
+    "in",
+    "include",
+    "inherit",
+    "initializer",
+    "int",
+    "lazy",
+    "let",
+    "list",
+    "match",
+    "method",
+    "module",
+    "mutable",
+    "new",
+    "nonrec",
+    "object",
+    "of",
+    "open",
+    "option",
+    "or",
+    "private",
+    "rec",
+    "result",
+    "sig",
+    "string",
+    "struct",
+    "then",
+    "to",
+    "true",
+    "try",
+    "type",
+    "unit",
+    "val",
+    "virtual",
+    "when",
+    "while",
+    "with",
+];
+
+/// A declaration of the generated module. Records referring to each other
+/// share a `type ... and ...` group; unions come with hand-written codecs,
+/// as ppx_deriving_yojson would encode them as tagged lists.
+pub enum OcamlDecl {
+    /// Records of one group, and whether they reject unknown fields.
+    Records(Vec<OcamlRecord>, bool),
+    Union(OcamlUnion),
+}
+
+pub struct OcamlRecord {
+    pub name: String,
+    pub deprecated: bool,
+    pub fields: Vec<OcamlField>,
+}
+
+pub struct OcamlField {
+    pub ident: String,
+    pub ty: String,
+    /// ppx_deriving_yojson attributes, e.g. `[@key "search_space"]`.
+    pub attributes: String,
+}
+
+/// A `oneOf`/`anyOf` field type, decoded by trying its options in order.
+pub struct OcamlUnion {
+    pub name: String,
+    /// `(constructor, type)` of each option.
+    pub variants: Vec<(String, String)>,
+}
+
+pub fn type_name(raw: &str) -> String {
+    identifier(snake_case(raw))
+}
+
+pub fn field_name(raw: &str) -> String {
+    identifier(snake_case(raw))
+}
+
+fn identifier(mut name: String) -> String {
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "v_");
+    }
+    if RESERVED.contains(&name.as_str()) {
+        name.push('_');
+    }
+    name
+}
+
+/// Identifier of a field, honouring an `x-ocaml-name` override.
+pub fn field_ident(field: &ResolvedField) -> String {
+    extension_override(&field.schema, "ocaml", "name").unwrap_or_else(|| field_name(&field.name))
+}
+
+/// OCaml string literal.
+pub fn string_literal(value: &str) -> String {
+    Value::String(value.to_string()).to_string()
+}
+
+/// Declarations being collected, with what each one depends on.
+struct Collector<'a> {
+    ctx: &'a LanguageContext,
+    graph: &'a BTreeMap<String, BTreeSet<String>>,
+    unions: Vec<(OcamlUnion, BTreeSet<Node>)>,
+}
+
+/// A declaration in dependency order: a component by raw name, or a union
+/// by generated name.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Node {
+    Component(String),
+    Union(String),
+}
+
+impl Collector<'_> {
+    /// Maps `schema`, adding the declarations it uses to `deps`. Unions are
+    /// named after `union`, and fall back to raw JSON when one of their
+    /// options leads back to `owner`, since their codecs are defined after
+    /// the types they use.
+    fn map_type(
+        &mut self,
+        schema: &SchemaObject,
+        owner: &str,
+        union: &str,
+        deps: &mut BTreeSet<Node>,
+    ) -> String {
+        if let Some(ty) = self.ctx.type_override(schema) {
+            return ty;
+        }
+
+        if let Some(reference) = map_reference(schema, self.ctx) {
+            if let Some(name) = schema
+                .reference
+                .as_deref()
+                .and_then(|r| ref_to_name(r).ok())
+            {
+                deps.insert(Node::Component(name));
+            }
+            return reference;
+        }
+
+        if let Some(inner) = nullable_inner(schema) {
+            return format!("{} option", self.map_type(&inner, owner, union, deps));
+        }
+
+        if let Some(literal) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+            return match literal {
+                Value::String(_) => "string",
+                Value::Bool(_) => "bool",
+                Value::Number(n) if n.is_f64() => "float",
+                _ => "int",
+            }
+            .to_string();
+        }
+
+        if let Some(subschemas) = &schema.subschemas {
+            return match one_of(subschemas).or_else(|| any_of(subschemas)) {
+                Some(options) if !self.leads_back(&options, owner) => {
+                    let name = self.declare_union(&options, owner, union);
+                    deps.insert(Node::Union(name.clone()));
+                    name
+                }
+                _ => {
+                    self.ctx.degrade_subschemas(schema);
+                    "Yojson.Safe.t".to_string()
+                }
+            };
+        }
+
+        if let Some(enum_values) = &schema.enum_values {
+            if enum_values.iter().all(|v| v.is_string()) {
+                return "string".to_string();
+            }
+        }
+
+        match map_primitive(schema) {
+            Some(InstanceType::Array) => {
+                let array = schema.array.as_deref();
+                if let Some(items) = array.and_then(array_tuple_items) {
+                    let items: Vec<String> = items
+                        .iter()
+                        .map(|s| self.map_type(s, owner, union, deps))
+                        .collect();
+                    return format!("({})", items.join(" * "));
+                }
+                match array.and_then(array_item_schema) {
+                    Some(item) => format!("{} list", self.map_type(&item, owner, union, deps)),
+                    None => "Yojson.Safe.t list".to_string(),
+                }
+            }
+            Some(InstanceType::Object) => {
+                let value = schema.object.as_deref().and_then(|object| {
+                    object_additional_properties(object)
+                        .or_else(|| object_pattern_properties(object).map(|(_, value)| value))
+                });
+                match value {
+                    Some(value) => {
+                        format!("{} string_map", self.map_type(&value, owner, union, deps))
+                    }
+                    None => "Yojson.Safe.t".to_string(),
+                }
+            }
+            Some(InstanceType::String) => "string".to_string(),
+            Some(InstanceType::Integer) => "int".to_string(),
+            Some(InstanceType::Number) => "float".to_string(),
+            Some(InstanceType::Boolean) => "bool".to_string(),
+            _ => "Yojson.Safe.t".to_string(),
+        }
+    }
+
+    fn leads_back(&self, options: &[SchemaObject], owner: &str) -> bool {
+        let mut refs = BTreeSet::new();
+        for option in options {
+            visit_schemas(option, &mut |schema| {
+                if let Some(name) = schema
+                    .reference
+                    .as_deref()
+                    .and_then(|r| ref_to_name(r).ok())
+                {
+                    refs.insert(name);
+                }
+            });
+        }
+        refs.iter()
+            .any(|name| name == owner || reaches(self.graph, name, owner))
+    }
+
+    fn declare_union(&mut self, options: &[SchemaObject], owner: &str, union: &str) -> String {
+        let mut name = union.to_string();
+        let mut counter = 1;
+        while self.unions.iter().any(|(u, _)| u.name == name) || self.ctx.has_type_name(&name) {
+            counter += 1;
+            name = format!("{}_{}", union, counter);
+        }
+        let mut deps = BTreeSet::new();
+        let mut variants: Vec<(String, String)> = Vec::new();
+        for (position, option) in options.iter().enumerate() {
+            let ty = self.map_type(option, owner, &format!("{}_option", name), &mut deps);
+            let mut constructor = sanitize_identifier(&ty);
+            if variants.iter().any(|(c, _)| *c == constructor) {
+                constructor = format!("Option{}", position + 1);
+            }
+            variants.push((constructor, ty));
+        }
+        self.unions.push((
+            OcamlUnion {
+                name: name.clone(),
+                variants,
+            },
+            deps,
+        ));
+        name
+    }
+}
+
+/// Type of the property `field` of the component `owner` on its own, for
+/// the coverage report.
+pub(crate) fn map_field(
+    field: &ResolvedField,
+    owner: &str,
+    types: &[ResolvedType],
+    ctx: &LanguageContext,
+) -> String {
+    let graph = field_refs(types);
+    let mut collector = Collector {
+        ctx,
+        graph: &graph,
+        unions: Vec::new(),
+    };
+    collector.map_type(&field.schema, owner, owner, &mut BTreeSet::new())
+}
+
+/// Declarations of the given components, each one after those it uses.
+pub fn declarations(types: &[ResolvedType], ctx: &LanguageContext, strict: bool) -> Vec<OcamlDecl> {
+    let graph = field_refs(types);
+    let mut collector = Collector {
+        ctx,
+        graph: &graph,
+        unions: Vec::new(),
+    };
+
+    let mut records: BTreeMap<String, (OcamlRecord, bool)> = BTreeMap::new();
+    let mut deps: BTreeMap<Node, BTreeSet<Node>> = BTreeMap::new();
+    for ty in types {
+        let name = ctx.type_name(&ty.name);
+        let mut used = BTreeSet::new();
+        let fields = ty
+            .fields
+            .iter()
+            .map(|field| {
+                let ident = field_ident(field);
+                let union = format!("{}_{}", name, ident.trim_end_matches('_'));
+                let mut mapped = collector.map_type(&field.schema, &ty.name, &union, &mut used);
+                let mut attributes = Vec::new();
+                if ident != field.name {
+                    attributes.push(format!("[@key {}]", string_literal(&field.name)));
+                }
+                if !field.required {
+                    if !mapped.ends_with(" option") {
+                        mapped = format!("{} option", mapped);
+                    }
+                    attributes.push("[@default None]".to_string());
+                }
+                OcamlField {
+                    ident,
+                    ty: mapped,
+                    attributes: attributes.join(" "),
+                }
+            })
+            .collect();
+        deps.insert(Node::Component(ty.name.clone()), used);
+        records.insert(
+            ty.name.clone(),
+            (
+                OcamlRecord {
+                    name,
+                    deprecated: ty.schema.metadata.as_ref().is_some_and(|m| m.deprecated),
+                    fields,
+                },
+                strict && ty.closed(),
+            ),
+        );
+    }
+    let mut unions: BTreeMap<String, OcamlUnion> = BTreeMap::new();
+    for (union, used) in collector.unions {
+        deps.insert(Node::Union(union.name.clone()), used);
+        unions.insert(union.name.clone(), union);
+    }
+
+    let nodes: Vec<Node> = types
+        .iter()
+        .map(|t| Node::Component(t.name.clone()))
+        .collect();
+    let mut order = Vec::new();
+    let mut placed = BTreeSet::new();
+    for node in &nodes {
+        place(node, &deps, &graph, &mut placed, &mut order);
+    }
+
+    order
+        .into_iter()
+        .filter_map(|group| match group.as_slice() {
+            [Node::Union(name)] => unions.remove(name).map(OcamlDecl::Union),
+            _ => {
+                let mut strict = true;
+                let records: Vec<OcamlRecord> = group
+                    .iter()
+                    .filter_map(|node| match node {
+                        Node::Component(name) => records.remove(name),
+                        Node::Union(_) => None,
+                    })
+                    .map(|(record, record_strict)| {
+                        strict &= record_strict;
+                        record
+                    })
+                    .collect();
+                (!records.is_empty()).then_some(OcamlDecl::Records(records, strict))
+            }
+        })
+        .collect()
+}
+
+/// Appends the group of `node` to `order` after the groups it depends on.
+/// Components reaching each other form one group; unions never do.
+fn place(
+    node: &Node,
+    deps: &BTreeMap<Node, BTreeSet<Node>>,
+    graph: &BTreeMap<String, BTreeSet<String>>,
+    placed: &mut BTreeSet<Node>,
+    order: &mut Vec<Vec<Node>>,
+) {
+    if placed.contains(node) {
+        return;
+    }
+    let group: Vec<Node> = match node {
+        Node::Component(name) => graph
+            .keys()
+            .filter(|other| {
+                *other == name || (reaches(graph, name, other) && reaches(graph, other, name))
+            })
+            .map(|other| Node::Component(other.clone()))
+            .collect(),
+        Node::Union(_) => vec![node.clone()],
+    };
+    placed.extend(group.iter().cloned());
+    for member in &group {
+        for dep in deps.get(member).into_iter().flatten() {
+            if !group.contains(dep) {
+                place(dep, deps, graph, placed, order);
+            }
+        }
+    }
+    order.push(group);
+}
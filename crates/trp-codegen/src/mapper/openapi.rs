@@ -0,0 +1,246 @@
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use super::jsonschema;
+use crate::metadata::{Metadata, MethodMetadata};
+use crate::resolver::ResolvedType;
+
+/// Version of the OpenAPI specification the document follows.
+pub const VERSION: &str = "3.1.0";
+
+pub(super) const SCHEMAS: &str = "#/components/schemas/";
+
+/// Components describing the JSON-RPC envelope, added next to the spec's.
+pub(super) const ID: &str = "JsonRpcId";
+pub(super) const ERROR: &str = "JsonRpcError";
+
+pub(super) fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("{}{}", SCHEMAS, name) })
+}
+
+/// Path of a method's operation. JSON-RPC serves every method from one
+/// endpoint, so the method name goes into a fragment: paths stay unique
+/// while requests still reach the server root.
+pub fn path(method: &str) -> String {
+    format!("/#{}", method)
+}
+
+fn params_schema(method: &MethodMetadata) -> Option<Value> {
+    if let Some(name) = &method.params_type {
+        return Some(schema_ref(name));
+    }
+    if method.params.is_empty() {
+        return None;
+    }
+    let properties: Map<String, Value> = method
+        .params
+        .iter()
+        .map(|name| (name.clone(), json!({})))
+        .collect();
+    Some(json!({ "type": "object", "properties": properties }))
+}
+
+pub(super) fn request_schema(method: &MethodMetadata) -> Value {
+    let mut required = vec!["jsonrpc", "method", "id"];
+    let mut properties = Map::new();
+    properties.insert("jsonrpc".to_string(), json!({ "const": "2.0" }));
+    properties.insert("method".to_string(), json!({ "const": method.name }));
+    if let Some(params) = params_schema(method) {
+        required.insert(2, "params");
+        properties.insert("params".to_string(), params);
+    }
+    properties.insert("id".to_string(), schema_ref(ID));
+    json!({ "type": "object", "required": required, "properties": properties })
+}
+
+/// Schema of the `error` member of a response: any of the errors the method
+/// declares, or any other JSON-RPC error.
+fn error_schema(method: &MethodMetadata, meta: &Metadata) -> Value {
+    let declared: Vec<Value> = method
+        .error_codes
+        .iter()
+        .filter_map(|code| meta.errors.iter().find(|e| e.code == *code))
+        .map(|error| {
+            let mut properties = Map::new();
+            properties.insert("code".to_string(), json!({ "const": error.code }));
+            if let Some(data) = &error.data_type {
+                properties.insert("data".to_string(), schema_ref(data));
+            }
+            json!({
+                "title": error.message,
+                "allOf": [schema_ref(ERROR)],
+                "properties": properties,
+            })
+        })
+        .collect();
+    if declared.is_empty() {
+        return schema_ref(ERROR);
+    }
+    let mut options = declared;
+    options.push(schema_ref(ERROR));
+    json!({ "anyOf": options })
+}
+
+pub(super) fn response_schema(method: &MethodMetadata, meta: &Metadata) -> Value {
+    let result = method
+        .result_type
+        .as_deref()
+        .map(schema_ref)
+        .unwrap_or_else(|| json!({}));
+    json!({
+        "oneOf": [
+            {
+                "type": "object",
+                "required": ["jsonrpc", "id", "result"],
+                "properties": {
+                    "jsonrpc": { "const": "2.0" },
+                    "id": schema_ref(ID),
+                    "result": result,
+                },
+            },
+            {
+                "type": "object",
+                "required": ["jsonrpc", "id", "error"],
+                "properties": {
+                    "jsonrpc": { "const": "2.0" },
+                    "id": schema_ref(ID),
+                    "error": error_schema(method, meta),
+                },
+            },
+        ],
+    })
+}
+
+/// Request and response examples of a method, built from its example
+/// pairings and keyed by pairing name.
+pub(super) fn examples(method: &MethodMetadata) -> (Map<String, Value>, Map<String, Value>) {
+    let mut requests = Map::new();
+    let mut responses = Map::new();
+    for (index, example) in method.examples.iter().enumerate() {
+        let name = match example.name.is_empty() {
+            true => format!("example-{}", index + 1),
+            false => example.name.clone(),
+        };
+        requests.insert(
+            name.clone(),
+            json!({ "value": {
+                "jsonrpc": "2.0",
+                "method": method.name,
+                "params": example.params,
+                "id": 1,
+            } }),
+        );
+        if let Some(result) = &example.result {
+            responses.insert(
+                name,
+                json!({ "value": { "jsonrpc": "2.0", "id": 1, "result": result } }),
+            );
+        }
+    }
+    (requests, responses)
+}
+
+fn operation(method: &MethodMetadata, meta: &Metadata) -> Value {
+    let (request_examples, response_examples) = examples(method);
+    let mut request = Map::new();
+    request.insert("schema".to_string(), request_schema(method));
+    if !request_examples.is_empty() {
+        request.insert("examples".to_string(), Value::Object(request_examples));
+    }
+    let mut response = Map::new();
+    response.insert("schema".to_string(), response_schema(method, meta));
+    if !response_examples.is_empty() {
+        response.insert("examples".to_string(), Value::Object(response_examples));
+    }
+
+    let mut operation = Map::new();
+    operation.insert("operationId".to_string(), Value::from(method.camel_name()));
+    if let Some(summary) = &method.summary {
+        operation.insert("summary".to_string(), Value::from(summary.as_str()));
+    }
+    operation.insert(
+        "requestBody".to_string(),
+        json!({ "required": true, "content": { "application/json": request } }),
+    );
+    operation.insert(
+        "responses".to_string(),
+        json!({ "200": {
+            "description": "JSON-RPC response carrying either the result or an error",
+            "content": { "application/json": response },
+        } }),
+    );
+    Value::Object(operation)
+}
+
+/// OpenAPI document modelling each method as a POST operation exchanging
+/// JSON-RPC envelopes.
+pub fn document(types: &[ResolvedType], meta: &Metadata) -> Result<Value> {
+    let mut info = Map::new();
+    info.insert("title".to_string(), Value::from(meta.title_or_default()));
+    info.insert(
+        "version".to_string(),
+        Value::from(meta.version.as_deref().unwrap_or("0.0.0")),
+    );
+    if let Some(license) = &meta.license {
+        info.insert("license".to_string(), json!({ "name": license }));
+    }
+
+    let mut document = Map::new();
+    document.insert("openapi".to_string(), Value::from(VERSION));
+    document.insert("info".to_string(), Value::Object(info));
+    document.insert(
+        "jsonSchemaDialect".to_string(),
+        Value::from(jsonschema::DIALECT),
+    );
+    if !meta.servers.is_empty() {
+        let servers: Vec<Value> = meta
+            .servers
+            .iter()
+            .map(|url| json!({ "url": url }))
+            .collect();
+        document.insert("servers".to_string(), Value::Array(servers));
+    }
+
+    let paths: Map<String, Value> = meta
+        .methods
+        .iter()
+        .map(|method| {
+            (
+                path(&method.name),
+                json!({ "post": operation(method, meta) }),
+            )
+        })
+        .collect();
+    document.insert("paths".to_string(), Value::Object(paths));
+
+    document.insert(
+        "components".to_string(),
+        json!({ "schemas": schemas(types)? }),
+    );
+    Ok(Value::Object(document))
+}
+
+/// The spec's schemas, plus `JsonRpcId` and `JsonRpcError`.
+pub(super) fn schemas(types: &[ResolvedType]) -> Result<Map<String, Value>> {
+    let mut schemas = Map::new();
+    for ty in types {
+        schemas.insert(ty.name.clone(), jsonschema::convert(ty, SCHEMAS)?);
+    }
+    schemas.insert(
+        ID.to_string(),
+        json!({ "type": ["string", "integer", "null"] }),
+    );
+    schemas.insert(
+        ERROR.to_string(),
+        json!({
+            "type": "object",
+            "required": ["code", "message"],
+            "properties": {
+                "code": { "type": "integer" },
+                "message": { "type": "string" },
+                "data": {},
+            },
+        }),
+    );
+    Ok(schemas)
+}
@@ -0,0 +1,341 @@
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::Value;
+
+use super::{
+    camel_case, extension_override, is_scalar, map_primitive, map_reference, nullable_inner,
+    sanitize_identifier, LanguageContext,
+};
+use crate::resolver::{
+    array_item_schema, array_tuple_items, object_additional_properties, object_pattern_properties,
+    ResolvedField, ResolvedType,
+};
+
+/// Namespace of the generated classes, autoloaded from `src/`.
+pub const NAMESPACE: &str = "Tx3\\Trp";
+
+/// Names PHP reserves for its own types, which can't name a class.
+const RESERVED_CLASSES: &[&str] = &[
+    "array", "bool", "callable", "false", "float", "int", "iterable", "list", "mixed", "never",
+    "null", "object", "parent", "resource", "self", "static", "string", "true", "void",
+];
+
+/// How a schema is written in PHP: the native type of a property and the
+/// more precise PHPDoc type, e.g. `array` and `list<string>`.
+pub struct PhpType {
+    pub native: String,
+    pub doc: String,
+}
+
+/// A generated class, one per component.
+pub struct PhpClass {
+    pub name: String,
+    pub deprecated: bool,
+    pub properties: Vec<PhpProperty>,
+}
+
+impl PhpClass {
+    /// Properties whose constructor parameter needs a `@param` tag.
+    pub fn documented(&self) -> Vec<&PhpProperty> {
+        self.properties.iter().filter(|p| p.needs_doc()).collect()
+    }
+
+    pub fn optional(&self) -> Vec<&PhpProperty> {
+        self.properties.iter().filter(|p| !p.required).collect()
+    }
+
+    pub fn required(&self) -> Vec<&PhpProperty> {
+        self.properties.iter().filter(|p| p.required).collect()
+    }
+}
+
+/// A constructor-promoted property of a generated class.
+pub struct PhpProperty {
+    pub name: String,
+    pub ident: String,
+    pub native: String,
+    pub doc: String,
+    pub required: bool,
+    /// Expression decoding the property in `fromArray`.
+    pub from_array: String,
+    /// Expression encoding the non-null property in `jsonSerialize`.
+    pub serialize: String,
+}
+
+impl PhpProperty {
+    /// Whether the PHPDoc type says more than the native one.
+    pub fn needs_doc(&self) -> bool {
+        let nullable_doc = self
+            .native
+            .strip_prefix('?')
+            .map(|native| format!("{}|null", native));
+        self.doc != self.native && Some(&self.doc) != nullable_doc.as_ref()
+    }
+
+    /// The JSON key, as a string literal.
+    pub fn key(&self) -> String {
+        string_literal(&self.name)
+    }
+
+    /// The property as a constructor parameter.
+    pub fn parameter(&self) -> String {
+        match self.required {
+            true => format!("public {} ${}", self.native, self.ident),
+            false => format!("public {} ${} = null", self.native, self.ident),
+        }
+    }
+}
+
+pub fn type_name(raw: &str) -> String {
+    let name = sanitize_identifier(raw);
+    if RESERVED_CLASSES.contains(&name.to_ascii_lowercase().as_str()) {
+        format!("{}Type", name)
+    } else {
+        name
+    }
+}
+
+pub fn field_name(raw: &str) -> String {
+    let mut out = camel_case(raw);
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert_str(0, "field");
+    }
+    // `$this` can't be redeclared.
+    if out == "this" {
+        out.push('_');
+    }
+    out
+}
+
+/// Identifier of a property, honouring an `x-php-name` override.
+pub fn field_ident(field: &ResolvedField) -> String {
+    extension_override(&field.schema, "php", "name").unwrap_or_else(|| field_name(&field.name))
+}
+
+fn same(ty: &str) -> PhpType {
+    PhpType {
+        native: ty.to_string(),
+        doc: ty.to_string(),
+    }
+}
+
+fn array(doc: String) -> PhpType {
+    PhpType {
+        native: "array".to_string(),
+        doc,
+    }
+}
+
+/// Single quoted PHP string literal.
+pub fn string_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+pub fn map_type(schema: &SchemaObject, ctx: &LanguageContext) -> PhpType {
+    if let Some(ty) = ctx.type_override(schema) {
+        return same(&ty);
+    }
+
+    if let Some(reference) = map_reference(schema, ctx) {
+        return same(&reference);
+    }
+
+    if let Some(inner) = nullable_inner(schema) {
+        return nullable(map_type(&inner, ctx));
+    }
+
+    if let Some(literal) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+        let native = match literal {
+            Value::String(_) => "string",
+            Value::Bool(_) => "bool",
+            Value::Number(n) if n.is_f64() => "float",
+            _ => "int",
+        };
+        return PhpType {
+            native: native.to_string(),
+            doc: literal_doc(literal),
+        };
+    }
+
+    if schema.subschemas.is_some() {
+        ctx.degrade_subschemas(schema);
+        return same("mixed");
+    }
+
+    if let Some(enum_values) = &schema.enum_values {
+        if !enum_values.is_empty() && enum_values.iter().all(|v| v.is_string()) {
+            let variants: Vec<String> = enum_values.iter().map(literal_doc).collect();
+            return PhpType {
+                native: "string".to_string(),
+                doc: variants.join("|"),
+            };
+        }
+    }
+
+    match map_primitive(schema) {
+        Some(InstanceType::Array) => {
+            let array_schema = schema.array.as_deref();
+            if let Some(items) = array_schema.and_then(array_tuple_items) {
+                let items: Vec<String> = items.iter().map(|s| map_type(s, ctx).doc).collect();
+                return array(format!("array{{{}}}", items.join(", ")));
+            }
+            match array_schema.and_then(array_item_schema) {
+                Some(item) => array(format!("list<{}>", map_type(&item, ctx).doc)),
+                None => array("list<mixed>".to_string()),
+            }
+        }
+        Some(InstanceType::Object) => match map_value_schema(schema) {
+            Some(value) => array(format!("array<string, {}>", map_type(&value, ctx).doc)),
+            None => array("array<string, mixed>".to_string()),
+        },
+        Some(InstanceType::String) => same("string"),
+        Some(InstanceType::Integer) => same("int"),
+        Some(InstanceType::Number) => same("float"),
+        Some(InstanceType::Boolean) => same("bool"),
+        _ => same("mixed"),
+    }
+}
+
+fn nullable(inner: PhpType) -> PhpType {
+    if inner.native == "mixed" {
+        return inner;
+    }
+    PhpType {
+        native: format!("?{}", inner.native),
+        doc: format!("{}|null", inner.doc),
+    }
+}
+
+/// PHPDoc literal type of a scalar, e.g. `'pre'` or `1`.
+fn literal_doc(value: &Value) -> String {
+    match value {
+        Value::String(s) => string_literal(s),
+        other => other.to_string(),
+    }
+}
+
+/// Value schema of a map, from `additionalProperties` or a single
+/// `patternProperties` entry.
+fn map_value_schema(schema: &SchemaObject) -> Option<SchemaObject> {
+    let object = schema.object.as_deref()?;
+    object_additional_properties(object)
+        .or_else(|| object_pattern_properties(object).map(|(_, value)| value))
+}
+
+/// Expression turning the decoded JSON `value` into the property type, or
+/// `None` when it is used as is.
+fn from_array(schema: &SchemaObject, ctx: &LanguageContext, value: &str) -> Option<String> {
+    if ctx.type_override(schema).is_some() {
+        return None;
+    }
+    if let Some(reference) = map_reference(schema, ctx) {
+        return Some(format!("{}::fromArray({})", reference, value));
+    }
+    if let Some(inner) = nullable_inner(schema) {
+        return from_array(&inner, ctx, value)
+            .map(|expr| format!("{} === null ? null : {}", value, expr));
+    }
+    let item = match map_primitive(schema) {
+        Some(InstanceType::Array) => schema.array.as_deref().and_then(array_item_schema),
+        Some(InstanceType::Object) => map_value_schema(schema),
+        _ => None,
+    }?;
+    let convert = from_array(&item, ctx, "$item")?;
+    Some(format!(
+        "array_map(static fn ($item) => {}, {})",
+        convert, value
+    ))
+}
+
+/// Expression encoding the non-null `value` in `jsonSerialize`, or `None`
+/// when `json_encode` takes it as is. Maps are cast to objects so that empty
+/// ones encode as `{}` rather than `[]`; classes encode themselves.
+fn serialize(schema: &SchemaObject, ctx: &LanguageContext, value: &str) -> Option<String> {
+    if ctx.type_override(schema).is_some() || map_reference(schema, ctx).is_some() {
+        return None;
+    }
+    if let Some(inner) = nullable_inner(schema) {
+        return serialize(&inner, ctx, value)
+            .map(|expr| format!("{} === null ? null : {}", value, expr));
+    }
+    match map_primitive(schema) {
+        Some(InstanceType::Object) => {
+            let converted = map_value_schema(schema)
+                .and_then(|item| serialize(&item, ctx, "$item"))
+                .map(|convert| format!("array_map(static fn ($item) => {}, {})", convert, value));
+            Some(format!(
+                "(object) {}",
+                converted.as_deref().unwrap_or(value)
+            ))
+        }
+        Some(InstanceType::Array) => {
+            let item = schema.array.as_deref().and_then(array_item_schema)?;
+            let convert = serialize(&item, ctx, "$item")?;
+            Some(format!(
+                "array_map(static fn ($item) => {}, {})",
+                convert, value
+            ))
+        }
+        _ => None,
+    }
+}
+
+pub fn class(ty: &ResolvedType, ctx: &LanguageContext) -> PhpClass {
+    PhpClass {
+        name: ctx.type_name(&ty.name),
+        deprecated: ty.schema.metadata.as_ref().is_some_and(|m| m.deprecated),
+        properties: properties(ty, ctx),
+    }
+}
+
+/// Properties of a class, required ones first since PHP wants optional
+/// constructor parameters last.
+fn properties(ty: &ResolvedType, ctx: &LanguageContext) -> Vec<PhpProperty> {
+    let mut properties: Vec<PhpProperty> = ty
+        .fields
+        .iter()
+        .map(|field| {
+            let ident = field_ident(field);
+            let key = format!("$data[{}]", string_literal(&field.name));
+            let is_nullable = nullable_inner(&field.schema).is_some();
+            let mut mapped = map_type(&field.schema, ctx);
+            if !field.required && !is_nullable {
+                mapped = nullable(mapped);
+            }
+
+            let schema = nullable_inner(&field.schema).unwrap_or_else(|| field.schema.clone());
+            let from_array = if field.required && !is_nullable {
+                let missing = format!(
+                    "throw new \\InvalidArgumentException({})",
+                    string_literal(&format!("missing field {}", field.name))
+                );
+                let value = format!("{} ?? {}", key, missing);
+                from_array(&schema, ctx, &value).unwrap_or(value)
+            } else {
+                let value = format!("{} ?? null", key);
+                match from_array(&schema, ctx, &key) {
+                    Some(expr) => format!("isset({}) ? {} : null", key, expr),
+                    None => value,
+                }
+            };
+            // Optional properties are only encoded when set, so their
+            // encoding needn't handle `null`.
+            let encoded = if field.required {
+                &field.schema
+            } else {
+                &schema
+            };
+            let property = format!("$this->{}", ident);
+            PhpProperty {
+                name: field.name.clone(),
+                serialize: serialize(encoded, ctx, &property).unwrap_or(property),
+                native: mapped.native,
+                doc: mapped.doc,
+                required: field.required,
+                from_array,
+                ident,
+            }
+        })
+        .collect();
+    properties.sort_by_key(|p| !p.required);
+    properties
+}
@@ -0,0 +1,360 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::Value;
+
+use super::{
+    extension_override, is_scalar, map_primitive, nullable_inner, sanitize_identifier, snake_case,
+    Construct, LanguageContext,
+};
+use crate::resolver::{
+    any_of, array_item_schema, array_tuple_items, object_additional_properties,
+    object_pattern_properties, one_of, ref_to_name, ResolvedField, ResolvedType,
+};
+
+/// Package of the generated messages.
+pub const PACKAGE: &str = "trp";
+
+/// Name of the file persisting the field numbers next to the `.proto`.
+pub const FIELD_NUMBERS: &str = "field_numbers.json";
+
+/// Field numbers by message and field name. Numbers are never reused: the
+/// fields a spec drops stay in the mapping and are reserved in the message.
+pub type FieldNumbers = BTreeMap<String, BTreeMap<String, u32>>;
+
+/// Numbers protobuf keeps for its own implementation.
+const IMPLEMENTATION_RESERVED: std::ops::RangeInclusive<u32> = 19000..=19999;
+
+const VALUE: &str = "google.protobuf.Value";
+const LIST_VALUE: &str = "google.protobuf.ListValue";
+const STRUCT: &str = "google.protobuf.Struct";
+
+pub struct ProtoMessage {
+    pub name: String,
+    pub deprecated: bool,
+    pub fields: Vec<ProtoField>,
+    /// `oneof` groups, each holding a field per option of a union.
+    pub oneofs: Vec<ProtoOneof>,
+    /// Numbers and names of fields the spec no longer declares.
+    pub reserved_numbers: Vec<u32>,
+    pub reserved_names: Vec<String>,
+}
+
+impl ProtoMessage {
+    /// `reserved` statement arguments, e.g. `4, 7`.
+    pub fn reserved_number_list(&self) -> String {
+        let numbers: Vec<String> = self.reserved_numbers.iter().map(u32::to_string).collect();
+        numbers.join(", ")
+    }
+
+    pub fn reserved_name_list(&self) -> String {
+        let names: Vec<String> = self
+            .reserved_names
+            .iter()
+            .map(|n| string_literal(n))
+            .collect();
+        names.join(", ")
+    }
+}
+
+pub struct ProtoField {
+    /// Type with its label, e.g. `repeated string` or `optional int64`.
+    pub ty: String,
+    pub name: String,
+    pub number: u32,
+    /// Field options, e.g. `json_name = "minAmount"`.
+    pub options: Vec<String>,
+}
+
+impl ProtoField {
+    pub fn option_list(&self) -> String {
+        self.options.join(", ")
+    }
+}
+
+pub struct ProtoOneof {
+    pub name: String,
+    pub fields: Vec<ProtoField>,
+}
+
+pub fn type_name(raw: &str) -> String {
+    sanitize_identifier(raw)
+}
+
+pub fn field_name(raw: &str) -> String {
+    let mut out = snake_case(raw);
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert_str(0, "field_");
+    }
+    out
+}
+
+/// Name of a field, honouring an `x-proto-name` override.
+pub fn field_ident(field: &ResolvedField) -> String {
+    extension_override(&field.schema, "proto", "name").unwrap_or_else(|| field_name(&field.name))
+}
+
+/// The JSON name protoc derives from a field name, e.g. `minAmount` for
+/// `min_amount`.
+fn default_json_name(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn string_literal(value: &str) -> String {
+    Value::String(value.to_string()).to_string()
+}
+
+/// Protobuf type of a schema that fits a single, unlabelled field.
+fn single(schema: &SchemaObject, ctx: &LanguageContext) -> String {
+    if let Some(ty) = ctx.type_override(schema) {
+        return ty;
+    }
+    if let Some(name) = schema
+        .reference
+        .as_deref()
+        .and_then(|r| ref_to_name(r).ok())
+    {
+        return ctx.type_name(&name);
+    }
+    if let Some(literal) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+        return match literal {
+            Value::String(_) => "string",
+            Value::Bool(_) => "bool",
+            Value::Number(n) if n.is_f64() => "double",
+            _ => "int64",
+        }
+        .to_string();
+    }
+    if schema.subschemas.is_some() {
+        ctx.degrade_subschemas(schema);
+        return VALUE.to_string();
+    }
+    if let Some(values) = &schema.enum_values {
+        if values.iter().all(|v| v.is_string()) {
+            return "string".to_string();
+        }
+        ctx.degrade(Construct::Enum);
+        return VALUE.to_string();
+    }
+    match map_primitive(schema) {
+        Some(InstanceType::String) => "string",
+        Some(InstanceType::Integer) => "int64",
+        Some(InstanceType::Number) => "double",
+        Some(InstanceType::Boolean) => "bool",
+        Some(InstanceType::Array) => LIST_VALUE,
+        Some(InstanceType::Object) => STRUCT,
+        _ => VALUE,
+    }
+    .to_string()
+}
+
+/// Whether `ty` may be repeated or be a map value; `Value` and `ListValue`
+/// stand for nested collections and can't.
+fn is_element(ty: &str) -> bool {
+    ty != VALUE && ty != LIST_VALUE
+}
+
+/// How a field is declared: a labelled type, or a union given as `oneof`
+/// options of `(suffix, type)`.
+pub(crate) enum Shape {
+    Field(String),
+    Oneof(Vec<(String, String)>),
+}
+
+pub(crate) fn shape(field: &ResolvedField, ctx: &LanguageContext) -> Shape {
+    if let Some(ty) = ctx.type_override(&field.schema) {
+        return Shape::Field(ty);
+    }
+    let inner = nullable_inner(&field.schema);
+    let optional = !field.required || inner.is_some();
+    let schema = inner.unwrap_or_else(|| field.schema.clone());
+    let labelled = |ty: String| match optional {
+        true => Shape::Field(format!("optional {}", ty)),
+        false => Shape::Field(ty),
+    };
+
+    if let Some(options) = schema
+        .subschemas
+        .as_ref()
+        .and_then(|s| one_of(s).or_else(|| any_of(s)))
+    {
+        let mut variants: Vec<(String, String)> = Vec::new();
+        // A `null` option is the oneof left unset.
+        let options = options
+            .iter()
+            .filter(|option| map_primitive(option) != Some(InstanceType::Null));
+        for (position, option) in options.enumerate() {
+            let ty = single(option, ctx);
+            let mut suffix = snake_case(&sanitize_identifier(ty.rsplit('.').next().unwrap_or(&ty)));
+            if variants.iter().any(|(s, _)| *s == suffix) {
+                suffix = format!("option_{}", position + 1);
+            }
+            variants.push((suffix, ty));
+        }
+        return Shape::Oneof(variants);
+    }
+
+    let array = schema.array.as_deref();
+    match map_primitive(&schema) {
+        Some(InstanceType::Array) => {
+            let element = match array.and_then(array_tuple_items) {
+                // Only tuples of one type fit a repeated field.
+                Some(items) => {
+                    let types: BTreeSet<String> = items.iter().map(|s| single(s, ctx)).collect();
+                    match types.len() {
+                        1 => types.into_iter().next(),
+                        _ => None,
+                    }
+                }
+                None => array
+                    .and_then(array_item_schema)
+                    .map(|item| single(&item, ctx)),
+            };
+            match element.filter(|ty| is_element(ty)) {
+                Some(ty) => Shape::Field(format!("repeated {}", ty)),
+                None => {
+                    if array.and_then(array_tuple_items).is_some() {
+                        ctx.degrade(Construct::Tuple);
+                    } else if array.and_then(array_item_schema).is_some() {
+                        ctx.degrade(Construct::Array);
+                    }
+                    labelled(LIST_VALUE.to_string())
+                }
+            }
+        }
+        Some(InstanceType::Object) => {
+            let value = schema.object.as_deref().and_then(|object| {
+                object_additional_properties(object)
+                    .or_else(|| object_pattern_properties(object).map(|(_, value)| value))
+            });
+            let mapped = value.as_ref().map(|value| single(value, ctx));
+            match mapped.filter(|ty| is_element(ty)) {
+                Some(ty) => Shape::Field(format!("map<string, {}>", ty)),
+                None => {
+                    if value.is_some() {
+                        ctx.degrade(Construct::Map);
+                    }
+                    labelled(STRUCT.to_string())
+                }
+            }
+        }
+        _ => labelled(single(&schema, ctx)),
+    }
+}
+
+/// Hands out field numbers for one message, keeping those of `known`.
+struct Numbering<'a> {
+    known: &'a mut BTreeMap<String, u32>,
+    used: BTreeSet<String>,
+}
+
+impl Numbering<'_> {
+    fn number(&mut self, name: &str) -> u32 {
+        self.used.insert(name.to_string());
+        if let Some(number) = self.known.get(name) {
+            return *number;
+        }
+        let mut next = self.known.values().max().map_or(1, |n| n + 1);
+        if IMPLEMENTATION_RESERVED.contains(&next) {
+            next = IMPLEMENTATION_RESERVED.end() + 1;
+        }
+        self.known.insert(name.to_string(), next);
+        next
+    }
+}
+
+fn json_options(name: &str, key: &str) -> Vec<String> {
+    match default_json_name(name) == key {
+        true => Vec::new(),
+        false => vec![format!("json_name = {}", string_literal(key))],
+    }
+}
+
+/// Messages of the given components, numbering fields after `numbers`,
+/// which gains the numbers of new fields.
+pub fn messages(
+    types: &[ResolvedType],
+    ctx: &LanguageContext,
+    numbers: &mut FieldNumbers,
+) -> Vec<ProtoMessage> {
+    types
+        .iter()
+        .map(|ty| {
+            let mut numbering = Numbering {
+                known: numbers.entry(ty.name.clone()).or_default(),
+                used: BTreeSet::new(),
+            };
+            let mut fields = Vec::new();
+            let mut oneofs = Vec::new();
+            for field in &ty.fields {
+                let name = field_ident(field);
+                let mut options = json_options(&name, &field.name);
+                if field.deprecated() {
+                    options.push("deprecated = true".to_string());
+                }
+                match shape(field, ctx) {
+                    Shape::Field(ty) => fields.push(ProtoField {
+                        number: numbering.number(&name),
+                        ty,
+                        name,
+                        options,
+                    }),
+                    Shape::Oneof(variants) => {
+                        let fields = variants
+                            .into_iter()
+                            .map(|(suffix, ty)| {
+                                let option = format!("{}_{}", name, suffix);
+                                ProtoField {
+                                    number: numbering.number(&option),
+                                    ty,
+                                    name: option,
+                                    options: options.clone(),
+                                }
+                            })
+                            .collect();
+                        oneofs.push(ProtoOneof { name, fields });
+                    }
+                }
+            }
+            let Numbering { known, used } = numbering;
+            let dropped: Vec<(&String, &u32)> = known
+                .iter()
+                .filter(|(name, _)| !used.contains(*name))
+                .collect();
+            let mut reserved_numbers: Vec<u32> = dropped.iter().map(|(_, n)| **n).collect();
+            reserved_numbers.sort_unstable();
+            ProtoMessage {
+                name: ctx.type_name(&ty.name),
+                deprecated: ty.deprecated(),
+                fields,
+                oneofs,
+                reserved_numbers,
+                reserved_names: dropped.into_iter().map(|(name, _)| name.clone()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Whether any message uses the well-known `struct.proto` types.
+pub fn uses_struct(messages: &[ProtoMessage]) -> bool {
+    messages
+        .iter()
+        .flat_map(|m| {
+            m.fields
+                .iter()
+                .chain(m.oneofs.iter().flat_map(|o| &o.fields))
+        })
+        .any(|f| f.ty.contains("google.protobuf."))
+}
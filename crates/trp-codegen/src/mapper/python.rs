@@ -0,0 +1,393 @@
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::Value;
+
+use super::{
+    extension_override, is_empty_object, is_scalar, map_primitive, map_reference,
+    sanitize_identifier, snake_case, strip_null, LanguageContext,
+};
+use crate::resolver::{
+    any_of, array_item_schema, array_tuple_items, extra_properties, object_additional_properties,
+    object_pattern_properties, one_of, Constraint, ResolvedField, ResolvedType,
+};
+
+/// Distribution name of the bindings on PyPI; they import as `trp`.
+pub const DISTRIBUTION: &str = "tx3-trp";
+
+/// A semantic version as PEP 440 spells it: the `alpha`, `beta` and `rc`
+/// pre-releases of `1.2.0-rc.1` become `1.2.0rc1`, and other pre-release or
+/// build labels become a local version.
+pub fn pep440(version: &str) -> String {
+    let (version, build) = match version.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (version, None),
+    };
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    let mut out = core.to_string();
+    let mut local = Vec::new();
+    if let Some(pre) = pre {
+        let (label, number) = pre.split_once('.').unwrap_or((pre, "0"));
+        let label = match label {
+            "alpha" | "a" => Some("a"),
+            "beta" | "b" => Some("b"),
+            "rc" => Some("rc"),
+            _ => None,
+        };
+        match label.filter(|_| number.chars().all(|c| c.is_ascii_digit())) {
+            Some(label) => out.push_str(&format!("{}{}", label, number)),
+            None => local.push(pre),
+        }
+    }
+    local.extend(build);
+    if !local.is_empty() {
+        let local: Vec<String> = local
+            .iter()
+            .map(|l| l.replace(|c: char| !c.is_ascii_alphanumeric(), "."))
+            .collect();
+        out.push('+');
+        out.push_str(&local.join("."));
+    }
+    out
+}
+
+pub fn type_name(raw: &str) -> String {
+    sanitize_identifier(raw)
+}
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Attribute name of a field: the wire name when it is a valid identifier,
+/// otherwise its snake_case form, kept clear of keywords, digits and the
+/// leading underscore pydantic reserves for private attributes.
+pub fn field_name(raw: &str) -> String {
+    let valid = raw.starts_with(|c: char| c.is_ascii_alphabetic())
+        && raw.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let mut name = match valid {
+        true => raw.to_string(),
+        false => snake_case(raw),
+    };
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "field_");
+    }
+    if KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+    name
+}
+
+/// Identifier of a field, honouring an `x-python-name` override.
+pub fn field_ident(field: &ResolvedField, ctx: &LanguageContext) -> String {
+    extension_override(&field.schema, "python", "name")
+        .unwrap_or_else(|| ctx.field_name(&field.name, field_name))
+}
+
+/// Value type of the members of `ty` beyond its declared fields, if it
+/// types them. Pydantic keeps them in `__pydantic_extra__`.
+pub fn extra_type(ty: &ResolvedType, ctx: &LanguageContext) -> Option<String> {
+    extra_properties(&ty.schema).map(|extra| map_type(&extra, ctx))
+}
+
+pub fn map_type(schema: &SchemaObject, ctx: &LanguageContext) -> String {
+    if let Some(ty) = ctx.type_override(schema) {
+        return ty;
+    }
+
+    if let Some(reference) = map_reference(schema, ctx) {
+        return reference;
+    }
+
+    if let Some(inner) = strip_null(schema) {
+        return ctx.wrap_nullable(&map_type(&inner, ctx));
+    }
+
+    if let Some(literal) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+        return format!("Literal[{}]", literal_value(literal));
+    }
+
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(options) = one_of(subschemas) {
+            let joined: Vec<String> = options.iter().map(|s| map_type(s, ctx)).collect();
+            return format!("Union[{}]", joined.join(", "));
+        }
+        if let Some(options) = any_of(subschemas) {
+            let joined: Vec<String> = options.iter().map(|s| map_type(s, ctx)).collect();
+            return format!("Union[{}]", joined.join(", "));
+        }
+    }
+
+    if let Some(enum_values) = &schema.enum_values {
+        let variants: Vec<String> = enum_values
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| format!("\"{}\"", s)))
+            .collect();
+        if !variants.is_empty() {
+            return format!("Literal[{}]", variants.join(", "));
+        }
+    }
+
+    if let Some(InstanceType::Array) = map_primitive(schema) {
+        if let Some(array) = &schema.array {
+            if let Some(items) = array_tuple_items(array) {
+                let items: Vec<String> = items.iter().map(|s| map_type(s, ctx)).collect();
+                return format!("Tuple[{}]", items.join(", "));
+            }
+            if let Some(item) = array_item_schema(array) {
+                return format!("List[{}]", map_type(&item, ctx));
+            }
+        }
+        return "List[Any]".to_string();
+    }
+
+    if is_empty_object(schema) {
+        return "Annotated[Dict[str, Any], Field(max_length=0)]".to_string();
+    }
+
+    if let Some(InstanceType::Object) = map_primitive(schema) {
+        if let Some(object) = &schema.object {
+            if let Some(additional) = object_additional_properties(object) {
+                return format!("Dict[str, {}]", map_type(&additional, ctx));
+            }
+            if let Some((pattern, value)) = object_pattern_properties(object) {
+                return format!(
+                    "Dict[Annotated[str, StringConstraints(pattern={})], {}]",
+                    literal_value(&Value::String(pattern)),
+                    map_type(&value, ctx)
+                );
+            }
+        }
+        return "Dict[str, Any]".to_string();
+    }
+
+    match map_primitive(schema) {
+        Some(InstanceType::String) => "str".to_string(),
+        Some(InstanceType::Integer) => "int".to_string(),
+        Some(InstanceType::Number) => "float".to_string(),
+        Some(InstanceType::Boolean) => "bool".to_string(),
+        Some(InstanceType::Null) => "None".to_string(),
+        _ => {
+            ctx.degrade_subschemas(schema);
+            "Any".to_string()
+        }
+    }
+}
+
+/// A string enum field, declared as a `(str, Enum)` class named after its
+/// type and field, e.g. `TxStatusStage`.
+#[derive(Debug, Clone)]
+pub struct PythonEnum {
+    pub name: String,
+    /// The field, as `Type.field`.
+    pub field: String,
+    /// Member identifiers with their wire values as string literals.
+    pub members: Vec<(String, String)>,
+}
+
+impl PythonEnum {
+    /// The member with wire value `value`, as an expression.
+    pub fn member(&self, value: &str) -> Option<String> {
+        let literal = Value::String(value.to_string()).to_string();
+        self.members
+            .iter()
+            .find(|(_, v)| *v == literal)
+            .map(|(ident, _)| format!("{}.{}", self.name, ident))
+    }
+}
+
+/// Identifier of an enum member: the value in SCREAMING_SNAKE_CASE.
+fn member_name(raw: &str) -> String {
+    let name = snake_case(raw).to_uppercase();
+    match name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        true => name,
+        false => format!("VALUE_{}", name).trim_end_matches('_').to_string(),
+    }
+}
+
+/// The enum class of `field` of `ty`, if its values are strings and no
+/// `x-python-type` takes its place. Members are named after the values
+/// unless the schema lists them in `x-enum-varnames`.
+pub fn enum_class(
+    ty: &ResolvedType,
+    field: &ResolvedField,
+    ctx: &LanguageContext,
+) -> Option<PythonEnum> {
+    if ctx.type_override(&field.schema).is_some() {
+        return None;
+    }
+    let schema = strip_null(&field.schema).unwrap_or_else(|| field.schema.clone());
+    let values = schema
+        .enum_values
+        .as_ref()
+        .filter(|values| !values.is_empty() && values.iter().all(|v| v.is_string()))?;
+    let varnames: Vec<&str> = schema
+        .extensions
+        .get("x-enum-varnames")
+        .and_then(|v| v.as_array())
+        .map(|names| names.iter().filter_map(|n| n.as_str()).collect())
+        .unwrap_or_default();
+
+    let mut name = format!(
+        "{}{}",
+        ctx.type_name(&ty.name),
+        sanitize_identifier(&field.name)
+    );
+    if ctx.has_type_name(&name) {
+        name.push_str("Value");
+    }
+    let members = values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, value)| {
+            let text = value.as_str()?;
+            let ident = match varnames.get(i) {
+                Some(varname) => member_name(varname),
+                None => ctx.variant_name(text, member_name),
+            };
+            Some((ident, value.to_string()))
+        })
+        .collect();
+    let field = format!("{}.{}", ctx.type_name(&ty.name), field_ident(field, ctx));
+    Some(PythonEnum {
+        name,
+        field,
+        members,
+    })
+}
+
+/// Enum classes of the fields of `ty`, in field order.
+pub fn enums(ty: &ResolvedType, ctx: &LanguageContext) -> Vec<PythonEnum> {
+    ty.fields
+        .iter()
+        .filter_map(|field| enum_class(ty, field, ctx))
+        .collect()
+}
+
+/// Annotation of `field` of `ty`: its enum class when it has one, otherwise
+/// the mapped schema with its constraints as `Annotated` metadata.
+/// Constructor parameters of enum fields also take the wire values, which
+/// pydantic coerces.
+pub fn field_type(
+    ty: &ResolvedType,
+    field: &ResolvedField,
+    parameter: bool,
+    ctx: &LanguageContext,
+) -> String {
+    if ctx.type_override(&field.schema).is_some() {
+        return map_type(&field.schema, ctx);
+    }
+    let inner = strip_null(&field.schema);
+    let schema = inner.as_ref().unwrap_or(&field.schema);
+    let name = match enum_class(ty, field, ctx) {
+        Some(class) if parameter => {
+            let values: Vec<&str> = class.members.iter().map(|(_, v)| v.as_str()).collect();
+            format!("Union[{}, Literal[{}]]", class.name, values.join(", "))
+        }
+        Some(class) => class.name,
+        None => annotated(map_type(schema, ctx), schema, &field.constraints),
+    };
+    match inner {
+        Some(_) => ctx.wrap_nullable(&name),
+        None => name,
+    }
+}
+
+/// `ty` annotated with the `annotated_types` metadata of `constraints`,
+/// which pydantic enforces and IDEs show, or `ty` itself if none applies.
+fn annotated(ty: String, schema: &SchemaObject, constraints: &[Constraint]) -> String {
+    let integer = map_primitive(schema) == Some(InstanceType::Integer);
+    let mut metadata = Vec::new();
+    for constraint in constraints {
+        match constraint {
+            Constraint::MinLength(n) | Constraint::MinItems(n) => {
+                metadata.push(format!("MinLen({})", n))
+            }
+            Constraint::MaxLength(n) => metadata.push(format!("MaxLen({})", n)),
+            Constraint::Pattern(pattern) => metadata.push(format!(
+                "StringConstraints(pattern={})",
+                literal_value(&Value::String(pattern.clone()))
+            )),
+            // pydantic rejects fractional bounds on `int` fields.
+            Constraint::Minimum(n) if integer => metadata.push(format!("Ge({})", n.ceil())),
+            Constraint::Maximum(n) if integer => metadata.push(format!("Le({})", n.floor())),
+            Constraint::Minimum(n) => metadata.push(format!("Ge({})", n)),
+            Constraint::Maximum(n) => metadata.push(format!("Le({})", n)),
+            // Checked by the generated validators and the key type respectively.
+            Constraint::UniqueItems | Constraint::Address | Constraint::KeyPattern(_) => {}
+        }
+    }
+    match metadata.is_empty() {
+        true => ty,
+        false => format!("Annotated[{}, {}]", ty, metadata.join(", ")),
+    }
+}
+
+/// Renders a JSON value as the equivalent Python literal.
+pub fn literal_value(value: &Value) -> String {
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(_) => value.to_string(),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(literal_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(map) => {
+            let entries: Vec<String> = map
+                .iter()
+                .map(|(k, v)| format!("{}: {}", Value::String(k.clone()), literal_value(v)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+/// Right-hand side of a model field declaration, if it needs one. Plain
+/// defaults are assigned directly; mutable defaults go through pydantic's
+/// `Field(...)`, as do renamed fields, which keep the wire name as their
+/// alias. Defaults of enum fields name the member of `class`. Constraints
+/// are part of the annotation instead.
+pub fn field_spec(
+    field: &ResolvedField,
+    default: Option<&Value>,
+    class: Option<&PythonEnum>,
+    ctx: &LanguageContext,
+) -> Option<String> {
+    let mut kwargs = Vec::new();
+    let ident = field_ident(field, ctx);
+    if ident != field.name {
+        kwargs.push(format!(
+            "alias={}",
+            literal_value(&Value::String(field.name.clone()))
+        ));
+    }
+    match default {
+        Some(value @ (Value::Array(_) | Value::Object(_))) => {
+            kwargs.push(format!("default_factory=lambda: {}", literal_value(value)))
+        }
+        Some(value) => {
+            let member = class.zip(value.as_str()).and_then(|(c, v)| c.member(v));
+            kwargs.push(format!(
+                "default={}",
+                member.unwrap_or_else(|| literal_value(value))
+            ))
+        }
+        None if !field.required => kwargs.push("default=None".to_string()),
+        None => {}
+    }
+
+    match kwargs.as_slice() {
+        [] => None,
+        [only] if only.starts_with("default=") => {
+            Some(only.trim_start_matches("default=").to_string())
+        }
+        _ => Some(format!("Field({})", kwargs.join(", "))),
+    }
+}
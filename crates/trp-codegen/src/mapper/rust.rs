@@ -0,0 +1,1669 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::Value;
+
+use super::{
+    ascii_fold, extension_override, is_empty_object, is_nullable, map_primitive, map_reference,
+    sanitize_identifier, snake_case, strip_null, union_options, union_tags, LanguageContext,
+};
+use crate::metadata::{Metadata, MethodMetadata};
+use crate::resolver::{
+    any_of, array_item_schema, array_tuple_items, extra_properties, object_additional_properties,
+    object_pattern_properties, one_of, visit_schemas, Constraint, ResolvedField, ResolvedType,
+};
+
+/// Struct declared next to `ValidationError` for objects that can hold no
+/// member, as serde encodes unit structs as `null` rather than `{}`.
+pub const EMPTY_OBJECT: &str = "EmptyObject";
+
+pub fn type_name(raw: &str) -> String {
+    sanitize_identifier(raw)
+}
+
+pub fn field_name(raw: &str) -> String {
+    let mut out = String::new();
+    for (i, ch) in ascii_fold(raw).chars().enumerate() {
+        if ch.is_ascii_alphanumeric() {
+            if i == 0 {
+                out.push(ch.to_ascii_lowercase());
+            } else {
+                out.push(ch);
+            }
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    if out.is_empty() || out == "_" {
+        "field".to_string()
+    } else if out.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("field_{}", out)
+    } else {
+        out
+    }
+}
+
+/// Identifier of a field, honouring an `x-rust-name` override.
+pub fn field_ident(field: &ResolvedField, ctx: &LanguageContext) -> String {
+    extension_override(&field.schema, "rust", "name")
+        .unwrap_or_else(|| escape_keyword(ctx.field_name(&field.name, field_name)))
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "try", "type", "unsafe", "use", "where",
+    "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+/// Turns keywords into raw identifiers; the few keywords that can't be raw
+/// get a trailing underscore instead.
+fn escape_keyword(ident: String) -> String {
+    match ident.as_str() {
+        "self" | "Self" | "super" | "crate" => format!("{}_", ident),
+        _ if KEYWORDS.contains(&ident.as_str()) => format!("r#{}", ident),
+        _ => ident,
+    }
+}
+
+pub fn map_type(schema: &SchemaObject, ctx: &LanguageContext) -> String {
+    if let Some(ty) = ctx.type_override(schema) {
+        return ty;
+    }
+
+    if let Some(reference) = map_reference(schema, ctx) {
+        return reference;
+    }
+
+    if let Some(inner) = strip_null(schema) {
+        return ctx.wrap_nullable(&map_type(&inner, ctx));
+    }
+
+    if let Some(options) = union_options(schema) {
+        let variants: Vec<String> = options.iter().map(|s| map_type(s, ctx)).collect();
+        return union_name(&variants);
+    }
+
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(options) = one_of(subschemas) {
+            let joined: Vec<String> = options.iter().map(|s| map_type(s, ctx)).collect();
+            return format!("std::vec::Vec<{}>", joined.join(" | "));
+        }
+        if let Some(options) = any_of(subschemas) {
+            let joined: Vec<String> = options.iter().map(|s| map_type(s, ctx)).collect();
+            return format!("std::vec::Vec<{}>", joined.join(" | "));
+        }
+    }
+
+    if let Some(InstanceType::Array) = map_primitive(schema) {
+        if let Some(array) = &schema.array {
+            if let Some(items) = array_tuple_items(array) {
+                let items: Vec<String> = items.iter().map(|s| map_type(s, ctx)).collect();
+                if items.len() == 1 {
+                    return format!("({},)", items[0]);
+                }
+                return format!("({})", items.join(", "));
+            }
+            if let Some(item) = array_item_schema(array) {
+                return format!("Vec<{}>", map_type(&item, ctx));
+            }
+        }
+        return "Vec<serde_json::Value>".to_string();
+    }
+
+    if is_empty_object(schema) {
+        return EMPTY_OBJECT.to_string();
+    }
+
+    if let Some(InstanceType::Object) = map_primitive(schema) {
+        if let Some(object) = &schema.object {
+            if let Some(additional) = object_additional_properties(object) {
+                return format!(
+                    "std::collections::HashMap<String, {}>",
+                    map_type(&additional, ctx)
+                );
+            }
+            if let Some((_, value)) = object_pattern_properties(object) {
+                return format!(
+                    "std::collections::HashMap<String, {}>",
+                    map_type(&value, ctx)
+                );
+            }
+        }
+        return "std::collections::HashMap<String, serde_json::Value>".to_string();
+    }
+
+    match map_primitive(schema) {
+        Some(InstanceType::String) => "String".to_string(),
+        Some(InstanceType::Integer) => "i64".to_string(),
+        Some(InstanceType::Number) => "f64".to_string(),
+        Some(InstanceType::Boolean) => "bool".to_string(),
+        Some(InstanceType::Null) => "Option<serde_json::Value>".to_string(),
+        _ => {
+            ctx.degrade_subschemas(schema);
+            "serde_json::Value".to_string()
+        }
+    }
+}
+
+/// Whether any field of `types` is typed with [`EMPTY_OBJECT`].
+pub fn uses_empty_object(types: &[ResolvedType], ctx: &LanguageContext) -> bool {
+    let mut used = false;
+    for ty in types {
+        for field in &ty.fields {
+            visit_schemas(&field.schema, &mut |schema| {
+                used |= ctx.type_override(schema).is_none() && is_empty_object(schema);
+            });
+        }
+    }
+    used
+}
+
+/// Enum declared for a `oneOf`/`anyOf` union of several types, with a
+/// variant per type. Serde decodes it as the first variant the value
+/// matches.
+pub struct RustUnion {
+    pub name: String,
+    pub variants: Vec<RustVariant>,
+    /// Whether a variant holds components, so that the union differs
+    /// between versions of the spec even where its name doesn't.
+    pub references: bool,
+}
+
+impl RustUnion {
+    /// Variant types for its doc comment, e.g. `String`, `Predicate`.
+    pub fn variant_list(&self) -> String {
+        let types: Vec<String> = self
+            .variants
+            .iter()
+            .map(|v| format!("`{}`", v.ty))
+            .collect();
+        types.join(", ")
+    }
+
+    /// Whether every variant has a `validate()` method.
+    pub fn all_validate(&self) -> bool {
+        self.variants.iter().all(|v| v.validates)
+    }
+}
+
+pub struct RustVariant {
+    pub name: String,
+    pub ty: String,
+    /// Value telling the variant apart, as listed by `variants()`, as a
+    /// string literal.
+    pub discriminator: String,
+    /// Whether the variant is a component with a `validate()` method.
+    pub validates: bool,
+    /// proptest strategy generating values of the variant.
+    pub strategy: String,
+}
+
+/// Identifier part standing for a Rust type in the name of a union.
+fn type_part(ty: &str) -> String {
+    let ty = ty
+        .replace("std::collections::", "")
+        .replace("std::vec::", "")
+        .replace("serde_json::", "Json");
+    sanitize_identifier(&ty)
+}
+
+// Unions are named after their variant types, e.g. `StringOrPredicate`.
+fn union_name(variants: &[String]) -> String {
+    let parts: Vec<String> = variants.iter().map(|v| type_part(v)).collect();
+    parts.join("Or")
+}
+
+/// The distinct unions referenced anywhere in `types`.
+pub fn unions(types: &[ResolvedType], ctx: &LanguageContext) -> Vec<RustUnion> {
+    let mut unions: Vec<RustUnion> = Vec::new();
+    let mut visit = |schema: &SchemaObject| {
+        if ctx.type_override(schema).is_some() {
+            return;
+        }
+        let Some(options) = union_options(schema) else {
+            return;
+        };
+        let name = map_type(schema, ctx);
+        if unions.iter().any(|u| u.name == name) {
+            return;
+        }
+        let mut names: Vec<String> = Vec::new();
+        for (position, option) in options.iter().enumerate() {
+            let mut variant = type_part(&map_type(option, ctx));
+            if names.contains(&variant) {
+                variant = format!("{}{}", variant, position + 1);
+            }
+            names.push(variant);
+        }
+        // Variants are told apart by their discriminator, if any, or else
+        // named after their type.
+        let discriminators = union_tags(schema, &options, types)
+            .map(|(_, tags)| tags)
+            .unwrap_or_else(|| names.clone());
+        let mut references = false;
+        visit_schemas(schema, &mut |s| references |= s.reference.is_some());
+        let variants = options
+            .iter()
+            .zip(names)
+            .zip(discriminators)
+            .map(|((option, name), discriminator)| RustVariant {
+                strategy: format!(
+                    "{}.prop_map(Self::{})",
+                    value_strategy(option, &[], ctx),
+                    name
+                ),
+                name,
+                ty: map_type(option, ctx),
+                discriminator: format!("{:?}", discriminator),
+                validates: option.reference.is_some() && map_reference(option, ctx).is_some(),
+            })
+            .collect();
+        unions.push(RustUnion {
+            name,
+            variants,
+            references,
+        });
+    };
+    for ty in types {
+        visit_schemas(&ty.schema, &mut visit);
+        for field in &ty.fields {
+            visit_schemas(&field.schema, &mut visit);
+        }
+    }
+    unions.sort_by(|a, b| a.name.cmp(&b.name));
+    unions
+}
+
+/// A field marked with `format: address`, which gets a `parse_` method
+/// returning it as an `address::Address`.
+pub struct AddressField {
+    pub ident: String,
+    /// `parse_address` for `address`.
+    pub method: String,
+    /// Whether the field is an `Option`.
+    pub optional: bool,
+}
+
+pub fn address_fields(ty: &ResolvedType, ctx: &LanguageContext) -> Vec<AddressField> {
+    ty.fields
+        .iter()
+        .filter(|field| ctx.type_override(&field.schema).is_none())
+        .filter(|field| field.constraints.contains(&Constraint::Address))
+        .map(|field| {
+            let ident = field_ident(field, ctx);
+            AddressField {
+                method: format!("parse_{}", ident.trim_start_matches("r#")),
+                ident,
+                optional: !field.required || is_nullable(&field.schema),
+            }
+        })
+        .collect()
+}
+
+/// Name and type of the `#[serde(flatten)]` map catching the members of
+/// `ty` beyond its declared fields, if it types them.
+pub fn extra_field(ty: &ResolvedType, ctx: &LanguageContext) -> Option<(String, String)> {
+    let extra = extra_properties(&ty.schema)?;
+    let mut name = "extra".to_string();
+    while ty.fields.iter().any(|f| field_ident(f, ctx) == name) {
+        name.push('_');
+    }
+    Some((
+        name,
+        format!(
+            "std::collections::HashMap<String, {}>",
+            map_type(&extra, ctx)
+        ),
+    ))
+}
+
+/// A struct of the `borrowed` module, mirroring a component with its
+/// strings borrowed from the input as `Cow<'a, str>`. Components holding no
+/// string, even indirectly, have no borrowed form and are used owned.
+pub struct BorrowedStruct {
+    pub name: String,
+    pub fields: Vec<BorrowedField>,
+}
+
+pub struct BorrowedField {
+    pub ident: String,
+    /// JSON key, or `None` for the flattened catch-all map.
+    pub wire: Option<String>,
+    pub ty: String,
+    pub optional: bool,
+    /// Whether the type has the `'a` lifetime, which serde only propagates
+    /// through `#[serde(borrow)]`.
+    pub borrow: bool,
+    /// Expressions converting `self.{ident}` to the owned field and back.
+    pub to_owned: String,
+    pub to_borrowed: String,
+}
+
+/// Borrowed counterparts of the components of `types` that hold strings.
+pub fn borrowed(types: &[ResolvedType], ctx: &LanguageContext) -> Vec<BorrowedStruct> {
+    let borrowing = borrowing_types(types, ctx);
+    let borrowed = Borrowed {
+        ctx,
+        borrowing: &borrowing,
+    };
+    types
+        .iter()
+        .filter(|ty| borrowing.contains(&ty.name))
+        .map(|ty| {
+            let mut fields: Vec<BorrowedField> = ty
+                .fields
+                .iter()
+                .map(|field| {
+                    let optional = !field.required && !is_nullable(&field.schema);
+                    let mut shape = borrowed.shape(&field.schema);
+                    if optional {
+                        shape = Shape::Option(Box::new(shape));
+                    }
+                    BorrowedField::new(
+                        field_ident(field, ctx),
+                        Some(field.name.clone()),
+                        shape,
+                        optional,
+                    )
+                })
+                .collect();
+            if let (Some(extra), Some((ident, _))) =
+                (extra_properties(&ty.schema), extra_field(ty, ctx))
+            {
+                let shape = Shape::Map(Box::new(borrowed.shape(&extra)));
+                fields.push(BorrowedField::new(ident, None, shape, false));
+            }
+            BorrowedStruct {
+                name: ctx.type_name(&ty.name),
+                fields,
+            }
+        })
+        .collect()
+}
+
+/// Raw names of the components holding a string, directly or through
+/// other components.
+fn borrowing_types(types: &[ResolvedType], ctx: &LanguageContext) -> BTreeSet<String> {
+    let mut borrowing = BTreeSet::new();
+    loop {
+        let borrowed = Borrowed {
+            ctx,
+            borrowing: &borrowing,
+        };
+        let found: Vec<String> = types
+            .iter()
+            .filter(|ty| !borrowing.contains(&ty.name))
+            .filter(|ty| {
+                ty.fields.iter().any(|f| borrowed.borrows(&f.schema))
+                    || extra_properties(&ty.schema).is_some_and(|e| borrowed.borrows(&e))
+            })
+            .map(|ty| ty.name.clone())
+            .collect();
+        if found.is_empty() {
+            return borrowing;
+        }
+        borrowing.extend(found);
+    }
+}
+
+struct Borrowed<'a> {
+    ctx: &'a LanguageContext,
+    borrowing: &'a BTreeSet<String>,
+}
+
+/// How a value is mapped in the `borrowed` module.
+enum Shape {
+    Str,
+    Component(String),
+    Option(Box<Shape>),
+    List(Box<Shape>),
+    Map(Box<Shape>),
+    Tuple(Vec<Shape>),
+    /// Used owned, as for the components without strings.
+    Owned(String),
+    /// `i64`, `f64` or `bool`.
+    Copy(String),
+}
+
+impl BorrowedField {
+    fn new(ident: String, wire: Option<String>, shape: Shape, optional: bool) -> Self {
+        let target = format!("&self.{}", ident);
+        Self {
+            ty: shape.ty(),
+            borrow: shape.borrows(),
+            to_owned: shape.convert(&target, 0, true),
+            to_borrowed: shape.convert(&target, 0, false),
+            ident,
+            wire,
+            optional,
+        }
+    }
+}
+
+impl Borrowed<'_> {
+    fn borrows(&self, schema: &SchemaObject) -> bool {
+        self.shape(schema).borrows()
+    }
+
+    fn shape(&self, schema: &SchemaObject) -> Shape {
+        if let Some(ty) = self.ctx.type_override(schema) {
+            return Shape::Owned(ty);
+        }
+        if let Some(reference) = &schema.reference {
+            let name = crate::resolver::ref_to_name(reference).unwrap_or_default();
+            return match self.borrowing.contains(&name) {
+                true => Shape::Component(self.ctx.type_name(&name)),
+                false => Shape::Owned(format!("super::{}", self.ctx.type_name(&name))),
+            };
+        }
+        if let Some(inner) = strip_null(schema) {
+            return Shape::Option(Box::new(self.shape(&inner)));
+        }
+        let owned = || Shape::Owned(map_type(schema, self.ctx));
+        if schema.subschemas.is_some() || is_empty_object(schema) {
+            return owned();
+        }
+        match map_primitive(schema) {
+            Some(InstanceType::String) => Shape::Str,
+            Some(InstanceType::Integer) => Shape::Copy("i64".to_string()),
+            Some(InstanceType::Number) => Shape::Copy("f64".to_string()),
+            Some(InstanceType::Boolean) => Shape::Copy("bool".to_string()),
+            Some(InstanceType::Array) => {
+                let Some(array) = schema.array.as_deref() else {
+                    return owned();
+                };
+                if let Some(items) = array_tuple_items(array) {
+                    return Shape::Tuple(items.iter().map(|item| self.shape(item)).collect());
+                }
+                match array_item_schema(array) {
+                    Some(item) => Shape::List(Box::new(self.shape(&item))),
+                    None => owned(),
+                }
+            }
+            Some(InstanceType::Object) => {
+                let value = schema.object.as_deref().and_then(|object| {
+                    object_additional_properties(object)
+                        .or_else(|| object_pattern_properties(object).map(|(_, value)| value))
+                });
+                match value {
+                    Some(value) => Shape::Map(Box::new(self.shape(&value))),
+                    None => owned(),
+                }
+            }
+            _ => owned(),
+        }
+    }
+}
+
+impl Shape {
+    fn ty(&self) -> String {
+        match self {
+            Shape::Str => "Cow<'a, str>".to_string(),
+            Shape::Component(name) => format!("{}<'a>", name),
+            Shape::Option(inner) => format!("Option<{}>", inner.ty()),
+            Shape::List(inner) => format!("Vec<{}>", inner.ty()),
+            Shape::Map(value) => format!("std::collections::HashMap<Cow<'a, str>, {}>", value.ty()),
+            Shape::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(Shape::ty).collect();
+                match items.len() {
+                    1 => format!("({},)", items[0]),
+                    _ => format!("({})", items.join(", ")),
+                }
+            }
+            Shape::Owned(ty) | Shape::Copy(ty) => ty.clone(),
+        }
+    }
+
+    fn borrows(&self) -> bool {
+        match self {
+            Shape::Str | Shape::Component(_) | Shape::Map(_) => true,
+            Shape::Option(inner) | Shape::List(inner) => inner.borrows(),
+            Shape::Tuple(items) => items.iter().any(Shape::borrows),
+            Shape::Owned(_) | Shape::Copy(_) => false,
+        }
+    }
+
+    /// Expression converting `value`, a reference, to the owned type or,
+    /// unless `owned`, from it.
+    fn convert(&self, value: &str, depth: usize, owned: bool) -> String {
+        // Method calls auto-reference, so they take the place itself.
+        let place = value.strip_prefix('&').unwrap_or(value);
+        if !self.borrows() {
+            // Both sides have the same type.
+            return match (self.is_copy(), value.starts_with('&')) {
+                (true, true) => place.to_string(),
+                (true, false) => format!("*{}", value),
+                (false, _) => format!("{}.clone()", place),
+            };
+        }
+        let var = format!("v{}", depth);
+        let recurse = |inner: &Shape, value: &str| inner.convert(value, depth + 1, owned);
+        // `|v| f(v)` is just `f`.
+        let closure = |body: String| match body.strip_suffix(&format!("({})", var)) {
+            Some(function) if !function.contains(['(', ' ']) => function.to_string(),
+            _ => format!("|{}| {}", var, body),
+        };
+        match self {
+            Shape::Str if owned => format!("{}.to_string()", place),
+            Shape::Str => format!("Cow::Borrowed({}.as_str())", place),
+            Shape::Component(name) if owned => format!("{}::to_owned({})", name, value),
+            Shape::Component(name) => format!("super::{}::as_borrowed({})", name, value),
+            Shape::Option(inner) => {
+                format!("{}.as_ref().map({})", place, closure(recurse(inner, &var)))
+            }
+            Shape::List(inner) => format!(
+                "{}.iter().map({}).collect()",
+                place,
+                closure(recurse(inner, &var))
+            ),
+            Shape::Map(inner) => {
+                let key = match owned {
+                    true => "k.to_string()".to_string(),
+                    false => "Cow::Borrowed(k.as_str())".to_string(),
+                };
+                format!(
+                    "{}.iter().map(|(k, {})| ({}, {})).collect()",
+                    place,
+                    var,
+                    key,
+                    recurse(inner, &var)
+                )
+            }
+            Shape::Tuple(items) => {
+                let items: Vec<String> = items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| recurse(item, &format!("&{}.{}", place, i)))
+                    .collect();
+                match items.len() {
+                    1 => format!("({},)", items[0]),
+                    _ => format!("({})", items.join(", ")),
+                }
+            }
+            Shape::Owned(_) | Shape::Copy(_) => unreachable!("owned shapes don't borrow"),
+        }
+    }
+
+    fn is_copy(&self) -> bool {
+        match self {
+            Shape::Copy(_) => true,
+            Shape::Option(inner) => inner.is_copy(),
+            Shape::Tuple(items) => items.iter().all(Shape::is_copy),
+            _ => false,
+        }
+    }
+}
+
+/// Rust expression producing `value` as an instance of `ty`, used to build
+/// `Default` impls. Values without a simple literal form round-trip through
+/// `serde_json`.
+pub fn default_expr(value: &Value, ty: &str) -> String {
+    if let Some(inner) = ty.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) {
+        return match value {
+            Value::Null => "None".to_string(),
+            _ => format!("Some({})", default_expr(value, inner)),
+        };
+    }
+
+    match (value, ty) {
+        (Value::String(s), "String") => format!("{:?}.to_string()", s),
+        (Value::Bool(b), "bool") => b.to_string(),
+        (Value::Number(n), "i64") if n.is_i64() => n.to_string(),
+        (Value::Number(n), "f64") => format!("{:?}", n.as_f64().unwrap_or_default()),
+        _ => format!(
+            "serde_json::from_value(serde_json::json!({})).expect(\"valid default\")",
+            value
+        ),
+    }
+}
+
+/// A raw string literal holding `s`, with as many `#` as it takes.
+pub fn raw_string(s: &str) -> String {
+    let mut hashes = "#".to_string();
+    while s.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    format!("r{}\"{}\"{}", hashes, s, hashes)
+}
+
+/// A program of a generated crate's `examples/` directory, sending the first
+/// example of a method to a server.
+pub struct RustExample {
+    /// File stem, which is also the name given to `cargo run --example`.
+    pub name: String,
+    pub method: String,
+    pub example: String,
+    pub params_type: String,
+    /// The params as a raw string literal of indented JSON.
+    pub params: String,
+}
+
+/// Examples for the methods whose params are a component and that come with
+/// an example, named after [`method_ident`].
+pub fn examples(meta: &Metadata, ctx: &LanguageContext) -> Vec<RustExample> {
+    meta.methods
+        .iter()
+        .filter_map(|method| {
+            let example = method.examples.first()?;
+            let params_type = method.params_type.as_ref()?;
+            Some(RustExample {
+                name: method_ident(meta, method),
+                method: method.name.clone(),
+                example: example.name.clone(),
+                params_type: ctx.type_name(params_type),
+                params: raw_string(&example.params_json()),
+            })
+        })
+        .collect()
+}
+
+/// A test of the crate's `tests/roundtrip.rs`, decoding an example of a
+/// method into the declared types.
+pub struct RustExampleTest {
+    /// Test function name, after the example's.
+    pub name: String,
+    pub method: String,
+    pub example: String,
+    /// Declared type and raw string literal of the params and, when given,
+    /// of the result.
+    pub values: Vec<(&'static str, String, String)>,
+}
+
+/// Names of the tests round-tripping a default value of each type, along
+/// with the type.
+pub fn round_trip_tests(types: &[ResolvedType], ctx: &LanguageContext) -> Vec<(String, String)> {
+    types
+        .iter()
+        .map(|ty| {
+            let name = ctx.type_name(&ty.name);
+            (format!("{}_round_trip", snake_case(&name)), name)
+        })
+        .collect()
+}
+
+/// Tests for every example of the methods whose params are a component.
+pub fn example_tests(meta: &Metadata, ctx: &LanguageContext) -> Vec<RustExampleTest> {
+    let mut names = BTreeSet::new();
+    let mut tests = Vec::new();
+    for method in &meta.methods {
+        let Some(params_type) = &method.params_type else {
+            continue;
+        };
+        for example in &method.examples {
+            let base = format!("example_{}", field_name(&snake_case(&example.name)));
+            let mut name = base.clone();
+            let mut n = 1;
+            while !names.insert(name.clone()) {
+                n += 1;
+                name = format!("{}_{}", base, n);
+            }
+            let mut values = vec![(
+                "params",
+                ctx.type_name(params_type),
+                raw_string(&example.params_json()),
+            )];
+            if let (Some(result_type), Some(result)) = (&method.result_type, &example.result) {
+                values.push((
+                    "result",
+                    ctx.type_name(result_type),
+                    raw_string(&serde_json::to_string_pretty(result).unwrap_or_default()),
+                ));
+            }
+            tests.push(RustExampleTest {
+                name,
+                method: method.name.clone(),
+                example: example.name.clone(),
+                values,
+            });
+        }
+    }
+    tests
+}
+
+/// Identifier of a method: the last segment of its name (`resolve` for
+/// `trp.resolve`), or the whole name when that is ambiguous.
+pub fn method_ident(meta: &Metadata, method: &MethodMetadata) -> String {
+    let short = |name: &str| snake_case(name.rsplit('.').next().unwrap_or(name));
+    let ambiguous = meta
+        .methods
+        .iter()
+        .filter(|m| short(&m.name) == short(&method.name))
+        .count()
+        > 1;
+    match ambiguous {
+        true => escape_keyword(snake_case(&method.name)),
+        false => escape_keyword(short(&method.name)),
+    }
+}
+
+/// A method of the spec, as a function of the `client` module and of the
+/// `server` module's handler.
+#[derive(Clone)]
+pub struct RustMethod {
+    pub ident: String,
+    pub method: String,
+    pub summary: Option<String>,
+    pub constant: String,
+    /// Type of the params object; `serde_json::Value` unless the method takes
+    /// a single component describing it.
+    pub params_type: String,
+    /// Whether the params are a component, with a `validate()` method.
+    pub typed_params: bool,
+    /// Variant of the server's `ParamStructure` the method accepts.
+    pub param_structure: &'static str,
+    /// Whether clients send the params as an array.
+    pub by_position: bool,
+    pub result_type: String,
+    /// Whether the result is a component, with a `validate()` method.
+    pub typed_result: bool,
+    pub idempotency_key: bool,
+}
+
+pub fn methods(meta: &Metadata, ctx: &LanguageContext) -> Vec<RustMethod> {
+    let json = || "serde_json::Value".to_string();
+    meta.methods
+        .iter()
+        .map(|method| RustMethod {
+            ident: method_ident(meta, method),
+            method: method.name.clone(),
+            summary: method.summary.clone(),
+            constant: method.screaming_name(),
+            params_type: method
+                .params_type
+                .as_ref()
+                .map(|t| ctx.type_name(t))
+                .unwrap_or_else(json),
+            typed_params: method.params_type.is_some(),
+            param_structure: method.param_structure.pascal_name(),
+            by_position: method.by_position(),
+            result_type: method
+                .result_type
+                .as_ref()
+                .map(|t| ctx.type_name(t))
+                .unwrap_or_else(json),
+            typed_result: method.result_type.is_some(),
+            idempotency_key: method.idempotency_key,
+        })
+        .collect()
+}
+
+/// A method of the wasm crate's client: the Rust client method it calls,
+/// and its name and the TypeScript types of its params and result in JS.
+pub struct WasmMethod {
+    pub rust: RustMethod,
+    /// `trpResolve` for `trp.resolve`, as the TypeScript client names it.
+    pub js_name: String,
+    pub ts_params: String,
+    pub ts_result: String,
+}
+
+/// The methods of the wasm crate's client, whose types are named in Rust
+/// by `ctx` and in TypeScript by `ts`.
+pub fn wasm_methods(
+    meta: &Metadata,
+    ctx: &LanguageContext,
+    ts: &LanguageContext,
+) -> Vec<WasmMethod> {
+    methods(meta, ctx)
+        .into_iter()
+        .zip(&meta.methods)
+        .map(|(rust, method)| WasmMethod {
+            rust,
+            js_name: method.camel_name(),
+            ts_params: method
+                .params_type
+                .as_ref()
+                .map(|t| ts.type_name(t))
+                .unwrap_or_else(|| "Record<string, unknown>".to_string()),
+            ts_result: method
+                .result_type
+                .as_ref()
+                .map(|t| ts.type_name(t))
+                .unwrap_or_else(|| "unknown".to_string()),
+        })
+        .collect()
+}
+
+/// Name of the health method servers answer besides the spec's: `health` in
+/// the namespace all methods of the spec share (`trp.health`), or in `rpc`.
+pub fn health_method(meta: &Metadata) -> String {
+    let namespace = |name: &str| name.split_once('.').map(|(ns, _)| ns.to_string());
+    let shared = meta
+        .methods
+        .first()
+        .and_then(|method| namespace(&method.name))
+        .filter(|ns| {
+            meta.methods
+                .iter()
+                .all(|method| namespace(&method.name).as_ref() == Some(ns))
+        });
+    format!("{}.health", shared.as_deref().unwrap_or("rpc"))
+}
+
+/// The methods of a TRP spec a `ResolverBackend` implements, in the crate's
+/// `backend` module: `trp.resolve` and `trp.submit`, with their TRP params
+/// and results. `None` for specs without them.
+pub struct Backend {
+    pub resolve: String,
+    pub submit: String,
+    /// The spec's other methods, which backends don't implement.
+    pub others: Vec<RustMethod>,
+}
+
+/// The identifier of the method `name` taking `params` to `result`.
+fn find_method(methods: &[RustMethod], name: &str, params: &str, result: &str) -> Option<String> {
+    methods
+        .iter()
+        .find(|m| m.method == name && m.params_type == params && m.result_type == result)
+        .map(|m| m.ident.clone())
+}
+
+pub fn backend(methods: &[RustMethod]) -> Option<Backend> {
+    let find = |name: &str, params: &str, result: &str| find_method(methods, name, params, result);
+    let resolve = find("trp.resolve", "ResolveParams", "TxEnvelope")?;
+    let submit = find("trp.submit", "SubmitParams", "SubmitResponse")?;
+    let others = methods
+        .iter()
+        .filter(|m| m.ident != resolve && m.ident != submit)
+        .cloned()
+        .collect();
+    Some(Backend {
+        resolve,
+        submit,
+        others,
+    })
+}
+
+/// The methods of a TRP spec the crate's `watch` module waits for
+/// transactions with: `trp.submit` and `trp.checkStatus`, with their TRP
+/// params and results. `None` for specs without them.
+pub struct Watch {
+    pub submit: String,
+    pub check_status: String,
+}
+
+pub fn watch(methods: &[RustMethod]) -> Option<Watch> {
+    let find = |name: &str, params: &str, result: &str| find_method(methods, name, params, result);
+    Some(Watch {
+        submit: find("trp.submit", "SubmitParams", "SubmitResponse")?,
+        check_status: find(
+            "trp.checkStatus",
+            "CheckStatusParams",
+            "CheckStatusResponse",
+        )?,
+    })
+}
+
+/// Name of the module holding the types of a spec version, after its
+/// major version: `v1` for `1.4.0`.
+pub fn version_module(version: &str) -> Option<String> {
+    let major = version.trim_start_matches('v').split('.').next()?;
+    major.parse::<u64>().ok().map(|major| format!("v{}", major))
+}
+
+/// A `From` or `TryFrom` impl converting a component of one spec version to
+/// its namesake in the other, rendered in the module of the earlier
+/// version. Conversions that can fail report a `ValidationError`.
+pub struct Conversion {
+    pub from: String,
+    pub to: String,
+    pub fallible: bool,
+    /// Identifiers of the target's fields with the expressions computing
+    /// them from `value`.
+    pub fields: Vec<(String, String)>,
+}
+
+impl Conversion {
+    /// Name of the parameter, underscored when no field reads it.
+    pub fn binding(&self) -> &str {
+        match self.fields.iter().any(|(_, expr)| expr.contains("value.")) {
+            true => "value",
+            false => "_value",
+        }
+    }
+}
+
+/// The components of one spec version, as seen from the module of the
+/// earlier one.
+pub struct Version<'a> {
+    pub types: &'a [ResolvedType],
+    pub ctx: &'a LanguageContext,
+    /// Prefix of the paths to the types: empty for the earlier version's own
+    /// types, `super::` for the current ones.
+    pub path: &'a str,
+}
+
+/// Conversions between the components `previous` and `current` both
+/// declare, in both directions.
+///
+/// A target field is computed from the source field of the same JSON key
+/// when their types are the same or the target's is wider: an optional
+/// value from a required one, `f64` from `i64`, or a component converting
+/// in turn. The conversion is fallible where the source may lack a value
+/// the target requires. Target fields the source doesn't declare must be
+/// optional and are left empty; source fields the target doesn't declare
+/// are dropped. Components with any other change aren't converted.
+pub fn conversions(previous: &Version, current: &Version) -> Vec<Conversion> {
+    let mut out = convertible(previous, current);
+    out.extend(convertible(current, previous));
+    out
+}
+
+fn convertible(from: &Version, to: &Version) -> Vec<Conversion> {
+    // Components convert when their fields do, which may in turn take
+    // components converting: start from all namesakes converting without
+    // failing and settle on the largest consistent set.
+    let mut converting: BTreeMap<String, bool> = from
+        .types
+        .iter()
+        .filter(|ty| to.types.iter().any(|t| t.name == ty.name))
+        .map(|ty| (ty.name.clone(), false))
+        .collect();
+    loop {
+        let mut changed = false;
+        for ty in from.types {
+            let Some(&fallible) = converting.get(&ty.name) else {
+                continue;
+            };
+            let target = to.types.iter().find(|t| t.name == ty.name).unwrap();
+            let converter = Converter {
+                from,
+                to,
+                converting: &converting,
+            };
+            match converter.fields(ty, target) {
+                None => {
+                    converting.remove(&ty.name);
+                    changed = true;
+                }
+                Some((_, now)) if now != fallible => {
+                    converting.insert(ty.name.clone(), now);
+                    changed = true;
+                }
+                Some(_) => {}
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let converter = Converter {
+        from,
+        to,
+        converting: &converting,
+    };
+    from.types
+        .iter()
+        .filter(|ty| converting.contains_key(&ty.name))
+        .map(|ty| {
+            let target = to.types.iter().find(|t| t.name == ty.name).unwrap();
+            let (fields, fallible) = converter.fields(ty, target).unwrap();
+            Conversion {
+                from: converter.from.path(&ty.name),
+                to: converter.to.path(&ty.name),
+                fallible,
+                fields,
+            }
+        })
+        .collect()
+}
+
+impl Version<'_> {
+    fn path(&self, name: &str) -> String {
+        format!("{}{}", self.path, self.ctx.type_name(name))
+    }
+
+    fn kind(&self, schema: &SchemaObject) -> Kind {
+        let leaf = || {
+            let mut references = false;
+            visit_schemas(schema, &mut |s| references |= s.reference.is_some());
+            Kind::Leaf(map_type(schema, self.ctx), references)
+        };
+        if self.ctx.type_override(schema).is_some() {
+            return Kind::Leaf(map_type(schema, self.ctx), false);
+        }
+        if let Some(reference) = &schema.reference {
+            let name = crate::resolver::ref_to_name(reference).unwrap_or_default();
+            return Kind::Component(name);
+        }
+        if let Some(inner) = strip_null(schema) {
+            return Kind::Option(Box::new(self.kind(&inner)));
+        }
+        if schema.subschemas.is_some() || is_empty_object(schema) {
+            return leaf();
+        }
+        match map_primitive(schema) {
+            Some(InstanceType::Array) => {
+                let item =
+                    schema
+                        .array
+                        .as_deref()
+                        .and_then(|array| match array_tuple_items(array) {
+                            Some(_) => None,
+                            None => array_item_schema(array),
+                        });
+                match item {
+                    Some(item) => Kind::List(Box::new(self.kind(&item))),
+                    None => leaf(),
+                }
+            }
+            Some(InstanceType::Object) => {
+                let value = schema.object.as_deref().and_then(|object| {
+                    object_additional_properties(object)
+                        .or_else(|| object_pattern_properties(object).map(|(_, value)| value))
+                });
+                match value {
+                    Some(value) => Kind::Map(Box::new(self.kind(&value))),
+                    None => leaf(),
+                }
+            }
+            _ => leaf(),
+        }
+    }
+
+    fn field_kind(&self, field: &ResolvedField) -> Kind {
+        let kind = self.kind(&field.schema);
+        match field.required || is_nullable(&field.schema) {
+            true => kind,
+            false => Kind::Option(Box::new(kind)),
+        }
+    }
+}
+
+/// How a value is typed, for comparing versions.
+enum Kind {
+    Component(String),
+    Option(Box<Kind>),
+    List(Box<Kind>),
+    Map(Box<Kind>),
+    /// Any other type, and whether it refers to components, which makes
+    /// the same name different types in both versions.
+    Leaf(String, bool),
+}
+
+struct Converter<'a> {
+    from: &'a Version<'a>,
+    to: &'a Version<'a>,
+    /// Components converting, and whether they may fail to.
+    converting: &'a BTreeMap<String, bool>,
+}
+
+impl Converter<'_> {
+    /// Field initializers of `to` from a `value` of `from`, and whether
+    /// any may fail, or `None` if they don't convert.
+    fn fields(
+        &self,
+        from: &ResolvedType,
+        to: &ResolvedType,
+    ) -> Option<(Vec<(String, String)>, bool)> {
+        let mut fields = Vec::new();
+        let mut fallible = false;
+        for field in &to.fields {
+            let ident = field_ident(field, self.to.ctx);
+            let target = self.to.field_kind(field);
+            let expr = match from.fields.iter().find(|f| f.name == field.name) {
+                Some(source) => {
+                    let value = format!("value.{}", field_ident(source, self.from.ctx));
+                    let (expr, fails) = self.convert(
+                        &self.from.field_kind(source),
+                        &target,
+                        value,
+                        &field.name,
+                        0,
+                    )?;
+                    fallible |= fails;
+                    expr
+                }
+                None if matches!(target, Kind::Option(_)) => "None".to_string(),
+                None => return None,
+            };
+            fields.push((ident, expr));
+        }
+        match (
+            extra_properties(&from.schema),
+            extra_field(to, self.to.ctx),
+            extra_properties(&to.schema),
+        ) {
+            (Some(source), Some((ident, _)), Some(target)) => {
+                let (source_ident, _) = extra_field(from, self.from.ctx)?;
+                let (expr, fails) = self.convert(
+                    &Kind::Map(Box::new(self.from.kind(&source))),
+                    &Kind::Map(Box::new(self.to.kind(&target))),
+                    format!("value.{}", source_ident),
+                    "*",
+                    0,
+                )?;
+                fallible |= fails;
+                fields.push((ident, expr));
+            }
+            (None, Some((ident, _)), _) => fields.push((ident, "Default::default()".to_string())),
+            _ => {}
+        }
+        Some((fields, fallible))
+    }
+
+    /// Expression converting `value`, of kind `from`, to kind `to`, and
+    /// whether it may fail, returning early with a `ValidationError` at
+    /// `key`.
+    fn convert(
+        &self,
+        from: &Kind,
+        to: &Kind,
+        value: String,
+        key: &str,
+        depth: usize,
+    ) -> Option<(String, bool)> {
+        let var = format!("v{}", depth);
+        let recurse =
+            |from: &Kind, to: &Kind, value: String| self.convert(from, to, value, key, depth + 1);
+        // `|v| f(v)` is just `f`.
+        let closure = |body: &str| match body.strip_suffix(&format!("({})", var)) {
+            Some(function) if !function.contains(['(', ' ']) => function.to_string(),
+            _ => format!("|{}| {}", var, body),
+        };
+        // `Ok(f()?)` is just `f()`.
+        let ok = |body: &str| match body.strip_suffix('?') {
+            Some(result) => result.to_string(),
+            None => format!("Ok::<_, ValidationError>({})", body),
+        };
+        match (from, to) {
+            (Kind::Leaf(a, false), Kind::Leaf(b, false)) if a == b => Some((value, false)),
+            (Kind::Leaf(a, false), Kind::Leaf(b, false)) if a == "i64" && b == "f64" => {
+                Some((format!("{} as f64", value), false))
+            }
+            (Kind::Component(a), Kind::Component(b)) if a == b => {
+                let to = self.to.path(b);
+                match *self.converting.get(a)? {
+                    false => Some((format!("{}::from({})", to, value), false)),
+                    true => Some((
+                        format!(
+                            "{}::try_from({}).map_err(|e| e.nested(\"{}\"))?",
+                            to, value, key
+                        ),
+                        true,
+                    )),
+                }
+            }
+            (Kind::Option(a), Kind::Option(b)) => {
+                let (inner, fallible) = recurse(a, b, var.clone())?;
+                Some(match (inner == var, fallible) {
+                    (true, _) => (value, false),
+                    (false, false) => (format!("{}.map({})", value, closure(&inner)), false),
+                    (false, true) => (
+                        format!("{}.map(|{}| {}).transpose()?", value, var, ok(&inner)),
+                        true,
+                    ),
+                })
+            }
+            (a, Kind::Option(b)) => {
+                let (inner, fallible) = recurse(a, b, value)?;
+                Some((format!("Some({})", inner), fallible))
+            }
+            (Kind::Option(a), b) => {
+                let value = format!(
+                    "{}.ok_or_else(|| ValidationError::new(\"{}\", \"is required\"))?",
+                    value, key
+                );
+                let (inner, _) = self.convert(a, b, value, key, depth)?;
+                Some((inner, true))
+            }
+            (Kind::List(a), Kind::List(b)) => {
+                let (inner, fallible) = recurse(a, b, var.clone())?;
+                Some(match (inner == var, fallible) {
+                    (true, _) => (value, false),
+                    (false, false) => (
+                        format!("{}.into_iter().map({}).collect()", value, closure(&inner)),
+                        false,
+                    ),
+                    (false, true) => (
+                        format!(
+                            "{}.into_iter().map(|{}| {}).collect::<Result<_, ValidationError>>()?",
+                            value,
+                            var,
+                            ok(&inner)
+                        ),
+                        true,
+                    ),
+                })
+            }
+            (Kind::Map(a), Kind::Map(b)) => {
+                let (inner, fallible) = recurse(a, b, var.clone())?;
+                Some(match (inner == var, fallible) {
+                    (true, _) => (value, false),
+                    (false, false) => (
+                        format!(
+                            "{}.into_iter().map(|(k, {})| (k, {})).collect()",
+                            value, var, inner
+                        ),
+                        false,
+                    ),
+                    (false, true) => (
+                        format!(
+                            "{}.into_iter().map(|(k, {})| Ok((k, {}))).collect::<Result<_, ValidationError>>()?",
+                            value, var, inner
+                        ),
+                        true,
+                    ),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A proptest `Arbitrary` impl of the `arbitrary` module.
+pub struct ArbitraryImpl {
+    pub name: String,
+    /// Items of the tuple generating the fields. Past a few fields they are
+    /// tuples themselves, as proptest implements `Strategy` for tuples of up
+    /// to twelve items.
+    pub strategy: Vec<String>,
+    /// Pattern binding the values generated by `strategy` to the fields.
+    pub pattern: String,
+    pub fields: Vec<String>,
+}
+
+/// Fields per tuple of an [`ArbitraryImpl`] strategy.
+const TUPLE_ITEMS: usize = 10;
+
+/// `Arbitrary` impls of `types`, generating values that pass `validate()`.
+/// Fields through which a component reaches itself are left empty, so that
+/// generation terminates.
+pub fn arbitrary(types: &[ResolvedType], ctx: &LanguageContext) -> Vec<ArbitraryImpl> {
+    let graph = super::field_refs(types);
+    types
+        .iter()
+        .map(|ty| {
+            let mut fields = Vec::new();
+            let mut strategies = Vec::new();
+            for field in &ty.fields {
+                let recursive = field_refs_of(&field.schema)
+                    .iter()
+                    .any(|name| *name == ty.name || super::reaches(&graph, name, &ty.name));
+                let optional = !field.required && !is_nullable(&field.schema);
+                let strategy = match (recursive, optional) {
+                    (true, true) => "Just(None)".to_string(),
+                    (true, false) => {
+                        format!("Just(<{}>::default())", map_type(&field.schema, ctx))
+                    }
+                    (false, true) => format!(
+                        "proptest::option::of({})",
+                        value_strategy(&field.schema, &field.constraints, ctx)
+                    ),
+                    (false, false) => value_strategy(&field.schema, &field.constraints, ctx),
+                };
+                fields.push(field_ident(field, ctx));
+                strategies.push(strategy);
+            }
+            if let (Some(extra), Some((ident, _))) =
+                (extra_properties(&ty.schema), extra_field(ty, ctx))
+            {
+                let declared: Vec<String> =
+                    ty.fields.iter().map(|f| format!("{:?}", f.name)).collect();
+                let key = format!(
+                    "any::<String>().prop_filter(\"undeclared key\", |key| ![{}].contains(&key.as_str()))",
+                    declared.join(", ")
+                );
+                strategies.push(format!(
+                    "proptest::collection::hash_map({}, {}, 0..=4)",
+                    key,
+                    value_strategy(&extra, &[], ctx)
+                ));
+                fields.push(ident);
+            }
+            let (strategy, pattern) = match fields.len() > TUPLE_ITEMS {
+                true => (
+                    strategies.chunks(TUPLE_ITEMS).map(tuple).collect(),
+                    tuple(&fields.chunks(TUPLE_ITEMS).map(tuple).collect::<Vec<_>>()),
+                ),
+                false => (strategies, tuple(&fields)),
+            };
+            ArbitraryImpl {
+                name: ctx.type_name(&ty.name),
+                strategy,
+                pattern,
+                fields,
+            }
+        })
+        .collect()
+}
+
+fn tuple(items: &[String]) -> String {
+    match items.len() {
+        1 => format!("({},)", items[0]),
+        _ => format!("({})", items.join(", ")),
+    }
+}
+
+/// Raw names of the components `schema` refers to, at any depth.
+fn field_refs_of(schema: &SchemaObject) -> Vec<String> {
+    let mut names = Vec::new();
+    visit_schemas(schema, &mut |schema| {
+        if let Some(name) = schema
+            .reference
+            .as_deref()
+            .and_then(|r| crate::resolver::ref_to_name(r).ok())
+        {
+            names.push(name);
+        }
+    });
+    names
+}
+
+/// Strategy generating values of `schema` that satisfy `constraints`.
+fn value_strategy(
+    schema: &SchemaObject,
+    constraints: &[Constraint],
+    ctx: &LanguageContext,
+) -> String {
+    if let Some(ty) = ctx.type_override(schema) {
+        return format!("any::<{}>()", ty);
+    }
+    if let Some(reference) = map_reference(schema, ctx) {
+        return format!("any::<{}>()", reference);
+    }
+    if let Some(inner) = strip_null(schema) {
+        return format!(
+            "proptest::option::of({})",
+            value_strategy(&inner, constraints, ctx)
+        );
+    }
+    let ty = map_type(schema, ctx);
+    if let Some(value) = schema.const_value.as_ref().filter(|v| super::is_scalar(v)) {
+        return format!("Just({})", default_expr(value, &ty));
+    }
+    if let Some(values) = schema
+        .enum_values
+        .as_ref()
+        .filter(|values| !values.is_empty() && values.iter().all(super::is_scalar))
+    {
+        let values: Vec<String> = values.iter().map(|v| default_expr(v, &ty)).collect();
+        return format!("proptest::sample::select(vec![{}])", values.join(", "));
+    }
+    if schema.subschemas.is_some() {
+        return format!("any::<{}>()", ty);
+    }
+    if is_empty_object(schema) {
+        return format!("Just({} {{}})", EMPTY_OBJECT);
+    }
+    let min = |n: &Option<u32>| n.unwrap_or(0);
+    match map_primitive(schema) {
+        // Enterprise mainnet addresses of random key hashes.
+        Some(InstanceType::String) if constraints.contains(&Constraint::Address) => {
+            "any::<[u8; 28]>().prop_map(|hash| crate::address::Address::from_bytes([&[0x61][..], &hash].concat()).expect(\"valid address\").to_string())"
+                .to_string()
+        }
+        Some(InstanceType::String) => {
+            let (mut min_length, mut max_length, mut pattern) = (None, None, None);
+            for constraint in constraints {
+                match constraint {
+                    Constraint::MinLength(n) => min_length = Some(*n),
+                    Constraint::MaxLength(n) => max_length = Some(*n),
+                    Constraint::Pattern(p) => pattern = Some(p.clone()),
+                    _ => {}
+                }
+            }
+            let lo = min(&min_length);
+            let hi = max_length.unwrap_or(lo + 16);
+            match pattern {
+                Some(pattern) if min_length.is_some() || max_length.is_some() => format!(
+                    "{}.prop_filter(\"length\", |value| ({}..={}).contains(&value.chars().count()))",
+                    string_regex(&pattern),
+                    lo,
+                    hi
+                ),
+                Some(pattern) => string_regex(&pattern),
+                None if min_length.is_some() || max_length.is_some() => {
+                    string_regex(&format!(".{{{},{}}}", lo, hi))
+                }
+                None => "any::<String>()".to_string(),
+            }
+        }
+        Some(InstanceType::Integer) => match number_range(constraints, true) {
+            Some(range) => range,
+            None => "any::<i64>()".to_string(),
+        },
+        Some(InstanceType::Number) => match number_range(constraints, false) {
+            Some(range) => range,
+            // Serde encodes NaN and infinities as `null`.
+            None => "prop::num::f64::NORMAL | prop::num::f64::ZERO".to_string(),
+        },
+        Some(InstanceType::Boolean) => "any::<bool>()".to_string(),
+        Some(InstanceType::Null) => "Just(None)".to_string(),
+        Some(InstanceType::Array) => {
+            let array = schema.array.as_deref();
+            if let Some(items) = array.and_then(array_tuple_items) {
+                let items: Vec<String> = items
+                    .iter()
+                    .map(|item| value_strategy(item, &[], ctx))
+                    .collect();
+                return tuple(&items);
+            }
+            let item = match array.and_then(array_item_schema) {
+                Some(item) => value_strategy(&item, &[], ctx),
+                None => "json_value()".to_string(),
+            };
+            let min_items = constraints
+                .iter()
+                .find_map(|c| match c {
+                    Constraint::MinItems(n) => Some(*n),
+                    _ => None,
+                })
+                .unwrap_or(0);
+            let mut strategy = format!(
+                "proptest::collection::vec({}, {}..={})",
+                item,
+                min_items,
+                min_items + 4
+            );
+            if constraints.contains(&Constraint::UniqueItems) {
+                strategy.push_str(
+                    ".prop_map(|mut items| { let mut seen = std::collections::HashSet::new(); \
+                     items.retain(|item| seen.insert(serde_json::to_string(item).unwrap_or_default())); items })",
+                );
+                if min_items > 1 {
+                    strategy.push_str(&format!(
+                        ".prop_filter(\"min items\", |items| items.len() >= {})",
+                        min_items
+                    ));
+                }
+            }
+            strategy
+        }
+        Some(InstanceType::Object) => {
+            let object = schema.object.as_deref();
+            let value = object.and_then(|object| {
+                object_additional_properties(object)
+                    .or_else(|| object_pattern_properties(object).map(|(_, value)| value))
+            });
+            let value = match value {
+                Some(value) => value_strategy(&value, &[], ctx),
+                None => "json_value()".to_string(),
+            };
+            let key = constraints
+                .iter()
+                .find_map(|c| match c {
+                    Constraint::KeyPattern(pattern) => Some(string_regex(pattern)),
+                    _ => None,
+                })
+                .unwrap_or_else(|| "any::<String>()".to_string());
+            format!("proptest::collection::hash_map({}, {}, 0..=4)", key, value)
+        }
+        _ => "json_value()".to_string(),
+    }
+}
+
+/// Strategy generating strings matching `pattern`. proptest generates whole
+/// matches but rejects anchors, so the outer ones are dropped.
+fn string_regex(pattern: &str) -> String {
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    let pattern = match pattern.strip_suffix('$') {
+        Some(rest) if !rest.ends_with('\\') => rest,
+        _ => pattern,
+    };
+    format!(
+        "proptest::string::string_regex({:?}).expect(\"valid pattern\")",
+        pattern
+    )
+}
+
+/// Range of the numbers satisfying `minimum` and `maximum`, if either is set.
+fn number_range(constraints: &[Constraint], integer: bool) -> Option<String> {
+    let (mut lo, mut hi) = (None, None);
+    for constraint in constraints {
+        match constraint {
+            Constraint::Minimum(n) => lo = Some(*n),
+            Constraint::Maximum(n) => hi = Some(*n),
+            _ => {}
+        }
+    }
+    if lo.is_none() && hi.is_none() {
+        return None;
+    }
+    Some(match integer {
+        true => format!(
+            "{}..={}",
+            lo.map(|n| format!("{}i64", n.ceil() as i64))
+                .unwrap_or_else(|| "i64::MIN".to_string()),
+            hi.map(|n| format!("{}i64", n.floor() as i64))
+                .unwrap_or_else(|| "i64::MAX".to_string())
+        ),
+        false => format!(
+            "{}..={}",
+            lo.map(|n| format!("{:?}", n))
+                .unwrap_or_else(|| "f64::MIN".to_string()),
+            hi.map(|n| format!("{:?}", n))
+                .unwrap_or_else(|| "f64::MAX".to_string())
+        ),
+    })
+}
+
+/// Rust literal for a scalar JSON value, comparable against the mapped type.
+pub fn literal(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("{:?}", s),
+        Value::Number(n) if n.is_f64() => format!("{:?}", n.as_f64().unwrap_or_default()),
+        other => other.to_string(),
+    }
+}
+
+/// Body of the generated `validate()` method of `ty`, one statement per line.
+/// Each field is checked against its constraints, and fields holding other
+/// generated structs are validated recursively.
+pub fn validation(ty: &ResolvedType, ctx: &LanguageContext) -> Vec<String> {
+    let mut lines = Vec::new();
+    for field in &ty.fields {
+        // Overridden types are opaque to the generator.
+        if ctx.type_override(&field.schema).is_some() {
+            continue;
+        }
+        let inner = strip_null(&field.schema).unwrap_or_else(|| field.schema.clone());
+        let integer = map_primitive(&inner) == Some(InstanceType::Integer);
+
+        let mut checks = Vec::new();
+        for constraint in &field.constraints {
+            let fail = format!(
+                "return Err(ValidationError::new({:?}, {:?}));",
+                field.name,
+                constraint.message()
+            );
+            let condition = match constraint {
+                Constraint::MinLength(1) | Constraint::MinItems(1) => {
+                    "value.is_empty()".to_string()
+                }
+                Constraint::MinLength(n) => format!("value.chars().count() < {}", n),
+                Constraint::MaxLength(n) => format!("value.chars().count() > {}", n),
+                Constraint::Pattern(pattern) => {
+                    checks.push(PATTERN_STATIC.to_string());
+                    checks.push(pattern_init(pattern));
+                    "!pattern.is_match(value)".to_string()
+                }
+                Constraint::Minimum(n) => {
+                    format!("{} < {}", number_operand(integer, *n), bound(integer, *n))
+                }
+                Constraint::Maximum(n) => {
+                    format!("{} > {}", number_operand(integer, *n), bound(integer, *n))
+                }
+                Constraint::MinItems(n) => format!("value.len() < {}", n),
+                Constraint::Address => "!address::is_address(value)".to_string(),
+                Constraint::KeyPattern(pattern) => {
+                    checks.push(PATTERN_STATIC.to_string());
+                    checks.push(pattern_init(pattern));
+                    "!value.keys().all(|key| pattern.is_match(key))".to_string()
+                }
+                Constraint::UniqueItems => {
+                    checks.push("let mut seen = std::collections::HashSet::new();".to_string());
+                    "!value.iter().all(|item| seen.insert(serde_json::to_string(item).unwrap_or_default()))"
+                        .to_string()
+                }
+            };
+            checks.push(format!("if {} {{", condition));
+            checks.push(format!("    {}", fail));
+            checks.push("}".to_string());
+        }
+
+        if inner.reference.is_some() || union_options(&inner).is_some() {
+            checks.push(format!(
+                "value.validate().map_err(|e| e.nested({:?}))?;",
+                field.name
+            ));
+        } else if let Some(item) = inner.array.as_deref().and_then(array_item_schema) {
+            if item.reference.is_some() || union_options(&item).is_some() {
+                checks.push("for (i, item) in value.iter().enumerate() {".to_string());
+                checks.push(format!(
+                    "    item.validate().map_err(|e| e.nested(&format!(\"{}[{{}}]\", i)))?;",
+                    field.name
+                ));
+                checks.push("}".to_string());
+            }
+        }
+
+        if checks.is_empty() {
+            continue;
+        }
+        if !field.required || is_nullable(&field.schema) {
+            lines.push(format!(
+                "if let Some(value) = &self.{} {{",
+                field_ident(field, ctx)
+            ));
+        } else {
+            lines.push("{".to_string());
+            lines.push(format!(
+                "    let value = &self.{};",
+                field_ident(field, ctx)
+            ));
+        }
+        lines.extend(checks.into_iter().map(|line| format!("    {}", line)));
+        lines.push("}".to_string());
+    }
+    lines
+}
+
+// Patterns are compiled once per field, on first use.
+const PATTERN_STATIC: &str =
+    "static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();";
+
+fn pattern_init(pattern: &str) -> String {
+    format!(
+        "let pattern = PATTERN.get_or_init(|| regex::Regex::new({:?}).expect(\"valid pattern\"));",
+        pattern
+    )
+}
+
+fn number_operand(integer: bool, bound: f64) -> &'static str {
+    if integer && bound.fract() != 0.0 {
+        "(*value as f64)"
+    } else {
+        "*value"
+    }
+}
+
+fn bound(integer: bool, bound: f64) -> String {
+    if integer && bound.fract() == 0.0 {
+        format!("{}", bound as i64)
+    } else {
+        format!("{:?}", bound)
+    }
+}
@@ -0,0 +1,282 @@
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::Value;
+
+use super::{
+    camel_case, extension_override, is_scalar, map_primitive, map_reference, nullable_inner,
+    sanitize_identifier, Construct, LanguageContext,
+};
+use crate::resolver::{
+    any_of, array_item_schema, array_tuple_items, object_additional_properties,
+    object_pattern_properties, one_of, ResolvedField, ResolvedType,
+};
+
+/// Package of the generated sources.
+pub const PACKAGE: &str = "trp";
+
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "case",
+    "catch",
+    "class",
+    "def",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "for",
+    "forSome",
+    "given",
+    "if",
+    "implicit",
+    "import",
+    "lazy",
+    "macro",
+    "match",
+    "new",
+    "null",
+    "object",
+    "override",
+    "package",
+    "private",
+    "protected",
+    "return",
+    "sealed",
+    "super",
+    "then",
+    "this",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "val",
+    "var",
+    "while",
+    "with",
+    "yield",
+];
+
+/// The components of a spec and the unions found in their fields.
+pub struct ScalaBinding {
+    pub classes: Vec<ScalaClass>,
+    pub unions: Vec<ScalaUnion>,
+}
+
+/// A case class, one per component.
+pub struct ScalaClass {
+    pub name: String,
+    pub deprecated: bool,
+    pub fields: Vec<ScalaField>,
+}
+
+impl ScalaClass {
+    pub fn has_optional_fields(&self) -> bool {
+        self.fields.iter().any(|f| !f.required)
+    }
+}
+
+pub struct ScalaField {
+    /// Key of the field in JSON.
+    pub name: String,
+    /// Parameter name, backquoted when it is a keyword.
+    pub ident: String,
+    /// Name the decoded value is bound to in the decoder.
+    pub local: String,
+    pub ty: String,
+    pub required: bool,
+}
+
+/// A `oneOf`/`anyOf` field type, rendered as a sealed trait with a case
+/// class wrapping each option. Decoding tries the options in order.
+pub struct ScalaUnion {
+    pub name: String,
+    /// `(case class, wrapped type)` of each option.
+    pub variants: Vec<(String, String)>,
+}
+
+pub fn type_name(raw: &str) -> String {
+    sanitize_identifier(raw)
+}
+
+pub fn field_name(raw: &str) -> String {
+    let mut out = camel_case(raw);
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert_str(0, "field");
+    }
+    out
+}
+
+/// Identifier of a field, honouring an `x-scala-name` override.
+pub fn field_ident(field: &ResolvedField) -> String {
+    extension_override(&field.schema, "scala", "name").unwrap_or_else(|| field_name(&field.name))
+}
+
+fn is_keyword(ident: &str) -> bool {
+    KEYWORDS.contains(&ident)
+}
+
+/// Double quoted Scala string literal.
+pub fn string_literal(value: &str) -> String {
+    Value::String(value.to_string()).to_string()
+}
+
+/// Maps `schema`, declaring the unions it contains in `unions`. A union is
+/// named `union`, or `union` suffixed with a counter when the name is taken.
+pub fn map_type(
+    schema: &SchemaObject,
+    ctx: &LanguageContext,
+    union: &str,
+    unions: &mut Vec<ScalaUnion>,
+) -> String {
+    if let Some(ty) = ctx.type_override(schema) {
+        return ty;
+    }
+
+    if let Some(reference) = map_reference(schema, ctx) {
+        return reference;
+    }
+
+    if let Some(inner) = nullable_inner(schema) {
+        return format!("Option[{}]", map_type(&inner, ctx, union, unions));
+    }
+
+    if let Some(literal) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+        return match literal {
+            Value::String(_) => "String",
+            Value::Bool(_) => "Boolean",
+            Value::Number(n) if n.is_f64() => "Double",
+            _ => "Long",
+        }
+        .to_string();
+    }
+
+    if let Some(subschemas) = &schema.subschemas {
+        return match one_of(subschemas).or_else(|| any_of(subschemas)) {
+            Some(options) => declare_union(&options, ctx, union, unions),
+            None => {
+                ctx.degrade(Construct::Composition);
+                "Json".to_string()
+            }
+        };
+    }
+
+    if let Some(enum_values) = &schema.enum_values {
+        if enum_values.iter().all(|v| v.is_string()) {
+            return "String".to_string();
+        }
+    }
+
+    match map_primitive(schema) {
+        Some(InstanceType::Array) => {
+            let array = schema.array.as_deref();
+            if let Some(items) = array.and_then(array_tuple_items) {
+                let items: Vec<String> = items
+                    .iter()
+                    .map(|s| map_type(s, ctx, union, unions))
+                    .collect();
+                return format!("({})", items.join(", "));
+            }
+            match array.and_then(array_item_schema) {
+                Some(item) => format!("List[{}]", map_type(&item, ctx, union, unions)),
+                None => "List[Json]".to_string(),
+            }
+        }
+        Some(InstanceType::Object) => {
+            let value = schema.object.as_deref().and_then(|object| {
+                object_additional_properties(object)
+                    .or_else(|| object_pattern_properties(object).map(|(_, value)| value))
+            });
+            match value {
+                Some(value) => format!("Map[String, {}]", map_type(&value, ctx, union, unions)),
+                None => "JsonObject".to_string(),
+            }
+        }
+        Some(InstanceType::String) => "String".to_string(),
+        Some(InstanceType::Integer) => "Long".to_string(),
+        Some(InstanceType::Number) => "Double".to_string(),
+        Some(InstanceType::Boolean) => "Boolean".to_string(),
+        _ => "Json".to_string(),
+    }
+}
+
+fn declare_union(
+    options: &[SchemaObject],
+    ctx: &LanguageContext,
+    union: &str,
+    unions: &mut Vec<ScalaUnion>,
+) -> String {
+    let mut name = union.to_string();
+    let mut counter = 1;
+    while unions.iter().any(|u| u.name == name) || ctx.has_type_name(&name) {
+        counter += 1;
+        name = format!("{}{}", union, counter);
+    }
+    // Reserve the name before mapping the options, which may declare
+    // unions of their own.
+    unions.push(ScalaUnion {
+        name: name.clone(),
+        variants: Vec::new(),
+    });
+    let index = unions.len() - 1;
+
+    let mut variants: Vec<(String, String)> = Vec::new();
+    for (position, option) in options.iter().enumerate() {
+        let ty = map_type(option, ctx, &format!("{}Option", name), unions);
+        let mut variant = format!("{}Value", sanitize_identifier(&ty));
+        if variants.iter().any(|(v, _)| *v == variant) {
+            variant = format!("Option{}Value", position + 1);
+        }
+        variants.push((variant, ty));
+    }
+    unions[index].variants = variants;
+    name
+}
+
+pub fn binding(types: &[ResolvedType], ctx: &LanguageContext) -> ScalaBinding {
+    let mut unions = Vec::new();
+    let classes = types
+        .iter()
+        .map(|ty| {
+            let name = ctx.type_name(&ty.name);
+            let fields = ty
+                .fields
+                .iter()
+                .map(|field| {
+                    let ident = field_ident(field);
+                    let union = format!("{}{}", name, sanitize_identifier(&ident));
+                    let mut mapped = map_type(&field.schema, ctx, &union, &mut unions);
+                    if !field.required && !mapped.starts_with("Option[") {
+                        mapped = format!("Option[{}]", mapped);
+                    }
+                    // Decoded values must not shadow the cursor they are
+                    // read from.
+                    let local = match is_keyword(&ident) || ident == "cursor" {
+                        true => format!("{}_", ident),
+                        false => ident.clone(),
+                    };
+                    let ident = match is_keyword(&ident) {
+                        true => format!("`{}`", ident),
+                        false => ident,
+                    };
+                    ScalaField {
+                        name: string_literal(&field.name),
+                        ident,
+                        local,
+                        ty: mapped,
+                        required: field.required,
+                    }
+                })
+                .collect();
+            ScalaClass {
+                name,
+                deprecated: ty.schema.metadata.as_ref().is_some_and(|m| m.deprecated),
+                fields,
+            }
+        })
+        .collect();
+    ScalaBinding { classes, unions }
+}
@@ -0,0 +1,471 @@
+use std::collections::BTreeSet;
+
+use schemars::schema::{InstanceType, SchemaObject};
+use serde_json::{json, Value};
+
+use super::{
+    is_empty_object, is_scalar, map_primitive, map_reference, sanitize_identifier, strip_null,
+    union_options, union_tags, LanguageContext,
+};
+use crate::metadata::Metadata;
+use crate::resolver::{
+    any_of, array_item_schema, array_tuple_items, extra_properties, object_additional_properties,
+    object_pattern_properties, one_of, ref_to_name, visit_schemas, Constraint, ResolvedField,
+    ResolvedType,
+};
+
+/// Name the `deno` flavor is published under on JSR, and the `ts` flavor
+/// on npm.
+pub const JSR_PACKAGE: &str = "@tx3/trp";
+
+/// Modules of the `deno` flavor exported by its package, by subpath. The
+/// client is the entry point.
+const JSR_EXPORTS: &[(&str, &str)] = &[
+    (".", "./client.ts"),
+    ("./types", "./types.ts"),
+    ("./schemas", "./schemas.ts"),
+    ("./constants", "./constants.ts"),
+    ("./errors", "./errors.ts"),
+    ("./error_codes", "./error_codes.ts"),
+    ("./mock", "./mock.ts"),
+    ("./utxo", "./utxo.ts"),
+    ("./address", "./address.ts"),
+];
+
+/// Range of `zod` versions the schemas are written against.
+const ZOD_VERSION: &str = "^3.23";
+
+/// Package manifest of the `deno` flavor: `jsr.json` for JSR, or with
+/// `imports` the `deno.json` that also maps `zod` to its npm package, so
+/// that the modules themselves only import bare specifiers and relative
+/// paths. Specs with TIR helpers also export them, as `./tir`, and specs
+/// with unions their helpers, as `./unions`.
+pub fn jsr_manifest(meta: &Metadata, imports: bool, tir: bool, unions: bool) -> Value {
+    let mut manifest = json!({
+        "name": JSR_PACKAGE,
+        "version": meta.version.as_deref().unwrap_or("0.0.0"),
+        "exports": exports(tir, unions),
+    });
+    if imports {
+        manifest["imports"] = json!({ "zod": format!("npm:zod@{}", ZOD_VERSION) });
+    }
+    manifest
+}
+
+/// Range of `react` versions the hooks are written against, those with
+/// hooks.
+const REACT_VERSION: &str = ">=16.8";
+
+/// `package.json` of the `ts` flavor, for npm. The package ships the
+/// TypeScript sources as they are, for bundlers and TypeScript-aware
+/// runtimes to compile, with the entry points of the `deno` flavor, and
+/// with `react` the hooks, as `./react`, which take React as a peer.
+pub fn npm_manifest(meta: &Metadata, tir: bool, unions: bool, react: bool) -> Value {
+    let mut manifest = json!({
+        "name": JSR_PACKAGE,
+        "version": meta.version.as_deref().unwrap_or("0.0.0"),
+        "description": format!(
+            "TypeScript bindings of the {}, generated from its OpenRPC spec",
+            meta.title_or_default()
+        ),
+    });
+    if let Some(license) = &meta.license {
+        manifest["license"] = json!(license);
+    }
+    manifest["type"] = json!("module");
+    manifest["exports"] = exports(tir, unions);
+    if react {
+        manifest["exports"]["./react"] = json!("./react.ts");
+    }
+    manifest["files"] = json!(["*.ts", "README.md"]);
+    manifest["dependencies"] = json!({ "zod": ZOD_VERSION });
+    if react {
+        manifest["peerDependencies"] = json!({ "react": REACT_VERSION });
+    }
+    manifest
+}
+
+fn exports(tir: bool, unions: bool) -> Value {
+    let mut exports: serde_json::Map<String, Value> = JSR_EXPORTS
+        .iter()
+        .map(|(path, module)| (path.to_string(), Value::from(*module)))
+        .collect();
+    if tir {
+        exports.insert("./tir".to_string(), Value::from("./tir.ts"));
+    }
+    if unions {
+        exports.insert("./unions".to_string(), Value::from("./unions.ts"));
+    }
+    Value::Object(exports)
+}
+
+pub fn type_name(raw: &str) -> String {
+    sanitize_identifier(raw)
+}
+
+/// Property name of a field. Keys that aren't identifiers, such as
+/// `3rd-party`, are quoted.
+pub fn field_name(raw: &str) -> String {
+    let mut chars = raw.chars();
+    let name = match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => return "\"\"".to_string(),
+    };
+    let identifier = !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    match identifier {
+        true => name,
+        false => Value::String(raw.to_string()).to_string(),
+    }
+}
+
+pub fn map_type(schema: &SchemaObject, ctx: &LanguageContext) -> String {
+    if let Some(ty) = ctx.type_override(schema) {
+        return ty;
+    }
+
+    if let Some(reference) = map_reference(schema, ctx) {
+        return reference;
+    }
+
+    if let Some(inner) = strip_null(schema) {
+        return ctx.wrap_nullable(&map_type(&inner, ctx));
+    }
+
+    if let Some(literal) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+        return literal.to_string();
+    }
+
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(options) = one_of(subschemas) {
+            let joined: Vec<String> = options.iter().map(|s| map_type(s, ctx)).collect();
+            return joined.join(" | ");
+        }
+        if let Some(options) = any_of(subschemas) {
+            let joined: Vec<String> = options.iter().map(|s| map_type(s, ctx)).collect();
+            return joined.join(" | ");
+        }
+    }
+
+    if let Some(enum_values) = &schema.enum_values {
+        let variants: Vec<String> = enum_values
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| format!("\"{}\"", s)))
+            .collect();
+        if !variants.is_empty() {
+            return variants.join(" | ");
+        }
+    }
+
+    if let Some(InstanceType::Array) = map_primitive(schema) {
+        if let Some(array) = &schema.array {
+            if let Some(items) = array_tuple_items(array) {
+                let items: Vec<String> = items.iter().map(|s| map_type(s, ctx)).collect();
+                return format!("[{}]", items.join(", "));
+            }
+            if let Some(item) = array_item_schema(array) {
+                return format!("{}[]", map_type(&item, ctx));
+            }
+        }
+        return "any[]".to_string();
+    }
+
+    if is_empty_object(schema) {
+        return "Record<string, never>".to_string();
+    }
+
+    if let Some(InstanceType::Object) = map_primitive(schema) {
+        if let Some(object) = &schema.object {
+            if let Some(additional) = object_additional_properties(object) {
+                return format!("Record<string, {}>", map_type(&additional, ctx));
+            }
+            if let Some((_, value)) = object_pattern_properties(object) {
+                return format!("Record<string, {}>", map_type(&value, ctx));
+            }
+        }
+        return "Record<string, any>".to_string();
+    }
+
+    match map_primitive(schema) {
+        Some(InstanceType::String) => "string".to_string(),
+        Some(InstanceType::Integer) | Some(InstanceType::Number) => "number".to_string(),
+        Some(InstanceType::Boolean) => "boolean".to_string(),
+        Some(InstanceType::Null) => "null".to_string(),
+        _ => {
+            ctx.degrade_subschemas(schema);
+            "any".to_string()
+        }
+    }
+}
+
+/// Value type of the index signature of `ty`, when it types the members
+/// beyond its declared fields. TypeScript wants the declared fields to fit
+/// the signature too, so their types join the union.
+pub fn index_signature(ty: &ResolvedType, ctx: &LanguageContext) -> Option<String> {
+    let extra = extra_properties(&ty.schema)?;
+    let mut members = vec![map_type(&extra, ctx)];
+    for field in &ty.fields {
+        members.push(map_type(&field.schema, ctx));
+        if !field.required {
+            members.push("undefined".to_string());
+        }
+    }
+    let mut seen = BTreeSet::new();
+    members.retain(|member| seen.insert(member.clone()));
+    Some(members.join(" | "))
+}
+
+/// Zod schema of the members of `ty` beyond its declared fields, for
+/// `.catchall()`.
+pub fn zod_catchall(ty: &ResolvedType, ctx: &LanguageContext) -> Option<String> {
+    extra_properties(&ty.schema).map(|extra| zod_type(&extra, ctx))
+}
+
+/// Zod schema accepting the values described by `schema`. References go
+/// through `z.lazy` so that schemas can be declared in any order and may be
+/// recursive.
+pub fn zod_type(schema: &SchemaObject, ctx: &LanguageContext) -> String {
+    if let Some(ty) = ctx.type_override(schema) {
+        return format!("z.custom<{}>()", ty);
+    }
+
+    if let Some(reference) = map_reference(schema, ctx) {
+        return format!("z.lazy(() => {}Schema)", reference);
+    }
+
+    if let Some(inner) = strip_null(schema) {
+        return format!("{}.nullable()", zod_type(&inner, ctx));
+    }
+
+    if let Some(literal) = schema.const_value.as_ref().filter(|v| is_scalar(v)) {
+        return format!("z.literal({})", literal);
+    }
+
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(options) = one_of(subschemas).or_else(|| any_of(subschemas)) {
+            let options: Vec<String> = options.iter().map(|s| zod_type(s, ctx)).collect();
+            return match options.as_slice() {
+                [single] => single.clone(),
+                _ => format!("z.union([{}])", options.join(", ")),
+            };
+        }
+    }
+
+    if let Some(enum_values) = &schema.enum_values {
+        let variants: Vec<String> = enum_values
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| format!("\"{}\"", s)))
+            .collect();
+        if !variants.is_empty() {
+            return format!("z.enum([{}])", variants.join(", "));
+        }
+    }
+
+    if let Some(InstanceType::Array) = map_primitive(schema) {
+        if let Some(array) = &schema.array {
+            if let Some(items) = array_tuple_items(array) {
+                let items: Vec<String> = items.iter().map(|s| zod_type(s, ctx)).collect();
+                return format!("z.tuple([{}])", items.join(", "));
+            }
+            if let Some(item) = array_item_schema(array) {
+                return format!("z.array({})", zod_type(&item, ctx));
+            }
+        }
+        return "z.array(z.any())".to_string();
+    }
+
+    if is_empty_object(schema) {
+        return "z.object({}).strict()".to_string();
+    }
+
+    if let Some(InstanceType::Object) = map_primitive(schema) {
+        if let Some(object) = &schema.object {
+            if let Some(additional) = object_additional_properties(object) {
+                return format!("z.record(z.string(), {})", zod_type(&additional, ctx));
+            }
+            if let Some((pattern, value)) = object_pattern_properties(object) {
+                return format!(
+                    "z.record(z.string().regex(new RegExp({})), {})",
+                    serde_json::Value::String(pattern),
+                    zod_type(&value, ctx)
+                );
+            }
+        }
+        return "z.record(z.string(), z.any())".to_string();
+    }
+
+    match map_primitive(schema) {
+        Some(InstanceType::String) => "z.string()".to_string(),
+        Some(InstanceType::Integer) => "z.number().int()".to_string(),
+        Some(InstanceType::Number) => "z.number()".to_string(),
+        Some(InstanceType::Boolean) => "z.boolean()".to_string(),
+        Some(InstanceType::Null) => "z.null()".to_string(),
+        _ => "z.any()".to_string(),
+    }
+}
+
+/// Zod schema of a property, with the field's constraints applied as
+/// refinements before it is made nullable or optional.
+pub fn zod_field(field: &ResolvedField, ctx: &LanguageContext) -> String {
+    let (inner, nullable) = match strip_null(&field.schema) {
+        Some(inner) => (inner, true),
+        None => (field.schema.clone(), false),
+    };
+
+    let mut expr = zod_type(&inner, ctx);
+    for constraint in &field.constraints {
+        let message = format!("{{ message: {:?} }}", constraint.message());
+        match constraint {
+            Constraint::MinLength(n) | Constraint::MinItems(n) => {
+                expr.push_str(&format!(".min({}, {})", n, message))
+            }
+            Constraint::MaxLength(n) => expr.push_str(&format!(".max({}, {})", n, message)),
+            Constraint::Pattern(pattern) => expr.push_str(&format!(
+                ".regex(new RegExp({}), {})",
+                serde_json::Value::String(pattern.clone()),
+                message
+            )),
+            Constraint::Minimum(n) => expr.push_str(&format!(".gte({}, {})", n, message)),
+            Constraint::Maximum(n) => expr.push_str(&format!(".lte({}, {})", n, message)),
+            // Already part of the record's key schema.
+            Constraint::KeyPattern(_) => {}
+            Constraint::Address => expr.push_str(&format!(".refine(isAddress, {})", message)),
+            Constraint::UniqueItems => expr.push_str(&format!(
+                ".refine((items) => new Set(items.map((item) => JSON.stringify(item))).size === items.length, {})",
+                message
+            )),
+        }
+    }
+    if nullable {
+        expr.push_str(".nullable()");
+    }
+    if !field.required {
+        expr.push_str(".optional()");
+    }
+    expr
+}
+
+/// A `oneOf`/`anyOf` union of several types, declared by `unions.ts` as a
+/// named alias with the list of its variants and a matcher.
+pub struct TsUnion {
+    pub name: String,
+    pub ty: String,
+    pub variants: Vec<TsVariant>,
+    /// Whether the variants can be told apart at runtime, by discriminator
+    /// or by JSON type, for the matcher.
+    pub dispatch: bool,
+}
+
+pub struct TsVariant {
+    /// Discriminator of the variant, or else its type's name, as a string
+    /// literal.
+    pub key: String,
+    pub ty: String,
+    /// Condition on `value` holding the variant.
+    pub test: String,
+}
+
+/// Identifier part standing for a TypeScript type in the name of a union.
+fn type_part(ty: &str) -> String {
+    sanitize_identifier(&ty.replace("[]", " List").replace("Record<string,", "Map "))
+}
+
+/// JSON type of the values of `schema`, as `typeof` tells them apart.
+fn json_kind(schema: &SchemaObject, types: &[ResolvedType]) -> Option<&'static str> {
+    let primitive = match &schema.reference {
+        Some(reference) => {
+            let name = ref_to_name(reference).ok()?;
+            let component = types.iter().find(|t| t.name == name)?;
+            match map_primitive(&component.schema) {
+                None if !component.fields.is_empty() => Some(InstanceType::Object),
+                primitive => primitive,
+            }
+        }
+        None => map_primitive(schema),
+    };
+    match primitive? {
+        InstanceType::String => Some("string"),
+        InstanceType::Integer | InstanceType::Number => Some("number"),
+        InstanceType::Boolean => Some("boolean"),
+        InstanceType::Array => Some("array"),
+        InstanceType::Object => Some("object"),
+        InstanceType::Null => None,
+    }
+}
+
+/// The distinct unions referenced anywhere in `types`, with the components
+/// their variants reference.
+pub fn unions(types: &[ResolvedType], ctx: &LanguageContext) -> (Vec<TsUnion>, Vec<String>) {
+    let mut unions: Vec<TsUnion> = Vec::new();
+    let mut imports: BTreeSet<String> = BTreeSet::new();
+    let mut visit = |schema: &SchemaObject| {
+        if ctx.type_override(schema).is_some() {
+            return;
+        }
+        let Some(options) = union_options(schema) else {
+            return;
+        };
+        let tys: Vec<String> = options.iter().map(|s| map_type(s, ctx)).collect();
+        let parts: Vec<String> = tys.iter().map(|ty| type_part(ty)).collect();
+        let name = parts.join("Or");
+        if unions.iter().any(|u| u.name == name) {
+            return;
+        }
+        visit_schemas(schema, &mut |s| imports.extend(map_reference(s, ctx)));
+        let mut names: Vec<String> = Vec::new();
+        for (position, part) in parts.into_iter().enumerate() {
+            match names.contains(&part) {
+                true => names.push(format!("{}{}", part, position + 1)),
+                false => names.push(part),
+            }
+        }
+        let tags = union_tags(schema, &options, types);
+        let kinds: Vec<Option<&str>> = options.iter().map(|s| json_kind(s, types)).collect();
+        let distinct: BTreeSet<&str> = kinds.iter().flatten().copied().collect();
+        let by_kind = distinct.len() == options.len();
+        let tests: Vec<String> = match &tags {
+            Some((property, tags)) => tags
+                .iter()
+                .map(|tag| format!("value[{:?}] === {:?}", property, tag))
+                .collect(),
+            None => kinds
+                .iter()
+                .map(|kind| match kind.unwrap_or_default() {
+                    "array" => "Array.isArray(value)".to_string(),
+                    "object" => {
+                        "typeof value === \"object\" && value !== null && !Array.isArray(value)"
+                            .to_string()
+                    }
+                    kind => format!("typeof value === {:?}", kind),
+                })
+                .collect(),
+        };
+        let keys = tags.as_ref().map(|(_, tags)| tags).unwrap_or(&names);
+        let variants = keys
+            .iter()
+            .zip(&tys)
+            .zip(tests)
+            .map(|((key, ty), test)| TsVariant {
+                key: format!("{:?}", key),
+                ty: ty.clone(),
+                test,
+            })
+            .collect();
+        unions.push(TsUnion {
+            name,
+            ty: tys.join(" | "),
+            variants,
+            dispatch: tags.is_some() || by_kind,
+        });
+    };
+    for ty in types {
+        visit_schemas(&ty.schema, &mut visit);
+        for field in &ty.fields {
+            visit_schemas(&field.schema, &mut visit);
+        }
+    }
+    unions.sort_by(|a, b| a.name.cmp(&b.name));
+    (unions, imports.into_iter().collect())
+}
@@ -0,0 +1,355 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::mapper;
+use crate::openrpc::ParamStructure;
+use crate::resolver::ref_to_name;
+
+/// Spec facts exposed to generated code as constants, for tooling that
+/// builds JSON-RPC envelopes without parsing the spec at runtime.
+#[derive(Debug, Serialize)]
+pub struct Metadata {
+    pub title: Option<String>,
+    pub version: Option<String>,
+    /// Name of the license in `info.license`.
+    pub license: Option<String>,
+    /// URLs of the declared `servers`.
+    pub servers: Vec<String>,
+    pub methods: Vec<MethodMetadata>,
+    /// Errors declared by the methods, once per code.
+    pub errors: Vec<ErrorMetadata>,
+    /// The spec document itself, when it is embedded in the bindings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec: Option<String>,
+}
+
+impl Metadata {
+    pub fn title_or_default(&self) -> &str {
+        self.title.as_deref().unwrap_or("TRP")
+    }
+
+    /// The embedded spec as a double quoted string literal, valid in
+    /// TypeScript, Python and Go alike.
+    pub fn quoted_spec(&self) -> Option<String> {
+        self.spec
+            .as_ref()
+            .map(|spec| serde_json::to_string(spec).unwrap_or_default())
+    }
+
+    pub fn retryable_errors(&self) -> Vec<&ErrorMetadata> {
+        self.errors.iter().filter(|e| e.retryable).collect()
+    }
+
+    /// Errors whose `data` refers to a component.
+    pub fn typed_errors(&self) -> Vec<&ErrorMetadata> {
+        self.errors
+            .iter()
+            .filter(|e| e.data_type.is_some())
+            .collect()
+    }
+
+    /// The embedded spec as a Rust raw string literal.
+    pub fn rust_spec(&self) -> Option<String> {
+        self.spec.as_deref().map(mapper::rust::raw_string)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MethodMetadata {
+    /// Wire name, e.g. `trp.resolve`.
+    pub name: String,
+    pub summary: Option<String>,
+    /// Param names in declared order.
+    pub params: Vec<String>,
+    /// Component describing the whole params object, when the method takes
+    /// a single `$ref` param.
+    pub params_type: Option<String>,
+    /// How calls send the params: the params object as is (by name), or as
+    /// the only member of an array (by position). Clients of methods taking
+    /// either send them by name.
+    pub param_structure: ParamStructure,
+    /// Component the result refers to.
+    pub result_type: Option<String>,
+    /// Codes of the errors the method declares, in declared order.
+    pub error_codes: Vec<i64>,
+    /// The example pairings of the method.
+    pub examples: Vec<MethodExample>,
+    /// Whether clients key each call with an idempotency key, so that
+    /// servers can tell retries apart from new calls, as declared by
+    /// `x-idempotency-key`.
+    pub idempotency_key: bool,
+    /// The notifications the server pushes to callers of the method, for
+    /// methods opening a subscription, as declared by `x-subscription`.
+    pub subscription: Option<Subscription>,
+}
+
+/// Notifications pushed to the callers of a subscription method, each
+/// carrying the subscription id the method returned and an event.
+#[derive(Debug, Serialize)]
+pub struct Subscription {
+    /// Wire name of the notifications, e.g. `trp.txStatusChanged`.
+    pub notification: String,
+    /// Component the events refer to.
+    pub event_type: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MethodExample {
+    pub name: String,
+    /// The params object, keyed by param name unless the method takes a
+    /// single param describing the whole object.
+    pub params: Value,
+    pub result: Option<Value>,
+}
+
+impl MethodExample {
+    /// The params as indented JSON.
+    pub fn params_json(&self) -> String {
+        serde_json::to_string_pretty(&self.params).unwrap_or_default()
+    }
+}
+
+impl MethodMetadata {
+    /// Whether clients send the params as an array.
+    pub fn by_position(&self) -> bool {
+        self.param_structure == ParamStructure::ByPosition
+    }
+
+    /// `TRP_RESOLVE` for `trp.resolve`.
+    pub fn screaming_name(&self) -> String {
+        mapper::snake_case(&self.name).to_uppercase()
+    }
+
+    /// `TrpResolve` for `trp.resolve`.
+    pub fn pascal_name(&self) -> String {
+        mapper::sanitize_identifier(&self.name)
+    }
+
+    /// `trpResolve` for `trp.resolve`.
+    pub fn camel_name(&self) -> String {
+        let pascal = self.pascal_name();
+        let mut chars = pascal.chars();
+        chars
+            .next()
+            .map(|first| first.to_lowercase().chain(chars).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorMetadata {
+    pub code: i64,
+    pub message: String,
+    /// Component describing the error's `data`.
+    pub data_type: Option<String>,
+    /// Whether a call failing with this error may succeed when retried, as
+    /// declared by `x-retryable`.
+    pub retryable: bool,
+}
+
+impl ErrorMetadata {
+    /// `UNSUPPORTED_TIR` for `Unsupported TIR`.
+    pub fn screaming_name(&self) -> String {
+        mapper::snake_case(&self.message).to_uppercase()
+    }
+
+    /// The message as a double quoted string literal.
+    pub fn quoted_message(&self) -> String {
+        serde_json::to_string(&self.message).unwrap_or_default()
+    }
+
+    /// `UnsupportedTir` for `Unsupported TIR`.
+    pub fn pascal_name(&self) -> String {
+        mapper::sanitize_identifier(&mapper::snake_case(&self.message))
+    }
+}
+
+/// Collects the metadata from the raw spec, whose key order is the declared
+/// order (the typed model sorts object properties).
+pub fn collect(raw: &Value) -> Metadata {
+    let info = |pointer: &str| {
+        raw.pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    let title = info("/info/title");
+    let version = info("/info/version");
+    let license = info("/info/license/name");
+    let servers = raw
+        .get("servers")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|server| Some(server.get("url")?.as_str()?.to_string()))
+        .collect();
+    let methods = raw
+        .get("methods")
+        .and_then(Value::as_array)
+        .map(|methods| {
+            methods
+                .iter()
+                .filter_map(|method| {
+                    let params = method.get("params");
+                    Some(MethodMetadata {
+                        name: method.get("name")?.as_str()?.to_string(),
+                        summary: method
+                            .get("summary")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                        params: param_names(raw, params),
+                        params_type: params.filter(|p| p.is_object()).and_then(schema_ref),
+                        param_structure: method
+                            .get("paramStructure")
+                            .and_then(|s| serde_json::from_value(s.clone()).ok())
+                            .unwrap_or_default(),
+                        result_type: method.get("result").and_then(schema_ref),
+                        error_codes: method
+                            .get("errors")
+                            .and_then(Value::as_array)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|error| error.get("code")?.as_i64())
+                            .collect(),
+                        examples: examples(method),
+                        idempotency_key: method.get("x-idempotency-key").and_then(Value::as_bool)
+                            == Some(true),
+                        subscription: method.get("x-subscription").and_then(|subscription| {
+                            Some(Subscription {
+                                notification: subscription
+                                    .get("notification")?
+                                    .as_str()?
+                                    .to_string(),
+                                event_type: schema_ref(subscription),
+                            })
+                        }),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Metadata {
+        title,
+        version,
+        license,
+        servers,
+        methods,
+        errors: errors(raw),
+        spec: None,
+    }
+}
+
+fn errors(raw: &Value) -> Vec<ErrorMetadata> {
+    let mut errors: Vec<ErrorMetadata> = Vec::new();
+    let declared = raw
+        .get("methods")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|method| method.get("errors")?.as_array())
+        .flatten();
+    for error in declared {
+        let Some(code) = error.get("code").and_then(Value::as_i64) else {
+            continue;
+        };
+        if errors.iter().any(|e| e.code == code) {
+            continue;
+        }
+        errors.push(ErrorMetadata {
+            code,
+            message: error
+                .get("message")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Error {}", code)),
+            data_type: error
+                .get("data")
+                .and_then(|data| data.get("$ref")?.as_str())
+                .and_then(|reference| ref_to_name(reference).ok()),
+            retryable: error.get("x-retryable").and_then(Value::as_bool) == Some(true),
+        });
+    }
+    errors
+}
+
+// A list holds one descriptor per param. A single descriptor describes the
+// whole params object, so its properties are the param names.
+fn param_names(raw: &Value, params: Option<&Value>) -> Vec<String> {
+    match params {
+        Some(Value::Array(list)) => list.iter().filter_map(descriptor_name).collect(),
+        Some(descriptor @ Value::Object(_)) => {
+            let properties = descriptor
+                .get("schema")
+                .map(|schema| resolve(raw, schema))
+                .and_then(|schema| schema.get("properties"))
+                .and_then(Value::as_object);
+            match properties {
+                Some(properties) => properties.keys().cloned().collect(),
+                None => descriptor_name(descriptor).into_iter().collect(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn descriptor_name(descriptor: &Value) -> Option<String> {
+    descriptor
+        .get("name")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn examples(method: &Value) -> Vec<MethodExample> {
+    let by_name = !method.get("params").is_some_and(Value::is_object);
+    method
+        .get("examples")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|pairing| {
+            let values = pairing.get("params")?.as_array()?;
+            let params = if by_name {
+                Value::Object(
+                    values
+                        .iter()
+                        .filter_map(|param| {
+                            Some((
+                                param.get("name")?.as_str()?.to_string(),
+                                param.get("value")?.clone(),
+                            ))
+                        })
+                        .collect(),
+                )
+            } else {
+                values.first()?.get("value")?.clone()
+            };
+            Some(MethodExample {
+                name: pairing
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                params,
+                result: pairing.get("result").and_then(|r| r.get("value")).cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Component a content descriptor's schema refers to.
+fn schema_ref(descriptor: &Value) -> Option<String> {
+    let reference = descriptor.get("schema")?.get("$ref")?.as_str()?;
+    ref_to_name(reference).ok()
+}
+
+fn resolve<'a>(raw: &'a Value, schema: &'a Value) -> &'a Value {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| {
+            raw.get("components")?
+                .get("schemas")?
+                .get(ref_to_name(reference).ok()?.as_str())
+        })
+        .unwrap_or(schema)
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use schemars::schema::Schema;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::bundle;
+
+#[derive(Deserialize, Debug)]
+pub struct OpenRpc {
+    #[allow(dead_code)]
+    pub openrpc: String,
+    pub info: Option<Info>,
+    pub methods: Vec<Method>,
+    pub components: Option<Components>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Info {
+    #[allow(dead_code)]
+    pub title: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Method {
+    pub name: String,
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub deprecated: bool,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    #[serde(default, rename = "paramStructure")]
+    pub param_structure: ParamStructure,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub params: Vec<ContentDescriptor>,
+    pub result: Option<ContentDescriptor>,
+    #[serde(default)]
+    pub errors: Vec<ErrorObject>,
+    #[serde(default)]
+    pub examples: Vec<ExamplePairing>,
+}
+
+/// How a method expects its params to be sent: as an object keyed by param
+/// name, as a positional array, or either way (the OpenRPC default).
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ParamStructure {
+    ByName,
+    ByPosition,
+    #[default]
+    Either,
+}
+
+impl ParamStructure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParamStructure::ByName => "by-name",
+            ParamStructure::ByPosition => "by-position",
+            ParamStructure::Either => "either",
+        }
+    }
+
+    /// `ByName`, the variant's name in generated code.
+    pub fn pascal_name(&self) -> &'static str {
+        match self {
+            ParamStructure::ByName => "ByName",
+            ParamStructure::ByPosition => "ByPosition",
+            ParamStructure::Either => "Either",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Tag {
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ErrorObject {
+    pub code: i64,
+    #[serde(default)]
+    pub message: String,
+    /// TRP uses `data` to describe the shape of the error payload, usually as a
+    /// `$ref` into `components.schemas`.
+    pub data: Option<Value>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ContentDescriptor {
+    pub name: String,
+    pub schema: Schema,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ExamplePairing {
+    pub name: String,
+    #[serde(default)]
+    pub params: Vec<Example>,
+    pub result: Option<Example>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Example {
+    pub name: String,
+    pub value: Value,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Components {
+    pub schemas: Option<HashMap<String, Schema>>,
+}
+
+impl OpenRpc {
+    pub fn version(&self) -> Option<&str> {
+        self.info.as_ref().and_then(|i| i.version.as_deref())
+    }
+
+    pub fn schemas(&self) -> Option<&HashMap<String, Schema>> {
+        self.components.as_ref().and_then(|c| c.schemas.as_ref())
+    }
+}
+
+/// Loads a spec from a file, or from a directory holding a split spec.
+pub fn load(path: &Path) -> Result<OpenRpc> {
+    parse(&read(path)?, &path.display().to_string())
+}
+
+/// The spec document at `path`, bundling a split spec when `path` is a
+/// directory.
+pub fn read(path: &Path) -> Result<String> {
+    if path.is_dir() {
+        let spec = bundle::merge(path)?;
+        return serde_json::to_string_pretty(&spec).context("failed to serialize spec");
+    }
+    fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+/// Parses a spec from memory; `origin` only serves error reporting.
+pub fn parse(data: &str, origin: &str) -> Result<OpenRpc> {
+    serde_json::from_str(data)
+        .with_context(|| format!("failed to parse OpenRPC spec from {}", origin))
+}
+
+/// Loads the spec as an untyped JSON document, preserving key order so that
+/// commands editing the spec in place produce minimal diffs.
+pub fn load_raw(path: &Path) -> Result<Value> {
+    if path.is_dir() {
+        return bundle::merge(path);
+    }
+    let data =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&data).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+/// Saves the spec to `path`, split over it if it's the directory of a
+/// split spec.
+pub fn save_raw(path: &Path, spec: &Value) -> Result<()> {
+    if path.is_dir() {
+        return bundle::write(path, spec);
+    }
+    let data = serde_json::to_string_pretty(spec).context("failed to serialize spec")?;
+    fs::write(path, data).with_context(|| format!("failed to write {}", path.display()))
+}
+
+// The TRP spec declares `params` as a single content descriptor describing the
+// whole params object, while OpenRPC proper uses a list. Accept both.
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<ContentDescriptor>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(Box<ContentDescriptor>),
+        Many(Vec<ContentDescriptor>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(descriptor) => vec![*descriptor],
+        OneOrMany::Many(list) => list,
+    })
+}
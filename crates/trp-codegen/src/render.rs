@@ -0,0 +1,1871 @@
+use anyhow::{Context, Result};
+use askama::Template;
+use schemars::schema::Schema;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error_codes::{self, ErrorClass, ReservedCode};
+use crate::mapper::{self, LanguageContext};
+use crate::metadata::{Metadata, MethodExample, MethodMetadata};
+use crate::resolver::{Constraint, ResolvedField, ResolvedType};
+use crate::split::Module;
+use crate::{LanguageConfig, WasmCrate};
+
+/// Renders every file generated for `lang`, keyed by path relative to the
+/// language's output directory. Without `modules` all types go into a
+/// single file. Every language also gets constants and errors files and a
+/// README from `meta`, and Rust, TypeScript, Python and Go a `utxo` file of
+/// UTxO reference helpers and an `address` file of address helpers, along
+/// with a `tir` file checking TIR programs for specs with a `TirInfo`. Rust
+/// also gets a `fingerprint` file hashing requests as canonical JSON, and a
+/// `hex` file the others share.
+pub(crate) fn render_language(
+    lang: &str,
+    types: &[ResolvedType],
+    modules: Option<&[Module]>,
+    ctx: &LanguageContext,
+    meta: &Metadata,
+    config: &LanguageConfig,
+    previous: Option<&Previous>,
+) -> Result<Vec<(String, String)>> {
+    let strict = config.strict;
+    let mut files = Vec::new();
+    match lang {
+        "ts" | "typescript" | "deno" => {
+            // Deno resolves relative imports by their full file name and
+            // publishes through a JSR manifest.
+            let deno = lang == "deno";
+            match modules {
+                None => files.push((
+                    "types.ts".to_string(),
+                    render_template(TsTemplate {
+                        types,
+                        ctx,
+                        imports: &[],
+                        exports: &[],
+                        deno,
+                    })?,
+                )),
+                Some(modules) => {
+                    for module in modules {
+                        files.push((
+                            format!("{}.ts", module.name),
+                            render_template(TsTemplate {
+                                types: &module.types,
+                                ctx,
+                                imports: &module.imports,
+                                exports: &[],
+                                deno,
+                            })?,
+                        ));
+                    }
+                    let names: Vec<String> = modules.iter().map(|m| m.name.clone()).collect();
+                    files.push((
+                        "types.ts".to_string(),
+                        render_template(TsTemplate {
+                            types: &[],
+                            ctx,
+                            imports: &[],
+                            exports: &names,
+                            deno,
+                        })?,
+                    ));
+                }
+            }
+            files.push((
+                "schemas.ts".to_string(),
+                render_template(TsSchemasTemplate {
+                    types,
+                    ctx,
+                    strict,
+                    deno,
+                    address: mapper::uses_address(types, ctx),
+                })?,
+            ));
+            files.push((
+                "constants.ts".to_string(),
+                render_template(TsConstantsTemplate { meta, deno })?,
+            ));
+            files.push((
+                "errors.ts".to_string(),
+                render_template(TsErrorsTemplate { meta, ctx, deno })?,
+            ));
+            files.push((
+                "error_codes.ts".to_string(),
+                render_template(TsErrorCodesTemplate {
+                    meta,
+                    classes: &ErrorClass::ALL,
+                    reserved: error_codes::RESERVED,
+                    deno,
+                })?,
+            ));
+            files.push((
+                "README.md".to_string(),
+                render_template(TsReadmeTemplate {
+                    meta,
+                    deno,
+                    react: config.ts_react && !deno,
+                    tir: mapper::tir(types, ctx).is_some(),
+                    union: mapper::ts::unions(types, ctx)
+                        .0
+                        .first()
+                        .map(|u| u.name.clone()),
+                })?,
+            ));
+            files.push((
+                "client.ts".to_string(),
+                render_template(TsClientTemplate { meta, ctx, deno })?,
+            ));
+            files.push((
+                "mock.ts".to_string(),
+                render_template(TsMockTemplate { meta, ctx, deno })?,
+            ));
+            let react = config.ts_react && !deno;
+            if react {
+                files.push((
+                    "react.ts".to_string(),
+                    render_template(TsReactTemplate { meta, ctx, deno })?,
+                ));
+            }
+            files.push((
+                "utxo.ts".to_string(),
+                render_template(TsUtxoTemplate { deno })?,
+            ));
+            files.push((
+                "address.ts".to_string(),
+                render_template(TsAddressTemplate { deno })?,
+            ));
+            let tir = mapper::tir(types, ctx);
+            if let Some(tir) = &tir {
+                files.push((
+                    "tir.ts".to_string(),
+                    render_template(TsTirTemplate { tir, deno })?,
+                ));
+            }
+            let (unions, imports) = mapper::ts::unions(types, ctx);
+            if !unions.is_empty() {
+                files.push((
+                    "unions.ts".to_string(),
+                    render_template(TsUnionsTemplate {
+                        unions: &unions,
+                        imports: &imports,
+                        ctx,
+                        deno,
+                    })?,
+                ));
+            }
+            let manifests = match deno {
+                true => [("deno.json", true), ("jsr.json", false)]
+                    .map(|(path, imports)| {
+                        let manifest = mapper::ts::jsr_manifest(
+                            meta,
+                            imports,
+                            tir.is_some(),
+                            !unions.is_empty(),
+                        );
+                        (path, manifest)
+                    })
+                    .to_vec(),
+                false => vec![(
+                    "package.json",
+                    mapper::ts::npm_manifest(meta, tir.is_some(), !unions.is_empty(), react),
+                )],
+            };
+            for (path, manifest) in manifests {
+                let contents = serde_json::to_string_pretty(&manifest)
+                    .with_context(|| format!("failed to serialize {}", path))?;
+                files.push((path.to_string(), format!("{}\n", contents)));
+            }
+        }
+        "python" => {
+            match modules {
+                None => {
+                    files.push((
+                        "types.py".to_string(),
+                        render_template(PythonTemplate {
+                            types,
+                            ctx,
+                            strict,
+                            imports: &[],
+                            exports: &[],
+                            address: mapper::uses_address(types, ctx).then_some(".address"),
+                        })?,
+                    ));
+                    files.push((
+                        "types.pyi".to_string(),
+                        render_template(PythonStubTemplate {
+                            types,
+                            ctx,
+                            imports: &[],
+                            exports: &[],
+                        })?,
+                    ));
+                }
+                Some(modules) => {
+                    for module in modules {
+                        files.push((
+                            format!("types/{}.py", module.name),
+                            render_template(PythonTemplate {
+                                types: &module.types,
+                                ctx,
+                                strict,
+                                imports: &module.imports,
+                                exports: &[],
+                                address: mapper::uses_address(&module.types, ctx)
+                                    .then_some("..address"),
+                            })?,
+                        ));
+                        files.push((
+                            format!("types/{}.pyi", module.name),
+                            render_template(PythonStubTemplate {
+                                types: &module.types,
+                                ctx,
+                                imports: &module.imports,
+                                exports: &[],
+                            })?,
+                        ));
+                    }
+                    let exports: Vec<(String, Vec<String>)> = modules
+                        .iter()
+                        .map(|m| {
+                            let names = m.types.iter().flat_map(|t| {
+                                let enums = t.python_enums(ctx).into_iter().map(|e| e.name);
+                                enums.chain([t.name.clone()])
+                            });
+                            (m.name.clone(), names.collect())
+                        })
+                        .collect();
+                    files.push((
+                        "types/__init__.py".to_string(),
+                        render_template(PythonTemplate {
+                            types: &[],
+                            ctx,
+                            strict,
+                            imports: &[],
+                            exports: &exports,
+                            address: None,
+                        })?,
+                    ));
+                    files.push((
+                        "types/__init__.pyi".to_string(),
+                        render_template(PythonStubTemplate {
+                            types: &[],
+                            ctx,
+                            imports: &[],
+                            exports: &exports,
+                        })?,
+                    ));
+                }
+            }
+            files.push((
+                "constants.py".to_string(),
+                render_template(PythonConstantsTemplate { meta })?,
+            ));
+            files.push((
+                "constants.pyi".to_string(),
+                render_template(PythonConstantsStubTemplate { meta })?,
+            ));
+            files.push((
+                "errors.py".to_string(),
+                render_template(PythonErrorsTemplate { meta, ctx })?,
+            ));
+            files.push((
+                "errors.pyi".to_string(),
+                render_template(PythonErrorsStubTemplate { meta, ctx })?,
+            ));
+            files.push((
+                "error_codes.py".to_string(),
+                render_template(PythonErrorCodesTemplate {
+                    meta,
+                    classes: &ErrorClass::ALL,
+                    reserved: error_codes::RESERVED,
+                })?,
+            ));
+            files.push((
+                "error_codes.pyi".to_string(),
+                render_template(PythonErrorCodesStubTemplate {
+                    reserved: error_codes::RESERVED,
+                })?,
+            ));
+            files.push(("utxo.py".to_string(), render_template(PythonUtxoTemplate)?));
+            files.push((
+                "address.py".to_string(),
+                render_template(PythonAddressTemplate)?,
+            ));
+            if let Some(tir) = &mapper::tir(types, ctx) {
+                files.push((
+                    "tir.py".to_string(),
+                    render_template(PythonTirTemplate { tir })?,
+                ));
+            }
+            files.push((
+                "__init__.py".to_string(),
+                render_template(PythonPackageTemplate {
+                    tir: mapper::tir(types, ctx).is_some(),
+                })?,
+            ));
+            // PEP 561 marker telling type checkers the package is typed.
+            files.push(("py.typed".to_string(), String::new()));
+            files.push((
+                "pyproject.toml".to_string(),
+                render_template(PythonProjectTemplate {
+                    meta,
+                    version: mapper::python::pep440(meta.version.as_deref().unwrap_or("0.0.0")),
+                    split: modules.is_some(),
+                })?,
+            ));
+            files.push((
+                "README.md".to_string(),
+                render_template(PythonReadmeTemplate {
+                    meta,
+                    ctx,
+                    tir: mapper::tir(types, ctx).is_some(),
+                })?,
+            ));
+        }
+        "go" => {
+            // Shared declarations stay in types.go; Go needs no imports
+            // between files of the same package.
+            let tuples = mapper::go::collect_tuples(types, ctx);
+            let unions = mapper::go::collect_unions(types, ctx);
+            let shared_types = if modules.is_some() { &[][..] } else { types };
+            files.push((
+                "types.go".to_string(),
+                render_template(GoTemplate {
+                    types: shared_types,
+                    ctx,
+                    tuples: &tuples,
+                    unions: &unions,
+                    imports: &mapper::go::imports(shared_types, &tuples, &unions, ctx),
+                    external: go_external(shared_types, ctx),
+                    shared: true,
+                })?,
+            ));
+            for module in modules.unwrap_or_default() {
+                files.push((
+                    format!("{}.go", module.name),
+                    render_template(GoTemplate {
+                        types: &module.types,
+                        ctx,
+                        tuples: &[],
+                        unions: &[],
+                        imports: &mapper::go::imports(&module.types, &[], &[], ctx),
+                        external: go_external(&module.types, ctx),
+                        shared: false,
+                    })?,
+                ));
+            }
+            files.push((
+                "constants.go".to_string(),
+                render_template(GoConstantsTemplate { meta })?,
+            ));
+            files.push((
+                "errors.go".to_string(),
+                render_template(GoErrorsTemplate { meta, ctx })?,
+            ));
+            files.push((
+                "error_codes.go".to_string(),
+                render_template(GoErrorCodesTemplate {
+                    meta,
+                    classes: &ErrorClass::ALL,
+                    reserved: error_codes::RESERVED,
+                })?,
+            ));
+            files.push((
+                "rpc.go".to_string(),
+                render_template(GoRpcTemplate {
+                    calls: &mapper::go::calls(meta, ctx),
+                    generics: config.go_generics,
+                })?,
+            ));
+            files.push(("utxo.go".to_string(), render_template(GoUtxoTemplate)?));
+            files.push((
+                "address.go".to_string(),
+                render_template(GoAddressTemplate)?,
+            ));
+            if let Some(tir) = &mapper::tir(types, ctx) {
+                files.push((
+                    "tir.go".to_string(),
+                    render_template(GoTirTemplate { tir })?,
+                ));
+            }
+            if let Some(module) = &config.go_module {
+                files.push((
+                    "go.mod".to_string(),
+                    render_template(GoModTemplate {
+                        meta,
+                        module: &mapper::go::module_path(module, meta.version.as_deref()),
+                        go: &mapper::go::go_directive(
+                            config.go_version.as_deref(),
+                            config.go_generics,
+                        )?,
+                    })?,
+                ));
+            }
+            let examples = mapper::go::examples(meta, ctx);
+            if !examples.is_empty() {
+                files.push((
+                    "example_test.go".to_string(),
+                    render_template(GoExampleTestTemplate {
+                        examples: &examples,
+                    })?,
+                ));
+            }
+            files.push((
+                "README.md".to_string(),
+                render_template(GoReadmeTemplate {
+                    meta,
+                    ctx,
+                    module: config
+                        .go_module
+                        .as_deref()
+                        .map(|m| mapper::go::module_path(m, meta.version.as_deref())),
+                    tir: mapper::tir(types, ctx).is_some(),
+                })?,
+            ));
+        }
+        "rust" => {
+            // The previous version's types share the `EmptyObject` of the
+            // current ones, so that fields typed with it convert as is.
+            let empty_object = mapper::rust::uses_empty_object(types, ctx)
+                || previous.is_some_and(|p| mapper::rust::uses_empty_object(&p.types, &p.ctx));
+            let address = mapper::uses_address(types, ctx)
+                || previous.is_some_and(|p| mapper::uses_address(&p.types, &p.ctx));
+            let borrowed = match config.rust_borrowed {
+                true => mapper::rust::borrowed(types, ctx),
+                false => Vec::new(),
+            };
+            let unions = mapper::rust::unions(types, ctx);
+            let mut names: Vec<String> = match modules {
+                None => types.iter().map(|t| ctx.type_name(&t.name)).collect(),
+                Some(modules) => modules
+                    .iter()
+                    .flat_map(|m| &m.types)
+                    .map(|t| ctx.type_name(&t.name))
+                    .collect(),
+            };
+            names.extend(unions.iter().map(|u| u.name.clone()));
+            let versions = previous.map(|p| (p.module.as_str(), p.current.as_str(), &names[..]));
+            if let Some(previous) = previous {
+                let taken = |name: &str| modules.is_some_and(|m| m.iter().any(|m| m.name == name));
+                if taken(&previous.module) || taken(&previous.current) {
+                    anyhow::bail!(
+                        "the {} and {} modules of the spec versions clash with a split module",
+                        previous.module,
+                        previous.current
+                    );
+                }
+                let conversions = mapper::rust::conversions(
+                    &mapper::rust::Version {
+                        types: &previous.types,
+                        ctx: &previous.ctx,
+                        path: "",
+                    },
+                    &mapper::rust::Version {
+                        types,
+                        ctx,
+                        path: "super::",
+                    },
+                );
+                files.push((
+                    format!("types/{}.rs", previous.module),
+                    render_template(RustTemplate {
+                        types: &previous.types,
+                        ctx: &previous.ctx,
+                        strict,
+                        modules: &[],
+                        child: true,
+                        empty_object: false,
+                        address: false,
+                        borrowed: &[],
+                        versions: None,
+                        conversions: &conversions,
+                        // Unions of plain values are the same in both
+                        // versions, and shared.
+                        unions: &mapper::rust::unions(&previous.types, &previous.ctx)
+                            .into_iter()
+                            .filter(|u| u.references || !unions.iter().any(|c| c.name == u.name))
+                            .collect::<Vec<_>>(),
+                        match_all: false,
+                    })?,
+                ));
+            }
+            match modules {
+                None => files.push((
+                    "types.rs".to_string(),
+                    render_template(RustTemplate {
+                        types,
+                        ctx,
+                        strict,
+                        modules: &[],
+                        child: false,
+                        empty_object,
+                        address,
+                        borrowed: &borrowed,
+                        versions,
+                        conversions: &[],
+                        unions: &unions,
+                        match_all: !unions.is_empty(),
+                    })?,
+                )),
+                Some(modules) => {
+                    for module in modules {
+                        files.push((
+                            format!("types/{}.rs", module.name),
+                            render_template(RustTemplate {
+                                types: &module.types,
+                                ctx,
+                                strict,
+                                modules: &[],
+                                child: true,
+                                empty_object: false,
+                                address: false,
+                                borrowed: &[],
+                                versions: None,
+                                conversions: &[],
+                                unions: &[],
+                                match_all: false,
+                            })?,
+                        ));
+                    }
+                    let names: Vec<String> = modules.iter().map(|m| m.name.clone()).collect();
+                    files.push((
+                        "types/mod.rs".to_string(),
+                        render_template(RustTemplate {
+                            types: &[],
+                            ctx,
+                            strict,
+                            modules: &names,
+                            child: false,
+                            empty_object,
+                            address,
+                            borrowed: &borrowed,
+                            versions,
+                            conversions: &[],
+                            unions: &unions,
+                            match_all: !unions.is_empty(),
+                        })?,
+                    ));
+                }
+            }
+            files.push((
+                "constants.rs".to_string(),
+                render_template(RustConstantsTemplate { meta })?,
+            ));
+            files.push((
+                "errors.rs".to_string(),
+                render_template(RustErrorsTemplate { meta, ctx })?,
+            ));
+            files.push((
+                "error_codes.rs".to_string(),
+                render_template(RustErrorCodesTemplate {
+                    meta,
+                    classes: &ErrorClass::ALL,
+                    reserved: error_codes::RESERVED,
+                })?,
+            ));
+            files.push(("utxo.rs".to_string(), render_template(RustUtxoTemplate)?));
+            files.push((
+                "address.rs".to_string(),
+                render_template(RustAddressTemplate)?,
+            ));
+            files.push((
+                "fingerprint.rs".to_string(),
+                render_template(RustFingerprintTemplate)?,
+            ));
+            files.push(("hex.rs".to_string(), render_template(RustHexTemplate)?));
+            let tir = mapper::tir(types, ctx);
+            if let Some(tir) = &tir {
+                files.push((
+                    "tir.rs".to_string(),
+                    render_template(RustTirTemplate { tir })?,
+                ));
+            }
+            files.push((
+                "README.md".to_string(),
+                render_template(RustReadmeTemplate {
+                    crate_name: None,
+                    meta,
+                    ctx,
+                    examples: &[],
+                    backend: false,
+                    watch: false,
+                    tir: tir.is_some(),
+                })?,
+            ));
+        }
+        "elm" => {
+            // Elm modules can't import each other in a cycle, which recursive
+            // components would need once split.
+            if modules.is_some() {
+                anyhow::bail!("elm output can't be split");
+            }
+            files.push((
+                "Trp/Types.elm".to_string(),
+                render_template(ElmTemplate {
+                    types: &mapper::elm::types(types, ctx),
+                })?,
+            ));
+        }
+        "php" => {
+            // PSR-4 autoloading wants a file per class, whatever the split.
+            for ty in types {
+                let class = mapper::php::class(ty, ctx);
+                files.push((
+                    format!("src/{}.php", class.name),
+                    render_template(PhpClassTemplate {
+                        namespace: mapper::php::NAMESPACE,
+                        class: &class,
+                    })?,
+                ));
+            }
+            files.push((
+                "composer.json".to_string(),
+                render_template(PhpComposerTemplate {
+                    meta,
+                    autoload_prefix: &Value::String(format!("{}\\", mapper::php::NAMESPACE))
+                        .to_string(),
+                })?,
+            ));
+        }
+        "scala" => {
+            let binding = mapper::scala::binding(types, ctx);
+            files.push((
+                format!("src/main/scala/{}/Types.scala", mapper::scala::PACKAGE),
+                render_template(ScalaTemplate {
+                    package: mapper::scala::PACKAGE,
+                    binding: &binding,
+                })?,
+            ));
+            files.push((
+                "build.sbt".to_string(),
+                render_template(ScalaBuildTemplate { meta })?,
+            ));
+            files.push((
+                "project/build.properties".to_string(),
+                "sbt.version=1.10.0\n".to_string(),
+            ));
+        }
+        "c" => {
+            files.push((
+                "trp.h".to_string(),
+                render_template(CHeaderTemplate {
+                    structs: &mapper::c::structs(types, ctx),
+                })?,
+            ));
+        }
+        "jsonschema" => {
+            // Validators load schemas by file, so there is one per component
+            // whatever the split.
+            let mut documents = mapper::jsonschema::standalone(types)?;
+            documents.push((
+                mapper::jsonschema::BUNDLE.to_string(),
+                mapper::jsonschema::bundle(types, meta)?,
+            ));
+            for (path, document) in documents {
+                let contents = serde_json::to_string_pretty(&document)
+                    .with_context(|| format!("failed to serialize {}", path))?;
+                files.push((path, format!("{}\n", contents)));
+            }
+        }
+        "openapi" => {
+            let document = mapper::openapi::document(types, meta)?;
+            let contents = serde_json::to_string_pretty(&document)
+                .context("failed to serialize openapi.json")?;
+            files.push(("openapi.json".to_string(), format!("{}\n", contents)));
+        }
+        "wasm" => {
+            // The TypeScript declarations of the types go into the package's
+            // `.d.ts`, where the methods refer to them.
+            let ts = mapper::build_context(types, "ts", &Default::default());
+            let declarations = render_template(TsTemplate {
+                types,
+                ctx: &ts,
+                imports: &[],
+                exports: &[],
+                deno: false,
+            })?;
+            let declarations = declarations
+                .split_once('\n')
+                .map_or("", |(_, rest)| rest)
+                .trim()
+                .replace("\n\n\n", "\n\n");
+            let methods = mapper::rust::wasm_methods(meta, ctx, &ts);
+            let krate = &config.wasm;
+            files.push((
+                "Cargo.toml".to_string(),
+                render_template(WasmCrateTemplate { meta, krate })?,
+            ));
+            files.push((
+                "src/lib.rs".to_string(),
+                render_template(WasmLibTemplate {
+                    meta,
+                    krate,
+                    methods: &methods,
+                    ts_types: mapper::rust::raw_string(&format!("\n{}\n", declarations)),
+                })?,
+            ));
+            files.push((
+                "README.md".to_string(),
+                render_template(WasmReadmeTemplate {
+                    meta,
+                    krate,
+                    methods: &methods,
+                })?,
+            ));
+        }
+        "asyncapi" => {
+            let document = mapper::asyncapi::document(types, meta)?;
+            let contents = serde_json::to_string_pretty(&document)
+                .context("failed to serialize asyncapi.json")?;
+            files.push(("asyncapi.json".to_string(), format!("{}\n", contents)));
+        }
+        "proto" => {
+            let mut numbers = config.proto_numbers.clone();
+            let messages = mapper::proto::messages(types, ctx, &mut numbers);
+            files.push((
+                format!("{}.proto", mapper::proto::PACKAGE),
+                render_template(ProtoTemplate {
+                    package: mapper::proto::PACKAGE,
+                    messages: &messages,
+                })?,
+            ));
+            let numbers = serde_json::to_string_pretty(&numbers)
+                .context("failed to serialize field numbers")?;
+            files.push((
+                mapper::proto::FIELD_NUMBERS.to_string(),
+                format!("{}\n", numbers),
+            ));
+        }
+        "cddl" => {
+            files.push((
+                "trp.cddl".to_string(),
+                render_template(CddlTemplate {
+                    root: mapper::cddl::root(meta, ctx),
+                    rules: &mapper::cddl::rules(types, ctx),
+                })?,
+            ));
+        }
+        "ocaml" => {
+            files.push((
+                "trp.ml".to_string(),
+                render_template(OcamlTemplate {
+                    declarations: &mapper::ocaml::declarations(types, ctx, strict),
+                })?,
+            ));
+            files.push((
+                "dune".to_string(),
+                "(library\n (name trp)\n (public_name trp)\n (libraries yojson)\n (preprocess\n  (pps ppx_deriving_yojson)))\n"
+                    .to_string(),
+            ));
+            files.push((
+                "dune-project".to_string(),
+                render_template(OcamlDuneProjectTemplate { meta })?,
+            ));
+        }
+        _ => anyhow::bail!("unsupported language: {}", lang),
+    }
+    Ok(files)
+}
+
+/// The manifest, root module and README turning the Rust files into a crate
+/// of its own, named `name`, along with programs sending the spec's method
+/// examples to a server and tests round-tripping the types. The crate's
+/// client, server, proxy and proptest support are modules behind cargo
+/// features, the proxy also being a program of its own. TRP specs also get
+/// a `backend` module adapting node logic to the server, those with
+/// `trp.checkStatus` a `watch` module waiting for submitted transactions,
+/// and those with both and a `TirInfo` a `trp` program calling servers from
+/// the command line.
+pub(crate) fn render_rust_crate(
+    name: &str,
+    types: &[ResolvedType],
+    meta: &Metadata,
+    ctx: &LanguageContext,
+) -> Result<Vec<(String, String)>> {
+    let examples = mapper::rust::examples(meta, ctx);
+    let methods = mapper::rust::methods(meta, ctx);
+    let backend = mapper::rust::backend(&methods);
+    let watch = mapper::rust::watch(&methods);
+    let tir = mapper::tir(types, ctx).is_some();
+    let cli = backend.is_some() && watch.is_some() && tir;
+    let mut files = vec![
+        (
+            "README.md".to_string(),
+            render_template(RustReadmeTemplate {
+                crate_name: Some(name),
+                meta,
+                ctx,
+                examples: &examples,
+                backend: backend.is_some(),
+                watch: watch.is_some(),
+                tir,
+            })?,
+        ),
+        (
+            "Cargo.toml".to_string(),
+            render_template(RustCrateTemplate {
+                name,
+                meta,
+                examples: &examples,
+                cli,
+                watch: watch.is_some(),
+            })?,
+        ),
+        (
+            "src/lib.rs".to_string(),
+            render_template(RustLibTemplate {
+                meta,
+                backend: backend.is_some(),
+                watch: watch.is_some(),
+                tir,
+            })?,
+        ),
+        (
+            "src/client.rs".to_string(),
+            render_template(RustClientTemplate {
+                meta,
+                methods: &methods,
+            })?,
+        ),
+        (
+            "src/schema.rs".to_string(),
+            render_template(RustSchemaTemplate {
+                meta,
+                bundle: &schema_bundle(types, meta)?,
+            })?,
+        ),
+        (
+            "src/health.rs".to_string(),
+            render_template(RustHealthTemplate {
+                method: &mapper::rust::health_method(meta),
+            })?,
+        ),
+        (
+            "src/server.rs".to_string(),
+            render_template(RustServerTemplate {
+                meta,
+                methods: &methods,
+            })?,
+        ),
+        (
+            "src/proxy.rs".to_string(),
+            render_template(RustProxyTemplate { methods: &methods })?,
+        ),
+        (
+            "src/bin/proxy.rs".to_string(),
+            render_template(RustProxyBinTemplate { name })?,
+        ),
+        (
+            "src/cbor.rs".to_string(),
+            render_template(RustCborTemplate { name })?,
+        ),
+        (
+            "src/testing.rs".to_string(),
+            render_template(RustTestingTemplate { methods: &methods })?,
+        ),
+        (
+            "src/arbitrary.rs".to_string(),
+            render_template(RustArbitraryTemplate {
+                impls: &mapper::rust::arbitrary(types, ctx),
+                unions: &mapper::rust::unions(types, ctx),
+            })?,
+        ),
+        (
+            "tests/roundtrip.rs".to_string(),
+            render_template(RustRoundTripTemplate {
+                name,
+                types: &mapper::rust::round_trip_tests(types, ctx),
+                tests: &mapper::rust::example_tests(meta, ctx),
+            })?,
+        ),
+    ];
+    if let Some(backend) = &backend {
+        files.push((
+            "src/backend.rs".to_string(),
+            render_template(RustBackendTemplate { name, backend })?,
+        ));
+    }
+    if let Some(watch) = &watch {
+        files.push((
+            "src/watch.rs".to_string(),
+            render_template(RustWatchTemplate { name, watch })?,
+        ));
+    }
+    if cli {
+        files.push((
+            "src/bin/trp.rs".to_string(),
+            render_template(RustTrpBinTemplate { name })?,
+        ));
+    }
+    for example in &examples {
+        files.push((
+            format!("examples/{}.rs", example.name),
+            render_template(RustExampleTemplate { name, example })?,
+        ));
+    }
+    Ok(files)
+}
+
+/// The JSON Schema bundle, to be embedded in a raw string literal.
+fn schema_bundle(types: &[ResolvedType], meta: &Metadata) -> Result<String> {
+    let bundle = serde_json::to_string_pretty(&mapper::jsonschema::bundle(types, meta)?)?;
+    anyhow::ensure!(
+        !bundle.contains("\"##"),
+        "the JSON Schema bundle can't be embedded in a raw string"
+    );
+    Ok(bundle)
+}
+
+fn render_template<T: Template>(template: T) -> Result<String> {
+    template.render().context("failed to render template")
+}
+
+#[derive(Template)]
+#[template(path = "ts/types.askama", escape = "none")]
+struct TsTemplate<'a> {
+    types: &'a [ResolvedType],
+    ctx: &'a LanguageContext,
+    imports: &'a [(String, Vec<String>)],
+    /// Modules re-exported by the index file of a split output.
+    exports: &'a [String],
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/schemas.askama", escape = "none")]
+struct TsSchemasTemplate<'a> {
+    types: &'a [ResolvedType],
+    ctx: &'a LanguageContext,
+    strict: bool,
+    deno: bool,
+    /// Whether fields refine with `isAddress`.
+    address: bool,
+}
+
+#[derive(Template)]
+#[template(path = "python/types.askama", escape = "none")]
+struct PythonTemplate<'a> {
+    types: &'a [ResolvedType],
+    ctx: &'a LanguageContext,
+    strict: bool,
+    imports: &'a [(String, Vec<String>)],
+    /// Types re-exported by the package `__init__` of a split output.
+    exports: &'a [(String, Vec<String>)],
+    /// Module to import `is_address` from, for the fields marked with
+    /// `format: address`.
+    address: Option<&'static str>,
+}
+
+/// `.pyi` stub of the models of [`PythonTemplate`], declaring their fields
+/// and keyword constructor for type checkers.
+#[derive(Template)]
+#[template(path = "python/stub.askama", escape = "none")]
+struct PythonStubTemplate<'a> {
+    types: &'a [ResolvedType],
+    ctx: &'a LanguageContext,
+    imports: &'a [(String, Vec<String>)],
+    exports: &'a [(String, Vec<String>)],
+}
+
+/// The binding's package `__init__.py`, importing its modules.
+#[derive(Template)]
+#[template(path = "python/package.askama", escape = "none")]
+struct PythonPackageTemplate {
+    /// Whether the binding has TIR helpers.
+    tir: bool,
+}
+
+fn go_external<'a>(types: &[ResolvedType], ctx: &'a LanguageContext) -> &'a [String] {
+    match mapper::go::uses_overrides(types, ctx) {
+        true => ctx.external_imports(),
+        false => &[],
+    }
+}
+
+#[derive(Template)]
+#[template(path = "go/types.askama", escape = "none")]
+struct GoTemplate<'a> {
+    types: &'a [ResolvedType],
+    ctx: &'a LanguageContext,
+    tuples: &'a [mapper::go::GoTuple],
+    unions: &'a [mapper::go::GoUnion],
+    imports: &'a [&'static str],
+    /// Configured import specs, for the files using overridden types.
+    external: &'a [String],
+    /// Whether this file holds the declarations shared by the package.
+    shared: bool,
+}
+
+#[derive(Template)]
+#[template(path = "rust/types.askama", escape = "none")]
+struct RustTemplate<'a> {
+    types: &'a [ResolvedType],
+    ctx: &'a LanguageContext,
+    strict: bool,
+    /// Submodules declared and re-exported by the root of a split output.
+    modules: &'a [String],
+    child: bool,
+    /// Whether to declare `EmptyObject`, which fields of any module may use.
+    empty_object: bool,
+    /// Whether the types validate addresses, importing the `address` module
+    /// for the root and its children.
+    address: bool,
+    /// Structs of the `borrowed` module, declared by the root file.
+    borrowed: &'a [mapper::rust::BorrowedStruct],
+    /// Modules of the previous and the current major version, declared by
+    /// the root file, and the types the latter re-exports.
+    versions: Option<(&'a str, &'a str, &'a [String])>,
+    /// Conversions between the previous version's types, rendered in its
+    /// module, and the current ones.
+    conversions: &'a [mapper::rust::Conversion],
+    /// Enums of the unions the types use.
+    unions: &'a [mapper::rust::RustUnion],
+    /// Whether to declare the `match_all!` macro, once for the crate.
+    match_all: bool,
+}
+
+/// The types of an earlier major version of the spec, rendered as a module
+/// of the Rust types next to the current ones.
+pub(crate) struct Previous {
+    /// Modules of the earlier and the current versions, e.g. `v1` and `v2`.
+    pub module: String,
+    pub current: String,
+    pub types: Vec<ResolvedType>,
+    pub ctx: LanguageContext,
+}
+
+#[derive(Template)]
+#[template(path = "ts/constants.askama", escape = "none")]
+struct TsConstantsTemplate<'a> {
+    meta: &'a Metadata,
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/errors.askama", escape = "none")]
+struct TsErrorsTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/error_codes.askama", escape = "none")]
+struct TsErrorCodesTemplate<'a> {
+    meta: &'a Metadata,
+    classes: &'a [ErrorClass],
+    reserved: &'a [ReservedCode],
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/README.md.askama", escape = "none")]
+struct TsReadmeTemplate<'a> {
+    meta: &'a Metadata,
+    deno: bool,
+    /// Whether the binding has React hooks.
+    react: bool,
+    /// Whether the binding has TIR helpers.
+    tir: bool,
+    /// A union of `unions.ts`, if the binding declares it, for the example.
+    union: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "ts/client.askama", escape = "none")]
+struct TsClientTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/react.askama", escape = "none")]
+struct TsReactTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/mock.askama", escape = "none")]
+struct TsMockTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/utxo.askama", escape = "none")]
+struct TsUtxoTemplate {
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/address.askama", escape = "none")]
+struct TsAddressTemplate {
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/tir.askama", escape = "none")]
+struct TsTirTemplate<'a> {
+    tir: &'a mapper::Tir,
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "ts/unions.askama", escape = "none")]
+struct TsUnionsTemplate<'a> {
+    unions: &'a [mapper::ts::TsUnion],
+    /// Components the variants reference.
+    imports: &'a [String],
+    ctx: &'a LanguageContext,
+    deno: bool,
+}
+
+#[derive(Template)]
+#[template(path = "python/constants.askama", escape = "none")]
+struct PythonConstantsTemplate<'a> {
+    meta: &'a Metadata,
+}
+
+#[derive(Template)]
+#[template(path = "python/constants_stub.askama", escape = "none")]
+struct PythonConstantsStubTemplate<'a> {
+    meta: &'a Metadata,
+}
+
+#[derive(Template)]
+#[template(path = "python/error_codes.askama", escape = "none")]
+struct PythonErrorCodesTemplate<'a> {
+    meta: &'a Metadata,
+    classes: &'a [ErrorClass],
+    reserved: &'a [ReservedCode],
+}
+
+#[derive(Template)]
+#[template(path = "python/error_codes_stub.askama", escape = "none")]
+struct PythonErrorCodesStubTemplate<'a> {
+    reserved: &'a [ReservedCode],
+}
+
+#[derive(Template)]
+#[template(path = "python/errors.askama", escape = "none")]
+struct PythonErrorsTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+}
+
+#[derive(Template)]
+#[template(path = "python/errors_stub.askama", escape = "none")]
+struct PythonErrorsStubTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+}
+
+#[derive(Template)]
+#[template(path = "python/utxo.askama", escape = "none")]
+struct PythonUtxoTemplate;
+
+#[derive(Template)]
+#[template(path = "python/address.askama", escape = "none")]
+struct PythonAddressTemplate;
+
+#[derive(Template)]
+#[template(path = "python/tir.askama", escape = "none")]
+struct PythonTirTemplate<'a> {
+    tir: &'a mapper::Tir,
+}
+
+#[derive(Template)]
+#[template(path = "python/pyproject.toml.askama", escape = "none")]
+struct PythonProjectTemplate<'a> {
+    meta: &'a Metadata,
+    /// The spec's version, as PEP 440 spells it.
+    version: String,
+    /// Whether the types are a `types` subpackage rather than a module.
+    split: bool,
+}
+
+#[derive(Template)]
+#[template(path = "python/README.md.askama", escape = "none")]
+struct PythonReadmeTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+    /// Whether the binding has TIR helpers.
+    tir: bool,
+}
+
+#[derive(Template)]
+#[template(path = "go/constants.askama", escape = "none")]
+struct GoConstantsTemplate<'a> {
+    meta: &'a Metadata,
+}
+
+#[derive(Template)]
+#[template(path = "go/errors.askama", escape = "none")]
+struct GoErrorsTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+}
+
+#[derive(Template)]
+#[template(path = "go/error_codes.askama", escape = "none")]
+struct GoErrorCodesTemplate<'a> {
+    meta: &'a Metadata,
+    classes: &'a [ErrorClass],
+    reserved: &'a [ReservedCode],
+}
+
+/// JSON-RPC envelopes and a call function per method.
+#[derive(Template)]
+#[template(path = "go/rpc.askama", escape = "none")]
+struct GoRpcTemplate<'a> {
+    calls: &'a [mapper::go::GoCall],
+    /// Whether the envelopes are generic types, or hold raw JSON for
+    /// toolchains older than Go 1.18.
+    generics: bool,
+}
+
+#[derive(Template)]
+#[template(path = "go/utxo.askama", escape = "none")]
+struct GoUtxoTemplate;
+
+#[derive(Template)]
+#[template(path = "go/address.askama", escape = "none")]
+struct GoAddressTemplate;
+
+#[derive(Template)]
+#[template(path = "go/tir.askama", escape = "none")]
+struct GoTirTemplate<'a> {
+    tir: &'a mapper::Tir,
+}
+
+/// Module file of the Go binding, for publishing it as a module of its own.
+#[derive(Template)]
+#[template(path = "go/go.mod.askama", escape = "none")]
+struct GoModTemplate<'a> {
+    meta: &'a Metadata,
+    module: &'a str,
+    /// Version of the `go` directive.
+    go: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "go/example_test.askama", escape = "none")]
+struct GoExampleTestTemplate<'a> {
+    examples: &'a [mapper::go::GoExample],
+}
+
+#[derive(Template)]
+#[template(path = "go/README.md.askama", escape = "none")]
+struct GoReadmeTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+    /// Module path of the generated `go.mod`, if any.
+    module: Option<String>,
+    /// Whether the binding has TIR helpers.
+    tir: bool,
+}
+
+#[derive(Template)]
+#[template(path = "rust/constants.askama", escape = "none")]
+struct RustConstantsTemplate<'a> {
+    meta: &'a Metadata,
+}
+
+#[derive(Template)]
+#[template(path = "rust/errors.askama", escape = "none")]
+struct RustErrorsTemplate<'a> {
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+}
+
+#[derive(Template)]
+#[template(path = "rust/error_codes.askama", escape = "none")]
+struct RustErrorCodesTemplate<'a> {
+    meta: &'a Metadata,
+    classes: &'a [ErrorClass],
+    reserved: &'a [ReservedCode],
+}
+
+#[derive(Template)]
+#[template(path = "rust/utxo.askama", escape = "none")]
+struct RustUtxoTemplate;
+
+#[derive(Template)]
+#[template(path = "rust/address.askama", escape = "none")]
+struct RustAddressTemplate;
+
+#[derive(Template)]
+#[template(path = "rust/fingerprint.askama", escape = "none")]
+struct RustFingerprintTemplate;
+
+#[derive(Template)]
+#[template(path = "rust/hex.askama", escape = "none")]
+struct RustHexTemplate;
+
+#[derive(Template)]
+#[template(path = "rust/tir.askama", escape = "none")]
+struct RustTirTemplate<'a> {
+    tir: &'a mapper::Tir,
+}
+
+#[derive(Template)]
+#[template(path = "rust/README.md.askama", escape = "none")]
+struct RustReadmeTemplate<'a> {
+    /// Name of the crate, when the files form one.
+    crate_name: Option<&'a str>,
+    meta: &'a Metadata,
+    ctx: &'a LanguageContext,
+    examples: &'a [mapper::rust::RustExample],
+    /// Whether the crate has a `backend` module.
+    backend: bool,
+    /// Whether the crate has a `watch` module.
+    watch: bool,
+    /// Whether the files have a `tir` module.
+    tir: bool,
+}
+
+#[derive(Template)]
+#[template(path = "rust/Cargo.toml.askama", escape = "none")]
+struct RustCrateTemplate<'a> {
+    name: &'a str,
+    meta: &'a Metadata,
+    /// Examples of the crate, which call the server with the blocking
+    /// client.
+    examples: &'a [mapper::rust::RustExample],
+    /// Whether the crate has the `trp` program.
+    cli: bool,
+    /// Whether the crate has a `watch` module, which sleeps with tokio.
+    watch: bool,
+}
+
+#[derive(Template)]
+#[template(path = "rust/example.askama", escape = "none")]
+struct RustExampleTemplate<'a> {
+    name: &'a str,
+    example: &'a mapper::rust::RustExample,
+}
+
+#[derive(Template)]
+#[template(path = "rust/lib.askama", escape = "none")]
+struct RustLibTemplate<'a> {
+    meta: &'a Metadata,
+    /// Whether the spec has the TRP methods of the `backend` module.
+    backend: bool,
+    /// Whether the spec has the TRP methods of the `watch` module.
+    watch: bool,
+    /// Whether the spec has the `TirInfo` of the `tir` module.
+    tir: bool,
+}
+
+#[derive(Template)]
+#[template(path = "rust/roundtrip.askama", escape = "none")]
+struct RustRoundTripTemplate<'a> {
+    name: &'a str,
+    /// Test name and type of each type's round trip.
+    types: &'a [(String, String)],
+    tests: &'a [mapper::rust::RustExampleTest],
+}
+
+#[derive(Template)]
+#[template(path = "rust/schema.askama", escape = "none")]
+struct RustSchemaTemplate<'a> {
+    meta: &'a Metadata,
+    /// The JSON Schema bundle of the components, pretty-printed.
+    bundle: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "rust/testing.askama", escape = "none")]
+struct RustTestingTemplate<'a> {
+    methods: &'a [mapper::rust::RustMethod],
+}
+
+#[derive(Template)]
+#[template(path = "rust/client.askama", escape = "none")]
+struct RustClientTemplate<'a> {
+    meta: &'a Metadata,
+    methods: &'a [mapper::rust::RustMethod],
+}
+
+#[derive(Template)]
+#[template(path = "rust/server.askama", escape = "none")]
+struct RustServerTemplate<'a> {
+    meta: &'a Metadata,
+    methods: &'a [mapper::rust::RustMethod],
+}
+
+#[derive(Template)]
+#[template(path = "rust/backend.askama", escape = "none")]
+struct RustBackendTemplate<'a> {
+    name: &'a str,
+    backend: &'a mapper::rust::Backend,
+}
+
+#[derive(Template)]
+#[template(path = "rust/proxy.askama", escape = "none")]
+struct RustProxyTemplate<'a> {
+    methods: &'a [mapper::rust::RustMethod],
+}
+
+#[derive(Template)]
+#[template(path = "rust/proxy_bin.askama", escape = "none")]
+struct RustProxyBinTemplate<'a> {
+    name: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "rust/trp_bin.askama", escape = "none")]
+struct RustTrpBinTemplate<'a> {
+    name: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "rust/cbor.askama", escape = "none")]
+struct RustCborTemplate<'a> {
+    name: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "rust/watch.askama", escape = "none")]
+struct RustWatchTemplate<'a> {
+    name: &'a str,
+    watch: &'a mapper::rust::Watch,
+}
+
+#[derive(Template)]
+#[template(path = "rust/health.askama", escape = "none")]
+struct RustHealthTemplate<'a> {
+    method: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "rust/arbitrary.askama", escape = "none")]
+struct RustArbitraryTemplate<'a> {
+    impls: &'a [mapper::rust::ArbitraryImpl],
+    unions: &'a [mapper::rust::RustUnion],
+}
+
+#[derive(Template)]
+#[template(path = "elm/types.askama", escape = "none")]
+struct ElmTemplate<'a> {
+    types: &'a [mapper::elm::ElmType],
+}
+
+#[derive(Template)]
+#[template(path = "php/class.askama", escape = "none")]
+struct PhpClassTemplate<'a> {
+    namespace: &'a str,
+    class: &'a mapper::php::PhpClass,
+}
+
+#[derive(Template)]
+#[template(path = "php/composer.json.askama", escape = "none")]
+struct PhpComposerTemplate<'a> {
+    meta: &'a Metadata,
+    /// The namespace prefix mapped to `src/`, as a JSON string.
+    autoload_prefix: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "scala/types.askama", escape = "none")]
+struct ScalaTemplate<'a> {
+    package: &'a str,
+    binding: &'a mapper::scala::ScalaBinding,
+}
+
+#[derive(Template)]
+#[template(path = "scala/build.sbt.askama", escape = "none")]
+struct ScalaBuildTemplate<'a> {
+    meta: &'a Metadata,
+}
+
+#[derive(Template)]
+#[template(path = "c/header.askama", escape = "none")]
+struct CHeaderTemplate<'a> {
+    structs: &'a [mapper::c::CStruct],
+}
+
+#[derive(Template)]
+#[template(path = "proto/messages.askama", escape = "none")]
+struct ProtoTemplate<'a> {
+    package: &'a str,
+    messages: &'a [mapper::proto::ProtoMessage],
+}
+
+#[derive(Template)]
+#[template(path = "cddl/types.askama", escape = "none")]
+struct CddlTemplate<'a> {
+    root: Option<String>,
+    rules: &'a [mapper::cddl::CddlRule],
+}
+
+#[derive(Template)]
+#[template(path = "ocaml/types.askama", escape = "none")]
+struct OcamlTemplate<'a> {
+    declarations: &'a [mapper::ocaml::OcamlDecl],
+}
+
+#[derive(Template)]
+#[template(path = "ocaml/dune-project.askama", escape = "none")]
+struct OcamlDuneProjectTemplate<'a> {
+    meta: &'a Metadata,
+}
+
+#[derive(Template)]
+#[template(path = "wasm/Cargo.toml.askama", escape = "none")]
+struct WasmCrateTemplate<'a> {
+    meta: &'a Metadata,
+    krate: &'a WasmCrate,
+}
+
+#[derive(Template)]
+#[template(path = "wasm/lib.askama", escape = "none")]
+struct WasmLibTemplate<'a> {
+    meta: &'a Metadata,
+    krate: &'a WasmCrate,
+    methods: &'a [mapper::rust::WasmMethod],
+    /// The TypeScript declarations of the types, as a raw string literal.
+    ts_types: String,
+}
+
+#[derive(Template)]
+#[template(path = "wasm/README.md.askama", escape = "none")]
+struct WasmReadmeTemplate<'a> {
+    meta: &'a Metadata,
+    krate: &'a WasmCrate,
+    methods: &'a [mapper::rust::WasmMethod],
+}
+
+mod filters {
+    use crate::mapper::LanguageContext;
+    use crate::resolver::{ResolvedField, ResolvedType};
+
+    /// Comma separated generated names of the given components.
+    pub fn type_list(names: &[String], ctx: &LanguageContext) -> Result<String, askama::Error> {
+        let names: Vec<String> = names.iter().map(|n| ctx.type_name(n)).collect();
+        Ok(names.join(", "))
+    }
+
+    /// Specifier importing the sibling TypeScript module `name`, with the
+    /// file extension Deno requires.
+    pub fn ts_module(name: &str, deno: &bool) -> Result<String, askama::Error> {
+        let ext = if *deno { ".ts" } else { "" };
+        Ok(format!("./{}{}", name, ext))
+    }
+
+    pub fn length(value: &[ResolvedField]) -> Result<usize, askama::Error> {
+        Ok(value.len())
+    }
+
+    /// Path under which a crate is imported, e.g. `trp_types` for `trp-types`.
+    pub fn rust_path(name: &str) -> Result<String, askama::Error> {
+        Ok(name.replace('-', "_"))
+    }
+
+    pub fn any_deprecated(types: &[ResolvedType]) -> Result<bool, askama::Error> {
+        Ok(types.iter().any(|t| t.has_deprecations()))
+    }
+}
+
+// Helper methods exposed to templates
+impl ResolvedType {
+    pub fn deprecated(&self) -> bool {
+        self.schema.metadata.as_ref().is_some_and(|m| m.deprecated)
+    }
+
+    pub fn deprecated_fields(&self) -> Vec<&ResolvedField> {
+        self.fields.iter().filter(|f| f.deprecated()).collect()
+    }
+
+    /// Whether the schema forbids properties beyond the declared ones.
+    pub fn closed(&self) -> bool {
+        self.schema
+            .object
+            .as_ref()
+            .and_then(|o| o.additional_properties.as_deref())
+            == Some(&Schema::Bool(false))
+    }
+
+    /// Arguments of the pydantic `model_config`, if the model needs one.
+    pub fn python_config(&self, strict: &bool, ctx: &LanguageContext) -> Option<String> {
+        let mut options = Vec::new();
+        if *strict && self.closed() {
+            options.push("extra=\"forbid\"");
+        }
+        if self.python_extra(ctx).is_some() {
+            options.push("extra=\"allow\"");
+        }
+        if self.fields.iter().any(|f| f.python_name(ctx) != f.name) {
+            options.push("populate_by_name=True");
+        }
+        (!options.is_empty()).then(|| options.join(", "))
+    }
+
+    pub fn python_extra(&self, ctx: &LanguageContext) -> Option<String> {
+        mapper::python::extra_type(self, ctx)
+    }
+
+    /// `(str, Enum)` classes of the string enum fields in Python.
+    pub fn python_enums(&self, ctx: &LanguageContext) -> Vec<mapper::python::PythonEnum> {
+        mapper::python::enums(self, ctx)
+    }
+
+    /// Annotation of `field` in Python, naming its enum class if it has one.
+    pub fn python_type(&self, field: &ResolvedField, ctx: &LanguageContext) -> String {
+        mapper::python::field_type(self, field, false, ctx).maybe_optional(field, ctx)
+    }
+
+    /// Type of the constructor parameter of `field` in Python stubs.
+    pub fn python_parameter(&self, field: &ResolvedField, ctx: &LanguageContext) -> String {
+        mapper::python::field_type(self, field, true, ctx).maybe_optional(field, ctx)
+    }
+
+    /// Right-hand side of the pydantic declaration of `field`, if any.
+    pub fn python_spec(&self, field: &ResolvedField, ctx: &LanguageContext) -> Option<String> {
+        let class = mapper::python::enum_class(self, field, ctx);
+        mapper::python::field_spec(field, field.default_value(), class.as_ref(), ctx)
+    }
+
+    pub fn rust_extra(&self, ctx: &LanguageContext) -> Option<(String, String)> {
+        mapper::rust::extra_field(self, ctx)
+    }
+
+    pub fn go_extra(&self, ctx: &LanguageContext) -> Option<mapper::go::GoExtra> {
+        mapper::go::extra(self, ctx)
+    }
+
+    pub fn ts_index_signature(&self, ctx: &LanguageContext) -> Option<String> {
+        mapper::ts::index_signature(self, ctx)
+    }
+
+    pub fn zod_catchall(&self, ctx: &LanguageContext) -> Option<String> {
+        mapper::ts::zod_catchall(self, ctx)
+    }
+
+    /// Named constants for the values of an enum field in Go.
+    pub fn go_enum_constants(
+        &self,
+        field: &ResolvedField,
+        ctx: &LanguageContext,
+    ) -> Vec<(String, String)> {
+        mapper::go::enum_constants(self, field, ctx)
+    }
+
+    pub fn has_deprecations(&self) -> bool {
+        self.deprecated() || self.fields.iter().any(|f| f.deprecated())
+    }
+
+    pub fn has_defaults(&self) -> bool {
+        self.fields.iter().any(|f| f.default_value().is_some())
+    }
+
+    pub fn const_fields(&self) -> Vec<&ResolvedField> {
+        self.fields
+            .iter()
+            .filter(|f| f.const_value().is_some())
+            .collect()
+    }
+
+    /// Fields marked with `format: address`, which need a pydantic validator.
+    pub fn python_address_fields(&self, ctx: &LanguageContext) -> Vec<&ResolvedField> {
+        self.fields
+            .iter()
+            .filter(|f| ctx.type_override(&f.schema).is_none())
+            .filter(|f| f.constraints.contains(&Constraint::Address))
+            .collect()
+    }
+
+    /// Fields declaring `uniqueItems`, which need a pydantic validator.
+    pub fn python_unique_fields(&self) -> Vec<&ResolvedField> {
+        self.fields
+            .iter()
+            .filter(|f| f.constraints.contains(&Constraint::UniqueItems))
+            .collect()
+    }
+
+    pub fn rust_validation(&self, ctx: &LanguageContext) -> Vec<String> {
+        mapper::rust::validation(self, ctx)
+    }
+
+    pub fn rust_address_fields(&self, ctx: &LanguageContext) -> Vec<mapper::rust::AddressField> {
+        mapper::rust::address_fields(self, ctx)
+    }
+
+    pub fn go_validation(&self, ctx: &LanguageContext) -> mapper::go::GoValidation {
+        mapper::go::validation(self, ctx)
+    }
+
+    /// Name of the generated serde hook rejecting values other than the
+    /// field's `const`.
+    pub fn rust_const_fn(&self, field: &ResolvedField) -> String {
+        format!(
+            "deserialize_{}_{}",
+            mapper::snake_case(&self.name),
+            mapper::snake_case(&field.name)
+        )
+    }
+}
+
+impl MethodMetadata {
+    /// One-line documentation of the method's client call.
+    pub fn doc(&self) -> String {
+        self.summary
+            .clone()
+            .unwrap_or_else(|| format!("Calls `{}`.", self.name))
+    }
+
+    /// The first example of a method whose params object is a component,
+    /// along with that component.
+    pub fn typed_example(&self) -> Option<(&MethodExample, &str)> {
+        Some((self.examples.first()?, self.params_type.as_deref()?))
+    }
+
+    /// TypeScript type of the params object of a client call.
+    pub fn ts_params(&self, ctx: &LanguageContext) -> String {
+        match &self.params_type {
+            Some(name) => format!("types.{}", ctx.type_name(name)),
+            None => "Record<string, unknown>".to_string(),
+        }
+    }
+
+    /// Result of the first example of the method that has one, as indented
+    /// JSON continuing a line indented by `indent` spaces.
+    pub fn example_result(&self, indent: usize) -> Option<String> {
+        let result = self.examples.iter().find_map(|e| e.result.as_ref())?;
+        let mut json = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut json, formatter);
+        result.serialize(&mut serializer).ok()?;
+        let json = String::from_utf8(json).ok()?;
+        Some(json.replace('\n', &format!("\n{}", " ".repeat(indent))))
+    }
+
+    pub fn ts_result(&self, ctx: &LanguageContext) -> String {
+        match &self.result_type {
+            Some(name) => format!("types.{}", ctx.type_name(name)),
+            None => "unknown".to_string(),
+        }
+    }
+}
+
+impl ResolvedField {
+    pub fn deprecated(&self) -> bool {
+        self.schema.metadata.as_ref().is_some_and(|m| m.deprecated)
+    }
+
+    /// Documentation of the key format of maps built from `patternProperties`.
+    pub fn key_doc(&self) -> Option<String> {
+        self.constraints.iter().find_map(|c| match c {
+            Constraint::KeyPattern(pattern) => Some(format!("Keys match `{}`.", pattern)),
+            _ => None,
+        })
+    }
+
+    /// JSDoc lines describing the field, one per line.
+    pub fn ts_doc_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.key_doc().into_iter().collect();
+        if self.const_value().is_none() {
+            if let Some(default) = self.default_doc() {
+                tags.push(format!("@default {}", default));
+            }
+        }
+        if self.deprecated() {
+            tags.push("@deprecated".to_string());
+        }
+        tags
+    }
+
+    /// The fixed value of a field declaring a scalar `const`.
+    pub fn const_value(&self) -> Option<&Value> {
+        self.schema
+            .const_value
+            .as_ref()
+            .filter(|v| mapper::is_scalar(v))
+    }
+
+    /// The field's `default`, or its `const` value when it has one.
+    pub fn default_value(&self) -> Option<&Value> {
+        self.schema.const_value.as_ref().or_else(|| {
+            self.schema
+                .metadata
+                .as_ref()
+                .and_then(|m| m.default.as_ref())
+        })
+    }
+
+    /// JSON rendering of the default, for documentation comments.
+    pub fn default_doc(&self) -> Option<String> {
+        self.default_value().map(Value::to_string)
+    }
+
+    /// The `const` value as a Rust string literal, for error messages.
+    pub fn rust_const_doc(&self) -> String {
+        format!("{:?}", self.default_doc().unwrap_or_default())
+    }
+
+    pub fn rust_const_literal(&self) -> String {
+        self.const_value()
+            .map(mapper::rust::literal)
+            .unwrap_or_default()
+    }
+
+    pub fn rust_default(&self, ctx: &LanguageContext) -> String {
+        match self.default_value() {
+            Some(value) => mapper::rust::default_expr(value, &self.rust_type(ctx)),
+            None => "Default::default()".to_string(),
+        }
+    }
+
+    pub fn ts_name(&self) -> String {
+        mapper::ts::field_name(&self.name)
+    }
+
+    pub fn python_name(&self, ctx: &LanguageContext) -> String {
+        mapper::python::field_ident(self, ctx)
+    }
+
+    pub fn go_name(&self, ctx: &LanguageContext) -> String {
+        mapper::go::field_ident(self, ctx)
+    }
+
+    pub fn rust_name(&self, ctx: &LanguageContext) -> String {
+        mapper::rust::field_ident(self, ctx)
+    }
+
+    pub fn ts_type(&self, ctx: &LanguageContext) -> String {
+        mapper::ts::map_type(&self.schema, ctx)
+    }
+
+    pub fn zod_type(&self, ctx: &LanguageContext) -> String {
+        mapper::ts::zod_field(self, ctx)
+    }
+
+    pub fn go_type(&self, ctx: &LanguageContext) -> String {
+        mapper::go::map_type(&self.schema, ctx)
+    }
+
+    pub fn rust_type(&self, ctx: &LanguageContext) -> String {
+        mapper::rust::map_type(&self.schema, ctx).maybe_optional(self, ctx)
+    }
+}
+
+trait OptionalRendering {
+    fn maybe_optional(self, field: &ResolvedField, ctx: &LanguageContext) -> String;
+}
+
+impl OptionalRendering for String {
+    fn maybe_optional(self, field: &ResolvedField, ctx: &LanguageContext) -> String {
+        // Nullable types are already wrapped by the mapper; wrapping them again
+        // would yield `Option<Option<T>>` and friends.
+        if field.required || mapper::is_nullable(&field.schema) {
+            self
+        } else {
+            ctx.wrap_optional(&self)
+        }
+    }
+}
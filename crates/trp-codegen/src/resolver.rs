@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+use std::ops::Deref as _;
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use schemars::schema::{
+    ArrayValidation, InstanceType, ObjectValidation, Schema, SchemaObject, SingleOrVec,
+    SubschemaValidation,
+};
+
+use serde::Serialize;
+
+use crate::openrpc::OpenRpc;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ResolvedType {
+    pub name: String,
+    pub schema: SchemaObject,
+    pub fields: Vec<ResolvedField>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ResolvedField {
+    pub name: String,
+    pub schema: SchemaObject,
+    pub required: bool,
+    pub constraints: Vec<Constraint>,
+}
+
+/// A validation keyword on a field schema that generated bindings enforce at
+/// runtime, on top of what the type system already guarantees.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Constraint {
+    MinLength(u32),
+    MaxLength(u32),
+    Pattern(String),
+    Minimum(f64),
+    Maximum(f64),
+    MinItems(u32),
+    UniqueItems,
+    /// Map keys must match the pattern of the object's `patternProperties`.
+    KeyPattern(String),
+    /// A Cardano address, as `format: address` marks string fields.
+    Address,
+}
+
+/// The string `format` of address fields.
+pub const ADDRESS_FORMAT: &str = "address";
+
+impl Constraint {
+    /// Error message reported when a value violates the constraint.
+    pub fn message(&self) -> String {
+        match self {
+            Constraint::MinLength(n) => format!("must be at least {} characters long", n),
+            Constraint::MaxLength(n) => format!("must be at most {} characters long", n),
+            Constraint::Pattern(p) => format!("must match pattern {}", p),
+            Constraint::Minimum(n) => format!("must be at least {}", n),
+            Constraint::Maximum(n) => format!("must be at most {}", n),
+            Constraint::MinItems(n) => format!("must contain at least {} items", n),
+            Constraint::UniqueItems => "items must be unique".to_string(),
+            Constraint::KeyPattern(p) => format!("keys must match pattern {}", p),
+            Constraint::Address => "must be a valid address".to_string(),
+        }
+    }
+}
+
+pub fn resolve_components(spec: &OpenRpc) -> Result<Vec<ResolvedType>> {
+    let components = spec
+        .components
+        .as_ref()
+        .and_then(|c| c.schemas.as_ref())
+        .context("no components.schemas present in OpenRPC spec")?;
+
+    let mut arena = Arena::new(components);
+    let mut resolved = Vec::with_capacity(arena.names.len());
+    for id in 0..arena.names.len() {
+        let name = arena.names[id];
+        let fields = arena
+            .fields(id)
+            .with_context(|| format!("failed to resolve fields of {}", name))?;
+        resolved.push(ResolvedType {
+            name: name.to_string(),
+            schema: arena.schemas[id].clone(),
+            fields: fields.iter().map(Field::resolve).collect(),
+        });
+    }
+
+    resolved.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(resolved)
+}
+
+fn normalize_schema(schema: &Schema) -> SchemaObject {
+    schema_object(schema).clone()
+}
+
+/// `schema` as an object, the empty schema standing for boolean ones.
+fn schema_object(schema: &Schema) -> &SchemaObject {
+    static EMPTY: OnceLock<SchemaObject> = OnceLock::new();
+    match schema {
+        Schema::Bool(_) => EMPTY.get_or_init(SchemaObject::default),
+        Schema::Object(obj) => obj,
+    }
+}
+
+/// Index of a component in the [`Arena`].
+type SchemaId = usize;
+
+/// A field borrowed from the spec while resolving, cloned into a
+/// [`ResolvedField`] once its component is done.
+#[derive(Clone, Copy)]
+struct Field<'a> {
+    name: &'a str,
+    schema: &'a SchemaObject,
+    required: bool,
+}
+
+impl Field<'_> {
+    fn resolve(&self) -> ResolvedField {
+        ResolvedField {
+            name: self.name.to_string(),
+            schema: self.schema.clone(),
+            required: self.required,
+            constraints: constraints(self.schema),
+        }
+    }
+}
+
+/// Progress of a component's fields in the [`Arena`].
+enum Fields<'a> {
+    Pending,
+    /// Being collected, so reaching the component again is a cycle.
+    InProgress,
+    Done(Rc<[Field<'a>]>),
+}
+
+/// The components of a spec interned by id, with the fields of each
+/// collected at most once however many `$ref`s and `allOf` chains reach it.
+/// Schemas are borrowed from the spec throughout, so the only clones are
+/// those of the resolved types themselves.
+struct Arena<'a> {
+    names: Vec<&'a str>,
+    schemas: Vec<&'a SchemaObject>,
+    ids: HashMap<&'a str, SchemaId>,
+    fields: Vec<Fields<'a>>,
+}
+
+impl<'a> Arena<'a> {
+    fn new(components: &'a HashMap<String, Schema>) -> Self {
+        let mut names = Vec::with_capacity(components.len());
+        let mut schemas = Vec::with_capacity(components.len());
+        let mut ids = HashMap::with_capacity(components.len());
+        for (id, (name, schema)) in components.iter().enumerate() {
+            names.push(name.as_str());
+            schemas.push(schema_object(schema));
+            ids.insert(name.as_str(), id);
+        }
+        let fields = names.iter().map(|_| Fields::Pending).collect();
+        Self {
+            names,
+            schemas,
+            ids,
+            fields,
+        }
+    }
+
+    /// The fields of the component `id`, collected on first use.
+    fn fields(&mut self, id: SchemaId) -> Result<Rc<[Field<'a>]>> {
+        match &self.fields[id] {
+            Fields::Done(fields) => return Ok(fields.clone()),
+            Fields::InProgress => {
+                anyhow::bail!("{} includes itself through $ref or allOf", self.names[id])
+            }
+            Fields::Pending => {}
+        }
+        self.fields[id] = Fields::InProgress;
+        let fields: Rc<[Field<'a>]> = self.collect(self.schemas[id])?.into();
+        self.fields[id] = Fields::Done(fields.clone());
+        Ok(fields)
+    }
+
+    fn collect(&mut self, schema: &'a SchemaObject) -> Result<Vec<Field<'a>>> {
+        if let Some(reference) = &schema.reference {
+            let target_name = ref_to_name(reference)?;
+            let target = *self
+                .ids
+                .get(target_name.as_str())
+                .with_context(|| format!("missing referenced schema {}", target_name))?;
+            return Ok(self.fields(target)?.to_vec());
+        }
+
+        let mut fields = Vec::new();
+
+        if let Some(subschemas) = &schema.subschemas {
+            if let Some(all_of) = &subschemas.all_of {
+                for sub in all_of {
+                    for field in self.collect(schema_object(sub))? {
+                        merge_field(&mut fields, field)?;
+                    }
+                }
+            }
+        }
+
+        if let Some(object) = &schema.object {
+            for (name, schema) in &object.properties {
+                merge_field(
+                    &mut fields,
+                    Field {
+                        name,
+                        schema: schema_object(schema),
+                        required: object.required.contains(name),
+                    },
+                )?;
+            }
+            // A parent may mark properties declared in its allOf branches as required.
+            for field in fields.iter_mut() {
+                if object.required.contains(field.name) {
+                    field.required = true;
+                }
+            }
+        }
+
+        Ok(fields)
+    }
+}
+
+/// Adds `incoming` to `fields`, merging it with an existing field of the same
+/// name. A field is required if any declaration requires it; declarations that
+/// disagree on anything but documentation are rejected.
+fn merge_field<'a>(fields: &mut Vec<Field<'a>>, incoming: Field<'a>) -> Result<()> {
+    match fields.iter_mut().find(|f| f.name == incoming.name) {
+        Some(existing) => {
+            if !same_shape(existing.schema, incoming.schema) {
+                anyhow::bail!("conflicting allOf definitions for field {}", incoming.name);
+            }
+            existing.required |= incoming.required;
+        }
+        None => fields.push(incoming),
+    }
+    Ok(())
+}
+
+/// Whether `a` and `b` are the same schema but for their metadata.
+fn same_shape(a: &SchemaObject, b: &SchemaObject) -> bool {
+    let SchemaObject {
+        metadata: _,
+        instance_type,
+        format,
+        enum_values,
+        const_value,
+        subschemas,
+        number,
+        string,
+        array,
+        object,
+        reference,
+        extensions,
+    } = a;
+    *instance_type == b.instance_type
+        && *format == b.format
+        && *enum_values == b.enum_values
+        && *const_value == b.const_value
+        && *subschemas == b.subschemas
+        && *number == b.number
+        && *string == b.string
+        && *array == b.array
+        && *object == b.object
+        && *reference == b.reference
+        && *extensions == b.extensions
+}
+
+/// Collects the constraints of `schema` that apply to its declared type.
+/// Keywords for other types, or on schemas without a `type`, are ignored
+/// since no binding could check them against a concrete value.
+pub fn constraints(schema: &SchemaObject) -> Vec<Constraint> {
+    let types: Vec<InstanceType> = match &schema.instance_type {
+        Some(SingleOrVec::Single(t)) => vec![**t],
+        Some(SingleOrVec::Vec(list)) => list.clone(),
+        None => Vec::new(),
+    };
+
+    let mut out = Vec::new();
+    if let Some(string) = schema
+        .string
+        .as_ref()
+        .filter(|_| types.contains(&InstanceType::String))
+    {
+        out.extend(
+            string
+                .min_length
+                .filter(|n| *n > 0)
+                .map(Constraint::MinLength),
+        );
+        out.extend(string.max_length.map(Constraint::MaxLength));
+        out.extend(string.pattern.clone().map(Constraint::Pattern));
+    }
+    if types.contains(&InstanceType::String) && schema.format.as_deref() == Some(ADDRESS_FORMAT) {
+        out.push(Constraint::Address);
+    }
+    if let Some(number) = schema
+        .number
+        .as_ref()
+        .filter(|_| types.contains(&InstanceType::Integer) || types.contains(&InstanceType::Number))
+    {
+        out.extend(number.minimum.map(Constraint::Minimum));
+        out.extend(number.maximum.map(Constraint::Maximum));
+    }
+    if let Some(object) = schema
+        .object
+        .as_deref()
+        .filter(|_| types.contains(&InstanceType::Object))
+    {
+        if object_additional_properties(object).is_none() {
+            if let Some((pattern, _)) = object_pattern_properties(object) {
+                out.push(Constraint::KeyPattern(pattern));
+            }
+        }
+    }
+    if let Some(array) = schema
+        .array
+        .as_ref()
+        .filter(|_| types.contains(&InstanceType::Array))
+    {
+        out.extend(array.min_items.filter(|n| *n > 0).map(Constraint::MinItems));
+        if array.unique_items == Some(true) {
+            out.push(Constraint::UniqueItems);
+        }
+    }
+    out
+}
+
+pub fn ref_to_name(reference: &str) -> Result<String> {
+    reference
+        .rsplit('/')
+        .next()
+        .map(|s| s.to_string())
+        .context("invalid reference string")
+}
+
+// Helpers for mapping
+pub fn array_item_schema(array: &ArrayValidation) -> Option<SchemaObject> {
+    array.items.as_ref().and_then(|items| match items {
+        schemars::schema::SingleOrVec::Single(schema) => Some(normalize_schema(schema)),
+        schemars::schema::SingleOrVec::Vec(_) => None,
+    })
+}
+
+/// Positional item schemas when `items` is a list, i.e. the array is a tuple.
+pub fn array_tuple_items(array: &ArrayValidation) -> Option<Vec<SchemaObject>> {
+    array.items.as_ref().and_then(|items| match items {
+        schemars::schema::SingleOrVec::Single(_) => None,
+        schemars::schema::SingleOrVec::Vec(list) => {
+            Some(list.iter().map(normalize_schema).collect())
+        }
+    })
+}
+
+/// Calls `visit` on `schema` and every schema nested inside it through array
+/// items, additional and pattern properties and unions. References are not
+/// followed.
+pub fn visit_schemas(schema: &SchemaObject, visit: &mut dyn FnMut(&SchemaObject)) {
+    visit(schema);
+    if let Some(array) = &schema.array {
+        let items = match &array.items {
+            Some(SingleOrVec::Single(item)) => std::slice::from_ref(item.as_ref()),
+            Some(SingleOrVec::Vec(items)) => items.as_slice(),
+            None => &[],
+        };
+        for item in items {
+            visit_schemas(schema_object(item), visit);
+        }
+    }
+    if let Some(object) = schema.object.as_deref() {
+        if let Some(additional) = object_additional_properties(object) {
+            visit_schemas(&additional, visit);
+        }
+        if let [(_, value)] = object
+            .pattern_properties
+            .iter()
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            visit_schemas(schema_object(value), visit);
+        }
+    }
+    if let Some(subschemas) = &schema.subschemas {
+        let options = subschemas.one_of.iter().chain(&subschemas.any_of);
+        for option in options.flatten() {
+            visit_schemas(schema_object(option), visit);
+        }
+    }
+}
+
+pub fn object_additional_properties(object: &ObjectValidation) -> Option<SchemaObject> {
+    object
+        .additional_properties
+        .as_ref()
+        .and_then(|props| match props.deref() {
+            schemars::schema::Schema::Object(schema) => Some(schema.clone()),
+            schemars::schema::Schema::Bool(true) => Some(SchemaObject {
+                instance_type: Some(vec![InstanceType::Object].into()),
+                ..Default::default()
+            }),
+            _ => None,
+        })
+}
+
+/// Value schema of the members a component takes beyond its declared
+/// properties, when its `additionalProperties` gives them a type.
+pub fn extra_properties(schema: &SchemaObject) -> Option<SchemaObject> {
+    match schema.object.as_ref()?.additional_properties.as_deref()? {
+        Schema::Object(extra) => Some(extra.clone()),
+        Schema::Bool(_) => None,
+    }
+}
+
+/// Key pattern and value schema of an object declaring a single
+/// `patternProperties` entry, i.e. a map whose keys follow a known format.
+/// Objects with several patterns have no single value type and are left to
+/// the generic map mapping.
+pub fn object_pattern_properties(object: &ObjectValidation) -> Option<(String, SchemaObject)> {
+    match object
+        .pattern_properties
+        .iter()
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [(pattern, schema)] => Some(((*pattern).clone(), normalize_schema(schema))),
+        _ => None,
+    }
+}
+
+pub fn one_of(subschemas: &SubschemaValidation) -> Option<Vec<SchemaObject>> {
+    subschemas
+        .one_of
+        .as_ref()
+        .map(|schemas| schemas.iter().map(normalize_schema).collect())
+}
+
+pub fn any_of(subschemas: &SubschemaValidation) -> Option<Vec<SchemaObject>> {
+    subschemas
+        .any_of
+        .as_ref()
+        .map(|schemas| schemas.iter().map(normalize_schema).collect())
+}
@@ -0,0 +1,149 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::analysis;
+use crate::mapper;
+use crate::openrpc::OpenRpc;
+use crate::resolver::{ref_to_name, visit_schemas, ResolvedType};
+
+/// Module holding the types shared by several tags, or used by none.
+pub const COMMON: &str = "common";
+
+/// How generated types are distributed over files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Split {
+    /// A single `types.*` file per language.
+    None,
+    /// One module per method tag, plus a `common` module for shared types.
+    PerTag,
+    /// One module per type.
+    PerType,
+}
+
+/// A group of types generated into the same file.
+pub struct Module {
+    pub name: String,
+    pub types: Vec<ResolvedType>,
+    /// Types defined in other modules that this module refers to, keyed by
+    /// module name.
+    pub imports: Vec<(String, Vec<String>)>,
+}
+
+/// Groups types by the tags of the methods reaching them. A type reachable
+/// from a single tag lives in that tag's module; everything else, and
+/// whatever it refers to, goes to `common`, so that tag modules only ever
+/// depend on `common` and never on each other.
+pub fn by_tag(spec: &OpenRpc, types: &[ResolvedType]) -> Vec<Module> {
+    let graph = analysis::component_graph(spec);
+
+    let mut tags: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for method in &spec.methods {
+        let mut seen = BTreeSet::new();
+        let mut queue: VecDeque<String> = analysis::method_refs(method).into_iter().collect();
+        while let Some(name) = queue.pop_front() {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            if let Some(next) = graph.get(&name) {
+                queue.extend(next.iter().cloned());
+            }
+        }
+        for ty in types.iter().filter(|t| seen.contains(&t.name)) {
+            let entry = tags.entry(ty.name.as_str()).or_default();
+            entry.extend(method.tags.iter().map(|t| mapper::snake_case(&t.name)));
+        }
+    }
+
+    let mut module_of: BTreeMap<String, String> = types
+        .iter()
+        .map(|ty| {
+            let module = match tags.get(ty.name.as_str()) {
+                Some(set) if set.len() == 1 => set.iter().next().cloned().unwrap_or_default(),
+                _ => COMMON.to_string(),
+            };
+            (ty.name.clone(), module)
+        })
+        .collect();
+
+    loop {
+        let promoted: Vec<String> = types
+            .iter()
+            .filter(|ty| module_of[&ty.name] == COMMON)
+            .flat_map(type_deps)
+            .filter(|dep| module_of.get(dep).is_some_and(|m| m != COMMON))
+            .collect();
+        if promoted.is_empty() {
+            break;
+        }
+        for name in promoted {
+            module_of.insert(name, COMMON.to_string());
+        }
+    }
+
+    group(types, &module_of)
+}
+
+/// File stems the generator already uses next to the split modules.
+const RESERVED: &[&str] = &["types", "schemas", "constants", "mod"];
+
+/// Puts every type in a module of its own, named after the type. Modules
+/// may refer to each other in cycles when the types are recursive.
+pub fn by_type(types: &[ResolvedType]) -> Vec<Module> {
+    let module_of: BTreeMap<String, String> = types
+        .iter()
+        .map(|ty| {
+            let mut module = mapper::snake_case(&ty.name);
+            if RESERVED.contains(&module.as_str()) {
+                module.push_str("_type");
+            }
+            (ty.name.clone(), module)
+        })
+        .collect();
+
+    group(types, &module_of)
+}
+
+/// Component names referenced by the generated fields of `ty`.
+pub fn type_deps(ty: &ResolvedType) -> BTreeSet<String> {
+    let mut deps = BTreeSet::new();
+    for field in &ty.fields {
+        visit_schemas(&field.schema, &mut |schema| {
+            if let Some(name) = schema
+                .reference
+                .as_deref()
+                .and_then(|r| ref_to_name(r).ok())
+            {
+                deps.insert(name);
+            }
+        });
+    }
+    deps
+}
+
+fn group(types: &[ResolvedType], module_of: &BTreeMap<String, String>) -> Vec<Module> {
+    let mut modules: BTreeMap<&str, Module> = BTreeMap::new();
+    for ty in types {
+        let name = module_of[&ty.name].as_str();
+        let module = modules.entry(name).or_insert_with(|| Module {
+            name: name.to_string(),
+            types: Vec::new(),
+            imports: Vec::new(),
+        });
+        module.types.push(ty.clone());
+    }
+
+    for module in modules.values_mut() {
+        let mut imports: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for dep in module.types.iter().flat_map(type_deps) {
+            if let Some(owner) = module_of.get(&dep).filter(|m| **m != module.name) {
+                imports.entry(owner.clone()).or_default().insert(dep);
+            }
+        }
+        module.imports = imports
+            .into_iter()
+            .map(|(owner, names)| (owner, names.into_iter().collect()))
+            .collect();
+    }
+
+    modules.into_values().collect()
+}
@@ -0,0 +1,93 @@
+//! `trp_types!` generates the TRP Rust types at compile time from a spec file
+//! pinned in the consuming crate, instead of vendoring the generated
+//! `types.rs`:
+//!
+//! ```ignore
+//! trp_macros::trp_types!("specs/trp.json");
+//! ```
+//!
+//! The path is relative to the consuming crate's manifest directory, and may
+//! name the directory of a split spec (see `trp_codegen::bundle`). The
+//! types are declared where the macro is invoked, next to a `constants`
+//! module holding the method names and spec version, and an `errors` module
+//! holding the declared error codes. The generated code relies on `serde` (with `derive`) and `regex`, which the consuming crate
+//! must depend on.
+
+use std::path::PathBuf;
+
+use proc_macro::{TokenStream, TokenTree};
+use trp_codegen::{bundle, LanguageConfig, Spec};
+
+#[proc_macro]
+pub fn trp_types(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens,
+        Err(message) => compile_error(&message),
+    }
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, String> {
+    let relative = spec_path(input)?;
+    let root = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| "CARGO_MANIFEST_DIR is not set".to_string())?;
+    let path = PathBuf::from(root).join(&relative);
+
+    let spec = Spec::load(&path).map_err(|e| format!("{:#}", e))?;
+    let files = trp_codegen::generate(&spec, &LanguageConfig::new("rust"))
+        .map_err(|e| format!("failed to generate types from {}: {:#}", relative, e))?;
+
+    let mut code = String::new();
+    for file in files {
+        match file.path.as_str() {
+            "types.rs" => code.push_str(&file.contents),
+            "constants.rs" | "errors.rs" => {
+                let module = file.path.trim_end_matches(".rs");
+                code.push_str(&format!("\npub mod {} {{\n", module));
+                // The types are declared in the parent module rather than in
+                // a `types` sibling.
+                code.push_str(
+                    &file
+                        .contents
+                        .replace("use super::types;", "use super::{self as types};"),
+                );
+                code.push_str("\n}\n");
+            }
+            _ => {}
+        }
+    }
+    // Makes cargo rebuild the consuming crate whenever the spec changes.
+    let sources = match path.is_dir() {
+        true => bundle::files(&path).map_err(|e| format!("{:#}", e))?,
+        false => vec![path.clone()],
+    };
+    for source in sources {
+        code.push_str(&format!(
+            "\nconst _: &str = include_str!({:?});\n",
+            source.display().to_string()
+        ));
+    }
+
+    code.parse()
+        .map_err(|e| format!("generated types from {} don't parse: {}", relative, e))
+}
+
+/// The string literal passed to the macro.
+fn spec_path(input: TokenStream) -> Result<String, String> {
+    let mut tokens = input.into_iter();
+    let literal = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => literal.to_string(),
+        _ => return Err("expected the spec path as a string literal".to_string()),
+    };
+    literal
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .filter(|s| !s.contains('\\'))
+        .map(str::to_string)
+        .ok_or_else(|| "expected the spec path as a plain string literal".to_string())
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("compile_error!({:?});", message)
+        .parse()
+        .expect("compile_error! invocation parses")
+}
@@ -0,0 +1,32 @@
+mod generated {
+    trp_macros::trp_types!("tests/fixtures/spec.json");
+}
+
+use generated::{constants, errors, Node};
+
+#[test]
+fn declares_constants() {
+    assert_eq!(constants::SPEC_VERSION, "1.0.0");
+    assert_eq!(constants::A_GET, "a.get");
+    assert_eq!(constants::A_GET_PARAMS, &["p"]);
+}
+
+#[test]
+fn decodes_and_validates() {
+    let node: Node =
+        serde_json::from_str(r#"{"label":"a","tree":{"children":[{"label":""}]}}"#).unwrap();
+    let err = node.validate().unwrap_err();
+    assert_eq!(err.path, "tree.children[0].label");
+}
+
+#[test]
+fn decodes_typed_error_data() {
+    let error: errors::TrpError =
+        serde_json::from_str(r#"{"code":-32000,"message":"Bad node","data":{"label":"a"}}"#)
+            .unwrap();
+    assert_eq!(error.kind(), Some(errors::TrpErrorKind::BadNode));
+    match error.typed_data() {
+        Some(Ok(errors::TrpErrorData::BadNode(node))) => assert_eq!(node.label, "a"),
+        other => panic!("unexpected data: {:?}", other),
+    }
+}
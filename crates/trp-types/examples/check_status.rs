@@ -0,0 +1,33 @@
+// Generated by cargo xtask gen --lang rust
+//! Sends the `check-status-confirmed` example of `trp.checkStatus` to a
+//! server and prints the result:
+//!
+//! ```text
+//! cargo run --features blocking --example check_status -- --url http://localhost:8000
+//! ```
+
+use trp_types::client::blocking::Client;
+use trp_types::CheckStatusParams;
+
+const PARAMS: &str = r#"{
+  "hashes": [
+    "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+  ]
+}"#;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    let url = args
+        .windows(2)
+        .find(|pair| pair[0] == "--url")
+        .map(|pair| pair[1].clone())
+        .unwrap_or_else(|| "http://localhost:8000".to_string());
+
+    let params: CheckStatusParams = serde_json::from_str(PARAMS)?;
+    params.validate()?;
+
+    // Error objects of failed calls come back as `ClientError::Rpc`.
+    let result = Client::new(&url).check_status(&params)?;
+    println!("{:#?}", result);
+    Ok(())
+}
\ No newline at end of file
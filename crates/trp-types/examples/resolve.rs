@@ -0,0 +1,39 @@
+// Generated by cargo xtask gen --lang rust
+//! Sends the `resolve-transfer` example of `trp.resolve` to a
+//! server and prints the result:
+//!
+//! ```text
+//! cargo run --features blocking --example resolve -- --url http://localhost:8000
+//! ```
+
+use trp_types::client::blocking::Client;
+use trp_types::ResolveParams;
+
+const PARAMS: &str = r#"{
+  "tir": {
+    "version": "v1alpha8",
+    "bytecode": "a1646e616d65687472616e73666572",
+    "encoding": "hex"
+  },
+  "args": {
+    "quantity": 1000000
+  },
+  "env": {}
+}"#;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    let url = args
+        .windows(2)
+        .find(|pair| pair[0] == "--url")
+        .map(|pair| pair[1].clone())
+        .unwrap_or_else(|| "http://localhost:8000".to_string());
+
+    let params: ResolveParams = serde_json::from_str(PARAMS)?;
+    params.validate()?;
+
+    // Error objects of failed calls come back as `ClientError::Rpc`.
+    let result = Client::new(&url).resolve(&params)?;
+    println!("{:#?}", result);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+// Generated by cargo xtask gen --lang rust
+//! Sends the `submit-vkey-witness` example of `trp.submit` to a
+//! server and prints the result:
+//!
+//! ```text
+//! cargo run --features blocking --example submit -- --url http://localhost:8000
+//! ```
+
+use trp_types::client::blocking::Client;
+use trp_types::SubmitParams;
+
+const PARAMS: &str = r#"{
+  "tx": {
+    "encoding": "hex",
+    "payload": "84a300d90102818258200000000000000000000000000000000000000000000000000000000000000000000181a200581d60000000000000000000000000000000000000000000000000000000001a000f4240021a0002a8b1a0f5f6"
+  },
+  "witnesses": [
+    {
+      "type": "vkey",
+      "key": {
+        "encoding": "hex",
+        "payload": "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+      },
+      "signature": {
+        "encoding": "hex",
+        "payload": "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+      }
+    }
+  ]
+}"#;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    let url = args
+        .windows(2)
+        .find(|pair| pair[0] == "--url")
+        .map(|pair| pair[1].clone())
+        .unwrap_or_else(|| "http://localhost:8000".to_string());
+
+    let params: SubmitParams = serde_json::from_str(PARAMS)?;
+    params.validate()?;
+
+    // Error objects of failed calls come back as `ClientError::Rpc`.
+    let result = Client::new(&url).submit(&params)?;
+    println!("{:#?}", result);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,289 @@
+// Generated by cargo xtask gen --lang rust
+//! Cardano addresses, as the fields the spec marks with `format: address`
+//! hold them: bech32 for Shelley addresses (`addr1…`, `stake_test1…`) and
+//! base58 for Byron ones.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::hex;
+
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Network id of mainnet; every other id is a test network.
+pub const MAINNET: u8 = 1;
+
+/// A Cardano address: its header byte, telling its kind and network, then
+/// its payload.
+///
+/// It parses from its bech32 or base58 text form, checking that the bech32
+/// prefix agrees with the header, and from hex with [`Address::from_hex`].
+/// It displays as its text form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Address {
+    bytes: Vec<u8>,
+}
+
+/// Why an [`Address`] didn't parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressError {
+    /// The text is neither bech32 nor base58, or the hex isn't hex.
+    Encoding,
+    /// The bech32 checksum doesn't match.
+    Checksum,
+    /// The bech32 prefix isn't the one of the address's kind and network.
+    Prefix { expected: String, found: String },
+    /// The header byte names no kind of address.
+    Header(u8),
+    /// The address is too short or too long for its kind.
+    Length(usize),
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Encoding => write!(f, "the address is neither bech32, base58 nor hex"),
+            Self::Checksum => write!(f, "the address checksum doesn't match"),
+            Self::Prefix { expected, found } => write!(
+                f,
+                "expected the address prefix {}, got {}",
+                expected, found
+            ),
+            Self::Header(header) => write!(f, "unknown address header {:#04x}", header),
+            Self::Length(len) => write!(f, "an address of this kind can't be {} bytes long", len),
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+impl Address {
+    /// The address made of `bytes`, checked to be of a known kind.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, AddressError> {
+        let header = *bytes.first().ok_or(AddressError::Length(0))?;
+        let valid = match header >> 4 {
+            // Base addresses: payment and stake credentials.
+            0..=3 => bytes.len() == 57,
+            // Pointer addresses: a payment credential and a chain pointer.
+            4 | 5 => bytes.len() >= 32,
+            // Enterprise and reward addresses: a single credential.
+            6 | 7 | 14 | 15 => bytes.len() == 29,
+            // Byron addresses: a tagged CBOR array.
+            8 => bytes.starts_with(&[0x82, 0xd8, 0x18]),
+            _ => return Err(AddressError::Header(header)),
+        };
+        match valid {
+            true => Ok(Self { bytes }),
+            false => Err(AddressError::Length(bytes.len())),
+        }
+    }
+
+    /// The address whose bytes `hex` encodes.
+    pub fn from_hex(text: &str) -> Result<Self, AddressError> {
+        let bytes = hex::decode(text).map_err(|_| AddressError::Encoding)?;
+        Self::from_bytes(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The bytes of the address as lowercase hex.
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.bytes)
+    }
+
+    pub fn is_byron(&self) -> bool {
+        self.bytes[0] >> 4 == 8
+    }
+
+    /// Whether the address names a stake credential rather than a payment
+    /// one.
+    pub fn is_reward(&self) -> bool {
+        self.bytes[0] >> 4 >= 14
+    }
+
+    /// The id of the network of a Shelley address, [`MAINNET`] or a test
+    /// network. Byron addresses don't tell it in their header.
+    pub fn network_id(&self) -> Option<u8> {
+        match self.is_byron() {
+            true => None,
+            false => Some(self.bytes[0] & 0x0f),
+        }
+    }
+
+    /// The bech32 prefix of a Shelley address.
+    pub fn prefix(&self) -> Option<&'static str> {
+        let network = self.network_id()?;
+        Some(match (self.is_reward(), network == MAINNET) {
+            (false, true) => "addr",
+            (false, false) => "addr_test",
+            (true, true) => "stake",
+            (true, false) => "stake_test",
+        })
+    }
+
+    /// The bech32 form of a Shelley address.
+    pub fn to_bech32(&self) -> Option<String> {
+        let prefix = self.prefix()?;
+        let data = regroup(&self.bytes, 8, 5, true)?;
+        let checksum = bech32_checksum(prefix, &data);
+        let chars = data
+            .iter()
+            .chain(&checksum)
+            .map(|&value| char::from(BECH32_CHARSET[usize::from(value)]));
+        Some(format!("{}1{}", prefix, chars.collect::<String>()))
+    }
+}
+
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        // Byron addresses are mixed-case, unlike bech32 prefixes.
+        let bech32 = text.rsplit_once('1').filter(|(prefix, _)| {
+            let valid = |c: char| c.is_ascii_digit() || c == '_';
+            !prefix.is_empty()
+                && (prefix.chars().all(|c| c.is_ascii_lowercase() || valid(c))
+                    || prefix.chars().all(|c| c.is_ascii_uppercase() || valid(c)))
+        });
+        let Some((prefix, data)) = bech32 else {
+            let address = Self::from_bytes(base58_decode(text).ok_or(AddressError::Encoding)?)?;
+            return match address.is_byron() {
+                true => Ok(address),
+                false => Err(AddressError::Encoding),
+            };
+        };
+        let prefix = prefix.to_ascii_lowercase();
+        let address = Self::from_bytes(bech32_decode(&prefix, data)?)?;
+        match address.prefix() {
+            Some(expected) if expected == prefix => Ok(address),
+            expected => Err(AddressError::Prefix {
+                expected: expected.unwrap_or("none, as a Byron address").to_string(),
+                found: prefix,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_bech32() {
+            Some(bech32) => f.write_str(&bech32),
+            None => f.write_str(&base58_encode(&self.bytes)),
+        }
+    }
+}
+
+/// Whether `text` is a valid address, as `validate()` checks the fields
+/// marked with `format: address`.
+pub fn is_address(text: &str) -> bool {
+    text.parse::<Address>().is_ok()
+}
+
+fn bech32_polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    const GENERATORS: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    values.into_iter().fold(1, |checksum, value| {
+        let top = checksum >> 25;
+        let checksum = ((checksum & 0x1ff_ffff) << 5) ^ u32::from(value);
+        (0..5)
+            .filter(|i| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, i| checksum ^ GENERATORS[i])
+    })
+}
+
+fn bech32_prefix_values(prefix: &str) -> impl Iterator<Item = u8> + '_ {
+    let high = prefix.bytes().map(|c| c >> 5);
+    let low = prefix.bytes().map(|c| c & 31);
+    high.chain([0]).chain(low)
+}
+
+fn bech32_checksum(prefix: &str, data: &[u8]) -> [u8; 6] {
+    let values = bech32_prefix_values(prefix).chain(data.iter().copied()).chain([0; 6]);
+    let polymod = bech32_polymod(values) ^ 1;
+    std::array::from_fn(|i| ((polymod >> (5 * (5 - i))) & 31) as u8)
+}
+
+/// The bytes of the bech32 `data` part after `prefix`, in lowercase, with
+/// the checksum checked.
+fn bech32_decode(prefix: &str, data: &str) -> Result<Vec<u8>, AddressError> {
+    let mixed_case = data.chars().any(|c| c.is_ascii_uppercase())
+        && data.chars().any(|c| c.is_ascii_lowercase());
+    if mixed_case || data.len() < 6 {
+        return Err(AddressError::Encoding);
+    }
+    let values = data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|&v| v == c.to_ascii_lowercase()))
+        .map(|position| position.map(|position| position as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(AddressError::Encoding)?;
+    if bech32_polymod(bech32_prefix_values(prefix).chain(values.iter().copied())) != 1 {
+        return Err(AddressError::Checksum);
+    }
+    regroup(&values[..values.len() - 6], 5, 8, false).ok_or(AddressError::Encoding)
+}
+
+/// Regroups `values` of `from` bits into values of `to` bits, padding the
+/// last one with zeros if `pad`, and else requiring the leftover bits to be.
+fn regroup(values: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let (mut acc, mut bits) = (0u32, 0);
+    let mut out = Vec::new();
+    for &value in values {
+        acc = (acc << from) | u32::from(value);
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & ((1 << to) - 1)) as u8);
+        }
+    }
+    match (pad, bits) {
+        (true, 0) => {}
+        (true, _) => out.push(((acc << (to - bits)) & ((1 << to) - 1)) as u8),
+        (false, _) if bits >= from || (acc & ((1 << bits) - 1)) != 0 => return None,
+        (false, _) => {}
+    }
+    Some(out)
+}
+
+fn base58_decode(text: &str) -> Option<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in text.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&v| v == c)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = text.bytes().take_while(|&c| c == b'1').count();
+    let mut out = vec![0; zeros];
+    out.extend(bytes);
+    Some(out)
+}
+
+fn base58_encode(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in bytes {
+        let mut carry = u32::from(byte);
+        for digit in digits.iter_mut().rev() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.insert(0, (carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    let ones = std::iter::repeat_n(b'1', zeros);
+    ones.chain(digits.iter().map(|&digit| BASE58_ALPHABET[usize::from(digit)]))
+        .map(char::from)
+        .collect()
+}
\ No newline at end of file
@@ -0,0 +1,301 @@
+// Generated by cargo xtask gen --lang rust
+//! proptest `Arbitrary` implementations generating values that pass
+//! `validate()`. Requires the `arbitrary` feature.
+#![allow(deprecated)]
+
+use proptest::prelude::*;
+
+#[allow(unused_imports)]
+use crate::*;
+
+/// Strategy for the values the spec leaves untyped: JSON scalars other than
+/// `null`, which optional fields would decode as `None`.
+pub fn json_value() -> impl Strategy<Value = serde_json::Value> {
+    prop_oneof![
+        any::<bool>().prop_map(serde_json::Value::from),
+        any::<i64>().prop_map(serde_json::Value::from),
+        any::<String>().prop_map(serde_json::Value::from),
+    ]
+}
+
+impl Arbitrary for BytesEnvelope {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+        )
+            .prop_map(|(encoding, payload)| Self {
+                encoding,
+                payload,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CheckStatusParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            proptest::collection::vec(any::<String>(), 1..=5),
+        )
+            .prop_map(|(hashes,)| Self {
+                hashes,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CheckStatusResponse {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            proptest::collection::hash_map(any::<String>(), any::<TxStatus>(), 0..=4),
+        )
+            .prop_map(|(statuses,)| Self {
+                statuses,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for InputNotResolvedDiagnostic {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<InputQueryDiagnostic>(),
+            any::<SearchSpaceDiagnostic>(),
+        )
+            .prop_map(|(name, query, search_space)| Self {
+                name,
+                query,
+                search_space,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for InputQueryDiagnostic {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            proptest::option::of(any::<[u8; 28]>().prop_map(|hash| crate::address::Address::from_bytes([&[0x61][..], &hash].concat()).expect("valid address").to_string())),
+            any::<bool>(),
+            proptest::collection::hash_map(any::<String>(), any::<String>(), 0..=4),
+            proptest::collection::vec(any::<String>(), 0..=4),
+            any::<bool>(),
+        )
+            .prop_map(|(address, collateral, min_amount, refs, support_many)| Self {
+                address,
+                collateral,
+                min_amount,
+                refs,
+                support_many,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for MissingTxArgDiagnostic {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+        )
+            .prop_map(|(key, r#type)| Self {
+                key,
+                r#type,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ResolveParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            proptest::collection::hash_map(any::<String>(), json_value(), 0..=4),
+            proptest::collection::hash_map(any::<String>(), json_value(), 0..=4),
+            any::<TirInfo>(),
+        )
+            .prop_map(|(args, env, tir)| Self {
+                args,
+                env,
+                tir,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for SearchSpaceDiagnostic {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            proptest::option::of(any::<i64>()),
+            proptest::option::of(any::<i64>()),
+            proptest::option::of(any::<i64>()),
+            proptest::collection::vec(any::<String>(), 0..=4),
+        )
+            .prop_map(|(by_address_count, by_asset_class_count, by_ref_count, matched)| Self {
+                by_address_count,
+                by_asset_class_count,
+                by_ref_count,
+                matched,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for SubmitParams {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<BytesEnvelope>(),
+            proptest::collection::vec(any::<SubmitWitness>(), 0..=4),
+        )
+            .prop_map(|(tx, witnesses)| Self {
+                tx,
+                witnesses,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for SubmitResponse {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+        )
+            .prop_map(|(hash,)| Self {
+                hash,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for SubmitWitness {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<BytesEnvelope>(),
+            any::<BytesEnvelope>(),
+            proptest::sample::select(vec!["vkey".to_string()]),
+        )
+            .prop_map(|(key, signature, r#type)| Self {
+                key,
+                signature,
+                r#type,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TirInfo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+            any::<String>(),
+        )
+            .prop_map(|(bytecode, encoding, version)| Self {
+                bytecode,
+                encoding,
+                version,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TxEnvelope {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+        )
+            .prop_map(|(hash, tx)| Self {
+                hash,
+                tx,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TxScriptFailureDiagnostic {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            proptest::collection::vec(any::<String>(), 0..=4),
+        )
+            .prop_map(|(logs,)| Self {
+                logs,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TxStatus {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            0i64..=i64::MAX,
+            proptest::sample::select(vec!["pending".to_string(), "propagated".to_string(), "acknowledged".to_string(), "confirmed".to_string(), "finalized".to_string(), "dropped".to_string(), "rolledBack".to_string(), "unknown".to_string()]),
+        )
+            .prop_map(|(confirmations, stage)| Self {
+                confirmations,
+                stage,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for UnsupportedTirDiagnostic {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+        )
+            .prop_map(|(expected, provided)| Self {
+                expected,
+                provided,
+            })
+            .boxed()
+    }
+}
@@ -0,0 +1,256 @@
+// Generated by cargo xtask gen --lang rust
+//! TRP servers built on a [`ResolverBackend`]: the domain logic of a node,
+//! resolving and submitting transactions as bytes. [`Backend`] turns one
+//! into a [`Handler`], decoding the byte envelopes of the params (`hex` or
+//! `base64`) and encoding the transactions and hashes of the results as
+//! hex. Requires the `server` feature.
+//!
+//! ```no_run
+//! use trp_types::backend::{Args, Backend, Resolved, ResolverBackend, Tir, Witness};
+//! use trp_types::errors::{TrpError, UNSUPPORTED_TIR};
+//! use trp_types::server::{serve, Options};
+//!
+//! struct Node;
+//!
+//! impl ResolverBackend for Node {
+//!     async fn resolve(&self, tir: Tir, args: Args, env: Args) -> Result<Resolved, TrpError> {
+//!         if tir.version != "v1beta0" {
+//!             return Err(TrpError {
+//!                 code: UNSUPPORTED_TIR,
+//!                 message: format!("unsupported TIR version {}", tir.version),
+//!                 data: None,
+//!             });
+//!         }
+//!         todo!("apply args and env to the program and build its transaction")
+//!     }
+//!
+//!     async fn submit(&self, tx: Vec<u8>, witnesses: Vec<Witness>) -> Result<Vec<u8>, TrpError> {
+//!         todo!("attach the witnesses and submit the transaction, returning its hash")
+//!     }
+//! }
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! let listener = jsonrpsee::tokio::net::TcpListener::bind("127.0.0.1:8000").await?;
+//! let handle = serve(listener, Backend(Node), Options::default());
+//! handle.stopped().await;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Memory`] is a stub backend, for tests and local development.
+//!
+//! The spec's other methods are answered as not found.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use jsonrpsee::types::error::INVALID_PARAMS_CODE;
+use jsonrpsee::types::error::METHOD_NOT_FOUND_CODE;
+
+use crate::constants;
+use crate::errors::TrpError;
+use crate::hex;
+use crate::server::Handler;
+use crate::*;
+
+/// The args or env of a resolution, as sent.
+pub type Args = HashMap<String, serde_json::Value>;
+
+/// A TIR program to resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tir {
+    pub version: String,
+    pub bytecode: Vec<u8>,
+}
+
+/// A transaction built by a resolution, ready to sign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved {
+    pub tx: Vec<u8>,
+    pub hash: Vec<u8>,
+}
+
+/// A verification key witness of a submitted transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness {
+    pub key: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+/// The domain logic of a TRP server. Errors are answered as they are, so
+/// backends should use the codes of [`crate::errors`].
+pub trait ResolverBackend: Send + Sync + 'static {
+    /// Resolves `tir` with `args` and `env` into a transaction.
+    fn resolve(
+        &self,
+        tir: Tir,
+        args: Args,
+        env: Args,
+    ) -> impl Future<Output = Result<Resolved, TrpError>> + Send;
+
+    /// Submits `tx` with `witnesses`, returning the hash of the transaction.
+    fn submit(
+        &self,
+        tx: Vec<u8>,
+        witnesses: Vec<Witness>,
+    ) -> impl Future<Output = Result<Vec<u8>, TrpError>> + Send;
+
+    /// Whether the node the backend relies on is reachable, or why not, as
+    /// [`Handler::ready`]. Reachable unless implemented.
+    fn ready(&self) -> impl Future<Output = Result<(), String>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// The [`Handler`] of a [`ResolverBackend`]. Params with a byte envelope
+/// that doesn't decode are answered with an invalid params error.
+#[derive(Debug, Clone, Default)]
+pub struct Backend<B>(pub B);
+
+impl<B: ResolverBackend> Handler for Backend<B> {
+    async fn resolve(&self, params: ResolveParams) -> Result<TxEnvelope, TrpError> {
+        let tir = Tir {
+            bytecode: decode("tir.bytecode", &params.tir.encoding, &params.tir.bytecode)?,
+            version: params.tir.version,
+        };
+        let resolved = self.0.resolve(tir, params.args, params.env).await?;
+        Ok(TxEnvelope {
+            hash: hex::encode(&resolved.hash),
+            tx: hex::encode(&resolved.tx),
+        })
+    }
+
+    async fn submit(&self, params: SubmitParams) -> Result<SubmitResponse, TrpError> {
+        let tx = decode("tx", &params.tx.encoding, &params.tx.payload)?;
+        let witnesses = params
+            .witnesses
+            .iter()
+            .enumerate()
+            .map(|(i, witness)| {
+                let path = |member: &str| format!("witnesses.{}.{}", i, member);
+                Ok(Witness {
+                    key: decode(&path("key"), &witness.key.encoding, &witness.key.payload)?,
+                    signature: decode(&path("signature"), &witness.signature.encoding, &witness.signature.payload)?,
+                })
+            })
+            .collect::<Result<_, TrpError>>()?;
+        let hash = self.0.submit(tx, witnesses).await?;
+        Ok(SubmitResponse { hash: hex::encode(&hash) })
+    }
+
+    async fn check_status(&self, _: CheckStatusParams) -> Result<CheckStatusResponse, TrpError> {
+        Err(TrpError {
+            code: METHOD_NOT_FOUND_CODE.into(),
+            message: format!("{} is not implemented by the backend", constants::TRP_CHECK_STATUS),
+            data: None,
+        })
+    }
+
+    fn ready(&self) -> impl Future<Output = Result<(), String>> + Send {
+        self.0.ready()
+    }
+}
+
+/// Decodes the `payload` of the byte envelope at `path` from `encoding`.
+fn decode(path: &str, encoding: &str, payload: &str) -> Result<Vec<u8>, TrpError> {
+    let decoded = match encoding {
+        "hex" => hex::decode(payload).ok(),
+        "base64" => from_base64(payload),
+        _ => {
+            return Err(invalid(format!("{}: unsupported encoding `{}`", path, encoding)));
+        }
+    };
+    decoded.ok_or_else(|| invalid(format!("{}: invalid {}", path, encoding)))
+}
+
+fn invalid(message: String) -> TrpError {
+    TrpError {
+        code: INVALID_PARAMS_CODE.into(),
+        message: "Invalid params".to_string(),
+        data: Some(serde_json::Value::String(message)),
+    }
+}
+
+/// Decodes standard or URL-safe base64, padded or not.
+fn from_base64(text: &str) -> Option<Vec<u8>> {
+    let sextet = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    };
+    let text = text.trim_end_matches('=');
+    if text.len() % 4 == 1 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut bits, mut len) = (0u32, 0);
+    for c in text.bytes() {
+        bits = (bits << 6) | u32::from(sextet(c)?);
+        len += 6;
+        if len >= 8 {
+            len -= 8;
+            bytes.push((bits >> len) as u8);
+            bits &= (1 << len) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+/// A [`ResolverBackend`] standing in for a node: resolving answers the TIR
+/// bytecode as the transaction, and submitting records the transaction.
+/// Hashes are the 64-bit FNV-1a of the transaction. Clones share their
+/// records.
+#[derive(Debug, Clone, Default)]
+pub struct Memory {
+    submitted: Arc<Mutex<Vec<Submitted>>>,
+}
+
+/// A transaction submitted to a [`Memory`] backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submitted {
+    pub tx: Vec<u8>,
+    pub witnesses: Vec<Witness>,
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The transactions submitted so far.
+    pub fn submitted(&self) -> Vec<Submitted> {
+        self.submitted
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn hash(tx: &[u8]) -> Vec<u8> {
+        let hash = tx.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        hash.to_be_bytes().to_vec()
+    }
+}
+
+impl ResolverBackend for Memory {
+    async fn resolve(&self, tir: Tir, _: Args, _: Args) -> Result<Resolved, TrpError> {
+        Ok(Resolved {
+            hash: Self::hash(&tir.bytecode),
+            tx: tir.bytecode,
+        })
+    }
+
+    async fn submit(&self, tx: Vec<u8>, witnesses: Vec<Witness>) -> Result<Vec<u8>, TrpError> {
+        let hash = Self::hash(&tx);
+        self.submitted
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Submitted { tx, witnesses });
+        Ok(hash)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+// Generated by cargo xtask gen --lang rust
+//! Serves a proxy forwarding calls to upstream servers, failing over
+//! between them:
+//!
+//! ```text
+//! cargo run --features proxy --bin trp-types-proxy -- \
+//!     --listen 127.0.0.1:8000 --upstream http://a:8000 --upstream http://b:8000
+//! ```
+
+use trp_types::proxy::Proxy;
+use trp_types::server::{serve, Options};
+use jsonrpsee::tokio;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    let listen = args
+        .windows(2)
+        .find(|pair| pair[0] == "--listen")
+        .map(|pair| pair[1].clone())
+        .unwrap_or_else(|| "127.0.0.1:8000".to_string());
+    let upstreams: Vec<String> = args
+        .windows(2)
+        .filter(|pair| pair[0] == "--upstream")
+        .map(|pair| pair[1].clone())
+        .collect();
+    if upstreams.is_empty() {
+        return Err("at least one --upstream is required".into());
+    }
+
+    tokio::runtime::Runtime::new()?.block_on(async {
+        let listener = tokio::net::TcpListener::bind(&listen).await?;
+        eprintln!("proxying {} to {}", listener.local_addr()?, upstreams.join(", "));
+        let handle = serve(listener, Proxy::new(upstreams), Options::default());
+        handle.stopped().await;
+        Ok(())
+    })
+}
\ No newline at end of file
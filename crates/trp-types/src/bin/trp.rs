@@ -0,0 +1,346 @@
+// Generated by cargo xtask gen --lang rust
+//! Command-line client of TRP servers, resolving a transaction and then
+//! signing and submitting it:
+//!
+//! ```text
+//! cargo run --features cli --bin trp -- resolve --url http://localhost:8000 \
+//!     --tir <hex> --tir-version v1beta0 --args '{"quantity": 1}' --output json > envelope.json
+//! cargo run --features cli --bin trp -- sign-submit --url http://localhost:8000 \
+//!     --envelope envelope.json --key payment.skey
+//! ```
+//!
+//! `resolve` prints the transaction of the resolved envelope as hex
+//! (`--output hex`, the default), the envelope as JSON (`--output json`) or
+//! the transaction decoded as CBOR diagnostic notation (`--output diag`).
+//!
+//! `sign-submit` reads an envelope as printed by `resolve --output json`,
+//! from `--envelope` or stdin, signs its hash with the ed25519 payment key
+//! of `--key` and submits the transaction with that witness, printing the
+//! hash the server answers. The hash signed is the blake2b-256 of the
+//! transaction body, which must match the envelope's: a mismatching envelope
+//! is refused rather than signed. The key file is a `cardano-cli` text envelope or
+//! the key's 32 bytes as hex. With `--dry-run` nothing is submitted: the
+//! transaction with the witness added to its witness set is printed instead,
+//! as hex, diagnostic notation (`--output diag`) or the submit params
+//! (`--output json`). With `--wait` the hash is printed once the transaction
+//! is confirmed (`--until acknowledged|confirmed|finalized`, with
+//! `--confirmations <n>`, 1 by default), its progress going to stderr; the
+//! command fails if the transaction is dropped or the `--timeout` (in
+//! seconds, 300 by default) elapses first. Against servers without
+//! `trp.checkStatus` it warns and prints the hash without waiting.
+
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use ed25519_dalek::{Signer, SigningKey};
+use trp_types::cbor;
+use trp_types::client::blocking::Client;
+use trp_types::hex;
+use trp_types::tir::TirEnvelope;
+use trp_types::watch::{Event, Stage, Wait, WaitError};
+use trp_types::{BytesEnvelope, ResolveParams, SubmitParams, SubmitWitness, TirInfo, TxEnvelope};
+
+const USAGE: &str = "usage:
+  trp resolve --url <url> --tir <hex> --tir-version <version> [--args <json>] [--env <json>] [--output hex|json|diag]
+  trp sign-submit --key <file> [--envelope <file>] (--url <url> [--wait [--until <stage>] [--confirmations <n>] \
+[--timeout <secs>] [--interval <secs>]] | --dry-run [--output hex|json|diag])";
+
+type Error = Box<dyn std::error::Error>;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("resolve") => resolve(&Flags(&args[1..])),
+        Some("sign-submit") => sign_submit(&Flags(&args[1..])),
+        _ => Err(USAGE.into()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("trp: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The `--name value` pairs and `--name` switches following a command.
+struct Flags<'a>(&'a [String]);
+
+impl Flags<'_> {
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .windows(2)
+            .find(|pair| pair[0] == name)
+            .map(|pair| pair[1].as_str())
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.0.iter().any(|arg| arg == name)
+    }
+
+    fn required(&self, name: &str) -> Result<&str, Error> {
+        self.get(name)
+            .ok_or_else(|| format!("{} is required\n{}", name, USAGE).into())
+    }
+
+    /// The JSON object given to `name`, empty when left out.
+    fn object(&self, name: &str) -> Result<HashMap<String, serde_json::Value>, Error> {
+        match self.get(name) {
+            Some(json) => serde_json::from_str(json)
+                .map_err(|err| format!("{} isn't a JSON object: {}", name, err).into()),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    /// The number given to `name`, or `default` when left out.
+    fn number<T: std::str::FromStr>(&self, name: &str, default: T) -> Result<T, Error> {
+        match self.get(name) {
+            Some(number) => number
+                .parse()
+                .map_err(|_| format!("{} isn't a number: {}", name, number).into()),
+            None => Ok(default),
+        }
+    }
+
+    fn output(&self) -> Result<&str, Error> {
+        let output = self.get("--output").unwrap_or("hex");
+        if !matches!(output, "hex" | "json" | "diag") {
+            return Err(format!("unknown output `{}`\n{}", output, USAGE).into());
+        }
+        Ok(output)
+    }
+}
+
+fn resolve(flags: &Flags) -> Result<(), Error> {
+    let output = flags.output()?;
+    let tir = TirInfo {
+        bytecode: flags.required("--tir")?.to_string(),
+        encoding: "hex".to_string(),
+        version: flags.required("--tir-version")?.to_string(),
+    };
+    let params = ResolveParams {
+        // Bytecode that isn't hex and unsupported versions fail here rather
+        // than with an opaque error from the server.
+        tir: TirEnvelope::try_from(tir)?.into(),
+        args: flags.object("--args")?,
+        env: flags.object("--env")?,
+    };
+    let envelope = Client::new(flags.required("--url")?).resolve(&params)?;
+    println!("{}", render(&envelope, output)?);
+    Ok(())
+}
+
+/// The envelope as `output` tells.
+fn render(envelope: &TxEnvelope, output: &str) -> Result<String, Error> {
+    Ok(match output {
+        "json" => serde_json::to_string_pretty(envelope)?,
+        "diag" => cbor::diagnostic(&from_hex("the resolved transaction", &envelope.tx)?)?,
+        _ => envelope.tx.clone(),
+    })
+}
+
+fn sign_submit(flags: &Flags) -> Result<(), Error> {
+    let output = flags.output()?;
+    let key = signing_key(flags.required("--key")?)?;
+    let envelope = match flags.get("--envelope") {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|err| format!("can't read {}: {}", path, err))?,
+        None => std::io::read_to_string(std::io::stdin())?,
+    };
+    let envelope: TxEnvelope = serde_json::from_str(&envelope)
+        .map_err(|err| format!("the envelope isn't a resolve result: {}", err))?;
+
+    let tx = from_hex("the resolved transaction", &envelope.tx)?;
+    let hash = body_hash(&tx)?;
+    if from_hex("the transaction hash", &envelope.hash)? != hash {
+        return Err(format!(
+            "the envelope's hash {} isn't that of its transaction body, {}",
+            envelope.hash,
+            hex::encode(&hash)
+        )
+        .into());
+    }
+    let vkey = key.verifying_key().to_bytes();
+    let signature = key.sign(&hash).to_bytes();
+    let params = SubmitParams {
+        tx: BytesEnvelope {
+            encoding: "hex".to_string(),
+            payload: envelope.tx.clone(),
+        },
+        witnesses: vec![SubmitWitness {
+            r#type: "vkey".to_string(),
+            key: BytesEnvelope {
+                encoding: "hex".to_string(),
+                payload: hex::encode(&vkey),
+            },
+            signature: BytesEnvelope {
+                encoding: "hex".to_string(),
+                payload: hex::encode(&signature),
+            },
+        }],
+    };
+
+    if flags.has("--dry-run") {
+        let witnessed = witnessed(&tx, &vkey, &signature)?;
+        match output {
+            "json" => println!("{}", serde_json::to_string_pretty(&params)?),
+            "diag" => println!("{}", cbor::diagnostic(&witnessed)?),
+            _ => println!("{}", hex::encode(&witnessed)),
+        }
+        return Ok(());
+    }
+    let client = Client::new(flags.required("--url")?);
+    if !flags.has("--wait") {
+        println!("{}", client.submit(&params)?.hash);
+        return Ok(());
+    }
+    let waited = client.submit_and_wait(&params, &wait(flags)?, |event| match event {
+        Event::Submitted(hash) => eprintln!("submitted {}", hash),
+        Event::Status(status) => eprintln!("{} ({} confirmations)", status.stage, status.confirmations),
+    });
+    let hash = match waited {
+        Ok((hash, _)) => hash,
+        // Servers predating trp.checkStatus can't be waited on.
+        Err(WaitError::Unsupported(hash)) => {
+            eprintln!("the server can't tell the transaction's status, not waiting for it");
+            hash
+        }
+        Err(err) => return Err(err.into()),
+    };
+    println!("{}", hash);
+    Ok(())
+}
+
+fn wait(flags: &Flags) -> Result<Wait, Error> {
+    let until = match flags.get("--until").unwrap_or("confirmed") {
+        "acknowledged" => Stage::Acknowledged,
+        "confirmed" => Stage::Confirmed,
+        "finalized" => Stage::Finalized,
+        other => return Err(format!("can't wait until `{}`\n{}", other, USAGE).into()),
+    };
+    let defaults = Wait::default();
+    let seconds = |name: &str, default: Duration| -> Result<Duration, Error> {
+        let seconds = flags.number(name, default.as_secs_f64())?;
+        Duration::try_from_secs_f64(seconds).map_err(|_| format!("{} isn't a duration: {}", name, seconds).into())
+    };
+    Ok(Wait {
+        until,
+        confirmations: flags.number("--confirmations", defaults.confirmations)?,
+        interval: seconds("--interval", defaults.interval)?,
+        timeout: seconds("--timeout", defaults.timeout)?,
+    })
+}
+
+/// The ed25519 key of a `cardano-cli` text envelope, or of 32 bytes as hex.
+fn signing_key(path: &str) -> Result<SigningKey, Error> {
+    let text = std::fs::read_to_string(path).map_err(|err| format!("can't read {}: {}", path, err))?;
+    let not_a_key = || Error::from(format!("{} isn't an ed25519 signing key", path));
+    let key = if text.trim_start().starts_with('{') {
+        let envelope: serde_json::Value = serde_json::from_str(&text)?;
+        envelope["cborHex"]
+            .as_str()
+            .and_then(|cbor| cbor.strip_prefix("5820"))
+            .ok_or_else(not_a_key)?
+            .to_string()
+    } else {
+        text.trim().to_string()
+    };
+    let key: [u8; 32] = from_hex(path, &key)?.try_into().map_err(|_| not_a_key())?;
+    Ok(SigningKey::from_bytes(&key))
+}
+
+/// The blake2b-256 of the body of `tx`, a `[body, witness set, ...]` array,
+/// which is the transaction's hash.
+fn body_hash(tx: &[u8]) -> Result<[u8; 32], Error> {
+    let (_, rest) = head(tx, 4)
+        .filter(|(len, _)| *len >= 2)
+        .ok_or("the resolved transaction isn't a [body, witness set, ...] array")?;
+    let body = &rest[..cbor::item_len(rest)?];
+    Ok(Blake2b::<U32>::digest(body).into())
+}
+
+/// `tx`, a `[body, witness set, ...]` array, with the verification key
+/// witness `[vkey, signature]` added to the witnesses under key 0 of its
+/// witness set. The other items are copied as they are.
+fn witnessed(tx: &[u8], vkey: &[u8], signature: &[u8]) -> Result<Vec<u8>, Error> {
+    let not_a_tx = || Error::from("the resolved transaction isn't a [body, witness set, ...] array");
+    let (len, rest) = head(tx, 4).filter(|(len, _)| *len >= 2).ok_or_else(not_a_tx)?;
+    let (body, rest) = rest.split_at(cbor::item_len(rest)?);
+    let (entries, mut set) = head(rest, 5).ok_or_else(not_a_tx)?;
+
+    // Conway writes the witnesses as a set, tagged 258.
+    let mut tagged = false;
+    let mut vkeys = Vec::new();
+    let mut others = Vec::new();
+    for _ in 0..entries {
+        let (key, rest) = set.split_at(cbor::item_len(set)?);
+        let (value, rest) = rest.split_at(cbor::item_len(rest)?);
+        set = rest;
+        if key != [0x00] {
+            others.push([key, value].concat());
+            continue;
+        }
+        let value = match value.strip_prefix(&[0xd9, 0x01, 0x02]) {
+            Some(value) => {
+                tagged = true;
+                value
+            }
+            None => value,
+        };
+        let (count, mut items) = head(value, 4).ok_or_else(not_a_tx)?;
+        for _ in 0..count {
+            let (item, rest) = items.split_at(cbor::item_len(items)?);
+            vkeys.push(item.to_vec());
+            items = rest;
+        }
+    }
+    vkeys.push([encode_head(4, 2), encode_head(2, 32), vkey.to_vec(), encode_head(2, 64), signature.to_vec()].concat());
+
+    let mut out = [encode_head(4, len), body.to_vec(), encode_head(5, others.len() as u64 + 1)].concat();
+    out.push(0x00);
+    if tagged {
+        out.extend([0xd9, 0x01, 0x02]);
+    }
+    out.extend(encode_head(4, vkeys.len() as u64));
+    out.extend(vkeys.concat());
+    out.extend(others.concat());
+    out.extend(set);
+    Ok(out)
+}
+
+/// The argument of the definite-length head of `major` type `bytes` start
+/// with, and the bytes after it.
+fn head(bytes: &[u8], major: u8) -> Option<(u64, &[u8])> {
+    let (initial, rest) = bytes.split_first()?;
+    if initial >> 5 != major {
+        return None;
+    }
+    match initial & 0x1f {
+        info @ 0..=23 => Some((u64::from(info), rest)),
+        info @ 24..=27 => {
+            let len = 1 << (info - 24);
+            let argument = rest.get(..len)?.iter().fold(0, |argument, byte| argument << 8 | u64::from(*byte));
+            Some((argument, &rest[len..]))
+        }
+        _ => None,
+    }
+}
+
+/// The shortest head of `major` type with `argument`.
+fn encode_head(major: u8, argument: u64) -> Vec<u8> {
+    let major = major << 5;
+    match argument {
+        0..=23 => vec![major | argument as u8],
+        24..=0xff => vec![major | 24, argument as u8],
+        0x100..=0xffff => [&[major | 25][..], &(argument as u16).to_be_bytes()].concat(),
+        0x1_0000..=0xffff_ffff => [&[major | 26][..], &(argument as u32).to_be_bytes()].concat(),
+        _ => [&[major | 27][..], &argument.to_be_bytes()].concat(),
+    }
+}
+
+fn from_hex(what: &str, text: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(text).map_err(|_| format!("{} isn't hex", what).into())
+}
\ No newline at end of file
@@ -0,0 +1,261 @@
+// Generated by cargo xtask gen --lang rust
+//! CBOR diagnostic notation (RFC 8949, section 8) of encoded items, such as
+//! the transactions of resolved envelopes, for reading them without
+//! external tools:
+//!
+//! ```
+//! let diag = trp_types::cbor::diagnostic(&[0x82, 0x01, 0x43, 0xca, 0xfe, 0x00]).unwrap();
+//! assert_eq!(diag, "[\n  1,\n  h'cafe00'\n]");
+//! ```
+//!
+//! Arrays and maps list their items one per line, indented by two spaces;
+//! indefinite-length items are marked with `_`.
+
+use std::fmt;
+
+use crate::hex;
+
+/// Nesting deeper than this is rejected rather than risking the stack.
+pub const MAX_DEPTH: usize = 256;
+
+/// Why bytes aren't a single well-formed CBOR item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CborError {
+    /// The bytes end in the middle of an item.
+    Truncated,
+    /// Bytes follow the item, from this offset.
+    Trailing(usize),
+    /// The item at this offset uses a reserved or misplaced encoding.
+    Malformed(usize),
+    /// The text string at this offset isn't UTF-8.
+    Utf8(usize),
+    /// Items are nested deeper than [`MAX_DEPTH`].
+    TooDeep,
+}
+
+impl fmt::Display for CborError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "truncated CBOR"),
+            Self::Trailing(offset) => write!(f, "trailing bytes after the CBOR item, at offset {}", offset),
+            Self::Malformed(offset) => write!(f, "malformed CBOR at offset {}", offset),
+            Self::Utf8(offset) => write!(f, "text string at offset {} isn't UTF-8", offset),
+            Self::TooDeep => write!(f, "CBOR nested deeper than {} levels", MAX_DEPTH),
+        }
+    }
+}
+
+impl std::error::Error for CborError {}
+
+/// The diagnostic notation of the single CBOR item encoded by `bytes`.
+pub fn diagnostic(bytes: &[u8]) -> Result<String, CborError> {
+    let mut reader = Reader {
+        bytes,
+        offset: 0,
+        indent: 0,
+    };
+    let mut out = String::new();
+    if !reader.item(&mut out, 0)? {
+        return Err(CborError::Malformed(0));
+    }
+    if reader.offset < bytes.len() {
+        return Err(CborError::Trailing(reader.offset));
+    }
+    Ok(out)
+}
+
+/// The length in bytes of the CBOR item `bytes` start with, for splitting
+/// sequences and arrays into their items.
+pub fn item_len(bytes: &[u8]) -> Result<usize, CborError> {
+    let mut reader = Reader {
+        bytes,
+        offset: 0,
+        indent: 0,
+    };
+    if !reader.item(&mut String::new(), 0)? {
+        return Err(CborError::Malformed(0));
+    }
+    Ok(reader.offset)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    /// How many containers enclose the next item.
+    indent: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], CborError> {
+        let end = self.offset.checked_add(len).ok_or(CborError::Truncated)?;
+        let taken = self.bytes.get(self.offset..end).ok_or(CborError::Truncated)?;
+        self.offset = end;
+        Ok(taken)
+    }
+
+    /// The major type, additional info and argument of the next head.
+    fn head(&mut self) -> Result<(u8, u8, u64), CborError> {
+        let initial = self.take(1)?[0];
+        let info = initial & 0x1f;
+        let argument = match info {
+            0..=23 => u64::from(info),
+            24..=27 => {
+                let taken = self.take(1 << (info - 24))?;
+                taken.iter().fold(0, |argument, byte| argument << 8 | u64::from(*byte))
+            }
+            28..=30 => return Err(CborError::Malformed(self.offset - 1)),
+            _ => 0,
+        };
+        Ok((initial >> 5, info, argument))
+    }
+
+    /// Writes the next item to `out`, or returns false on a break.
+    fn item(&mut self, out: &mut String, depth: usize) -> Result<bool, CborError> {
+        if depth > MAX_DEPTH {
+            return Err(CborError::TooDeep);
+        }
+        let start = self.offset;
+        let (major, info, argument) = self.head()?;
+        if info == 31 && !matches!(major, 2..=5 | 7) {
+            return Err(CborError::Malformed(start));
+        }
+        let length = if info == 31 { None } else { Some(argument) };
+        match major {
+            0 => out.push_str(&argument.to_string()),
+            1 => out.push_str(&(-1 - i128::from(argument)).to_string()),
+            2 | 3 => match length {
+                Some(len) => self.string(out, major, len, start)?,
+                None => {
+                    let mut chunks = Vec::new();
+                    loop {
+                        let chunk = self.offset;
+                        let (chunk_major, chunk_info, len) = self.head()?;
+                        if chunk_major == 7 && chunk_info == 31 {
+                            break;
+                        }
+                        if chunk_major != major || chunk_info == 31 {
+                            return Err(CborError::Malformed(chunk));
+                        }
+                        let mut text = String::new();
+                        self.string(&mut text, major, len, chunk)?;
+                        chunks.push(text);
+                    }
+                    match chunks.is_empty() {
+                        true if major == 2 => out.push_str("''_"),
+                        true => out.push_str("\"\"_"),
+                        false => out.push_str(&format!("(_ {})", chunks.join(", "))),
+                    }
+                }
+            },
+            4 => self.container(out, depth, length, '[', ']', false)?,
+            5 => self.container(out, depth, length, '{', '}', true)?,
+            6 => {
+                out.push_str(&argument.to_string());
+                out.push('(');
+                if !self.item(out, depth + 1)? {
+                    return Err(CborError::Malformed(self.offset - 1));
+                }
+                out.push(')');
+            }
+            _ => match info {
+                20 => out.push_str("false"),
+                21 => out.push_str("true"),
+                22 => out.push_str("null"),
+                23 => out.push_str("undefined"),
+                24 if argument < 32 => return Err(CborError::Malformed(start)),
+                0..=19 | 24 => out.push_str(&format!("simple({})", argument)),
+                25 => float(out, half(argument as u16)),
+                26 => float(out, f64::from(f32::from_bits(argument as u32))),
+                27 => float(out, f64::from_bits(argument)),
+                _ => return Ok(false),
+            },
+        }
+        Ok(true)
+    }
+
+    fn string(&mut self, out: &mut String, major: u8, len: u64, start: usize) -> Result<(), CborError> {
+        let len = usize::try_from(len).map_err(|_| CborError::Truncated)?;
+        let bytes = self.take(len)?;
+        if major == 2 {
+            out.push_str("h'");
+            out.push_str(&hex::encode(bytes));
+            out.push('\'');
+        } else {
+            let text = std::str::from_utf8(bytes).map_err(|_| CborError::Utf8(start))?;
+            out.push_str(&serde_json::Value::from(text).to_string());
+        }
+        Ok(())
+    }
+
+    /// Writes an array, or a map when `pairs`, one entry per line. Without
+    /// a `length` the entries run up to a break.
+    fn container(
+        &mut self,
+        out: &mut String,
+        depth: usize,
+        length: Option<u64>,
+        open: char,
+        close: char,
+        pairs: bool,
+    ) -> Result<(), CborError> {
+        out.push(open);
+        if length.is_none() {
+            out.push('_');
+        }
+        self.indent += 1;
+        let indent = "  ".repeat(self.indent);
+        let mut count = 0u64;
+        while length.is_none_or(|length| count < length) {
+            let mut entry = String::new();
+            if !self.item(&mut entry, depth + 1)? {
+                if length.is_some() {
+                    return Err(CborError::Malformed(self.offset - 1));
+                }
+                break;
+            }
+            if pairs {
+                entry.push_str(": ");
+                if !self.item(&mut entry, depth + 1)? {
+                    return Err(CborError::Malformed(self.offset - 1));
+                }
+            }
+            out.push_str(if count == 0 { "\n" } else { ",\n" });
+            out.push_str(&indent);
+            out.push_str(&entry);
+            count += 1;
+        }
+        self.indent -= 1;
+        if count > 0 {
+            out.push('\n');
+            out.push_str(&indent[2..]);
+        }
+        out.push(close);
+        Ok(())
+    }
+}
+
+fn half(bits: u16) -> f64 {
+    let exponent = i32::from(bits >> 10 & 0x1f);
+    let mantissa = f64::from(bits & 0x3ff);
+    let magnitude = match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (mantissa + 1024.0) * 2f64.powi(exponent - 25),
+    };
+    if bits & 0x8000 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+fn float(out: &mut String, value: f64) {
+    if value.is_nan() {
+        out.push_str("NaN");
+    } else if value.is_infinite() {
+        out.push_str(if value > 0.0 { "Infinity" } else { "-Infinity" });
+    } else {
+        out.push_str(&format!("{:?}", value));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,550 @@
+// Generated by cargo xtask gen --lang rust
+//! JSON-RPC client over HTTP, built on reqwest. Requires the `client`
+//! feature; the `blocking` feature adds a synchronous client.
+//!
+//! Before their first call, clients check that the server implements a spec
+//! version compatible with theirs, as told by [`constants::DISCOVER_METHOD`].
+//!
+//! Calls of the methods the spec marks with `x-idempotency-key` carry an
+//! [`idempotency_key()`], which their `_with_key` variant takes from the
+//! caller: retrying a call with the key it was first made with lets the
+//! server answer its first result rather than run it twice.
+//!
+//! Clients built with `validate_responses()` check every result against
+//! the schema of its method, embedded in [`schema`], before decoding it:
+//! results breaking it fail with [`ClientError::SchemaViolation`], which
+//! points at the offending value. It's meant for servers of unknown
+//! quality, whose results would otherwise fail to decode with less to go on,
+//! or decode despite breaking constraints the types don't enforce.
+//!
+//! [`Middleware`] added with `with_middleware()` runs around every call of
+//! the spec's methods, for logging, auth refresh or metrics.
+#![allow(deprecated)]
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// std's clock panics in browsers.
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::constants;
+use crate::error_codes;
+use crate::errors::TrpError;
+use crate::health::{Health, HEALTH_METHOD};
+use crate::hex;
+use crate::schema::{self, SchemaViolation};
+#[allow(unused_imports)]
+use crate::*;
+
+/// Error of a call made through a [`Client`].
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request couldn't be sent, or its response read.
+    Http(reqwest::Error),
+    /// The server answered with an error object.
+    Rpc(TrpError),
+    /// The result doesn't decode into the type the spec declares.
+    Decode(serde_json::Error),
+    /// The result breaks the schema of its method, as checked by clients
+    /// built with `validate_responses()`.
+    SchemaViolation(SchemaViolation),
+    /// The server implements a spec version incompatible with the client's.
+    VersionMismatch { client: String, server: String },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http(err) => write!(f, "request failed: {}", err),
+            Self::Rpc(err) => write!(f, "{}", err),
+            Self::Decode(err) => write!(f, "invalid result: {}", err),
+            Self::SchemaViolation(err) => write!(f, "result breaks its schema at {}", err),
+            Self::VersionMismatch { client, server } => write!(
+                f,
+                "server implements spec version {}, incompatible with the client's {}",
+                server, client
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Http(err) => Some(err),
+            Self::Rpc(err) => Some(err),
+            Self::Decode(err) => Some(err),
+            Self::SchemaViolation(err) => Some(err),
+            Self::VersionMismatch { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Http(err)
+    }
+}
+
+impl From<TrpError> for ClientError {
+    fn from(err: TrpError) -> Self {
+        Self::Rpc(err)
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl From<SchemaViolation> for ClientError {
+    fn from(err: SchemaViolation) -> Self {
+        Self::SchemaViolation(err)
+    }
+}
+
+/// Hooks run around every call of a client, for logging, auth refresh or
+/// metrics. Both do nothing unless implemented.
+pub trait Middleware: Send + Sync {
+    /// Runs before a call of `method` is sent, and may replace its params.
+    fn on_request(&self, _method: &str, _params: &mut serde_json::Value) {}
+
+    /// Runs once a call of `method` ended, with its result or error and
+    /// how long it took.
+    fn on_response(
+        &self,
+        _method: &str,
+        _outcome: Result<&serde_json::Value, &ClientError>,
+        _latency: Duration,
+    ) {
+    }
+}
+
+/// The middleware of a client, in the order they were added.
+#[derive(Clone, Default)]
+struct Chain(Vec<Arc<dyn Middleware>>);
+
+impl fmt::Debug for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} middleware", self.0.len())
+    }
+}
+
+impl Chain {
+    /// The params of a call of `method`, as the middleware replace them.
+    fn request<P: Serialize + ?Sized>(&self, method: &str, params: &P) -> Result<serde_json::Value, ClientError> {
+        let mut params = serde_json::to_value(params)?;
+        for middleware in &self.0 {
+            middleware.on_request(method, &mut params);
+        }
+        Ok(params)
+    }
+
+    /// Hands what a call of `method` started at `start` ended with to the
+    /// middleware, then decodes its result.
+    fn response<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        outcome: Result<serde_json::Value, ClientError>,
+        start: Instant,
+    ) -> Result<R, ClientError> {
+        let latency = start.elapsed();
+        for middleware in &self.0 {
+            middleware.on_response(method, outcome.as_ref(), latency);
+        }
+        Ok(serde_json::from_value(outcome?)?)
+    }
+}
+
+/// Client of a server at a given URL. Clones share the connection pool.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    url: String,
+    next_id: Arc<AtomicU64>,
+    checks_version: bool,
+    validates_responses: bool,
+    server_version: Arc<OnceLock<Option<String>>>,
+    middleware: Chain,
+}
+
+impl Client {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_http(reqwest::Client::new(), url)
+    }
+
+    /// A client sending its requests through `http`, e.g. to set headers or
+    /// timeouts.
+    pub fn with_http(http: reqwest::Client, url: impl Into<String>) -> Self {
+        Self {
+            http,
+            url: url.into(),
+            next_id: Arc::new(AtomicU64::new(1)),
+            checks_version: true,
+            validates_responses: false,
+            server_version: Arc::default(),
+            middleware: Chain::default(),
+        }
+    }
+
+    /// A client calling the server whatever spec version it implements.
+    pub fn skip_version_check(mut self) -> Self {
+        self.checks_version = false;
+        self
+    }
+
+    /// A client checking every result against the schema of its method
+    /// before decoding it, failing with [`ClientError::SchemaViolation`].
+    pub fn validate_responses(mut self) -> Self {
+        self.validates_responses = true;
+        self
+    }
+
+    /// A client running `middleware` around every call of a method, after
+    /// the middleware added before it. Calls whose future is dropped don't
+    /// reach [`Middleware::on_response`].
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.0.push(Arc::new(middleware));
+        self
+    }
+
+    /// Checks that the server implements a spec version compatible with the
+    /// client's, as the first call does. Servers not telling their version
+    /// are assumed compatible.
+    pub async fn check_version(&self) -> Result<(), ClientError> {
+        let server = match self.server_version.get() {
+            Some(version) => version,
+            None => {
+                let discovered = self.send(constants::DISCOVER_METHOD, &[(); 0], None).await;
+                let version = server_version(discovered)?;
+                self.server_version.get_or_init(|| version)
+            }
+        };
+        check_version(server.as_deref())
+    }
+
+    /// Calls `method` with `params`, decoding its result.
+    ///
+    /// Dropping the returned future, e.g. on a timeout or in a `select!`,
+    /// aborts the call: its connection is closed, which a server run by the
+    /// crate's `server::serve` takes as a cancellation.
+    pub async fn call<P, R>(&self, method: &str, params: &P) -> Result<R, ClientError>
+    where
+        P: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        self.intercept(method, params, None).await
+    }
+
+    /// Calls `method` with `params` and `key` as its idempotency key.
+    pub async fn call_with_key<P, R>(&self, method: &str, params: &P, key: &str) -> Result<R, ClientError>
+    where
+        P: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        self.intercept(method, params, Some(key)).await
+    }
+
+    /// Sends a call of `method` through the middleware, once the server's
+    /// version checks out.
+    async fn intercept<P, R>(&self, method: &str, params: &P, key: Option<&str>) -> Result<R, ClientError>
+    where
+        P: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        if self.checks_version {
+            self.check_version().await?;
+        }
+        let params = self.middleware.request(method, params)?;
+        let start = Instant::now();
+        let outcome = self.send(method, &params, key).await;
+        self.middleware.response(method, outcome, start)
+    }
+
+    async fn send<P, R>(&self, method: &str, params: &P, key: Option<&str>) -> Result<R, ClientError>
+    where
+        P: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let request = request(&self.next_id, method, params);
+        let mut post = self.http.post(&self.url).json(&request);
+        if let Some(key) = key {
+            post = post.header(constants::IDEMPOTENCY_HEADER, key);
+        }
+        // JSON-RPC errors may come with an HTTP error status, so the body is
+        // read whatever the status.
+        let response = post.send().await?;
+        result(method, response.json().await?, self.validates_responses)
+    }
+
+    /// Calls `trp.resolve`: Resolve a proto transaction into a signed transaction envelope.
+    pub async fn resolve(&self, params: &ResolveParams) -> Result<TxEnvelope, ClientError> {
+        self.call(constants::TRP_RESOLVE, params).await
+    }
+
+    /// Calls `trp.submit`: Submit a resolved transaction with witnesses.
+    /// The call gets a fresh idempotency key; retries should go through
+    /// [`Self::submit_with_key`].
+    pub async fn submit(&self, params: &SubmitParams) -> Result<SubmitResponse, ClientError> {
+        self.submit_with_key(params, &idempotency_key()).await
+    }
+
+    /// Calls `trp.submit` with `key` as its idempotency key. A call
+    /// retried with the key it was first made with is answered its first
+    /// result rather than run again.
+    pub async fn submit_with_key(&self, params: &SubmitParams, key: &str) -> Result<SubmitResponse, ClientError> {
+        self.call_with_key(constants::TRP_SUBMIT, params, key).await
+    }
+
+    /// Calls `trp.checkStatus`: Check how far submitted transactions have made it on chain.
+    pub async fn check_status(&self, params: &CheckStatusParams) -> Result<CheckStatusResponse, ClientError> {
+        self.call(constants::TRP_CHECK_STATUS, params).await
+    }
+
+    /// The health of the server, answered to [`HEALTH_METHOD`]. It skips the
+    /// version check, so that probes work across versions.
+    pub async fn health(&self) -> Result<Health, ClientError> {
+        self.send(HEALTH_METHOD, &[(); 0], None).await
+    }
+}
+
+/// A fresh idempotency key: 128 bits from the OS's random source, as hex.
+///
+/// # Panics
+///
+/// When the OS has no random source to read from.
+pub fn idempotency_key() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("the OS random source is unavailable");
+    hex::encode(&bytes)
+}
+
+fn request<P: Serialize + ?Sized>(next_id: &AtomicU64, method: &str, params: &P) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": next_id.fetch_add(1, Ordering::Relaxed),
+        "method": method,
+        "params": params,
+    })
+}
+
+/// The result of a response body to `method`, or its error object. The
+/// result is checked against the schema of the method first if `validates`.
+fn result<R: DeserializeOwned>(
+    method: &str,
+    mut body: serde_json::Value,
+    validates: bool,
+) -> Result<R, ClientError> {
+    if let Some(error) = body.get_mut("error") {
+        let error: TrpError = serde_json::from_value(error.take())?;
+        return Err(error.into());
+    }
+    let result = body["result"].take();
+    if validates {
+        schema::validate_result(method, &result)?;
+    }
+    Ok(serde_json::from_value(result)?)
+}
+
+/// Spec version of the client, checked against the server's.
+const VERSION: Option<&str> = Some(constants::SPEC_VERSION);
+
+/// The `info.version` of the spec a server answered, `None` if it doesn't
+/// answer the discover method or leaves the version out.
+fn server_version(
+    discovered: Result<serde_json::Value, ClientError>,
+) -> Result<Option<String>, ClientError> {
+    match discovered {
+        Ok(spec) => Ok(spec["info"]["version"].as_str().map(String::from)),
+        Err(ClientError::Rpc(err)) if err.code == error_codes::METHOD_NOT_FOUND => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Versions are compatible when they agree up to their first non-zero
+/// component, as semver caret requirements do: `1.2.0` and `1.5.1` are,
+/// `0.1.0` and `0.2.0` aren't.
+fn check_version(server: Option<&str>) -> Result<(), ClientError> {
+    let (Some(client), Some(server)) = (VERSION, server) else {
+        return Ok(());
+    };
+    let significant = |version: &str| {
+        let mut components = Vec::new();
+        for component in version.split(['.', '-', '+']).take(3) {
+            components.push(component.to_string());
+            if component != "0" {
+                break;
+            }
+        }
+        components
+    };
+    if significant(client) == significant(server) {
+        return Ok(());
+    }
+    Err(ClientError::VersionMismatch {
+        client: client.to_string(),
+        server: server.to_string(),
+    })
+}
+
+/// A client blocking the calling thread on each call, with the methods of
+/// the async [`Client`](super::Client). Requires the `blocking` feature.
+/// Its calls can't be aborted; set a timeout on its reqwest client instead.
+#[cfg(feature = "blocking")]
+pub mod blocking {
+    use super::*;
+
+    /// Client of a server at a given URL. Clones share the connection pool.
+    #[derive(Debug, Clone)]
+    pub struct Client {
+        http: reqwest::blocking::Client,
+        url: String,
+        next_id: Arc<AtomicU64>,
+        checks_version: bool,
+        validates_responses: bool,
+        server_version: Arc<OnceLock<Option<String>>>,
+        middleware: Chain,
+    }
+
+    impl Client {
+        pub fn new(url: impl Into<String>) -> Self {
+            Self::with_http(reqwest::blocking::Client::new(), url)
+        }
+
+        /// A client sending its requests through `http`, e.g. to set headers
+        /// or timeouts.
+        pub fn with_http(http: reqwest::blocking::Client, url: impl Into<String>) -> Self {
+            Self {
+                http,
+                url: url.into(),
+                next_id: Arc::new(AtomicU64::new(1)),
+                checks_version: true,
+                validates_responses: false,
+                server_version: Arc::default(),
+                middleware: Chain::default(),
+            }
+        }
+
+        /// A client calling the server whatever spec version it implements.
+        pub fn skip_version_check(mut self) -> Self {
+            self.checks_version = false;
+            self
+        }
+
+        /// A client checking every result against the schema of its method
+        /// before decoding it, failing with [`ClientError::SchemaViolation`].
+        pub fn validate_responses(mut self) -> Self {
+            self.validates_responses = true;
+            self
+        }
+
+        /// A client running `middleware` around every call of a method,
+        /// after the middleware added before it.
+        pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+            self.middleware.0.push(Arc::new(middleware));
+            self
+        }
+
+        /// Checks that the server implements a spec version compatible with
+        /// the client's, as the first call does. Servers not telling their
+        /// version are assumed compatible.
+        pub fn check_version(&self) -> Result<(), ClientError> {
+            let server = match self.server_version.get() {
+                Some(version) => version,
+                None => {
+                    let discovered = self.send(constants::DISCOVER_METHOD, &[(); 0], None);
+                    let version = server_version(discovered)?;
+                    self.server_version.get_or_init(|| version)
+                }
+            };
+            check_version(server.as_deref())
+        }
+
+        /// Calls `method` with `params`, decoding its result.
+        pub fn call<P, R>(&self, method: &str, params: &P) -> Result<R, ClientError>
+        where
+            P: Serialize + ?Sized,
+            R: DeserializeOwned,
+        {
+            self.intercept(method, params, None)
+        }
+
+        /// Calls `method` with `params` and `key` as its idempotency key.
+        pub fn call_with_key<P, R>(&self, method: &str, params: &P, key: &str) -> Result<R, ClientError>
+        where
+            P: Serialize + ?Sized,
+            R: DeserializeOwned,
+        {
+            self.intercept(method, params, Some(key))
+        }
+
+        /// Sends a call of `method` through the middleware, once the
+        /// server's version checks out.
+        fn intercept<P, R>(&self, method: &str, params: &P, key: Option<&str>) -> Result<R, ClientError>
+        where
+            P: Serialize + ?Sized,
+            R: DeserializeOwned,
+        {
+            if self.checks_version {
+                self.check_version()?;
+            }
+            let params = self.middleware.request(method, params)?;
+            let start = Instant::now();
+            let outcome = self.send(method, &params, key);
+            self.middleware.response(method, outcome, start)
+        }
+
+        fn send<P, R>(&self, method: &str, params: &P, key: Option<&str>) -> Result<R, ClientError>
+        where
+            P: Serialize + ?Sized,
+            R: DeserializeOwned,
+        {
+            let request = request(&self.next_id, method, params);
+            let mut post = self.http.post(&self.url).json(&request);
+            if let Some(key) = key {
+                post = post.header(constants::IDEMPOTENCY_HEADER, key);
+            }
+            let response = post.send()?;
+            result(method, response.json()?, self.validates_responses)
+        }
+
+        /// Calls `trp.resolve`: Resolve a proto transaction into a signed transaction envelope.
+        pub fn resolve(&self, params: &ResolveParams) -> Result<TxEnvelope, ClientError> {
+            self.call(constants::TRP_RESOLVE, params)
+        }
+
+        /// Calls `trp.submit`: Submit a resolved transaction with witnesses.
+        /// The call gets a fresh idempotency key; retries should go through
+        /// [`Self::submit_with_key`].
+        pub fn submit(&self, params: &SubmitParams) -> Result<SubmitResponse, ClientError> {
+            self.submit_with_key(params, &idempotency_key())
+        }
+
+        /// Calls `trp.submit` with `key` as its idempotency key. A
+        /// call retried with the key it was first made with is answered its
+        /// first result rather than run again.
+        pub fn submit_with_key(&self, params: &SubmitParams, key: &str) -> Result<SubmitResponse, ClientError> {
+            self.call_with_key(constants::TRP_SUBMIT, params, key)
+        }
+
+        /// Calls `trp.checkStatus`: Check how far submitted transactions have made it on chain.
+        pub fn check_status(&self, params: &CheckStatusParams) -> Result<CheckStatusResponse, ClientError> {
+            self.call(constants::TRP_CHECK_STATUS, params)
+        }
+
+        /// The health of the server, answered to [`HEALTH_METHOD`]. It skips
+        /// the version check, so that probes work across versions.
+        pub fn health(&self) -> Result<Health, ClientError> {
+            self.send(HEALTH_METHOD, &[(); 0], None)
+        }
+    }
+}
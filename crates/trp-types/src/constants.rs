@@ -0,0 +1,568 @@
+// Generated by cargo xtask gen --lang rust
+
+/// Version of the spec these bindings were generated from.
+pub const SPEC_VERSION: &str = "0.1.1";
+
+pub const TRP_RESOLVE: &str = "trp.resolve";
+/// Params of `trp.resolve` in declared order.
+pub const TRP_RESOLVE_PARAMS: &[&str] = &["tir", "args", "env"];
+
+pub const TRP_SUBMIT: &str = "trp.submit";
+/// Params of `trp.submit` in declared order.
+pub const TRP_SUBMIT_PARAMS: &[&str] = &["tx", "witnesses"];
+
+pub const TRP_CHECK_STATUS: &str = "trp.checkStatus";
+/// Params of `trp.checkStatus` in declared order.
+pub const TRP_CHECK_STATUS_PARAMS: &[&str] = &["hashes"];
+
+
+/// OpenRPC method answering the spec document of a server, whose version
+/// clients check.
+pub const DISCOVER_METHOD: &str = "rpc.discover";
+
+/// HTTP header carrying the idempotency key of a call: servers answer the
+/// calls repeating the key of an earlier one with its result rather than
+/// running them again.
+pub const IDEMPOTENCY_HEADER: &str = "idempotency-key";
+
+/// The OpenRPC spec these bindings were generated from.
+pub const OPENRPC_SPEC: &str = r##"{
+  "openrpc": "1.3.2",
+  "info": {
+    "title": "Transaction Resolver Protocol",
+    "version": "0.1.1",
+    "description": "OpenRPC schema inferred from the TRP Rust client implementation.",
+    "license": {
+      "name": "Apache-2.0"
+    }
+  },
+  "servers": [
+    {
+      "name": "default",
+      "url": "http://localhost:8545"
+    }
+  ],
+  "methods": [
+    {
+      "name": "trp.resolve",
+      "summary": "Resolve a proto transaction into a signed transaction envelope.",
+      "tags": [
+        {
+          "name": "resolve"
+        }
+      ],
+      "params": {
+        "name": "ResolveParams",
+        "schema": {
+          "$ref": "#/components/schemas/ResolveParams"
+        }
+      },
+      "result": {
+        "name": "TxEnvelope",
+        "schema": {
+          "$ref": "#/components/schemas/TxEnvelope"
+        }
+      },
+      "errors": [
+        {
+          "code": -32000,
+          "message": "Unsupported TIR",
+          "data": {
+            "$ref": "#/components/schemas/UnsupportedTirDiagnostic"
+          }
+        },
+        {
+          "code": -32001,
+          "message": "Missing transaction argument",
+          "data": {
+            "$ref": "#/components/schemas/MissingTxArgDiagnostic"
+          }
+        },
+        {
+          "code": -32002,
+          "message": "Input not resolved",
+          "x-retryable": true,
+          "data": {
+            "$ref": "#/components/schemas/InputNotResolvedDiagnostic"
+          }
+        },
+        {
+          "code": -32003,
+          "message": "Tx script failure",
+          "data": {
+            "$ref": "#/components/schemas/TxScriptFailureDiagnostic"
+          }
+        }
+      ],
+      "examples": [
+        {
+          "name": "resolve-transfer",
+          "params": [
+            {
+              "name": "ResolveParams",
+              "value": {
+                "tir": {
+                  "version": "v1alpha8",
+                  "bytecode": "a1646e616d65687472616e73666572",
+                  "encoding": "hex"
+                },
+                "args": {
+                  "quantity": 1000000
+                },
+                "env": {}
+              }
+            }
+          ],
+          "result": {
+            "name": "TxEnvelope",
+            "value": {
+              "tx": "84a300d90102818258200000000000000000000000000000000000000000000000000000000000000000000181a200581d60000000000000000000000000000000000000000000000000000000001a000f4240021a0002a8b1a0f5f6",
+              "hash": "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "trp.submit",
+      "summary": "Submit a resolved transaction with witnesses.",
+      "x-idempotency-key": true,
+      "tags": [
+        {
+          "name": "submit"
+        }
+      ],
+      "params": {
+        "name": "SubmitParams",
+        "schema": {
+          "$ref": "#/components/schemas/SubmitParams"
+        }
+      },
+      "result": {
+        "name": "SubmitResponse",
+        "schema": {
+          "$ref": "#/components/schemas/SubmitResponse"
+        }
+      },
+      "examples": [
+        {
+          "name": "submit-vkey-witness",
+          "params": [
+            {
+              "name": "SubmitParams",
+              "value": {
+                "tx": {
+                  "encoding": "hex",
+                  "payload": "84a300d90102818258200000000000000000000000000000000000000000000000000000000000000000000181a200581d60000000000000000000000000000000000000000000000000000000001a000f4240021a0002a8b1a0f5f6"
+                },
+                "witnesses": [
+                  {
+                    "type": "vkey",
+                    "key": {
+                      "encoding": "hex",
+                      "payload": "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+                    },
+                    "signature": {
+                      "encoding": "hex",
+                      "payload": "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+                    }
+                  }
+                ]
+              }
+            }
+          ],
+          "result": {
+            "name": "SubmitResponse",
+            "value": {
+              "hash": "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "trp.checkStatus",
+      "summary": "Check how far submitted transactions have made it on chain.",
+      "tags": [
+        {
+          "name": "submit"
+        }
+      ],
+      "params": {
+        "name": "CheckStatusParams",
+        "schema": {
+          "$ref": "#/components/schemas/CheckStatusParams"
+        }
+      },
+      "result": {
+        "name": "CheckStatusResponse",
+        "schema": {
+          "$ref": "#/components/schemas/CheckStatusResponse"
+        }
+      },
+      "examples": [
+        {
+          "name": "check-status-confirmed",
+          "params": [
+            {
+              "name": "CheckStatusParams",
+              "value": {
+                "hashes": [
+                  "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+                ]
+              }
+            }
+          ],
+          "result": {
+            "name": "CheckStatusResponse",
+            "value": {
+              "statuses": {
+                "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001": {
+                  "stage": "confirmed",
+                  "confirmations": 3
+                }
+              }
+            }
+          }
+        }
+      ]
+    }
+  ],
+  "components": {
+    "schemas": {
+      "BytesEnvelope": {
+        "title": "BytesEnvelope",
+        "type": "object",
+        "properties": {
+          "encoding": {
+            "type": "string"
+          },
+          "payload": {
+            "type": "string"
+          }
+        },
+        "required": [
+          "encoding",
+          "payload"
+        ]
+      },
+      "SubmitResponse": {
+        "title": "SubmitResponse",
+        "type": "object",
+        "properties": {
+          "hash": {
+            "type": "string"
+          }
+        },
+        "required": [
+          "hash"
+        ]
+      },
+      "TxEnvelope": {
+        "title": "TxEnvelope",
+        "type": "object",
+        "properties": {
+          "tx": {
+            "type": "string",
+            "description": "Hex-encoded transaction bytes"
+          },
+          "hash": {
+            "type": "string",
+            "description": "Transaction hash"
+          }
+        },
+        "required": [
+          "tx",
+          "hash"
+        ]
+      },
+      "TirInfo": {
+        "title": "TirInfo",
+        "type": "object",
+        "properties": {
+          "version": {
+            "type": "string",
+            "description": "Version of the TIR the bytecode is compiled to",
+            "x-supported": [
+              "v1alpha8",
+              "v1beta0"
+            ]
+          },
+          "bytecode": {
+            "type": "string"
+          },
+          "encoding": {
+            "type": "string",
+            "description": "Encoding for the TIR bytecode, e.g. base64 or hex"
+          }
+        },
+        "required": [
+          "version",
+          "bytecode",
+          "encoding"
+        ]
+      },
+      "SubmitWitness": {
+        "title": "SubmitWitness",
+        "type": "object",
+        "properties": {
+          "type": {
+            "type": "string",
+            "enum": [
+              "vkey"
+            ]
+          },
+          "key": {
+            "$ref": "#/components/schemas/BytesEnvelope"
+          },
+          "signature": {
+            "$ref": "#/components/schemas/BytesEnvelope"
+          }
+        },
+        "required": [
+          "type",
+          "key",
+          "signature"
+        ]
+      },
+      "UnsupportedTirDiagnostic": {
+        "title": "UnsupportedTirDiagnostic",
+        "type": "object",
+        "properties": {
+          "provided": {
+            "type": "string"
+          },
+          "expected": {
+            "type": "string"
+          }
+        },
+        "required": [
+          "provided",
+          "expected"
+        ]
+      },
+      "MissingTxArgDiagnostic": {
+        "title": "MissingTxArgDiagnostic",
+        "type": "object",
+        "properties": {
+          "key": {
+            "type": "string"
+          },
+          "type": {
+            "type": "string"
+          }
+        },
+        "required": [
+          "key",
+          "type"
+        ]
+      },
+      "SearchSpaceDiagnostic": {
+        "title": "SearchSpaceDiagnostic",
+        "type": "object",
+        "properties": {
+          "matched": {
+            "type": "array",
+            "items": {
+              "type": "string"
+            }
+          },
+          "by_address_count": {
+            "type": [
+              "integer",
+              "null"
+            ]
+          },
+          "by_asset_class_count": {
+            "type": [
+              "integer",
+              "null"
+            ]
+          },
+          "by_ref_count": {
+            "type": [
+              "integer",
+              "null"
+            ]
+          }
+        },
+        "required": [
+          "matched"
+        ]
+      },
+      "InputQueryDiagnostic": {
+        "title": "InputQueryDiagnostic",
+        "type": "object",
+        "properties": {
+          "address": {
+            "type": [
+              "string",
+              "null"
+            ],
+            "format": "address"
+          },
+          "min_amount": {
+            "type": "object",
+            "additionalProperties": {
+              "type": "string"
+            }
+          },
+          "refs": {
+            "type": "array",
+            "items": {
+              "type": "string"
+            }
+          },
+          "support_many": {
+            "type": "boolean"
+          },
+          "collateral": {
+            "type": "boolean"
+          }
+        },
+        "required": [
+          "min_amount",
+          "refs",
+          "support_many",
+          "collateral"
+        ]
+      },
+      "InputNotResolvedDiagnostic": {
+        "title": "InputNotResolvedDiagnostic",
+        "type": "object",
+        "properties": {
+          "name": {
+            "type": "string"
+          },
+          "query": {
+            "$ref": "#/components/schemas/InputQueryDiagnostic"
+          },
+          "search_space": {
+            "$ref": "#/components/schemas/SearchSpaceDiagnostic"
+          }
+        },
+        "required": [
+          "name",
+          "query",
+          "search_space"
+        ]
+      },
+      "TxScriptFailureDiagnostic": {
+        "title": "TxScriptFailureDiagnostic",
+        "type": "object",
+        "properties": {
+          "logs": {
+            "type": "array",
+            "items": {
+              "type": "string"
+            }
+          }
+        },
+        "required": [
+          "logs"
+        ]
+      },
+      "SubmitParams": {
+        "title": "SubmitParams",
+        "type": "object",
+        "properties": {
+          "tx": {
+            "$ref": "#/components/schemas/BytesEnvelope"
+          },
+          "witnesses": {
+            "type": "array",
+            "items": {
+              "$ref": "#/components/schemas/SubmitWitness"
+            }
+          }
+        },
+        "required": [
+          "tx",
+          "witnesses"
+        ]
+      },
+      "ResolveParams": {
+        "title": "ResolveParams",
+        "type": "object",
+        "properties": {
+          "tir": {
+            "$ref": "#/components/schemas/TirInfo"
+          },
+          "args": {
+            "type": "object"
+          },
+          "env": {
+            "type": "object"
+          }
+        },
+        "required": [
+          "tir",
+          "args",
+          "env"
+        ]
+      },
+      "TxStatus": {
+        "title": "TxStatus",
+        "type": "object",
+        "properties": {
+          "stage": {
+            "type": "string",
+            "enum": [
+              "pending",
+              "propagated",
+              "acknowledged",
+              "confirmed",
+              "finalized",
+              "dropped",
+              "rolledBack",
+              "unknown"
+            ],
+            "description": "Lifecycle stage of the transaction: accepted by the server (pending), sent to peers (propagated), seen in a peer's mempool (acknowledged), included in a block (confirmed), past the rollback horizon (finalized), evicted without being included (dropped), undone by a rollback (rolledBack), or never seen (unknown)"
+          },
+          "confirmations": {
+            "type": "integer",
+            "minimum": 0,
+            "description": "Blocks on top of the one including the transaction"
+          }
+        },
+        "required": [
+          "stage",
+          "confirmations"
+        ]
+      },
+      "CheckStatusParams": {
+        "title": "CheckStatusParams",
+        "type": "object",
+        "properties": {
+          "hashes": {
+            "type": "array",
+            "items": {
+              "type": "string"
+            },
+            "minItems": 1,
+            "description": "Hashes of the transactions, as answered by trp.submit"
+          }
+        },
+        "required": [
+          "hashes"
+        ]
+      },
+      "CheckStatusResponse": {
+        "title": "CheckStatusResponse",
+        "type": "object",
+        "properties": {
+          "statuses": {
+            "type": "object",
+            "additionalProperties": {
+              "$ref": "#/components/schemas/TxStatus"
+            },
+            "description": "Status of each hash asked for"
+          }
+        },
+        "required": [
+          "statuses"
+        ]
+      }
+    }
+  }
+}"##;
@@ -0,0 +1,152 @@
+// Generated by cargo xtask gen --lang rust
+//! The JSON-RPC error codes of the protocol, split into ranges so that the
+//! errors of JSON-RPC itself, of transports and of the spec's methods never
+//! share a code.
+
+use std::ops::RangeInclusive;
+
+use serde::Serialize;
+
+use super::errors::{TrpError, TrpErrorKind};
+
+/// Who answers errors of a given code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// Errors of JSON-RPC itself, reserved by it.
+    Protocol,
+    /// Errors answered by servers, proxies and their JSON-RPC library, whatever the method.
+    Transport,
+    /// Errors the spec's methods declare.
+    Application,
+}
+
+/// Codes of protocol errors.
+pub const PROTOCOL: RangeInclusive<i64> = -32768..=-32100;
+
+/// Codes of transport errors.
+pub const TRANSPORT: RangeInclusive<i64> = -32099..=-32004;
+
+/// Codes of application errors.
+pub const APPLICATION: RangeInclusive<i64> = -32003..=-32000;
+
+/// `Parse error`, a protocol error.
+pub const PARSE_ERROR: i64 = -32700;
+
+/// `Invalid request`, a protocol error.
+pub const INVALID_REQUEST: i64 = -32600;
+
+/// `Method not found`, a protocol error.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/// `Invalid params`, a protocol error.
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// `Internal error`, a protocol error.
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// `No upstream available`, a transport error.
+pub const UPSTREAM_UNAVAILABLE: i64 = -32004;
+
+/// `Batched requests are not supported`, a transport error.
+pub const BATCHES_NOT_SUPPORTED: i64 = -32005;
+
+/// `Too many subscriptions`, a transport error.
+pub const TOO_MANY_SUBSCRIPTIONS: i64 = -32006;
+
+/// `Request is too big`, a transport error.
+pub const OVERSIZED_REQUEST: i64 = -32007;
+
+/// `Response is too big`, a transport error.
+pub const OVERSIZED_RESPONSE: i64 = -32008;
+
+/// `Server is busy`, a transport error.
+pub const SERVER_IS_BUSY: i64 = -32009;
+
+/// `Batch request is too big`, a transport error.
+pub const TOO_BIG_BATCH_REQUEST: i64 = -32010;
+
+/// `Batch response is too big`, a transport error.
+pub const TOO_BIG_BATCH_RESPONSE: i64 = -32011;
+
+/// `Rate limit exceeded`, a transport error.
+pub const RATE_LIMITED: i64 = -32012;
+
+/// `Idempotency key conflict`, a transport error.
+pub const IDEMPOTENCY_CONFLICT: i64 = -32013;
+
+/// `Request cancelled`, a transport error.
+pub const REQUEST_CANCELLED: i64 = -32800;
+
+/// Codes of the errors the spec declares.
+pub const APPLICATION_CODES: &[i64] = &[super::errors::UNSUPPORTED_TIR, super::errors::MISSING_TRANSACTION_ARGUMENT, super::errors::INPUT_NOT_RESOLVED, super::errors::TX_SCRIPT_FAILURE];
+
+impl ErrorClass {
+    /// The class of `code`, `None` for codes outside of every range.
+    pub fn of(code: i64) -> Option<Self> {
+        match code {
+            REQUEST_CANCELLED => Some(Self::Transport),
+            code if PROTOCOL.contains(&code) => Some(Self::Protocol),
+            code if TRANSPORT.contains(&code) => Some(Self::Transport),
+            code if APPLICATION.contains(&code) => Some(Self::Application),
+            _ => None,
+        }
+    }
+
+    /// The codes of the class.
+    pub fn range(self) -> RangeInclusive<i64> {
+        match self {
+            Self::Protocol => PROTOCOL,
+            Self::Transport => TRANSPORT,
+            Self::Application => APPLICATION,
+        }
+    }
+}
+
+impl TrpError {
+    /// The error the spec declares as `kind`, with its declared message.
+    pub fn declared(kind: TrpErrorKind) -> Self {
+        TrpError {
+            code: kind.code(),
+            message: kind.message().to_string(),
+            data: None,
+        }
+    }
+
+    /// The error the spec declares as `kind`, carrying `data`.
+    pub fn declared_with_data<T: Serialize>(
+        kind: TrpErrorKind,
+        data: &T,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(TrpError {
+            data: Some(serde_json::to_value(data)?),
+            ..Self::declared(kind)
+        })
+    }
+
+    /// A protocol or transport error, as answered whatever the method.
+    ///
+    /// # Panics
+    ///
+    /// If `code` is neither: errors of the methods must be declared by the
+    /// spec, and built with [`TrpError::declared`].
+    pub fn reserved(code: i64, message: impl Into<String>) -> Self {
+        assert!(
+            matches!(
+                ErrorClass::of(code),
+                Some(ErrorClass::Protocol | ErrorClass::Transport)
+            ),
+            "{} is not a protocol or transport error code",
+            code
+        );
+        TrpError {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// The class of the error's code.
+    pub fn class(&self) -> Option<ErrorClass> {
+        ErrorClass::of(self.code)
+    }
+}
\ No newline at end of file
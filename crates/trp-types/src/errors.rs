@@ -0,0 +1,121 @@
+// Generated by cargo xtask gen --lang rust
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::types;
+
+/// Code of the `Unsupported TIR` error.
+pub const UNSUPPORTED_TIR: i64 = -32000;
+
+/// Code of the `Missing transaction argument` error.
+pub const MISSING_TRANSACTION_ARGUMENT: i64 = -32001;
+
+/// Code of the `Input not resolved` error.
+pub const INPUT_NOT_RESOLVED: i64 = -32002;
+
+/// Code of the `Tx script failure` error.
+pub const TX_SCRIPT_FAILURE: i64 = -32003;
+
+/// The errors declared by the spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrpErrorKind {
+    /// Unsupported TIR (-32000)
+    UnsupportedTir,
+    /// Missing transaction argument (-32001)
+    MissingTransactionArgument,
+    /// Input not resolved (-32002)
+    InputNotResolved,
+    /// Tx script failure (-32003)
+    TxScriptFailure,
+}
+
+impl TrpErrorKind {
+    pub fn from_code(code: i64) -> Option<Self> {
+        match code {
+            UNSUPPORTED_TIR => Some(Self::UnsupportedTir),
+            MISSING_TRANSACTION_ARGUMENT => Some(Self::MissingTransactionArgument),
+            INPUT_NOT_RESOLVED => Some(Self::InputNotResolved),
+            TX_SCRIPT_FAILURE => Some(Self::TxScriptFailure),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> i64 {
+        match *self {
+            Self::UnsupportedTir => UNSUPPORTED_TIR,
+            Self::MissingTransactionArgument => MISSING_TRANSACTION_ARGUMENT,
+            Self::InputNotResolved => INPUT_NOT_RESOLVED,
+            Self::TxScriptFailure => TX_SCRIPT_FAILURE,
+        }
+    }
+
+    /// The message the spec declares for the error.
+    pub fn message(&self) -> &'static str {
+        match *self {
+            Self::UnsupportedTir => "Unsupported TIR",
+            Self::MissingTransactionArgument => "Missing transaction argument",
+            Self::InputNotResolved => "Input not resolved",
+            Self::TxScriptFailure => "Tx script failure",
+        }
+    }
+
+    /// Whether a call failing with this error may succeed when retried.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::InputNotResolved)
+    }
+}
+
+/// Error object of a failed call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrpError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl TrpError {
+    /// The declared error this is, if any.
+    pub fn kind(&self) -> Option<TrpErrorKind> {
+        TrpErrorKind::from_code(self.code)
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_some_and(|kind| kind.is_retryable())
+    }
+
+    /// Decodes `data` into the type the spec declares for this error. `None`
+    /// when the error declares no data type or carries no data.
+    pub fn typed_data(&self) -> Option<Result<TrpErrorData, serde_json::Error>> {
+        let data = self.data.clone()?;
+        match self.code {
+            UNSUPPORTED_TIR => Some(serde_json::from_value(data).map(TrpErrorData::UnsupportedTir)),
+            MISSING_TRANSACTION_ARGUMENT => Some(serde_json::from_value(data).map(TrpErrorData::MissingTransactionArgument)),
+            INPUT_NOT_RESOLVED => Some(serde_json::from_value(data).map(TrpErrorData::InputNotResolved)),
+            TX_SCRIPT_FAILURE => Some(serde_json::from_value(data).map(TrpErrorData::TxScriptFailure)),
+            _ => None,
+        }
+    }
+}
+
+/// Typed `data` of the declared errors.
+#[derive(Debug, Clone)]
+pub enum TrpErrorData {
+    /// Data of `Unsupported TIR` errors.
+    UnsupportedTir(types::UnsupportedTirDiagnostic),
+    /// Data of `Missing transaction argument` errors.
+    MissingTransactionArgument(types::MissingTxArgDiagnostic),
+    /// Data of `Input not resolved` errors.
+    InputNotResolved(types::InputNotResolvedDiagnostic),
+    /// Data of `Tx script failure` errors.
+    TxScriptFailure(types::TxScriptFailureDiagnostic),
+}
+
+impl fmt::Display for TrpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for TrpError {}
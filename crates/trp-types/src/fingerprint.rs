@@ -0,0 +1,149 @@
+// Generated by cargo xtask gen --lang rust
+//! Canonical JSON and fingerprints of requests, so that requests meaning
+//! the same thing hash the same whatever the order of their members and
+//! the formatting of their strings and numbers. Caches, idempotency keys
+//! and matching recorded calls should all key requests by their
+//! [`request_fingerprint`].
+//!
+//! serde_json only parses decimal numbers to the nearest double, as other
+//! languages do, with its `float_roundtrip` feature, which the generated
+//! crate enables; without it, some fingerprint differently than elsewhere.
+
+use std::fmt::Write as _;
+
+use serde_json::{Number, Value};
+use sha2::{Digest, Sha256};
+
+use super::hex;
+
+/// The SHA-256 of the canonical JSON of `[method, params]`. Calls without
+/// params are fingerprinted with `null`.
+pub fn request_fingerprint(method: &str, params: &Value) -> [u8; 32] {
+    let mut text = String::from("[");
+    write_string(&mut text, method);
+    text.push(',');
+    write_value(&mut text, params);
+    text.push(']');
+    Sha256::digest(text.as_bytes()).into()
+}
+
+/// A fingerprint as lowercase hex.
+pub fn to_hex(fingerprint: &[u8; 32]) -> String {
+    hex::encode(fingerprint)
+}
+
+/// `value` as canonical JSON, after RFC 8785: without whitespace, with the
+/// members of objects sorted by the UTF-16 code units of their names,
+/// strings escaping only quotes, backslashes and control characters, and
+/// numbers in the shortest form that reads back the same. Unlike RFC 8785,
+/// integers are written exactly rather than as doubles, so that amounts
+/// past 2^53 stay apart; numbers with a fraction of zero are written as
+/// integers, `1.0` as `1`.
+pub fn canonical_json(value: &Value) -> String {
+    let mut text = String::new();
+    write_value(&mut text, value);
+    text
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+        Value::Number(number) => write_number(out, number),
+        Value::String(text) => write_string(out, text),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, (name, value)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, name);
+                out.push(':');
+                write_value(out, value);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_number(out: &mut String, number: &Number) {
+    if let Some(n) = number.as_u64() {
+        let _ = write!(out, "{}", n);
+    } else if let Some(n) = number.as_i64() {
+        let _ = write!(out, "{}", n);
+    } else if let Some(n) = number.as_f64() {
+        // Integral doubles in the range of integers read as one; `-0.0`
+        // among them, as `0`.
+        if n.fract() == 0.0 && n.abs() < 2f64.powi(64) {
+            let _ = write!(out, "{}", n as i128);
+        } else {
+            write_double(out, n);
+        }
+    }
+}
+
+/// `n` as JavaScript's `Number.prototype.toString` writes it, which RFC
+/// 8785 follows: plain up to 21 integral digits and down to 6 leading
+/// zeros, with an exponent otherwise.
+fn write_double(out: &mut String, n: f64) {
+    if n < 0.0 {
+        out.push('-');
+    }
+    // The shortest digits reading back as `n`, with the exponent of the
+    // first one.
+    let scientific = format!("{:e}", n.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let e = exponent.parse::<i32>().unwrap_or(0) + 1;
+    if k <= e && e <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat_n('0', (e - k) as usize));
+    } else if 0 < e && e <= 21 {
+        let (integral, fraction) = digits.split_at(e as usize);
+        let _ = write!(out, "{}.{}", integral, fraction);
+    } else if -6 < e && e <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', -e as usize));
+        out.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        out.push_str(first);
+        if !rest.is_empty() {
+            let _ = write!(out, ".{}", rest);
+        }
+        let _ = write!(out, "e{}{}", if e > 0 { "+" } else { "-" }, (e - 1).abs());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+// Generated by cargo xtask gen --lang rust
+//! Health report of a server, for load balancers and monitoring.
+
+use serde::{Deserialize, Serialize};
+
+/// Method answering the [`Health`] of a server, besides those of the spec.
+pub const HEALTH_METHOD: &str = "trp.health";
+
+/// Path answering the [`Health`] of a server to plain HTTP `GET` requests,
+/// with status 200 when it is ready and 503 otherwise.
+pub const HEALTH_PATH: &str = "/healthz";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Health {
+    /// Whether the server can serve calls, its backend being reachable.
+    pub ready: bool,
+    /// Version of the spec the server implements.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Seconds since the server started.
+    pub uptime: u64,
+    /// Why the backend is unreachable, when it is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
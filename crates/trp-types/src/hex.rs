@@ -0,0 +1,29 @@
+// Generated by cargo xtask gen --lang rust
+//! Hex text of bytes, as the protocol writes hashes, keys, transactions and
+//! bytecode.
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// `bytes` as lowercase hex.
+pub fn encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        text.push(char::from(DIGITS[usize::from(byte >> 4)]));
+        text.push(char::from(DIGITS[usize::from(byte & 0xf)]));
+    }
+    text
+}
+
+/// The bytes `text` encodes in either case, failing at the offset of the
+/// first character that isn't a hex digit, or at the end of text of an odd
+/// length.
+pub fn decode(text: &str) -> Result<Vec<u8>, usize> {
+    let digit = |i: usize| match text.as_bytes().get(i) {
+        Some(c) => char::from(*c).to_digit(16).ok_or(i),
+        None => Err(i),
+    };
+    (0..text.len())
+        .step_by(2)
+        .map(|i| Ok((digit(i)? << 4 | digit(i + 1)?) as u8))
+        .collect()
+}
\ No newline at end of file
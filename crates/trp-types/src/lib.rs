@@ -0,0 +1,33 @@
+// Generated by cargo xtask gen --lang rust
+//! Types of the Transaction Resolver Protocol, generated from its OpenRPC spec.
+
+mod types;
+
+pub use types::*;
+
+pub mod address;
+pub mod cbor;
+pub mod constants;
+pub mod error_codes;
+pub mod errors;
+pub mod fingerprint;
+pub mod health;
+pub mod hex;
+pub mod schema;
+pub mod tir;
+pub mod utxo;
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "server")]
+pub mod backend;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "proxy")]
+pub mod proxy;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "client")]
+pub mod watch;
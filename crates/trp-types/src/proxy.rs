@@ -0,0 +1,218 @@
+// Generated by cargo xtask gen --lang rust
+//! A server forwarding calls to several upstream servers, failing over
+//! between them. Requires the `proxy` feature.
+//!
+//! Calls go round-robin to the upstreams deemed healthy. An upstream is
+//! deemed unhealthy when it can't be reached, implements an incompatible
+//! spec version or answers results the spec doesn't allow, and healthy
+//! again once it answers a call or a health probe. Unhealthy upstreams are
+//! tried again after [`RETRY_AFTER`], and [`Handler::ready`] probes them
+//! all. Error objects of upstreams are answered as they are.
+//!
+//! Calls of the methods the spec marks with `x-idempotency-key` are
+//! forwarded with an idempotency key, and only fail over when their
+//! upstream couldn't be connected to: one that may have run them isn't
+//! asked to run them again elsewhere.
+#![allow(deprecated)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::client::{idempotency_key, Client, ClientError};
+use crate::constants;
+use crate::error_codes;
+use crate::errors::TrpError;
+use crate::server::Handler;
+#[allow(unused_imports)]
+use crate::*;
+
+/// Code of the error answering calls no upstream could answer.
+pub const UPSTREAM_UNAVAILABLE_CODE: i32 = error_codes::UPSTREAM_UNAVAILABLE as i32;
+
+/// Time after which an unhealthy upstream is tried again.
+pub const RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// A [`Handler`] forwarding calls to upstream servers. Clones share the
+/// upstreams and their health.
+#[derive(Debug, Clone)]
+pub struct Proxy {
+    upstreams: Arc<[Upstream]>,
+    next: Arc<AtomicUsize>,
+}
+
+#[derive(Debug)]
+struct Upstream {
+    url: String,
+    client: Client,
+    /// When the upstream was deemed unhealthy, if it is.
+    down: Mutex<Option<Instant>>,
+}
+
+impl Upstream {
+    fn set_healthy(&self, healthy: bool) {
+        let mut down = self.down.lock().unwrap_or_else(PoisonError::into_inner);
+        *down = match (healthy, *down) {
+            (true, _) => None,
+            (false, Some(since)) => Some(since),
+            (false, None) => Some(Instant::now()),
+        };
+    }
+
+    fn down_since(&self) -> Option<Instant> {
+        *self.down.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Proxy {
+    /// A proxy to the servers at `urls`, all deemed healthy at first.
+    pub fn new<I>(urls: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let upstreams = urls
+            .into_iter()
+            .map(|url| {
+                let url = url.into();
+                Upstream {
+                    client: Client::new(url.clone()),
+                    url,
+                    down: Mutex::new(None),
+                }
+            })
+            .collect();
+        Self {
+            upstreams,
+            next: Arc::default(),
+        }
+    }
+
+    /// The URLs of the upstreams deemed healthy.
+    pub fn healthy(&self) -> Vec<&str> {
+        self.upstreams
+            .iter()
+            .filter(|upstream| upstream.down_since().is_none())
+            .map(|upstream| upstream.url.as_str())
+            .collect()
+    }
+
+    /// The upstreams to try a call on, in order: the healthy ones from the
+    /// next in turn, then the unhealthy ones due for another try.
+    fn candidates(&self) -> Vec<&Upstream> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let len = self.upstreams.len();
+        let rotated = (0..len).map(|i| &self.upstreams[(start + i) % len]);
+        let (healthy, unhealthy): (Vec<&Upstream>, Vec<&Upstream>) =
+            rotated.partition(|upstream| upstream.down_since().is_none());
+        let due = unhealthy
+            .into_iter()
+            .filter(|upstream| upstream.down_since().is_some_and(|since| since.elapsed() >= RETRY_AFTER));
+        healthy.into_iter().chain(due).collect()
+    }
+
+    /// Calls `method` on the candidate upstreams until one answers a result
+    /// passing `validate`, or an error object.
+    async fn forward<P, R>(
+        &self,
+        method: &str,
+        params: &P,
+        idempotent: bool,
+        validate: fn(&R) -> Result<(), ValidationError>,
+    ) -> Result<R, TrpError>
+    where
+        P: Serialize + Sync,
+        R: DeserializeOwned,
+    {
+        let key = idempotent.then(idempotency_key);
+        let mut failure = "no upstream is available".to_string();
+        for upstream in self.candidates() {
+            let result = match &key {
+                Some(key) => upstream.client.call_with_key::<P, R>(method, params, key).await,
+                None => upstream.client.call::<P, R>(method, params).await,
+            };
+            let err = match result {
+                Ok(result) => match validate(&result) {
+                    Ok(()) => {
+                        upstream.set_healthy(true);
+                        return Ok(result);
+                    }
+                    Err(err) => format!("{} answered an invalid result: {}", upstream.url, err),
+                },
+                Err(ClientError::Rpc(err)) => {
+                    upstream.set_healthy(true);
+                    return Err(err);
+                }
+                Err(err) => {
+                    let unsent = matches!(&err, ClientError::Http(err) if err.is_connect());
+                    let err = format!("{}: {}", upstream.url, err);
+                    if idempotent && !unsent {
+                        upstream.set_healthy(false);
+                        return Err(unavailable(err));
+                    }
+                    err
+                }
+            };
+            upstream.set_healthy(false);
+            if idempotent {
+                return Err(unavailable(err));
+            }
+            failure = err;
+        }
+        Err(unavailable(failure))
+    }
+}
+
+fn unavailable(message: String) -> TrpError {
+    TrpError {
+        code: UPSTREAM_UNAVAILABLE_CODE.into(),
+        message: "No upstream available".to_string(),
+        data: Some(serde_json::Value::String(message)),
+    }
+}
+
+/// Accepts any result, for the methods whose result has no schema.
+#[allow(dead_code)]
+fn any<R>(_: &R) -> Result<(), ValidationError> {
+    Ok(())
+}
+
+impl Handler for Proxy {
+    async fn resolve(&self, params: ResolveParams) -> Result<TxEnvelope, TrpError> {
+        let validate = TxEnvelope::validate;
+        self.forward(constants::TRP_RESOLVE, &params, false, validate).await
+    }
+
+
+    async fn submit(&self, params: SubmitParams) -> Result<SubmitResponse, TrpError> {
+        let validate = SubmitResponse::validate;
+        self.forward(constants::TRP_SUBMIT, &params, true, validate).await
+    }
+
+
+    async fn check_status(&self, params: CheckStatusParams) -> Result<CheckStatusResponse, TrpError> {
+        let validate = CheckStatusResponse::validate;
+        self.forward(constants::TRP_CHECK_STATUS, &params, false, validate).await
+    }
+
+
+    /// Probes the health of every upstream; ready if any is.
+    async fn ready(&self) -> Result<(), String> {
+        let mut ready = false;
+        for upstream in self.upstreams.iter() {
+            let healthy = match upstream.client.health().await {
+                Ok(health) => health.ready,
+                Err(_) => false,
+            };
+            upstream.set_healthy(healthy);
+            ready |= healthy;
+        }
+        match ready {
+            true => Ok(()),
+            false => Err("no upstream is healthy".to_string()),
+        }
+    }
+}
\ No newline at end of file
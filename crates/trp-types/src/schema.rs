@@ -0,0 +1,716 @@
+// Generated by cargo xtask gen --lang rust
+//! The JSON Schemas of the components, as exported to `bundle.schema.json`,
+//! and a validator of raw JSON against them. Clients built with
+//! `validate_responses()` check every result against the schema of its
+//! method before decoding it, to report where a server strays from the spec
+//! rather than a bare decoding error.
+//!
+//! The validator knows the keywords of JSON Schema 2020-12 that constrain
+//! values, except `dependentSchemas`, `if`/`then`/`else` and the
+//! `unevaluated` ones; `format` and annotations are ignored. Patterns are
+//! searched for with the `regex` crate.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use serde_json::{Map, Value};
+
+#[allow(unused_imports)]
+use crate::constants;
+
+/// Every component under the `$defs` of a single document.
+pub const BUNDLE: &str = r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "bundle.schema.json",
+  "title": "Transaction Resolver Protocol",
+  "$defs": {
+    "BytesEnvelope": {
+      "title": "BytesEnvelope",
+      "type": "object",
+      "required": [
+        "encoding",
+        "payload"
+      ],
+      "properties": {
+        "encoding": {
+          "type": "string"
+        },
+        "payload": {
+          "type": "string"
+        }
+      }
+    },
+    "CheckStatusParams": {
+      "title": "CheckStatusParams",
+      "type": "object",
+      "required": [
+        "hashes"
+      ],
+      "properties": {
+        "hashes": {
+          "description": "Hashes of the transactions, as answered by trp.submit",
+          "type": "array",
+          "items": {
+            "type": "string"
+          },
+          "minItems": 1
+        }
+      }
+    },
+    "CheckStatusResponse": {
+      "title": "CheckStatusResponse",
+      "type": "object",
+      "required": [
+        "statuses"
+      ],
+      "properties": {
+        "statuses": {
+          "description": "Status of each hash asked for",
+          "type": "object",
+          "additionalProperties": {
+            "$ref": "#/$defs/TxStatus"
+          }
+        }
+      }
+    },
+    "InputNotResolvedDiagnostic": {
+      "title": "InputNotResolvedDiagnostic",
+      "type": "object",
+      "required": [
+        "name",
+        "query",
+        "search_space"
+      ],
+      "properties": {
+        "name": {
+          "type": "string"
+        },
+        "query": {
+          "$ref": "#/$defs/InputQueryDiagnostic"
+        },
+        "search_space": {
+          "$ref": "#/$defs/SearchSpaceDiagnostic"
+        }
+      }
+    },
+    "InputQueryDiagnostic": {
+      "title": "InputQueryDiagnostic",
+      "type": "object",
+      "required": [
+        "collateral",
+        "min_amount",
+        "refs",
+        "support_many"
+      ],
+      "properties": {
+        "address": {
+          "type": [
+            "string",
+            "null"
+          ],
+          "format": "address"
+        },
+        "collateral": {
+          "type": "boolean"
+        },
+        "min_amount": {
+          "type": "object",
+          "additionalProperties": {
+            "type": "string"
+          }
+        },
+        "refs": {
+          "type": "array",
+          "items": {
+            "type": "string"
+          }
+        },
+        "support_many": {
+          "type": "boolean"
+        }
+      }
+    },
+    "MissingTxArgDiagnostic": {
+      "title": "MissingTxArgDiagnostic",
+      "type": "object",
+      "required": [
+        "key",
+        "type"
+      ],
+      "properties": {
+        "key": {
+          "type": "string"
+        },
+        "type": {
+          "type": "string"
+        }
+      }
+    },
+    "ResolveParams": {
+      "title": "ResolveParams",
+      "type": "object",
+      "required": [
+        "args",
+        "env",
+        "tir"
+      ],
+      "properties": {
+        "args": {
+          "type": "object"
+        },
+        "env": {
+          "type": "object"
+        },
+        "tir": {
+          "$ref": "#/$defs/TirInfo"
+        }
+      }
+    },
+    "SearchSpaceDiagnostic": {
+      "title": "SearchSpaceDiagnostic",
+      "type": "object",
+      "required": [
+        "matched"
+      ],
+      "properties": {
+        "by_address_count": {
+          "type": [
+            "integer",
+            "null"
+          ]
+        },
+        "by_asset_class_count": {
+          "type": [
+            "integer",
+            "null"
+          ]
+        },
+        "by_ref_count": {
+          "type": [
+            "integer",
+            "null"
+          ]
+        },
+        "matched": {
+          "type": "array",
+          "items": {
+            "type": "string"
+          }
+        }
+      }
+    },
+    "SubmitParams": {
+      "title": "SubmitParams",
+      "type": "object",
+      "required": [
+        "tx",
+        "witnesses"
+      ],
+      "properties": {
+        "tx": {
+          "$ref": "#/$defs/BytesEnvelope"
+        },
+        "witnesses": {
+          "type": "array",
+          "items": {
+            "$ref": "#/$defs/SubmitWitness"
+          }
+        }
+      }
+    },
+    "SubmitResponse": {
+      "title": "SubmitResponse",
+      "type": "object",
+      "required": [
+        "hash"
+      ],
+      "properties": {
+        "hash": {
+          "type": "string"
+        }
+      }
+    },
+    "SubmitWitness": {
+      "title": "SubmitWitness",
+      "type": "object",
+      "required": [
+        "key",
+        "signature",
+        "type"
+      ],
+      "properties": {
+        "key": {
+          "$ref": "#/$defs/BytesEnvelope"
+        },
+        "signature": {
+          "$ref": "#/$defs/BytesEnvelope"
+        },
+        "type": {
+          "type": "string",
+          "enum": [
+            "vkey"
+          ]
+        }
+      }
+    },
+    "TirInfo": {
+      "title": "TirInfo",
+      "type": "object",
+      "required": [
+        "bytecode",
+        "encoding",
+        "version"
+      ],
+      "properties": {
+        "bytecode": {
+          "type": "string"
+        },
+        "encoding": {
+          "description": "Encoding for the TIR bytecode, e.g. base64 or hex",
+          "type": "string"
+        },
+        "version": {
+          "description": "Version of the TIR the bytecode is compiled to",
+          "type": "string",
+          "x-supported": [
+            "v1alpha8",
+            "v1beta0"
+          ]
+        }
+      }
+    },
+    "TxEnvelope": {
+      "title": "TxEnvelope",
+      "type": "object",
+      "required": [
+        "hash",
+        "tx"
+      ],
+      "properties": {
+        "hash": {
+          "description": "Transaction hash",
+          "type": "string"
+        },
+        "tx": {
+          "description": "Hex-encoded transaction bytes",
+          "type": "string"
+        }
+      }
+    },
+    "TxScriptFailureDiagnostic": {
+      "title": "TxScriptFailureDiagnostic",
+      "type": "object",
+      "required": [
+        "logs"
+      ],
+      "properties": {
+        "logs": {
+          "type": "array",
+          "items": {
+            "type": "string"
+          }
+        }
+      }
+    },
+    "TxStatus": {
+      "title": "TxStatus",
+      "type": "object",
+      "required": [
+        "confirmations",
+        "stage"
+      ],
+      "properties": {
+        "confirmations": {
+          "description": "Blocks on top of the one including the transaction",
+          "type": "integer",
+          "minimum": 0
+        },
+        "stage": {
+          "description": "Lifecycle stage of the transaction: accepted by the server (pending), sent to peers (propagated), seen in a peer's mempool (acknowledged), included in a block (confirmed), past the rollback horizon (finalized), evicted without being included (dropped), undone by a rollback (rolledBack), or never seen (unknown)",
+          "type": "string",
+          "enum": [
+            "pending",
+            "propagated",
+            "acknowledged",
+            "confirmed",
+            "finalized",
+            "dropped",
+            "rolledBack",
+            "unknown"
+          ]
+        }
+      }
+    },
+    "UnsupportedTirDiagnostic": {
+      "title": "UnsupportedTirDiagnostic",
+      "type": "object",
+      "required": [
+        "expected",
+        "provided"
+      ],
+      "properties": {
+        "expected": {
+          "type": "string"
+        },
+        "provided": {
+          "type": "string"
+        }
+      }
+    }
+  }
+}"##;
+
+/// Component the params object of `method` is an instance of, `None` for
+/// the methods whose params the spec leaves open.
+pub fn params_schema(method: &str) -> Option<&'static str> {
+    match method {
+        constants::TRP_RESOLVE => Some("ResolveParams"),
+        constants::TRP_SUBMIT => Some("SubmitParams"),
+        constants::TRP_CHECK_STATUS => Some("CheckStatusParams"),
+        _ => None,
+    }
+}
+
+/// Component the result of `method` is an instance of, `None` for the
+/// methods whose result the spec leaves open.
+pub fn result_schema(method: &str) -> Option<&'static str> {
+    match method {
+        constants::TRP_RESOLVE => Some("TxEnvelope"),
+        constants::TRP_SUBMIT => Some("SubmitResponse"),
+        constants::TRP_CHECK_STATUS => Some("CheckStatusResponse"),
+        _ => None,
+    }
+}
+
+/// A value breaking the schema it was checked against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Component the value was checked against.
+    pub schema: String,
+    /// Location of the offending value as a JSON path, e.g. `$.tx` or
+    /// `$.statuses["9f2c"]`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} (schema {})", self.path, self.message, self.schema)
+    }
+}
+
+impl std::error::Error for SchemaViolation {}
+
+/// Checks `value` against the schema of component `name`, reporting the
+/// first violation found.
+pub fn validate(name: &str, value: &Value) -> Result<(), SchemaViolation> {
+    let violation = |path: String, message: String| SchemaViolation {
+        schema: name.to_string(),
+        path,
+        message,
+    };
+    let schema = defs()
+        .get(name)
+        .ok_or_else(|| violation("$".to_string(), format!("no component named `{}`", name)))?;
+    check(schema, value, "$").map_err(|(path, message)| violation(path, message))
+}
+
+/// Checks the result of a call of `method` against its schema. Results of
+/// methods without one are all valid.
+pub fn validate_result(method: &str, value: &Value) -> Result<(), SchemaViolation> {
+    match result_schema(method) {
+        Some(name) => validate(name, value),
+        None => Ok(()),
+    }
+}
+
+fn defs() -> &'static Map<String, Value> {
+    static DEFS: OnceLock<Map<String, Value>> = OnceLock::new();
+    DEFS.get_or_init(|| {
+        let bundle: Value = serde_json::from_str(BUNDLE).expect("the bundle is valid JSON");
+        match bundle.get("$defs") {
+            Some(Value::Object(defs)) => defs.clone(),
+            _ => Map::new(),
+        }
+    })
+}
+
+/// Where a violation is, and what it is.
+type Violation = (String, String);
+
+fn check(schema: &Value, value: &Value, path: &str) -> Result<(), Violation> {
+    let fail = |message: String| Err((path.to_string(), message));
+    let schema = match schema {
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => return fail("no value is allowed here".to_string()),
+        Value::Object(schema) => schema,
+        _ => return Ok(()),
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let target = reference
+            .strip_prefix("#/$defs/")
+            .and_then(|name| defs().get(name));
+        match target {
+            Some(target) => check(target, value, path)?,
+            None => return fail(format!("unresolved reference `{}`", reference)),
+        }
+    }
+
+    if let Some(types) = schema.get("type") {
+        let allowed: Vec<&str> = match types {
+            Value::String(name) => vec![name],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|name| is_type(value, name)) {
+            return fail(format!("expected {}, found {}", allowed.join(" or "), type_of(value)));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if !equal(value, expected) {
+            return fail(format!("expected {}", expected));
+        }
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.iter().any(|allowed| equal(value, allowed)) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            return fail(format!("expected one of {}, found {}", allowed.join(", "), value));
+        }
+    }
+
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        for branch in all {
+            check(branch, value, path)?;
+        }
+    }
+    if let Some(Value::Array(any)) = schema.get("anyOf") {
+        if !any.iter().any(|branch| check(branch, value, path).is_ok()) {
+            return fail(format!("matches none of the {} alternatives of anyOf", any.len()));
+        }
+    }
+    if let Some(Value::Array(one)) = schema.get("oneOf") {
+        let matching = one
+            .iter()
+            .filter(|branch| check(branch, value, path).is_ok())
+            .count();
+        if matching != 1 {
+            return fail(format!(
+                "matches {} of the {} alternatives of oneOf, instead of exactly one",
+                matching,
+                one.len()
+            ));
+        }
+    }
+    if let Some(not) = schema.get("not") {
+        if check(not, value, path).is_ok() {
+            return fail("matches the schema of not".to_string());
+        }
+    }
+
+    match value {
+        Value::Object(members) => check_object(schema, members, path),
+        Value::Array(items) => check_array(schema, items, path),
+        Value::String(text) => check_string(schema, text, path),
+        Value::Number(_) => check_number(schema, value, path),
+        _ => Ok(()),
+    }
+}
+
+fn check_object(schema: &Map<String, Value>, members: &Map<String, Value>, path: &str) -> Result<(), Violation> {
+    let fail = |message: String| Err((path.to_string(), message));
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !members.contains_key(name) {
+                return fail(format!("missing required member `{}`", name));
+            }
+        }
+    }
+    if let Some(min) = schema.get("minProperties").and_then(Value::as_u64) {
+        if (members.len() as u64) < min {
+            return fail(format!("expected at least {} members, found {}", min, members.len()));
+        }
+    }
+    if let Some(max) = schema.get("maxProperties").and_then(Value::as_u64) {
+        if members.len() as u64 > max {
+            return fail(format!("expected at most {} members, found {}", max, members.len()));
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let patterns = schema.get("patternProperties").and_then(Value::as_object);
+    for (name, member) in members {
+        let member_path = member_path(path, name);
+        if let Some(names) = schema.get("propertyNames") {
+            check(names, &Value::from(name.as_str()), &member_path)?;
+        }
+        let mut evaluated = false;
+        if let Some(property) = properties.and_then(|properties| properties.get(name)) {
+            check(property, member, &member_path)?;
+            evaluated = true;
+        }
+        for (pattern, property) in patterns.into_iter().flatten() {
+            if matches(pattern, name, &member_path)? {
+                check(property, member, &member_path)?;
+                evaluated = true;
+            }
+        }
+        if !evaluated {
+            match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => {
+                    return Err((member_path, "unexpected member".to_string()));
+                }
+                Some(additional) => check(additional, member, &member_path)?,
+                None => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_array(schema: &Map<String, Value>, items: &[Value], path: &str) -> Result<(), Violation> {
+    let fail = |message: String| Err((path.to_string(), message));
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if (items.len() as u64) < min {
+            return fail(format!("expected at least {} items, found {}", min, items.len()));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if items.len() as u64 > max {
+            return fail(format!("expected at most {} items, found {}", max, items.len()));
+        }
+    }
+    if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+        for (i, item) in items.iter().enumerate() {
+            if items[..i].iter().any(|earlier| equal(earlier, item)) {
+                return Err((format!("{}[{}]", path, i), "duplicate item".to_string()));
+            }
+        }
+    }
+
+    let prefix = match schema.get("prefixItems") {
+        Some(Value::Array(prefix)) => prefix.as_slice(),
+        _ => &[],
+    };
+    for (i, item) in items.iter().enumerate() {
+        let item_path = format!("{}[{}]", path, i);
+        match prefix.get(i) {
+            Some(schema) => check(schema, item, &item_path)?,
+            None => match schema.get("items") {
+                Some(Value::Bool(false)) => {
+                    return Err((item_path, format!("expected at most {} items", prefix.len())));
+                }
+                Some(items) => check(items, item, &item_path)?,
+                None => {}
+            },
+        }
+    }
+    if let Some(contains) = schema.get("contains") {
+        if !items.iter().any(|item| check(contains, item, path).is_ok()) {
+            return fail("no item matches the schema of contains".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn check_string(schema: &Map<String, Value>, text: &str, path: &str) -> Result<(), Violation> {
+    let fail = |message: String| Err((path.to_string(), message));
+    // Lengths count code points, as JSON Schema does.
+    let length = text.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if length < min {
+            return fail(format!("expected at least {} characters, found {}", min, length));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if length > max {
+            return fail(format!("expected at most {} characters, found {}", max, length));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        if !matches(pattern, text, path)? {
+            return fail(format!("expected a match of `{}`", pattern));
+        }
+    }
+    Ok(())
+}
+
+fn check_number(schema: &Map<String, Value>, value: &Value, path: &str) -> Result<(), Violation> {
+    let fail = |message: String| Err((path.to_string(), message));
+    let n = value.as_f64().unwrap_or_default();
+    let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+    if let Some(min) = bound("minimum").filter(|min| n < *min) {
+        return fail(format!("expected at least {}, found {}", min, value));
+    }
+    if let Some(min) = bound("exclusiveMinimum").filter(|min| n <= *min) {
+        return fail(format!("expected more than {}, found {}", min, value));
+    }
+    if let Some(max) = bound("maximum").filter(|max| n > *max) {
+        return fail(format!("expected at most {}, found {}", max, value));
+    }
+    if let Some(max) = bound("exclusiveMaximum").filter(|max| n >= *max) {
+        return fail(format!("expected less than {}, found {}", max, value));
+    }
+    if let Some(factor) = bound("multipleOf").filter(|factor| *factor > 0.0) {
+        if (n / factor).fract() != 0.0 {
+            return fail(format!("expected a multiple of {}, found {}", factor, value));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `pattern` matches anywhere in `text`.
+fn matches(pattern: &str, text: &str, path: &str) -> Result<bool, Violation> {
+    match regex::Regex::new(pattern) {
+        Ok(regex) => Ok(regex.is_match(text)),
+        Err(err) => Err((path.to_string(), format!("invalid pattern `{}`: {}", pattern, err))),
+    }
+}
+
+fn is_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        _ => true,
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Equality of JSON Schema, under which `1` and `1.0` are the same number.
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x == y || ((x.is_f64() || y.is_f64()) && x.as_f64() == y.as_f64()),
+        (Value::Array(x), Value::Array(y)) => x.len() == y.len() && x.iter().zip(y).all(|(x, y)| equal(x, y)),
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len() && x.iter().all(|(name, x)| y.get(name).is_some_and(|y| equal(x, y)))
+        }
+        _ => a == b,
+    }
+}
+
+/// The path of member `name` of the value at `path`: `$.tx`, or
+/// `$["not an identifier"]`.
+pub(crate) fn member_path(path: &str, name: &str) -> String {
+    let plain = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        format!("{}.{}", path, name)
+    } else {
+        format!("{}[{}]", path, Value::from(name))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,1020 @@
+// Generated by cargo xtask gen --lang rust
+//! JSON-RPC methods for a jsonrpsee server, and a server enforcing
+//! [`Limits`] on its clients, letting them cancel their calls, answering
+//! retried calls once ([`Idempotency`]), logging them ([`Logging`]) and
+//! reporting its [`Health`]. Requires the `server` feature.
+#![allow(deprecated)]
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime};
+
+use jsonrpsee::core::{BoxError, EmptyServerParams};
+
+use jsonrpsee::server::middleware::rpc::{ResponseFuture, RpcServiceBuilder, RpcServiceT};
+use jsonrpsee::server::{
+    serve_with_graceful_shutdown, stop_channel, BatchRequestConfig, HttpRequest, MethodResponse,
+    HttpBody, HttpResponse, Methods, ResponsePayload, Server, ServerHandle,
+};
+use jsonrpsee::tokio;
+use jsonrpsee::tokio::net::TcpListener;
+use jsonrpsee::types::{ErrorObjectOwned, Request};
+use jsonrpsee::RpcModule;
+use serde::{Deserialize, Serialize};
+pub use tokio_util::sync::CancellationToken;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tower_http::decompression::RequestDecompressionLayer;
+
+use crate::constants;
+use crate::error_codes;
+use crate::errors::TrpError;
+use crate::fingerprint::{request_fingerprint, to_hex};
+use crate::health::{Health, HEALTH_METHOD, HEALTH_PATH};
+#[allow(unused_imports)]
+use crate::*;
+
+/// The methods of the spec, implemented by a server. Errors are answered as
+/// they are, so handlers should return the errors the spec declares.
+pub trait Handler: Send + Sync + 'static {
+    /// `trp.resolve`: Resolve a proto transaction into a signed transaction envelope.
+    fn resolve(
+        &self,
+        params: ResolveParams,
+    ) -> impl Future<Output = Result<TxEnvelope, TrpError>> + Send;
+
+    /// `trp.submit`: Submit a resolved transaction with witnesses.
+    fn submit(
+        &self,
+        params: SubmitParams,
+    ) -> impl Future<Output = Result<SubmitResponse, TrpError>> + Send;
+
+    /// `trp.checkStatus`: Check how far submitted transactions have made it on chain.
+    fn check_status(
+        &self,
+        params: CheckStatusParams,
+    ) -> impl Future<Output = Result<CheckStatusResponse, TrpError>> + Send;
+
+    /// Whether the backend the handler relies on is reachable, or why not,
+    /// probed by health checks. Reachable unless implemented.
+    fn ready(&self) -> impl Future<Output = Result<(), String>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// A module routing each method of the spec to `handler`, to be served with
+/// `jsonrpsee::server::Server`. Params are validated before they reach the
+/// handler. The module also answers [`HEALTH_METHOD`], counting uptime from
+/// its creation, and [`constants::DISCOVER_METHOD`] with the spec.
+pub fn rpc_module<H: Handler>(handler: H) -> RpcModule<H> {
+    let mut module = RpcModule::new(handler);
+    let started = Instant::now();
+    module
+        .register_async_method(HEALTH_METHOD, move |_, handler, _| async move {
+            let ready = handler.ready().await;
+            Ok::<_, ErrorObjectOwned>(Health {
+                ready: ready.is_ok(),
+                version: Some(constants::SPEC_VERSION.to_string()),
+                uptime: started.elapsed().as_secs(),
+                error: ready.err(),
+            })
+        })
+        .expect("method names are unique");
+    module
+        .register_method(constants::DISCOVER_METHOD, |_, _, _| discover())
+        .expect("method names are unique");
+    module
+        .register_async_method(constants::TRP_RESOLVE, |params, handler, _| async move {
+            let params: ResolveParams = parse_params(params, ParamStructure::Either)?;
+            params.validate().map_err(|err| {
+                let code = jsonrpsee::types::error::INVALID_PARAMS_CODE;
+                ErrorObjectOwned::owned(code, err.to_string(), None::<()>)
+            })?;
+            handler.resolve(params).await.map_err(error_object)
+        })
+        .expect("method names are unique");
+    module
+        .register_async_method(constants::TRP_SUBMIT, |params, handler, _| async move {
+            let params: SubmitParams = parse_params(params, ParamStructure::Either)?;
+            params.validate().map_err(|err| {
+                let code = jsonrpsee::types::error::INVALID_PARAMS_CODE;
+                ErrorObjectOwned::owned(code, err.to_string(), None::<()>)
+            })?;
+            handler.submit(params).await.map_err(error_object)
+        })
+        .expect("method names are unique");
+    module
+        .register_async_method(constants::TRP_CHECK_STATUS, |params, handler, _| async move {
+            let params: CheckStatusParams = parse_params(params, ParamStructure::Either)?;
+            params.validate().map_err(|err| {
+                let code = jsonrpsee::types::error::INVALID_PARAMS_CODE;
+                ErrorObjectOwned::owned(code, err.to_string(), None::<()>)
+            })?;
+            handler.check_status(params).await.map_err(error_object)
+        })
+        .expect("method names are unique");
+    module
+}
+
+/// The embedded spec document.
+fn discover() -> serde_json::Value {
+    serde_json::from_str(constants::OPENRPC_SPEC).expect("the embedded spec is JSON")
+}
+
+/// How a method takes its params, as its `paramStructure` declares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+enum ParamStructure {
+    /// As the params object.
+    ByName,
+    /// As an array holding the params object.
+    ByPosition,
+    /// Either way.
+    Either,
+}
+
+/// Parses the params of a call of a method taking `structure`, answering
+/// params sent the other way with an invalid params error.
+fn parse_params<T: serde::de::DeserializeOwned>(
+    params: jsonrpsee::types::Params<'_>,
+    structure: ParamStructure,
+) -> Result<T, ErrorObjectOwned> {
+    let invalid = |message: &str| {
+        let code = jsonrpsee::types::error::INVALID_PARAMS_CODE;
+        ErrorObjectOwned::owned(code, message.to_string(), None::<()>)
+    };
+    let positional = params
+        .as_str()
+        .is_some_and(|raw| raw.trim_start().starts_with('['));
+    match (structure, positional) {
+        (ParamStructure::ByName, true) => Err(invalid("params must be sent by name, as an object")),
+        (ParamStructure::ByPosition, false) => {
+            Err(invalid("params must be sent by position, as an array"))
+        }
+        (_, true) => {
+            let [params]: [T; 1] = params.parse()?;
+            Ok(params)
+        }
+        (_, false) => params.parse(),
+    }
+}
+
+fn error_object(error: TrpError) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(error.code as i32, error.message, error.data)
+}
+
+/// How [`serve`] runs the server.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub limits: Limits,
+    /// Lets browsers call the server from other origins. `None` sends no
+    /// CORS headers, so browsers only allow same-origin calls.
+    pub cors: Option<Cors>,
+    /// Compresses responses with gzip or brotli for clients accepting them,
+    /// and decompresses requests sent so. Body size limits apply to the
+    /// decompressed request.
+    pub compression: bool,
+    /// Answers the calls repeating the idempotency key of an earlier one
+    /// once. `None` runs every call.
+    pub idempotency: Option<Idempotency>,
+    /// Logs every call. `None` logs nothing.
+    pub logging: Option<Logging>,
+}
+
+/// Cross-origin access for browser apps.
+#[derive(Debug, Clone, Default)]
+pub struct Cors {
+    /// Origins allowed to call the server, e.g. `https://app.example`.
+    /// Empty allows any origin.
+    pub origins: Vec<String>,
+}
+
+/// Code of the error answering calls over a client's [`RateLimit`], -32012.
+pub const RATE_LIMITED_CODE: i32 = error_codes::RATE_LIMITED as i32;
+
+/// Limits put on clients, so that a misbehaving one can't exhaust the
+/// server. Requests over a limit are answered with a JSON-RPC error.
+#[derive(Debug, Clone)]
+pub struct Limits {
+    /// Maximum size of a request body in bytes, answered with error
+    /// [`error_codes::OVERSIZED_REQUEST`] beyond it.
+    pub max_request_body_size: u32,
+    /// Maximum number of calls in a batch, answered with error
+    /// [`error_codes::TOO_BIG_BATCH_REQUEST`] beyond it. `None` allows batches of any length.
+    pub max_batch_len: Option<u32>,
+    /// Calls allowed to each client. `None` doesn't limit them.
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl Default for Limits {
+    /// 10 MiB bodies, batches of 100 calls and no rate limit.
+    fn default() -> Self {
+        Self {
+            max_request_body_size: 10 * 1024 * 1024,
+            max_batch_len: Some(100),
+            rate_limit: None,
+        }
+    }
+}
+
+/// A token bucket per client: each call takes a token, and a client out of
+/// tokens is answered with [`RATE_LIMITED_CODE`]. Each call of a batch counts.
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    /// Calls a client can make at once, after being idle.
+    pub burst: u32,
+    /// Tokens given back to each client per second.
+    pub per_second: f64,
+    pub key: ClientKey,
+}
+
+/// What tells clients apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientKey {
+    /// The IP address of the connection.
+    Ip,
+    /// The value of a request header, e.g. an API key, or the client address
+    /// a reverse proxy sets. Requests without it are keyed by IP.
+    Header(String),
+}
+
+/// Serves `handler` on `listener` as `options` say, until the returned
+/// handle stops it. Must be called within a tokio runtime.
+///
+/// Each call can be cancelled: by closing its connection, or by a
+/// [`CANCEL_METHOD`] call naming its request id on the same connection, such
+/// as a WebSocket or HTTP/2 one carrying several calls at once, which
+/// answers whether the call was still in flight. Calls of other connections
+/// can't be cancelled, whoever the client. Cancelled calls are
+/// answered with [`CANCELLED_CODE`] and their handler future is dropped;
+/// handlers doing work outside of it should watch [`cancellation()`].
+///
+/// Plain `GET` requests to [`HEALTH_PATH`] are answered the [`Health`] of
+/// the server, bypassing rate limits, for load balancers to probe.
+pub fn serve<H: Handler>(listener: TcpListener, handler: H, options: Options) -> ServerHandle {
+    let Options {
+        limits,
+        cors,
+        compression,
+        idempotency,
+        logging,
+    } = options;
+    let methods = rpc_module(handler);
+    let batches = match limits.max_batch_len {
+        Some(len) => BatchRequestConfig::Limit(len),
+        None => BatchRequestConfig::Unlimited,
+    };
+    let builder = Server::builder()
+        .max_request_body_size(limits.max_request_body_size)
+        .set_batch_request_config(batches)
+        .set_http_middleware(
+            tower::ServiceBuilder::new()
+                .option_layer(cors.map(cors_layer))
+                .layer(CompressionLayer::new().gzip(compression).br(compression))
+                .layer(
+                    RequestDecompressionLayer::new()
+                        .gzip(compression)
+                        .br(compression),
+                )
+                .layer_fn({
+                    let methods = methods.clone();
+                    move |service| Healthz {
+                        service,
+                        methods: methods.clone(),
+                    }
+                }),
+        )
+        .to_service_builder();
+    let key = limits.rate_limit.as_ref().map(|limit| limit.key.clone());
+    let logging = logging.map(Arc::new);
+    let limiter = limits.rate_limit.map(|limit| Arc::new(RateLimiter::new(limit)));
+    let calls = Arc::new(Calls::default());
+    let store = idempotency.map(|idempotency| idempotency.store);
+    let (stop_handle, server_handle) = stop_channel();
+
+    tokio::spawn(async move {
+        let mut connection = 0_u64;
+        loop {
+            let (socket, remote) = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(err) => {
+                        // Such as running out of file descriptors, which
+                        // closing connections may fix.
+                        eprintln!("failed to accept a connection: {}", err);
+                        tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                        continue;
+                    }
+                },
+                _ = stop_handle.clone().shutdown() => break,
+            };
+            connection += 1;
+            let builder = builder.clone();
+            let methods = methods.clone();
+            let key = key.clone();
+            let limiter = limiter.clone();
+            let calls = calls.clone();
+            let store = store.clone();
+            let logging = logging.clone();
+            let stop = stop_handle.clone();
+            let service = tower::service_fn(move |request: HttpRequest<_>| {
+                let client = client_of(&request, remote.ip(), key.as_ref());
+                let idempotency_key = request
+                    .headers()
+                    .get(constants::IDEMPOTENCY_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .map(String::from);
+                let (limiter, calls, store) = (limiter.clone(), calls.clone(), store.clone());
+                let logged = match (&logging, &key) {
+                    (Some(logging), Some(ClientKey::Header(name))) if logging.redacts(name) => {
+                        format!("#{}", fnv1a(&client))
+                    }
+                    _ => client.clone(),
+                };
+                let logging = logging.clone();
+                let rate_limited = client;
+                let middleware = RpcServiceBuilder::new()
+                    .layer_fn(move |service| Logged {
+                        service,
+                        logging: logging.clone(),
+                        client: logged.clone(),
+                    })
+                    .layer_fn(move |service| RateLimited {
+                        service,
+                        limiter: limiter.clone(),
+                        client: rate_limited.clone(),
+                    })
+                    .layer_fn(move |service| Cancellable {
+                        service,
+                        calls: calls.clone(),
+                        connection,
+                    })
+                    .layer_fn(move |service| Idempotent {
+                        service,
+                        store: store.clone(),
+                        key: idempotency_key.clone(),
+                    });
+                let mut service = builder
+                    .clone()
+                    .set_rpc_middleware(middleware)
+                    .build(methods.clone(), stop.clone());
+                async move { tower::Service::call(&mut service, request).await }
+            });
+            tokio::spawn(serve_with_graceful_shutdown(
+                socket,
+                service,
+                stop_handle.clone().shutdown(),
+            ));
+        }
+    });
+
+    server_handle
+}
+
+/// How long [`serve`] waits before accepting connections again after
+/// failing to.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// HTTP middleware answering `GET` requests to [`HEALTH_PATH`].
+#[derive(Clone)]
+struct Healthz<S> {
+    service: S,
+    methods: Methods,
+}
+
+impl<S, B> tower::Service<HttpRequest<B>> for Healthz<S>
+where
+    S: tower::Service<HttpRequest<B>, Response = HttpResponse, Error = BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<HttpResponse, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest<B>) -> Self::Future {
+        if request.method() != "GET" || request.uri().path() != HEALTH_PATH {
+            return Box::pin(self.service.call(request));
+        }
+        let methods = self.methods.clone();
+        Box::pin(async move {
+            let health: Health = methods.call(HEALTH_METHOD, EmptyServerParams::new()).await?;
+            let mut response = HttpResponse::new(HttpBody::from(serde_json::to_string(&health)?));
+            if !health.ready {
+                *response.status_mut() = http::StatusCode::SERVICE_UNAVAILABLE;
+            }
+            let json = http::HeaderValue::from_static("application/json");
+            response.headers_mut().insert(http::header::CONTENT_TYPE, json);
+            Ok(response)
+        })
+    }
+}
+
+fn cors_layer(cors: Cors) -> CorsLayer {
+    let origins = if cors.origins.is_empty() {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::predicate(move |origin, _| cors.origins.iter().any(|allowed| origin == allowed.as_str()))
+    };
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(Any)
+        .allow_headers(Any)
+}
+
+/// The client sending `request`, as `key` tells clients apart, by IP without
+/// one.
+fn client_of<B>(request: &HttpRequest<B>, ip: IpAddr, key: Option<&ClientKey>) -> String {
+    match key {
+        Some(ClientKey::Header(name)) => match request.headers().get(name) {
+            Some(value) => String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            None => ip.to_string(),
+        },
+        _ => ip.to_string(),
+    }
+}
+
+/// The token buckets of the clients seen.
+struct RateLimiter {
+    limit: RateLimit,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Clients past which full buckets are forgotten, as they hold no state.
+const MAX_BUCKETS: usize = 10_000;
+
+impl RateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token from the bucket of `client`, if it has one left.
+    fn take(&self, client: &str) -> bool {
+        let burst = f64::from(self.limit.burst);
+        let now = Instant::now();
+        let refill = |bucket: &Bucket| {
+            let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+            (bucket.tokens + elapsed * self.limit.per_second).min(burst)
+        };
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        if buckets.len() >= MAX_BUCKETS {
+            buckets.retain(|_, bucket| refill(bucket) < burst);
+        }
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+        });
+        bucket.tokens = refill(bucket);
+        bucket.updated = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+/// RPC middleware answering the calls of a client out of tokens.
+#[derive(Clone)]
+struct RateLimited<S> {
+    service: S,
+    limiter: Option<Arc<RateLimiter>>,
+    client: String,
+}
+
+impl<'a, S: RpcServiceT<'a>> RpcServiceT<'a> for RateLimited<S> {
+    type Future = ResponseFuture<S::Future>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        match &self.limiter {
+            Some(limiter) if !limiter.take(&self.client) => {
+                let error = ErrorObjectOwned::owned(RATE_LIMITED_CODE, "Rate limit exceeded", None::<()>);
+                ResponseFuture::ready(MethodResponse::error(request.id, error))
+            }
+            _ => ResponseFuture::future(self.service.call(request)),
+        }
+    }
+}
+
+/// Method cancelling a call of the same connection, with params
+/// `{"id": <request id>}`. It answers `true` if the call was in flight.
+pub const CANCEL_METHOD: &str = "rpc.cancel";
+
+/// Code of the error answering a cancelled call.
+pub const CANCELLED_CODE: i32 = error_codes::REQUEST_CANCELLED as i32;
+
+tokio::task_local! {
+    static CANCELLATION: CancellationToken;
+}
+
+/// The token of the call being handled, cancelled along with it. Outside of
+/// a call made through [`serve`], a token that is never cancelled.
+pub fn cancellation() -> CancellationToken {
+    CANCELLATION
+        .try_with(CancellationToken::clone)
+        .unwrap_or_default()
+}
+
+/// The calls in flight, by connection and request id. Serials tell apart
+/// calls reusing an id, so that one ending doesn't unregister the other.
+#[derive(Default)]
+struct Calls {
+    next: AtomicU64,
+    tokens: Mutex<HashMap<(u64, String), (u64, CancellationToken)>>,
+}
+
+impl Calls {
+    fn register(&self, key: (u64, String), token: CancellationToken) -> u64 {
+        let serial = self.next.fetch_add(1, Ordering::Relaxed);
+        let mut tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+        tokens.insert(key, (serial, token));
+        serial
+    }
+
+    fn cancel(&self, key: &(u64, String)) -> bool {
+        let tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+        match tokens.get(key) {
+            Some((_, token)) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Unregisters a call once it ends, answered or dropped.
+struct InFlight {
+    calls: Arc<Calls>,
+    key: (u64, String),
+    serial: u64,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        let mut tokens = self.calls.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+        if tokens.get(&self.key).is_some_and(|(serial, _)| *serial == self.serial) {
+            tokens.remove(&self.key);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CancelParams {
+    id: serde_json::Value,
+}
+
+/// RPC middleware tying each call to a token, and answering
+/// [`CANCEL_METHOD`].
+#[derive(Clone)]
+struct Cancellable<S> {
+    service: S,
+    calls: Arc<Calls>,
+    /// The serial of the connection the calls come from.
+    connection: u64,
+}
+
+impl<'a, S> RpcServiceT<'a> for Cancellable<S>
+where
+    S: RpcServiceT<'a>,
+    S::Future: 'a,
+{
+    type Future = Pin<Box<dyn Future<Output = MethodResponse> + Send + 'a>>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        if request.method_name() == CANCEL_METHOD {
+            let response = match request.params().parse::<CancelParams>() {
+                Ok(params) => {
+                    let key = (self.connection, params.id.to_string());
+                    let payload = ResponsePayload::success(self.calls.cancel(&key));
+                    MethodResponse::response(request.id, payload, usize::MAX)
+                }
+                Err(err) => MethodResponse::error(request.id, err),
+            };
+            return Box::pin(std::future::ready(response));
+        }
+
+        let id = request.id.clone().into_owned();
+        let key = (self.connection, serde_json::to_string(&id).unwrap_or_default());
+        let token = CancellationToken::new();
+        let serial = self.calls.register(key.clone(), token.clone());
+        let in_flight = InFlight {
+            calls: self.calls.clone(),
+            key,
+            serial,
+        };
+        let call = CANCELLATION.scope(token.clone(), self.service.call(request));
+        Box::pin(async move {
+            let _in_flight = in_flight;
+            tokio::select! {
+                response = call => response,
+                _ = token.cancelled() => {
+                    let error = ErrorObjectOwned::owned(CANCELLED_CODE, "Request cancelled", None::<()>);
+                    MethodResponse::error(id, error)
+                }
+            }
+        })
+    }
+}
+
+/// Code of the error answering a call whose idempotency key is held by a
+/// call still running, or was used for a call with other params, -32013.
+pub const IDEMPOTENCY_CONFLICT_CODE: i32 = error_codes::IDEMPOTENCY_CONFLICT as i32;
+
+/// Methods whose calls are deduplicated by their idempotency key, those the
+/// spec marks with `x-idempotency-key`.
+const IDEMPOTENT_METHODS: &[&str] = &[
+    constants::TRP_SUBMIT,
+];
+
+/// Deduplication of the calls of the methods the spec marks with
+/// `x-idempotency-key`, by the key clients send in
+/// [`constants::IDEMPOTENCY_HEADER`]. A call repeating the key of one that
+/// succeeded is answered its result without reaching the handler; one
+/// repeating the key of a call still running, or of a call with other
+/// params, is answered [`IDEMPOTENCY_CONFLICT_CODE`]. Failed and cancelled
+/// calls release their key, so that retrying them runs them again. Calls
+/// without a key always run.
+#[derive(Clone)]
+pub struct Idempotency {
+    pub store: Arc<dyn IdempotencyStore>,
+}
+
+impl Idempotency {
+    /// Keys kept in the server's memory, for `ttl` after their call succeeded.
+    pub fn in_memory(ttl: Duration) -> Self {
+        Self {
+            store: Arc::new(MemoryStore::new(ttl)),
+        }
+    }
+}
+
+impl fmt::Debug for Idempotency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Idempotency").finish_non_exhaustive()
+    }
+}
+
+/// Future returned by an [`IdempotencyStore`].
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// What an [`IdempotencyStore`] knows of a key being claimed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Claim {
+    /// The key was free, and is now held by the call claiming it.
+    Claimed,
+    /// A call holding the key is still running.
+    Running,
+    /// A call with the key succeeded, with this result as JSON.
+    Done(String),
+    /// The key was used for a call with other params.
+    Mismatch,
+}
+
+/// Where [`Idempotency`] keeps the keys of calls and the results of those
+/// that succeeded. [`MemoryStore`] keeps them in the process; servers
+/// running several instances should share a store, e.g. in a database.
+pub trait IdempotencyStore: Send + Sync + 'static {
+    /// Claims `key` for a call whose params have `fingerprint`, the hex of
+    /// its [`request_fingerprint`], unless a call with the key was made
+    /// already.
+    fn claim<'a>(&'a self, key: &'a str, fingerprint: &'a str) -> StoreFuture<'a, Claim>;
+
+    /// Records the result, as JSON, of the call holding `key`, or releases
+    /// the key if the call failed.
+    fn finish<'a>(&'a self, key: &'a str, result: Option<String>) -> StoreFuture<'a, ()>;
+}
+
+/// An [`IdempotencyStore`] in memory, forgetting keys `ttl` after their call
+/// succeeded.
+#[derive(Debug)]
+pub struct MemoryStore {
+    ttl: Duration,
+    keys: Mutex<HashMap<String, Stored>>,
+}
+
+#[derive(Debug)]
+struct Stored {
+    fingerprint: String,
+    /// The result, once the call succeeded.
+    result: Option<String>,
+    finished: Instant,
+}
+
+/// Keys past which those expired are forgotten.
+const MAX_KEYS: usize = 10_000;
+
+impl MemoryStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn expired(&self, stored: &Stored) -> bool {
+        stored.result.is_some() && stored.finished.elapsed() >= self.ttl
+    }
+}
+
+impl IdempotencyStore for MemoryStore {
+    fn claim<'a>(&'a self, key: &'a str, fingerprint: &'a str) -> StoreFuture<'a, Claim> {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        if keys.len() >= MAX_KEYS {
+            keys.retain(|_, stored| !self.expired(stored));
+        }
+        let claim = match keys.get(key) {
+            Some(stored) if self.expired(stored) => None,
+            Some(stored) if stored.fingerprint != fingerprint => Some(Claim::Mismatch),
+            Some(Stored { result: Some(result), .. }) => Some(Claim::Done(result.clone())),
+            Some(_) => Some(Claim::Running),
+            None => None,
+        };
+        let claim = claim.unwrap_or_else(|| {
+            let stored = Stored {
+                fingerprint: fingerprint.to_string(),
+                result: None,
+                finished: Instant::now(),
+            };
+            keys.insert(key.to_string(), stored);
+            Claim::Claimed
+        });
+        Box::pin(std::future::ready(claim))
+    }
+
+    fn finish<'a>(&'a self, key: &'a str, result: Option<String>) -> StoreFuture<'a, ()> {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        match result {
+            Some(result) => {
+                if let Some(stored) = keys.get_mut(key) {
+                    stored.result = Some(result);
+                    stored.finished = Instant::now();
+                }
+            }
+            None => {
+                keys.remove(key);
+            }
+        }
+        Box::pin(std::future::ready(()))
+    }
+}
+
+/// Releases the key of a call dropped before it finished, e.g. cancelled.
+struct Held {
+    store: Arc<dyn IdempotencyStore>,
+    key: Option<String>,
+}
+
+impl Drop for Held {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let store = self.store.clone();
+            tokio::spawn(async move { store.finish(&key, None).await });
+        }
+    }
+}
+
+/// RPC middleware deduplicating the calls of [`IDEMPOTENT_METHODS`] by the
+/// idempotency key of their HTTP request.
+#[derive(Clone)]
+struct Idempotent<S> {
+    service: S,
+    store: Option<Arc<dyn IdempotencyStore>>,
+    key: Option<String>,
+}
+
+impl<'a, S> RpcServiceT<'a> for Idempotent<S>
+where
+    S: RpcServiceT<'a> + Clone + Send + Sync + 'a,
+    S::Future: 'a,
+{
+    type Future = Pin<Box<dyn Future<Output = MethodResponse> + Send + 'a>>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let (Some(store), Some(key)) = (&self.store, &self.key) else {
+            return Box::pin(self.service.call(request));
+        };
+        if !IDEMPOTENT_METHODS.contains(&request.method_name()) {
+            return Box::pin(self.service.call(request));
+        }
+        // Keys are scoped to their method, and params compared by their
+        // fingerprint, whatever their formatting.
+        let key = format!("{}:{}", request.method_name(), key);
+        let params = request.params();
+        let raw = params.as_str().unwrap_or("null");
+        let fingerprint = serde_json::from_str::<serde_json::Value>(raw)
+            .map(|params| to_hex(&request_fingerprint(request.method_name(), &params)))
+            .unwrap_or_else(|_| raw.to_string());
+        let (store, service) = (store.clone(), self.service.clone());
+        Box::pin(async move {
+            let id = request.id.clone().into_owned();
+            let conflict = |message: &str| {
+                let error = ErrorObjectOwned::owned(IDEMPOTENCY_CONFLICT_CODE, message, None::<()>);
+                MethodResponse::error(id.clone(), error)
+            };
+            match store.claim(&key, &fingerprint).await {
+                Claim::Claimed => {}
+                Claim::Running => return conflict("A call with this idempotency key is running"),
+                Claim::Mismatch => return conflict("Idempotency key used with other params"),
+                Claim::Done(result) => {
+                    let result: serde_json::Value = serde_json::from_str(&result).unwrap_or_default();
+                    return MethodResponse::response(id, ResponsePayload::success(result), usize::MAX);
+                }
+            }
+            let mut held = Held {
+                store: store.clone(),
+                key: Some(key),
+            };
+            let response = service.call(request).await;
+            let key = held.key.take().expect("the key is held until the call ends");
+            let result = match response.is_success() {
+                true => serde_json::from_str::<serde_json::Value>(response.as_result())
+                    .ok()
+                    .map(|mut body| body["result"].take().to_string()),
+                false => None,
+            };
+            store.finish(&key, result).await;
+            response
+        })
+    }
+}
+
+/// Logging of the calls a server answers: a [`CallRecord`] each, handed to
+/// `sink` once the call ends, answered or dropped.
+#[derive(Clone)]
+pub struct Logging {
+    /// Names of the members replaced by `"[redacted]"` wherever they appear
+    /// in the logged params. A client header listed here, when it tells
+    /// clients apart, is logged hashed.
+    pub redact: Vec<String>,
+    pub sink: Arc<dyn Fn(&CallRecord) + Send + Sync>,
+}
+
+impl Default for Logging {
+    /// Writes each record as a line of JSON to stderr, redacting nothing.
+    fn default() -> Self {
+        Self {
+            redact: Vec::new(),
+            sink: Arc::new(|record| {
+                if let Ok(line) = serde_json::to_string(record) {
+                    eprintln!("{}", line);
+                }
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for Logging {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Logging")
+            .field("redact", &self.redact)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Logging {
+    fn redacts(&self, name: &str) -> bool {
+        self.redact.iter().any(|redacted| redacted.eq_ignore_ascii_case(name))
+    }
+
+    /// `value` with the members to redact replaced.
+    fn redacted(&self, mut value: serde_json::Value) -> serde_json::Value {
+        fn walk(logging: &Logging, value: &mut serde_json::Value) {
+            match value {
+                serde_json::Value::Object(members) => {
+                    for (name, member) in members {
+                        match logging.redacts(name) {
+                            true => *member = serde_json::Value::from("[redacted]"),
+                            false => walk(logging, member),
+                        }
+                    }
+                }
+                serde_json::Value::Array(items) => items.iter_mut().for_each(|item| walk(logging, item)),
+                _ => {}
+            }
+        }
+        walk(self, &mut value);
+        value
+    }
+}
+
+/// A call, as logged.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallRecord {
+    /// When the call was received, in milliseconds since the Unix epoch.
+    pub time: u64,
+    /// The client, as [`ClientKey`] tells them apart: its IP address unless
+    /// keyed by a header.
+    pub client: String,
+    pub method: String,
+    pub id: serde_json::Value,
+    /// Hex of the [`request_fingerprint`] of the call, telling calls with
+    /// the same params apart whatever is redacted.
+    pub params_hash: String,
+    pub params: serde_json::Value,
+    pub latency_ms: f64,
+    /// Code of the error answered, if any; [`CANCELLED_CODE`] for calls
+    /// dropped before their answer, e.g. as their client disconnected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<i32>,
+}
+
+/// 64-bit FNV-1a hash of `input`, as hex.
+fn fnv1a(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// The record of a call in flight, logged as cancelled if dropped before
+/// the call is answered.
+struct Unlogged {
+    logging: Arc<Logging>,
+    record: Option<CallRecord>,
+    started: Instant,
+}
+
+impl Unlogged {
+    fn log(&mut self, error_code: Option<i32>) {
+        if let Some(mut record) = self.record.take() {
+            record.latency_ms = self.started.elapsed().as_secs_f64() * 1000.0;
+            record.error_code = error_code;
+            (self.logging.sink)(&record);
+        }
+    }
+}
+
+impl Drop for Unlogged {
+    fn drop(&mut self) {
+        self.log(Some(CANCELLED_CODE));
+    }
+}
+
+/// RPC middleware logging each call.
+#[derive(Clone)]
+struct Logged<S> {
+    service: S,
+    logging: Option<Arc<Logging>>,
+    client: String,
+}
+
+impl<'a, S> RpcServiceT<'a> for Logged<S>
+where
+    S: RpcServiceT<'a>,
+    S::Future: 'a,
+{
+    type Future = Pin<Box<dyn Future<Output = MethodResponse> + Send + 'a>>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let Some(logging) = &self.logging else {
+            return Box::pin(self.service.call(request));
+        };
+        let params = request.params();
+        let params: serde_json::Value = params
+            .as_str()
+            .and_then(|params| serde_json::from_str(params).ok())
+            .unwrap_or_default();
+        let time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        let record = CallRecord {
+            time: time.as_millis() as u64,
+            client: self.client.clone(),
+            method: request.method_name().to_string(),
+            id: serde_json::to_value(&request.id).unwrap_or_default(),
+            params_hash: to_hex(&request_fingerprint(request.method_name(), &params)),
+            params: logging.redacted(params),
+            latency_ms: 0.0,
+            error_code: None,
+        };
+        let mut unlogged = Unlogged {
+            logging: logging.clone(),
+            record: Some(record),
+            started: Instant::now(),
+        };
+        let call = self.service.call(request);
+        Box::pin(async move {
+            let response = call.await;
+            unlogged.log(response.as_error_code());
+            response
+        })
+    }
+}
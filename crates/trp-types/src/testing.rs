@@ -0,0 +1,256 @@
+// Generated by cargo xtask gen --lang rust
+//! Random requests of the spec's methods, for servers to test themselves
+//! against: [`valid`] ones, whose params are generated by the `Arbitrary`
+//! implementations of the `arbitrary` module and pass their schema, and
+//! [`near_valid`] ones, the same with a single [`Mutation`] breaking their
+//! schema, which servers should answer with an error rather than a result
+//! or a crash. Requires the `testing` feature.
+//!
+//! The strategies plug into proptest; fuzz targets turn their input into a
+//! request with [`from_seed`] instead:
+//!
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| {
+//!     let request = testing::from_seed(data);
+//!     let _ = handle(request.to_json(1));
+//! });
+//! ```
+#![allow(deprecated)]
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::{Config, RngAlgorithm, TestRng, TestRunner};
+use serde_json::{Map, Value};
+
+use crate::constants;
+use crate::schema;
+#[allow(unused_imports)]
+use crate::*;
+
+/// Wire names of the methods requests are generated for.
+pub const METHODS: &[&str] = &[
+    constants::TRP_RESOLVE,
+    constants::TRP_SUBMIT,
+    constants::TRP_CHECK_STATUS,
+];
+
+/// Wire names of the methods taking their params by position, as an array
+/// holding the params object.
+const BY_POSITION: &[&str] = &[
+];
+
+/// A call of `method` with `params`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: &'static str,
+    pub params: Value,
+}
+
+impl Request {
+    /// The request as a JSON-RPC request object with `id`, its params
+    /// structured as the method takes them.
+    pub fn to_json(&self, id: u64) -> Value {
+        let params = match BY_POSITION.contains(&self.method) {
+            true => Value::Array(vec![self.params.clone()]),
+            false => self.params.clone(),
+        };
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": self.method,
+            "params": params,
+        })
+    }
+}
+
+/// How a [`near_valid`] request strays from a valid one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mutation {
+    /// The member at `path` was removed.
+    Removed { path: String },
+    /// The value at `path` was replaced with one of another JSON type.
+    Retyped { path: String },
+    /// The string at `path` was lengthened by a character, moving it out of
+    /// an enum or past a maximum length.
+    Lengthened { path: String },
+    /// The number at `path` was made negative or fractional.
+    Skewed { path: String },
+    /// The array at `path` was emptied.
+    Emptied { path: String },
+}
+
+impl Mutation {
+    /// Location of the mutated value, as a JSON path.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Removed { path }
+            | Self::Retyped { path }
+            | Self::Lengthened { path }
+            | Self::Skewed { path }
+            | Self::Emptied { path } => path,
+        }
+    }
+}
+
+/// Requests of `method` whose params pass its schema, `None` for methods
+/// not in [`METHODS`]. Methods leaving their params open get objects of
+/// arbitrary JSON scalars.
+pub fn valid(method: &str) -> Option<BoxedStrategy<Request>> {
+    let params = match method {
+        constants::TRP_RESOLVE => any::<ResolveParams>()
+            .prop_map(|params| serde_json::to_value(params).expect("params serialize"))
+            .boxed(),
+        constants::TRP_SUBMIT => any::<SubmitParams>()
+            .prop_map(|params| serde_json::to_value(params).expect("params serialize"))
+            .boxed(),
+        constants::TRP_CHECK_STATUS => any::<CheckStatusParams>()
+            .prop_map(|params| serde_json::to_value(params).expect("params serialize"))
+            .boxed(),
+        _ => return None,
+    };
+    let method = METHODS.iter().copied().find(|m| *m == method)?;
+    Some(params.prop_map(move |params| Request { method, params }).boxed())
+}
+
+/// Valid requests of any of the [`METHODS`].
+pub fn any_valid() -> BoxedStrategy<Request> {
+    let strategies: Vec<_> = METHODS.iter().filter_map(|method| valid(method)).collect();
+    proptest::strategy::Union::new(strategies).boxed()
+}
+
+/// Requests of `method` with a [`Mutation`] making their params break its
+/// schema, `None` for methods whose params have no schema.
+pub fn near_valid(method: &str) -> Option<BoxedStrategy<(Request, Mutation)>> {
+    let name = schema::params_schema(method)?;
+    let mutations = (valid(method)?, any::<Index>(), 0..5usize)
+        .prop_filter_map("no mutation breaks the schema", move |(request, at, kind)| {
+            let mut request = request;
+            let mutation = mutate(&mut request.params, at, kind)?;
+            schema::validate(name, &request.params).is_err().then_some((request, mutation))
+        });
+    Some(mutations.boxed())
+}
+
+/// Near-valid requests of any of the [`METHODS`] with a schema, of which
+/// there has to be one.
+pub fn any_near_valid() -> BoxedStrategy<(Request, Mutation)> {
+    let strategies: Vec<_> = METHODS.iter().filter_map(|method| near_valid(method)).collect();
+    proptest::strategy::Union::new(strategies).boxed()
+}
+
+/// A request derived from `seed` alone, e.g. the input of a fuzz target:
+/// valid or near-valid, of any of the [`METHODS`]. Equal seeds give equal
+/// requests.
+pub fn from_seed(seed: &[u8]) -> Request {
+    let mut key = [0u8; 32];
+    for (i, byte) in seed.iter().enumerate() {
+        key[i % 32] ^= byte.rotate_left((i / 32) as u32);
+    }
+    key[0] ^= seed.len() as u8;
+    let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &key);
+    let mut runner = TestRunner::new_with_rng(Config::default(), rng);
+    let strategy = prop_oneof![
+        any_valid(),
+        any_near_valid().prop_map(|(request, _)| request),
+    ];
+    strategy
+        .new_tree(&mut runner)
+        .expect("requests can be generated")
+        .current()
+}
+
+/// Applies mutation `kind` to the value at `at` among those of `params`,
+/// `None` if it doesn't apply there.
+fn mutate(params: &mut Value, at: Index, kind: usize) -> Option<Mutation> {
+    let mut paths = Vec::new();
+    locations(params, &mut Vec::new(), &mut paths);
+    let steps = at.get(&paths).clone();
+    let path = json_path(&steps);
+    if kind == 0 {
+        let (last, parent) = steps.split_last()?;
+        let Step::Member(name) = last else {
+            return None;
+        };
+        lookup(params, parent)?.as_object_mut()?.remove(name)?;
+        return Some(Mutation::Removed { path });
+    }
+    let value = lookup(params, &steps)?;
+    match (kind, &mut *value) {
+        (1, _) => {
+            *value = match value {
+                Value::String(_) => Value::from(0),
+                Value::Number(_) => Value::from("0"),
+                Value::Object(_) => Value::Array(Vec::new()),
+                _ => Value::Object(Map::new()),
+            };
+            Some(Mutation::Retyped { path })
+        }
+        (2, Value::String(text)) => {
+            text.push('x');
+            Some(Mutation::Lengthened { path })
+        }
+        (3, Value::Number(number)) => {
+            let n = number.as_f64()?;
+            *value = match n >= 0.0 && n.fract() == 0.0 {
+                true => Value::from(-n - 1.0),
+                false => Value::from(n.trunc() + 0.5),
+            };
+            Some(Mutation::Skewed { path })
+        }
+        (4, Value::Array(items)) if !items.is_empty() => {
+            items.clear();
+            Some(Mutation::Emptied { path })
+        }
+        _ => None,
+    }
+}
+
+/// A step from a value into one of its members or items.
+#[derive(Debug, Clone)]
+enum Step {
+    Member(String),
+    Item(usize),
+}
+
+/// Pushes the steps to every value nested in `value`, `value` itself
+/// included.
+fn locations(value: &Value, prefix: &mut Vec<Step>, paths: &mut Vec<Vec<Step>>) {
+    paths.push(prefix.clone());
+    let nested: Vec<(Step, &Value)> = match value {
+        Value::Object(members) => members
+            .iter()
+            .map(|(name, member)| (Step::Member(name.clone()), member))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (Step::Item(i), item))
+            .collect(),
+        _ => Vec::new(),
+    };
+    for (step, nested) in nested {
+        prefix.push(step);
+        locations(nested, prefix, paths);
+        prefix.pop();
+    }
+}
+
+fn lookup<'a>(value: &'a mut Value, steps: &[Step]) -> Option<&'a mut Value> {
+    steps.iter().try_fold(value, |value, step| match step {
+        Step::Member(name) => value.get_mut(name.as_str()),
+        Step::Item(i) => value.get_mut(*i),
+    })
+}
+
+/// `steps` as a JSON path, as [`schema::SchemaViolation`] reports it.
+fn json_path(steps: &[Step]) -> String {
+    let mut path = String::from("$");
+    for step in steps {
+        match step {
+            Step::Member(name) => path = schema::member_path(&path, name),
+            Step::Item(i) => path = format!("{}[{}]", path, i),
+        }
+    }
+    path
+}
\ No newline at end of file
@@ -0,0 +1,248 @@
+// Generated by cargo xtask gen --lang rust
+//! TIR programs, as `trp.resolve` takes them in a [`TirInfo`]: the
+//! bytecode of a transaction template compiled to a version of the TIR,
+//! encoded as hex or base64. Servers answer bytecode they can't decode with
+//! an opaque error; a [`TirEnvelope`] checks it before it's sent.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::hex;
+use super::types::TirInfo;
+
+/// Versions of the TIR the spec supports.
+pub const TIR_VERSIONS: &[&str] = &["v1alpha8", "v1beta0"];
+
+/// Encoding of TIR bytecode in a [`TirInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TirEncoding {
+    #[default]
+    Hex,
+    /// Standard or URL-safe base64, padded or not; encoded as standard
+    /// padded base64.
+    Base64,
+}
+
+impl TirEncoding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::Base64 => "base64",
+        }
+    }
+
+    /// `bytes` as text of the encoding, hex being lowercase.
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Hex => hex::encode(bytes),
+            Self::Base64 => to_base64(bytes),
+        }
+    }
+
+    /// The bytes `text` encodes, failing at the offset of the first
+    /// character that can't be decoded.
+    pub fn decode(self, text: &str) -> Result<Vec<u8>, TirError> {
+        let decoded = match self {
+            Self::Hex => hex::decode(text),
+            Self::Base64 => from_base64(text),
+        };
+        decoded.map_err(|offset| TirError::Bytecode {
+            encoding: self,
+            offset,
+        })
+    }
+}
+
+impl fmt::Display for TirEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TirEncoding {
+    type Err = TirError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "hex" => Ok(Self::Hex),
+            "base64" => Ok(Self::Base64),
+            _ => Err(TirError::Encoding(text.to_string())),
+        }
+    }
+}
+
+/// A TIR program of a supported version, with its bytecode decoded.
+///
+/// It converts from a [`TirInfo`] with `try_from`, which fails on
+/// unsupported versions and encodings and on bytecode that doesn't decode,
+/// and back into one with the bytecode encoded as [`encoding`](Self::encoding)
+/// tells.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TirEnvelope {
+    version: String,
+    encoding: TirEncoding,
+    bytecode: Vec<u8>,
+}
+
+impl TirEnvelope {
+    /// The program of `bytecode` compiled to `version`, to be sent as
+    /// `encoding`.
+    pub fn new(
+        version: &str,
+        encoding: TirEncoding,
+        bytecode: Vec<u8>,
+    ) -> Result<Self, TirError> {
+        if !TIR_VERSIONS.contains(&version) {
+            return Err(TirError::Version(version.to_string()));
+        }
+        if bytecode.is_empty() {
+            return Err(TirError::Empty);
+        }
+        Ok(Self {
+            version: version.to_string(),
+            encoding,
+            bytecode,
+        })
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn encoding(&self) -> TirEncoding {
+        self.encoding
+    }
+
+    pub fn bytecode(&self) -> &[u8] {
+        &self.bytecode
+    }
+
+    /// The same program, to be sent as `encoding`.
+    pub fn with_encoding(self, encoding: TirEncoding) -> Self {
+        Self { encoding, ..self }
+    }
+}
+
+impl TryFrom<&TirInfo> for TirEnvelope {
+    type Error = TirError;
+
+    fn try_from(info: &TirInfo) -> Result<Self, Self::Error> {
+        let encoding: TirEncoding = info.encoding.parse()?;
+        Self::new(&info.version, encoding, encoding.decode(&info.bytecode)?)
+    }
+}
+
+impl TryFrom<TirInfo> for TirEnvelope {
+    type Error = TirError;
+
+    fn try_from(info: TirInfo) -> Result<Self, Self::Error> {
+        Self::try_from(&info)
+    }
+}
+
+impl From<&TirEnvelope> for TirInfo {
+    fn from(envelope: &TirEnvelope) -> Self {
+        Self {
+            bytecode: envelope.encoding.encode(&envelope.bytecode),
+            encoding: envelope.encoding.as_str().to_string(),
+            version: envelope.version.clone(),
+        }
+    }
+}
+
+impl From<TirEnvelope> for TirInfo {
+    fn from(envelope: TirEnvelope) -> Self {
+        Self::from(&envelope)
+    }
+}
+
+/// Why a TIR program isn't one servers resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TirError {
+    /// The version isn't one of [`TIR_VERSIONS`].
+    Version(String),
+    /// The encoding is neither `hex` nor `base64`.
+    Encoding(String),
+    /// The bytecode doesn't decode, from this byte offset of its text on.
+    Bytecode { encoding: TirEncoding, offset: usize },
+    /// The bytecode is empty.
+    Empty,
+}
+
+impl fmt::Display for TirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Version(version) => write!(
+                f,
+                "unsupported TIR version `{}`, expected one of {}",
+                version,
+                TIR_VERSIONS.join(", ")
+            ),
+            Self::Encoding(encoding) => write!(
+                f,
+                "unsupported TIR encoding `{}`, expected hex or base64",
+                encoding
+            ),
+            Self::Bytecode { encoding, offset } => write!(
+                f,
+                "the TIR bytecode isn't valid {} at offset {}",
+                encoding, offset
+            ),
+            Self::Empty => write!(f, "the TIR bytecode is empty"),
+        }
+    }
+}
+
+impl std::error::Error for TirError {}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, byte)| bits | (u32::from(*byte) << (16 - 8 * i)));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => text.push(BASE64[((bits >> (18 - 6 * i)) & 0x3f) as usize] as char),
+                false => text.push('='),
+            }
+        }
+    }
+    text
+}
+
+/// Decodes standard or URL-safe base64, padded or not, rejecting a
+/// dangling last character and padding that doesn't end a 4 character group.
+fn from_base64(text: &str) -> Result<Vec<u8>, usize> {
+    let sextet = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    };
+    let data = text.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    let (mut bits, mut len) = (0u32, 0);
+    for (offset, c) in data.bytes().enumerate() {
+        bits = (bits << 6) | u32::from(sextet(c).ok_or(offset)?);
+        len += 6;
+        if len >= 8 {
+            len -= 8;
+            bytes.push((bits >> len) as u8);
+            bits &= (1 << len) - 1;
+        }
+    }
+    if data.len() % 4 == 1 {
+        return Err(data.len() - 1);
+    }
+    let padding = text.len() - data.len();
+    if padding > 0 && (padding > 2 || !text.len().is_multiple_of(4)) {
+        return Err(data.len());
+    }
+    Ok(bytes)
+}
\ No newline at end of file
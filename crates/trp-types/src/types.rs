@@ -0,0 +1,378 @@
+// Generated by cargo xtask gen --lang rust
+use serde::{Deserialize, Serialize};
+
+// Children get it through their `use super::*`.
+#[allow(unused_imports)]
+use super::address;
+
+/// A schema constraint violated by a value, as reported by `validate()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Location of the offending value, e.g. `tx.payload` or `witnesses[0]`.
+    pub path: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(path: &str, message: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Prefixes the path with the field holding the offending value.
+    pub fn nested(self, parent: &str) -> Self {
+        Self {
+            path: format!("{}.{}", parent, self.path),
+            message: self.message,
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BytesEnvelope {
+    #[serde(rename = "encoding")]
+    pub encoding: String,
+    #[serde(rename = "payload")]
+    pub payload: String,
+}
+
+impl BytesEnvelope {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckStatusParams {
+    #[serde(rename = "hashes")]
+    pub hashes: Vec<String>,
+}
+
+impl CheckStatusParams {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        {
+            let value = &self.hashes;
+            if value.is_empty() {
+                return Err(ValidationError::new("hashes", "must contain at least 1 items"));
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckStatusResponse {
+    #[serde(rename = "statuses")]
+    pub statuses: std::collections::HashMap<String, TxStatus>,
+}
+
+impl CheckStatusResponse {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InputNotResolvedDiagnostic {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "query")]
+    pub query: InputQueryDiagnostic,
+    #[serde(rename = "search_space")]
+    pub search_space: SearchSpaceDiagnostic,
+}
+
+impl InputNotResolvedDiagnostic {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        {
+            let value = &self.query;
+            value.validate().map_err(|e| e.nested("query"))?;
+        }
+        {
+            let value = &self.search_space;
+            value.validate().map_err(|e| e.nested("search_space"))?;
+        }
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InputQueryDiagnostic {
+    #[serde(rename = "address")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(rename = "collateral")]
+    pub collateral: bool,
+    #[serde(rename = "min_amount")]
+    pub min_amount: std::collections::HashMap<String, String>,
+    #[serde(rename = "refs")]
+    pub refs: Vec<String>,
+    #[serde(rename = "support_many")]
+    pub support_many: bool,
+}
+
+impl InputQueryDiagnostic {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(value) = &self.address {
+            if !address::is_address(value) {
+                return Err(ValidationError::new("address", "must be a valid address"));
+            }
+        }
+        Ok(())
+    }
+
+    /// `address` as an address.
+    pub fn parse_address(&self) -> Option<Result<address::Address, address::AddressError>> {
+        self.address.as_deref().map(str::parse)
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MissingTxArgDiagnostic {
+    #[serde(rename = "key")]
+    pub key: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+}
+
+impl MissingTxArgDiagnostic {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResolveParams {
+    #[serde(rename = "args")]
+    pub args: std::collections::HashMap<String, serde_json::Value>,
+    #[serde(rename = "env")]
+    pub env: std::collections::HashMap<String, serde_json::Value>,
+    #[serde(rename = "tir")]
+    pub tir: TirInfo,
+}
+
+impl ResolveParams {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        {
+            let value = &self.tir;
+            value.validate().map_err(|e| e.nested("tir"))?;
+        }
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchSpaceDiagnostic {
+    #[serde(rename = "by_address_count")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_address_count: Option<i64>,
+    #[serde(rename = "by_asset_class_count")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_asset_class_count: Option<i64>,
+    #[serde(rename = "by_ref_count")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_ref_count: Option<i64>,
+    #[serde(rename = "matched")]
+    pub matched: Vec<String>,
+}
+
+impl SearchSpaceDiagnostic {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubmitParams {
+    #[serde(rename = "tx")]
+    pub tx: BytesEnvelope,
+    #[serde(rename = "witnesses")]
+    pub witnesses: Vec<SubmitWitness>,
+}
+
+impl SubmitParams {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        {
+            let value = &self.tx;
+            value.validate().map_err(|e| e.nested("tx"))?;
+        }
+        {
+            let value = &self.witnesses;
+            for (i, item) in value.iter().enumerate() {
+                item.validate().map_err(|e| e.nested(&format!("witnesses[{}]", i)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubmitResponse {
+    #[serde(rename = "hash")]
+    pub hash: String,
+}
+
+impl SubmitResponse {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubmitWitness {
+    #[serde(rename = "key")]
+    pub key: BytesEnvelope,
+    #[serde(rename = "signature")]
+    pub signature: BytesEnvelope,
+    #[serde(rename = "type")]
+    pub r#type: String,
+}
+
+impl SubmitWitness {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        {
+            let value = &self.key;
+            value.validate().map_err(|e| e.nested("key"))?;
+        }
+        {
+            let value = &self.signature;
+            value.validate().map_err(|e| e.nested("signature"))?;
+        }
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TirInfo {
+    #[serde(rename = "bytecode")]
+    pub bytecode: String,
+    #[serde(rename = "encoding")]
+    pub encoding: String,
+    #[serde(rename = "version")]
+    pub version: String,
+}
+
+impl TirInfo {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TxEnvelope {
+    #[serde(rename = "hash")]
+    pub hash: String,
+    #[serde(rename = "tx")]
+    pub tx: String,
+}
+
+impl TxEnvelope {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TxScriptFailureDiagnostic {
+    #[serde(rename = "logs")]
+    pub logs: Vec<String>,
+}
+
+impl TxScriptFailureDiagnostic {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TxStatus {
+    #[serde(rename = "confirmations")]
+    pub confirmations: i64,
+    #[serde(rename = "stage")]
+    pub stage: String,
+}
+
+impl TxStatus {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        {
+            let value = &self.confirmations;
+            if *value < 0 {
+                return Err(ValidationError::new("confirmations", "must be at least 0"));
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnsupportedTirDiagnostic {
+    #[serde(rename = "expected")]
+    pub expected: String,
+    #[serde(rename = "provided")]
+    pub provided: String,
+}
+
+impl UnsupportedTirDiagnostic {
+    /// Checks the constraints of the spec that deserialization alone does
+    /// not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
@@ -0,0 +1,119 @@
+// Generated by cargo xtask gen --lang rust
+//! References to transaction outputs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::hex;
+
+/// Length of a transaction hash in bytes.
+pub const TXID_LEN: usize = 32;
+
+/// A reference to the output `index` of the transaction hashed `txid`.
+///
+/// It parses from the `txid#index` text form, e.g.
+/// `"1f1e…0a#0"` with the hash as hex, and from JSON as that string or as
+/// an object `{"txid": "1f1e…0a", "index": 0}`. It displays and serializes
+/// canonically, as the string with the hash in lowercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UtxoRef {
+    pub txid: [u8; TXID_LEN],
+    pub index: u32,
+}
+
+impl UtxoRef {
+    pub fn new(txid: [u8; TXID_LEN], index: u32) -> Self {
+        Self { txid, index }
+    }
+
+    /// The reference to output `index` of the transaction hashed `txid`, as
+    /// hex.
+    pub fn from_hex(txid: &str, index: u32) -> Result<Self, UtxoRefError> {
+        if txid.len() != TXID_LEN * 2 {
+            return Err(UtxoRefError::TxidLength(txid.len()));
+        }
+        let mut bytes = [0; TXID_LEN];
+        bytes.copy_from_slice(&hex::decode(txid).map_err(|_| UtxoRefError::TxidHex)?);
+        Ok(Self::new(bytes, index))
+    }
+
+    /// The transaction hash as lowercase hex.
+    pub fn txid_hex(&self) -> String {
+        hex::encode(&self.txid)
+    }
+}
+
+/// Why a [`UtxoRef`] didn't parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UtxoRefError {
+    /// The text isn't of the `txid#index` form.
+    Format,
+    /// The hash isn't [`TXID_LEN`] bytes long, being this many hex digits.
+    TxidLength(usize),
+    /// The hash isn't hex.
+    TxidHex,
+    /// The index isn't a decimal `u32`.
+    Index,
+}
+
+impl fmt::Display for UtxoRefError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Format => write!(f, "expected a `txid#index` UTxO reference"),
+            Self::TxidLength(len) => write!(
+                f,
+                "expected a transaction hash of {} hex digits, got {}",
+                TXID_LEN * 2,
+                len
+            ),
+            Self::TxidHex => write!(f, "the transaction hash isn't hex"),
+            Self::Index => write!(f, "the output index isn't a 32-bit unsigned integer"),
+        }
+    }
+}
+
+impl std::error::Error for UtxoRefError {}
+
+impl FromStr for UtxoRef {
+    type Err = UtxoRefError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (txid, index) = text.split_once('#').ok_or(UtxoRefError::Format)?;
+        // `u32::from_str` takes a leading `+`.
+        if index.is_empty() || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(UtxoRefError::Index);
+        }
+        let index = index.parse().map_err(|_| UtxoRefError::Index)?;
+        Self::from_hex(txid, index)
+    }
+}
+
+impl fmt::Display for UtxoRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.txid_hex(), self.index)
+    }
+}
+
+impl Serialize for UtxoRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for UtxoRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Form {
+            Text(String),
+            Object { txid: String, index: u32 },
+        }
+        let parsed = match Form::deserialize(deserializer)? {
+            Form::Text(text) => text.parse(),
+            Form::Object { txid, index } => Self::from_hex(&txid, index),
+        };
+        parsed.map_err(serde::de::Error::custom)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,317 @@
+// Generated by cargo xtask gen --lang rust
+//! Waiting for submitted transactions to make it on chain, polling
+//! `trp.checkStatus` until they reach a [`Stage`] or a timeout elapses.
+//! Requires the `client` feature; the `blocking` feature adds the same
+//! methods to the blocking client. Servers predating `trp.checkStatus`
+//! answer that it's not found, which ends a wait with
+//! [`WaitError::Unsupported`], carrying the hash of the transaction
+//! `submit_and_wait` submitted.
+//!
+//! ```no_run
+//! use trp_types::client::Client;
+//! use trp_types::watch::{Event, Wait};
+//!
+//! # async fn run(params: trp_types::SubmitParams) -> Result<(), trp_types::watch::WaitError> {
+//! let client = Client::new("http://localhost:8000");
+//! let (hash, status) = client
+//!     .submit_and_wait(&params, &Wait::default(), |event| match event {
+//!         Event::Submitted(hash) => eprintln!("submitted {}", hash),
+//!         Event::Status(status) => eprintln!("{} ({} confirmations)", status.stage, status.confirmations),
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::client::{Client, ClientError};
+use crate::error_codes;
+use crate::{CheckStatusParams, CheckStatusResponse, SubmitParams, TxStatus};
+
+/// Lifecycle stage of a transaction, as `TxStatus::stage` tells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Pending,
+    Propagated,
+    Acknowledged,
+    Confirmed,
+    Finalized,
+    /// Evicted without being included; the transaction won't make it.
+    Dropped,
+    /// Included, then undone by a rollback; it may be included again.
+    RolledBack,
+    /// Not seen by the server, or a stage this client doesn't know.
+    Unknown,
+}
+
+impl Stage {
+    pub fn of(status: &TxStatus) -> Self {
+        match status.stage.as_str() {
+            "pending" => Self::Pending,
+            "propagated" => Self::Propagated,
+            "acknowledged" => Self::Acknowledged,
+            "confirmed" => Self::Confirmed,
+            "finalized" => Self::Finalized,
+            "dropped" => Self::Dropped,
+            "rolledBack" => Self::RolledBack,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// How far along the way to finality the stage is, if on it.
+    fn progress(self) -> Option<u8> {
+        match self {
+            Self::Pending => Some(0),
+            Self::Propagated => Some(1),
+            Self::Acknowledged => Some(2),
+            Self::Confirmed => Some(3),
+            Self::Finalized => Some(4),
+            Self::Dropped | Self::RolledBack | Self::Unknown => None,
+        }
+    }
+}
+
+/// What to wait for, and for how long.
+#[derive(Debug, Clone)]
+pub struct Wait {
+    /// The stage to wait for; later stages do too.
+    pub until: Stage,
+    /// Confirmations to wait for once confirmed, if waiting for
+    /// [`Stage::Confirmed`]. Finalized transactions have enough.
+    pub confirmations: u64,
+    /// Time between two status checks.
+    pub interval: Duration,
+    /// Time after which waiting fails with [`WaitError::Timeout`]. It's
+    /// checked between calls; set a timeout on the client to bound those.
+    pub timeout: Duration,
+}
+
+impl Default for Wait {
+    /// Waits up to 5 minutes for a confirmation, checking every 2 seconds.
+    fn default() -> Self {
+        Self {
+            until: Stage::Confirmed,
+            confirmations: 1,
+            interval: Duration::from_secs(2),
+            timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Progress of a wait, handed to its callback.
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// The transaction was submitted, and got this hash.
+    Submitted(String),
+    /// The status of the transaction changed: it reached a new stage or got
+    /// more confirmations.
+    Status(TxStatus),
+}
+
+/// Why a wait ended before the transaction reached its stage.
+#[derive(Debug)]
+pub enum WaitError {
+    /// Submitting or checking the status failed.
+    Client(ClientError),
+    /// The transaction was dropped.
+    Dropped(TxStatus),
+    /// The timeout elapsed, with the last status seen, if any.
+    Timeout(Option<TxStatus>),
+    /// The server doesn't implement `trp.checkStatus`, so the transaction
+    /// of this hash can't be waited for. It was submitted all the same.
+    Unsupported(String),
+}
+
+impl fmt::Display for WaitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Client(err) => write!(f, "{}", err),
+            Self::Dropped(_) => write!(f, "the transaction was dropped"),
+            Self::Timeout(Some(status)) => write!(
+                f,
+                "timed out with the transaction {} ({} confirmations)",
+                status.stage, status.confirmations
+            ),
+            Self::Timeout(None) => write!(f, "timed out before the transaction was seen"),
+            Self::Unsupported(hash) => write!(
+                f,
+                "the server doesn't implement trp.checkStatus, so transaction {} can't be waited for",
+                hash
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WaitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Client(err) => Some(err),
+            Self::Dropped(_) | Self::Timeout(_) | Self::Unsupported(_) => None,
+        }
+    }
+}
+
+impl From<ClientError> for WaitError {
+    fn from(err: ClientError) -> Self {
+        Self::Client(err)
+    }
+}
+
+fn params(hash: &str) -> CheckStatusParams {
+    CheckStatusParams {
+        hashes: vec![hash.to_string()],
+    }
+}
+
+/// The statuses a check answered, or [`WaitError::Unsupported`] when the
+/// server doesn't know the method.
+fn checked(
+    hash: &str,
+    response: Result<CheckStatusResponse, ClientError>,
+) -> Result<CheckStatusResponse, WaitError> {
+    match response {
+        Err(ClientError::Rpc(err)) if err.code == error_codes::METHOD_NOT_FOUND => {
+            Err(WaitError::Unsupported(hash.to_string()))
+        }
+        response => Ok(response?),
+    }
+}
+
+/// The statuses of a wait so far: reports changes and tells when it's over.
+struct Watcher<'a, F> {
+    wait: &'a Wait,
+    on_event: F,
+    last: Option<TxStatus>,
+    deadline: Instant,
+}
+
+impl<'a, F: FnMut(&Event)> Watcher<'a, F> {
+    fn new(wait: &'a Wait, on_event: F) -> Self {
+        Self {
+            wait,
+            on_event,
+            last: None,
+            deadline: Instant::now() + wait.timeout,
+        }
+    }
+
+    /// Takes the status the server answered for `hash`, returning the
+    /// outcome of the wait once it's over.
+    fn observe(&mut self, hash: &str, mut statuses: HashMap<String, TxStatus>) -> Option<Result<TxStatus, WaitError>> {
+        let status = statuses.remove(hash).unwrap_or(TxStatus {
+            stage: "unknown".to_string(),
+            confirmations: 0,
+        });
+        let changed = self.last.as_ref().is_none_or(|last| {
+            (&last.stage, last.confirmations) != (&status.stage, status.confirmations)
+        });
+        if changed {
+            (self.on_event)(&Event::Status(status.clone()));
+            self.last = Some(status.clone());
+        }
+        let stage = Stage::of(&status);
+        if stage == Stage::Dropped {
+            return Some(Err(WaitError::Dropped(status)));
+        }
+        let reached = match (stage.progress(), self.wait.until.progress()) {
+            (Some(stage), Some(until)) => stage >= until,
+            _ => false,
+        };
+        let confirmed = self.wait.until != Stage::Confirmed
+            || stage == Stage::Finalized
+            || u64::try_from(status.confirmations).unwrap_or(0) >= self.wait.confirmations;
+        if reached && confirmed {
+            return Some(Ok(status));
+        }
+        None
+    }
+
+    /// How long to sleep before the next check, or the timeout error if it
+    /// has elapsed.
+    fn pause(&mut self) -> Result<Duration, WaitError> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(WaitError::Timeout(self.last.take()));
+        }
+        Ok(self.wait.interval.min(left))
+    }
+}
+
+impl Client {
+    /// Waits for the transaction of `hash` to reach the stage `wait` tells,
+    /// calling `on_event` as its status changes. Unknown transactions are
+    /// waited for, in case the server hasn't seen them yet.
+    ///
+    /// Fails with [`WaitError::Unsupported`] if the server doesn't implement
+    /// `trp.checkStatus`.
+    pub async fn wait_for_confirmation(
+        &self,
+        hash: &str,
+        wait: &Wait,
+        on_event: impl FnMut(&Event),
+    ) -> Result<TxStatus, WaitError> {
+        let mut watcher = Watcher::new(wait, on_event);
+        loop {
+            let response = checked(hash, self.check_status(&params(hash)).await)?;
+            if let Some(outcome) = watcher.observe(hash, response.statuses) {
+                return outcome;
+            }
+            tokio::time::sleep(watcher.pause()?).await;
+        }
+    }
+
+    /// Submits a transaction and waits for it as
+    /// [`wait_for_confirmation`](Self::wait_for_confirmation) does,
+    /// answering its hash and last status. If the server can't tell the
+    /// status, the [`WaitError::Unsupported`] error carries the hash.
+    pub async fn submit_and_wait(
+        &self,
+        params: &SubmitParams,
+        wait: &Wait,
+        mut on_event: impl FnMut(&Event),
+    ) -> Result<(String, TxStatus), WaitError> {
+        let hash = self.submit(params).await?.hash;
+        on_event(&Event::Submitted(hash.clone()));
+        let status = self.wait_for_confirmation(&hash, wait, on_event).await?;
+        Ok((hash, status))
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl crate::client::blocking::Client {
+    /// Waits for the transaction of `hash` to reach the stage `wait` tells,
+    /// as the async [`Client::wait_for_confirmation`] does, blocking the
+    /// calling thread.
+    pub fn wait_for_confirmation(
+        &self,
+        hash: &str,
+        wait: &Wait,
+        on_event: impl FnMut(&Event),
+    ) -> Result<TxStatus, WaitError> {
+        let mut watcher = Watcher::new(wait, on_event);
+        loop {
+            let response = checked(hash, self.check_status(&params(hash)))?;
+            if let Some(outcome) = watcher.observe(hash, response.statuses) {
+                return outcome;
+            }
+            std::thread::sleep(watcher.pause()?);
+        }
+    }
+
+    /// Submits a transaction and waits for it, as the async
+    /// [`Client::submit_and_wait`] does, blocking the calling thread.
+    pub fn submit_and_wait(
+        &self,
+        params: &SubmitParams,
+        wait: &Wait,
+        mut on_event: impl FnMut(&Event),
+    ) -> Result<(String, TxStatus), WaitError> {
+        let hash = self.submit(params)?.hash;
+        on_event(&Event::Submitted(hash.clone()));
+        let status = self.wait_for_confirmation(&hash, wait, on_event)?;
+        Ok((hash, status))
+    }
+}
\ No newline at end of file
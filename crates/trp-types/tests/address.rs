@@ -0,0 +1,72 @@
+//! Addresses parse from their bech32 and base58 forms, tell their network,
+//! convert between hex and bech32, and fail `validate()` when invalid.
+
+use trp_types::address::{is_address, Address, AddressError, MAINNET};
+use trp_types::InputQueryDiagnostic;
+
+const BASE: &str = "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x";
+const BASE_TEST: &str = "addr_test1qz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgs68faae";
+const POINTER: &str = "addr1gx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer5pnz75xxcrzqf96k";
+const ENTERPRISE: &str = "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8";
+const ENTERPRISE_HEX: &str = "619493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e";
+const REWARD_TEST: &str = "stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn";
+const BYRON: &str = "Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi";
+const BYRON_DAEDALUS: &str = "DdzFFzCqrhsw3prhfMFDNFowbzUku3QmrMwarfjUbWXRisodn97R436SHc1rimp4MhPNmbdYb1aTdqtGSJixMVMi5MkArDQJ6Sc1n3Ez";
+
+#[test]
+fn forms() {
+    for (text, network) in [
+        (BASE, Some(MAINNET)),
+        (BASE_TEST, Some(0)),
+        (POINTER, Some(MAINNET)),
+        (ENTERPRISE, Some(MAINNET)),
+        (REWARD_TEST, Some(0)),
+        (BYRON, None),
+        (BYRON_DAEDALUS, None),
+    ] {
+        let address: Address = text
+            .parse()
+            .unwrap_or_else(|err| panic!("{}: {}", text, err));
+        assert_eq!(address.network_id(), network, "{}", text);
+        assert_eq!(address.to_string(), text);
+    }
+
+    let enterprise = Address::from_hex(ENTERPRISE_HEX).unwrap();
+    assert_eq!(enterprise.to_bech32().as_deref(), Some(ENTERPRISE));
+    assert_eq!(
+        ENTERPRISE.parse::<Address>().unwrap().to_hex(),
+        ENTERPRISE_HEX
+    );
+    assert_eq!(BYRON.parse::<Address>().unwrap().to_bech32(), None);
+    assert_eq!(
+        ENTERPRISE.to_uppercase().parse::<Address>().unwrap(),
+        enterprise
+    );
+}
+
+#[test]
+fn invalid() {
+    let typo = ENTERPRISE.replace("66hrl8", "66hrl9");
+    assert_eq!(typo.parse::<Address>(), Err(AddressError::Checksum));
+    let network = ENTERPRISE.replacen("addr", "addr_test", 1);
+    assert!(matches!(
+        network.parse::<Address>(),
+        Err(AddressError::Checksum | AddressError::Prefix { .. })
+    ));
+    assert!(!is_address("not an address"));
+    assert!(!is_address(""));
+    assert_eq!(
+        Address::from_hex(&ENTERPRISE_HEX[..40]),
+        Err(AddressError::Length(20))
+    );
+    assert_eq!(Address::from_hex("90"), Err(AddressError::Header(0x90)));
+
+    let diagnostic = |address: &str| InputQueryDiagnostic {
+        address: Some(address.to_string()),
+        ..Default::default()
+    };
+    assert!(diagnostic(BASE).validate().is_ok());
+    assert!(diagnostic(BASE).parse_address().unwrap().is_ok());
+    let err = diagnostic(&typo).validate().unwrap_err();
+    assert_eq!(err.to_string(), "address: must be a valid address");
+}
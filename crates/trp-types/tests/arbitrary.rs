@@ -0,0 +1,32 @@
+//! Generated values must pass validation and survive a JSON round trip.
+//! Run with `cargo test -p trp-types --features arbitrary`.
+#![cfg(feature = "arbitrary")]
+
+use proptest::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use trp_types::{ResolveParams, SubmitParams, SubmitResponse, TxEnvelope};
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) {
+    let json = serde_json::to_value(value).unwrap();
+    let decoded: T = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+}
+
+proptest! {
+    #[test]
+    fn resolve(params in any::<ResolveParams>(), result in any::<TxEnvelope>()) {
+        params.validate().unwrap();
+        result.validate().unwrap();
+        round_trip(&params);
+        round_trip(&result);
+    }
+
+    #[test]
+    fn submit(params in any::<SubmitParams>(), result in any::<SubmitResponse>()) {
+        params.validate().unwrap();
+        result.validate().unwrap();
+        round_trip(&params);
+        round_trip(&result);
+    }
+}
@@ -0,0 +1,61 @@
+//! A resolver backend served as a handler gets the bytes of the envelopes
+//! sent, whatever their encoding, and its results are hex-encoded. Run with
+//! `cargo test -p trp-types --features client,server`.
+#![cfg(all(feature = "client", feature = "server"))]
+
+use jsonrpsee::tokio;
+use serde_json::json;
+use trp_types::backend::{Backend, Memory, Submitted, Witness};
+use trp_types::client::{Client, ClientError};
+use trp_types::server::{serve, Options};
+use trp_types::{ResolveParams, SubmitParams};
+
+#[test]
+fn envelopes() {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let memory = Memory::new();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = Client::new(format!("http://{}", listener.local_addr().unwrap()));
+        let handle = serve(listener, Backend(memory.clone()), Options::default());
+
+        let resolve = |encoding: &str, bytecode: &str| -> ResolveParams {
+            let tir = json!({ "version": "v1beta0", "encoding": encoding, "bytecode": bytecode });
+            serde_json::from_value(json!({ "args": {}, "env": {}, "tir": tir })).unwrap()
+        };
+        let hex = client.resolve(&resolve("hex", "cafe01")).await.unwrap();
+        assert_eq!(hex.tx, "cafe01");
+        let base64 = client.resolve(&resolve("base64", "yv4B")).await.unwrap();
+        assert_eq!(base64.tx, "cafe01");
+        assert_eq!(base64.hash, hex.hash);
+
+        let submit = json!({
+            "tx": { "encoding": "hex", "payload": "cafe01" },
+            "witnesses": [{
+                "type": "vkey",
+                "key": { "encoding": "base64", "payload": "AQI=" },
+                "signature": { "encoding": "hex", "payload": "0304" },
+            }],
+        });
+        let submit: SubmitParams = serde_json::from_value(submit).unwrap();
+        let submitted = client.submit(&submit).await.unwrap();
+        assert_eq!(submitted.hash, hex.hash);
+        let witness = Witness {
+            key: vec![1, 2],
+            signature: vec![3, 4],
+        };
+        let expected = Submitted {
+            tx: vec![0xca, 0xfe, 0x01],
+            witnesses: vec![witness],
+        };
+        assert_eq!(memory.submitted(), [expected]);
+
+        for (encoding, bytecode) in [("hex", "caf"), ("base58", "cafe")] {
+            match client.resolve(&resolve(encoding, bytecode)).await {
+                Err(ClientError::Rpc(err)) => assert_eq!(err.code, -32602, "{}", err),
+                other => panic!("{:?}", other),
+            }
+        }
+
+        handle.stop().unwrap();
+    });
+}
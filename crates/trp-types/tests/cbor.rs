@@ -0,0 +1,150 @@
+//! Diagnostic notation of the examples of RFC 8949, appendix A, and of
+//! malformed items. The `trp` program is run against a stub backend with
+//! `cargo test -p trp-types --features cli,server`.
+
+use trp_types::cbor::{diagnostic, CborError};
+
+fn hex(text: &str) -> Vec<u8> {
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn scalars() {
+    for (encoded, expected) in [
+        ("00", "0"),
+        ("17", "23"),
+        ("1818", "24"),
+        ("1b000000e8d4a51000", "1000000000000"),
+        ("1bffffffffffffffff", "18446744073709551615"),
+        ("3bffffffffffffffff", "-18446744073709551616"),
+        ("3863", "-100"),
+        ("f90000", "0.0"),
+        ("f98000", "-0.0"),
+        ("f93c00", "1.0"),
+        ("fb3ff199999999999a", "1.1"),
+        ("f97bff", "65504.0"),
+        ("f90001", "5.960464477539063e-8"),
+        ("fa47c35000", "100000.0"),
+        ("f97c00", "Infinity"),
+        ("f97e00", "NaN"),
+        ("fbfff0000000000000", "-Infinity"),
+        ("f4", "false"),
+        ("f5", "true"),
+        ("f6", "null"),
+        ("f7", "undefined"),
+        ("f0", "simple(16)"),
+        ("f8ff", "simple(255)"),
+        ("c11a514b67b0", "1(1363896240)"),
+        ("d74401020304", "23(h'01020304')"),
+        ("40", "h''"),
+        ("60", "\"\""),
+        ("6161", "\"a\""),
+        ("62225c", "\"\\\"\\\\\""),
+        ("63e6b0b4", "\"水\""),
+        ("5f42010243030405ff", "(_ h'0102', h'030405')"),
+        ("7f657374726561646d696e67ff", "(_ \"strea\", \"ming\")"),
+        ("5fff", "''_"),
+    ] {
+        assert_eq!(diagnostic(&hex(encoded)).unwrap(), expected, "{}", encoded);
+    }
+}
+
+#[test]
+fn containers() {
+    for (encoded, expected) in [
+        ("80", "[]"),
+        ("a0", "{}"),
+        ("83010203", "[\n  1,\n  2,\n  3\n]"),
+        (
+            "8301820203820405",
+            "[\n  1,\n  [\n    2,\n    3\n  ],\n  [\n    4,\n    5\n  ]\n]",
+        ),
+        ("a201020304", "{\n  1: 2,\n  3: 4\n}"),
+        (
+            "a26161016162820203",
+            "{\n  \"a\": 1,\n  \"b\": [\n    2,\n    3\n  ]\n}",
+        ),
+        ("9fff", "[_]"),
+        (
+            "9f018202039f0405ffff",
+            "[_\n  1,\n  [\n    2,\n    3\n  ],\n  [_\n    4,\n    5\n  ]\n]",
+        ),
+        (
+            "bf61610161629f0203ffff",
+            "{_\n  \"a\": 1,\n  \"b\": [_\n    2,\n    3\n  ]\n}",
+        ),
+        ("d90102820102", "258([\n  1,\n  2\n])"),
+    ] {
+        assert_eq!(diagnostic(&hex(encoded)).unwrap(), expected, "{}", encoded);
+    }
+}
+
+#[test]
+fn malformed() {
+    for (encoded, expected) in [
+        ("", CborError::Truncated),
+        ("18", CborError::Truncated),
+        ("8301", CborError::Truncated),
+        ("9b00000000ffffffff", CborError::Truncated),
+        ("0000", CborError::Trailing(1)),
+        ("1c", CborError::Malformed(0)),
+        ("ff", CborError::Malformed(0)),
+        ("1f", CborError::Malformed(0)),
+        ("8201ff", CborError::Malformed(2)),
+        ("bf01ff", CborError::Malformed(2)),
+        ("5f6161ff", CborError::Malformed(1)),
+        ("f818", CborError::Malformed(0)),
+        ("62c328", CborError::Utf8(0)),
+    ] {
+        assert_eq!(diagnostic(&hex(encoded)), Err(expected), "{}", encoded);
+    }
+    let nested = [vec![0x81; 1000], vec![0x00]].concat();
+    assert_eq!(diagnostic(&nested), Err(CborError::TooDeep));
+}
+
+#[cfg(all(feature = "cli", feature = "server"))]
+#[test]
+fn resolve() {
+    use jsonrpsee::tokio;
+    use std::process::Command;
+    use trp_types::backend::{Backend, Memory};
+    use trp_types::server::{serve, Options};
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let listener = runtime
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle =
+        runtime.block_on(async { serve(listener, Backend(Memory::new()), Options::default()) });
+
+    // The stub backend answers the bytecode as the transaction.
+    let trp = |output: &str| {
+        let out = Command::new(env!("CARGO_BIN_EXE_trp"))
+            .args([
+                "resolve",
+                "--url",
+                &url,
+                "--tir",
+                "a1006161",
+                "--tir-version",
+                "v1beta0",
+            ])
+            .args(["--args", "{\"quantity\": 1}", "--output", output])
+            .output()
+            .unwrap();
+        (out.status.success(), String::from_utf8(out.stdout).unwrap())
+    };
+    assert_eq!(trp("hex"), (true, "a1006161\n".to_string()));
+    assert_eq!(trp("diag"), (true, "{\n  0: \"a\"\n}\n".to_string()));
+    let (success, json) = trp("json");
+    assert!(success);
+    let envelope: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(envelope["tx"], "a1006161");
+    assert!(!trp("cbor").0);
+
+    handle.stop().unwrap();
+}
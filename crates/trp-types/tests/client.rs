@@ -0,0 +1,223 @@
+//! Clients check the spec version of the server before their first call,
+//! key their submissions for the server to answer retries once, run their
+//! middleware around calls and, if asked to, check results against their
+//! schema. Run
+//! with `cargo test -p trp-types --features blocking,server`.
+#![cfg(all(feature = "blocking", feature = "server"))]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use jsonrpsee::server::{Server, ServerHandle};
+use jsonrpsee::tokio;
+use jsonrpsee::RpcModule;
+use serde_json::{json, Value};
+use trp_types::client::blocking::Client;
+use trp_types::client::{idempotency_key, ClientError, Middleware};
+use trp_types::constants::{DISCOVER_METHOD, SPEC_VERSION, TRP_CHECK_STATUS, TRP_SUBMIT};
+use trp_types::error_codes;
+use trp_types::errors::TrpError;
+use trp_types::server::{self, Handler, Idempotency, Options, IDEMPOTENCY_CONFLICT_CODE};
+use trp_types::{
+    CheckStatusParams, CheckStatusResponse, ResolveParams, SubmitParams, SubmitResponse, TxEnvelope,
+};
+
+/// A server implementing spec `version`, answering `true` to submissions.
+fn start(runtime: &tokio::runtime::Runtime, version: &str) -> (String, ServerHandle) {
+    let version = version.to_string();
+    let mut module = RpcModule::new(());
+    module
+        .register_method(
+            DISCOVER_METHOD,
+            move |_, _, _| json!({ "info": { "version": version.clone() } }),
+        )
+        .unwrap();
+    module.register_method(TRP_SUBMIT, |_, _, _| true).unwrap();
+    runtime.block_on(async {
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        (url, server.start(module))
+    })
+}
+
+#[test]
+fn version_check() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let compatible = format!("{}-patched", SPEC_VERSION);
+    let (url, handle) = start(&runtime, &compatible);
+    let submitted: Value = Client::new(&url).call(TRP_SUBMIT, &json!({})).unwrap();
+    assert_eq!(submitted, true);
+    handle.stop().unwrap();
+
+    let (url, handle) = start(&runtime, "99.0.0");
+    let err = Client::new(&url)
+        .call::<_, Value>(TRP_SUBMIT, &json!({}))
+        .unwrap_err();
+    assert!(
+        matches!(err, ClientError::VersionMismatch { .. }),
+        "{}",
+        err
+    );
+    let client = Client::new(&url).skip_version_check();
+    assert!(client.call::<_, Value>(TRP_SUBMIT, &json!({})).is_ok());
+    handle.stop().unwrap();
+}
+
+/// Marks the params of every call and records how calls ended.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<(String, bool, Duration)>>>);
+
+impl Middleware for Recorder {
+    fn on_request(&self, _method: &str, params: &mut Value) {
+        params["marked"] = true.into();
+    }
+
+    fn on_response(&self, method: &str, outcome: Result<&Value, &ClientError>, latency: Duration) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((method.to_string(), outcome.is_ok(), latency));
+    }
+}
+
+#[test]
+fn middleware() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut module = RpcModule::new(());
+    module
+        .register_method(TRP_SUBMIT, |params, _, _| params.parse::<Value>().unwrap())
+        .unwrap();
+    let (url, handle) = runtime.block_on(async {
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        (url, server.start(module))
+    });
+
+    let recorder = Recorder::default();
+    let client = Client::new(&url)
+        .skip_version_check()
+        .with_middleware(recorder.clone());
+    let echoed: Value = client.call(TRP_SUBMIT, &json!({ "tx": 1 })).unwrap();
+    assert_eq!(echoed, json!({ "tx": 1, "marked": true }));
+    assert!(client.call::<_, Value>(TRP_CHECK_STATUS, &json!({})).is_err());
+
+    let calls = recorder.0.lock().unwrap();
+    let ended: Vec<_> = calls.iter().map(|(method, ok, _)| (method.as_str(), *ok)).collect();
+    assert_eq!(ended, [(TRP_SUBMIT, true), (TRP_CHECK_STATUS, false)]);
+    assert!(calls.iter().all(|(_, _, latency)| *latency > Duration::ZERO));
+    handle.stop().unwrap();
+}
+
+/// Accepts every submission, numbering them.
+struct Counting(Arc<AtomicUsize>);
+
+impl Handler for Counting {
+    async fn resolve(&self, _: ResolveParams) -> Result<TxEnvelope, TrpError> {
+        std::future::pending().await
+    }
+
+    async fn submit(&self, _: SubmitParams) -> Result<SubmitResponse, TrpError> {
+        let count = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(SubmitResponse {
+            hash: count.to_string(),
+        })
+    }
+
+    async fn check_status(&self, _: CheckStatusParams) -> Result<CheckStatusResponse, TrpError> {
+        std::future::pending().await
+    }
+}
+
+#[test]
+fn idempotency() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let submitted = Arc::new(AtomicUsize::new(0));
+    let options = Options {
+        idempotency: Some(Idempotency::in_memory(Duration::from_secs(60))),
+        ..Options::default()
+    };
+    let (url, handle) = runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handler = Counting(submitted.clone());
+        (url, server::serve(listener, handler, options))
+    });
+    let params = |payload: &str| -> SubmitParams {
+        let params = json!({ "tx": { "encoding": "hex", "payload": payload }, "witnesses": [] });
+        serde_json::from_value(params).unwrap()
+    };
+    let client = Client::new(&url);
+
+    let first = client.submit_with_key(&params("00"), "retried").unwrap();
+    let retried = client.submit_with_key(&params("00"), "retried").unwrap();
+    assert_eq!(retried.hash, first.hash);
+    assert_eq!(submitted.load(Ordering::SeqCst), 1);
+
+    match client.submit_with_key(&params("01"), "retried") {
+        Err(ClientError::Rpc(err)) => assert_eq!(err.code, i64::from(IDEMPOTENCY_CONFLICT_CODE)),
+        other => panic!("{:?}", other),
+    }
+
+    assert_eq!(IDEMPOTENCY_CONFLICT_CODE, -32013);
+    assert_ne!(i64::from(IDEMPOTENCY_CONFLICT_CODE), error_codes::SERVER_IS_BUSY);
+
+    client.submit(&params("00")).unwrap();
+    client.submit(&params("00")).unwrap();
+    assert_eq!(submitted.load(Ordering::SeqCst), 3);
+
+    let key = idempotency_key();
+    assert_eq!(key.len(), 32);
+    assert!(key.bytes().all(|byte| byte.is_ascii_hexdigit()));
+    assert_ne!(key, idempotency_key());
+
+    handle.stop().unwrap();
+}
+
+#[test]
+fn response_validation() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut module = RpcModule::new(());
+    module
+        .register_method(
+            TRP_CHECK_STATUS,
+            |_, _, _| json!({ "statuses": { "9f2c": { "stage": "landed", "confirmations": 1 } } }),
+        )
+        .unwrap();
+    module.register_method(TRP_SUBMIT, |_, _, _| true).unwrap();
+    let (url, handle) = runtime.block_on(async {
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        (url, server.start(module))
+    });
+    let params = CheckStatusParams {
+        hashes: vec!["9f2c".to_string()],
+    };
+
+    // The stage decodes into a string, whatever it is.
+    let lenient = Client::new(&url);
+    let statuses = lenient.check_status(&params).unwrap();
+    assert_eq!(statuses.statuses["9f2c"].stage, "landed");
+
+    let paranoid = Client::new(&url).validate_responses();
+    match paranoid.check_status(&params) {
+        Err(ClientError::SchemaViolation(err)) => {
+            assert_eq!(err.schema, "CheckStatusResponse");
+            assert_eq!(err.path, r#"$.statuses["9f2c"].stage"#);
+        }
+        other => panic!("{:?}", other),
+    }
+    let submit = json!({ "tx": { "encoding": "hex", "payload": "00" }, "witnesses": [] });
+    let submit: SubmitParams = serde_json::from_value(submit).unwrap();
+    assert!(matches!(
+        lenient.submit(&submit),
+        Err(ClientError::Decode(_))
+    ));
+    let err = paranoid.submit(&submit).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "result breaks its schema at $: expected object, found boolean (schema SubmitResponse)"
+    );
+
+    handle.stop().unwrap();
+}
@@ -0,0 +1,53 @@
+use trp_types::error_codes::{self, ErrorClass};
+use trp_types::errors::{TrpError, TrpErrorData, TrpErrorKind, INPUT_NOT_RESOLVED};
+
+#[test]
+fn declared_errors_are_recognised_by_code() {
+    let error: TrpError = serde_json::from_str(
+        r#"{"code": -32002, "message": "Input not resolved", "data": {"name": "source"}}"#,
+    )
+    .unwrap();
+    assert_eq!(error.kind(), Some(TrpErrorKind::InputNotResolved));
+    assert_eq!(
+        error.kind().map(|kind| kind.code()),
+        Some(INPUT_NOT_RESOLVED)
+    );
+    assert!(error.is_retryable());
+    assert_eq!(error.to_string(), "Input not resolved (-32002)");
+
+    let error: TrpError =
+        serde_json::from_str(r#"{"code": -32601, "message": "Method not found"}"#).unwrap();
+    assert_eq!(error.kind(), None);
+    assert!(!error.is_retryable());
+}
+
+#[test]
+fn error_data_decodes_into_the_declared_type() {
+    let error: TrpError = serde_json::from_str(
+        r#"{"code": -32001, "message": "Missing transaction argument", "data": {"key": "quantity", "type": "Int"}}"#,
+    )
+    .unwrap();
+    match error.typed_data() {
+        Some(Ok(TrpErrorData::MissingTransactionArgument(data))) => {
+            assert_eq!(data.key, "quantity")
+        }
+        other => panic!("unexpected data: {:?}", other),
+    }
+
+    let error = TrpError {
+        data: Some(serde_json::json!({"key": 1})),
+        ..error
+    };
+    assert!(matches!(error.typed_data(), Some(Err(_))));
+}
+
+#[test]
+fn declared_codes_are_application_errors() {
+    for code in error_codes::APPLICATION_CODES {
+        assert!(error_codes::APPLICATION.contains(code));
+    }
+    let error = TrpError::declared(TrpErrorKind::UnsupportedTir);
+    assert_eq!(error.class(), Some(ErrorClass::Application));
+    let error = TrpError::reserved(error_codes::UPSTREAM_UNAVAILABLE, "No upstream");
+    assert_eq!(error.class(), Some(ErrorClass::Transport));
+}
@@ -0,0 +1,49 @@
+//! The generated types must accept the examples the spec gives for each
+//! method, so this crate is checked against the spec it was generated from.
+
+use serde_json::Value;
+use trp_types::{ResolveParams, SubmitParams, SubmitResponse, TxEnvelope};
+
+const SPEC: &str = include_str!("../../../specs/trp.json");
+
+/// The `(params, result)` values of the examples of `method`.
+fn examples(method: &str) -> Vec<(Value, Value)> {
+    let spec: Value = serde_json::from_str(SPEC).unwrap();
+    let method = spec["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["name"] == method)
+        .unwrap_or_else(|| panic!("{} is not in the spec", method));
+    let examples = method["examples"].as_array().unwrap();
+    assert!(!examples.is_empty());
+    examples
+        .iter()
+        .map(|e| {
+            (
+                e["params"][0]["value"].clone(),
+                e["result"]["value"].clone(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn resolve_examples() {
+    for (params, result) in examples(trp_types::constants::TRP_RESOLVE) {
+        let params: ResolveParams = serde_json::from_value(params).unwrap();
+        params.validate().unwrap();
+        let result: TxEnvelope = serde_json::from_value(result).unwrap();
+        result.validate().unwrap();
+    }
+}
+
+#[test]
+fn submit_examples() {
+    for (params, result) in examples(trp_types::constants::TRP_SUBMIT) {
+        let params: SubmitParams = serde_json::from_value(params).unwrap();
+        params.validate().unwrap();
+        let result: SubmitResponse = serde_json::from_value(result).unwrap();
+        result.validate().unwrap();
+    }
+}
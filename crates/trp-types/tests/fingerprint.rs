@@ -0,0 +1,82 @@
+//! Requests meaning the same thing fingerprint the same, whatever the order
+//! of their members and the formatting of their strings and numbers.
+
+use serde_json::{json, Value};
+use trp_types::fingerprint::{canonical_json, request_fingerprint, to_hex};
+
+fn parse(text: &str) -> Value {
+    serde_json::from_str(text).unwrap()
+}
+
+#[test]
+fn canonical() {
+    // The example of RFC 8785, section 3.2.2.
+    let value = parse(
+        r#"{
+            "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+            "string": "€$\u000F\u000aA'B\"\\\\\"\/",
+            "literals": [null, true, false]
+        }"#,
+    );
+    assert_eq!(
+        canonical_json(&value),
+        r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+    );
+
+    for (number, expected) in [
+        ("0", "0"),
+        ("-0.0", "0"),
+        ("1.0", "1"),
+        ("-25.00", "-25"),
+        ("1e2", "100"),
+        ("9007199254740993", "9007199254740993"),
+        ("-9223372036854775808", "-9223372036854775808"),
+        ("18446744073709551615", "18446744073709551615"),
+        ("1e21", "1e+21"),
+        ("123456.789", "123456.789"),
+        ("0.000001", "0.000001"),
+        ("0.0000001", "1e-7"),
+        ("-1.5e-9", "-1.5e-9"),
+    ] {
+        assert_eq!(canonical_json(&parse(number)), expected, "{}", number);
+    }
+
+    // Members sort by UTF-16 code units: U+1F600 before U+FB01.
+    let value = json!({ "ﬁ": 1, "😀": 2, "b": 3, "a": { "z": [], "y": {} } });
+    assert_eq!(
+        canonical_json(&value),
+        r#"{"a":{"y":{},"z":[]},"b":3,"😀":2,"ﬁ":1}"#
+    );
+}
+
+#[test]
+fn fingerprints() {
+    let fingerprint =
+        |method: &str, params: &str| to_hex(&request_fingerprint(method, &parse(params)));
+    // SHA-256 of `["trp.resolve",{"a":1}]`.
+    let expected = "09c1dfba2e8dd758133e44b2ec4cbf8f231320857280275d09cb39658a8d3b06";
+    assert_eq!(fingerprint("trp.resolve", r#"{"a":1}"#), expected);
+    assert_eq!(fingerprint("trp.resolve", "{ \"a\": 1.0 }"), expected);
+    assert_ne!(fingerprint("trp.submit", r#"{"a":1}"#), expected);
+    assert_ne!(fingerprint("trp.resolve", r#"{"a":2}"#), expected);
+
+    assert_eq!(
+        fingerprint("trp.submit", "null"),
+        "6e063e4c06d82ed19c913cb29cad7a41168b205d48672185f1039d8d7d90392d"
+    );
+    // Spanning several SHA-256 blocks.
+    let long = json!({ "text": "x".repeat(100) }).to_string();
+    assert_eq!(
+        fingerprint("m", &long),
+        "d14a4d53cbd66f55cdf55fadb0c11e5670b30cceb55b5f6757119a4ca894f86f"
+    );
+
+    let reordered = [
+        r#"{"tir":{"version":"v1beta0","encoding":"hex","bytecode":"00"},"args":{"b":[1,2],"a":"A"}}"#,
+        r#"{"args":{"a":"A","b":[1.0,2e0]},"tir":{"bytecode":"00","encoding":"hex","version":"v1beta0"}}"#,
+    ];
+    assert_eq!(
+        fingerprint("trp.resolve", reordered[0]),
+        fingerprint("trp.resolve", reordered[1])
+    );
+}
@@ -0,0 +1,25 @@
+//! Hex encodes lowercase, decodes either case and tells where invalid text
+//! stops decoding.
+
+use trp_types::hex;
+
+#[test]
+fn round_trip() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    let text = hex::encode(&bytes);
+    assert_eq!(&text[..8], "00010203");
+    assert_eq!(&text[text.len() - 4..], "feff");
+    assert_eq!(hex::decode(&text).unwrap(), bytes);
+    assert_eq!(hex::decode("CAFEbabe").unwrap(), [0xca, 0xfe, 0xba, 0xbe]);
+    assert_eq!(hex::encode(&[]), "");
+    assert_eq!(hex::decode("").unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn invalid() {
+    assert_eq!(hex::decode("cafg"), Err(3));
+    assert_eq!(hex::decode("caf"), Err(3));
+    // Signs aren't digits, whatever `from_str_radix` makes of them.
+    assert_eq!(hex::decode("+f"), Err(0));
+    assert_eq!(hex::decode("é0"), Err(0));
+}
@@ -0,0 +1,113 @@
+//! The proxy fails over from upstreams that can't be reached or answer
+//! invalid results, and reports whether any upstream is ready. Run with
+//! `cargo test -p trp-types --features proxy`.
+#![cfg(feature = "proxy")]
+
+use jsonrpsee::server::{Server, ServerHandle};
+use jsonrpsee::tokio;
+use jsonrpsee::RpcModule;
+use serde_json::json;
+use trp_types::constants::TRP_RESOLVE;
+use trp_types::errors::TrpError;
+use trp_types::proxy::{Proxy, UPSTREAM_UNAVAILABLE_CODE};
+use trp_types::server::{serve, Handler, Options};
+use trp_types::{
+    CheckStatusParams, CheckStatusResponse, ResolveParams, SubmitParams, SubmitResponse, TxEnvelope,
+};
+
+/// Resolves and submits everything.
+struct Upstream;
+
+impl Handler for Upstream {
+    async fn resolve(&self, _: ResolveParams) -> Result<TxEnvelope, TrpError> {
+        Ok(TxEnvelope {
+            hash: "resolved".into(),
+            tx: "00".into(),
+        })
+    }
+
+    async fn submit(&self, _: SubmitParams) -> Result<SubmitResponse, TrpError> {
+        Ok(SubmitResponse {
+            hash: "submitted".into(),
+        })
+    }
+
+    async fn check_status(&self, _: CheckStatusParams) -> Result<CheckStatusResponse, TrpError> {
+        Ok(CheckStatusResponse {
+            statuses: Default::default(),
+        })
+    }
+}
+
+/// A URL nothing listens on.
+async fn dead() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+async fn live() -> (String, ServerHandle) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    (url, serve(listener, Upstream, Options::default()))
+}
+
+/// A server answering resolutions with something other than an envelope.
+async fn invalid() -> (String, ServerHandle) {
+    let mut module = RpcModule::new(());
+    module.register_method(TRP_RESOLVE, |_, _, _| true).unwrap();
+    let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", server.local_addr().unwrap());
+    (url, server.start(module))
+}
+
+fn submit_params() -> SubmitParams {
+    let params = json!({ "tx": { "encoding": "hex", "payload": "00" }, "witnesses": [] });
+    serde_json::from_value(params).unwrap()
+}
+
+fn resolve_params() -> ResolveParams {
+    let params = json!({
+        "args": {},
+        "env": {},
+        "tir": { "bytecode": "00", "encoding": "hex", "version": "v1beta0" },
+    });
+    serde_json::from_value(params).unwrap()
+}
+
+#[test]
+fn failover() {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let (live, live_handle) = live().await;
+        let (invalid, invalid_handle) = invalid().await;
+        let proxy = Proxy::new([dead().await, invalid.clone(), live.clone()]);
+
+        for _ in 0..3 {
+            let resolved = proxy.resolve(resolve_params()).await.unwrap();
+            assert_eq!(resolved.hash, "resolved");
+            // Submissions only fail over from upstreams never reached.
+            let submitted = proxy.submit(submit_params()).await.unwrap();
+            assert_eq!(submitted.hash, "submitted");
+        }
+        assert_eq!(proxy.healthy(), [live.as_str()]);
+
+        live_handle.stop().unwrap();
+        invalid_handle.stop().unwrap();
+    });
+}
+
+#[test]
+fn readiness() {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let proxy = Proxy::new([dead().await]);
+        assert!(proxy.ready().await.is_err());
+        let err = proxy.submit(submit_params()).await.unwrap_err();
+        assert_eq!(err.code, i64::from(UPSTREAM_UNAVAILABLE_CODE));
+
+        let (live, handle) = live().await;
+        let proxy = Proxy::new([dead().await, live.clone()]);
+        assert!(proxy.ready().await.is_ok());
+        assert_eq!(proxy.healthy(), [live.as_str()]);
+
+        handle.stop().unwrap();
+    });
+}
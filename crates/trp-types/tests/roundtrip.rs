@@ -0,0 +1,175 @@
+// Generated by cargo xtask gen --lang rust
+//! Every type must survive a JSON round trip, and the examples of the spec
+//! must decode into the declared types and pass validation.
+#![allow(deprecated)]
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+#[allow(unused_imports)]
+use trp_types::*;
+
+/// Encodes `value`, decodes it back and checks that the decoded value
+/// encodes the same.
+#[allow(dead_code)]
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) {
+    let json = serde_json::to_value(value).unwrap();
+    let decoded: T = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+}
+
+#[test]
+fn bytes_envelope_round_trip() {
+    round_trip(&BytesEnvelope::default());
+}
+
+#[test]
+fn check_status_params_round_trip() {
+    round_trip(&CheckStatusParams::default());
+}
+
+#[test]
+fn check_status_response_round_trip() {
+    round_trip(&CheckStatusResponse::default());
+}
+
+#[test]
+fn input_not_resolved_diagnostic_round_trip() {
+    round_trip(&InputNotResolvedDiagnostic::default());
+}
+
+#[test]
+fn input_query_diagnostic_round_trip() {
+    round_trip(&InputQueryDiagnostic::default());
+}
+
+#[test]
+fn missing_tx_arg_diagnostic_round_trip() {
+    round_trip(&MissingTxArgDiagnostic::default());
+}
+
+#[test]
+fn resolve_params_round_trip() {
+    round_trip(&ResolveParams::default());
+}
+
+#[test]
+fn search_space_diagnostic_round_trip() {
+    round_trip(&SearchSpaceDiagnostic::default());
+}
+
+#[test]
+fn submit_params_round_trip() {
+    round_trip(&SubmitParams::default());
+}
+
+#[test]
+fn submit_response_round_trip() {
+    round_trip(&SubmitResponse::default());
+}
+
+#[test]
+fn submit_witness_round_trip() {
+    round_trip(&SubmitWitness::default());
+}
+
+#[test]
+fn tir_info_round_trip() {
+    round_trip(&TirInfo::default());
+}
+
+#[test]
+fn tx_envelope_round_trip() {
+    round_trip(&TxEnvelope::default());
+}
+
+#[test]
+fn tx_script_failure_diagnostic_round_trip() {
+    round_trip(&TxScriptFailureDiagnostic::default());
+}
+
+#[test]
+fn tx_status_round_trip() {
+    round_trip(&TxStatus::default());
+}
+
+#[test]
+fn unsupported_tir_diagnostic_round_trip() {
+    round_trip(&UnsupportedTirDiagnostic::default());
+}
+
+/// The `resolve-transfer` example of `trp.resolve`.
+#[test]
+fn example_resolve_transfer() {
+    let params: ResolveParams = serde_json::from_str(r#"{
+  "tir": {
+    "version": "v1alpha8",
+    "bytecode": "a1646e616d65687472616e73666572",
+    "encoding": "hex"
+  },
+  "args": {
+    "quantity": 1000000
+  },
+  "env": {}
+}"#).unwrap();
+    params.validate().unwrap();
+    round_trip(&params);
+    let result: TxEnvelope = serde_json::from_str(r#"{
+  "tx": "84a300d90102818258200000000000000000000000000000000000000000000000000000000000000000000181a200581d60000000000000000000000000000000000000000000000000000000001a000f4240021a0002a8b1a0f5f6",
+  "hash": "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+}"#).unwrap();
+    result.validate().unwrap();
+    round_trip(&result);
+}
+
+/// The `submit-vkey-witness` example of `trp.submit`.
+#[test]
+fn example_submit_vkey_witness() {
+    let params: SubmitParams = serde_json::from_str(r#"{
+  "tx": {
+    "encoding": "hex",
+    "payload": "84a300d90102818258200000000000000000000000000000000000000000000000000000000000000000000181a200581d60000000000000000000000000000000000000000000000000000000001a000f4240021a0002a8b1a0f5f6"
+  },
+  "witnesses": [
+    {
+      "type": "vkey",
+      "key": {
+        "encoding": "hex",
+        "payload": "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+      },
+      "signature": {
+        "encoding": "hex",
+        "payload": "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+      }
+    }
+  ]
+}"#).unwrap();
+    params.validate().unwrap();
+    round_trip(&params);
+    let result: SubmitResponse = serde_json::from_str(r#"{
+  "hash": "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+}"#).unwrap();
+    result.validate().unwrap();
+    round_trip(&result);
+}
+
+/// The `check-status-confirmed` example of `trp.checkStatus`.
+#[test]
+fn example_check_status_confirmed() {
+    let params: CheckStatusParams = serde_json::from_str(r#"{
+  "hashes": [
+    "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001"
+  ]
+}"#).unwrap();
+    params.validate().unwrap();
+    round_trip(&params);
+    let result: CheckStatusResponse = serde_json::from_str(r#"{
+  "statuses": {
+    "3f1d2c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff001": {
+      "stage": "confirmed",
+      "confirmations": 3
+    }
+  }
+}"#).unwrap();
+    result.validate().unwrap();
+    round_trip(&result);
+}
@@ -0,0 +1,109 @@
+//! Raw JSON is checked against the embedded schemas of the components,
+//! violations pointing at the offending value.
+
+use serde_json::{json, Value};
+use trp_types::constants::{TRP_CHECK_STATUS, TRP_RESOLVE, TRP_SUBMIT};
+use trp_types::schema::{self, result_schema, validate, validate_result, SchemaViolation};
+
+fn violation(name: &str, value: Value) -> (String, String) {
+    let SchemaViolation {
+        schema,
+        path,
+        message,
+    } = validate(name, &value).unwrap_err();
+    assert_eq!(schema, name);
+    (path, message)
+}
+
+#[test]
+fn results() {
+    assert_eq!(result_schema(TRP_RESOLVE), Some("TxEnvelope"));
+    assert_eq!(result_schema(TRP_SUBMIT), Some("SubmitResponse"));
+    assert_eq!(result_schema("rpc.discover"), None);
+    assert!(validate_result("rpc.discover", &json!(42)).is_ok());
+
+    let statuses = json!({ "statuses": {
+        "9f2c": { "stage": "confirmed", "confirmations": 3 },
+        "ab01": { "stage": "pending", "confirmations": 0.0 },
+    }});
+    assert!(validate_result(TRP_CHECK_STATUS, &statuses).is_ok());
+
+    let statuses = json!({ "statuses": { "9f2c": { "stage": "landed", "confirmations": 3 } } });
+    let err = validate_result(TRP_CHECK_STATUS, &statuses).unwrap_err();
+    assert_eq!(err.schema, "CheckStatusResponse");
+    assert_eq!(err.path, r#"$.statuses["9f2c"].stage"#);
+    assert!(err.message.starts_with(r#"expected one of "pending", "#));
+    assert!(err.message.ends_with(r#"found "landed""#));
+}
+
+#[test]
+fn violations() {
+    for (name, value, path, message) in [
+        (
+            "TxEnvelope",
+            json!({ "hash": "00" }),
+            "$",
+            "missing required member `tx`",
+        ),
+        (
+            "TxEnvelope",
+            json!({ "hash": "00", "tx": 1 }),
+            "$.tx",
+            "expected string, found number",
+        ),
+        ("TxEnvelope", json!([]), "$", "expected object, found array"),
+        (
+            "SubmitParams",
+            json!({ "tx": { "encoding": "hex", "payload": "00" }, "witnesses": [{}] }),
+            "$.witnesses[0]",
+            "missing required member `key`",
+        ),
+        (
+            "TxStatus",
+            json!({ "stage": "pending", "confirmations": -1 }),
+            "$.confirmations",
+            "expected at least 0, found -1",
+        ),
+        (
+            "TxStatus",
+            json!({ "stage": "pending", "confirmations": 1.5 }),
+            "$.confirmations",
+            "expected integer, found number",
+        ),
+        ("Missing", json!({}), "$", "no component named `Missing`"),
+    ] {
+        assert_eq!(
+            violation(name, value.clone()),
+            (path.to_string(), message.to_string()),
+            "{} {}",
+            name,
+            value
+        );
+    }
+
+    let err = validate("TxEnvelope", &json!({ "hash": 0, "tx": "00" })).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "$.hash: expected string, found number (schema TxEnvelope)"
+    );
+}
+
+#[test]
+fn bundle() {
+    let bundle: Value = serde_json::from_str(schema::BUNDLE).unwrap();
+    let exported: Value = serde_json::from_str(include_str!(
+        "../../../codegen/jsonschema/bundle.schema.json"
+    ))
+    .unwrap();
+    assert_eq!(bundle, exported);
+
+    // The results of the examples of the spec are valid.
+    let spec: Value = serde_json::from_str(include_str!("../../../specs/trp.json")).unwrap();
+    for method in spec["methods"].as_array().unwrap() {
+        let name = method["name"].as_str().unwrap();
+        for example in method["examples"].as_array().into_iter().flatten() {
+            let result = &example["result"]["value"];
+            assert!(validate_result(name, result).is_ok(), "{} {}", name, result);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! Requests over the server's limits are answered with JSON-RPC errors,
+//! calls can be cancelled, CORS and compression apply when enabled, calls
+//! are logged redacted, and health checks report the backend. Run with
+//! `cargo test -p trp-types --features server`.
+#![cfg(feature = "server")]
+
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+
+use jsonrpsee::server::ServerHandle;
+use jsonrpsee::tokio;
+use serde_json::{json, Value};
+use trp_types::constants::{DISCOVER_METHOD, OPENRPC_SPEC, SPEC_VERSION};
+use trp_types::error_codes;
+use trp_types::errors::TrpError;
+use trp_types::fingerprint::{request_fingerprint, to_hex};
+use trp_types::health::{Health, HEALTH_METHOD, HEALTH_PATH};
+use trp_types::server::{
+    serve, CallRecord, ClientKey, Cors, Handler, Limits, Logging, Options, RateLimit,
+    CANCELLED_CODE, CANCEL_METHOD, RATE_LIMITED_CODE,
+};
+use trp_types::{
+    CheckStatusParams, CheckStatusResponse, ResolveParams, SubmitParams, SubmitResponse, TxEnvelope,
+};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+/// Fails submissions, never finishes resolving, and has no backend.
+struct Unavailable;
+
+impl Handler for Unavailable {
+    async fn resolve(&self, _: ResolveParams) -> Result<TxEnvelope, TrpError> {
+        std::future::pending().await
+    }
+
+    async fn submit(&self, _: SubmitParams) -> Result<SubmitResponse, TrpError> {
+        Err(TrpError {
+            code: error_codes::UPSTREAM_UNAVAILABLE,
+            message: "unavailable".into(),
+            data: None,
+        })
+    }
+
+    async fn check_status(&self, _: CheckStatusParams) -> Result<CheckStatusResponse, TrpError> {
+        std::future::pending().await
+    }
+
+    async fn ready(&self) -> Result<(), String> {
+        Err("backend down".into())
+    }
+}
+
+fn call(id: u64) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": "trp.submit", "params": {} })
+}
+
+/// Posts `body` with an `x-api-key` header, returning the response body
+/// whatever the status.
+fn post(url: &str, key: &str, body: &Value) -> Value {
+    let response = match ureq::post(url).set("x-api-key", key).send_json(body) {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(err) => panic!("{}", err),
+    };
+    response.into_json().unwrap()
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Runtime::new().unwrap()
+}
+
+fn start(runtime: &tokio::runtime::Runtime, options: Options) -> (String, ServerHandle) {
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        (url, serve(listener, Unavailable, options))
+    })
+}
+
+#[test]
+fn limits() {
+    let runtime = runtime();
+    let limits = Limits {
+        max_request_body_size: 1024,
+        max_batch_len: Some(2),
+        rate_limit: Some(RateLimit {
+            burst: 2,
+            per_second: 0.001,
+            key: ClientKey::Header("x-api-key".into()),
+        }),
+    };
+    let options = Options {
+        limits,
+        ..Options::default()
+    };
+    let (url, handle) = start(&runtime, options);
+
+    assert_eq!(RATE_LIMITED_CODE, -32012);
+    assert_ne!(i64::from(RATE_LIMITED_CODE), error_codes::BATCHES_NOT_SUPPORTED);
+    for id in 0..2 {
+        assert_ne!(
+            post(&url, "a", &call(id))["error"]["code"],
+            RATE_LIMITED_CODE
+        );
+    }
+    assert_eq!(
+        post(&url, "a", &call(2))["error"]["code"],
+        RATE_LIMITED_CODE
+    );
+    assert_ne!(
+        post(&url, "b", &call(3))["error"]["code"],
+        RATE_LIMITED_CODE
+    );
+
+    let batch = json!([call(4), call(5), call(6)]);
+    assert_eq!(
+        post(&url, "c", &batch)["error"]["code"],
+        error_codes::TOO_BIG_BATCH_REQUEST
+    );
+
+    let oversized =
+        json!({ "jsonrpc": "2.0", "id": 7, "method": "trp.submit", "params": "0".repeat(2048) });
+    assert_eq!(
+        post(&url, "d", &oversized)["error"]["code"],
+        error_codes::OVERSIZED_REQUEST
+    );
+
+    handle.stop().unwrap();
+}
+
+/// Sends `body` over the WebSocket `socket`.
+fn send(socket: &mut WebSocket<MaybeTlsStream<TcpStream>>, body: &Value) {
+    socket.send(Message::text(body.to_string())).unwrap();
+}
+
+/// The next response `socket` receives.
+fn receive(socket: &mut WebSocket<MaybeTlsStream<TcpStream>>) -> Value {
+    loop {
+        if let Message::Text(text) = socket.read().unwrap() {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+#[test]
+fn cancel() {
+    let runtime = runtime();
+    let (url, handle) = start(&runtime, Options::default());
+    let ws = url.replace("http://", "ws://");
+    let (mut caller, _) = tungstenite::connect(&ws).unwrap();
+    let (mut other, _) = tungstenite::connect(&ws).unwrap();
+
+    let resolve = json!({
+        "jsonrpc": "2.0",
+        "id": "slow",
+        "method": "trp.resolve",
+        "params": { "tir": { "bytecode": "00", "encoding": "hex", "version": "v1alpha1" }, "args": {}, "env": {} },
+    });
+    send(&mut caller, &resolve);
+    std::thread::sleep(std::time::Duration::from_millis(200));
+
+    // Only the connection of the call can cancel it.
+    let cancel =
+        json!({ "jsonrpc": "2.0", "id": 1, "method": CANCEL_METHOD, "params": { "id": "slow" } });
+    assert_eq!(post(&url, "", &cancel)["result"], false);
+    send(&mut other, &cancel);
+    assert_eq!(receive(&mut other)["result"], false);
+
+    send(&mut caller, &cancel);
+    let mut responses = [receive(&mut caller), receive(&mut caller)];
+    responses.sort_by_key(|response| response["id"].is_string());
+    assert_eq!(responses[0]["result"], true);
+    assert_eq!(responses[1]["error"]["code"], CANCELLED_CODE);
+    send(&mut caller, &cancel);
+    assert_eq!(receive(&mut caller)["result"], false);
+
+    handle.stop().unwrap();
+}
+
+#[test]
+fn http() {
+    let runtime = runtime();
+    let options = Options {
+        cors: Some(Cors {
+            origins: vec!["https://app.example".into()],
+        }),
+        compression: true,
+        ..Options::default()
+    };
+    let (url, handle) = start(&runtime, options);
+
+    let preflight = |origin: &str| {
+        let response = ureq::request("OPTIONS", &url)
+            .set("origin", origin)
+            .set("access-control-request-method", "POST")
+            .call()
+            .unwrap();
+        response
+            .header("access-control-allow-origin")
+            .map(String::from)
+    };
+    assert_eq!(
+        preflight("https://app.example").as_deref(),
+        Some("https://app.example")
+    );
+    assert_eq!(preflight("https://other.example"), None);
+
+    let response = ureq::post(&url)
+        .set("accept-encoding", "br")
+        .send_json(call(1))
+        .unwrap();
+    assert_eq!(response.header("content-encoding"), Some("br"));
+
+    handle.stop().unwrap();
+}
+
+/// The spec's methods take their params either way: as the params object
+/// or as an array holding it.
+#[test]
+fn param_structure() {
+    let runtime = runtime();
+    let (url, handle) = start(&runtime, Options::default());
+
+    let params = json!({ "tx": { "encoding": "hex", "payload": "00" }, "witnesses": [] });
+    for params in [params.clone(), json!([params])] {
+        let request =
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "trp.submit", "params": params });
+        assert_eq!(post(&url, "", &request)["error"]["message"], "unavailable");
+    }
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "trp.submit",
+        "params": [params, params],
+    });
+    assert_eq!(post(&url, "", &request)["error"]["code"], -32602);
+
+    handle.stop().unwrap();
+}
+
+#[test]
+fn health() {
+    let runtime = runtime();
+    let (url, handle) = start(&runtime, Options::default());
+
+    let response = match ureq::get(&format!("{}{}", url, HEALTH_PATH)).call() {
+        Err(ureq::Error::Status(503, response)) => response,
+        other => panic!("{:?}", other),
+    };
+    let health: Health = response.into_json().unwrap();
+    assert!(!health.ready);
+    assert_eq!(health.error.as_deref(), Some("backend down"));
+    assert_eq!(health.version.as_deref(), Some(SPEC_VERSION));
+
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": DISCOVER_METHOD });
+    let spec = post(&url, "", &request);
+    assert_eq!(spec["result"]["info"]["version"], SPEC_VERSION);
+    let embedded: Value = serde_json::from_str(OPENRPC_SPEC).unwrap();
+    assert_eq!(spec["result"]["openrpc"], embedded["openrpc"]);
+    assert_eq!(spec["result"]["methods"], embedded["methods"]);
+
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": HEALTH_METHOD });
+    let health: Health = serde_json::from_value(post(&url, "", &request)["result"].take()).unwrap();
+    assert!(!health.ready);
+
+    handle.stop().unwrap();
+}
+
+#[test]
+fn logging() {
+    let runtime = runtime();
+    let records: Arc<Mutex<Vec<CallRecord>>> = Arc::default();
+    let logging = Logging {
+        redact: vec!["signature".into(), "x-api-key".into()],
+        sink: Arc::new({
+            let records = records.clone();
+            move |record| records.lock().unwrap().push(record.clone())
+        }),
+    };
+    let options = Options {
+        limits: Limits {
+            rate_limit: Some(RateLimit {
+                burst: 10,
+                per_second: 1.0,
+                key: ClientKey::Header("x-api-key".into()),
+            }),
+            ..Limits::default()
+        },
+        logging: Some(logging),
+        ..Options::default()
+    };
+    let (url, handle) = start(&runtime, options);
+
+    let witness = json!({ "key": "00", "signature": "secret" });
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "trp.submit",
+        "params": { "tx": "00", "witnesses": [witness] },
+    });
+    post(&url, "key", &request);
+    handle.stop().unwrap();
+
+    let records = records.lock().unwrap();
+    let record = &records[0];
+    assert_eq!(record.method, "trp.submit");
+    assert_eq!(record.id, 1);
+    assert_eq!(record.error_code, Some(-32602));
+    assert_eq!(record.params["witnesses"][0]["key"], "00");
+    assert_eq!(record.params["witnesses"][0]["signature"], "[redacted]");
+    assert!(record.client.starts_with('#'));
+    let params = json!({ "tx": "00", "witnesses": [witness] });
+    let fingerprint = request_fingerprint("trp.submit", &params);
+    assert_eq!(record.params_hash, to_hex(&fingerprint));
+}
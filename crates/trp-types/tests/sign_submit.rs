@@ -0,0 +1,193 @@
+//! `trp sign-submit` checks and signs the hash of a resolved envelope and
+//! either submits the transaction with that witness or prints it witnessed. Run
+//! with `cargo test -p trp-types --features cli,server`.
+#![cfg(all(feature = "cli", feature = "server"))]
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use ed25519_dalek::{Signer, SigningKey};
+use jsonrpsee::tokio;
+use serde_json::json;
+use trp_types::backend::{Backend, Memory, Submitted, Witness};
+use trp_types::constants::OPENRPC_SPEC;
+use trp_types::server::{serve, Options};
+
+// The first key of RFC 8032, section 7.1.
+const SECRET: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+const PUBLIC: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(text: &str) -> Vec<u8> {
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// The blake2b-256 of a transaction body, as hex.
+fn body_hash(body: &str) -> String {
+    hex(&Blake2b::<U32>::digest(from_hex(body)))
+}
+
+fn signature(hash: &str) -> String {
+    let key: [u8; 32] = from_hex(SECRET).try_into().unwrap();
+    hex(&SigningKey::from_bytes(&key)
+        .sign(&from_hex(hash))
+        .to_bytes())
+}
+
+/// A file of `contents` in a directory of the test's own.
+fn file(test: &str, name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("trp-sign-submit-{}-{}", std::process::id(), test));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Runs `trp sign-submit` with `args` and `stdin`, answering whether it
+/// succeeded and what it printed.
+fn trp(args: &[&str], stdin: &str) -> (bool, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_trp"))
+        .arg("sign-submit")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let out = child.wait_with_output().unwrap();
+    (out.status.success(), String::from_utf8(out.stdout).unwrap())
+}
+
+#[test]
+fn dry_run() {
+    let skey = json!({
+        "type": "PaymentSigningKeyShelley_ed25519",
+        "description": "Payment Signing Key",
+        "cborHex": format!("5820{}", SECRET),
+    });
+    let skey = file("dry-run", "payment.skey", &skey.to_string());
+    let skey = skey.to_str().unwrap();
+    let hash = body_hash("a0");
+    let witness = format!("825820{}5840{}", PUBLIC, signature(&hash));
+
+    // [{}, {}, true, null], with no witness yet.
+    let envelope = json!({ "tx": "84a0a0f5f6", "hash": hash }).to_string();
+    let (success, out) = trp(&["--key", skey, "--dry-run"], &envelope);
+    assert!(success);
+    assert_eq!(out, format!("84a0a10081{}f5f6\n", witness));
+
+    // A witness set holding a tagged set of one witness and a script.
+    let existing = format!("825820{}5840{}", "11".repeat(32), "22".repeat(64));
+    let tx = format!("84a0a200d9010281{}038141aaf5f6", existing);
+    let envelope = file(
+        "dry-run",
+        "envelope.json",
+        &json!({ "tx": tx, "hash": hash }).to_string(),
+    );
+    let envelope = envelope.to_str().unwrap();
+    let (success, out) = trp(&["--key", skey, "--envelope", envelope, "--dry-run"], "");
+    assert!(success);
+    assert_eq!(
+        out,
+        format!("84a0a200d9010282{}{}038141aaf5f6\n", existing, witness)
+    );
+
+    let (success, out) = trp(
+        &[
+            "--key",
+            skey,
+            "--envelope",
+            envelope,
+            "--dry-run",
+            "--output",
+            "json",
+        ],
+        "",
+    );
+    assert!(success);
+    let params: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(params["tx"]["payload"], tx);
+    assert_eq!(params["witnesses"][0]["type"], "vkey");
+    assert_eq!(params["witnesses"][0]["key"]["payload"], PUBLIC);
+    assert_eq!(params["witnesses"][0]["signature"]["payload"], signature(&hash));
+
+    // Witnesses carry the raw key and signature, as the spec's example does.
+    let spec: serde_json::Value = serde_json::from_str(OPENRPC_SPEC).unwrap();
+    let submit = spec["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|method| method["name"] == "trp.submit")
+        .unwrap();
+    let example = &submit["examples"][0]["params"][0]["value"]["witnesses"][0];
+    assert_eq!(example["key"]["payload"].as_str().unwrap().len(), PUBLIC.len());
+    assert_eq!(example["signature"]["payload"].as_str().unwrap().len(), 128);
+
+    let not_a_tx = json!({ "tx": "a0", "hash": hash }).to_string();
+    assert!(!trp(&["--key", skey, "--dry-run"], &not_a_tx).0);
+    let not_a_key = file("dry-run", "other.skey", "cafe");
+    assert!(!trp(&["--key", not_a_key.to_str().unwrap(), "--dry-run"], "").0);
+}
+
+#[test]
+fn submit() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let memory = Memory::new();
+    let listener = runtime
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle =
+        runtime.block_on(async { serve(listener, Backend(memory.clone()), Options::default()) });
+
+    let skey = file("submit", "payment.skey", SECRET);
+    let hash = body_hash("a0");
+    let envelope = json!({ "tx": "84a0a0f5f6", "hash": hash }).to_string();
+    let (success, out) = trp(&["--key", skey.to_str().unwrap(), "--url", &url], &envelope);
+    assert!(success);
+    assert_eq!(out.trim().len(), 16, "{}", out);
+    let expected = Submitted {
+        tx: from_hex("84a0a0f5f6"),
+        witnesses: vec![Witness {
+            key: from_hex(PUBLIC),
+            signature: from_hex(&signature(&hash)),
+        }],
+    };
+    assert_eq!(memory.submitted(), [expected]);
+
+    handle.stop().unwrap();
+}
+
+#[test]
+fn tampered_hash() {
+    let skey = file("tampered-hash", "payment.skey", SECRET);
+    let skey = skey.to_str().unwrap();
+    let hash = body_hash("a0");
+
+    let envelope = json!({ "tx": "84a0a0f5f6", "hash": hash.to_uppercase() }).to_string();
+    assert!(trp(&["--key", skey, "--dry-run"], &envelope).0);
+
+    // The hash of another body, or one of the right body flipped.
+    let mut tampered = from_hex(&hash);
+    tampered[0] ^= 1;
+    for hash in [body_hash("a1"), hex(&tampered)] {
+        let envelope = json!({ "tx": "84a0a0f5f6", "hash": hash }).to_string();
+        let (success, out) = trp(&["--key", skey, "--dry-run"], &envelope);
+        assert!(!success);
+        assert_eq!(out, "");
+    }
+}
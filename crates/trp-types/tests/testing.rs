@@ -0,0 +1,87 @@
+//! Generated requests pass the schema of their method's params, and their
+//! mutations break it; the server accepts every valid one. Run with
+//! `cargo test -p trp-types --features testing,server`.
+#![cfg(all(feature = "testing", feature = "server"))]
+
+use jsonrpsee::tokio;
+use proptest::prelude::*;
+use serde_json::Value;
+use trp_types::errors::TrpError;
+use trp_types::schema::{params_schema, validate};
+use trp_types::server::{rpc_module, Handler};
+use trp_types::testing::{self, Mutation, Request, METHODS};
+use trp_types::{
+    CheckStatusParams, CheckStatusResponse, ResolveParams, SubmitParams, SubmitResponse, TxEnvelope,
+};
+
+/// Answers every call at once.
+struct Stub;
+
+impl Handler for Stub {
+    async fn resolve(&self, _: ResolveParams) -> Result<TxEnvelope, TrpError> {
+        Ok(TxEnvelope {
+            hash: "00".into(),
+            tx: "00".into(),
+        })
+    }
+
+    async fn submit(&self, _: SubmitParams) -> Result<SubmitResponse, TrpError> {
+        Ok(SubmitResponse { hash: "00".into() })
+    }
+
+    async fn check_status(&self, _: CheckStatusParams) -> Result<CheckStatusResponse, TrpError> {
+        Ok(CheckStatusResponse::default())
+    }
+}
+
+/// The response of the server to `request`.
+fn answer(request: &Request) -> Value {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let module = rpc_module(Stub);
+    let body = request.to_json(1).to_string();
+    let (response, _) = runtime.block_on(module.raw_json_request(&body, 1)).unwrap();
+    serde_json::from_str(&response).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn valid(request in testing::any_valid()) {
+        let schema = params_schema(request.method).unwrap();
+        prop_assert!(validate(schema, &request.params).is_ok());
+        let response = answer(&request);
+        prop_assert!(response.get("result").is_some(), "{} {}", request.params, response);
+    }
+
+    #[test]
+    fn near_valid((request, mutation) in testing::any_near_valid()) {
+        let schema = params_schema(request.method).unwrap();
+        let err = validate(schema, &request.params).unwrap_err();
+        // The violation is at the mutated value or around it.
+        let (outer, inner) = match &mutation {
+            Mutation::Removed { path } => (err.path.as_str(), path.as_str()),
+            _ => (mutation.path(), err.path.as_str()),
+        };
+        prop_assert!(inner.starts_with(outer), "{:?} {}", mutation, err);
+        let response = answer(&request);
+        prop_assert!(response.get("result").is_some() || response.get("error").is_some());
+    }
+}
+
+#[test]
+fn methods() {
+    assert_eq!(METHODS.len(), 3);
+    for method in METHODS {
+        assert!(testing::valid(method).is_some());
+        assert!(testing::near_valid(method).is_some());
+    }
+    assert!(testing::valid("trp.unknown").is_none());
+
+    let seeds: [&[u8]; 4] = [b"", b"a", b"b", &[7; 100]];
+    for seed in seeds {
+        let request = testing::from_seed(seed);
+        assert_eq!(testing::from_seed(seed), request);
+        assert!(METHODS.contains(&request.method));
+    }
+}
@@ -0,0 +1,93 @@
+//! TIR programs are checked before they're sent: their version has to be
+//! supported, their encoding known and their bytecode has to decode.
+
+use trp_types::tir::{TirEncoding, TirEnvelope, TirError, TIR_VERSIONS};
+use trp_types::TirInfo;
+
+fn info(version: &str, encoding: &str, bytecode: &str) -> TirInfo {
+    TirInfo {
+        bytecode: bytecode.to_string(),
+        encoding: encoding.to_string(),
+        version: version.to_string(),
+    }
+}
+
+#[test]
+fn round_trip() {
+    assert_eq!(TIR_VERSIONS, ["v1alpha8", "v1beta0"]);
+    let envelope = TirEnvelope::try_from(&info("v1beta0", "hex", "A1006161")).unwrap();
+    assert_eq!(envelope.version(), "v1beta0");
+    assert_eq!(envelope.encoding(), TirEncoding::Hex);
+    assert_eq!(envelope.bytecode(), [0xa1, 0x00, 0x61, 0x61]);
+    let sent = TirInfo::from(&envelope);
+    assert_eq!(sent.bytecode, "a1006161");
+
+    let sent = TirInfo::from(envelope.clone().with_encoding(TirEncoding::Base64));
+    assert_eq!(
+        (sent.encoding.as_str(), sent.bytecode.as_str()),
+        ("base64", "oQBhYQ==")
+    );
+    assert_eq!(
+        TirEnvelope::try_from(sent).unwrap(),
+        envelope.with_encoding(TirEncoding::Base64)
+    );
+
+    // Unpadded and URL-safe base64 decode too.
+    for text in ["oQBhYQ", "-_8=", "-_8"] {
+        assert!(TirEncoding::Base64.decode(text).is_ok(), "{}", text);
+    }
+    assert_eq!(TirEncoding::Base64.decode("-_8").unwrap(), [0xfb, 0xff]);
+    for len in 1..8 {
+        let bytes: Vec<u8> = (0..len).map(|i| i * 37).collect();
+        let text = TirEncoding::Base64.encode(&bytes);
+        assert!(text.len().is_multiple_of(4));
+        assert_eq!(TirEncoding::Base64.decode(&text).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn rejected() {
+    let bytecode = |encoding, offset| Err(TirError::Bytecode { encoding, offset });
+    for (info, expected) in [
+        (
+            info("v1alpha1", "hex", "00"),
+            Err(TirError::Version("v1alpha1".into())),
+        ),
+        (
+            info("v1beta0", "utf8", "00"),
+            Err(TirError::Encoding("utf8".into())),
+        ),
+        (info("v1beta0", "hex", ""), Err(TirError::Empty)),
+        (info("v1beta0", "hex", "0g"), bytecode(TirEncoding::Hex, 1)),
+        (info("v1beta0", "hex", "000"), bytecode(TirEncoding::Hex, 3)),
+        (info("v1beta0", "hex", "0é"), bytecode(TirEncoding::Hex, 1)),
+        (
+            info("v1beta0", "base64", "oQBhY"),
+            bytecode(TirEncoding::Base64, 4),
+        ),
+        (
+            info("v1beta0", "base64", "oQ=hYQ"),
+            bytecode(TirEncoding::Base64, 2),
+        ),
+        (
+            info("v1beta0", "base64", "oQBhYQ="),
+            bytecode(TirEncoding::Base64, 6),
+        ),
+        (
+            info("v1beta0", "base64", "oQBhYQ==="),
+            bytecode(TirEncoding::Base64, 6),
+        ),
+    ] {
+        assert_eq!(TirEnvelope::try_from(&info), expected, "{:?}", info);
+    }
+    assert_eq!(
+        TirEnvelope::new("v2", TirEncoding::Hex, vec![0])
+            .unwrap_err()
+            .to_string(),
+        "unsupported TIR version `v2`, expected one of v1alpha8, v1beta0"
+    );
+    assert_eq!(
+        TirEncoding::Hex.decode("0x00").unwrap_err().to_string(),
+        "the TIR bytecode isn't valid hex at offset 1"
+    );
+}
@@ -0,0 +1,39 @@
+//! UTxO references parse from their text and object forms, reject invalid
+//! hashes and indexes, and serialize canonically.
+
+use trp_types::utxo::{UtxoRef, UtxoRefError};
+
+const TXID: &str = "1F1E1D1C1B1A191817161514131211100F0E0D0C0B0A09080706050403020100";
+
+#[test]
+fn forms() {
+    let text: UtxoRef = format!("{}#7", TXID).parse().unwrap();
+    assert_eq!(text.txid[0], 0x1f);
+    assert_eq!(text.index, 7);
+
+    let object = serde_json::json!({ "txid": TXID, "index": 7 });
+    assert_eq!(serde_json::from_value::<UtxoRef>(object).unwrap(), text);
+    let string = serde_json::json!(format!("{}#7", TXID));
+    assert_eq!(serde_json::from_value::<UtxoRef>(string).unwrap(), text);
+
+    let canonical = format!("{}#7", TXID.to_lowercase());
+    assert_eq!(text.to_string(), canonical);
+    assert_eq!(serde_json::to_value(text).unwrap(), canonical);
+}
+
+#[test]
+fn invalid() {
+    let cases = [
+        ("no-separator".to_string(), UtxoRefError::Format),
+        (format!("{}#", TXID), UtxoRefError::Index),
+        (format!("{}#+1", TXID), UtxoRefError::Index),
+        (format!("{}#4294967296", TXID), UtxoRefError::Index),
+        (format!("{}#0", &TXID[2..]), UtxoRefError::TxidLength(62)),
+        (format!("{}zz#0", &TXID[2..]), UtxoRefError::TxidHex),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(text.parse::<UtxoRef>(), Err(expected), "{}", text);
+    }
+    let object = serde_json::json!({ "txid": "00", "index": 0 });
+    assert!(serde_json::from_value::<UtxoRef>(object).is_err());
+}
@@ -0,0 +1,263 @@
+//! Waiting for a submitted transaction polls its status until it reaches
+//! the stage asked for, reporting each change, and fails when it's dropped
+//! or the timeout elapses. Run with
+//! `cargo test -p trp-types --features blocking,server`.
+#![cfg(all(feature = "blocking", feature = "server"))]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use jsonrpsee::server::ServerHandle;
+use jsonrpsee::tokio;
+use serde_json::json;
+use trp_types::client::{blocking, Client};
+use trp_types::error_codes;
+use trp_types::errors::TrpError;
+use trp_types::server::{serve, Handler, Options};
+use trp_types::watch::{Event, Stage, Wait, WaitError};
+use trp_types::{
+    CheckStatusParams, CheckStatusResponse, ResolveParams, SubmitParams, SubmitResponse,
+    TxEnvelope, TxStatus,
+};
+
+/// Answers the stages of `stages` in turn to the status checks of the
+/// transaction it submitted, then the last one. Without stages, it answers
+/// that `trp.checkStatus` isn't found, as servers predating it do.
+#[derive(Clone)]
+struct Chain {
+    stages: &'static [(&'static str, i64)],
+    checks: Arc<AtomicUsize>,
+}
+
+impl Handler for Chain {
+    async fn resolve(&self, _: ResolveParams) -> Result<TxEnvelope, TrpError> {
+        std::future::pending().await
+    }
+
+    async fn submit(&self, _: SubmitParams) -> Result<SubmitResponse, TrpError> {
+        Ok(SubmitResponse {
+            hash: "cafe".into(),
+        })
+    }
+
+    async fn check_status(
+        &self,
+        params: CheckStatusParams,
+    ) -> Result<CheckStatusResponse, TrpError> {
+        if self.stages.is_empty() {
+            return Err(TrpError {
+                code: error_codes::METHOD_NOT_FOUND,
+                message: "Method not found".into(),
+                data: None,
+            });
+        }
+        let check = self.checks.fetch_add(1, Ordering::SeqCst);
+        let (stage, confirmations) = self.stages[check.min(self.stages.len() - 1)];
+        let statuses = params
+            .hashes
+            .into_iter()
+            .filter(|hash| hash == "cafe")
+            .map(|hash| {
+                let status = TxStatus {
+                    stage: stage.into(),
+                    confirmations,
+                };
+                (hash, status)
+            })
+            .collect();
+        Ok(CheckStatusResponse { statuses })
+    }
+}
+
+fn start(runtime: &tokio::runtime::Runtime, chain: Chain) -> (String, ServerHandle) {
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        (url, serve(listener, chain, Options::default()))
+    })
+}
+
+fn chain(stages: &'static [(&'static str, i64)]) -> Chain {
+    Chain {
+        stages,
+        checks: Arc::default(),
+    }
+}
+
+fn params() -> SubmitParams {
+    serde_json::from_value(json!({ "tx": { "encoding": "hex", "payload": "00" }, "witnesses": [] }))
+        .unwrap()
+}
+
+fn wait(until: Stage, confirmations: u64) -> Wait {
+    Wait {
+        until,
+        confirmations,
+        interval: Duration::from_millis(10),
+        timeout: Duration::from_secs(5),
+    }
+}
+
+/// The events of a wait, written as `stage/confirmations`.
+fn describe(event: &Event) -> String {
+    match event {
+        Event::Submitted(hash) => format!("submitted {}", hash),
+        Event::Status(status) => format!("{}/{}", status.stage, status.confirmations),
+    }
+}
+
+const STAGES: &[(&str, i64)] = &[
+    ("unknown", 0),
+    ("pending", 0),
+    ("pending", 0),
+    ("acknowledged", 0),
+    ("confirmed", 0),
+    ("confirmed", 1),
+    ("confirmed", 2),
+    ("finalized", 9),
+];
+
+#[test]
+fn submit_and_wait() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (url, handle) = start(&runtime, chain(STAGES));
+    let client = blocking::Client::new(&url);
+    let mut events = Vec::new();
+    let (hash, status) = client
+        .submit_and_wait(&params(), &wait(Stage::Confirmed, 2), |event| {
+            events.push(describe(event))
+        })
+        .unwrap();
+    assert_eq!(hash, "cafe");
+    assert_eq!(
+        (status.stage.as_str(), status.confirmations),
+        ("confirmed", 2)
+    );
+    // Unchanged statuses aren't reported again.
+    let expected = [
+        "submitted cafe",
+        "unknown/0",
+        "pending/0",
+        "acknowledged/0",
+        "confirmed/0",
+        "confirmed/1",
+        "confirmed/2",
+    ];
+    assert_eq!(events, expected);
+    handle.stop().unwrap();
+
+    // Later stages count, whatever their confirmations.
+    let (url, handle) = start(&runtime, chain(STAGES));
+    let status = runtime
+        .block_on(Client::new(&url).wait_for_confirmation(
+            "cafe",
+            &wait(Stage::Confirmed, 5),
+            |_| {},
+        ))
+        .unwrap();
+    assert_eq!(status.stage, "finalized");
+    handle.stop().unwrap();
+
+    let (url, handle) = start(&runtime, chain(STAGES));
+    let client = blocking::Client::new(&url);
+    let status = client
+        .wait_for_confirmation("cafe", &wait(Stage::Acknowledged, 5), |_| {})
+        .unwrap();
+    assert_eq!(status.stage, "acknowledged");
+    handle.stop().unwrap();
+}
+
+#[test]
+fn failures() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (url, handle) = start(&runtime, chain(&[("pending", 0), ("dropped", 0)]));
+    let client = blocking::Client::new(&url);
+    match client.wait_for_confirmation("cafe", &wait(Stage::Confirmed, 1), |_| {}) {
+        Err(WaitError::Dropped(status)) => assert_eq!(status.stage, "dropped"),
+        other => panic!("{:?}", other),
+    }
+    handle.stop().unwrap();
+
+    let (url, handle) = start(&runtime, chain(&[("pending", 0)]));
+    let client = blocking::Client::new(&url);
+    let short = Wait {
+        timeout: Duration::from_millis(50),
+        ..wait(Stage::Confirmed, 1)
+    };
+    match client.wait_for_confirmation("cafe", &short, |_| {}) {
+        Err(WaitError::Timeout(Some(status))) => assert_eq!(status.stage, "pending"),
+        other => panic!("{:?}", other),
+    }
+    match client.wait_for_confirmation("beef", &short, |_| {}) {
+        Err(WaitError::Timeout(Some(status))) => assert_eq!(status.stage, "unknown"),
+        other => panic!("{:?}", other),
+    }
+    handle.stop().unwrap();
+
+    // Servers without trp.checkStatus still get the transaction.
+    let (url, handle) = start(&runtime, chain(&[]));
+    let client = blocking::Client::new(&url);
+    let mut events = Vec::new();
+    match client.submit_and_wait(&params(), &wait(Stage::Confirmed, 1), |event| {
+        events.push(describe(event))
+    }) {
+        Err(WaitError::Unsupported(hash)) => assert_eq!(hash, "cafe"),
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(events, ["submitted cafe"]);
+    handle.stop().unwrap();
+}
+
+/// Runs `trp sign-submit --wait` against the server at `url`, answering
+/// whether it succeeded, its output and its progress.
+#[cfg(feature = "cli")]
+fn sign_submit_and_wait(url: &str) -> (bool, String, String) {
+    use std::process::{Command, Stdio};
+
+    let key = std::env::temp_dir().join(format!("trp-watch-{}.skey", std::process::id()));
+    std::fs::write(&key, "00".repeat(32)).unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_trp"))
+        .args(["sign-submit", "--url", url, "--key", key.to_str().unwrap()])
+        .args(["--wait", "--confirmations", "2", "--interval", "0.01"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The hash is the blake2b-256 of the empty body, `a0`.
+    let hash = "d36a2619a672494604e11bb447cbcf5231e9f2ba25c2169177edc941bd50ad6c";
+    let envelope = json!({ "tx": "84a0a0f5f6", "hash": hash }).to_string();
+    std::io::Write::write_all(&mut child.stdin.take().unwrap(), envelope.as_bytes()).unwrap();
+    let out = child.wait_with_output().unwrap();
+    (
+        out.status.success(),
+        String::from_utf8(out.stdout).unwrap(),
+        String::from_utf8(out.stderr).unwrap(),
+    )
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cli() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (url, handle) = start(&runtime, chain(STAGES));
+    let (success, out, progress) = sign_submit_and_wait(&url);
+    assert!(success);
+    assert_eq!(out, "cafe\n");
+    assert!(progress.starts_with("submitted cafe\n"), "{}", progress);
+    assert!(
+        progress.ends_with("confirmed (2 confirmations)\n"),
+        "{}",
+        progress
+    );
+    handle.stop().unwrap();
+
+    // Servers without trp.checkStatus get the transaction, unwaited for.
+    let (url, handle) = start(&runtime, chain(&[]));
+    let (success, out, progress) = sign_submit_and_wait(&url);
+    assert!(success, "{}", progress);
+    assert_eq!(out, "cafe\n");
+    assert!(progress.contains("not waiting"), "{}", progress);
+    handle.stop().unwrap();
+}
@@ -0,0 +1,72 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use trp_codegen::{bundle, openrpc};
+
+#[derive(Parser, Debug)]
+pub struct SplitArgs {
+    /// Path to the bundled OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Directory to write the split spec into
+    #[arg(long, default_value = "specs/trp")]
+    pub dir: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+pub struct MergeArgs {
+    /// Directory holding the split spec
+    #[arg(long, default_value = "specs/trp")]
+    pub dir: PathBuf,
+    /// Path to write the bundled OpenRPC spec to
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Write nothing, and fail if the bundled spec isn't the merge of the
+    /// split one
+    #[arg(long, default_value_t = false)]
+    pub check: bool,
+}
+
+pub fn split(args: SplitArgs) -> Result<()> {
+    let spec = openrpc::load_raw(&args.openrpc)?;
+    fs::create_dir_all(&args.dir)
+        .with_context(|| format!("failed to create directory {}", args.dir.display()))?;
+    bundle::write(&args.dir, &spec)?;
+    let files = bundle::files(&args.dir)?;
+    tracing::info!(
+        "split {} into {} file(s) under {}",
+        args.openrpc.display(),
+        files.len(),
+        args.dir.display()
+    );
+    Ok(())
+}
+
+pub fn merge(args: MergeArgs) -> Result<()> {
+    if !args.dir.is_dir() {
+        anyhow::bail!("{} isn't the directory of a split spec", args.dir.display());
+    }
+    let merged = openrpc::read(&args.dir)?;
+    if args.check {
+        let bundled = fs::read_to_string(&args.openrpc).unwrap_or_default();
+        if bundled != merged {
+            anyhow::bail!(
+                "{} is out of date with {}; run `cargo run --package xtask -- merge` to update it",
+                args.openrpc.display(),
+                args.dir.display()
+            );
+        }
+        return Ok(());
+    }
+    fs::write(&args.openrpc, merged)
+        .with_context(|| format!("failed to write {}", args.openrpc.display()))?;
+    tracing::info!(
+        "merged {} into {}",
+        args.dir.display(),
+        args.openrpc.display()
+    );
+    Ok(())
+}
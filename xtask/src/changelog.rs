@@ -0,0 +1,122 @@
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+
+use crate::diff::{self, Change, ChangeKind};
+use crate::git;
+use trp_codegen::openrpc::{self, OpenRpc};
+
+#[derive(Parser, Debug)]
+pub struct ChangelogArgs {
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Git revision to start from (exclusive)
+    #[arg(long)]
+    pub since: String,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ChangelogFormat::Markdown)]
+    pub format: ChangelogFormat,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ChangelogFormat {
+    Markdown,
+    Json,
+}
+
+#[derive(Serialize)]
+struct Changelog {
+    since: String,
+    revisions: Vec<RevisionEntry>,
+}
+
+#[derive(Serialize)]
+struct RevisionEntry {
+    commit: String,
+    subject: String,
+    version: Option<String>,
+    changes: Vec<Change>,
+}
+
+pub fn run(args: ChangelogArgs) -> Result<()> {
+    let mut revisions = Vec::new();
+    for rev in git::revisions_touching(&args.since, &args.openrpc)? {
+        let old = spec_at(&format!("{}^", rev.commit), &args)?;
+        let new = spec_at(&rev.commit, &args)?;
+        let changes = diff::diff_specs(&old, &new)?;
+        if changes.is_empty() {
+            continue;
+        }
+        revisions.push(RevisionEntry {
+            version: new.version().map(str::to_string),
+            commit: rev.commit,
+            subject: rev.subject,
+            changes,
+        });
+    }
+
+    let changelog = Changelog {
+        since: args.since,
+        revisions,
+    };
+
+    match args.format {
+        ChangelogFormat::Json => println!("{}", serde_json::to_string_pretty(&changelog)?),
+        ChangelogFormat::Markdown => print!("{}", render_markdown(&changelog)),
+    }
+
+    Ok(())
+}
+
+/// Loads the spec at `rev`, treating a revision without the file as an empty
+/// spec so that its introduction shows up as additions.
+fn spec_at(rev: &str, args: &ChangelogArgs) -> Result<OpenRpc> {
+    match git::show_spec(rev, &args.openrpc) {
+        Ok(source) => openrpc::parse(&source, &format!("{}:{}", rev, args.openrpc.display())),
+        Err(_) => openrpc::parse(EMPTY_SPEC, "empty spec"),
+    }
+}
+
+const EMPTY_SPEC: &str =
+    r#"{ "openrpc": "1.3.2", "methods": [], "components": { "schemas": {} } }"#;
+
+fn render_markdown(changelog: &Changelog) -> String {
+    let mut out = format!("# Changelog since {}\n", changelog.since);
+    if changelog.revisions.is_empty() {
+        out.push_str("\nNo spec changes.\n");
+    }
+
+    for rev in &changelog.revisions {
+        let short = &rev.commit[..rev.commit.len().min(8)];
+        let _ = write!(out, "\n## {} {}", short, rev.subject);
+        if let Some(version) = &rev.version {
+            let _ = write!(out, " (v{})", version);
+        }
+        out.push('\n');
+
+        for (kind, heading) in [
+            (ChangeKind::Added, "Added"),
+            (ChangeKind::Removed, "Removed"),
+            (ChangeKind::Changed, "Changed"),
+        ] {
+            let entries: Vec<&Change> = rev.changes.iter().filter(|c| c.kind == kind).collect();
+            if entries.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "\n### {}\n", heading);
+            for c in entries {
+                let _ = writeln!(
+                    out,
+                    "- {} `{}`: {} ({})",
+                    c.subject, c.path, c.detail, c.impact
+                );
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::IsTerminal as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use similar::{ChangeTag, TextDiff};
+use trp_codegen::mapper::proto::FieldNumbers;
+use trp_codegen::Spec;
+
+use crate::gen::{self, GenArgs};
+use crate::manifest;
+
+/// Lines of unchanged context around each hunk of a diff.
+const CONTEXT: usize = 3;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Color {
+    /// Color when printing to a terminal and `NO_COLOR` isn't set
+    Auto,
+    Always,
+    Never,
+}
+
+impl Color {
+    fn enabled(self) -> bool {
+        match self {
+            Color::Auto => {
+                std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+            }
+            Color::Always => true,
+            Color::Never => false,
+        }
+    }
+}
+
+/// How a file on disk strays from the generated one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Drift {
+    /// Generated, but missing on disk.
+    Added,
+    /// On disk from an earlier run, but no longer generated.
+    Removed,
+    Changed,
+}
+
+/// A file on disk that generation would add, remove or change.
+struct FileDrift {
+    path: PathBuf,
+    drift: Drift,
+    old: String,
+    new: String,
+}
+
+/// Renders the bindings into a scratch directory and compares them with
+/// the files on disk, printing a diff of each file that differs and a
+/// summary per language. Fails if any does.
+pub fn run(
+    args: &GenArgs,
+    spec: &Spec,
+    previous: &Option<Arc<Spec>>,
+    banner: &[String],
+    proto_numbers: &FieldNumbers,
+) -> Result<()> {
+    let stage = tempfile::tempdir().context("failed to create a scratch directory")?;
+    let recorded = manifest::recorded(&args.out);
+    let drifts = gen::for_each_language(args, |lang| {
+        let (config, dir) = gen::language_config(args, previous, lang, proto_numbers)?;
+        let staged = stage.path().join(lang);
+        let written = gen::generate_language(args, spec, lang, &config, &staged, banner)?;
+        compare(&dir, &staged, &written, &recorded)
+    })?;
+
+    let color = args.color.enabled();
+    let mut out = String::new();
+    if !args.quiet {
+        for drift in drifts.iter().flatten() {
+            out.push_str(&diff(drift, color));
+        }
+    }
+    let langs: Vec<String> = args.lang.iter().map(|l| l.to_lowercase()).collect();
+    out.push_str(&summary(&langs, &drifts));
+    print!("{}", out);
+
+    let total: usize = drifts.iter().map(Vec::len).sum();
+    if total > 0 {
+        anyhow::bail!(
+            "{} generated file(s) out of date; rerun without --check to update them",
+            total
+        );
+    }
+    Ok(())
+}
+
+/// The files of one language that differ between `dir` on disk and the
+/// `written` ones under `staged`, sorted by path. Files the manifest
+/// `recorded` under `dir` but that weren't generated are removed.
+fn compare(
+    dir: &Path,
+    staged: &Path,
+    written: &[PathBuf],
+    recorded: &[PathBuf],
+) -> Result<Vec<FileDrift>> {
+    let mut drifts = Vec::new();
+    let mut generated = BTreeSet::new();
+    for file in written {
+        let relative = file.strip_prefix(staged).unwrap_or(file);
+        let path = dir.join(relative);
+        let new = read(file)?;
+        let (drift, old) = match path.is_file() {
+            true => (Drift::Changed, read(&path)?),
+            false => (Drift::Added, String::new()),
+        };
+        if drift == Drift::Added || old != new {
+            drifts.push(FileDrift {
+                path: path.clone(),
+                drift,
+                old,
+                new,
+            });
+        }
+        generated.insert(path);
+    }
+    for path in recorded {
+        if path.starts_with(dir) && !generated.contains(path) {
+            drifts.push(FileDrift {
+                path: path.clone(),
+                drift: Drift::Removed,
+                old: read(path)?,
+                new: String::new(),
+            });
+        }
+    }
+    drifts.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(drifts)
+}
+
+fn read(path: &Path) -> Result<String> {
+    let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(String::from_utf8_lossy(&data).into_owned())
+}
+
+/// A unified diff from the file on disk to the generated one, with
+/// headers in bold, hunk ranges in cyan, removed lines in red and added
+/// ones in green when `color` is set.
+fn diff(file: &FileDrift, color: bool) -> String {
+    let paint = |code: &str, text: &str| match color {
+        true => format!("\x1b[{}m{}\x1b[0m", code, text),
+        false => text.to_string(),
+    };
+    let path = file.path.display();
+    let (from, to) = match file.drift {
+        Drift::Added => ("/dev/null".to_string(), format!("b/{}", path)),
+        Drift::Removed => (format!("a/{}", path), "/dev/null".to_string()),
+        Drift::Changed => (format!("a/{}", path), format!("b/{}", path)),
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", paint("1", &format!("--- {}", from)));
+    let _ = writeln!(out, "{}", paint("1", &format!("+++ {}", to)));
+    let lines = TextDiff::from_lines(&file.old, &file.new);
+    for hunk in lines.unified_diff().context_radius(CONTEXT).iter_hunks() {
+        let _ = writeln!(out, "{}", paint("36", &hunk.header().to_string()));
+        for change in hunk.iter_changes() {
+            let line = change.value().trim_end_matches(['\n', '\r']);
+            let _ = match change.tag() {
+                ChangeTag::Delete => writeln!(out, "{}", paint("31", &format!("-{}", line))),
+                ChangeTag::Insert => writeln!(out, "{}", paint("32", &format!("+{}", line))),
+                ChangeTag::Equal => writeln!(out, " {}", line),
+            };
+            if change.missing_newline() {
+                out.push_str("\\ No newline at end of file\n");
+            }
+        }
+    }
+    out
+}
+
+/// A table of the files added, removed and changed in each language, with
+/// their totals.
+fn summary(langs: &[String], drifts: &[Vec<FileDrift>]) -> String {
+    let count =
+        |files: &[FileDrift], drift: Drift| files.iter().filter(|f| f.drift == drift).count();
+    let width = langs
+        .iter()
+        .map(String::len)
+        .chain(["language".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:width$}  {:>5}  {:>7}  {:>7}",
+        "language", "added", "removed", "changed"
+    );
+    let mut totals = [0; 3];
+    for (lang, files) in langs.iter().zip(drifts) {
+        let counts = [
+            count(files, Drift::Added),
+            count(files, Drift::Removed),
+            count(files, Drift::Changed),
+        ];
+        for (total, count) in totals.iter_mut().zip(counts) {
+            *total += count;
+        }
+        let _ = writeln!(
+            out,
+            "{:width$}  {:>5}  {:>7}  {:>7}",
+            lang, counts[0], counts[1], counts[2]
+        );
+    }
+    let _ = writeln!(
+        out,
+        "{:width$}  {:>5}  {:>7}  {:>7}",
+        "total", totals[0], totals[1], totals[2]
+    );
+    out
+}
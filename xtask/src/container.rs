@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::{Component, Path};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::gen::GenArgs;
+use crate::tool;
+
+/// The pinned generation environment, relative to the repository's root.
+const DOCKERFILE: &str = "container/Dockerfile";
+/// Tag of the image built from [`DOCKERFILE`].
+const IMAGE: &str = "trp-gen";
+/// Where the copy of the inputs is mounted in the container.
+const WORKDIR: &str = "/work";
+/// Container engines tried, in order of preference.
+const ENGINES: &[&str] = &["docker", "podman"];
+
+/// Reruns the generation that just ran on the host inside the image built
+/// from [`DOCKERFILE`], and fails unless the container renders every file
+/// byte for byte the same.
+///
+/// The container works on a scratch copy of the inputs (the spec, the
+/// output directory and the Rust crate) and runs `gen --check` over it
+/// with the host's arguments, so that whatever differs is reported the
+/// way `--check` reports drift.
+pub fn verify(args: &GenArgs) -> Result<()> {
+    let _span = tracing::debug_span!("container").entered();
+    let engine = ENGINES
+        .iter()
+        .copied()
+        .find(|engine| tool::available(engine, &["--version"]))
+        .with_context(|| format!("--container needs one of {}", ENGINES.join(" or ")))?;
+    if !Path::new(DOCKERFILE).is_file() {
+        anyhow::bail!(
+            "{} not found; run --container from the repository's root",
+            DOCKERFILE
+        );
+    }
+
+    let scratch = tempfile::tempdir().context("failed to create a scratch directory")?;
+    let mut inputs = vec![&args.openrpc, &args.out];
+    inputs.extend(&args.rust_previous);
+    inputs.extend(&args.rust_crate);
+    inputs.extend(&args.wasm_crate);
+    for input in inputs {
+        copy_input(input, scratch.path())?;
+    }
+
+    tracing::info!("building the {} image with {}", IMAGE, engine);
+    tool::run(
+        engine,
+        &[
+            "build", "--quiet", "--tag", IMAGE, "--file", DOCKERFILE, ".",
+        ],
+        Path::new("."),
+    )?;
+
+    let volume = format!("{}:{}", scratch.path().display(), WORKDIR);
+    let mut command = vec![
+        "run".to_string(),
+        "--rm".into(),
+        "--volume".into(),
+        volume,
+        "--workdir".into(),
+        WORKDIR.into(),
+    ];
+    // Files written to the mount must stay removable by the host's user.
+    if let Some(user) = host_user() {
+        command.extend(["--user".to_string(), user]);
+    }
+    command.push(IMAGE.into());
+    command.extend(container_args());
+
+    // The check prints its diffs and summary straight to the terminal.
+    tracing::info!("generating in the {} container", IMAGE);
+    let status = Command::new(engine)
+        .args(&command)
+        .status()
+        .with_context(|| format!("failed to run {}", engine))?;
+    if !status.success() {
+        anyhow::bail!("the container's output differs from the host's, or it failed to run");
+    }
+    tracing::info!("the container's output is identical to the host's");
+    Ok(())
+}
+
+/// The arguments `xtask` was given after `gen`, to run the same generation
+/// in the container as a check of the files the host generated.
+fn container_args() -> Vec<String> {
+    let mut forwarded: Vec<String> = std::env::args()
+        .skip_while(|arg| arg != "gen")
+        .filter(|arg| !matches!(arg.as_str(), "--container" | "--clean"))
+        .collect();
+    if !forwarded.iter().any(|arg| arg == "--check") {
+        forwarded.push("--check".into());
+    }
+    forwarded
+}
+
+/// Copies `input`, a file or directory relative to the working directory,
+/// to the same place under `scratch`.
+fn copy_input(input: &Path, scratch: &Path) -> Result<()> {
+    if !input
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        anyhow::bail!(
+            "{} must be relative to the working directory, which the container gets a copy of",
+            input.display()
+        );
+    }
+    if !input.exists() {
+        return Ok(());
+    }
+    let target = scratch.join(input);
+    if input.is_dir() {
+        return tool::copy_dir(input, &target);
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    fs::copy(input, &target).with_context(|| format!("failed to copy {}", input.display()))?;
+    Ok(())
+}
+
+/// `uid:gid` of the host's user, where `id` tells them.
+fn host_user() -> Option<String> {
+    let id = |flag| {
+        tool::output("id", &[flag], Path::new("."))
+            .ok()
+            .flatten()
+            .map(|out| out.trim().to_string())
+    };
+    Some(format!("{}:{}", id("-u")?, id("-g")?))
+}
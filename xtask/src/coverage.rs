@@ -0,0 +1,127 @@
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+
+use trp_codegen::coverage::{self, Entry, Support, LANGUAGES};
+use trp_codegen::openrpc;
+
+#[derive(Parser, Debug)]
+pub struct CoverageArgs {
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Comma separated list of languages to report on (all of ts, python, go,
+    /// rust, elm, php, scala, ocaml, c, proto and cddl by default)
+    #[arg(long, value_delimiter = ',')]
+    pub lang: Vec<String>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = CoverageFormat::Text)]
+    pub format: CoverageFormat,
+    /// Fail if any construct isn't fully supported by every language
+    #[arg(long, default_value_t = false)]
+    pub strict: bool,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum CoverageFormat {
+    Text,
+    Json,
+}
+
+pub fn run(args: CoverageArgs) -> Result<()> {
+    let spec = openrpc::load(&args.openrpc)?;
+    let languages: Vec<&str> = match args.lang.is_empty() {
+        true => LANGUAGES.to_vec(),
+        false => args.lang.iter().map(String::as_str).collect(),
+    };
+    if let Some(unknown) = languages.iter().find(|lang| !LANGUAGES.contains(lang)) {
+        anyhow::bail!(
+            "no coverage for {}; expected one of {}",
+            unknown,
+            LANGUAGES.join(", ")
+        );
+    }
+
+    let entries = coverage::report(&spec, &languages)?;
+    match args.format {
+        CoverageFormat::Text => print!("{}", text(&entries, &languages)),
+        CoverageFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+    }
+
+    let degraded = entries.iter().filter(|e| !e.is_full()).count();
+    if args.strict && degraded > 0 {
+        anyhow::bail!(
+            "{} construct(s) in {} aren't fully supported",
+            degraded,
+            args.openrpc.display()
+        );
+    }
+    Ok(())
+}
+
+/// A table of the entries with a column per language, followed by the
+/// totals of each language.
+fn text(entries: &[Entry], languages: &[&str]) -> String {
+    let label = |support: Support| match support {
+        Support::Full => "full",
+        Support::Partial => "partial",
+        Support::Unsupported => "none",
+    };
+    let location_width = entries
+        .iter()
+        .map(|e| e.location.len())
+        .chain(["property".len()])
+        .max()
+        .unwrap_or_default();
+    let construct_width = entries
+        .iter()
+        .map(|e| e.construct.label().len())
+        .chain(["construct".len()])
+        .max()
+        .unwrap_or_default();
+    let widths: Vec<usize> = languages.iter().map(|l| l.len().max(7)).collect();
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "{:location_width$}  {:construct_width$}",
+        "property", "construct"
+    );
+    for (lang, width) in languages.iter().zip(&widths) {
+        let _ = write!(out, "  {:width$}", lang);
+    }
+    out = out.trim_end().to_string();
+    out.push('\n');
+    for entry in entries {
+        let mut line = format!(
+            "{:location_width$}  {:construct_width$}",
+            entry.location,
+            entry.construct.label()
+        );
+        for (lang, width) in languages.iter().zip(&widths) {
+            let _ = write!(line, "  {:width$}", label(entry.support[*lang]));
+        }
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+
+    out.push('\n');
+    for lang in languages {
+        let count = |support: Support| {
+            entries
+                .iter()
+                .filter(|e| e.support[*lang] == support)
+                .count()
+        };
+        let _ = writeln!(
+            out,
+            "{}: {} full, {} partial, {} unsupported",
+            lang,
+            count(Support::Full),
+            count(Support::Partial),
+            count(Support::Unsupported)
+        );
+    }
+    out
+}
@@ -0,0 +1,587 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use anyhow::Result;
+use schemars::schema::{Schema, SchemaObject};
+use serde::Serialize;
+use serde_json::Value;
+
+use trp_codegen::openrpc::{ErrorObject, Method, OpenRpc, ParamStructure};
+use trp_codegen::resolver::{self, ResolvedField, ResolvedType};
+
+/// How a change affects consumers of the protocol, ordered by severity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Impact {
+    Patch,
+    Minor,
+    Major,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Subject {
+    Method,
+    Schema,
+    Field,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl fmt::Display for Impact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Impact::Patch => "patch",
+            Impact::Minor => "minor",
+            Impact::Major => "major",
+        })
+    }
+}
+
+impl fmt::Display for Subject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Subject::Method => "method",
+            Subject::Schema => "schema",
+            Subject::Field => "field",
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Change {
+    pub subject: Subject,
+    pub kind: ChangeKind,
+    /// Method name, schema name, or `Schema.field`.
+    pub path: String,
+    pub detail: String,
+    pub impact: Impact,
+}
+
+/// Compares two revisions of the spec and lists every method, schema and
+/// field level change together with its compatibility impact.
+pub fn diff_specs(old: &OpenRpc, new: &OpenRpc) -> Result<Vec<Change>> {
+    let mut changes = Vec::new();
+    diff_methods(&old.methods, &new.methods, &mut changes);
+
+    let old_types = resolver::resolve_components(old)?;
+    let new_types = resolver::resolve_components(new)?;
+    diff_types(&old_types, &new_types, &mut changes);
+
+    Ok(changes)
+}
+
+/// The highest impact among `changes`, if there are any.
+pub fn overall_impact(changes: &[Change]) -> Option<Impact> {
+    changes.iter().map(|c| c.impact).max()
+}
+
+fn diff_methods(old: &[Method], new: &[Method], out: &mut Vec<Change>) {
+    let old: BTreeMap<&str, &Method> = old.iter().map(|m| (m.name.as_str(), m)).collect();
+    let new: BTreeMap<&str, &Method> = new.iter().map(|m| (m.name.as_str(), m)).collect();
+
+    for (name, method) in &old {
+        let Some(updated) = new.get(name) else {
+            out.push(change(
+                Subject::Method,
+                ChangeKind::Removed,
+                name,
+                "method removed",
+                Impact::Major,
+            ));
+            continue;
+        };
+
+        let old_params: Vec<Value> = method
+            .params
+            .iter()
+            .map(|p| schema_value(&p.schema))
+            .collect();
+        let new_params: Vec<Value> = updated
+            .params
+            .iter()
+            .map(|p| schema_value(&p.schema))
+            .collect();
+        if old_params != new_params {
+            out.push(change(
+                Subject::Method,
+                ChangeKind::Changed,
+                name,
+                "params changed",
+                Impact::Major,
+            ));
+        }
+
+        let old_result = method.result.as_ref().map(|r| schema_value(&r.schema));
+        let new_result = updated.result.as_ref().map(|r| schema_value(&r.schema));
+        if old_result != new_result {
+            out.push(change(
+                Subject::Method,
+                ChangeKind::Changed,
+                name,
+                "result changed",
+                Impact::Major,
+            ));
+        }
+
+        diff_errors(name, &method.errors, &updated.errors, out);
+
+        if method.param_structure != updated.param_structure {
+            // Accepting either structure keeps existing callers working;
+            // narrowing to one of them breaks callers using the other.
+            let impact = if updated.param_structure == ParamStructure::Either {
+                Impact::Minor
+            } else {
+                Impact::Major
+            };
+            out.push(change(
+                Subject::Method,
+                ChangeKind::Changed,
+                name,
+                &format!(
+                    "paramStructure changed from {} to {}",
+                    method.param_structure.as_str(),
+                    updated.param_structure.as_str()
+                ),
+                impact,
+            ));
+        }
+
+        if !method.deprecated && updated.deprecated {
+            out.push(change(
+                Subject::Method,
+                ChangeKind::Changed,
+                name,
+                "method deprecated",
+                Impact::Minor,
+            ));
+        }
+
+        if method.summary != updated.summary {
+            out.push(change(
+                Subject::Method,
+                ChangeKind::Changed,
+                name,
+                "summary changed",
+                Impact::Patch,
+            ));
+        }
+    }
+
+    for name in new.keys().filter(|n| !old.contains_key(*n)) {
+        out.push(change(
+            Subject::Method,
+            ChangeKind::Added,
+            name,
+            "method added",
+            Impact::Minor,
+        ));
+    }
+}
+
+/// Compares the errors a method declares by code: clients match on codes
+/// and read the data of each, so removing a code or changing its data
+/// breaks them, while a new code is one more they may get.
+fn diff_errors(method: &str, old: &[ErrorObject], new: &[ErrorObject], out: &mut Vec<Change>) {
+    let old: BTreeMap<i64, &ErrorObject> = old.iter().map(|e| (e.code, e)).collect();
+    let new: BTreeMap<i64, &ErrorObject> = new.iter().map(|e| (e.code, e)).collect();
+
+    for (code, error) in &old {
+        let Some(updated) = new.get(code) else {
+            out.push(change(
+                Subject::Method,
+                ChangeKind::Changed,
+                method,
+                &format!("error {} ({}) removed", code, error.message),
+                Impact::Major,
+            ));
+            continue;
+        };
+
+        if error.data != updated.data {
+            let (detail, impact) = match (&error.data, &updated.data) {
+                (None, Some(_)) => ("data added", Impact::Minor),
+                (Some(_), None) => ("data removed", Impact::Major),
+                _ => ("data changed", Impact::Major),
+            };
+            out.push(change(
+                Subject::Method,
+                ChangeKind::Changed,
+                method,
+                &format!("error {} {}", code, detail),
+                impact,
+            ));
+        }
+
+        if error.message != updated.message {
+            out.push(change(
+                Subject::Method,
+                ChangeKind::Changed,
+                method,
+                &format!(
+                    "error {} message changed from \"{}\" to \"{}\"",
+                    code, error.message, updated.message
+                ),
+                Impact::Patch,
+            ));
+        }
+    }
+
+    for (code, error) in new.iter().filter(|(c, _)| !old.contains_key(*c)) {
+        out.push(change(
+            Subject::Method,
+            ChangeKind::Changed,
+            method,
+            &format!("error {} ({}) added", code, error.message),
+            Impact::Minor,
+        ));
+    }
+}
+
+fn diff_types(old: &[ResolvedType], new: &[ResolvedType], out: &mut Vec<Change>) {
+    let old: BTreeMap<&str, &ResolvedType> = old.iter().map(|t| (t.name.as_str(), t)).collect();
+    let new: BTreeMap<&str, &ResolvedType> = new.iter().map(|t| (t.name.as_str(), t)).collect();
+
+    for (name, ty) in &old {
+        let Some(updated) = new.get(name) else {
+            out.push(change(
+                Subject::Schema,
+                ChangeKind::Removed,
+                name,
+                "schema removed",
+                Impact::Major,
+            ));
+            continue;
+        };
+
+        if ty.fields.is_empty() && updated.fields.is_empty() {
+            if let Some((detail, impact)) = compare_schemas(&ty.schema, &updated.schema) {
+                out.push(change(
+                    Subject::Schema,
+                    ChangeKind::Changed,
+                    name,
+                    &detail,
+                    impact,
+                ));
+            }
+        } else {
+            diff_fields(name, &ty.fields, &updated.fields, out);
+        }
+    }
+
+    for name in new.keys().filter(|n| !old.contains_key(*n)) {
+        out.push(change(
+            Subject::Schema,
+            ChangeKind::Added,
+            name,
+            "schema added",
+            Impact::Minor,
+        ));
+    }
+}
+
+fn diff_fields(owner: &str, old: &[ResolvedField], new: &[ResolvedField], out: &mut Vec<Change>) {
+    let old: BTreeMap<&str, &ResolvedField> = old.iter().map(|f| (f.name.as_str(), f)).collect();
+    let new: BTreeMap<&str, &ResolvedField> = new.iter().map(|f| (f.name.as_str(), f)).collect();
+
+    for (name, field) in &old {
+        let path = format!("{}.{}", owner, name);
+        let Some(updated) = new.get(name) else {
+            out.push(change(
+                Subject::Field,
+                ChangeKind::Removed,
+                &path,
+                "field removed",
+                Impact::Major,
+            ));
+            continue;
+        };
+
+        if field.required != updated.required {
+            let detail = if updated.required {
+                "field became required"
+            } else {
+                "field became optional"
+            };
+            out.push(change(
+                Subject::Field,
+                ChangeKind::Changed,
+                &path,
+                detail,
+                Impact::Major,
+            ));
+        }
+
+        if let Some((detail, impact)) = compare_schemas(&field.schema, &updated.schema) {
+            out.push(change(
+                Subject::Field,
+                ChangeKind::Changed,
+                &path,
+                &detail,
+                impact,
+            ));
+        }
+    }
+
+    for (name, field) in new.iter().filter(|(n, _)| !old.contains_key(*n)) {
+        let (detail, impact) = if field.required {
+            ("required field added", Impact::Major)
+        } else {
+            ("optional field added", Impact::Minor)
+        };
+        out.push(change(
+            Subject::Field,
+            ChangeKind::Added,
+            &format!("{}.{}", owner, name),
+            detail,
+            impact,
+        ));
+    }
+}
+
+/// Classifies the difference between two schemas: documentation-only edits
+/// are patches, widened enums are additive, anything else is breaking.
+fn compare_schemas(old: &SchemaObject, new: &SchemaObject) -> Option<(String, Impact)> {
+    if old == new {
+        return None;
+    }
+
+    let mut old_shape = old.clone();
+    let mut new_shape = new.clone();
+    let old_meta = old_shape.metadata.take().unwrap_or_default();
+    let new_meta = new_shape.metadata.take().unwrap_or_default();
+    if old_shape == new_shape {
+        if !old_meta.deprecated && new_meta.deprecated {
+            return Some(("deprecated".to_string(), Impact::Minor));
+        }
+        return Some(("documentation changed".to_string(), Impact::Patch));
+    }
+
+    let old_enum = old_shape.enum_values.take();
+    let new_enum = new_shape.enum_values.take();
+    if old_shape == new_shape {
+        if let (Some(old_enum), Some(new_enum)) = (&old_enum, &new_enum) {
+            let old_set: BTreeSet<String> = old_enum.iter().map(Value::to_string).collect();
+            let new_set: BTreeSet<String> = new_enum.iter().map(Value::to_string).collect();
+            let removed: Vec<&String> = old_set.difference(&new_set).collect();
+            let added: Vec<&String> = new_set.difference(&old_set).collect();
+            if removed.is_empty() {
+                let added: Vec<&str> = added.iter().map(|s| s.as_str()).collect();
+                return Some((
+                    format!("enum values added: {}", added.join(", ")),
+                    Impact::Minor,
+                ));
+            }
+            let removed: Vec<&str> = removed.iter().map(|s| s.as_str()).collect();
+            return Some((
+                format!("enum values removed: {}", removed.join(", ")),
+                Impact::Major,
+            ));
+        }
+    }
+
+    Some(("type changed".to_string(), Impact::Major))
+}
+
+fn schema_value(schema: &Schema) -> Value {
+    serde_json::to_value(schema).unwrap_or(Value::Null)
+}
+
+fn change(subject: Subject, kind: ChangeKind, path: &str, detail: &str, impact: Impact) -> Change {
+    Change {
+        subject,
+        kind,
+        path: path.to_string(),
+        detail: detail.to_string(),
+        impact,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// A spec with one method taking `Params`, answering `Result` and
+    /// declaring one error.
+    pub(crate) fn base() -> Value {
+        json!({
+            "openrpc": "1.3.2",
+            "info": { "title": "Diff", "version": "1.2.3" },
+            "methods": [{
+                "name": "tree.walk",
+                "params": [{ "name": "params", "schema": { "$ref": "#/components/schemas/Params" } }],
+                "result": { "name": "result", "schema": { "$ref": "#/components/schemas/Result" } },
+                "errors": [{ "code": -32000, "message": "Node not found" }]
+            }],
+            "components": {
+                "schemas": {
+                    "Params": {
+                        "type": "object",
+                        "properties": {
+                            "root": { "type": "string" },
+                            "depth": { "type": "integer" }
+                        },
+                        "required": ["root"]
+                    },
+                    "Result": {
+                        "type": "object",
+                        "properties": { "label": { "type": "string" } },
+                        "required": ["label"]
+                    }
+                }
+            }
+        })
+    }
+
+    /// The changes from `old` to `new`, as `(path, detail, impact)`.
+    pub(crate) fn changes(old: &Value, new: &Value) -> Vec<(String, String, Impact)> {
+        let old = serde_json::from_value(old.clone()).unwrap();
+        let new = serde_json::from_value(new.clone()).unwrap();
+        diff_specs(&old, &new)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.path, c.detail, c.impact))
+            .collect()
+    }
+
+    fn change(path: &str, detail: &str, impact: Impact) -> (String, String, Impact) {
+        (path.to_string(), detail.to_string(), impact)
+    }
+
+    #[test]
+    fn unchanged() {
+        assert!(changes(&base(), &base()).is_empty());
+        assert_eq!(overall_impact(&[]), None);
+    }
+
+    #[test]
+    fn methods() {
+        let mut new = base();
+        new["methods"][0]["name"] = "tree.prune".into();
+        assert_eq!(
+            changes(&base(), &new),
+            [
+                change("tree.walk", "method removed", Impact::Major),
+                change("tree.prune", "method added", Impact::Minor),
+            ]
+        );
+        let mut both = new.clone();
+        both["methods"]
+            .as_array_mut()
+            .unwrap()
+            .push(base()["methods"][0].clone());
+        assert_eq!(
+            changes(&new, &both),
+            [change("tree.walk", "method added", Impact::Minor)]
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let mut new = base();
+        let properties = &mut new["components"]["schemas"]["Params"]["properties"];
+        properties.as_object_mut().unwrap().remove("depth");
+        properties["order"] = json!({ "type": "string" });
+        assert_eq!(
+            changes(&base(), &new),
+            [
+                change("Params.depth", "field removed", Impact::Major),
+                change("Params.order", "optional field added", Impact::Minor),
+            ]
+        );
+
+        let mut new = base();
+        new["components"]["schemas"]["Result"]["properties"]["size"] = json!({ "type": "integer" });
+        new["components"]["schemas"]["Result"]["required"] = json!(["label", "size"]);
+        assert_eq!(
+            changes(&base(), &new),
+            [change("Result.size", "required field added", Impact::Major)]
+        );
+    }
+
+    #[test]
+    fn required() {
+        let mut new = base();
+        new["components"]["schemas"]["Params"]["required"] = json!(["root", "depth"]);
+        assert_eq!(
+            changes(&base(), &new),
+            [change(
+                "Params.depth",
+                "field became required",
+                Impact::Major
+            )]
+        );
+        assert_eq!(
+            changes(&new, &base()),
+            [change(
+                "Params.depth",
+                "field became optional",
+                Impact::Major
+            )]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut new = base();
+        new["methods"][0]["errors"] = json!([
+            { "code": -32000, "message": "No such node", "data": { "type": "string" } },
+            { "code": -32001, "message": "Walk interrupted" }
+        ]);
+        assert_eq!(
+            changes(&base(), &new),
+            [
+                change("tree.walk", "error -32000 data added", Impact::Minor),
+                change(
+                    "tree.walk",
+                    "error -32000 message changed from \"Node not found\" to \"No such node\"",
+                    Impact::Patch
+                ),
+                change(
+                    "tree.walk",
+                    "error -32001 (Walk interrupted) added",
+                    Impact::Minor
+                ),
+            ]
+        );
+
+        let mut old = new.clone();
+        old["methods"][0]["errors"][0]["data"] = json!({ "type": "integer" });
+        new["methods"][0]["errors"] = json!([]);
+        assert_eq!(
+            changes(&old, &new),
+            [
+                change(
+                    "tree.walk",
+                    "error -32001 (Walk interrupted) removed",
+                    Impact::Major
+                ),
+                change(
+                    "tree.walk",
+                    "error -32000 (No such node) removed",
+                    Impact::Major
+                ),
+            ]
+        );
+        new["methods"][0]["errors"] = json!([{ "code": -32000, "message": "No such node" }]);
+        assert_eq!(
+            changes(&old, &new),
+            [
+                change(
+                    "tree.walk",
+                    "error -32001 (Walk interrupted) removed",
+                    Impact::Major
+                ),
+                change("tree.walk", "error -32000 data removed", Impact::Major),
+            ]
+        );
+    }
+}
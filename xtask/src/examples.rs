@@ -0,0 +1,99 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use schemars::schema::Schema;
+use serde_json::Value;
+
+use crate::validator::Validator;
+use trp_codegen::openrpc::{self, OpenRpc};
+
+#[derive(Parser, Debug)]
+pub struct ExamplesArgs {
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+}
+
+pub fn run(args: ExamplesArgs) -> Result<()> {
+    let spec = openrpc::load(&args.openrpc)?;
+    let components = spec
+        .schemas()
+        .context("no components.schemas present in OpenRPC spec")?;
+    let validator = Validator::new(components);
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for (location, schema, value) in collect_examples(&spec) {
+        checked += 1;
+        let Some(schema) = schema else {
+            failures.push(format!("{}: no param with this name is declared", location));
+            continue;
+        };
+        for v in validator.validate(schema, value) {
+            failures.push(format!("{}: {}: {}", location, v.path, v.message));
+        }
+    }
+
+    for failure in &failures {
+        tracing::error!("{}", failure);
+    }
+
+    if !failures.is_empty() {
+        anyhow::bail!(
+            "{} example mismatch(es) found across {} example(s)",
+            failures.len(),
+            checked
+        );
+    }
+
+    println!("{} example(s) match their schemas", checked);
+    Ok(())
+}
+
+/// Pairs every example in the spec with the schema it is supposed to satisfy.
+/// Example params that don't match any declared param carry no schema.
+fn collect_examples(spec: &OpenRpc) -> Vec<(String, Option<&Schema>, &Value)> {
+    let mut out = Vec::new();
+
+    for method in &spec.methods {
+        for pairing in &method.examples {
+            for param in &pairing.params {
+                let location = format!(
+                    "methods.{}.examples.{}.params.{}",
+                    method.name, pairing.name, param.name
+                );
+                let schema = method
+                    .params
+                    .iter()
+                    .find(|p| p.name == param.name)
+                    .map(|p| &p.schema);
+                out.push((location, schema, &param.value));
+            }
+            if let (Some(example), Some(result)) = (&pairing.result, &method.result) {
+                let location = format!(
+                    "methods.{}.examples.{}.result.{}",
+                    method.name, pairing.name, example.name
+                );
+                out.push((location, Some(&result.schema), &example.value));
+            }
+        }
+    }
+
+    if let Some(schemas) = spec.schemas() {
+        let mut names: Vec<&String> = schemas.keys().collect();
+        names.sort();
+        for name in names {
+            let schema = &schemas[name];
+            if let Schema::Object(obj) = schema {
+                let examples = obj.metadata.as_ref().map(|m| m.examples.as_slice());
+                for (i, value) in examples.unwrap_or_default().iter().enumerate() {
+                    let location = format!("components.schemas.{}.examples[{}]", name, i);
+                    out.push((location, Some(schema), value));
+                }
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,78 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// Formatters tried for each language, in order of preference. The first one
+/// found on `PATH` formats the generated files in place.
+fn defaults(lang: &str) -> &'static [&'static str] {
+    match lang {
+        "ts" | "typescript" => &["prettier --write --log-level warn"],
+        "deno" => &["deno fmt --quiet"],
+        "python" => &["ruff format --quiet", "black --quiet"],
+        "go" => &["gofmt -w"],
+        "rust" | "wasm" => &["rustfmt --edition 2021"],
+        "elm" => &["elm-format --yes"],
+        "php" => &["php-cs-fixer fix --quiet"],
+        "scala" => &["scalafmt --quiet"],
+        "c" => &["clang-format -i"],
+        "proto" => &["buf format -w", "clang-format -i"],
+        "ocaml" => &["ocamlformat --inplace --enable-outside-detected-project"],
+        _ => &[],
+    }
+}
+
+/// Parses a `lang=command` formatter override.
+pub fn parse_override(raw: &str) -> Result<(String, String), String> {
+    raw.split_once('=')
+        .map(|(lang, command)| (lang.to_lowercase(), command.to_string()))
+        .filter(|(lang, command)| !lang.is_empty() && !command.trim().is_empty())
+        .ok_or_else(|| format!("expected <lang>=<command>, got {}", raw))
+}
+
+/// Runs the formatter of `lang` over `files`. `command` replaces the default
+/// candidates. A formatter that isn't installed is skipped with a note, while
+/// one that rejects the files fails generation, as that points at a template
+/// emitting invalid code.
+pub fn format_files(lang: &str, command: Option<&str>, files: &[PathBuf]) -> Result<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+    let candidates: Vec<&str> = match command {
+        Some(command) => vec![command],
+        None => defaults(lang).to_vec(),
+    };
+
+    for candidate in &candidates {
+        let mut words = candidate.split_whitespace();
+        let Some(program) = words.next() else {
+            continue;
+        };
+        let output = match Command::new(program).args(words).args(files).output() {
+            Ok(output) => output,
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to run {}", program));
+            }
+        };
+        if !output.status.success() {
+            anyhow::bail!(
+                "{} failed on the generated {} files:\n{}",
+                candidate,
+                lang,
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            );
+        }
+        return Ok(());
+    }
+
+    if !candidates.is_empty() {
+        tracing::warn!(
+            "skipping formatting of {} files: {} not found",
+            lang,
+            candidates.join(" or ")
+        );
+    }
+    Ok(())
+}
@@ -1,20 +1,34 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 use anyhow::{Context, Result};
-use askama::Template;
 use clap::Parser;
+use trp_codegen::mapper::naming::Naming;
+use trp_codegen::mapper::proto::FieldNumbers;
+use trp_codegen::mapper::{self, LanguageContext};
+use trp_codegen::metadata::{self, Metadata};
+use trp_codegen::resolver::{self, ResolvedType};
+use trp_codegen::split::Split;
+use trp_codegen::{LanguageConfig, Spec, WasmCrate};
 
-use crate::mapper::{self, LanguageContext};
-use crate::openrpc::OpenRpc;
-use crate::resolver::{ResolvedField, ResolvedType};
+use crate::check;
+use crate::container;
+use crate::format;
+use crate::header::{self, HeaderArgs};
+use crate::manifest;
+use crate::provenance;
+use crate::watch;
 
 #[derive(Parser, Debug)]
 pub struct GenArgs {
     /// Path to the OpenRPC spec
     #[arg(long, default_value = "specs/trp.json")]
     pub openrpc: PathBuf,
-    /// Comma separated list of languages to generate (ts, python, go, rust)
+    /// Comma separated list of languages to generate (ts, deno, python, go, rust, elm,
+    /// php, scala, c, ocaml, proto, cddl, jsonschema, openapi, asyncapi, wasm)
     #[arg(long, value_delimiter = ',')]
     pub lang: Vec<String>,
     /// Output directory
@@ -23,160 +37,421 @@ pub struct GenArgs {
     /// Clean output directory before generating
     #[arg(long, default_value_t = false)]
     pub clean: bool,
+    /// Reject unknown fields when decoding types declared with
+    /// `additionalProperties: false` (Rust, Python and TypeScript schemas)
+    #[arg(long, default_value_t = false)]
+    pub strict: bool,
+    /// How to distribute the generated types over files
+    #[arg(long, value_enum, default_value_t = Split::None)]
+    pub split: Split,
+    /// Embed the OpenRPC spec as an `OPENRPC_SPEC` string constant
+    #[arg(long, default_value_t = false)]
+    pub embed_spec: bool,
+    /// Print the resolved types, language contexts and metadata the
+    /// templates are rendered from as JSON before rendering
+    #[arg(long, default_value_t = false)]
+    pub dump_context: bool,
+    /// Run each language's formatter (rustfmt, prettier, deno fmt, ruff or black,
+    /// gofmt, elm-format, php-cs-fixer, scalafmt, clang-format, ocamlformat,
+    /// buf) over the generated files; missing formatters are skipped
+    #[arg(long, default_value_t = false)]
+    pub format: bool,
+    /// Formatter command for a language, as `<lang>=<command>`; the file
+    /// paths are appended to it
+    #[arg(long, value_parser = format::parse_override)]
+    pub formatter: Vec<(String, String)>,
+    /// Naming convention of a language, as `<lang>.<entry>=<value>` where
+    /// the entry is `types`, `fields` or `variants` (pascal, camel, snake,
+    /// screaming-snake) or `acronyms` (title, upper)
+    #[arg(long, value_parser = parse_naming)]
+    pub naming: Vec<(String, String, String)>,
+    /// Import line for the types files of a language, as `<lang>=<line>`,
+    /// bringing in the external types of `x-{lang}-type` overrides
+    #[arg(long, value_parser = parse_import)]
+    pub import: Vec<(String, String)>,
+    #[command(flatten)]
+    pub header: HeaderArgs,
+    /// Regenerate whenever the spec or a template changes
+    #[arg(long, default_value_t = false)]
+    pub watch: bool,
+    /// Write nothing, and fail with a diff of every generated file that
+    /// differs from the one on disk
+    #[arg(long, default_value_t = false, conflicts_with_all = ["watch", "clean"])]
+    pub check: bool,
+    /// Also write `provenance.json` to the output directory, recording the
+    /// generator version, the spec's hash, the time and the git commit
+    #[arg(long, default_value_t = false)]
+    pub provenance: bool,
+    /// Minisign secret key to sign `manifest.json` (and `provenance.json`)
+    /// with, into `.minisig` files next to them; an encrypted key's
+    /// password is read from TRP_SIGN_PASSWORD, or prompted for
+    #[arg(long, conflicts_with = "check")]
+    pub sign_key: Option<PathBuf>,
+    /// Then generate again in the pinned environment of
+    /// `container/Dockerfile` (with docker or podman), and fail unless
+    /// every file comes out byte for byte the same as on the host
+    #[arg(long, default_value_t = false, conflicts_with_all = ["watch", "timestamp"])]
+    pub container: bool,
+    /// With --check, print the summary of the differences without the
+    /// diffs themselves
+    #[arg(long, default_value_t = false, requires = "check")]
+    pub quiet: bool,
+    /// When to color the diffs of --check
+    #[arg(long, value_enum, default_value_t = check::Color::Auto)]
+    pub color: check::Color,
+    /// Number of languages generated concurrently (defaults to the number
+    /// of CPUs)
+    #[arg(long)]
+    pub jobs: Option<usize>,
+    /// Generate the Rust binding as a crate in this directory, named after
+    /// it, instead of loose files under the output directory
+    #[arg(long)]
+    pub rust_crate: Option<PathBuf>,
+    /// Also generate a Rust `borrowed` module, whose types hold their
+    /// strings as `Cow<'a, str>` for zero-copy deserialization
+    #[arg(long, default_value_t = false)]
+    pub rust_borrowed: bool,
+    /// OpenRPC spec of the previous major version, whose Rust types are
+    /// generated into a module named after it (e.g. `v1`) along with
+    /// conversions to and from the current types
+    #[arg(long)]
+    pub rust_previous: Option<PathBuf>,
+    /// Generate the wasm-bindgen client (`--lang wasm`) as a crate in this
+    /// directory, named after it, instead of `{out}/wasm` named `trp-wasm`.
+    /// It depends on the crate of --rust-crate by path when given
+    #[arg(long)]
+    pub wasm_crate: Option<PathBuf>,
+    /// Also generate React hooks wrapping the TypeScript client's methods
+    /// (`react.ts`), which take React as a peer dependency
+    #[arg(long, default_value_t = false)]
+    pub ts_react: bool,
+    /// Declare the Go JSON-RPC envelopes without generics, holding raw
+    /// JSON, for toolchains older than Go 1.18
+    #[arg(long, default_value_t = false)]
+    pub go_no_generics: bool,
+    /// Module path of a `go.mod` written with the Go binding, suffixed
+    /// with the spec's major version from 2 on (e.g. `github.com/org/trp-go`)
+    #[arg(long)]
+    pub go_module: Option<String>,
+    /// Version of the `go` directive of `go.mod` (defaults to the oldest Go
+    /// release the binding builds with)
+    #[arg(long, requires = "go_module")]
+    pub go_version: Option<String>,
 }
 
 pub fn run(args: GenArgs) -> Result<()> {
+    if args.watch {
+        return watch::run(&args);
+    }
+
+    // Field numbers persisted by an earlier run must survive `--clean`.
+    let proto_numbers = load_proto_numbers(&args.out.join("proto"))?;
+    let (spec, previous) = tracing::debug_span!("parse").in_scope(|| -> Result<_> {
+        let spec = Spec::load(&args.openrpc)?;
+        let previous = match &args.rust_previous {
+            Some(path) => Some(Arc::new(Spec::load(path)?)),
+            None => None,
+        };
+        Ok((spec, previous))
+    })?;
+    if args.dump_context {
+        let types = resolver::resolve_components(&spec.document)?;
+        dump_context(&types, &args, &metadata::collect(&spec.raw))?;
+    }
+    let banner = args.header.lines(spec.document.version());
+    if args.check {
+        check::run(&args, &spec, &previous, &banner, &proto_numbers)?;
+        return verify_in_container(&args);
+    }
+
     if args.clean && args.out.exists() {
         fs::remove_dir_all(&args.out)
             .with_context(|| format!("failed to clean output directory {}", args.out.display()))?;
     }
-
     fs::create_dir_all(&args.out)
         .with_context(|| format!("failed to create output directory {}", args.out.display()))?;
 
-    let spec = load_openrpc(&args.openrpc)?;
-    let resolved = crate::resolver::resolve_components(&spec)?;
-
-    for lang in &args.lang {
-        let lang = lang.to_lowercase();
-        let ctx = mapper::build_context(&resolved, &lang);
-        let rendered = render_language(&lang, &resolved, &ctx)?;
-        let lang_dir = args.out.join(&lang);
-        fs::create_dir_all(&lang_dir)
-            .with_context(|| format!("failed to create directory {}", lang_dir.display()))?;
-        let file_path = lang_dir.join(output_file_name(&lang));
-        fs::write(&file_path, rendered)
-            .with_context(|| format!("failed to write {}", file_path.display()))?;
-    }
+    let written = for_each_language(&args, |lang| {
+        let (config, dir) = language_config(&args, &previous, lang, &proto_numbers)?;
+        generate_language(&args, &spec, lang, &config, &dir, &banner)
+    })?;
 
-    Ok(())
+    let langs: Vec<String> = args.lang.iter().map(|l| l.to_lowercase()).collect();
+    let written = written.concat();
+    manifest::write(
+        &args.out,
+        &args.openrpc,
+        spec.document.version(),
+        &langs,
+        &written,
+    )?;
+    let mut signed = vec![args.out.join(manifest::FILE_NAME)];
+    if args.provenance {
+        signed.push(provenance::write(
+            &args.out,
+            &args.openrpc,
+            spec.document.version(),
+        )?);
+    }
+    if let Some(key) = &args.sign_key {
+        provenance::sign(&signed, key, &manifest::hash_spec(&args.openrpc)?)?;
+    }
+    tracing::info!(
+        "generated {} file(s) for {} into {}",
+        written.len(),
+        langs.join(", "),
+        args.out.display()
+    );
+    verify_in_container(&args)
 }
 
-fn output_file_name(lang: &str) -> &str {
-    match lang {
-        "ts" | "typescript" => "types.ts",
-        "python" => "types.py",
-        "go" => "types.go",
-        "rust" => "types.rs",
-        _ => "types.txt",
+fn verify_in_container(args: &GenArgs) -> Result<()> {
+    match args.container {
+        true => container::verify(args),
+        false => Ok(()),
     }
 }
 
-fn load_openrpc(path: &Path) -> Result<OpenRpc> {
-    let data =
-        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
-    let spec: OpenRpc = serde_json::from_str(&data)
-        .with_context(|| format!("failed to parse OpenRPC spec from {}", path.display()))?;
-    Ok(spec)
+/// Runs `task` for each of the requested languages, lower cased, returning
+/// its results in the order given.
+///
+/// Languages are independent of each other, so a pool of workers takes
+/// them off a shared queue. Every language is attempted; the first
+/// failure, in the order given, is reported.
+pub fn for_each_language<T: Send>(
+    args: &GenArgs,
+    task: impl Fn(&str) -> Result<T> + Sync,
+) -> Result<Vec<T>> {
+    let jobs = args
+        .jobs
+        .or_else(|| thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1)
+        .clamp(1, args.lang.len().max(1));
+    let queue = Mutex::new(args.lang.iter().enumerate());
+    let results = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let Some((index, lang)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let lang = lang.to_lowercase();
+                let result = tracing::debug_span!("language", lang).in_scope(|| task(&lang));
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
 }
 
-fn render_language(lang: &str, types: &[ResolvedType], ctx: &LanguageContext) -> Result<String> {
-    match lang {
-        "ts" | "typescript" => {
-            let rendered = render_template(TsTemplate { types, ctx });
-            rendered
+/// What to generate for `lang`, and the directory its files belong in.
+pub fn language_config(
+    args: &GenArgs,
+    previous: &Option<Arc<Spec>>,
+    lang: &str,
+    proto_numbers: &FieldNumbers,
+) -> Result<(LanguageConfig, PathBuf)> {
+    let config = LanguageConfig {
+        strict: args.strict,
+        split: args.split,
+        embed_spec: args.embed_spec,
+        rust_borrowed: args.rust_borrowed,
+        rust_previous: previous.clone().filter(|_| lang == "rust"),
+        ts_react: args.ts_react,
+        go_generics: !args.go_no_generics,
+        go_module: args.go_module.clone(),
+        go_version: args.go_version.clone(),
+        naming: naming(&args.naming, lang)?,
+        imports: args
+            .import
+            .iter()
+            .filter(|(l, _)| l == lang)
+            .map(|(_, line)| line.clone())
+            .collect(),
+        proto_numbers: proto_numbers.clone(),
+        ..LanguageConfig::new(lang)
+    };
+    Ok(match (lang, &args.rust_crate) {
+        ("rust", Some(dir)) => {
+            let config = LanguageConfig {
+                rust_crate: Some(crate_name(dir)?),
+                ..config
+            };
+            (config, dir.clone())
         }
-        "python" => {
-            let rendered = render_template(PythonTemplate { types, ctx });
-            rendered
+        ("wasm", _) => {
+            let mut krate = WasmCrate::default();
+            if let Some(dir) = &args.wasm_crate {
+                krate.name = crate_name(dir)?;
+            }
+            let dir = args
+                .wasm_crate
+                .clone()
+                .unwrap_or_else(|| args.out.join(lang));
+            if let Some(types) = &args.rust_crate {
+                krate.types = crate_name(types)?;
+                krate.types_path = Some(relative_path(&dir, types)?);
+            }
+            let config = LanguageConfig {
+                wasm: krate,
+                ..config
+            };
+            (config, dir)
         }
-        "go" => {
-            let rendered = render_template(GoTemplate { types, ctx });
-            rendered
-        }
-        "rust" => {
-            let rendered = render_template(RustTemplate { types, ctx });
-            rendered
-        }
-        _ => anyhow::bail!("unsupported language: {}", lang),
-    }
+        _ => (config, args.out.join(lang)),
+    })
 }
 
-fn render_template<T: Template>(template: T) -> Result<String> {
-    template.render().context("failed to render template")
+/// Name of the crate generated in `dir`, that of the directory.
+fn crate_name(dir: &Path) -> Result<String> {
+    dir.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .with_context(|| format!("invalid crate directory {}", dir.display()))
 }
 
-#[derive(Template)]
-#[template(path = "ts/types.askama", escape = "none")]
-struct TsTemplate<'a> {
-    types: &'a [ResolvedType],
-    ctx: &'a LanguageContext,
+/// Path of the directory `to` relative to the directory `from`, with `/`
+/// separators as Cargo manifests take them.
+fn relative_path(from: &Path, to: &Path) -> Result<String> {
+    let cwd = std::env::current_dir().context("failed to read the working directory")?;
+    let absolute = |path: &Path| {
+        let mut normal = PathBuf::new();
+        for component in cwd.join(path).components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normal.pop();
+                }
+                component => normal.push(component),
+            }
+        }
+        normal
+    };
+    let (from, to) = (absolute(from), absolute(to));
+    let common = from
+        .components()
+        .zip(to.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let parents = from.components().count() - common;
+    let mut parts: Vec<String> = vec!["..".to_string(); parents];
+    parts.extend(
+        to.components()
+            .skip(common)
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+    Ok(match parts.is_empty() {
+        true => ".".to_string(),
+        false => parts.join("/"),
+    })
 }
 
-#[derive(Template)]
-#[template(path = "python/types.askama", escape = "none")]
-struct PythonTemplate<'a> {
-    types: &'a [ResolvedType],
-    ctx: &'a LanguageContext,
+/// Renders the files of one language into `lang_dir` and optionally
+/// formats them, returning their paths.
+pub fn generate_language(
+    args: &GenArgs,
+    spec: &Spec,
+    lang: &str,
+    config: &LanguageConfig,
+    lang_dir: &Path,
+    banner: &[String],
+) -> Result<Vec<PathBuf>> {
+    let files = trp_codegen::generate(spec, config)?;
+    let write = tracing::debug_span!("write").entered();
+    fs::create_dir_all(lang_dir)
+        .with_context(|| format!("failed to create directory {}", lang_dir.display()))?;
+    let mut written = Vec::new();
+    for file in files {
+        let file_path = lang_dir.join(&file.path);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory {}", parent.display()))?;
+        }
+        let rendered = header::prepend(banner, &file_path, file.contents);
+        fs::write(&file_path, rendered)
+            .with_context(|| format!("failed to write {}", file_path.display()))?;
+        tracing::trace!(path = %file_path.display(), "wrote");
+        written.push(file_path);
+    }
+    drop(write);
+    if args.format {
+        let _format = tracing::debug_span!("format").entered();
+        let command = args
+            .formatter
+            .iter()
+            .find(|(l, _)| l == lang)
+            .map(|(_, command)| command.as_str());
+        // READMEs and manifests, including extensionless dune files and
+        // go.mod, are left to the formatter-agnostic templates.
+        let sources: Vec<PathBuf> = written
+            .iter()
+            .filter(|path| {
+                !matches!(
+                    path.extension().and_then(|e| e.to_str()),
+                    None | Some("md" | "toml" | "json" | "properties" | "mod")
+                )
+            })
+            .cloned()
+            .collect();
+        format::format_files(lang, command, &sources)?;
+    }
+    Ok(written)
 }
 
-#[derive(Template)]
-#[template(path = "go/types.askama", escape = "none")]
-struct GoTemplate<'a> {
-    types: &'a [ResolvedType],
-    ctx: &'a LanguageContext,
+/// Parses a `lang.entry=value` naming convention.
+fn parse_naming(raw: &str) -> Result<(String, String, String), String> {
+    let invalid = || format!("expected <lang>.<entry>=<value>, got {}", raw);
+    let (key, value) = raw.split_once('=').ok_or_else(invalid)?;
+    let (lang, entry) = key.split_once('.').ok_or_else(invalid)?;
+    Ok((lang.to_lowercase(), entry.to_string(), value.to_string()))
 }
 
-#[derive(Template)]
-#[template(path = "rust/types.askama", escape = "none")]
-struct RustTemplate<'a> {
-    types: &'a [ResolvedType],
-    ctx: &'a LanguageContext,
+/// Parses a `lang=line` import.
+fn parse_import(raw: &str) -> Result<(String, String), String> {
+    raw.split_once('=')
+        .map(|(lang, line)| (lang.to_lowercase(), line.to_string()))
+        .filter(|(lang, line)| !lang.is_empty() && !line.trim().is_empty())
+        .ok_or_else(|| format!("expected <lang>=<line>, got {}", raw))
 }
 
-mod filters {
-    use crate::resolver::ResolvedField;
-
-    pub fn length(value: &Vec<ResolvedField>) -> Result<usize, askama::Error> {
-        Ok(value.len())
+/// Naming conventions given on the command line for `lang`.
+fn naming(entries: &[(String, String, String)], lang: &str) -> Result<Naming> {
+    let mut naming = Naming::default();
+    for (_, entry, value) in entries.iter().filter(|(l, _, _)| l == lang) {
+        naming.set(entry, value)?;
     }
+    Ok(naming)
 }
 
-// Helper methods exposed to templates
-impl ResolvedField {
-    pub fn ts_name(&self) -> String {
-        mapper::ts::field_name(&self.name)
+/// Field numbers written by an earlier protobuf run into `dir`, if any.
+fn load_proto_numbers(dir: &Path) -> Result<FieldNumbers> {
+    let path = dir.join(mapper::proto::FIELD_NUMBERS);
+    if !path.exists() {
+        return Ok(FieldNumbers::new());
     }
-
-    pub fn python_name(&self) -> String {
-        mapper::python::field_name(&self.name)
-    }
-
-    pub fn go_name(&self) -> String {
-        mapper::go::field_name(&self.name)
-    }
-
-    pub fn rust_name(&self) -> String {
-        mapper::rust::field_name(&self.name)
-    }
-
-    pub fn ts_type(&self, ctx: &LanguageContext) -> String {
-        mapper::ts::map_type(&self.schema, ctx).maybe_optional(self.required, ctx)
-    }
-
-    pub fn python_type(&self, ctx: &LanguageContext) -> String {
-        mapper::python::map_type(&self.schema, ctx).maybe_optional(self.required, ctx)
-    }
-
-    pub fn go_type(&self, ctx: &LanguageContext) -> String {
-        mapper::go::map_type(&self.schema, ctx)
-    }
-
-    pub fn rust_type(&self, ctx: &LanguageContext) -> String {
-        mapper::rust::map_type(&self.schema, ctx).maybe_optional(self.required, ctx)
-    }
-}
-
-trait OptionalRendering {
-    fn maybe_optional(self, required: bool, ctx: &LanguageContext) -> String;
+    let data =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&data).with_context(|| format!("failed to parse {}", path.display()))
 }
 
-impl OptionalRendering for String {
-    fn maybe_optional(self, required: bool, ctx: &LanguageContext) -> String {
-        if required {
-            self
-        } else {
-            ctx.wrap_optional(&self)
-        }
-    }
+fn dump_context(types: &[ResolvedType], args: &GenArgs, meta: &Metadata) -> Result<()> {
+    let contexts = args
+        .lang
+        .iter()
+        .map(|lang| {
+            let lang = lang.to_lowercase();
+            let ctx = mapper::build_context(types, &lang, &naming(&args.naming, &lang)?);
+            Ok((lang, ctx))
+        })
+        .collect::<Result<BTreeMap<String, LanguageContext>>>()?;
+    let dump = serde_json::json!({
+        "metadata": meta,
+        "types": types,
+        "contexts": contexts,
+    });
+    let data = serde_json::to_string_pretty(&dump).context("failed to serialize context")?;
+    println!("{}", data);
+    Ok(())
 }
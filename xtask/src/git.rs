@@ -0,0 +1,88 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use trp_codegen::bundle;
+
+/// Reads `path` as it existed at `rev`.
+pub fn show_file(rev: &str, path: &Path) -> Result<String> {
+    let spec = object_spec(rev, path);
+    run(&["show", &spec]).with_context(|| format!("failed to read {} from git", spec))
+}
+
+/// Reads the spec at `path` as it existed at `rev`, bundling it if it was
+/// split over a directory then.
+pub fn show_spec(rev: &str, path: &Path) -> Result<String> {
+    let object = object_spec(rev, path);
+    if run(&["cat-file", "-t", &object]).ok().as_deref() != Some("tree") {
+        return show_file(rev, path);
+    }
+    let spec = bundle::merge_with(|file| show_file(rev, &path.join(file)))?;
+    serde_json::to_string_pretty(&spec).context("failed to serialize spec")
+}
+
+/// Returns the most recent tag reachable from HEAD.
+pub fn last_tag() -> Result<String> {
+    run(&["describe", "--tags", "--abbrev=0"]).context("no git tag found to compare against")
+}
+
+/// The commit checked out, if the working directory is in a repository.
+pub fn head() -> Option<String> {
+    run(&["rev-parse", "HEAD"]).ok()
+}
+
+/// Whether `path` has changes that aren't committed, or isn't tracked.
+pub fn is_modified(path: &Path) -> Result<bool> {
+    let path = path.display().to_string();
+    Ok(!run(&["status", "--porcelain", "--", &path])?.is_empty())
+}
+
+/// A commit that touched a given path.
+pub struct Revision {
+    pub commit: String,
+    pub subject: String,
+}
+
+/// Lists commits in `since..HEAD` that touched `path`, oldest first.
+pub fn revisions_touching(since: &str, path: &Path) -> Result<Vec<Revision>> {
+    let range = format!("{}..HEAD", since);
+    let path = path.display().to_string();
+    let log = run(&["log", "--reverse", "--format=%H %s", &range, "--", &path])?;
+    Ok(log
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(commit, subject)| Revision {
+            commit: commit.to_string(),
+            subject: subject.to_string(),
+        })
+        .collect())
+}
+
+/// `rev:path`, naming `path` at `rev` for git.
+fn object_spec(rev: &str, path: &Path) -> String {
+    // `./` makes git resolve the path relative to the working directory
+    // instead of the repository root.
+    if path.is_relative() {
+        format!("{}:./{}", rev, path.display())
+    } else {
+        format!("{}:{}", rev, path.display())
+    }
+}
+
+fn run(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)
+        .context("git produced non-UTF-8 output")?
+        .trim_end()
+        .to_string())
+}
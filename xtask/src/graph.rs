@@ -0,0 +1,155 @@
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use serde_json::Value;
+
+use trp_codegen::analysis;
+use trp_codegen::openrpc::{self, OpenRpc};
+
+#[derive(Parser, Debug)]
+pub struct GraphArgs {
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+    pub format: GraphFormat,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+/// An edge from a method or component to the component it references,
+/// labelled with the role the reference plays for methods.
+struct Edge {
+    from: String,
+    to: String,
+    label: Option<String>,
+}
+
+pub fn run(args: GraphArgs) -> Result<()> {
+    let spec = openrpc::load(&args.openrpc)?;
+    let methods: Vec<String> = spec.methods.iter().map(|m| m.name.clone()).collect();
+    let components: BTreeSet<String> = spec
+        .schemas()
+        .map(|s| s.keys().cloned().collect())
+        .unwrap_or_default();
+    let edges = collect_edges(&spec);
+
+    let rendered = match args.format {
+        GraphFormat::Dot => render_dot(&methods, &components, &edges),
+        GraphFormat::Mermaid => render_mermaid(&methods, &components, &edges),
+    };
+    print!("{}", rendered);
+    Ok(())
+}
+
+fn collect_edges(spec: &OpenRpc) -> Vec<Edge> {
+    let mut edges = Vec::new();
+
+    for method in &spec.methods {
+        let mut roles: Vec<(String, Value)> = Vec::new();
+        for d in &method.params {
+            roles.push(("params".to_string(), to_value(&d.schema)));
+        }
+        if let Some(d) = &method.result {
+            roles.push(("result".to_string(), to_value(&d.schema)));
+        }
+        for e in &method.errors {
+            if let Some(data) = &e.data {
+                roles.push((format!("error {}", e.code), data.clone()));
+            }
+        }
+        for (label, value) in roles {
+            for to in analysis::collect_refs(&value) {
+                edges.push(Edge {
+                    from: method.name.clone(),
+                    to,
+                    label: Some(label.clone()),
+                });
+            }
+        }
+    }
+
+    for (from, targets) in analysis::component_graph(spec) {
+        for to in targets {
+            edges.push(Edge {
+                from: from.clone(),
+                to,
+                label: None,
+            });
+        }
+    }
+
+    edges
+}
+
+fn render_dot(methods: &[String], components: &BTreeSet<String>, edges: &[Edge]) -> String {
+    let mut out = String::from("digraph trp {\n    rankdir=LR;\n    node [shape=box];\n");
+    for m in methods {
+        let _ = writeln!(out, "    \"{}\" [shape=ellipse, style=filled];", m);
+    }
+    for c in components {
+        let _ = writeln!(out, "    \"{}\";", c);
+    }
+    for e in edges {
+        match &e.label {
+            Some(label) => {
+                let _ = writeln!(
+                    out,
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                    e.from, e.to, label
+                );
+            }
+            None => {
+                let _ = writeln!(out, "    \"{}\" -> \"{}\";", e.from, e.to);
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(methods: &[String], components: &BTreeSet<String>, edges: &[Edge]) -> String {
+    let mut out = String::from("graph LR\n");
+    for m in methods {
+        let _ = writeln!(out, "    {}([\"{}\"])", mermaid_id(m), m);
+    }
+    for c in components {
+        let _ = writeln!(out, "    {}[\"{}\"]", mermaid_id(c), c);
+    }
+    for e in edges {
+        match &e.label {
+            Some(label) => {
+                let _ = writeln!(
+                    out,
+                    "    {} -->|{}| {}",
+                    mermaid_id(&e.from),
+                    label,
+                    mermaid_id(&e.to)
+                );
+            }
+            None => {
+                let _ = writeln!(out, "    {} --> {}", mermaid_id(&e.from), mermaid_id(&e.to));
+            }
+        }
+    }
+    out
+}
+
+// Mermaid node ids may not contain dots or other punctuation.
+fn mermaid_id(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde_json::{json, Map, Value};
+
+use trp_codegen::openrpc;
+
+#[derive(Parser, Debug)]
+pub struct InferArgs {
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Sample JSON payload to infer the schema from
+    #[arg(long)]
+    pub from: PathBuf,
+    /// Name of the new entry in components.schemas
+    #[arg(long)]
+    pub name: String,
+    /// Replace an existing schema with the same name
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+    /// Print the inferred schema instead of writing it into the spec
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+}
+
+pub fn run(args: InferArgs) -> Result<()> {
+    let data = fs::read_to_string(&args.from)
+        .with_context(|| format!("failed to read {}", args.from.display()))?;
+    let payload: Value = serde_json::from_str(&data)
+        .with_context(|| format!("failed to parse payload from {}", args.from.display()))?;
+
+    let mut schema = infer_schema(&payload);
+    if let Value::Object(obj) = &mut schema {
+        obj.shift_insert(0, "title".to_string(), Value::String(args.name.clone()));
+    }
+
+    if args.dry_run {
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
+    let mut spec = openrpc::load_raw(&args.openrpc)?;
+    let schemas = spec
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+        .context("no components.schemas present in OpenRPC spec")?;
+
+    if schemas.contains_key(&args.name) && !args.force {
+        anyhow::bail!(
+            "schema {} already exists (use --force to replace it)",
+            args.name
+        );
+    }
+    schemas.insert(args.name.clone(), schema);
+
+    openrpc::save_raw(&args.openrpc, &spec)?;
+    println!(
+        "added components.schemas.{} to {}",
+        args.name,
+        args.openrpc.display()
+    );
+    Ok(())
+}
+
+/// Derives a skeleton schema from a sample value. Every property seen in the
+/// sample is marked as required; the result is meant to be refined by hand.
+fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "type": "null" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(n) if n.is_i64() || n.is_u64() => json!({ "type": "integer" }),
+        Value::Number(_) => json!({ "type": "number" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => {
+            let item_schema = items.first().map(infer_schema).unwrap_or_else(|| json!({}));
+            json!({ "type": "array", "items": item_schema })
+        }
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, v)| (name.clone(), infer_schema(v)))
+                .collect();
+            let required: Vec<&String> = fields.keys().collect();
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            })
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::io::IsTerminal as _;
+
+use clap::ValueEnum;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::time::Uptime;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum LogFormat {
+    /// Human readable lines
+    Text,
+    /// One JSON object per line, for CI to ingest
+    Json,
+}
+
+/// Sends the diagnostics of every command to stderr, leaving stdout to the
+/// reports and artifacts the commands print.
+///
+/// Progress is logged at `info`, the time each phase of generation takes
+/// (parse, resolve, render, write, format) from `-v` on and every file
+/// written with `-vv`. `RUST_LOG` takes precedence over the verbosity.
+pub fn init(verbose: u8, format: LogFormat) {
+    let level = match verbose {
+        0 => "info",
+        1 => "debug",
+        _ => "trace",
+    };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_span_events(FmtSpan::CLOSE);
+    match format {
+        // Span timings go with the timestamps, which are only worth the
+        // noise once the spans are logged.
+        LogFormat::Text if verbose == 0 && std::env::var_os("RUST_LOG").is_none() => builder
+            .without_time()
+            .with_target(false)
+            .with_ansi(color())
+            .init(),
+        LogFormat::Text => builder
+            .with_timer(Uptime::default())
+            .with_target(false)
+            .with_ansi(color())
+            .init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
+
+/// Whether stderr is a terminal, and `NO_COLOR` isn't set.
+fn color() -> bool {
+    std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
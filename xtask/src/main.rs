@@ -1,7 +1,27 @@
+mod bundle;
+mod changelog;
+mod check;
+mod container;
+mod coverage;
+mod diff;
+mod examples;
+mod format;
 mod gen;
-mod mapper;
-mod openrpc;
-mod resolver;
+mod git;
+mod graph;
+mod header;
+mod infer;
+mod log;
+mod manifest;
+mod new_method;
+mod provenance;
+mod publish;
+mod release;
+mod semver;
+mod tool;
+mod validate;
+mod validator;
+mod watch;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -12,19 +32,67 @@ use clap::{Parser, Subcommand};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Log more: the time each phase takes with -v, every file written
+    /// with -vv (RUST_LOG takes precedence)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Format of the log lines written to stderr
+    #[arg(long, global = true, value_enum, default_value_t = log::LogFormat::Text)]
+    log_format: log::LogFormat,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Generate language bindings from the OpenRPC specification
-    Gen(gen::GenArgs),
+    Gen(Box<gen::GenArgs>),
+    /// Validate spec examples against their schemas
+    Examples(examples::ExamplesArgs),
+    /// Infer a component schema from a sample payload
+    Infer(infer::InferArgs),
+    /// Append a method skeleton to the OpenRPC specification
+    NewMethod(new_method::NewMethodArgs),
+    /// Recommend the info.version bump implied by spec changes since a git revision
+    Semver(semver::SemverArgs),
+    /// Emit a changelog of spec changes from git history
+    Changelog(changelog::ChangelogArgs),
+    /// Check references in the spec and optionally report dead schemas
+    Validate(validate::ValidateArgs),
+    /// Print the schema reference graph as DOT or Mermaid
+    Graph(graph::GraphArgs),
+    /// Report how faithfully each language's bindings map the spec's schemas
+    Coverage(coverage::CoverageArgs),
+    /// Split the OpenRPC specification into a file per method and schema
+    Split(bundle::SplitArgs),
+    /// Bundle a split OpenRPC specification into a single file
+    Merge(bundle::MergeArgs),
+    /// Bump the spec version, regenerate and check the bindings, and
+    /// prepare their publish artifacts
+    Release(release::ReleaseArgs),
+    /// Package the bindings for their registries, check and upload them
+    Publish(publish::PublishArgs),
+    /// Verify the signed manifest and provenance of generated bindings
+    Verify(provenance::VerifyArgs),
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    log::init(cli.verbose, cli.log_format);
 
     match cli.command {
-        Commands::Gen(args) => gen::run(args)?,
+        Commands::Gen(args) => gen::run(*args)?,
+        Commands::Examples(args) => examples::run(args)?,
+        Commands::Infer(args) => infer::run(args)?,
+        Commands::NewMethod(args) => new_method::run(args)?,
+        Commands::Semver(args) => semver::run(args)?,
+        Commands::Changelog(args) => changelog::run(args)?,
+        Commands::Validate(args) => validate::run(args)?,
+        Commands::Graph(args) => graph::run(args)?,
+        Commands::Coverage(args) => coverage::run(args)?,
+        Commands::Split(args) => bundle::split(args)?,
+        Commands::Merge(args) => bundle::merge(args)?,
+        Commands::Release(args) => release::run(args)?,
+        Commands::Publish(args) => publish::run(args)?,
+        Commands::Verify(args) => provenance::verify(args)?,
     }
 
     Ok(())
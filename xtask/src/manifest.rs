@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use trp_codegen::openrpc;
+
+pub const FILE_NAME: &str = "manifest.json";
+
+/// Inventory of the generated files, letting packaging pipelines verify
+/// artifact integrity and detect hand edits of generated code.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Manifest {
+    pub generator: Generator,
+    pub spec: Source,
+    /// SHA-256 of each generated file, keyed by its path relative to the
+    /// output directory.
+    pub files: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Generator {
+    pub name: String,
+    pub version: String,
+}
+
+impl Generator {
+    /// This build of the generator.
+    pub fn current() -> Self {
+        Generator {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Source {
+    pub version: Option<String>,
+    pub sha256: String,
+}
+
+/// Writes the manifest of `out`. Entries of languages not generated this
+/// time are carried over from the previous manifest, as long as their
+/// files still exist.
+pub fn write(
+    out: &Path,
+    spec_path: &Path,
+    spec_version: Option<&str>,
+    langs: &[String],
+    files: &[PathBuf],
+) -> Result<()> {
+    let path = out.join(FILE_NAME);
+    let mut entries = BTreeMap::new();
+    if let Some(previous) = load(&path) {
+        for (file, hash) in previous.files {
+            let regenerated = langs
+                .iter()
+                .any(|lang| file.split('/').next() == Some(lang.as_str()));
+            if !regenerated && out.join(&file).is_file() {
+                entries.insert(file, hash);
+            }
+        }
+    }
+    for file in files {
+        let relative = file.strip_prefix(out).unwrap_or(file);
+        let key = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        entries.insert(key, hash_file(file)?);
+    }
+
+    let manifest = Manifest {
+        generator: Generator::current(),
+        spec: Source {
+            version: spec_version.map(str::to_string),
+            sha256: hash_spec(spec_path)?,
+        },
+        files: entries,
+    };
+    let mut data =
+        serde_json::to_string_pretty(&manifest).context("failed to serialize manifest")?;
+    data.push('\n');
+    fs::write(&path, data).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Paths of the files listed by the manifest of `out` that still exist.
+/// Files generated outside `out`, such as those of the Rust crate, are
+/// listed under their own path.
+pub fn recorded(out: &Path) -> Vec<PathBuf> {
+    let Some(manifest) = load(&out.join(FILE_NAME)) else {
+        return Vec::new();
+    };
+    manifest
+        .files
+        .keys()
+        .filter_map(|key| {
+            [out.join(key), PathBuf::from(key)]
+                .into_iter()
+                .find(|path| path.is_file())
+        })
+        .collect()
+}
+
+pub fn load(path: &Path) -> Option<Manifest> {
+    let data = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+pub fn hash_file(path: &Path) -> Result<String> {
+    let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(sha256(&data))
+}
+
+/// SHA-256 of the spec at `path`, bundled first if it's split over a
+/// directory, so that it matches the hash of the bundled document.
+pub fn hash_spec(path: &Path) -> Result<String> {
+    Ok(sha256(openrpc::read(path)?.as_bytes()))
+}
+
+fn sha256(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde_json::{json, Value};
+
+use crate::check::Color;
+use crate::gen::{self, GenArgs};
+use crate::header::HeaderArgs;
+use trp_codegen::mapper::sanitize_identifier;
+use trp_codegen::openrpc;
+use trp_codegen::split::Split;
+
+#[derive(Parser, Debug)]
+pub struct NewMethodArgs {
+    /// Fully qualified method name, e.g. trp.something
+    pub name: String,
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// One-line summary for the method
+    #[arg(long)]
+    pub summary: Option<String>,
+    /// Regenerate bindings for these languages after updating the spec
+    #[arg(long, value_delimiter = ',')]
+    pub lang: Vec<String>,
+    /// Output directory used when regenerating bindings
+    #[arg(long, default_value = "codegen")]
+    pub out: PathBuf,
+}
+
+pub fn run(args: NewMethodArgs) -> Result<()> {
+    let mut spec = openrpc::load_raw(&args.openrpc)?;
+
+    let base = sanitize_identifier(args.name.rsplit('.').next().unwrap_or(&args.name));
+    let params_name = format!("{}Params", base);
+    let result_name = format!("{}Response", base);
+
+    let methods = spec
+        .get_mut("methods")
+        .and_then(Value::as_array_mut)
+        .context("no methods array present in OpenRPC spec")?;
+    if methods.iter().any(|m| m["name"] == args.name.as_str()) {
+        anyhow::bail!("method {} already exists", args.name);
+    }
+    methods.push(method_skeleton(
+        &args.name,
+        args.summary.as_deref(),
+        &params_name,
+        &result_name,
+    ));
+
+    let schemas = spec
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+        .context("no components.schemas present in OpenRPC spec")?;
+    for name in [&params_name, &result_name] {
+        if !schemas.contains_key(name) {
+            schemas.insert(name.clone(), schema_skeleton(name));
+        }
+    }
+
+    openrpc::save_raw(&args.openrpc, &spec)?;
+    println!(
+        "added method {} ({} -> {}) to {}",
+        args.name,
+        params_name,
+        result_name,
+        args.openrpc.display()
+    );
+
+    if !args.lang.is_empty() {
+        gen::run(GenArgs {
+            openrpc: args.openrpc,
+            lang: args.lang,
+            out: args.out,
+            clean: false,
+            strict: false,
+            split: Split::None,
+            embed_spec: false,
+            dump_context: false,
+            format: false,
+            formatter: Vec::new(),
+            naming: Vec::new(),
+            import: Vec::new(),
+            header: HeaderArgs::default(),
+            watch: false,
+            provenance: false,
+            sign_key: None,
+            container: false,
+            check: false,
+            quiet: false,
+            color: Color::Auto,
+            jobs: None,
+            rust_crate: None,
+            rust_borrowed: false,
+            rust_previous: None,
+            wasm_crate: None,
+            ts_react: false,
+            go_no_generics: false,
+            go_module: None,
+            go_version: None,
+        })?;
+    }
+
+    Ok(())
+}
+
+fn method_skeleton(name: &str, summary: Option<&str>, params: &str, result: &str) -> Value {
+    json!({
+        "name": name,
+        "summary": summary.unwrap_or("TODO: describe this method."),
+        "params": {
+            "name": params,
+            "schema": { "$ref": format!("#/components/schemas/{}", params) }
+        },
+        "result": {
+            "name": result,
+            "schema": { "$ref": format!("#/components/schemas/{}", result) }
+        },
+        "errors": [],
+        "examples": [
+            {
+                "name": format!("{}-example", name.rsplit('.').next().unwrap_or(name).replace('_', "-")),
+                "params": [ { "name": params, "value": {} } ],
+                "result": { "name": result, "value": {} }
+            }
+        ]
+    })
+}
+
+fn schema_skeleton(name: &str) -> Value {
+    json!({
+        "title": name,
+        "type": "object",
+        "properties": {},
+        "required": []
+    })
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use minisign::{PublicKey, SecretKey, SecretKeyBox, SignatureBox};
+use serde::{Deserialize, Serialize};
+
+use crate::git;
+use crate::header;
+use crate::manifest::{self, Generator};
+
+pub const FILE_NAME: &str = "provenance.json";
+/// Extension appended to the name of a signed file for its signature.
+const SIGNATURE_EXTENSION: &str = "minisig";
+/// Environment variable holding the password of an encrypted secret key,
+/// which is prompted for otherwise.
+const PASSWORD_VAR: &str = "TRP_SIGN_PASSWORD";
+
+/// Where the bindings of `out` come from, for consumers to trace them back
+/// to the canonical spec.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Provenance {
+    pub generator: Generator,
+    pub spec: Spec,
+    /// SHA-256 of the `manifest.json` written with this file.
+    pub manifest: String,
+    /// When the bindings were generated, as an RFC 3339 UTC timestamp.
+    pub timestamp: String,
+    /// The commit checked out, if generated in a git repository.
+    pub commit: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Spec {
+    pub path: String,
+    pub version: Option<String>,
+    pub sha256: String,
+    /// Whether the spec had changes not in `commit`.
+    pub modified: bool,
+}
+
+/// Writes the provenance of the bindings of `out`, generated from the spec
+/// at `spec_path`, once their manifest is written.
+pub fn write(out: &Path, spec_path: &Path, spec_version: Option<&str>) -> Result<PathBuf> {
+    let commit = git::head();
+    let modified = match commit {
+        Some(_) => git::is_modified(spec_path)?,
+        None => false,
+    };
+    let provenance = Provenance {
+        generator: Generator::current(),
+        spec: Spec {
+            path: spec_path.display().to_string(),
+            version: spec_version.map(str::to_string),
+            sha256: manifest::hash_spec(spec_path)?,
+            modified,
+        },
+        manifest: manifest::hash_file(&out.join(manifest::FILE_NAME))?,
+        timestamp: header::utc_now(),
+        commit,
+    };
+    if provenance.spec.modified {
+        tracing::warn!(
+            "{} has uncommitted changes; the provenance records it as modified",
+            spec_path.display()
+        );
+    }
+    let path = out.join(FILE_NAME);
+    let mut data =
+        serde_json::to_string_pretty(&provenance).context("failed to serialize provenance")?;
+    data.push('\n');
+    fs::write(&path, data).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Signs each of `files` with the minisign secret key at `key`, writing
+/// the signature of each next to it, as `{file}.minisig`. The trusted
+/// comment of the signatures names the file and the spec's hash.
+pub fn sign(files: &[PathBuf], key: &Path, spec_sha256: &str) -> Result<()> {
+    let secret = secret_key(key)?;
+    for file in files {
+        let data = fs::read(file).with_context(|| format!("failed to read {}", file.display()))?;
+        let name = file
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let trusted = format!(
+            "timestamp:{}\tfile:{}\tspec:{}",
+            header::utc_now(),
+            name,
+            spec_sha256
+        );
+        let signature = minisign::sign(None, &secret, Cursor::new(data), Some(&trusted), None)
+            .with_context(|| format!("failed to sign {}", file.display()))?;
+        let path = signature_path(file);
+        fs::write(&path, signature.to_string())
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        tracing::info!("signed {} into {}", file.display(), path.display());
+    }
+    Ok(())
+}
+
+/// Reads the minisign secret key at `path`, decrypting it with the
+/// password in `TRP_SIGN_PASSWORD`, or one prompted for, unless it was
+/// created without one.
+fn secret_key(path: &Path) -> Result<SecretKey> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("failed to read secret key {}", path.display()))?;
+    let parse = || {
+        SecretKeyBox::from_string(&text)
+            .with_context(|| format!("{} isn't a minisign secret key", path.display()))
+    };
+    if let Ok(key) = parse()?.into_unencrypted_secret_key() {
+        return Ok(key);
+    }
+    let password = match std::env::var(PASSWORD_VAR) {
+        Ok(password) => Some(password),
+        // rsign encrypts the keys it creates without a password with an
+        // empty one.
+        Err(_) => match parse()?.into_secret_key(Some(String::new())) {
+            Ok(key) => return Ok(key),
+            Err(_) => None,
+        },
+    };
+    parse()?
+        .into_secret_key(password)
+        .with_context(|| format!("failed to decrypt secret key {}", path.display()))
+}
+
+fn signature_path(file: &Path) -> PathBuf {
+    let mut path = file.as_os_str().to_owned();
+    path.push(".");
+    path.push(SIGNATURE_EXTENSION);
+    PathBuf::from(path)
+}
+
+#[derive(Parser, Debug)]
+pub struct VerifyArgs {
+    /// Output directory of the bindings
+    #[arg(long, default_value = "codegen")]
+    pub out: PathBuf,
+    /// Minisign public key the manifest was signed with
+    #[arg(long)]
+    pub public_key: PathBuf,
+    /// Canonical OpenRPC spec, which the bindings must be generated from
+    #[arg(long)]
+    pub openrpc: Option<PathBuf>,
+}
+
+/// Verifies the signatures of the manifest and provenance of `out`, the
+/// hash of every file the manifest lists, the manifest's hash in the
+/// provenance and, given the canonical spec, the spec's hash in both.
+pub fn verify(args: VerifyArgs) -> Result<()> {
+    let key = PublicKey::from_file(&args.public_key)
+        .with_context(|| format!("failed to read public key {}", args.public_key.display()))?;
+    let manifest_path = args.out.join(manifest::FILE_NAME);
+    let manifest = manifest::load(&manifest_path)
+        .with_context(|| format!("{} is missing or malformed", manifest_path.display()))?;
+    let provenance_path = args.out.join(FILE_NAME);
+    let provenance: Option<Provenance> = match provenance_path.is_file() {
+        true => {
+            let data = fs::read_to_string(&provenance_path)
+                .with_context(|| format!("failed to read {}", provenance_path.display()))?;
+            Some(
+                serde_json::from_str(&data)
+                    .with_context(|| format!("failed to parse {}", provenance_path.display()))?,
+            )
+        }
+        false => None,
+    };
+
+    let mut problems = Vec::new();
+    let mut signed = vec![manifest_path.clone()];
+    signed.extend(provenance.as_ref().map(|_| provenance_path.clone()));
+    for file in &signed {
+        if let Err(err) = verify_signature(&key, file) {
+            problems.push(format!("{:#}", err));
+        }
+    }
+
+    for (file, expected) in &manifest.files {
+        let path = [args.out.join(file), PathBuf::from(file)]
+            .into_iter()
+            .find(|path| path.is_file());
+        match path.map(|path| manifest::hash_file(&path)).transpose()? {
+            Some(hash) if &hash == expected => {}
+            Some(_) => problems.push(format!("{} differs from the manifest", file)),
+            None => problems.push(format!("{} is missing", file)),
+        }
+    }
+
+    if let Some(provenance) = &provenance {
+        if provenance.manifest != manifest::hash_file(&manifest_path)? {
+            problems.push(format!(
+                "{} doesn't match the manifest it was written with",
+                FILE_NAME
+            ));
+        }
+        if provenance.spec.sha256 != manifest.spec.sha256 {
+            problems.push(format!(
+                "{} and {} name different specs",
+                FILE_NAME,
+                manifest::FILE_NAME
+            ));
+        }
+    }
+    if let Some(spec) = &args.openrpc {
+        if manifest.spec.sha256 != manifest::hash_spec(spec)? {
+            problems.push(format!(
+                "the bindings weren't generated from {}",
+                spec.display()
+            ));
+        }
+    }
+
+    for problem in &problems {
+        println!("- {}", problem);
+    }
+    if !problems.is_empty() {
+        anyhow::bail!(
+            "{} problem(s) found in {}",
+            problems.len(),
+            args.out.display()
+        );
+    }
+    match &provenance {
+        Some(provenance) => println!(
+            "verified {} file(s), generated by {} {} from spec {} at {}",
+            manifest.files.len(),
+            provenance.generator.name,
+            provenance.generator.version,
+            provenance.spec.sha256,
+            provenance.commit.as_deref().unwrap_or("an unknown commit")
+        ),
+        None => println!("verified {} file(s)", manifest.files.len()),
+    }
+    Ok(())
+}
+
+fn verify_signature(key: &PublicKey, file: &Path) -> Result<()> {
+    let path = signature_path(file);
+    let signature = SignatureBox::from_file(&path)
+        .with_context(|| format!("failed to read signature {}", path.display()))?;
+    let data = fs::read(file).with_context(|| format!("failed to read {}", file.display()))?;
+    minisign::verify(key, &signature, Cursor::new(data), true, false, false)
+        .with_context(|| format!("{} doesn't match its signature", file.display()))
+}
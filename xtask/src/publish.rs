@@ -0,0 +1,434 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use serde_json::Value;
+use tempfile::TempDir;
+use trp_codegen::mapper::python::pep440;
+use trp_codegen::openrpc;
+
+use crate::manifest;
+use crate::tool;
+
+#[derive(Parser, Debug)]
+pub struct PublishArgs {
+    /// Package and validate the bindings without uploading them
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Output directory of the bindings
+    #[arg(long, default_value = "codegen")]
+    pub out: PathBuf,
+    /// Directory of the Rust binding's crate
+    #[arg(long, default_value = "crates/trp-types")]
+    pub rust_crate: PathBuf,
+    /// Comma separated list of registries to publish to (all by default)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub only: Vec<Registry>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Registry {
+    /// The Rust crate, to crates.io
+    Crates,
+    /// The TypeScript package, to npm
+    Npm,
+    /// The Python distribution, to PyPI
+    Pypi,
+}
+
+impl Registry {
+    fn label(self) -> &'static str {
+        match self {
+            Registry::Crates => "crates.io",
+            Registry::Npm => "npm",
+            Registry::Pypi => "pypi",
+        }
+    }
+}
+
+/// A package built for a registry, and what's wrong with it.
+struct Package {
+    name: String,
+    version: String,
+    problems: Vec<String>,
+    /// Directory the package is published from.
+    dir: PathBuf,
+    /// Files to upload, for registries that take built files.
+    artifacts: Vec<PathBuf>,
+    /// Keeps the built files until they're uploaded.
+    _scratch: Option<TempDir>,
+}
+
+enum Outcome {
+    Packaged(Package),
+    Skipped(String),
+}
+
+/// Packages each binding the way its registry's tooling would, and checks
+/// the packages' versions against the spec's, their metadata and that they
+/// hold every generated file. Unless `--dry-run` is given, the packages are
+/// then uploaded, provided that every check passed.
+pub fn run(args: PublishArgs) -> Result<()> {
+    let spec = openrpc::load(&args.openrpc)?;
+    let version = spec
+        .version()
+        .context("spec has no info.version")?
+        .to_string();
+    let recorded = manifest::recorded(&args.out);
+    let registries = match args.only.is_empty() {
+        true => vec![Registry::Crates, Registry::Npm, Registry::Pypi],
+        false => args.only.clone(),
+    };
+
+    let mut outcomes = Vec::new();
+    for registry in registries {
+        let _span = tracing::debug_span!("package", registry = registry.label()).entered();
+        let outcome = match registry {
+            Registry::Crates => crate_package(&args, &version, &recorded)?,
+            Registry::Npm => npm_package(&args, &version, &recorded)?,
+            Registry::Pypi => python_package(&args, &version, &recorded)?,
+        };
+        outcomes.push((registry, outcome));
+    }
+    print!("{}", report(&outcomes));
+
+    let problems: usize = outcomes
+        .iter()
+        .filter_map(|(_, outcome)| match outcome {
+            Outcome::Packaged(package) => Some(package.problems.len()),
+            Outcome::Skipped(_) => None,
+        })
+        .sum();
+    if problems > 0 {
+        anyhow::bail!("{} problem(s) found in the packages", problems);
+    }
+    if args.dry_run {
+        return Ok(());
+    }
+    for (registry, outcome) in &outcomes {
+        if let Outcome::Packaged(package) = outcome {
+            upload(*registry, package, &args)?;
+            tracing::info!(
+                "published {} {} to {}",
+                package.name,
+                package.version,
+                registry.label()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Runs `cargo publish --dry-run`, which builds the packaged crate, and
+/// compares its files with the generated ones.
+fn crate_package(args: &PublishArgs, version: &str, recorded: &[PathBuf]) -> Result<Outcome> {
+    let dir = args.rust_crate.clone();
+    let cargo_toml = dir.join("Cargo.toml");
+    let text = fs::read_to_string(&cargo_toml)
+        .with_context(|| format!("failed to read {}", cargo_toml.display()))?;
+    let name = toml_value(&text, "package", "name").unwrap_or_default();
+    let mut package = Package {
+        version: toml_value(&text, "package", "version").unwrap_or_default(),
+        name,
+        problems: Vec::new(),
+        dir: dir.clone(),
+        artifacts: Vec::new(),
+        _scratch: None,
+    };
+    check_version(&mut package, version, "Cargo.toml");
+    for key in ["description", "license"] {
+        if toml_value(&text, "package", key).is_none() {
+            package.problems.push(format!("Cargo.toml has no {}", key));
+        }
+    }
+
+    let manifest_path = cargo_toml.to_string_lossy().into_owned();
+    let cargo = tool::cargo();
+    let list = tool::output(
+        &cargo,
+        &[
+            "package",
+            "--list",
+            "--allow-dirty",
+            "--manifest-path",
+            &manifest_path,
+        ],
+        Path::new("."),
+    )?
+    .unwrap_or_default();
+    let listed: Vec<&str> = list.lines().collect();
+    check_files(&mut package, &dir, recorded, |file| listed.contains(&file));
+
+    if let Err(err) = tool::run(
+        &cargo,
+        &[
+            "publish",
+            "--dry-run",
+            "--quiet",
+            "--allow-dirty",
+            "--manifest-path",
+            &manifest_path,
+        ],
+        Path::new("."),
+    ) {
+        package.problems.push(format!("{:#}", err));
+    }
+    Ok(Outcome::Packaged(package))
+}
+
+/// Runs `npm pack --dry-run` and compares the package it reports with the
+/// generated one.
+fn npm_package(args: &PublishArgs, version: &str, recorded: &[PathBuf]) -> Result<Outcome> {
+    let dir = args.out.join("ts");
+    let Some(json) = tool::output("npm", &["pack", "--dry-run", "--json"], &dir)? else {
+        return Ok(Outcome::Skipped("npm not found".to_string()));
+    };
+    let report: Value = serde_json::from_str(&json).context("failed to parse npm pack output")?;
+    let packed = &report[0];
+    let mut package = Package {
+        name: packed["name"].as_str().unwrap_or_default().to_string(),
+        version: packed["version"].as_str().unwrap_or_default().to_string(),
+        problems: Vec::new(),
+        dir: dir.clone(),
+        artifacts: Vec::new(),
+        _scratch: None,
+    };
+    check_version(&mut package, version, "package.json");
+
+    let manifest_path = dir.join("package.json");
+    let manifest: Value = serde_json::from_str(
+        &fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?,
+    )
+    .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+    for key in ["description", "license"] {
+        if manifest.get(key).is_none() {
+            package
+                .problems
+                .push(format!("package.json has no {}", key));
+        }
+    }
+
+    let files: Vec<&str> = packed["files"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|file| file["path"].as_str())
+        .collect();
+    check_files(&mut package, &dir, recorded, |file| files.contains(&file));
+    Ok(Outcome::Packaged(package))
+}
+
+/// Builds the sdist and wheel with `python3 -m build`, or only the sdist
+/// straight from setuptools when `build` isn't installed, and compares the
+/// sdist's files with the generated ones. The build runs on a copy of the
+/// binding, as setuptools leaves its egg-info behind.
+fn python_package(args: &PublishArgs, version: &str, recorded: &[PathBuf]) -> Result<Outcome> {
+    let dir = args.out.join("python");
+    let pyproject = dir.join("pyproject.toml");
+    let text = fs::read_to_string(&pyproject)
+        .with_context(|| format!("failed to read {}", pyproject.display()))?;
+
+    let scratch = tempfile::tempdir().context("failed to create a scratch directory")?;
+    let source = scratch.path().join("source");
+    let dist = scratch.path().join("dist");
+    tool::copy_dir(&dir, &source)?;
+    let dist_arg = dist.to_string_lossy().into_owned();
+    let built = if tool::available("python3", &["-m", "build", "--version"]) {
+        tool::run(
+            "python3",
+            &[
+                "-m", "build", "--sdist", "--wheel", "--outdir", &dist_arg, ".",
+            ],
+            &source,
+        )?
+    } else if tool::available("python3", &["-c", "import setuptools"]) {
+        tracing::warn!("python3 -m build not found; checking the sdist only");
+        let script = format!(
+            "from setuptools import build_meta; build_meta.build_sdist({:?})",
+            dist_arg
+        );
+        fs::create_dir_all(&dist)
+            .with_context(|| format!("failed to create directory {}", dist.display()))?;
+        tool::run("python3", &["-c", &script], &source)?
+    } else {
+        false
+    };
+    if !built {
+        return Ok(Outcome::Skipped(
+            "python3 with build or setuptools not found".to_string(),
+        ));
+    }
+
+    let mut artifacts: Vec<PathBuf> = fs::read_dir(&dist)
+        .with_context(|| format!("failed to list {}", dist.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    artifacts.sort();
+    let sdist = artifacts
+        .iter()
+        .find(|path| path.to_string_lossy().ends_with(".tar.gz"))
+        .context("the build produced no sdist")?;
+    // Members of the sdist sit under a `{name}-{version}` directory.
+    let listing = tool::output(
+        "python3",
+        &[
+            "-c",
+            "import sys, tarfile; print('\\n'.join(m.split('/', 1)[-1] for m in tarfile.open(sys.argv[1]).getnames()))",
+            &sdist.to_string_lossy(),
+        ],
+        Path::new("."),
+    )?
+    .unwrap_or_default();
+    let files: Vec<&str> = listing.lines().collect();
+
+    let mut package = Package {
+        name: toml_value(&text, "project", "name").unwrap_or_default(),
+        version: toml_value(&text, "project", "version").unwrap_or_default(),
+        problems: Vec::new(),
+        dir: dir.clone(),
+        artifacts: artifacts.clone(),
+        _scratch: None,
+    };
+    check_version(&mut package, &pep440(version), "pyproject.toml");
+    if toml_value(&text, "project", "description").is_none() {
+        package
+            .problems
+            .push("pyproject.toml has no description".to_string());
+    }
+    if !text.contains("\nlicense = ") {
+        package
+            .problems
+            .push("pyproject.toml has no license".to_string());
+    }
+    check_files(&mut package, &dir, recorded, |file| files.contains(&file));
+    package._scratch = Some(scratch);
+    Ok(Outcome::Packaged(package))
+}
+
+fn check_version(package: &mut Package, expected: &str, manifest: &str) {
+    if package.version != expected {
+        package.problems.push(format!(
+            "{} is at version {}, but the spec at {}; regenerate the bindings",
+            manifest, package.version, expected
+        ));
+    }
+}
+
+/// Flags the generated files under `dir` that `packaged` says the package
+/// lacks, by their path relative to `dir`.
+fn check_files(
+    package: &mut Package,
+    dir: &Path,
+    recorded: &[PathBuf],
+    packaged: impl Fn(&str) -> bool,
+) {
+    for file in recorded {
+        let Ok(relative) = file.strip_prefix(dir) else {
+            continue;
+        };
+        // Package paths use slashes on every platform.
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !packaged(&relative) {
+            package
+                .problems
+                .push(format!("{} is missing from the package", relative));
+        }
+    }
+}
+
+/// The string value of `key` in the `[table]` of a TOML document, read
+/// line by line as the generated manifests put one key on each.
+fn toml_value(text: &str, table: &str, key: &str) -> Option<String> {
+    let header = format!("[{}]", table);
+    let mut lines = text
+        .lines()
+        .skip_while(|line| line.trim() != header)
+        .skip(1);
+    lines
+        .by_ref()
+        .take_while(|line| !line.starts_with('['))
+        .find_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+            (name.trim() == key).then(|| value.to_string())
+        })
+}
+
+/// A line per registry with the package built for it, followed by its
+/// problems.
+fn report(outcomes: &[(Registry, Outcome)]) -> String {
+    let mut out = String::new();
+    for (registry, outcome) in outcomes {
+        match outcome {
+            Outcome::Packaged(package) => {
+                let status = match package.problems.len() {
+                    0 => "ok".to_string(),
+                    n => format!("{} problem(s)", n),
+                };
+                let _ = writeln!(
+                    out,
+                    "{:9}  {} {}: {}",
+                    registry.label(),
+                    package.name,
+                    package.version,
+                    status
+                );
+                for problem in &package.problems {
+                    let _ = writeln!(out, "  - {}", problem.replace('\n', "\n    "));
+                }
+            }
+            Outcome::Skipped(reason) => {
+                let _ = writeln!(out, "{:9}  skipped: {}", registry.label(), reason);
+            }
+        }
+    }
+    out
+}
+
+fn upload(registry: Registry, package: &Package, args: &PublishArgs) -> Result<()> {
+    match registry {
+        Registry::Crates => {
+            let manifest = args.rust_crate.join("Cargo.toml");
+            tool::run(
+                &tool::cargo(),
+                &["publish", "--manifest-path", &manifest.to_string_lossy()],
+                Path::new("."),
+            )?;
+        }
+        Registry::Npm => {
+            tool::run("npm", &["publish", "--access", "public"], &package.dir)?;
+        }
+        Registry::Pypi => {
+            if !package
+                .artifacts
+                .iter()
+                .any(|path| path.extension().is_some_and(|e| e == "whl"))
+            {
+                anyhow::bail!("no wheel to upload to PyPI; install the build module");
+            }
+            let mut upload = vec!["-m".to_string(), "twine".into(), "upload".into()];
+            upload.extend(
+                package
+                    .artifacts
+                    .iter()
+                    .map(|path| path.to_string_lossy().into_owned()),
+            );
+            let upload: Vec<&str> = upload.iter().map(String::as_str).collect();
+            if !tool::run("python3", &upload, Path::new("."))? {
+                anyhow::bail!("python3 not found");
+            }
+        }
+    }
+    Ok(())
+}
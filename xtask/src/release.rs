@@ -0,0 +1,448 @@
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde_json::Value;
+use trp_codegen::openrpc;
+use trp_codegen::split::Split;
+
+use crate::check::Color;
+use crate::gen::{self, GenArgs};
+use crate::git;
+use crate::header::HeaderArgs;
+use crate::semver::{self, Bump, Version};
+use crate::tool;
+
+/// Every language a release regenerates.
+const LANGUAGES: &[&str] = &[
+    "ts",
+    "deno",
+    "python",
+    "go",
+    "rust",
+    "elm",
+    "php",
+    "scala",
+    "c",
+    "ocaml",
+    "proto",
+    "cddl",
+    "jsonschema",
+    "openapi",
+];
+
+#[derive(Parser, Debug)]
+pub struct ReleaseArgs {
+    /// Version to release, e.g. 1.2.0 or 1.2.0-rc.1, or the part of the
+    /// current version to bump: major, minor or patch
+    pub version: String,
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Output directory of the bindings
+    #[arg(long, default_value = "codegen")]
+    pub out: PathBuf,
+    /// Directory of the Rust binding's crate
+    #[arg(long, default_value = "crates/trp-types")]
+    pub rust_crate: PathBuf,
+    /// Module path of the Go binding, for a `go.mod` to tag releases of
+    #[arg(long)]
+    pub go_module: Option<String>,
+    /// Directory to prepare the publish artifacts in, under a subdirectory
+    /// named after the version
+    #[arg(long, default_value = "dist")]
+    pub dist: PathBuf,
+    /// Don't check that the bindings compile
+    #[arg(long, default_value_t = false)]
+    pub skip_checks: bool,
+    /// Release even if the version doesn't follow the current one, or is a
+    /// smaller bump than the spec changes since the last tag require
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+}
+
+/// Bumps `info.version`, regenerates every binding (their package
+/// manifests included), checks that they compile and prepares the
+/// artifacts of each ecosystem under `dist/<version>`, along with a
+/// `RELEASE.md` listing how to publish them. Nothing is committed, tagged
+/// or uploaded. Generating reads the spec from disk, so the new version is
+/// written first; if a later step fails, the spec is written back as it
+/// was and the bindings regenerated from it.
+pub fn run(args: ReleaseArgs) -> Result<()> {
+    let mut spec = openrpc::load_raw(&args.openrpc)?;
+    let current = spec
+        .pointer("/info/version")
+        .and_then(Value::as_str)
+        .context("spec has no info.version")?
+        .to_string();
+    let version = next_version(&current, &args.version)?;
+    if !args.force {
+        check_version(&args, &current, &version)?;
+    }
+
+    let _release = tracing::debug_span!("release", version).entered();
+    let original = Original::read(&args.openrpc, &spec)?;
+    spec["info"]["version"] = Value::String(version.clone());
+    openrpc::save_raw(&args.openrpc, &spec)?;
+    tracing::info!("bumped info.version from {} to {}", current, version);
+
+    if let Err(err) = prepare(&args, &version) {
+        original.restore(&args.openrpc)?;
+        tracing::warn!("restored info.version {}", current);
+        if let Err(err) = gen::run(gen_args(&args)) {
+            tracing::warn!(
+                "failed to regenerate the bindings of {}: {:#}",
+                current,
+                err
+            );
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// The spec as it was before its version was bumped: the text of a single
+/// file, to write back byte for byte, or the merged parts of a split spec.
+enum Original {
+    File(String),
+    Split(Value),
+}
+
+impl Original {
+    fn read(path: &Path, spec: &Value) -> Result<Self> {
+        if path.is_dir() {
+            return Ok(Self::Split(spec.clone()));
+        }
+        fs::read_to_string(path)
+            .map(Self::File)
+            .with_context(|| format!("failed to read {}", path.display()))
+    }
+
+    fn restore(&self, path: &Path) -> Result<()> {
+        match self {
+            Self::File(text) => fs::write(path, text)
+                .with_context(|| format!("failed to restore {}", path.display())),
+            Self::Split(spec) => openrpc::save_raw(path, spec),
+        }
+    }
+}
+
+/// The steps of a release once the spec carries its version: regenerating
+/// the bindings, checking them and preparing the artifacts.
+fn prepare(args: &ReleaseArgs, version: &str) -> Result<()> {
+    gen::run(gen_args(args))?;
+
+    if args.skip_checks {
+        tracing::warn!("skipping compile checks");
+    } else {
+        compile_checks(args)?;
+    }
+
+    let dist = std::env::current_dir()
+        .context("failed to read the working directory")?
+        .join(&args.dist)
+        .join(version);
+    if dist.exists() {
+        fs::remove_dir_all(&dist).with_context(|| format!("failed to clean {}", dist.display()))?;
+    }
+    fs::create_dir_all(&dist)
+        .with_context(|| format!("failed to create directory {}", dist.display()))?;
+    let notes = artifacts(args, version, &dist)?;
+    let path = dist.join("RELEASE.md");
+    fs::write(&path, &notes).with_context(|| format!("failed to write {}", path.display()))?;
+    print!("{}", notes);
+    Ok(())
+}
+
+/// The version `requested` stands for: a bump of `current`, or a version
+/// of its own.
+fn next_version(current: &str, requested: &str) -> Result<String> {
+    let bump = match requested {
+        "major" => Bump::Major,
+        "minor" => Bump::Minor,
+        "patch" => Bump::Patch,
+        version => {
+            let version = version.trim_start_matches('v');
+            Version::parse(version)?;
+            return Ok(version.to_string());
+        }
+    };
+    Ok(Version::parse(current)?.bumped(bump).to_string())
+}
+
+/// Fails unless `version` follows `current` by at least the bump the spec
+/// changes since the last tag require.
+fn check_version(args: &ReleaseArgs, current: &str, version: &str) -> Result<()> {
+    let (from, to) = (Version::parse(current)?, Version::parse(version)?);
+    let key = |v: Version| (v.major, v.minor, v.patch);
+    if version == current || key(to) < key(from) {
+        anyhow::bail!(
+            "{} doesn't follow the current version {}; pass --force to release it anyway",
+            version,
+            current
+        );
+    }
+
+    let tag = match git::last_tag() {
+        Ok(tag) => tag,
+        Err(_) => {
+            tracing::warn!("no git tag to compare the spec with; skipping the semver check");
+            return Ok(());
+        }
+    };
+    let spec = openrpc::load(&args.openrpc)?;
+    let assessment = semver::assess(&tag, &args.openrpc, &spec)?;
+    let actual = assessment.baseline.bump_to(to);
+    if actual < assessment.required {
+        anyhow::bail!(
+            "{} is a {} bump from {} ({}), but the {} spec change(s) since then require a {} bump; \
+             pass --force to release it anyway",
+            version,
+            actual,
+            assessment.baseline,
+            tag,
+            assessment.changes.len(),
+            assessment.required
+        );
+    }
+    Ok(())
+}
+
+fn gen_args(args: &ReleaseArgs) -> GenArgs {
+    GenArgs {
+        openrpc: args.openrpc.clone(),
+        lang: LANGUAGES.iter().map(|l| l.to_string()).collect(),
+        out: args.out.clone(),
+        clean: false,
+        strict: false,
+        split: Split::None,
+        embed_spec: false,
+        dump_context: false,
+        format: false,
+        formatter: Vec::new(),
+        naming: Vec::new(),
+        import: Vec::new(),
+        header: HeaderArgs::default(),
+        watch: false,
+        provenance: false,
+        sign_key: None,
+        container: false,
+        check: false,
+        quiet: false,
+        color: Color::Auto,
+        jobs: None,
+        rust_crate: Some(args.rust_crate.clone()),
+        rust_borrowed: false,
+        rust_previous: None,
+        wasm_crate: None,
+        ts_react: false,
+        go_no_generics: false,
+        go_module: args.go_module.clone(),
+        go_version: None,
+    }
+}
+
+/// Checks that the bindings of the languages whose toolchain is installed
+/// compile. The TypeScript ones need their `zod` dependency installed, so
+/// they're left to the projects using them.
+fn compile_checks(args: &ReleaseArgs) -> Result<()> {
+    let _checks = tracing::debug_span!("checks").entered();
+    let cargo = tool::cargo();
+    let manifest = args.rust_crate.join("Cargo.toml");
+    let manifest = manifest.to_string_lossy();
+    // Bytecode goes to a scratch directory rather than among the bindings.
+    let scratch = tempfile::tempdir().context("failed to create a scratch directory")?;
+    let pycache = format!("pycache_prefix={}", scratch.path().display());
+    let go = args.out.join("go");
+    let mut checks = vec![
+        (
+            "rust",
+            cargo.as_str(),
+            vec![
+                "check",
+                "--quiet",
+                "--all-features",
+                "--manifest-path",
+                &manifest,
+            ],
+            PathBuf::from("."),
+        ),
+        (
+            "python",
+            "python3",
+            vec!["-X", &pycache, "-m", "compileall", "-q", "."],
+            args.out.join("python"),
+        ),
+    ];
+    // Go only builds a directory as a module.
+    if go.join("go.mod").exists() {
+        checks.push(("go", "go", vec!["vet", "./..."], go));
+    }
+
+    for (lang, program, check, dir) in checks {
+        match tool::run(program, &check, &dir)? {
+            true => tracing::info!("{} binding compiles", lang),
+            false => tracing::warn!("skipping the {} check: {} not found", lang, program),
+        }
+    }
+    Ok(())
+}
+
+/// Prepares the artifacts of each ecosystem in `dist`, returning the
+/// release notes listing them and how to publish them.
+fn artifacts(args: &ReleaseArgs, version: &str, dist: &Path) -> Result<String> {
+    let _artifacts = tracing::debug_span!("artifacts").entered();
+    let dist_arg = dist.to_string_lossy().into_owned();
+    let mut notes = format!("# Release {}\n\n", version);
+    let mut skipped = Vec::new();
+
+    // Crate, packaged without verification as the compile check built it.
+    let target = tempfile::tempdir().context("failed to create a scratch directory")?;
+    let manifest = args.rust_crate.join("Cargo.toml");
+    let packaged = tool::run(
+        &tool::cargo(),
+        &[
+            "package",
+            "--quiet",
+            "--no-verify",
+            "--allow-dirty",
+            "--manifest-path",
+            &manifest.to_string_lossy(),
+            "--target-dir",
+            &target.path().to_string_lossy(),
+        ],
+        Path::new("."),
+    )?;
+    let crates = copy_files(&target.path().join("package"), "crate", dist)?;
+    match (packaged, crates.as_slice()) {
+        (true, [file, ..]) => {
+            let _ = writeln!(
+                notes,
+                "- Rust: `{}`; publish with `cargo publish --manifest-path {}`",
+                file,
+                manifest.display()
+            );
+        }
+        _ => skipped.push("Rust crate"),
+    }
+
+    // npm tarball.
+    let added = produced(dist, || {
+        tool::run(
+            "npm",
+            &["pack", "--quiet", "--pack-destination", &dist_arg],
+            &args.out.join("ts"),
+        )
+    })?;
+    match added.as_slice() {
+        [file, ..] => {
+            let _ = writeln!(
+                notes,
+                "- TypeScript: `{}`; publish with `npm publish {} --access public`",
+                file, file
+            );
+        }
+        [] => skipped.push("npm tarball (npm not found)"),
+    }
+
+    // sdist and wheel, built from a copy so the build leaves no egg-info
+    // behind among the generated files.
+    let python = args.out.join("python");
+    if tool::available("python3", &["-m", "build", "--version"]) {
+        let scratch = tempfile::tempdir().context("failed to create a scratch directory")?;
+        tool::copy_dir(&python, scratch.path())?;
+        let added = produced(dist, || {
+            tool::run(
+                "python3",
+                &[
+                    "-m", "build", "--sdist", "--wheel", "--outdir", &dist_arg, ".",
+                ],
+                scratch.path(),
+            )
+        })?;
+        let files: Vec<String> = added.iter().map(|f| format!("`{}`", f)).collect();
+        let _ = writeln!(
+            notes,
+            "- Python: {}; publish with `twine upload {}`",
+            files.join(", "),
+            added.join(" ")
+        );
+    } else {
+        skipped.push("Python sdist and wheel (python3 -m build not found)");
+    }
+
+    // JSR publishes from the sources.
+    let _ = writeln!(
+        notes,
+        "- Deno: publish with `deno publish` in `{}`",
+        args.out.join("deno").display()
+    );
+
+    // Go modules are published by tagging the commit.
+    let go = args.out.join("go");
+    match go.join("go.mod").exists() {
+        true => {
+            let tag = format!("{}/v{}", go.display(), version);
+            let _ = writeln!(
+                notes,
+                "- Go: tag the release commit with `git tag {}` and push the tag",
+                tag
+            );
+        }
+        false => skipped.push("Go tag (no go.mod; pass --go-module)"),
+    }
+
+    if !skipped.is_empty() {
+        notes.push_str("\nSkipped:\n\n");
+        for what in &skipped {
+            let _ = writeln!(notes, "- {}", what);
+        }
+    }
+    let _ = writeln!(
+        notes,
+        "\nCommit the bumped spec and regenerated bindings, then tag the commit `v{}`.",
+        version
+    );
+    Ok(notes)
+}
+
+/// Runs `step`, returning the names of the files it added to `dist`.
+fn produced(dist: &Path, step: impl FnOnce() -> Result<bool>) -> Result<Vec<String>> {
+    let before = file_names(dist)?;
+    step()?;
+    Ok(file_names(dist)?
+        .into_iter()
+        .filter(|name| !before.contains(name))
+        .collect())
+}
+
+fn file_names(dir: &Path) -> Result<BTreeSet<String>> {
+    let entries = fs::read_dir(dir).with_context(|| format!("failed to list {}", dir.display()))?;
+    Ok(entries
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect())
+}
+
+/// Copies the files of `dir` with the extension `extension` into `dist`,
+/// returning their names.
+fn copy_files(dir: &Path, extension: &str, dist: &Path) -> Result<Vec<String>> {
+    let mut copied = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(copied);
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == extension) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            fs::copy(&path, dist.join(&name))
+                .with_context(|| format!("failed to copy {}", path.display()))?;
+            copied.push(name);
+        }
+    }
+    copied.sort();
+    Ok(copied)
+}
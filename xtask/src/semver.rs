@@ -0,0 +1,257 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use crate::diff::{self, Impact};
+use trp_codegen::openrpc::{self, OpenRpc};
+
+use crate::git;
+
+#[derive(Parser, Debug)]
+pub struct SemverArgs {
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Git revision holding the baseline spec (defaults to the most recent tag)
+    #[arg(long)]
+    pub since: Option<String>,
+    /// Fail unless info.version is bumped at least as much as recommended
+    #[arg(long, default_value_t = false)]
+    pub check: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Bump::None => "none",
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub fn parse(raw: &str) -> Result<Self> {
+        let core = raw.trim_start_matches('v');
+        let core = core.split(['-', '+']).next().unwrap_or(core);
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.len() != 3 {
+            anyhow::bail!("invalid semantic version {}", raw);
+        }
+        let number = |s: &str| {
+            s.parse::<u64>()
+                .with_context(|| format!("invalid semantic version {}", raw))
+        };
+        Ok(Self {
+            major: number(parts[0])?,
+            minor: number(parts[1])?,
+            patch: number(parts[2])?,
+        })
+    }
+
+    pub fn bumped(self, bump: Bump) -> Self {
+        match bump {
+            Bump::None => self,
+            Bump::Patch => Self {
+                patch: self.patch + 1,
+                ..self
+            },
+            Bump::Minor => Self {
+                minor: self.minor + 1,
+                patch: 0,
+                ..self
+            },
+            Bump::Major => Self {
+                major: self.major + 1,
+                minor: 0,
+                patch: 0,
+            },
+        }
+    }
+
+    /// The kind of bump that leads from `self` to `next`.
+    pub fn bump_to(self, next: Version) -> Bump {
+        if next.major != self.major {
+            Bump::Major
+        } else if next.minor != self.minor {
+            Bump::Minor
+        } else if next.patch != self.patch {
+            Bump::Patch
+        } else {
+            Bump::None
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Maps the impact of a change set onto a version bump. Pre-1.0 versions
+/// follow the Cargo convention: breaking changes bump the minor component
+/// and everything else bumps the patch component.
+pub fn recommend(current: Version, impact: Option<Impact>) -> Bump {
+    match (impact, current.major) {
+        (None, _) => Bump::None,
+        (Some(Impact::Major), 0) => Bump::Minor,
+        (Some(_), 0) => Bump::Patch,
+        (Some(Impact::Major), _) => Bump::Major,
+        (Some(Impact::Minor), _) => Bump::Minor,
+        (Some(Impact::Patch), _) => Bump::Patch,
+    }
+}
+
+/// What the changes to the spec at `path` since the revision `since`
+/// amount to.
+pub struct Assessment {
+    pub changes: Vec<diff::Change>,
+    /// `info.version` at `since`.
+    pub baseline: Version,
+    pub required: Bump,
+}
+
+/// Compares the spec at `path` as of the revision `since` with `new`.
+pub fn assess(since: &str, path: &Path, new: &OpenRpc) -> Result<Assessment> {
+    let old_source = git::show_spec(since, path)?;
+    let old = openrpc::parse(&old_source, &format!("{}:{}", since, path.display()))?;
+    let changes = diff::diff_specs(&old, new)?;
+    let baseline = Version::parse(old.version().context("baseline spec has no info.version")?)?;
+    let required = recommend(baseline, diff::overall_impact(&changes));
+    Ok(Assessment {
+        changes,
+        baseline,
+        required,
+    })
+}
+
+pub fn run(args: SemverArgs) -> Result<()> {
+    let since = match args.since {
+        Some(rev) => rev,
+        None => git::last_tag()?,
+    };
+    let new = openrpc::load(&args.openrpc)?;
+    let Assessment {
+        changes,
+        baseline: old_version,
+        required,
+    } = assess(&since, &args.openrpc, &new)?;
+    for c in &changes {
+        println!("[{}] {} {}: {}", c.impact, c.subject, c.path, c.detail);
+    }
+
+    let new_version = Version::parse(new.version().context("spec has no info.version")?)?;
+    let actual = old_version.bump_to(new_version);
+
+    println!(
+        "{} change(s) since {}; recommended bump: {} ({} -> {})",
+        changes.len(),
+        since,
+        required,
+        old_version,
+        old_version.bumped(required)
+    );
+
+    if args.check && actual < required {
+        anyhow::bail!(
+            "info.version {} is a {} bump from {}, but the changes require a {} bump",
+            new_version,
+            actual,
+            old_version,
+            required
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::diff::tests::{base, changes};
+
+    /// The bump the changes from `old` to `new` require of `version`.
+    fn required(version: &str, old: &serde_json::Value, new: &serde_json::Value) -> Bump {
+        let impact = changes(old, new)
+            .into_iter()
+            .map(|(_, _, impact)| impact)
+            .max();
+        recommend(Version::parse(version).unwrap(), impact)
+    }
+
+    #[test]
+    fn versions() {
+        let version = Version::parse("v1.2.3-rc.1").unwrap();
+        assert_eq!(version.to_string(), "1.2.3");
+        assert_eq!(version.bumped(Bump::Patch).to_string(), "1.2.4");
+        assert_eq!(version.bumped(Bump::Minor).to_string(), "1.3.0");
+        assert_eq!(version.bumped(Bump::Major).to_string(), "2.0.0");
+        assert_eq!(version.bump_to(version.bumped(Bump::Minor)), Bump::Minor);
+        assert_eq!(version.bump_to(version), Bump::None);
+        assert!(Version::parse("1.2").is_err());
+    }
+
+    #[test]
+    fn bumps() {
+        let mut added = base();
+        added["methods"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({ "name": "tree.prune", "params": [], "result": { "name": "done", "schema": { "type": "boolean" } } }));
+        let mut removed = base();
+        removed["methods"] = json!([]);
+        let mut optional = base();
+        optional["components"]["schemas"]["Params"]["required"] = json!([]);
+        let mut required_field = base();
+        required_field["components"]["schemas"]["Params"]["required"] = json!(["root", "depth"]);
+        let mut optional_field = base();
+        optional_field["components"]["schemas"]["Params"]["properties"]["order"] =
+            json!({ "type": "string" });
+        let mut error_added = base();
+        error_added["methods"][0]["errors"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({ "code": -32001, "message": "Walk interrupted" }));
+        let mut error_removed = base();
+        error_removed["methods"][0]["errors"] = json!([]);
+        let mut message = base();
+        message["methods"][0]["errors"][0]["message"] = "No such node".into();
+
+        for (new, stable, initial) in [
+            (&base(), Bump::None, Bump::None),
+            (&added, Bump::Minor, Bump::Patch),
+            (&removed, Bump::Major, Bump::Minor),
+            (&optional, Bump::Major, Bump::Minor),
+            (&required_field, Bump::Major, Bump::Minor),
+            (&optional_field, Bump::Minor, Bump::Patch),
+            (&error_added, Bump::Minor, Bump::Patch),
+            (&error_removed, Bump::Major, Bump::Minor),
+            (&message, Bump::Patch, Bump::Patch),
+        ] {
+            assert_eq!(required("1.2.3", &base(), new), stable, "{}", new);
+            assert_eq!(required("0.1.0", &base(), new), initial, "{}", new);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// The cargo running xtask, or the one on `PATH`.
+pub fn cargo() -> String {
+    std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string())
+}
+
+/// Whether `program` runs `args` successfully, to tell whether an optional
+/// tool (or a module of it, as with `python3 -m build`) is installed.
+pub fn available(program: &str, args: &[&str]) -> bool {
+    Command::new(program)
+        .args(args)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Runs `program` with `args` in `dir`. Returns `false` without running
+/// anything when the program isn't installed, and fails with its output
+/// when it fails.
+pub fn run(program: &str, args: &[&str], dir: &Path) -> Result<bool> {
+    Ok(output(program, args, dir)?.is_some())
+}
+
+/// Runs `program` like [`run`], returning what it printed on stdout.
+pub fn output(program: &str, args: &[&str], dir: &Path) -> Result<Option<String>> {
+    tracing::debug!(
+        "running {} {} in {}",
+        program,
+        args.join(" "),
+        dir.display()
+    );
+    let output = match Command::new(program).args(args).current_dir(dir).output() {
+        Ok(output) => output,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("failed to run {}", program)),
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let log = match stderr.trim().is_empty() {
+            true => stdout.trim_end(),
+            false => stderr.trim_end(),
+        };
+        anyhow::bail!(
+            "{} {} failed in {}:\n{}",
+            program,
+            args.join(" "),
+            dir.display(),
+            log
+        );
+    }
+    Ok(Some(stdout))
+}
+
+/// Copies `from` into `to`, recursively.
+pub fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to).with_context(|| format!("failed to create {}", to.display()))?;
+    let entries =
+        fs::read_dir(from).with_context(|| format!("failed to list {}", from.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target)
+                .with_context(|| format!("failed to copy {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
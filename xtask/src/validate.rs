@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use schemars::schema::Schema;
+use serde_json::Value;
+
+use trp_codegen::analysis;
+use trp_codegen::error_codes;
+use trp_codegen::openrpc;
+
+#[derive(Parser, Debug)]
+pub struct ValidateArgs {
+    /// Path to the OpenRPC spec
+    #[arg(long, default_value = "specs/trp.json")]
+    pub openrpc: PathBuf,
+    /// Also report unreferenced components and unsatisfiable schemas
+    #[arg(long, default_value_t = false)]
+    pub unused: bool,
+}
+
+pub fn run(args: ValidateArgs) -> Result<()> {
+    let spec = openrpc::load(&args.openrpc)?;
+    let components = spec
+        .schemas()
+        .context("no components.schemas present in OpenRPC spec")?;
+    let graph = analysis::component_graph(&spec);
+
+    let mut problems = Vec::new();
+
+    for method in &spec.methods {
+        let mut locations = Vec::new();
+        for d in method.params.iter() {
+            locations.push((format!("params.{}", d.name), schema_value(&d.schema)));
+        }
+        if let Some(d) = &method.result {
+            locations.push(("result".to_string(), schema_value(&d.schema)));
+        }
+        for e in &method.errors {
+            if let Some(data) = &e.data {
+                locations.push((format!("errors.{}", e.code), data.clone()));
+            }
+        }
+
+        for (location, value) in locations {
+            for target in analysis::collect_refs(&value) {
+                if !components.contains_key(&target) {
+                    problems.push(format!(
+                        "methods.{}.{}: unresolved reference to {}",
+                        method.name, location, target
+                    ));
+                }
+            }
+        }
+    }
+    for problem in error_codes::check(&spec) {
+        let line = format!("{}: {}", problem.location, problem.message);
+        match problem.severity {
+            error_codes::Severity::Warning => tracing::warn!("{}", line),
+            error_codes::Severity::Error => problems.push(line),
+        }
+    }
+    for (name, targets) in &graph {
+        for target in targets.iter().filter(|t| !components.contains_key(*t)) {
+            problems.push(format!(
+                "components.schemas.{}: unresolved reference to {}",
+                name, target
+            ));
+        }
+    }
+
+    if args.unused {
+        let reachable = analysis::reachable_components(&spec, &graph);
+        for name in graph.keys().filter(|n| !reachable.contains(*n)) {
+            problems.push(format!(
+                "components.schemas.{}: not reachable from any method",
+                name
+            ));
+        }
+        for (path, reason) in analysis::unsatisfiable_schemas(components) {
+            problems.push(format!("components.schemas.{}: {}", path, reason));
+        }
+    }
+
+    for problem in &problems {
+        tracing::error!("{}", problem);
+    }
+    if !problems.is_empty() {
+        anyhow::bail!(
+            "{} problem(s) found in {}",
+            problems.len(),
+            args.openrpc.display()
+        );
+    }
+
+    println!("{} is valid", args.openrpc.display());
+    Ok(())
+}
+
+fn schema_value(schema: &Schema) -> Value {
+    serde_json::to_value(schema).unwrap_or(Value::Null)
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+
+use trp_codegen::resolver::ref_to_name;
+
+/// A single mismatch between a JSON value and the schema it was checked against.
+#[derive(Debug)]
+pub struct Violation {
+    pub path: String,
+    pub message: String,
+}
+
+/// Minimal JSON Schema validator covering the subset of keywords used by the
+/// TRP spec. References are resolved against `components.schemas`.
+pub struct Validator<'a> {
+    components: &'a HashMap<String, Schema>,
+}
+
+impl<'a> Validator<'a> {
+    pub fn new(components: &'a HashMap<String, Schema>) -> Self {
+        Self { components }
+    }
+
+    pub fn validate(&self, schema: &Schema, value: &Value) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.check(schema, value, "$", &mut violations);
+        violations
+    }
+
+    fn check(&self, schema: &Schema, value: &Value, path: &str, out: &mut Vec<Violation>) {
+        match schema {
+            Schema::Bool(true) => {}
+            Schema::Bool(false) => out.push(violation(path, "no value is allowed here")),
+            Schema::Object(obj) => self.check_object(obj, value, path, out),
+        }
+    }
+
+    fn check_object(
+        &self,
+        schema: &SchemaObject,
+        value: &Value,
+        path: &str,
+        out: &mut Vec<Violation>,
+    ) {
+        if let Some(reference) = &schema.reference {
+            let target = ref_to_name(reference)
+                .ok()
+                .and_then(|name| self.components.get(&name));
+            match target {
+                Some(target) => self.check(target, value, path, out),
+                None => out.push(violation(
+                    path,
+                    format!("unresolved reference {}", reference),
+                )),
+            }
+            return;
+        }
+
+        if let Some(types) = &schema.instance_type {
+            let allowed: Vec<InstanceType> = match types {
+                SingleOrVec::Single(t) => vec![**t],
+                SingleOrVec::Vec(list) => list.clone(),
+            };
+            if !allowed.iter().any(|t| matches_type(*t, value)) {
+                let names: Vec<String> = allowed.iter().map(|t| format!("{:?}", t)).collect();
+                out.push(violation(
+                    path,
+                    format!("expected {}, found {}", names.join(" | "), describe(value)),
+                ));
+                return;
+            }
+        }
+
+        if let Some(values) = &schema.enum_values {
+            if !values.contains(value) {
+                let allowed: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                out.push(violation(
+                    path,
+                    format!("{} is not one of {}", value, allowed.join(", ")),
+                ));
+            }
+        }
+
+        if let Some(constant) = &schema.const_value {
+            if constant != value {
+                out.push(violation(path, format!("expected constant {}", constant)));
+            }
+        }
+
+        if let Some(subschemas) = &schema.subschemas {
+            if let Some(all_of) = &subschemas.all_of {
+                for sub in all_of {
+                    self.check(sub, value, path, out);
+                }
+            }
+            if let Some(any_of) = &subschemas.any_of {
+                if !any_of.iter().any(|sub| self.validate_at(sub, value, path)) {
+                    out.push(violation(path, "value matches none of the anyOf schemas"));
+                }
+            }
+            if let Some(one_of) = &subschemas.one_of {
+                let matched = one_of
+                    .iter()
+                    .filter(|sub| self.validate_at(sub, value, path))
+                    .count();
+                if matched != 1 {
+                    out.push(violation(
+                        path,
+                        format!(
+                            "value matches {} oneOf schemas, expected exactly 1",
+                            matched
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if let (Some(object), Value::Object(map)) = (&schema.object, value) {
+            for name in &object.required {
+                if !map.contains_key(name) {
+                    out.push(violation(
+                        path,
+                        format!("missing required property `{}`", name),
+                    ));
+                }
+            }
+            for (key, item) in map {
+                let item_path = format!("{}.{}", path, key);
+                let patterns: Vec<&Schema> = object
+                    .pattern_properties
+                    .iter()
+                    .filter(|(pattern, _)| Regex::new(pattern).is_ok_and(|re| re.is_match(key)))
+                    .map(|(_, schema)| schema)
+                    .collect();
+                if let Some(prop) = object.properties.get(key) {
+                    self.check(prop, item, &item_path, out);
+                } else if !patterns.is_empty() {
+                    for schema in patterns {
+                        self.check(schema, item, &item_path, out);
+                    }
+                } else if let Some(additional) = &object.additional_properties {
+                    match additional.as_ref() {
+                        Schema::Bool(false) => {
+                            out.push(violation(path, format!("unexpected property `{}`", key)))
+                        }
+                        other => self.check(other, item, &item_path, out),
+                    }
+                }
+            }
+        }
+
+        if let (Some(string), Value::String(text)) = (&schema.string, value) {
+            let length = text.chars().count() as u32;
+            if let Some(min) = string.min_length.filter(|min| length < *min) {
+                out.push(violation(
+                    path,
+                    format!("string is shorter than {} characters", min),
+                ));
+            }
+            if let Some(max) = string.max_length.filter(|max| length > *max) {
+                out.push(violation(
+                    path,
+                    format!("string is longer than {} characters", max),
+                ));
+            }
+            if let Some(pattern) = &string.pattern {
+                match Regex::new(pattern) {
+                    Ok(re) if re.is_match(text) => {}
+                    Ok(_) => out.push(violation(
+                        path,
+                        format!("string does not match {}", pattern),
+                    )),
+                    Err(_) => out.push(violation(path, format!("invalid pattern {}", pattern))),
+                }
+            }
+        }
+
+        if let (Some(number), Some(n)) = (&schema.number, value.as_f64()) {
+            if let Some(minimum) = number.minimum.filter(|min| n < *min) {
+                out.push(violation(
+                    path,
+                    format!("{} is less than {}", value, minimum),
+                ));
+            }
+            if let Some(maximum) = number.maximum.filter(|max| n > *max) {
+                out.push(violation(
+                    path,
+                    format!("{} is greater than {}", value, maximum),
+                ));
+            }
+        }
+
+        if let (Some(array), Value::Array(items)) = (&schema.array, value) {
+            if let Some(min) = array.min_items.filter(|min| (items.len() as u32) < *min) {
+                out.push(violation(path, format!("expected at least {} items", min)));
+            }
+            if array.unique_items == Some(true)
+                && items
+                    .iter()
+                    .enumerate()
+                    .any(|(i, item)| items[..i].contains(item))
+            {
+                out.push(violation(path, "items are not unique"));
+            }
+            match &array.items {
+                Some(SingleOrVec::Single(item_schema)) => {
+                    for (i, item) in items.iter().enumerate() {
+                        self.check(item_schema, item, &format!("{}[{}]", path, i), out);
+                    }
+                }
+                Some(SingleOrVec::Vec(tuple)) => {
+                    for (i, (item_schema, item)) in tuple.iter().zip(items).enumerate() {
+                        self.check(item_schema, item, &format!("{}[{}]", path, i), out);
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn validate_at(&self, schema: &Schema, value: &Value, path: &str) -> bool {
+        let mut scratch = Vec::new();
+        self.check(schema, value, path, &mut scratch);
+        scratch.is_empty()
+    }
+}
+
+fn matches_type(ty: InstanceType, value: &Value) -> bool {
+    match ty {
+        InstanceType::Null => value.is_null(),
+        InstanceType::Boolean => value.is_boolean(),
+        InstanceType::Object => value.is_object(),
+        InstanceType::Array => value.is_array(),
+        InstanceType::Number => value.is_number(),
+        InstanceType::String => value.is_string(),
+        InstanceType::Integer => value.is_i64() || value.is_u64(),
+    }
+}
+
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn violation(path: &str, message: impl Into<String>) -> Violation {
+    Violation {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
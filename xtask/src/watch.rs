@@ -0,0 +1,141 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use notify::{RecursiveMode, Watcher};
+
+use crate::gen::GenArgs;
+use crate::tool;
+
+/// Events arriving within this window are handled as a single change, as
+/// editors tend to write a file in several steps.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Regenerates the bindings whenever the spec or a template changes, until
+/// interrupted.
+///
+/// Templates are compiled into xtask, so every run goes through `cargo run`
+/// with the same arguments, rebuilding xtask first when a template changed.
+pub fn run(args: &GenArgs) -> Result<()> {
+    let spec = fs::canonicalize(&args.openrpc)
+        .with_context(|| format!("failed to read {}", args.openrpc.display()))?;
+    // A split spec is watched as a whole.
+    let (spec_dir, spec_mode) = match spec.is_dir() {
+        true => (spec.clone(), RecursiveMode::Recursive),
+        false => (
+            spec.parent().unwrap_or(Path::new("/")).to_path_buf(),
+            RecursiveMode::NonRecursive,
+        ),
+    };
+    let templates = Path::new(env!("CARGO_MANIFEST_DIR")).join("../crates/trp-codegen/templates");
+    let templates = fs::canonicalize(&templates)
+        .with_context(|| format!("failed to find templates in {}", templates.display()))?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("failed to start file watcher")?;
+    // The spec's directory rather than the file itself, so that editors
+    // replacing the file on save don't end the watch.
+    watcher
+        .watch(&spec_dir, spec_mode)
+        .with_context(|| format!("failed to watch {}", spec_dir.display()))?;
+    watcher
+        .watch(&templates, RecursiveMode::Recursive)
+        .with_context(|| format!("failed to watch {}", templates.display()))?;
+
+    tracing::info!(
+        "watching {} and {}",
+        args.openrpc.display(),
+        templates.display()
+    );
+    regenerate(&args.out)?;
+
+    while let Ok(event) = rx.recv() {
+        let mut relevant = touches(&event, &spec, &templates);
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            relevant |= touches(&event, &spec, &templates);
+        }
+        if relevant {
+            regenerate(&args.out)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn touches(event: &notify::Result<notify::Event>, spec: &Path, templates: &Path) -> bool {
+    let Ok(event) = event else {
+        return false;
+    };
+    !event.kind.is_access()
+        && event
+            .paths
+            .iter()
+            .any(|p| p.starts_with(spec) || p.starts_with(templates))
+}
+
+/// Runs the generator once and reports the files it changed. Generation
+/// errors are reported without ending the watch.
+fn regenerate(out: &Path) -> Result<()> {
+    let before = snapshot(out);
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|a| a != "--watch")
+        .collect();
+    let status = Command::new(tool::cargo())
+        .args(["run", "--quiet", "--package", "xtask", "--"])
+        .args(&args)
+        .status()
+        .context("failed to run cargo")?;
+    if !status.success() {
+        tracing::error!("generation failed; waiting for changes");
+        return Ok(());
+    }
+
+    let after = snapshot(out);
+    let mut changed = 0;
+    for (path, hash) in &after {
+        let verb = match before.get(path) {
+            None => "created",
+            Some(old) if old != hash => "updated",
+            Some(_) => continue,
+        };
+        tracing::info!("{} {}", verb, path.display());
+        changed += 1;
+    }
+    for path in before.keys().filter(|p| !after.contains_key(*p)) {
+        tracing::info!("removed {}", path.display());
+        changed += 1;
+    }
+    if changed == 0 {
+        tracing::info!("regenerated, no files changed");
+    }
+    Ok(())
+}
+
+/// Content hashes of every file under `dir`.
+fn snapshot(dir: &Path) -> BTreeMap<PathBuf, u64> {
+    let mut files = BTreeMap::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if let Ok(data) = fs::read(&path) {
+                let mut hasher = DefaultHasher::new();
+                data.hash(&mut hasher);
+                files.insert(path, hasher.finish());
+            }
+        }
+    }
+    files
+}